@@ -0,0 +1,108 @@
+use crate::errors::DashboardError;
+use crate::methods::{admin, dashboard, initialization, sales, statement};
+use crate::storage::{
+    AssetDashboard, Cursor, LinkedContracts, SaleProposal, SellerCommitments, SellerSale,
+    TradeRecordV2, UserStatement,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+
+#[contract]
+pub struct DashboardContract;
+
+#[contractimpl]
+impl DashboardContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        contracts: LinkedContracts,
+    ) -> Result<(), DashboardError> {
+        initialization::construct(env, admin, contracts)
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        contracts: LinkedContracts,
+    ) -> Result<(), DashboardError> {
+        initialization::initialize(env, admin, contracts)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Holders, supply, last price and 30-day volume, distributions and polls of an asset
+    pub fn asset_dashboard(env: Env, asset_id: u64) -> Result<AssetDashboard, DashboardError> {
+        dashboard::asset_dashboard(env, asset_id)
+    }
+
+    /// One page of an address's holdings, trades, payouts received and votes cast
+    /// between two timestamps, with the cursor of the next page
+    pub fn user_statement(
+        env: Env,
+        address: Address,
+        from_ts: u64,
+        to_ts: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> Result<(UserStatement, Option<Cursor>), DashboardError> {
+        statement::user_statement(env, address, from_ts, to_ts, cursor, limit)
+    }
+
+    /// Trading proposals for up to 50 (seller, buyer, asset_id) keys, `None` where none exists
+    pub fn get_sale_proposals_bulk(
+        env: Env,
+        keys: Vec<(Address, Address, u64)>,
+    ) -> Result<Vec<Option<SaleProposal>>, DashboardError> {
+        sales::get_sale_proposals_bulk(env, keys)
+    }
+
+    /// Trade records for up to 50 trade ids, `None` for unknown ids
+    pub fn get_trade_records_bulk(
+        env: Env,
+        trade_ids: Vec<u32>,
+    ) -> Result<Vec<Option<TradeRecordV2>>, DashboardError> {
+        sales::get_trade_records_bulk(env, trade_ids)
+    }
+
+    /// A seller's trading proposals with their terms, optionally only unexpired
+    /// ones or those of one asset
+    pub fn get_seller_sales_detailed(
+        env: Env,
+        seller: Address,
+        only_active: bool,
+        asset_id: Option<u64>,
+    ) -> Result<Vec<SellerSale>, DashboardError> {
+        sales::get_seller_sales_detailed(env, seller, only_active, asset_id)
+    }
+
+    /// Trading proposals backed by the seller's allowance to trading, with the
+    /// total committed and any uncommitted allowance or shortfall
+    pub fn get_seller_commitments(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<SellerCommitments, DashboardError> {
+        sales::get_seller_commitments(env, seller, asset_id)
+    }
+
+    /// Admin replaces the contract addresses the dashboard reads from
+    pub fn set_linked_contracts(
+        env: Env,
+        admin: Address,
+        contracts: LinkedContracts,
+    ) -> Result<(), DashboardError> {
+        admin::set_linked_contracts(env, admin, contracts)
+    }
+
+    pub fn get_linked_contracts(env: Env) -> Result<LinkedContracts, DashboardError> {
+        admin::get_linked_contracts(env)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, DashboardError> {
+        admin::get_admin(env)
+    }
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by dashboard entrypoints
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DashboardError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    AssetNotFound = 4,
+    InvalidRange = 5,
+    TooManyKeys = 6,
+}
@@ -0,0 +1,14 @@
+use crate::storage::LinkedContracts;
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// Contract initialization
+pub fn emit_init(env: &Env, admin: Address, contracts: LinkedContracts) {
+    env.events()
+        .publish((symbol_short!("init"),), (admin, contracts));
+}
+
+/// Contract addresses replaced by the admin
+pub fn emit_contracts_updated(env: &Env, contracts: LinkedContracts) {
+    env.events()
+        .publish((symbol_short!("contracts"),), contracts);
+}
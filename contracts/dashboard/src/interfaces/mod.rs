@@ -0,0 +1,56 @@
+use crate::storage::{
+    AssetMarketStats, Cursor, DistributionRecord, HolderPayout, SaleProposal, TradeRecordV2,
+    VoteReceipt,
+};
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+// Read-only views of the platform contracts used by the dashboard
+
+#[contractclient(name = "FractcoreClient")]
+pub trait FractcoreInterface {
+    fn asset_exists(env: Env, asset_id: u64) -> bool;
+    fn asset_supply(env: Env, asset_id: u64) -> u64;
+    fn get_asset_owner_count(env: Env, asset_id: u64) -> u32;
+    fn owner_assets(env: Env, owner: Address) -> Vec<u64>;
+    fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64;
+}
+
+#[contractclient(name = "TradingClient")]
+pub trait TradingInterface {
+    fn get_asset_market_stats(env: Env, asset_id: u64, since: u64) -> AssetMarketStats;
+    fn get_user_trades_page(
+        env: Env,
+        user: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<(u32, TradeRecordV2)>, Option<Cursor>);
+    fn get_trade_record(env: Env, trade_id: u32) -> TradeRecordV2;
+    fn get_sale_proposal(env: Env, seller: Address, buyer: Address, asset_id: u64) -> SaleProposal;
+    fn get_seller_sales(env: Env, seller: Address) -> Vec<(Address, u64)>;
+    fn get_current_allowance(env: Env, seller: Address, asset_id: u64) -> u64;
+}
+
+#[contractclient(name = "FundingClient")]
+pub trait FundingInterface {
+    fn total_distributed(env: Env, asset_id: u64) -> u128;
+    fn get_distribution_count(env: Env, asset_id: u64) -> u32;
+    fn get_distribution(env: Env, asset_id: u64, index: u32) -> Option<DistributionRecord>;
+    fn get_holder_payouts_page(
+        env: Env,
+        holder: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<HolderPayout>, Option<Cursor>);
+}
+
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn get_asset_polls(env: Env, asset_id: u64) -> Vec<u32>;
+    fn get_active_polls(env: Env) -> Vec<u32>;
+    fn get_voter_receipts_page(
+        env: Env,
+        voter: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<VoteReceipt>, Option<Cursor>);
+}
@@ -0,0 +1,31 @@
+use crate::errors::DashboardError;
+use crate::events;
+use crate::storage::{self, DataKey, LinkedContracts};
+use soroban_sdk::{Address, Env};
+
+pub fn get_admin(env: Env) -> Result<Address, DashboardError> {
+    storage::get(&env, &DataKey::Admin).ok_or(DashboardError::NotInitialized)
+}
+
+/// Point the dashboard at redeployed contracts (admin only)
+pub fn set_linked_contracts(
+    env: Env,
+    admin: Address,
+    contracts: LinkedContracts,
+) -> Result<(), DashboardError> {
+    admin.require_auth();
+
+    if admin != get_admin(env.clone())? {
+        return Err(DashboardError::Unauthorized);
+    }
+
+    storage::set(&env, &DataKey::Contracts, &contracts);
+
+    events::emit_contracts_updated(&env, contracts);
+
+    Ok(())
+}
+
+pub fn get_linked_contracts(env: Env) -> Result<LinkedContracts, DashboardError> {
+    storage::get(&env, &DataKey::Contracts).ok_or(DashboardError::NotInitialized)
+}
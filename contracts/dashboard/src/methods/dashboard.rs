@@ -0,0 +1,48 @@
+use crate::errors::DashboardError;
+use crate::interfaces::{FractcoreClient, FundingClient, GovernanceClient, TradingClient};
+use crate::methods::admin;
+use crate::storage::{AssetDashboard, VOLUME_WINDOW};
+use soroban_sdk::Env;
+
+/// Build an asset page from fractcore, trading, funding and governance in one call
+pub fn asset_dashboard(env: Env, asset_id: u64) -> Result<AssetDashboard, DashboardError> {
+    let contracts = admin::get_linked_contracts(env.clone())?;
+
+    let fractcore = FractcoreClient::new(&env, &contracts.fractcore);
+    if !fractcore.asset_exists(&asset_id) {
+        return Err(DashboardError::AssetNotFound);
+    }
+
+    let since = env.ledger().timestamp().saturating_sub(VOLUME_WINDOW);
+    let market =
+        TradingClient::new(&env, &contracts.trading).get_asset_market_stats(&asset_id, &since);
+
+    let funding = FundingClient::new(&env, &contracts.funding);
+    let last_distribution = match funding.get_distribution_count(&asset_id) {
+        0 => None,
+        count => funding.get_distribution(&asset_id, &(count - 1)),
+    };
+
+    let governance = GovernanceClient::new(&env, &contracts.governance);
+    let asset_polls = governance.get_asset_polls(&asset_id);
+    let active = governance.get_active_polls();
+    let active_polls = asset_polls
+        .iter()
+        .filter(|poll_id| active.contains(poll_id))
+        .count() as u32;
+
+    Ok(AssetDashboard {
+        asset_id,
+        holders: fractcore.get_asset_owner_count(&asset_id),
+        supply: fractcore.asset_supply(&asset_id),
+        last_price: market.last_unit_price,
+        last_trade_at: market.last_trade_at,
+        volume_30d: market.volume,
+        trades_30d: market.trade_count,
+        total_distributed: funding.total_distributed(&asset_id),
+        last_distribution: last_distribution.as_ref().map(|record| record.distributed),
+        last_distribution_at: last_distribution.map(|record| record.timestamp),
+        active_polls,
+        total_polls: asset_polls.len(),
+    })
+}
@@ -0,0 +1,51 @@
+use crate::errors::DashboardError;
+use crate::events;
+use crate::storage::{self, DataKey, LinkedContracts};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
+
+/// Initialize the dashboard with the contracts it reads from
+pub fn initialize(
+    env: Env,
+    admin: Address,
+    contracts: LinkedContracts,
+) -> Result<(), DashboardError> {
+    admin.require_auth();
+    configure(env, admin, contracts)
+}
+
+/// Deploy-time initialization. The constructor runs atomically with contract
+/// creation, so no one can initialize first and the admin's auth is not needed.
+pub fn construct(
+    env: Env,
+    admin: Address,
+    contracts: LinkedContracts,
+) -> Result<(), DashboardError> {
+    configure(env, admin, contracts)
+}
+
+fn configure(env: Env, admin: Address, contracts: LinkedContracts) -> Result<(), DashboardError> {
+    if storage::has_or_bare(&env, &DataKey::Admin) {
+        return Err(DashboardError::AlreadyInitialized);
+    }
+
+    storage::mark_namespaced(&env);
+
+    storage::set(&env, &DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::Contracts, &contracts);
+
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(admin.clone(), contracts.clone()).to_xdr(&env))
+        .into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
+
+    events::emit_init(&env, admin, contracts);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
+}
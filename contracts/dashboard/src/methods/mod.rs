@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod dashboard;
+pub mod initialization;
+pub mod sales;
+pub mod statement;
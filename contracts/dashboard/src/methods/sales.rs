@@ -0,0 +1,143 @@
+use crate::errors::DashboardError;
+use crate::interfaces::TradingClient;
+use crate::methods::admin;
+use crate::storage::{
+    Commitment, SaleProposal, SellerCommitments, SellerSale, TradeRecordV2, MAX_BULK_READ,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Trading proposals for up to MAX_BULK_READ (seller, buyer, asset_id) keys,
+/// `None` where no proposal exists, in key order
+pub fn get_sale_proposals_bulk(
+    env: Env,
+    keys: Vec<(Address, Address, u64)>,
+) -> Result<Vec<Option<SaleProposal>>, DashboardError> {
+    if keys.len() > MAX_BULK_READ {
+        return Err(DashboardError::TooManyKeys);
+    }
+
+    let trading = trading(&env)?;
+    let mut proposals = Vec::new(&env);
+    for (seller, buyer, asset_id) in keys.iter() {
+        proposals.push_back(
+            trading
+                .try_get_sale_proposal(&seller, &buyer, &asset_id)
+                .ok()
+                .and_then(|proposal| proposal.ok()),
+        );
+    }
+    Ok(proposals)
+}
+
+/// Trade records for up to MAX_BULK_READ ids, `None` for unknown ids, in id order
+pub fn get_trade_records_bulk(
+    env: Env,
+    trade_ids: Vec<u32>,
+) -> Result<Vec<Option<TradeRecordV2>>, DashboardError> {
+    if trade_ids.len() > MAX_BULK_READ {
+        return Err(DashboardError::TooManyKeys);
+    }
+
+    let trading = trading(&env)?;
+    let mut records = Vec::new(&env);
+    for trade_id in trade_ids.iter() {
+        records.push_back(
+            trading
+                .try_get_trade_record(&trade_id)
+                .ok()
+                .and_then(|record| record.ok()),
+        );
+    }
+    Ok(records)
+}
+
+/// The seller's trading proposals with their terms, in the order they were made.
+///
+/// `only_active` leaves out expired proposals that were never cleaned up, and
+/// `asset_id` keeps one asset's proposals.
+pub fn get_seller_sales_detailed(
+    env: Env,
+    seller: Address,
+    only_active: bool,
+    asset_id: Option<u64>,
+) -> Result<Vec<SellerSale>, DashboardError> {
+    let now = env.ledger().timestamp();
+    let mut sales = Vec::new(&env);
+
+    for proposal in seller_proposals(&env, &seller, asset_id)?.iter() {
+        let expired = now > proposal.expires_at;
+        if only_active && expired {
+            continue;
+        }
+        sales.push_back(SellerSale {
+            buyer: proposal.buyer,
+            asset_id: proposal.asset_id,
+            token_amount: proposal.token_amount,
+            price: proposal.price,
+            expires_at: proposal.expires_at,
+            expired,
+        });
+    }
+    Ok(sales)
+}
+
+/// What a seller's allowance to trading for an asset is reserved for, and how
+/// it compares with the allowance fractcore actually holds.
+///
+/// Proposals stay committed after they expire until they are cleaned up or
+/// withdrawn, so `expires_at` lets wallets spot stale ones. A non-zero
+/// `uncommitted` is orphaned allowance the seller may want to reset. Extension
+/// contracts hold their own allowances and report them themselves.
+pub fn get_seller_commitments(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+) -> Result<SellerCommitments, DashboardError> {
+    let allowance = trading(&env)?.get_current_allowance(&seller, &asset_id);
+    let mut commitments = Vec::new(&env);
+
+    for proposal in seller_proposals(&env, &seller, Some(asset_id))?.iter() {
+        commitments.push_back(Commitment {
+            counterparty: proposal.buyer,
+            token_amount: proposal.token_amount,
+            expires_at: proposal.expires_at,
+        });
+    }
+
+    let total_committed = commitments
+        .iter()
+        .map(|commitment| commitment.token_amount)
+        .sum::<u64>();
+
+    Ok(SellerCommitments {
+        commitments,
+        total_committed,
+        allowance,
+        uncommitted: allowance.saturating_sub(total_committed),
+        shortfall: total_committed.saturating_sub(allowance),
+    })
+}
+
+fn trading(env: &Env) -> Result<TradingClient<'_>, DashboardError> {
+    let contracts = admin::get_linked_contracts(env.clone())?;
+    Ok(TradingClient::new(env, &contracts.trading))
+}
+
+/// The seller's open proposals, optionally of one asset, in the order they were made
+fn seller_proposals(
+    env: &Env,
+    seller: &Address,
+    asset_id: Option<u64>,
+) -> Result<Vec<SaleProposal>, DashboardError> {
+    let trading = trading(env)?;
+    let mut proposals = Vec::new(env);
+    for (buyer, sale_asset) in trading.get_seller_sales(seller).iter() {
+        if asset_id.is_some_and(|wanted| wanted != sale_asset) {
+            continue;
+        }
+        if let Ok(Ok(proposal)) = trading.try_get_sale_proposal(seller, &buyer, &sale_asset) {
+            proposals.push_back(proposal);
+        }
+    }
+    Ok(proposals)
+}
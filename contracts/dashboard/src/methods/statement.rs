@@ -0,0 +1,134 @@
+use crate::errors::DashboardError;
+use crate::interfaces::{FractcoreClient, FundingClient, GovernanceClient, TradingClient};
+use crate::methods::admin;
+use crate::storage::{
+    Cursor, Holding, StatementTrade, TradeSide, UserStatement, MAX_STATEMENT_PAGE,
+    SECTION_HOLDINGS, SECTION_PAYOUTS, SECTION_TRADES, SECTION_VOTES,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// One page of an address's activity: current holdings, then trades, payouts
+/// received and votes cast between `from_ts` and `to_ts` (inclusive).
+///
+/// Each page reads up to `limit` entries from one source contract and keeps
+/// those in range, so a page can come back empty while the cursor moves on.
+/// Holdings are today's balances and are not filtered by time.
+pub fn user_statement(
+    env: Env,
+    address: Address,
+    from_ts: u64,
+    to_ts: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<(UserStatement, Option<Cursor>), DashboardError> {
+    if from_ts > to_ts {
+        return Err(DashboardError::InvalidRange);
+    }
+    let contracts = admin::get_linked_contracts(env.clone())?;
+    let limit = if limit == 0 || limit > MAX_STATEMENT_PAGE {
+        MAX_STATEMENT_PAGE
+    } else {
+        limit
+    };
+
+    // A zero snapshot marks a section that has not been started yet
+    let (section, source) = match cursor {
+        None => (SECTION_HOLDINGS, None),
+        Some(cursor) => (
+            cursor.page,
+            (cursor.snapshot > 0).then_some(Cursor {
+                page: 0,
+                position: cursor.position,
+                snapshot: cursor.snapshot,
+            }),
+        ),
+    };
+    let in_range = |timestamp: u64| timestamp >= from_ts && timestamp <= to_ts;
+
+    let mut statement = UserStatement {
+        holdings: Vec::new(&env),
+        trades: Vec::new(&env),
+        payouts: Vec::new(&env),
+        votes: Vec::new(&env),
+    };
+
+    let next_source = match section {
+        SECTION_HOLDINGS => {
+            let fractcore = FractcoreClient::new(&env, &contracts.fractcore);
+            let assets = fractcore.owner_assets(&address);
+            let start = source.map_or(0, |cursor| cursor.position).min(assets.len());
+            let end = assets.len().min(start + limit);
+            for asset_id in assets.slice(start..end).iter() {
+                statement.holdings.push_back(Holding {
+                    asset_id,
+                    balance: fractcore.balance_of(&address, &asset_id),
+                });
+            }
+            (end < assets.len()).then_some(Cursor {
+                page: 0,
+                position: end,
+                snapshot: assets.len() as u64,
+            })
+        }
+        SECTION_TRADES => {
+            let (trades, next) = TradingClient::new(&env, &contracts.trading)
+                .get_user_trades_page(&address, &source, &limit);
+            for (trade_id, trade) in trades.iter() {
+                if !in_range(trade.timestamp) {
+                    continue;
+                }
+                let (side, counterparty) = if trade.seller == address {
+                    (TradeSide::Seller, trade.buyer)
+                } else {
+                    (TradeSide::Buyer, trade.seller)
+                };
+                statement.trades.push_back(StatementTrade {
+                    trade_id,
+                    asset_id: trade.asset_id,
+                    side,
+                    counterparty,
+                    token_amount: trade.token_amount,
+                    price: trade.price,
+                    fees: trade.settlement.fees + trade.settlement.royalties,
+                    timestamp: trade.timestamp,
+                });
+            }
+            next
+        }
+        SECTION_PAYOUTS => {
+            let (payouts, next) = FundingClient::new(&env, &contracts.funding)
+                .get_holder_payouts_page(&address, &source, &limit);
+            for payout in payouts.iter() {
+                if in_range(payout.timestamp) {
+                    statement.payouts.push_back(payout);
+                }
+            }
+            next
+        }
+        SECTION_VOTES => {
+            let (receipts, next) = GovernanceClient::new(&env, &contracts.governance)
+                .get_voter_receipts_page(&address, &source, &limit);
+            for receipt in receipts.iter() {
+                if in_range(receipt.timestamp) {
+                    statement.votes.push_back(receipt);
+                }
+            }
+            next
+        }
+        _ => return Ok((statement, None)),
+    };
+
+    let next = match next_source {
+        Some(source) => Some(Cursor {
+            page: section,
+            position: source.position,
+            snapshot: source.snapshot,
+        }),
+        None => (section < SECTION_VOTES).then_some(Cursor {
+            page: section + 1,
+            position: 0,
+            snapshot: 0,
+        }),
+    };
+    Ok((statement, next))
+}
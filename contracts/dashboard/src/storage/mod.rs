@@ -0,0 +1,247 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// The dashboard keeps nothing in temporary storage.
+pub use paging::Cursor;
+pub use placement::*;
+
+/// Storage keys for dashboard contract data
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,
+    Contracts,      // LinkedContracts read by asset_dashboard
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("dash");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin | DataKey::Contracts | DataKey::InitConfigHash => Placement::Instance,
+        }
+    }
+}
+
+/// Platform contracts the dashboard reads from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkedContracts {
+    pub fractcore: Address,
+    pub funding: Address,
+    pub trading: Address,
+    pub governance: Address,
+}
+
+/// Everything an asset page shows, read from all four contracts in one call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDashboard {
+    pub asset_id: u64,
+    // fractcore
+    pub holders: u32,
+    pub supply: u64,
+    // trading
+    pub last_price: Option<u128>, // Per fraction, from the latest trade
+    pub last_trade_at: Option<u64>,
+    pub volume_30d: u128,
+    pub trades_30d: u32,
+    // funding
+    pub total_distributed: u128,
+    pub last_distribution: Option<u128>, // Amount paid to holders
+    pub last_distribution_at: Option<u64>,
+    // governance
+    pub active_polls: u32,
+    pub total_polls: u32,
+}
+
+/// Trading's `AssetMarketStats`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetMarketStats {
+    pub last_unit_price: Option<u128>,
+    pub last_trade_at: Option<u64>,
+    pub volume: u128,
+    pub trade_count: u32,
+}
+
+/// Funding's `DistributionMode`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DistributionMode {
+    Snapshot,
+    TimeWeighted(u64),
+    EqualPerHolder,
+    Tiered(Vec<WeightTier>),
+}
+
+/// Funding's `WeightTier`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightTier {
+    pub min_balance: u64,
+    pub weight: u32,
+}
+
+/// Funding's `DistributionRecord`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionRecord {
+    pub requested: u128,
+    pub fee: u128,
+    pub distributed: u128,
+    pub recipients: u32,
+    pub timestamp: u64,
+    pub mode: DistributionMode,
+    pub excluded: Vec<Address>,
+}
+
+/// Trading's `SettlementBreakdown`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementBreakdown {
+    pub gross: u128,
+    pub fees: u128,
+    pub royalties: u128,
+    pub net_to_seller: u128,
+    pub payment_token: Address,
+}
+
+/// Trading's `TradeRecordV2`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeRecordV2 {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub timestamp: u64,
+    pub referrer: Option<Address>,
+    pub settlement: SettlementBreakdown,
+}
+
+/// Funding's `HolderPayout`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderPayout {
+    pub asset_id: u64,
+    pub distribution_id: u32,
+    pub amount: u128,
+    pub timestamp: u64,
+}
+
+/// Governance's `VoteReceipt`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteReceipt {
+    pub poll_id: u32,
+    pub voter: Address,
+    pub option_index: u32,
+    pub voting_power: u64,
+    pub timestamp: u64,
+}
+
+/// Trading's `SaleProposal`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleProposal {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub is_active: bool,
+    pub timestamp: u64,
+    pub expires_at: u64,
+    pub arbiter: Option<Address>,
+}
+
+/// A seller's trading proposal with the terms a dashboard row shows
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SellerSale {
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub expires_at: u64,
+    pub expired: bool, // Past expires_at; the proposal can no longer be executed
+}
+
+/// One trading proposal reserved against a seller's allowance to trading
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Commitment {
+    pub counterparty: Address, // The proposal's buyer
+    pub token_amount: u64,
+    pub expires_at: u64,
+}
+
+/// Seller's allowance to trading set against the proposals it backs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SellerCommitments {
+    pub commitments: Vec<Commitment>,
+    pub total_committed: u64,
+    pub allowance: u64,
+    pub uncommitted: u64, // Allowance no proposal accounts for (orphaned)
+    pub shortfall: u64,   // Commitments the allowance no longer covers
+}
+
+/// Fractions of one asset an address currently holds
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Holding {
+    pub asset_id: u64,
+    pub balance: u64,
+}
+
+/// Which side of a trade the statement's address was on
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TradeSide {
+    Buyer,
+    Seller,
+}
+
+/// A trade from the point of view of one of its parties
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatementTrade {
+    pub trade_id: u32,
+    pub asset_id: u64,
+    pub side: TradeSide,
+    pub counterparty: Address,
+    pub token_amount: u64,
+    pub price: u128, // What the buyer paid
+    pub fees: u128,  // Trading fee and royalties withheld from the seller
+    pub timestamp: u64,
+}
+
+/// One page of an address's activity statement. Each page is filled from a
+/// single section, so the other three lists are empty.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserStatement {
+    pub holdings: Vec<Holding>,      // fractcore, current balances
+    pub trades: Vec<StatementTrade>, // trading
+    pub payouts: Vec<HolderPayout>,  // funding distributions received
+    pub votes: Vec<VoteReceipt>,     // governance
+}
+
+/// Sections of a user statement, walked in this order; the statement cursor's
+/// `page` holds the section and its `position` and `snapshot` the source cursor
+pub const SECTION_HOLDINGS: u32 = 0;
+pub const SECTION_TRADES: u32 = 1;
+pub const SECTION_PAYOUTS: u32 = 2;
+pub const SECTION_VOTES: u32 = 3;
+
+/// Maximum entries read from a source contract per statement page
+pub const MAX_STATEMENT_PAGE: u32 = 20;
+
+/// Window the dashboard's trading volume covers (30 days)
+pub const VOLUME_WINDOW: u64 = 30 * 24 * 60 * 60;
+
+/// Keys accepted by one bulk read
+pub const MAX_BULK_READ: u32 = 50;
@@ -0,0 +1,627 @@
+#![cfg(test)]
+
+use crate::contract::*;
+use crate::errors::DashboardError;
+use crate::storage::{
+    AssetMarketStats, DataKey, Holding, LinkedContracts, SaleProposal, SettlementBreakdown,
+    StatementTrade, TradeRecordV2, TradeSide, UserStatement, VoteReceipt, MAX_BULK_READ,
+    VOLUME_WINDOW,
+};
+use placement::{testutils::strip_namespaces, PlacedKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+mod fractcore {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+mod funding {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/funding.wasm");
+}
+
+// Asset SAC whose own balance starts funded, so funding can distribute from it
+mod mock_sac {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockSac;
+
+    #[contractimpl]
+    impl MockSac {
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(1_000_000)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            let from_balance = Self::balance(env.clone(), from.clone());
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&from, &(from_balance - amount));
+            env.storage().persistent().set(&to, &(to_balance + amount));
+        }
+    }
+}
+
+// Trading stand-in returning preset market stats, trades, proposals and
+// allowance, and recording the window start
+mod mock_trading {
+    use crate::storage::{AssetMarketStats, Cursor, SaleProposal, TradeRecordV2};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Vec};
+
+    #[contract]
+    pub struct MockTrading;
+
+    #[contractimpl]
+    impl MockTrading {
+        pub fn set_stats(env: Env, stats: AssetMarketStats) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("stats"), &stats);
+        }
+
+        pub fn set_user_trades(env: Env, trades: Vec<(u32, TradeRecordV2)>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("trades"), &trades);
+        }
+
+        pub fn get_user_trades_page(
+            env: Env,
+            _user: Address,
+            _cursor: Option<Cursor>,
+            _limit: u32,
+        ) -> (Vec<(u32, TradeRecordV2)>, Option<Cursor>) {
+            let trades = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("trades"))
+                .unwrap_or(Vec::new(&env));
+            (trades, None)
+        }
+
+        pub fn get_trade_record(env: Env, trade_id: u32) -> TradeRecordV2 {
+            let trades: Vec<(u32, TradeRecordV2)> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("trades"))
+                .unwrap_or(Vec::new(&env));
+            match trades.iter().find(|(id, _)| *id == trade_id) {
+                Some((_, record)) => record,
+                None => panic!("trade not found"),
+            }
+        }
+
+        pub fn set_proposals(env: Env, proposals: Vec<SaleProposal>, allowance: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("sales"), &proposals);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("allow"), &allowance);
+        }
+
+        pub fn get_sale_proposal(
+            env: Env,
+            seller: Address,
+            buyer: Address,
+            asset_id: u64,
+        ) -> SaleProposal {
+            let found = Self::proposals(&env).iter().find(|proposal| {
+                proposal.seller == seller
+                    && proposal.buyer == buyer
+                    && proposal.asset_id == asset_id
+            });
+            match found {
+                Some(proposal) => proposal,
+                None => panic!("proposal not found"),
+            }
+        }
+
+        pub fn get_seller_sales(env: Env, seller: Address) -> Vec<(Address, u64)> {
+            let mut sales = Vec::new(&env);
+            for proposal in Self::proposals(&env).iter() {
+                if proposal.seller == seller {
+                    sales.push_back((proposal.buyer, proposal.asset_id));
+                }
+            }
+            sales
+        }
+
+        pub fn get_current_allowance(env: Env, _seller: Address, _asset_id: u64) -> u64 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("allow"))
+                .unwrap_or(0)
+        }
+
+        fn proposals(env: &Env) -> Vec<SaleProposal> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("sales"))
+                .unwrap_or(Vec::new(env))
+        }
+
+        pub fn last_since(env: Env) -> u64 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("since"))
+                .unwrap_or(0)
+        }
+
+        pub fn get_asset_market_stats(env: Env, _asset_id: u64, since: u64) -> AssetMarketStats {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("since"), &since);
+            env.storage()
+                .instance()
+                .get(&symbol_short!("stats"))
+                .unwrap_or(AssetMarketStats {
+                    last_unit_price: None,
+                    last_trade_at: None,
+                    volume: 0,
+                    trade_count: 0,
+                })
+        }
+    }
+}
+
+// Governance stand-in with settable poll lists and vote receipts
+mod mock_governance {
+    use crate::storage::{Cursor, VoteReceipt};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Vec};
+
+    #[contract]
+    pub struct MockGovernance;
+
+    #[contractimpl]
+    impl MockGovernance {
+        pub fn set_polls(env: Env, asset_polls: Vec<u32>, active: Vec<u32>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("asset"), &asset_polls);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("active"), &active);
+        }
+
+        pub fn get_asset_polls(env: Env, _asset_id: u64) -> Vec<u32> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("asset"))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        pub fn get_active_polls(env: Env) -> Vec<u32> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("active"))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        pub fn set_receipts(env: Env, receipts: Vec<VoteReceipt>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("receipts"), &receipts);
+        }
+
+        pub fn get_voter_receipts_page(
+            env: Env,
+            _voter: Address,
+            _cursor: Option<Cursor>,
+            _limit: u32,
+        ) -> (Vec<VoteReceipt>, Option<Cursor>) {
+            let receipts = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("receipts"))
+                .unwrap_or(Vec::new(&env));
+            (receipts, None)
+        }
+    }
+}
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    dashboard: DashboardContractClient<'static>,
+    fractcore: fractcore::Client<'static>,
+    funding: funding::Client<'static>,
+    trading: mock_trading::MockTradingClient<'static>,
+    governance: mock_governance::MockGovernanceClient<'static>,
+    sac: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let admin = Address::generate(&env);
+
+    let fractcore_id = env.register(fractcore::WASM, (admin.clone(),));
+    let fractcore = fractcore::Client::new(&env, &fractcore_id);
+
+    let trading_id = env.register(mock_trading::MockTrading, ());
+    let trading = mock_trading::MockTradingClient::new(&env, &trading_id);
+
+    let funding_id = env.register(funding::WASM, (admin.clone(), fractcore_id.clone()));
+    let funding = funding::Client::new(&env, &funding_id);
+    let sac = env.register(mock_sac::MockSac, ());
+
+    let governance_id = env.register(mock_governance::MockGovernance, ());
+    let governance = mock_governance::MockGovernanceClient::new(&env, &governance_id);
+
+    let dashboard_id = env.register(
+        DashboardContract,
+        (
+            admin.clone(),
+            LinkedContracts {
+                fractcore: fractcore_id,
+                funding: funding_id,
+                trading: trading_id,
+                governance: governance_id,
+            },
+        ),
+    );
+    let dashboard = DashboardContractClient::new(&env, &dashboard_id);
+
+    Setup {
+        env,
+        admin,
+        dashboard,
+        fractcore,
+        funding,
+        trading,
+        governance,
+        sac,
+    }
+}
+
+#[test]
+fn test_asset_dashboard_aggregates_all_contracts() {
+    let s = setup();
+    let owner = Address::generate(&s.env);
+    let holder = Address::generate(&s.env);
+
+    let asset_id = s.fractcore.mint(&owner, &1000);
+    s.fractcore.transfer(&owner, &holder, &asset_id, &100);
+    s.trading.set_stats(&AssetMarketStats {
+        last_unit_price: Some(50),
+        last_trade_at: Some(0),
+        volume: 5000,
+        trade_count: 1,
+    });
+
+    s.funding.register_asset_sac(&owner, &asset_id, &s.sac);
+    s.funding.distribute_funds(
+        &s.admin,
+        &asset_id,
+        &1000u128,
+        &String::from_str(&s.env, "Rent"),
+    );
+
+    s.governance
+        .set_polls(&vec![&s.env, 1u32, 2u32], &vec![&s.env, 2u32, 7u32]);
+
+    let view = s.dashboard.asset_dashboard(&asset_id);
+    assert_eq!(view.holders, 2);
+    assert_eq!(view.supply, 1000);
+    assert_eq!(view.last_price, Some(50));
+    assert_eq!(view.volume_30d, 5000);
+    assert_eq!(view.trades_30d, 1);
+    assert_eq!(view.total_distributed, 1000);
+    assert_eq!(view.last_distribution, Some(1000));
+    assert_eq!(view.active_polls, 1);
+    assert_eq!(view.total_polls, 2);
+
+    // Trading is asked for the trailing 30 days only
+    s.env.ledger().set_timestamp(VOLUME_WINDOW + 500);
+    s.dashboard.asset_dashboard(&asset_id);
+    assert_eq!(s.trading.last_since(), 500);
+
+    assert_eq!(
+        s.dashboard.try_asset_dashboard(&999),
+        Err(Ok(DashboardError::AssetNotFound))
+    );
+}
+
+fn proposal(
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    expires_at: u64,
+) -> SaleProposal {
+    SaleProposal {
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        asset_id,
+        token_amount,
+        price,
+        is_active: true,
+        timestamp: 0,
+        expires_at,
+        arbiter: None,
+    }
+}
+
+fn trade(
+    s: &Setup,
+    trade_id: u32,
+    seller: &Address,
+    buyer: &Address,
+    at: u64,
+) -> (u32, TradeRecordV2) {
+    (
+        trade_id,
+        TradeRecordV2 {
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            asset_id: 0,
+            token_amount: 10,
+            price: 500,
+            timestamp: at,
+            referrer: None,
+            settlement: SettlementBreakdown {
+                gross: 500,
+                fees: 5,
+                royalties: 10,
+                net_to_seller: 485,
+                payment_token: s.sac.clone(),
+            },
+        },
+    )
+}
+
+#[test]
+fn test_user_statement_walks_every_section_within_range() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    let other = Address::generate(&s.env);
+
+    let rented = s.fractcore.mint(&user, &1000);
+    let held = s.fractcore.mint(&user, &500);
+    s.fractcore.transfer(&user, &other, &rented, &250);
+
+    s.funding.register_asset_sac(&user, &rented, &s.sac);
+    s.env.ledger().set_timestamp(100);
+    s.funding.distribute_funds(
+        &s.admin,
+        &rented,
+        &1000u128,
+        &String::from_str(&s.env, "Rent"),
+    );
+    s.env.ledger().set_timestamp(5000);
+    s.funding.distribute_funds(
+        &s.admin,
+        &rented,
+        &1000u128,
+        &String::from_str(&s.env, "Rent"),
+    );
+
+    s.trading.set_user_trades(&vec![
+        &s.env,
+        trade(&s, 3, &user, &other, 200),
+        trade(&s, 8, &other, &user, 9000),
+    ]);
+    let receipt = VoteReceipt {
+        poll_id: 1,
+        voter: user.clone(),
+        option_index: 0,
+        voting_power: 750,
+        timestamp: 300,
+    };
+    s.governance.set_receipts(&vec![&s.env, receipt.clone()]);
+
+    // One entry per page, walked to the end
+    let mut all = UserStatement {
+        holdings: vec![&s.env],
+        trades: vec![&s.env],
+        payouts: vec![&s.env],
+        votes: vec![&s.env],
+    };
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let (page, next) = s.dashboard.user_statement(&user, &0, &1000, &cursor, &1);
+        assert!(
+            page.holdings.len() + page.trades.len() + page.payouts.len() + page.votes.len() <= 1
+        );
+        all.holdings.append(&page.holdings);
+        all.trades.append(&page.trades);
+        all.payouts.append(&page.payouts);
+        all.votes.append(&page.votes);
+        pages += 1;
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert!(pages >= 4);
+
+    assert_eq!(
+        all.holdings,
+        vec![
+            &s.env,
+            Holding {
+                asset_id: rented,
+                balance: 750
+            },
+            Holding {
+                asset_id: held,
+                balance: 500
+            },
+        ]
+    );
+    // The trade at 9000 and the payout at 5000 fall outside the range
+    assert_eq!(
+        all.trades,
+        vec![
+            &s.env,
+            StatementTrade {
+                trade_id: 3,
+                asset_id: 0,
+                side: TradeSide::Seller,
+                counterparty: other,
+                token_amount: 10,
+                price: 500,
+                fees: 15,
+                timestamp: 200,
+            },
+        ]
+    );
+    assert_eq!(all.payouts.len(), 1);
+    let payout = all.payouts.get(0).unwrap();
+    assert_eq!((payout.asset_id, payout.distribution_id), (rented, 0));
+    assert_eq!((payout.amount, payout.timestamp), (750, 100));
+    assert_eq!(all.votes, vec![&s.env, receipt]);
+
+    assert_eq!(
+        s.dashboard.try_user_statement(&user, &1000, &0, &None, &0),
+        Err(Ok(DashboardError::InvalidRange))
+    );
+}
+
+#[test]
+fn test_seller_sales_detailed_filters_by_status_and_asset() {
+    let s = setup();
+    let seller = Address::generate(&s.env);
+    let buyer = Address::generate(&s.env);
+    let other_buyer = Address::generate(&s.env);
+    s.trading.set_proposals(
+        &vec![
+            &s.env,
+            proposal(&seller, &buyer, 1, 100, 5000, 100),
+            proposal(&seller, &other_buyer, 1, 200, 9000, 500),
+            proposal(&seller, &buyer, 2, 50, 1000, 500),
+            proposal(&other_buyer, &buyer, 1, 10, 100, 500),
+        ],
+        &0,
+    );
+
+    // The first proposal lapses; it stays listed until cleaned up
+    s.env.ledger().set_timestamp(101);
+    let all = s
+        .dashboard
+        .get_seller_sales_detailed(&seller, &false, &None);
+    assert_eq!(all.len(), 3);
+    let first = all.get(0).unwrap();
+    assert_eq!(first.buyer, buyer);
+    assert_eq!((first.token_amount, first.price), (100, 5000));
+    assert!(first.expired);
+
+    let active = s.dashboard.get_seller_sales_detailed(&seller, &true, &None);
+    assert_eq!(active.len(), 2);
+    assert!(active.iter().all(|sale| !sale.expired));
+
+    let active_asset = s
+        .dashboard
+        .get_seller_sales_detailed(&seller, &true, &Some(1));
+    assert_eq!(active_asset.len(), 1);
+    let sale = active_asset.get(0).unwrap();
+    assert_eq!(sale.buyer, other_buyer);
+    assert_eq!(
+        (sale.token_amount, sale.price, sale.expires_at),
+        (200, 9000, 500)
+    );
+}
+
+#[test]
+fn test_seller_commitments_account_for_allowance() {
+    let s = setup();
+    let seller = Address::generate(&s.env);
+    let buyer = Address::generate(&s.env);
+    let other_buyer = Address::generate(&s.env);
+    let proposals = vec![
+        &s.env,
+        proposal(&seller, &buyer, 1, 100, 5000, 100),
+        proposal(&seller, &other_buyer, 1, 50, 2500, 500),
+        proposal(&seller, &buyer, 2, 10, 500, 500),
+    ];
+    s.trading.set_proposals(&proposals, &150);
+
+    let view = s.dashboard.get_seller_commitments(&seller, &1);
+    assert_eq!(view.commitments.len(), 2);
+    let first = view.commitments.get(0).unwrap();
+    assert_eq!((first.counterparty, first.token_amount), (buyer, 100));
+    assert_eq!(view.total_committed, 150);
+    assert_eq!(
+        (view.allowance, view.uncommitted, view.shortfall),
+        (150, 0, 0)
+    );
+
+    // Allowance granted outside the trading flow shows up as orphaned
+    s.trading.set_proposals(&proposals, &400);
+    let view = s.dashboard.get_seller_commitments(&seller, &1);
+    assert_eq!(view.uncommitted, 250);
+
+    // A reset leaves the open proposals uncovered
+    s.trading.set_proposals(&proposals, &0);
+    let view = s.dashboard.get_seller_commitments(&seller, &1);
+    assert_eq!((view.allowance, view.shortfall), (0, 150));
+}
+
+#[test]
+fn test_bulk_reads() {
+    let s = setup();
+    let seller = Address::generate(&s.env);
+    let buyer = Address::generate(&s.env);
+    let stranger = Address::generate(&s.env);
+    s.trading.set_proposals(
+        &vec![&s.env, proposal(&seller, &buyer, 1, 100, 5000, 500)],
+        &100,
+    );
+    s.trading
+        .set_user_trades(&vec![&s.env, trade(&s, 3, &seller, &buyer, 200)]);
+
+    let proposals = s.dashboard.get_sale_proposals_bulk(&vec![
+        &s.env,
+        (seller.clone(), buyer.clone(), 1),
+        (seller.clone(), stranger, 1),
+    ]);
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals.get(0).unwrap().unwrap().price, 5000);
+    assert!(proposals.get(1).unwrap().is_none());
+
+    let records = s
+        .dashboard
+        .get_trade_records_bulk(&vec![&s.env, 4u32, 3u32]);
+    assert!(records.get(0).unwrap().is_none());
+    assert_eq!(records.get(1).unwrap().unwrap().price, 500);
+
+    let mut too_many = vec![&s.env];
+    for id in 0..=MAX_BULK_READ {
+        too_many.push_back(id);
+    }
+    assert_eq!(
+        s.dashboard.try_get_trade_records_bulk(&too_many),
+        Err(Ok(DashboardError::TooManyKeys))
+    );
+}
+
+#[test]
+fn test_linked_contracts_admin_only() {
+    let s = setup();
+    let contracts = s.dashboard.get_linked_contracts();
+    let outsider = Address::generate(&s.env);
+
+    assert_eq!(
+        s.dashboard.try_set_linked_contracts(&outsider, &contracts),
+        Err(Ok(DashboardError::Unauthorized))
+    );
+    assert_eq!(
+        s.dashboard.try_initialize(&outsider, &contracts),
+        Err(Ok(DashboardError::AlreadyInitialized))
+    );
+    s.dashboard.set_linked_contracts(&s.admin, &contracts);
+
+    // Nor once upgraded from before namespacing, with the admin under its bare key
+    strip_namespaces(&s.env, &s.dashboard.address, &[DataKey::NAMESPACE]);
+    assert_eq!(
+        s.dashboard.try_initialize(&outsider, &contracts),
+        Err(Ok(DashboardError::AlreadyInitialized))
+    );
+}
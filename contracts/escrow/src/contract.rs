@@ -0,0 +1,299 @@
+use crate::errors::EscrowError;
+use crate::methods::{custody, disputes, escrow, forwards, initialization, queries};
+use crate::storage::{ArbitratedSale, Escrow, ForwardSale, ForwardTerms, Settlement};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        trading_contract: Address,
+    ) -> Result<(), EscrowError> {
+        initialization::construct(env, admin, trading_contract)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Seller opens a milestone escrow, locking the fractions in the contract
+    pub fn create_escrow(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        arbiter: Option<Address>,
+        asset_id: u64,
+        token_amount: u64,
+        payments: Vec<(u128, u64)>,
+    ) -> Result<u32, EscrowError> {
+        escrow::create_escrow(
+            env,
+            seller,
+            buyer,
+            arbiter,
+            asset_id,
+            token_amount,
+            payments,
+        )
+    }
+
+    /// Buyer funds the full escrow price
+    pub fn accept_escrow(env: Env, buyer: Address, escrow_id: u32) -> Result<(), EscrowError> {
+        escrow::accept_escrow(env, buyer, escrow_id)
+    }
+
+    /// Seller cancels an unfunded escrow
+    pub fn cancel_escrow(env: Env, seller: Address, escrow_id: u32) -> Result<(), EscrowError> {
+        escrow::cancel_escrow(env, seller, escrow_id)
+    }
+
+    /// Buyer, seller or arbiter confirms a milestone; returns true once released
+    pub fn confirm_milestone(
+        env: Env,
+        caller: Address,
+        escrow_id: u32,
+        index: u32,
+    ) -> Result<bool, EscrowError> {
+        escrow::confirm_milestone(env, caller, escrow_id, index)
+    }
+
+    /// Refund a missed milestone (or any pending milestone, by the arbiter)
+    pub fn refund_milestone(
+        env: Env,
+        caller: Address,
+        escrow_id: u32,
+        index: u32,
+    ) -> Result<(), EscrowError> {
+        escrow::refund_milestone(env, caller, escrow_id, index)
+    }
+
+    /// Freeze a pending milestone until the arbiter rules
+    pub fn dispute_milestone(
+        env: Env,
+        caller: Address,
+        escrow_id: u32,
+        index: u32,
+    ) -> Result<(), EscrowError> {
+        escrow::dispute_milestone(env, caller, escrow_id, index)
+    }
+
+    /// Seller lists a sale settled through an arbiter-backed dispute window
+    #[allow(clippy::too_many_arguments)]
+    pub fn confirm_sale_with_arbiter(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        arbiter: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        duration_seconds: u64,
+    ) -> Result<(), EscrowError> {
+        disputes::confirm_sale_with_arbiter(
+            env,
+            seller,
+            buyer,
+            arbiter,
+            asset_id,
+            token_amount,
+            price,
+            duration_seconds,
+        )
+    }
+
+    /// Seller withdraws an unpaid arbitrated sale
+    pub fn withdraw_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), EscrowError> {
+        disputes::withdraw_sale(env, seller, buyer, asset_id)
+    }
+
+    /// Buyer pays for an arbitrated sale; returns the settlement ID
+    pub fn finish_transaction(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+    ) -> Result<u32, EscrowError> {
+        disputes::finish_transaction(env, buyer, seller, asset_id, token_amount, price)
+    }
+
+    /// Buyer disputes a paid arbitrated sale within the dispute window
+    pub fn dispute_settlement(
+        env: Env,
+        buyer: Address,
+        settlement_id: u32,
+    ) -> Result<(), EscrowError> {
+        disputes::dispute_settlement(env, buyer, settlement_id)
+    }
+
+    /// Complete an undisputed settlement after the window; returns the trade ID
+    pub fn release_settlement(env: Env, settlement_id: u32) -> Result<u32, EscrowError> {
+        disputes::release_settlement(env, settlement_id)
+    }
+
+    /// Arbiter refunds or releases a disputed settlement
+    pub fn resolve_dispute(
+        env: Env,
+        arbiter: Address,
+        settlement_id: u32,
+        refund: bool,
+    ) -> Result<u32, EscrowError> {
+        disputes::resolve_dispute(env, arbiter, settlement_id, refund)
+    }
+
+    /// Seller fixes the price now for a sale that settles on or after
+    /// `terms.settle_after`; the fractions are held by the contract until then
+    #[allow(clippy::too_many_arguments)]
+    pub fn confirm_forward_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        duration_seconds: u64,
+        terms: ForwardTerms,
+    ) -> Result<(), EscrowError> {
+        forwards::confirm_forward_sale(
+            env,
+            seller,
+            buyer,
+            asset_id,
+            token_amount,
+            price,
+            duration_seconds,
+            terms,
+        )
+    }
+
+    /// Buyer accepts a forward sale and locks the deposit until settlement
+    pub fn commit_forward_sale(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<(), EscrowError> {
+        forwards::commit_forward_sale(env, buyer, seller, asset_id)
+    }
+
+    /// Seller withdraws a forward sale the buyer has not committed to
+    pub fn withdraw_forward_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), EscrowError> {
+        forwards::withdraw_forward_sale(env, seller, buyer, asset_id)
+    }
+
+    /// Seller or buyer cancels a committed forward sale: alone until the cancel
+    /// cutoff, together afterwards, or alone once it expired unsettled (the
+    /// deposit is then forfeited). Returns false while waiting for the other party.
+    pub fn cancel_forward_sale(
+        env: Env,
+        caller: Address,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<bool, EscrowError> {
+        forwards::cancel_forward_sale(env, caller, seller, buyer, asset_id)
+    }
+
+    /// Buyer settles a forward sale on or after its date; returns the trade ID
+    pub fn finish_forward_sale(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<u32, EscrowError> {
+        forwards::finish_forward_sale(env, buyer, seller, asset_id)
+    }
+
+    /// Close a forward sale that expired unsettled (keepers earn the bounty)
+    pub fn cleanup_forward_sale(
+        env: Env,
+        keeper: Address,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<i128, EscrowError> {
+        forwards::cleanup_forward_sale(env, keeper, seller, buyer, asset_id)
+    }
+
+    /// Fractions of an asset held for escrows, settlements and forward sales
+    pub fn get_held_tokens(env: Env, asset_id: u64) -> u64 {
+        custody::get_held_tokens(env, asset_id)
+    }
+
+    /// XLM held for escrows, settlements and forward sales
+    pub fn get_held_xlm(env: Env) -> i128 {
+        custody::get_held_xlm(env)
+    }
+
+    /// Fractions of an asset held beyond what escrows, settlements and forwards are owed
+    pub fn get_rescuable_tokens(env: Env, asset_id: u64) -> Result<u64, EscrowError> {
+        custody::get_rescuable_tokens(env, asset_id)
+    }
+
+    /// Return fractions sent to this contract by mistake (admin only)
+    pub fn rescue_fractions(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        to: Address,
+        amount: u64,
+    ) -> Result<(), EscrowError> {
+        custody::rescue_fractions(env, admin, asset_id, to, amount)
+    }
+
+    pub fn get_escrow(env: Env, escrow_id: u32) -> Result<Escrow, EscrowError> {
+        queries::get_escrow(env, escrow_id)
+    }
+
+    pub fn get_user_escrows(env: Env, user: Address) -> Vec<u32> {
+        queries::get_user_escrows(env, user)
+    }
+
+    pub fn get_settlement(env: Env, settlement_id: u32) -> Result<Settlement, EscrowError> {
+        queries::get_settlement(env, settlement_id)
+    }
+
+    pub fn get_user_settlements(env: Env, user: Address) -> Vec<u32> {
+        queries::get_user_settlements(env, user)
+    }
+
+    pub fn get_arbitrated_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Option<ArbitratedSale> {
+        queries::get_arbitrated_sale(env, seller, buyer, asset_id)
+    }
+
+    pub fn get_forward_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Option<ForwardSale> {
+        queries::get_forward_sale(env, seller, buyer, asset_id)
+    }
+
+    /// Trading contract this extension settles through
+    pub fn get_trading_contract(env: Env) -> Result<Address, EscrowError> {
+        queries::get_trading_contract(env)
+    }
+}
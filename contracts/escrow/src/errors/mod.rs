@@ -0,0 +1,55 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by escrow entrypoints
+///
+/// Codes shared with the trading contract keep its numbers, so a failure the
+/// trading hooks report reads the same here; codes from 100 are escrow's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidTokenAmount = 3,
+    InvalidPrice = 4,
+    SelfTrade = 5,
+    InvalidDuration = 6,
+    AssetNotFound = 7,
+    InsufficientBalance = 8,
+    SaleAlreadyExists = 9,
+    SaleNotFound = 10,
+    UnauthorizedBuyer = 12,
+    UnauthorizedSeller = 13,
+    SaleExpired = 14,
+    SaleNotExpired = 15,
+    TokenAmountMismatch = 16,
+    PriceMismatch = 17,
+    SellerInsufficientBalance = 18,
+    BuyerInsufficientFunds = 19,
+    InsufficientAllowance = 20,
+    PriceOverflow = 21,
+    Unauthorized = 23,
+    InvalidAmount = 24,
+    EscrowNotFound = 25,
+    InvalidEscrowStatus = 26,
+    InvalidMilestones = 27,
+    MilestoneNotFound = 28,
+    MilestoneAlreadySettled = 29,
+    MilestoneNotDue = 30,
+    NotEscrowParty = 31,
+    InvalidArbiter = 32,
+    NoArbiter = 33,
+    UnauthorizedArbiter = 34,
+    MilestoneDisputed = 35,
+    SettlementNotFound = 36,
+    InvalidSettlementStatus = 37,
+    DisputeWindowClosed = 38,
+    DisputeWindowOpen = 39,
+    AddressBlocked = 46,
+    ScreeningFailed = 47,
+    AssetPaused = 48,
+    UpstreamPaused = 51,
+    ExceedsReleasedTranche = 53,
+    BuyerNotEligible = 54,
+    SettlementNotDue = 100,
+}
@@ -0,0 +1,240 @@
+use crate::storage::{ArbitratedSale, Escrow, ForwardSale, Settlement};
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// contract initialization event
+pub fn emit_init_event(env: &Env, admin: &Address, trading_contract: &Address) {
+    env.events().publish(
+        (symbol_short!("init"),),
+        (admin.clone(), trading_contract.clone()),
+    );
+}
+
+/// escrow creation event
+pub fn emit_escrow_created_event(env: &Env, escrow_id: u32, escrow: &Escrow) {
+    env.events().publish(
+        (symbol_short!("esc_new"), escrow_id),
+        (
+            escrow.seller.clone(),
+            escrow.buyer.clone(),
+            escrow.asset_id,
+            escrow.token_amount,
+            escrow.total_price,
+            escrow.milestones.len(),
+        ),
+    );
+}
+
+/// escrow funded by the buyer event
+pub fn emit_escrow_funded_event(env: &Env, escrow_id: u32, buyer: &Address, amount: u128) {
+    env.events().publish(
+        (symbol_short!("esc_fund"), escrow_id),
+        (buyer.clone(), amount),
+    );
+}
+
+/// milestone confirmation by one party event
+pub fn emit_milestone_confirmed_event(env: &Env, escrow_id: u32, index: u32, by: &Address) {
+    env.events()
+        .publish((symbol_short!("esc_conf"), escrow_id), (index, by.clone()));
+}
+
+/// milestone release event
+pub fn emit_milestone_released_event(
+    env: &Env,
+    escrow_id: u32,
+    index: u32,
+    amount: u128,
+    tokens: u64,
+) {
+    env.events().publish(
+        (symbol_short!("esc_rel"), escrow_id),
+        (index, amount, tokens),
+    );
+}
+
+/// milestone refund event
+pub fn emit_milestone_refunded_event(
+    env: &Env,
+    escrow_id: u32,
+    index: u32,
+    amount: u128,
+    tokens: u64,
+) {
+    env.events().publish(
+        (symbol_short!("esc_rfd"), escrow_id),
+        (index, amount, tokens),
+    );
+}
+
+/// escrow cancellation event
+pub fn emit_escrow_cancelled_event(env: &Env, escrow_id: u32) {
+    env.events()
+        .publish((symbol_short!("esc_cncl"), escrow_id), ());
+}
+
+/// escrow completion event
+pub fn emit_escrow_completed_event(env: &Env, escrow_id: u32) {
+    env.events()
+        .publish((symbol_short!("esc_done"), escrow_id), ());
+}
+
+/// milestone dispute event
+pub fn emit_milestone_disputed_event(env: &Env, escrow_id: u32, index: u32, by: &Address) {
+    env.events()
+        .publish((symbol_short!("esc_disp"), escrow_id), (index, by.clone()));
+}
+
+/// arbitrated sale listed for its buyer event
+pub fn emit_arbitrated_sale_event(env: &Env, sale: &ArbitratedSale) {
+    env.events().publish(
+        (symbol_short!("arb_new"),),
+        (
+            sale.seller.clone(),
+            sale.buyer.clone(),
+            sale.arbiter.clone(),
+            sale.asset_id,
+            sale.token_amount,
+            sale.price,
+            sale.expires_at,
+        ),
+    );
+}
+
+/// arbitrated sale withdrawn by the seller event
+pub fn emit_arbitrated_withdrawn_event(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+) {
+    env.events().publish(
+        (symbol_short!("arb_wdr"),),
+        (seller.clone(), buyer.clone(), asset_id),
+    );
+}
+
+/// arbitrated sale paid and held for the dispute window event
+pub fn emit_settlement_held_event(env: &Env, settlement_id: u32, settlement: &Settlement) {
+    env.events().publish(
+        (symbol_short!("held"), settlement_id),
+        (
+            settlement.sale.seller.clone(),
+            settlement.sale.buyer.clone(),
+            settlement.sale.arbiter.clone(),
+            settlement.sale.asset_id,
+            settlement.sale.token_amount,
+            settlement.sale.price,
+            settlement.dispute_deadline,
+        ),
+    );
+}
+
+/// buyer dispute event
+pub fn emit_settlement_disputed_event(env: &Env, settlement_id: u32, buyer: &Address) {
+    env.events()
+        .publish((symbol_short!("disputed"), settlement_id), buyer.clone());
+}
+
+/// settlement released to the parties event
+pub fn emit_settlement_released_event(env: &Env, settlement_id: u32, trade_id: u32) {
+    env.events()
+        .publish((symbol_short!("released"), settlement_id), trade_id);
+}
+
+/// settlement refunded by the arbiter event
+pub fn emit_settlement_refunded_event(env: &Env, settlement_id: u32, arbiter: &Address) {
+    env.events()
+        .publish((symbol_short!("refunded"), settlement_id), arbiter.clone());
+}
+
+/// forward sale listed with its settlement terms event
+pub fn emit_forward_created_event(env: &Env, forward: &ForwardSale) {
+    env.events().publish(
+        (symbol_short!("fwd_new"),),
+        (
+            forward.seller.clone(),
+            forward.buyer.clone(),
+            forward.asset_id,
+            forward.terms.clone(),
+        ),
+    );
+}
+
+/// buyer committed to a forward sale, locking the deposit event
+pub fn emit_forward_committed_event(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+    deposit: u128,
+) {
+    env.events().publish(
+        (symbol_short!("fwd_cmt"),),
+        (seller.clone(), buyer.clone(), asset_id, deposit),
+    );
+}
+
+/// one party asked to cancel a committed forward sale event
+pub fn emit_forward_cancel_requested_event(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+    requested_by: &Address,
+) {
+    env.events().publish(
+        (symbol_short!("fwd_req"),),
+        (
+            seller.clone(),
+            buyer.clone(),
+            asset_id,
+            requested_by.clone(),
+        ),
+    );
+}
+
+/// forward sale cancelled, withdrawn or lapsed (forfeited = deposit paid to the seller) event
+pub fn emit_forward_cancelled_event(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+    forfeited: bool,
+) {
+    env.events().publish(
+        (symbol_short!("fwd_cncl"),),
+        (seller.clone(), buyer.clone(), asset_id, forfeited),
+    );
+}
+
+/// forward sale settled on its date event
+pub fn emit_forward_settled_event(env: &Env, forward: &ForwardSale, deposit: u128, trade_id: u32) {
+    env.events().publish(
+        (symbol_short!("fwd_done"),),
+        (
+            forward.seller.clone(),
+            forward.buyer.clone(),
+            forward.asset_id,
+            deposit,
+            trade_id,
+        ),
+    );
+}
+
+/// fractions sent to the contract by mistake returned event
+pub fn emit_rescued_event(env: &Env, asset_id: u64, to: &Address, amount: u64) {
+    env.events()
+        .publish((symbol_short!("rescued"), asset_id), (to.clone(), amount));
+}
+
+/// holdings fell short of obligations and the asset was paused event.
+/// Carries (owed, held) for the asset's fractions and for XLM.
+pub fn emit_invariant_violation_event(
+    env: &Env,
+    asset_id: u64,
+    tokens: (u64, u64),
+    xlm: (i128, i128),
+) {
+    env.events()
+        .publish((symbol_short!("inv_alert"), asset_id), (tokens, xlm));
+}
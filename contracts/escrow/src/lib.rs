@@ -0,0 +1,14 @@
+#![no_std]
+
+pub mod contract;
+pub mod errors;
+pub mod events;
+pub mod interfaces;
+pub mod methods;
+pub mod storage;
+
+#[cfg(test)]
+pub mod tests;
+
+pub use contract::EscrowContract;
+pub use errors::EscrowError;
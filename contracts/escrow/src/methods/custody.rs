@@ -0,0 +1,104 @@
+use crate::errors::EscrowError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Fractions of an asset the contract holds on behalf of escrows, settlements
+/// and forward sales
+pub fn get_held_tokens(env: Env, asset_id: u64) -> u64 {
+    storage::get(&env, &DataKey::HeldTokens(asset_id)).unwrap_or(0)
+}
+
+/// XLM the contract holds on behalf of escrows, settlements and forward sales
+pub fn get_held_xlm(env: Env) -> i128 {
+    storage::get(&env, &DataKey::HeldXlm).unwrap_or(0)
+}
+
+/// Record fractions and XLM that entered the contract and are owed to a party
+pub fn hold(env: &Env, asset_id: u64, tokens: u64, xlm: i128) {
+    adjust(env, asset_id, tokens as i128, xlm);
+}
+
+/// Record fractions and XLM paid out of the contract
+pub fn release(env: &Env, asset_id: u64, tokens: u64, xlm: i128) {
+    adjust(env, asset_id, -(tokens as i128), -xlm);
+}
+
+fn adjust(env: &Env, asset_id: u64, tokens: i128, xlm: i128) {
+    if tokens != 0 {
+        let held = get_held_tokens(env.clone(), asset_id) as i128 + tokens;
+        storage::set(env, &DataKey::HeldTokens(asset_id), &(held.max(0) as u64));
+    }
+    if xlm != 0 {
+        let held = get_held_xlm(env.clone()) + xlm;
+        storage::set(env, &DataKey::HeldXlm, &held.max(0));
+    }
+}
+
+/// Check the contract still holds what it owes after a payout.
+///
+/// Its fraction balance must cover the held fractions of the asset, and its XLM
+/// balance the held XLM. A violation has trading pause the asset and emits an
+/// alert instead of reverting, so the pause survives; it stays paused until
+/// the trading admin unpauses it. Returns whether the invariants held.
+pub fn check_invariants(env: &Env, asset_id: u64) -> Result<bool, EscrowError> {
+    let contract_id = env.current_contract_address();
+
+    let fnft_client = FNFTClient::new(env, &utils::get_fnft_contract(env)?);
+    let token_balance = fnft_client.balance_of(&contract_id, &asset_id);
+    let tokens_owed = get_held_tokens(env.clone(), asset_id);
+
+    let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+    let xlm_balance = xlm_client.balance(&contract_id);
+    let xlm_owed = get_held_xlm(env.clone());
+
+    if token_balance >= tokens_owed && xlm_balance >= xlm_owed {
+        return Ok(true);
+    }
+
+    utils::trading(env)?.pause_asset(&contract_id, &asset_id);
+    events::emit_invariant_violation_event(
+        env,
+        asset_id,
+        (tokens_owed, token_balance),
+        (xlm_owed, xlm_balance),
+    );
+
+    Ok(false)
+}
+
+/// Fractions of an asset held beyond what escrows, settlements and forward
+/// sales are owed
+pub fn get_rescuable_tokens(env: Env, asset_id: u64) -> Result<u64, EscrowError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let balance = fnft_client.balance_of(&env.current_contract_address(), &asset_id);
+    Ok(balance.saturating_sub(get_held_tokens(env, asset_id)))
+}
+
+/// Admin returns fractions sent to this contract by mistake; fractions held
+/// for escrows, settlements and forward sales cannot be taken
+pub fn rescue_fractions(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    to: Address,
+    amount: u64,
+) -> Result<(), EscrowError> {
+    utils::require_admin(&env, &admin)?;
+
+    if amount == 0 {
+        return Err(EscrowError::InvalidTokenAmount);
+    }
+    if amount > get_rescuable_tokens(env.clone(), asset_id)? {
+        return Err(EscrowError::InsufficientBalance);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    fnft_client.transfer(&env.current_contract_address(), &to, &asset_id, &amount);
+
+    events::emit_rescued_event(&env, asset_id, &to, amount);
+
+    Ok(())
+}
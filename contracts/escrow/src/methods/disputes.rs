@@ -0,0 +1,334 @@
+use crate::errors::EscrowError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{custody, utils};
+use crate::storage::{
+    self, ArbitratedSale, Conduct, DataKey, Settlement, SettlementStatus, DISPUTE_WINDOW,
+};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Seller lists a sale to one buyer with an arbiter. The fractions stay with
+/// the seller under an allowance to this contract; once the buyer pays, both
+/// legs are held for the dispute window and the arbiter rules on disputes.
+#[allow(clippy::too_many_arguments)]
+pub fn confirm_sale_with_arbiter(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    arbiter: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    duration_seconds: u64,
+) -> Result<(), EscrowError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(EscrowError::InvalidTokenAmount);
+    }
+    if price == 0 {
+        return Err(EscrowError::InvalidPrice);
+    }
+    if seller == buyer {
+        return Err(EscrowError::SelfTrade);
+    }
+    if arbiter == seller || arbiter == buyer {
+        return Err(EscrowError::InvalidArbiter);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+    utils::trading(&env)?.check_parties(&seller, &buyer, &asset_id);
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(EscrowError::AssetNotFound);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < token_amount {
+        return Err(EscrowError::InsufficientBalance);
+    }
+
+    let key = DataKey::ArbitratedSale(seller.clone(), buyer.clone(), asset_id);
+    if storage::has(&env, &key) {
+        return Err(EscrowError::SaleAlreadyExists);
+    }
+
+    let contract_id = env.current_contract_address();
+    let current_allowance = fnft_client.allowance(&seller, &contract_id, &asset_id);
+    fnft_client.approve(
+        &seller,
+        &contract_id,
+        &asset_id,
+        &(current_allowance + token_amount),
+    );
+
+    let sale = ArbitratedSale {
+        seller,
+        buyer,
+        arbiter,
+        asset_id,
+        token_amount,
+        price,
+        expires_at: env.ledger().timestamp() + duration_seconds,
+    };
+    storage::set(&env, &key, &sale);
+
+    events::emit_arbitrated_sale_event(&env, &sale);
+
+    Ok(())
+}
+
+/// Seller withdraws an unpaid arbitrated sale, expired or not, and the
+/// allowance it granted is reduced again
+pub fn withdraw_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), EscrowError> {
+    seller.require_auth();
+
+    let sale = get_arbitrated_sale(&env, &seller, &buyer, asset_id)?;
+    storage::remove(
+        &env,
+        &DataKey::ArbitratedSale(seller.clone(), buyer.clone(), asset_id),
+    );
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let current_allowance = fnft_client.allowance(&seller, &contract_id, &asset_id);
+    fnft_client.approve(
+        &seller,
+        &contract_id,
+        &asset_id,
+        &current_allowance.saturating_sub(sale.token_amount),
+    );
+
+    utils::trading(&env)?.record_conduct(&contract_id, &seller, &Conduct::Cancellation);
+
+    events::emit_arbitrated_withdrawn_event(&env, &seller, &buyer, asset_id);
+
+    Ok(())
+}
+
+/// Buyer pays for an arbitrated sale on the terms they expect. The fractions
+/// and the payment are held by this contract for the dispute window; returns
+/// the settlement ID.
+pub fn finish_transaction(
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    asset_id: u64,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, EscrowError> {
+    buyer.require_auth();
+
+    let sale = get_arbitrated_sale(&env, &seller, &buyer, asset_id)?;
+    if env.ledger().timestamp() > sale.expires_at {
+        return Err(EscrowError::SaleExpired);
+    }
+    if sale.token_amount != expected_token_amount {
+        return Err(EscrowError::TokenAmountMismatch);
+    }
+    if sale.price != expected_price {
+        return Err(EscrowError::PriceMismatch);
+    }
+    if sale.price > i128::MAX as u128 {
+        return Err(EscrowError::PriceOverflow);
+    }
+
+    let trading = utils::trading(&env)?;
+    trading.check_trade(&seller, &buyer, &asset_id);
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    if xlm_client.balance(&buyer) < sale.price as i128 {
+        return Err(EscrowError::BuyerInsufficientFunds);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < sale.token_amount {
+        return Err(EscrowError::SellerInsufficientBalance);
+    }
+    if fnft_client.allowance(&seller, &contract_id, &asset_id) < sale.token_amount {
+        return Err(EscrowError::InsufficientAllowance);
+    }
+
+    // Delist before the external calls so a re-entrant call finds nothing to fill
+    storage::remove(
+        &env,
+        &DataKey::ArbitratedSale(seller.clone(), buyer.clone(), asset_id),
+    );
+
+    trading.draw_exit(&contract_id, &seller, &asset_id, &sale.token_amount);
+    fnft_client.transfer_from(
+        &contract_id,
+        &seller,
+        &contract_id,
+        &asset_id,
+        &sale.token_amount,
+    );
+    xlm_client.transfer(&buyer, &contract_id, &(sale.price as i128));
+
+    Ok(hold_settlement(&env, sale))
+}
+
+/// Record a paid arbitrated sale whose fractions and payment the contract now holds
+fn hold_settlement(env: &Env, sale: ArbitratedSale) -> u32 {
+    custody::hold(env, sale.asset_id, sale.token_amount, sale.price as i128);
+
+    let settlement_id: u32 = storage::get(env, &DataKey::SettlementCounter).unwrap_or(0) + 1;
+
+    let now = env.ledger().timestamp();
+    let settlement = Settlement {
+        sale,
+        settled_at: now,
+        dispute_deadline: now + DISPUTE_WINDOW,
+        status: SettlementStatus::Pending,
+    };
+
+    storage::set(env, &DataKey::Settlement(settlement_id), &settlement);
+    storage::set(env, &DataKey::SettlementCounter, &settlement_id);
+
+    utils::add_to_user_settlements(env, settlement.sale.seller.clone(), settlement_id);
+    utils::add_to_user_settlements(env, settlement.sale.buyer.clone(), settlement_id);
+
+    events::emit_settlement_held_event(env, settlement_id, &settlement);
+
+    settlement_id
+}
+
+/// Buyer flags a problem with a held sale before the dispute window closes
+pub fn dispute_settlement(env: Env, buyer: Address, settlement_id: u32) -> Result<(), EscrowError> {
+    buyer.require_auth();
+
+    let mut settlement = utils::get_settlement(&env, settlement_id)?;
+    if settlement.sale.buyer != buyer {
+        return Err(EscrowError::UnauthorizedBuyer);
+    }
+    if settlement.status != SettlementStatus::Pending {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+    if env.ledger().timestamp() > settlement.dispute_deadline {
+        return Err(EscrowError::DisputeWindowClosed);
+    }
+
+    settlement.status = SettlementStatus::Disputed;
+    storage::set(&env, &DataKey::Settlement(settlement_id), &settlement);
+
+    let trading = utils::trading(&env)?;
+    let contract_id = env.current_contract_address();
+    trading.record_conduct(&contract_id, &settlement.sale.seller, &Conduct::Dispute);
+    trading.record_conduct(&contract_id, &buyer, &Conduct::Dispute);
+
+    events::emit_settlement_disputed_event(&env, settlement_id, &buyer);
+
+    Ok(())
+}
+
+/// Anyone can complete an undisputed settlement once the dispute window has closed
+pub fn release_settlement(env: Env, settlement_id: u32) -> Result<u32, EscrowError> {
+    let mut settlement = utils::get_settlement(&env, settlement_id)?;
+    if settlement.status != SettlementStatus::Pending {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+    if env.ledger().timestamp() <= settlement.dispute_deadline {
+        return Err(EscrowError::DisputeWindowOpen);
+    }
+
+    release(&env, settlement_id, &mut settlement)
+}
+
+/// Arbiter rules on a disputed settlement: refund unwinds the sale, otherwise
+/// it completes as agreed. Returns the trade ID on release, 0 on refund.
+pub fn resolve_dispute(
+    env: Env,
+    arbiter: Address,
+    settlement_id: u32,
+    refund: bool,
+) -> Result<u32, EscrowError> {
+    arbiter.require_auth();
+
+    let mut settlement = utils::get_settlement(&env, settlement_id)?;
+    if settlement.sale.arbiter != arbiter {
+        return Err(EscrowError::UnauthorizedArbiter);
+    }
+    if settlement.status != SettlementStatus::Disputed {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+
+    if !refund {
+        return release(&env, settlement_id, &mut settlement);
+    }
+
+    settlement.status = SettlementStatus::Refunded;
+    storage::set(&env, &DataKey::Settlement(settlement_id), &settlement);
+
+    let sale = &settlement.sale;
+    pay_out(&env, sale, &sale.seller, &sale.buyer)?;
+
+    events::emit_settlement_refunded_event(&env, settlement_id, &arbiter);
+
+    Ok(0)
+}
+
+/// Deliver fractions to the buyer and payment to the seller, then record the
+/// trade on the trading contract
+fn release(env: &Env, settlement_id: u32, settlement: &mut Settlement) -> Result<u32, EscrowError> {
+    settlement.status = SettlementStatus::Released;
+    storage::set(env, &DataKey::Settlement(settlement_id), &*settlement);
+
+    let sale = &settlement.sale;
+    pay_out(env, sale, &sale.buyer, &sale.seller)?;
+
+    let trade_id = utils::trading(env)?.record_trade(
+        &env.current_contract_address(),
+        &sale.seller,
+        &sale.buyer,
+        &sale.asset_id,
+        &sale.token_amount,
+        &sale.price,
+    );
+
+    events::emit_settlement_released_event(env, settlement_id, trade_id);
+
+    Ok(trade_id)
+}
+
+fn pay_out(
+    env: &Env,
+    sale: &ArbitratedSale,
+    tokens_to: &Address,
+    xlm_to: &Address,
+) -> Result<(), EscrowError> {
+    utils::require_not_paused(env, sale.asset_id)?;
+    let contract_id = env.current_contract_address();
+
+    let fnft_client = FNFTClient::new(env, &utils::get_fnft_contract(env)?);
+    fnft_client.transfer(&contract_id, tokens_to, &sale.asset_id, &sale.token_amount);
+
+    if *xlm_to == sale.seller {
+        utils::pay_seller(env, xlm_to, sale.asset_id, sale.price as i128)?;
+    } else {
+        let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+        xlm_client.transfer(&contract_id, xlm_to, &(sale.price as i128));
+    }
+
+    custody::release(env, sale.asset_id, sale.token_amount, sale.price as i128);
+    custody::check_invariants(env, sale.asset_id)?;
+
+    Ok(())
+}
+
+pub fn get_arbitrated_sale(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+) -> Result<ArbitratedSale, EscrowError> {
+    match storage::get(
+        env,
+        &DataKey::ArbitratedSale(seller.clone(), buyer.clone(), asset_id),
+    ) {
+        Some(sale) => Ok(sale),
+        None => Err(EscrowError::SaleNotFound),
+    }
+}
@@ -0,0 +1,376 @@
+use crate::errors::EscrowError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{custody, utils};
+use crate::storage::{
+    self, Conduct, DataKey, Escrow, EscrowStatus, Milestone, MilestoneStatus, MAX_ESCROW_MILESTONES,
+};
+use soroban_sdk::{token::TokenClient, Address, Env, Vec};
+
+/// Seller opens a milestone escrow and locks the fractions in the contract.
+/// `payments` is the schedule of (xlm_amount, deadline) stages.
+pub fn create_escrow(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    arbiter: Option<Address>,
+    asset_id: u64,
+    token_amount: u64,
+    payments: Vec<(u128, u64)>,
+) -> Result<u32, EscrowError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(EscrowError::InvalidTokenAmount);
+    }
+    if seller == buyer {
+        return Err(EscrowError::SelfTrade);
+    }
+    if let Some(arbiter) = &arbiter {
+        if *arbiter == seller || *arbiter == buyer {
+            return Err(EscrowError::InvalidArbiter);
+        }
+    }
+    if payments.is_empty() || payments.len() > MAX_ESCROW_MILESTONES {
+        return Err(EscrowError::InvalidMilestones);
+    }
+
+    let now = env.ledger().timestamp();
+    let mut milestones = Vec::new(&env);
+    let mut total_price: u128 = 0;
+    let mut last_deadline = now;
+    for (amount, deadline) in payments.iter() {
+        // Deadlines must be in the future and in schedule order
+        if amount == 0 || deadline <= now || deadline < last_deadline {
+            return Err(EscrowError::InvalidMilestones);
+        }
+        last_deadline = deadline;
+
+        total_price = match total_price.checked_add(amount) {
+            Some(total) => total,
+            None => return Err(EscrowError::PriceOverflow),
+        };
+
+        milestones.push_back(Milestone {
+            amount,
+            deadline,
+            seller_confirmed: false,
+            buyer_confirmed: false,
+            disputed: false,
+            status: MilestoneStatus::Pending,
+        });
+    }
+    if total_price > i128::MAX as u128 {
+        return Err(EscrowError::PriceOverflow);
+    }
+    let trading = utils::trading(&env)?;
+    trading.check_parties(&seller, &buyer, &asset_id);
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(EscrowError::AssetNotFound);
+    }
+    let seller_balance = fnft_client.balance_of(&seller, &asset_id);
+    if seller_balance < token_amount {
+        return Err(EscrowError::InsufficientBalance);
+    }
+    trading.draw_exit(
+        &env.current_contract_address(),
+        &seller,
+        &asset_id,
+        &token_amount,
+    );
+
+    fnft_client.transfer(
+        &seller,
+        &env.current_contract_address(),
+        &asset_id,
+        &token_amount,
+    );
+    custody::hold(&env, asset_id, token_amount, 0);
+
+    let escrow_id: u32 = storage::get(&env, &DataKey::EscrowCounter).unwrap_or(0) + 1;
+
+    let escrow = Escrow {
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        arbiter,
+        asset_id,
+        token_amount,
+        total_price,
+        milestones,
+        tokens_settled: 0,
+        status: EscrowStatus::Proposed,
+        created_at: now,
+    };
+
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+    storage::set(&env, &DataKey::EscrowCounter, &escrow_id);
+
+    utils::add_to_user_escrows(&env, seller, escrow_id);
+    utils::add_to_user_escrows(&env, buyer, escrow_id);
+
+    events::emit_escrow_created_event(&env, escrow_id, &escrow);
+
+    Ok(escrow_id)
+}
+
+/// Buyer accepts the escrow by depositing the full price with the contract
+pub fn accept_escrow(env: Env, buyer: Address, escrow_id: u32) -> Result<(), EscrowError> {
+    buyer.require_auth();
+
+    let mut escrow = utils::get_escrow(&env, escrow_id)?;
+    if escrow.buyer != buyer {
+        return Err(EscrowError::UnauthorizedBuyer);
+    }
+    if escrow.status != EscrowStatus::Proposed {
+        return Err(EscrowError::InvalidEscrowStatus);
+    }
+    utils::trading(&env)?.check_parties(&escrow.seller, &buyer, &escrow.asset_id);
+
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    if xlm_client.balance(&buyer) < escrow.total_price as i128 {
+        return Err(EscrowError::BuyerInsufficientFunds);
+    }
+    xlm_client.transfer(
+        &buyer,
+        &env.current_contract_address(),
+        &(escrow.total_price as i128),
+    );
+    custody::hold(&env, escrow.asset_id, 0, escrow.total_price as i128);
+
+    escrow.status = EscrowStatus::Active;
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+
+    events::emit_escrow_funded_event(&env, escrow_id, &buyer, escrow.total_price);
+
+    Ok(())
+}
+
+/// Seller cancels an escrow the buyer has not funded yet and gets the fractions back
+pub fn cancel_escrow(env: Env, seller: Address, escrow_id: u32) -> Result<(), EscrowError> {
+    seller.require_auth();
+
+    let mut escrow = utils::get_escrow(&env, escrow_id)?;
+    if escrow.seller != seller {
+        return Err(EscrowError::UnauthorizedSeller);
+    }
+    if escrow.status != EscrowStatus::Proposed {
+        return Err(EscrowError::InvalidEscrowStatus);
+    }
+    utils::require_not_paused(&env, escrow.asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    fnft_client.transfer(
+        &env.current_contract_address(),
+        &seller,
+        &escrow.asset_id,
+        &escrow.token_amount,
+    );
+    custody::release(&env, escrow.asset_id, escrow.token_amount, 0);
+    custody::check_invariants(&env, escrow.asset_id)?;
+
+    escrow.status = EscrowStatus::Cancelled;
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+
+    events::emit_escrow_cancelled_event(&env, escrow_id);
+
+    Ok(())
+}
+
+/// Confirm a milestone. It is released once both buyer and seller confirmed,
+/// or immediately when the arbiter confirms. Returns whether it was released.
+pub fn confirm_milestone(
+    env: Env,
+    caller: Address,
+    escrow_id: u32,
+    index: u32,
+) -> Result<bool, EscrowError> {
+    caller.require_auth();
+
+    let mut escrow = utils::get_escrow(&env, escrow_id)?;
+    let mut milestone = get_pending_milestone(&escrow, index)?;
+
+    let by_arbiter = escrow.arbiter == Some(caller.clone());
+    if milestone.disputed && !by_arbiter {
+        return Err(EscrowError::MilestoneDisputed);
+    }
+    if caller == escrow.seller {
+        milestone.seller_confirmed = true;
+    } else if caller == escrow.buyer {
+        milestone.buyer_confirmed = true;
+    } else if !by_arbiter {
+        return Err(EscrowError::NotEscrowParty);
+    }
+
+    events::emit_milestone_confirmed_event(&env, escrow_id, index, &caller);
+
+    let release = by_arbiter || (milestone.seller_confirmed && milestone.buyer_confirmed);
+    if release {
+        let amount = milestone.amount;
+        let tokens = settle_milestone(&env, escrow_id, &mut escrow, index, milestone, true)?;
+        events::emit_milestone_released_event(&env, escrow_id, index, amount, tokens);
+        finish_if_settled(&env, escrow_id, &mut escrow);
+    } else {
+        escrow.milestones.set(index, milestone);
+    }
+
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+
+    Ok(release)
+}
+
+/// Refund a milestone: the payment goes back to the buyer and the matching
+/// fractions back to the seller. Parties may only do this once the deadline
+/// was missed; the arbiter may do it at any time to resolve a dispute.
+pub fn refund_milestone(
+    env: Env,
+    caller: Address,
+    escrow_id: u32,
+    index: u32,
+) -> Result<(), EscrowError> {
+    caller.require_auth();
+
+    let mut escrow = utils::get_escrow(&env, escrow_id)?;
+    let milestone = get_pending_milestone(&escrow, index)?;
+
+    if escrow.arbiter != Some(caller.clone()) {
+        if caller != escrow.seller && caller != escrow.buyer {
+            return Err(EscrowError::NotEscrowParty);
+        }
+        if milestone.disputed {
+            return Err(EscrowError::MilestoneDisputed);
+        }
+        if env.ledger().timestamp() <= milestone.deadline {
+            return Err(EscrowError::MilestoneNotDue);
+        }
+    }
+
+    let amount = milestone.amount;
+    let tokens = settle_milestone(&env, escrow_id, &mut escrow, index, milestone, false)?;
+    events::emit_milestone_refunded_event(&env, escrow_id, index, amount, tokens);
+    finish_if_settled(&env, escrow_id, &mut escrow);
+
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+
+    Ok(())
+}
+
+/// Either party freezes a pending milestone; from then on only the arbiter can
+/// release or refund it
+pub fn dispute_milestone(
+    env: Env,
+    caller: Address,
+    escrow_id: u32,
+    index: u32,
+) -> Result<(), EscrowError> {
+    caller.require_auth();
+
+    let mut escrow = utils::get_escrow(&env, escrow_id)?;
+    if caller != escrow.seller && caller != escrow.buyer {
+        return Err(EscrowError::NotEscrowParty);
+    }
+    if escrow.arbiter.is_none() {
+        return Err(EscrowError::NoArbiter);
+    }
+
+    let mut milestone = get_pending_milestone(&escrow, index)?;
+    milestone.disputed = true;
+    escrow.milestones.set(index, milestone);
+
+    storage::set(&env, &DataKey::Escrow(escrow_id), &escrow);
+    let trading = utils::trading(&env)?;
+    let contract_id = env.current_contract_address();
+    trading.record_conduct(&contract_id, &escrow.seller, &Conduct::Dispute);
+    trading.record_conduct(&contract_id, &escrow.buyer, &Conduct::Dispute);
+
+    events::emit_milestone_disputed_event(&env, escrow_id, index, &caller);
+
+    Ok(())
+}
+
+fn get_pending_milestone(escrow: &Escrow, index: u32) -> Result<Milestone, EscrowError> {
+    if escrow.status != EscrowStatus::Active {
+        return Err(EscrowError::InvalidEscrowStatus);
+    }
+    let milestone = match escrow.milestones.get(index) {
+        Some(milestone) => milestone,
+        None => return Err(EscrowError::MilestoneNotFound),
+    };
+    if milestone.status != MilestoneStatus::Pending {
+        return Err(EscrowError::MilestoneAlreadySettled);
+    }
+    Ok(milestone)
+}
+
+/// Move one milestone's XLM and its proportional share of fractions.
+/// Released: XLM to seller, fractions to buyer. Refunded: the reverse.
+/// The last pending milestone takes the rounding remainder of fractions.
+fn settle_milestone(
+    env: &Env,
+    escrow_id: u32,
+    escrow: &mut Escrow,
+    index: u32,
+    mut milestone: Milestone,
+    release: bool,
+) -> Result<u64, EscrowError> {
+    utils::require_not_paused(env, escrow.asset_id)?;
+
+    let pending = escrow
+        .milestones
+        .iter()
+        .filter(|m| m.status == MilestoneStatus::Pending)
+        .count();
+
+    let tokens = if pending == 1 {
+        escrow.token_amount - escrow.tokens_settled
+    } else {
+        (escrow.token_amount as u128 * milestone.amount / escrow.total_price) as u64
+    };
+
+    milestone.status = if release {
+        MilestoneStatus::Released
+    } else {
+        MilestoneStatus::Refunded
+    };
+    escrow.milestones.set(index, milestone.clone());
+    escrow.tokens_settled += tokens;
+
+    // Persist before the external calls so a re-entrant call sees the milestone as settled
+    storage::set(env, &DataKey::Escrow(escrow_id), &*escrow);
+
+    let (xlm_to, tokens_to) = if release {
+        (&escrow.seller, &escrow.buyer)
+    } else {
+        (&escrow.buyer, &escrow.seller)
+    };
+
+    let contract_id = env.current_contract_address();
+    if release {
+        utils::pay_seller(env, xlm_to, escrow.asset_id, milestone.amount as i128)?;
+    } else {
+        let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+        xlm_client.transfer(&contract_id, xlm_to, &(milestone.amount as i128));
+    }
+
+    if tokens > 0 {
+        let fnft_client = FNFTClient::new(env, &utils::get_fnft_contract(env)?);
+        fnft_client.transfer(&contract_id, tokens_to, &escrow.asset_id, &tokens);
+    }
+
+    custody::release(env, escrow.asset_id, tokens, milestone.amount as i128);
+    custody::check_invariants(env, escrow.asset_id)?;
+
+    Ok(tokens)
+}
+
+fn finish_if_settled(env: &Env, escrow_id: u32, escrow: &mut Escrow) {
+    let all_settled = escrow
+        .milestones
+        .iter()
+        .all(|m| m.status != MilestoneStatus::Pending);
+    if all_settled {
+        escrow.status = EscrowStatus::Completed;
+        events::emit_escrow_completed_event(env, escrow_id);
+    }
+}
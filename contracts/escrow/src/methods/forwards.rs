@@ -0,0 +1,372 @@
+use crate::errors::EscrowError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{custody, utils};
+use crate::storage::{self, Conduct, DataKey, ForwardSale, ForwardTerms};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Seller fixes the price now for a sale that settles once `settle_after` has
+/// passed, within the sale's lifetime.
+///
+/// The fractions are moved into the contract straight away, so the seller
+/// cannot sell them twice. The buyer locks the deposit with `commit_forward_sale`.
+#[allow(clippy::too_many_arguments)]
+pub fn confirm_forward_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    duration_seconds: u64,
+    terms: ForwardTerms,
+) -> Result<(), EscrowError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(EscrowError::InvalidTokenAmount);
+    }
+    if price == 0 {
+        return Err(EscrowError::InvalidPrice);
+    }
+    if price > i128::MAX as u128 {
+        return Err(EscrowError::PriceOverflow);
+    }
+    if seller == buyer {
+        return Err(EscrowError::SelfTrade);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+
+    let now = env.ledger().timestamp();
+    if terms.settle_after <= now || terms.settle_after >= now + duration_seconds {
+        return Err(EscrowError::InvalidDuration);
+    }
+    if terms.cancel_cutoff > terms.settle_after {
+        return Err(EscrowError::InvalidDuration);
+    }
+    if terms.buyer_deposit > price {
+        return Err(EscrowError::InvalidAmount);
+    }
+
+    let key = DataKey::ForwardSale(seller.clone(), buyer.clone(), asset_id);
+    if storage::has(&env, &key) {
+        return Err(EscrowError::SaleAlreadyExists);
+    }
+
+    let trading = utils::trading(&env)?;
+    trading.check_parties(&seller, &buyer, &asset_id);
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(EscrowError::AssetNotFound);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < token_amount {
+        return Err(EscrowError::InsufficientBalance);
+    }
+
+    let contract_id = env.current_contract_address();
+    trading.draw_exit(&contract_id, &seller, &asset_id, &token_amount);
+    fnft_client.transfer(&seller, &contract_id, &asset_id, &token_amount);
+    custody::hold(&env, asset_id, token_amount, 0);
+
+    let forward = ForwardSale {
+        seller,
+        buyer,
+        asset_id,
+        token_amount,
+        price,
+        expires_at: now + duration_seconds,
+        terms,
+        committed: false,
+        cancel_requested_by: None,
+    };
+    storage::set(&env, &key, &forward);
+
+    events::emit_forward_created_event(&env, &forward);
+
+    Ok(())
+}
+
+/// Buyer accepts a forward sale's terms and locks the deposit until settlement
+pub fn commit_forward_sale(
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    asset_id: u64,
+) -> Result<(), EscrowError> {
+    buyer.require_auth();
+
+    let mut forward = get_forward_sale(&env, &seller, &buyer, asset_id)?;
+    if env.ledger().timestamp() > forward.expires_at {
+        return Err(EscrowError::SaleExpired);
+    }
+    if forward.committed {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+
+    let deposit = forward.terms.buyer_deposit;
+    if deposit > 0 {
+        let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+        if xlm_client.balance(&buyer) < deposit as i128 {
+            return Err(EscrowError::BuyerInsufficientFunds);
+        }
+        xlm_client.transfer(&buyer, &env.current_contract_address(), &(deposit as i128));
+        custody::hold(&env, asset_id, 0, deposit as i128);
+    }
+
+    forward.committed = true;
+    set_forward(&env, &forward);
+
+    events::emit_forward_committed_event(&env, &seller, &buyer, asset_id, deposit);
+
+    Ok(())
+}
+
+/// Seller withdraws a forward sale the buyer has not committed to and gets the
+/// fractions back; once committed only `cancel_forward_sale` can end it
+pub fn withdraw_forward_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), EscrowError> {
+    seller.require_auth();
+
+    let forward = get_forward_sale(&env, &seller, &buyer, asset_id)?;
+    if forward.committed {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+
+    close(&env, &forward, false)?;
+    utils::trading(&env)?.record_conduct(
+        &env.current_contract_address(),
+        &seller,
+        &Conduct::Cancellation,
+    );
+
+    events::emit_forward_cancelled_event(&env, &seller, &buyer, asset_id, false);
+
+    Ok(())
+}
+
+/// Cancel a committed forward sale. Returns true once cancelled, or false when
+/// the caller's request is recorded and waits for the other party.
+///
+/// Either party cancels alone up to the cancel cutoff, and the deposit goes back
+/// to the buyer. After the cutoff both parties must ask. Once the sale has
+/// expired unsettled either party may cancel, and the buyer forfeits the deposit
+/// to the seller. The seller always gets the fractions back.
+pub fn cancel_forward_sale(
+    env: Env,
+    caller: Address,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<bool, EscrowError> {
+    caller.require_auth();
+
+    if caller != seller && caller != buyer {
+        return Err(EscrowError::Unauthorized);
+    }
+
+    let mut forward = get_forward_sale(&env, &seller, &buyer, asset_id)?;
+    // Uncommitted forward sales are withdrawn by the seller with withdraw_forward_sale
+    if !forward.committed {
+        return Err(EscrowError::InvalidSettlementStatus);
+    }
+
+    let now = env.ledger().timestamp();
+    let forfeit = now > forward.expires_at;
+    let agreed = match &forward.cancel_requested_by {
+        Some(requester) => *requester != caller,
+        None => false,
+    };
+    if !forfeit && now > forward.terms.cancel_cutoff && !agreed {
+        forward.cancel_requested_by = Some(caller.clone());
+        set_forward(&env, &forward);
+        events::emit_forward_cancel_requested_event(&env, &seller, &buyer, asset_id, &caller);
+        return Ok(false);
+    }
+
+    close(&env, &forward, forfeit)?;
+    // A lapsed forward sale is the buyer's default, not a cancellation
+    if !forfeit {
+        utils::trading(&env)?.record_conduct(
+            &env.current_contract_address(),
+            &caller,
+            &Conduct::Cancellation,
+        );
+    }
+
+    events::emit_forward_cancelled_event(&env, &seller, &buyer, asset_id, forfeit);
+
+    Ok(true)
+}
+
+/// Buyer settles a forward sale on or after its date: the held fractions go to
+/// the buyer and the seller receives the deposit plus the rest of the price.
+/// Returns the trade ID.
+pub fn finish_forward_sale(
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    asset_id: u64,
+) -> Result<u32, EscrowError> {
+    buyer.require_auth();
+
+    let forward = get_forward_sale(&env, &seller, &buyer, asset_id)?;
+    let now = env.ledger().timestamp();
+    if now > forward.expires_at {
+        return Err(EscrowError::SaleExpired);
+    }
+    if now < forward.terms.settle_after {
+        return Err(EscrowError::SettlementNotDue);
+    }
+
+    let trading = utils::trading(&env)?;
+    trading.check_trade(&seller, &buyer, &asset_id);
+
+    let deposit = if forward.committed {
+        forward.terms.buyer_deposit
+    } else {
+        0
+    };
+    let due = (forward.price - deposit) as i128;
+
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    if xlm_client.balance(&buyer) < due {
+        return Err(EscrowError::BuyerInsufficientFunds);
+    }
+
+    // Remove before the external calls so a re-entrant call finds nothing to settle
+    storage::remove(
+        &env,
+        &DataKey::ForwardSale(seller.clone(), buyer.clone(), asset_id),
+    );
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    fnft_client.transfer(&contract_id, &buyer, &asset_id, &forward.token_amount);
+    // Collect the balance first so the seller is paid, and any fee withheld,
+    // from the full price at once
+    if due > 0 {
+        xlm_client.transfer(&buyer, &contract_id, &due);
+    }
+    utils::pay_seller(&env, &seller, asset_id, forward.price as i128)?;
+    custody::release(&env, asset_id, forward.token_amount, deposit as i128);
+    custody::check_invariants(&env, asset_id)?;
+
+    let trade_id = trading.record_trade(
+        &contract_id,
+        &seller,
+        &buyer,
+        &asset_id,
+        &forward.token_amount,
+        &forward.price,
+    );
+
+    events::emit_forward_settled_event(&env, &forward, deposit, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Anyone can close a forward sale that expired unsettled: the seller gets the
+/// fractions back and a committed buyer forfeits the deposit to the seller.
+/// The keeper earns the trading bounty unless they are a party to the sale.
+pub fn cleanup_forward_sale(
+    env: Env,
+    keeper: Address,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<i128, EscrowError> {
+    keeper.require_auth();
+
+    let forward = get_forward_sale(&env, &seller, &buyer, asset_id)?;
+    if env.ledger().timestamp() <= forward.expires_at {
+        return Err(EscrowError::SaleNotExpired);
+    }
+
+    close(&env, &forward, true)?;
+
+    let trading = utils::trading(&env)?;
+    let contract_id = env.current_contract_address();
+    trading.record_conduct(&contract_id, &seller, &Conduct::Expiry);
+
+    events::emit_forward_cancelled_event(&env, &seller, &buyer, asset_id, true);
+
+    // Parties could otherwise farm bounties by letting their own sales lapse
+    if keeper == seller || keeper == buyer {
+        return Ok(0);
+    }
+
+    Ok(trading.pay_keeper(&contract_id, &keeper))
+}
+
+/// Remove a forward sale, return the held fractions to the seller and the
+/// deposit, if any, to the buyer or, when `forfeit`, to the seller
+fn close(env: &Env, forward: &ForwardSale, forfeit: bool) -> Result<(), EscrowError> {
+    storage::remove(
+        env,
+        &DataKey::ForwardSale(
+            forward.seller.clone(),
+            forward.buyer.clone(),
+            forward.asset_id,
+        ),
+    );
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(env, &utils::get_fnft_contract(env)?);
+    fnft_client.transfer(
+        &contract_id,
+        &forward.seller,
+        &forward.asset_id,
+        &forward.token_amount,
+    );
+
+    let deposit = if forward.committed {
+        forward.terms.buyer_deposit
+    } else {
+        0
+    };
+    if deposit > 0 {
+        let refund_to = if forfeit {
+            &forward.seller
+        } else {
+            &forward.buyer
+        };
+        let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+        xlm_client.transfer(&contract_id, refund_to, &(deposit as i128));
+    }
+    custody::release(env, forward.asset_id, forward.token_amount, deposit as i128);
+    custody::check_invariants(env, forward.asset_id)?;
+
+    Ok(())
+}
+
+pub fn get_forward_sale(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+) -> Result<ForwardSale, EscrowError> {
+    match storage::get(
+        env,
+        &DataKey::ForwardSale(seller.clone(), buyer.clone(), asset_id),
+    ) {
+        Some(forward) => Ok(forward),
+        None => Err(EscrowError::SaleNotFound),
+    }
+}
+
+fn set_forward(env: &Env, forward: &ForwardSale) {
+    storage::set(
+        env,
+        &DataKey::ForwardSale(
+            forward.seller.clone(),
+            forward.buyer.clone(),
+            forward.asset_id,
+        ),
+        forward,
+    );
+}
@@ -0,0 +1,42 @@
+use crate::errors::EscrowError;
+use crate::events;
+use crate::interfaces::TradingClient;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
+
+/// Deploy-time initialization for a trading contract; the fractcore and XLM
+/// contracts are the ones that trading contract settles in. The constructor
+/// runs once, atomically with contract creation, so no one can initialize
+/// first and the admin's auth is not needed.
+pub fn construct(env: Env, admin: Address, trading_contract: Address) -> Result<(), EscrowError> {
+    storage::mark_namespaced(&env);
+
+    let trading = TradingClient::new(&env, &trading_contract);
+    storage::set(&env, &DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::TradingContract, &trading_contract);
+    storage::set(
+        &env,
+        &DataKey::FNFTContract,
+        &trading.get_fnft_contract_address(),
+    );
+    storage::set(
+        &env,
+        &DataKey::XLMContract,
+        &trading.get_xlm_contract_address_public(),
+    );
+
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(admin.clone(), trading_contract.clone()).to_xdr(&env))
+        .into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
+
+    events::emit_init_event(&env, &admin, &trading_contract);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
+}
@@ -0,0 +1,7 @@
+pub mod custody;
+pub mod disputes;
+pub mod escrow;
+pub mod forwards;
+pub mod initialization;
+pub mod queries;
+pub mod utils;
@@ -0,0 +1,42 @@
+use crate::errors::EscrowError;
+use crate::methods::utils;
+use crate::storage::{self, ArbitratedSale, DataKey, Escrow, ForwardSale, Settlement};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn get_escrow(env: Env, escrow_id: u32) -> Result<Escrow, EscrowError> {
+    utils::get_escrow(&env, escrow_id)
+}
+
+pub fn get_user_escrows(env: Env, user: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::UserEscrows(user)).unwrap_or(Vec::new(&env))
+}
+
+pub fn get_settlement(env: Env, settlement_id: u32) -> Result<Settlement, EscrowError> {
+    utils::get_settlement(&env, settlement_id)
+}
+
+pub fn get_user_settlements(env: Env, user: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::UserSettlements(user)).unwrap_or(Vec::new(&env))
+}
+
+pub fn get_arbitrated_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Option<ArbitratedSale> {
+    storage::get(&env, &DataKey::ArbitratedSale(seller, buyer, asset_id))
+}
+
+pub fn get_forward_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Option<ForwardSale> {
+    storage::get(&env, &DataKey::ForwardSale(seller, buyer, asset_id))
+}
+
+pub fn get_trading_contract(env: Env) -> Result<Address, EscrowError> {
+    utils::get_trading_contract(&env)
+}
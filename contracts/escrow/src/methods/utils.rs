@@ -0,0 +1,131 @@
+use crate::errors::EscrowError;
+use crate::interfaces::TradingClient;
+use crate::storage::{self, DataKey, Escrow, Settlement};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+pub fn require_admin(env: &Env, admin: &Address) -> Result<(), EscrowError> {
+    admin.require_auth();
+
+    let stored_admin: Option<Address> = storage::get(env, &DataKey::Admin);
+    match stored_admin {
+        Some(stored) if stored == *admin => Ok(()),
+        Some(_) => Err(EscrowError::Unauthorized),
+        None => Err(EscrowError::NotInitialized),
+    }
+}
+
+pub fn get_trading_contract(env: &Env) -> Result<Address, EscrowError> {
+    match storage::get(env, &DataKey::TradingContract) {
+        Some(address) => Ok(address),
+        None => Err(EscrowError::NotInitialized),
+    }
+}
+
+pub fn trading(env: &Env) -> Result<TradingClient<'_>, EscrowError> {
+    Ok(TradingClient::new(env, &get_trading_contract(env)?))
+}
+
+pub fn get_fnft_contract(env: &Env) -> Result<Address, EscrowError> {
+    match storage::get(env, &DataKey::FNFTContract) {
+        Some(address) => Ok(address),
+        None => Err(EscrowError::NotInitialized),
+    }
+}
+
+pub fn get_xlm_contract(env: &Env) -> Result<Address, EscrowError> {
+    match storage::get(env, &DataKey::XLMContract) {
+        Some(address) => Ok(address),
+        None => Err(EscrowError::NotInitialized),
+    }
+}
+
+/// Fail while trading has the asset paused, or fractcore is paused, like a
+/// trade on the trading contract itself would
+pub fn require_not_paused(env: &Env, asset_id: u64) -> Result<(), EscrowError> {
+    let trading = trading(env)?;
+    if trading.is_asset_paused(&asset_id) {
+        return Err(EscrowError::AssetPaused);
+    }
+    // An unreadable pause state counts as a pause, as it does on trading
+    match trading.try_get_upstream_pause() {
+        Ok(Ok(None)) => Ok(()),
+        _ => Err(EscrowError::UpstreamPaused),
+    }
+}
+
+/// Check a listing lifetime against the asset's sale duration limits on trading
+pub fn require_valid_duration(
+    env: &Env,
+    asset_id: u64,
+    duration_seconds: u64,
+) -> Result<(), EscrowError> {
+    let limits = trading(env)?.get_asset_sale_duration_limits(&asset_id);
+    if !(limits.min_seconds..=limits.max_seconds).contains(&duration_seconds) {
+        return Err(EscrowError::InvalidDuration);
+    }
+    Ok(())
+}
+
+/// Pay a seller from XLM this contract holds: trading takes the amount,
+/// withholds the trading fee and routes the rest like any sale
+pub fn pay_seller(
+    env: &Env,
+    seller: &Address,
+    asset_id: u64,
+    amount: i128,
+) -> Result<(), EscrowError> {
+    let trading = trading(env)?;
+    let contract_id = env.current_contract_address();
+    authorize_payment(env, &get_xlm_contract(env)?, &trading.address, amount);
+    trading.pay_seller(&contract_id, &contract_id, seller, &asset_id, &amount);
+    Ok(())
+}
+
+/// Let trading take `amount` of this contract's XLM in the `pay_seller` call
+/// that follows, the one transfer that call makes from its payer
+pub fn authorize_payment(env: &Env, xlm_contract: &Address, trading: &Address, amount: i128) {
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: xlm_contract.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (env.current_contract_address(), trading.clone(), amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+}
+
+pub fn get_escrow(env: &Env, escrow_id: u32) -> Result<Escrow, EscrowError> {
+    match storage::get(env, &DataKey::Escrow(escrow_id)) {
+        Some(escrow) => Ok(escrow),
+        None => Err(EscrowError::EscrowNotFound),
+    }
+}
+
+pub fn add_to_user_escrows(env: &Env, user: Address, escrow_id: u32) {
+    let mut escrows: Vec<u32> =
+        storage::get(env, &DataKey::UserEscrows(user.clone())).unwrap_or(Vec::new(env));
+
+    escrows.push_back(escrow_id);
+    storage::set(env, &DataKey::UserEscrows(user), &escrows);
+}
+
+pub fn get_settlement(env: &Env, settlement_id: u32) -> Result<Settlement, EscrowError> {
+    match storage::get(env, &DataKey::Settlement(settlement_id)) {
+        Some(settlement) => Ok(settlement),
+        None => Err(EscrowError::SettlementNotFound),
+    }
+}
+
+pub fn add_to_user_settlements(env: &Env, user: Address, settlement_id: u32) {
+    let mut settlements: Vec<u32> =
+        storage::get(env, &DataKey::UserSettlements(user.clone())).unwrap_or(Vec::new(env));
+
+    settlements.push_back(settlement_id);
+    storage::set(env, &DataKey::UserSettlements(user), &settlements);
+}
@@ -0,0 +1,191 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// Escrow keeps nothing in temporary storage: every record it holds backs
+// fractions or XLM in its custody, or a listing the seller has approved.
+pub use placement::*;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Proposed,  // Seller locked fractions, waiting for buyer to fund
+    Active,    // Buyer funded the full price, milestones in progress
+    Completed, // Every milestone released or refunded
+    Cancelled, // Seller withdrew before the buyer funded
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Released, // Payment sent to seller, matching fractions sent to buyer
+    Refunded, // Payment returned to buyer, matching fractions returned to seller
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Milestone {
+    pub amount: u128,  // XLM released to the seller for this stage
+    pub deadline: u64, // After this the stage can be refunded if not released
+    pub seller_confirmed: bool,
+    pub buyer_confirmed: bool,
+    pub disputed: bool, // Only the arbiter can settle a disputed milestone
+    pub status: MilestoneStatus,
+}
+
+// OTC deal settled in stages; fractions and XLM are held by the contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Escrow {
+    pub seller: Address,
+    pub buyer: Address,
+    pub arbiter: Option<Address>,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub total_price: u128,
+    pub milestones: Vec<Milestone>,
+    pub tokens_settled: u64, // Fractions already released or refunded
+    pub status: EscrowStatus,
+    pub created_at: u64,
+}
+
+// Sale the seller listed for one buyer with an arbiter; the fractions stay with
+// the seller, under an allowance to this contract, until the buyer pays
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitratedSale {
+    pub seller: Address,
+    pub buyer: Address,
+    pub arbiter: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SettlementStatus {
+    Pending,  // Held by the contract until the dispute window closes
+    Disputed, // Buyer flagged a problem, waiting for the arbiter
+    Released, // Fractions to buyer, XLM to seller
+    Refunded, // Fractions back to seller, XLM back to buyer
+}
+
+// Paid arbitrated sale whose fractions and payment are held by the contract
+// until the dispute window passes or the arbiter rules
+#[contracttype]
+#[derive(Clone)]
+pub struct Settlement {
+    pub sale: ArbitratedSale, // Terms the buyer accepted
+    pub settled_at: u64,
+    pub dispute_deadline: u64,
+    pub status: SettlementStatus,
+}
+
+// Terms of a sale that fixes the price now but settles on a later date
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForwardTerms {
+    pub settle_after: u64,   // finish_forward_sale fails before this time
+    pub buyer_deposit: u128, // XLM the buyer locks on commitment, credited against the price
+    pub cancel_cutoff: u64,  // Until then either committed party may cancel alone
+}
+
+// A forward sale and where the parties stand; the seller's fractions are held
+// by the contract from listing until settlement or cancellation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForwardSale {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub expires_at: u64,
+    pub terms: ForwardTerms,
+    pub committed: bool, // Buyer accepted the terms and locked the deposit
+    pub cancel_requested_by: Option<Address>, // Party waiting for a mutual cancellation
+}
+
+// Trading's `SaleDurationLimits`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleDurationLimits {
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+// Trading's `UpstreamPause`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpstreamPause {
+    pub since: u64,
+    pub retry_after: u64,
+}
+
+// Trading's `Conduct`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Conduct {
+    Cancellation,
+    Expiry,
+    Dispute,
+}
+
+// Storage keys for escrow contract
+#[contracttype(export = false)]
+pub enum DataKey {
+    // Core contract data
+    Admin,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
+    TradingContract,
+    FNFTContract, // Read from the trading contract at initialization
+    XLMContract,  // Read from the trading contract at initialization
+
+    // Milestone escrows
+    EscrowCounter,
+    Escrow(u32),          // escrow_id -> Escrow
+    UserEscrows(Address), // seller or buyer -> Vec<u32> (escrow_ids)
+
+    // Arbitrated sales, listed and then held during the dispute window
+    ArbitratedSale(Address, Address, u64), // (seller, buyer, asset_id) -> ArbitratedSale
+    SettlementCounter,
+    Settlement(u32),          // settlement_id -> Settlement
+    UserSettlements(Address), // seller or buyer -> Vec<u32> (settlement_ids)
+
+    // Forward (T+N) sales
+    ForwardSale(Address, Address, u64), // (seller, buyer, asset_id) -> ForwardSale
+
+    // Custody
+    HeldTokens(u64), // asset_id -> fractions held for escrows, settlements and forwards
+    HeldXlm,         // XLM held for escrows, settlements and forwards
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("escrow");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::TradingContract
+            | DataKey::FNFTContract
+            | DataKey::XLMContract
+            | DataKey::EscrowCounter
+            | DataKey::SettlementCounter
+            | DataKey::HeldXlm => Placement::Instance,
+            DataKey::Escrow(..)
+            | DataKey::UserEscrows(..)
+            | DataKey::ArbitratedSale(..)
+            | DataKey::Settlement(..)
+            | DataKey::UserSettlements(..)
+            | DataKey::ForwardSale(..)
+            | DataKey::HeldTokens(..) => Placement::Persistent,
+        }
+    }
+}
+
+// Constants
+pub const MAX_ESCROW_MILESTONES: u32 = 20;
+pub const DISPUTE_WINDOW: u64 = 259200; // 3 days after an arbitrated sale is paid
@@ -0,0 +1,744 @@
+#![cfg(test)]
+
+use crate::contract::*;
+use crate::errors::EscrowError;
+use crate::storage::{
+    EscrowStatus, ForwardTerms, MilestoneStatus, SettlementStatus, DISPUTE_WINDOW,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, LedgerInfo},
+    token, vec, Address, Env,
+};
+
+// Import the FNFT and trading contracts for testing
+mod fnft {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+mod trading {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/trading.wasm");
+}
+
+// Trading only takes a governance contract built for its fractcore as governor
+#[allow(clippy::too_many_arguments)]
+mod governance {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/governance.wasm");
+}
+
+const DAY: u64 = 86400;
+const WEEK: u64 = 7 * DAY;
+
+struct EscrowSetup {
+    env: Env,
+    admin: Address,
+    escrow_client: EscrowContractClient<'static>,
+    trading_client: trading::Client<'static>,
+    fnft_client: fnft::Client<'static>,
+    xlm_client: token::Client<'static>,
+    seller: Address,
+    buyer: Address,
+    arbiter: Address,
+    asset_id: u64,
+}
+
+fn setup() -> EscrowSetup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
+    let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
+
+    let xlm_sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let xlm_contract_id = xlm_sac.address();
+
+    let trading_contract_id = env.register(
+        trading::WASM,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            xlm_contract_id.clone(),
+        ),
+    );
+    let trading_client = trading::Client::new(&env, &trading_contract_id);
+
+    let escrow_contract_id =
+        env.register(EscrowContract, (admin.clone(), trading_contract_id.clone()));
+    let escrow_client = EscrowContractClient::new(&env, &escrow_contract_id);
+    trading_client.set_extension(&admin, &escrow_contract_id, &true);
+
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    token::StellarAssetClient::new(&env, &xlm_contract_id).mint(&buyer, &10000);
+
+    let xlm_client = token::Client::new(&env, &xlm_contract_id);
+
+    EscrowSetup {
+        env,
+        admin,
+        escrow_client,
+        trading_client,
+        fnft_client,
+        xlm_client,
+        seller,
+        buyer,
+        arbiter,
+        asset_id,
+    }
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    let current_ledger = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: current_ledger.timestamp + seconds,
+        ..current_ledger
+    });
+}
+
+/// 300 fractions for 3000 XLM in three stages of 1000, 1000 and 1000
+fn open_escrow(s: &EscrowSetup) -> u32 {
+    let now = s.env.ledger().timestamp();
+    let payments = vec![
+        &s.env,
+        (1000u128, now + DAY),
+        (1000u128, now + 2 * DAY),
+        (1000u128, now + 3 * DAY),
+    ];
+    s.escrow_client.create_escrow(
+        &s.seller,
+        &s.buyer,
+        &Some(s.arbiter.clone()),
+        &s.asset_id,
+        &300,
+        &payments,
+    )
+}
+
+#[test]
+fn test_initialization_reads_trading_configuration() {
+    let s = setup();
+
+    assert_eq!(
+        s.escrow_client.get_trading_contract(),
+        s.trading_client.address
+    );
+    assert!(s.escrow_client.get_init_config_hash().is_some());
+}
+
+#[test]
+fn test_escrow_locks_fractions_and_funds() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+    let contract_id = s.escrow_client.address.clone();
+
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 700);
+    assert_eq!(s.fnft_client.balance_of(&contract_id, &s.asset_id), 300);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 300);
+    assert_eq!(
+        s.escrow_client.get_escrow(&escrow_id).status,
+        EscrowStatus::Proposed
+    );
+
+    s.escrow_client.accept_escrow(&s.buyer, &escrow_id);
+
+    assert_eq!(s.xlm_client.balance(&s.buyer), 7000);
+    assert_eq!(s.xlm_client.balance(&contract_id), 3000);
+    assert_eq!(s.escrow_client.get_held_xlm(), 3000);
+    assert_eq!(
+        s.escrow_client.get_escrow(&escrow_id).status,
+        EscrowStatus::Active
+    );
+    assert_eq!(
+        s.escrow_client.get_user_escrows(&s.seller),
+        vec![&s.env, escrow_id]
+    );
+}
+
+#[test]
+fn test_milestone_released_after_both_confirm() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+    s.escrow_client.accept_escrow(&s.buyer, &escrow_id);
+
+    assert!(!s.escrow_client.confirm_milestone(&s.seller, &escrow_id, &0));
+    assert_eq!(s.xlm_client.balance(&s.seller), 0);
+
+    assert!(s.escrow_client.confirm_milestone(&s.buyer, &escrow_id, &0));
+    assert_eq!(s.xlm_client.balance(&s.seller), 1000);
+    assert_eq!(s.fnft_client.balance_of(&s.buyer, &s.asset_id), 100);
+
+    let escrow = s.escrow_client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_confirm_milestone(&s.buyer, &escrow_id, &0),
+        Err(Ok(EscrowError::MilestoneAlreadySettled))
+    );
+}
+
+#[test]
+fn test_arbiter_release_and_missed_milestone_refund() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+    s.escrow_client.accept_escrow(&s.buyer, &escrow_id);
+
+    // Arbiter alone can release a stage
+    assert!(s
+        .escrow_client
+        .confirm_milestone(&s.arbiter, &escrow_id, &0));
+
+    // Parties cannot refund before the deadline passes
+    assert_eq!(
+        s.escrow_client
+            .try_refund_milestone(&s.buyer, &escrow_id, &1),
+        Err(Ok(EscrowError::MilestoneNotDue))
+    );
+
+    advance_time(&s.env, 2 * DAY + 1);
+    s.escrow_client.refund_milestone(&s.buyer, &escrow_id, &1);
+
+    // Arbiter settles the dispute over the final stage in the buyer's favour
+    s.escrow_client.refund_milestone(&s.arbiter, &escrow_id, &2);
+
+    let escrow = s.escrow_client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Completed);
+    assert_eq!(escrow.tokens_settled, 300);
+
+    assert_eq!(s.xlm_client.balance(&s.seller), 1000);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 9000);
+    assert_eq!(s.fnft_client.balance_of(&s.buyer, &s.asset_id), 100);
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 900);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 0);
+    assert_eq!(s.escrow_client.get_held_xlm(), 0);
+}
+
+#[test]
+fn test_cancel_unfunded_escrow() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+
+    assert_eq!(
+        s.escrow_client.try_cancel_escrow(&s.buyer, &escrow_id),
+        Err(Ok(EscrowError::UnauthorizedSeller))
+    );
+    s.escrow_client.cancel_escrow(&s.seller, &escrow_id);
+
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 1000);
+    assert_eq!(
+        s.escrow_client.try_accept_escrow(&s.buyer, &escrow_id),
+        Err(Ok(EscrowError::InvalidEscrowStatus))
+    );
+}
+
+#[test]
+fn test_escrow_rejects_invalid_schedule_and_outsiders() {
+    let s = setup();
+    let now = s.env.ledger().timestamp();
+
+    // Deadlines out of order
+    let payments = vec![&s.env, (1000u128, now + 2 * DAY), (1000u128, now + DAY)];
+    assert_eq!(
+        s.escrow_client
+            .try_create_escrow(&s.seller, &s.buyer, &None, &s.asset_id, &300, &payments),
+        Err(Ok(EscrowError::InvalidMilestones))
+    );
+
+    // Arbiter must be independent
+    let payments = vec![&s.env, (1000u128, now + DAY)];
+    assert_eq!(
+        s.escrow_client.try_create_escrow(
+            &s.seller,
+            &s.buyer,
+            &Some(s.buyer.clone()),
+            &s.asset_id,
+            &300,
+            &payments
+        ),
+        Err(Ok(EscrowError::InvalidArbiter))
+    );
+
+    let escrow_id = open_escrow(&s);
+    s.escrow_client.accept_escrow(&s.buyer, &escrow_id);
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.escrow_client
+            .try_confirm_milestone(&outsider, &escrow_id, &0),
+        Err(Ok(EscrowError::NotEscrowParty))
+    );
+    assert!(matches!(
+        s.escrow_client.try_get_escrow(&99),
+        Err(Ok(EscrowError::EscrowNotFound))
+    ));
+}
+
+#[test]
+fn test_disputed_milestone_only_settled_by_arbiter() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+    s.escrow_client.accept_escrow(&s.buyer, &escrow_id);
+
+    s.escrow_client.confirm_milestone(&s.seller, &escrow_id, &0);
+    s.escrow_client.dispute_milestone(&s.buyer, &escrow_id, &0);
+    assert_eq!(s.trading_client.get_reputation(&s.seller).disputes, 1);
+
+    assert_eq!(
+        s.escrow_client
+            .try_confirm_milestone(&s.buyer, &escrow_id, &0),
+        Err(Ok(EscrowError::MilestoneDisputed))
+    );
+    advance_time(&s.env, DAY + 1);
+    assert_eq!(
+        s.escrow_client
+            .try_refund_milestone(&s.buyer, &escrow_id, &0),
+        Err(Ok(EscrowError::MilestoneDisputed))
+    );
+
+    s.escrow_client.refund_milestone(&s.arbiter, &escrow_id, &0);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 8000);
+}
+
+#[test]
+fn test_hooks_refuse_unregistered_extension() {
+    let s = setup();
+    s.trading_client
+        .set_extension(&s.admin, &s.escrow_client.address, &false);
+
+    // Drawing on the exit queue is the first hook an escrow calls
+    let now = s.env.ledger().timestamp();
+    let payments = vec![&s.env, (1000u128, now + DAY)];
+    assert_eq!(
+        s.escrow_client
+            .try_create_escrow(&s.seller, &s.buyer, &None, &s.asset_id, &300, &payments),
+        Err(Ok(EscrowError::Unauthorized))
+    );
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 1000);
+}
+
+#[test]
+fn test_paused_asset_blocks_escrows() {
+    let s = setup();
+    let escrow_id = open_escrow(&s);
+    s.trading_client
+        .set_asset_paused(&s.admin, &s.asset_id, &true);
+
+    assert_eq!(
+        s.escrow_client.try_accept_escrow(&s.buyer, &escrow_id),
+        Err(Ok(EscrowError::AssetPaused))
+    );
+    assert_eq!(
+        s.escrow_client.try_cancel_escrow(&s.seller, &escrow_id),
+        Err(Ok(EscrowError::AssetPaused))
+    );
+
+    // Fractcore's own pause reads as trading reports it
+    s.trading_client
+        .set_asset_paused(&s.admin, &s.asset_id, &false);
+    s.fnft_client.pause(&s.admin, &0);
+    assert_eq!(
+        s.escrow_client.try_accept_escrow(&s.buyer, &escrow_id),
+        Err(Ok(EscrowError::UpstreamPaused))
+    );
+}
+
+fn arbitrated_trade(s: &EscrowSetup) -> u32 {
+    s.escrow_client.confirm_sale_with_arbiter(
+        &s.seller,
+        &s.buyer,
+        &s.arbiter,
+        &s.asset_id,
+        &200,
+        &2000,
+        &DAY,
+    );
+    s.escrow_client
+        .finish_transaction(&s.buyer, &s.seller, &s.asset_id, &200, &2000)
+}
+
+#[test]
+fn test_arbitrated_trade_held_then_released() {
+    let s = setup();
+    let settlement_id = arbitrated_trade(&s);
+    let contract_id = s.escrow_client.address.clone();
+
+    // Both legs sit with the contract during the dispute window
+    assert_eq!(s.fnft_client.balance_of(&contract_id, &s.asset_id), 200);
+    assert_eq!(s.xlm_client.balance(&contract_id), 2000);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 200);
+    assert_eq!(s.escrow_client.get_held_xlm(), 2000);
+    assert_eq!(s.trading_client.get_trade_count(), 0);
+    assert_eq!(
+        s.escrow_client.get_user_settlements(&s.buyer),
+        vec![&s.env, settlement_id]
+    );
+    assert_eq!(
+        s.escrow_client.try_release_settlement(&settlement_id),
+        Err(Ok(EscrowError::DisputeWindowOpen))
+    );
+
+    advance_time(&s.env, DISPUTE_WINDOW + 1);
+    let trade_id = s.escrow_client.release_settlement(&settlement_id);
+
+    // The trade lands in the trading history like any other
+    assert_eq!(trade_id, 1);
+    assert_eq!(s.trading_client.get_trade_record(&trade_id).price, 2000);
+    assert_eq!(
+        s.trading_client.get_reputation(&s.seller).completed_trades,
+        1
+    );
+    assert_eq!(s.fnft_client.balance_of(&s.buyer, &s.asset_id), 200);
+    assert_eq!(s.xlm_client.balance(&s.seller), 2000);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 0);
+    assert_eq!(s.escrow_client.get_held_xlm(), 0);
+    assert_eq!(
+        s.escrow_client.get_settlement(&settlement_id).status,
+        SettlementStatus::Released
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_dispute_settlement(&s.buyer, &settlement_id),
+        Err(Ok(EscrowError::InvalidSettlementStatus))
+    );
+}
+
+#[test]
+fn test_arbiter_refunds_disputed_trade() {
+    let s = setup();
+    let settlement_id = arbitrated_trade(&s);
+
+    s.escrow_client.dispute_settlement(&s.buyer, &settlement_id);
+    assert_eq!(s.trading_client.get_reputation(&s.seller).disputes, 1);
+    assert_eq!(s.trading_client.get_reputation(&s.buyer).disputes, 1);
+
+    // Dispute blocks the permissionless release
+    advance_time(&s.env, DISPUTE_WINDOW + 1);
+    assert_eq!(
+        s.escrow_client.try_release_settlement(&settlement_id),
+        Err(Ok(EscrowError::InvalidSettlementStatus))
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_resolve_dispute(&s.seller, &settlement_id, &false),
+        Err(Ok(EscrowError::UnauthorizedArbiter))
+    );
+
+    assert_eq!(
+        s.escrow_client
+            .resolve_dispute(&s.arbiter, &settlement_id, &true),
+        0
+    );
+
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 1000);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 10000);
+    assert_eq!(s.trading_client.get_trade_count(), 0);
+    assert_eq!(
+        s.escrow_client.get_settlement(&settlement_id).status,
+        SettlementStatus::Refunded
+    );
+}
+
+#[test]
+fn test_dispute_after_window_rejected() {
+    let s = setup();
+    let settlement_id = arbitrated_trade(&s);
+
+    advance_time(&s.env, DISPUTE_WINDOW + 1);
+    assert_eq!(
+        s.escrow_client
+            .try_dispute_settlement(&s.buyer, &settlement_id),
+        Err(Ok(EscrowError::DisputeWindowClosed))
+    );
+}
+
+#[test]
+fn test_arbitrated_sale_withdrawn_and_expired() {
+    let s = setup();
+    let contract_id = s.escrow_client.address.clone();
+    s.escrow_client.confirm_sale_with_arbiter(
+        &s.seller,
+        &s.buyer,
+        &s.arbiter,
+        &s.asset_id,
+        &200,
+        &2000,
+        &DAY,
+    );
+    assert_eq!(
+        s.fnft_client
+            .allowance(&s.seller, &contract_id, &s.asset_id),
+        200
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_finish_transaction(&s.buyer, &s.seller, &s.asset_id, &200, &1999),
+        Err(Ok(EscrowError::PriceMismatch))
+    );
+
+    advance_time(&s.env, DAY + 1);
+    assert_eq!(
+        s.escrow_client
+            .try_finish_transaction(&s.buyer, &s.seller, &s.asset_id, &200, &2000),
+        Err(Ok(EscrowError::SaleExpired))
+    );
+
+    s.escrow_client
+        .withdraw_sale(&s.seller, &s.buyer, &s.asset_id);
+    assert_eq!(
+        s.fnft_client
+            .allowance(&s.seller, &contract_id, &s.asset_id),
+        0
+    );
+    assert!(s
+        .escrow_client
+        .get_arbitrated_sale(&s.seller, &s.buyer, &s.asset_id)
+        .is_none());
+    assert_eq!(
+        s.trading_client
+            .get_reputation(&s.seller)
+            .cancelled_proposals,
+        1
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_withdraw_sale(&s.seller, &s.buyer, &s.asset_id),
+        Err(Ok(EscrowError::SaleNotFound))
+    );
+}
+
+#[test]
+fn test_forward_sale_settles_after_date() {
+    let s = setup();
+    let now = s.env.ledger().timestamp();
+
+    // The settlement date must fall inside the sale's lifetime
+    let late = ForwardTerms {
+        settle_after: now + WEEK,
+        buyer_deposit: 1000,
+        cancel_cutoff: now,
+    };
+    assert_eq!(
+        s.escrow_client.try_confirm_forward_sale(
+            &s.seller,
+            &s.buyer,
+            &s.asset_id,
+            &100,
+            &5000,
+            &WEEK,
+            &late
+        ),
+        Err(Ok(EscrowError::InvalidDuration))
+    );
+
+    let terms = ForwardTerms {
+        settle_after: now + DAY,
+        buyer_deposit: 1000,
+        cancel_cutoff: now + 3600,
+    };
+    s.escrow_client.confirm_forward_sale(
+        &s.seller,
+        &s.buyer,
+        &s.asset_id,
+        &100,
+        &5000,
+        &WEEK,
+        &terms,
+    );
+
+    // The fractions are held by the contract, not left under an allowance
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 900);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 100);
+
+    s.escrow_client
+        .commit_forward_sale(&s.buyer, &s.seller, &s.asset_id);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 9000);
+    assert!(
+        s.escrow_client
+            .get_forward_sale(&s.seller, &s.buyer, &s.asset_id)
+            .unwrap()
+            .committed
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_withdraw_forward_sale(&s.seller, &s.buyer, &s.asset_id),
+        Err(Ok(EscrowError::InvalidSettlementStatus))
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_finish_forward_sale(&s.buyer, &s.seller, &s.asset_id),
+        Err(Ok(EscrowError::SettlementNotDue))
+    );
+
+    advance_time(&s.env, DAY);
+    assert_eq!(
+        s.escrow_client
+            .finish_forward_sale(&s.buyer, &s.seller, &s.asset_id),
+        1
+    );
+
+    assert_eq!(s.fnft_client.balance_of(&s.buyer, &s.asset_id), 100);
+    assert_eq!(s.xlm_client.balance(&s.seller), 5000);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 5000);
+    assert_eq!(s.escrow_client.get_held_tokens(&s.asset_id), 0);
+    assert_eq!(s.escrow_client.get_held_xlm(), 0);
+    assert!(s
+        .escrow_client
+        .get_forward_sale(&s.seller, &s.buyer, &s.asset_id)
+        .is_none());
+}
+
+#[test]
+fn test_forward_sale_cancellation_conditions() {
+    let s = setup();
+    let keeper = Address::generate(&s.env);
+
+    let list = |terms_from: u64| {
+        let terms = ForwardTerms {
+            settle_after: terms_from + DAY,
+            buyer_deposit: 1000,
+            cancel_cutoff: terms_from + 3600,
+        };
+        s.escrow_client.confirm_forward_sale(
+            &s.seller,
+            &s.buyer,
+            &s.asset_id,
+            &100,
+            &5000,
+            &WEEK,
+            &terms,
+        );
+        s.escrow_client
+            .commit_forward_sale(&s.buyer, &s.seller, &s.asset_id);
+    };
+
+    // Before the cutoff either party cancels alone and the deposit is refunded
+    list(s.env.ledger().timestamp());
+    assert!(s
+        .escrow_client
+        .cancel_forward_sale(&s.buyer, &s.seller, &s.buyer, &s.asset_id));
+    assert_eq!(s.xlm_client.balance(&s.buyer), 10000);
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 1000);
+
+    // After the cutoff both parties must ask
+    list(s.env.ledger().timestamp());
+    advance_time(&s.env, 7200);
+    assert!(!s
+        .escrow_client
+        .cancel_forward_sale(&s.seller, &s.seller, &s.buyer, &s.asset_id));
+    assert!(!s
+        .escrow_client
+        .cancel_forward_sale(&s.seller, &s.seller, &s.buyer, &s.asset_id));
+    assert!(s
+        .escrow_client
+        .cancel_forward_sale(&s.buyer, &s.seller, &s.buyer, &s.asset_id));
+    assert_eq!(s.xlm_client.balance(&s.buyer), 10000);
+
+    // A buyer who never settles forfeits the deposit once the sale lapses
+    list(s.env.ledger().timestamp());
+    assert_eq!(
+        s.escrow_client
+            .try_cleanup_forward_sale(&keeper, &s.seller, &s.buyer, &s.asset_id),
+        Err(Ok(EscrowError::SaleNotExpired))
+    );
+    advance_time(&s.env, WEEK + 1);
+    s.escrow_client
+        .cleanup_forward_sale(&keeper, &s.seller, &s.buyer, &s.asset_id);
+    assert_eq!(s.xlm_client.balance(&s.seller), 1000);
+    assert_eq!(s.xlm_client.balance(&s.buyer), 9000);
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 1000);
+    assert_eq!(s.escrow_client.get_held_xlm(), 0);
+    assert_eq!(s.trading_client.get_reputation(&s.seller).expired_unsold, 1);
+}
+
+#[test]
+fn test_forward_sale_fee_withheld_once_from_full_price() {
+    let s = setup();
+    let governor = s.env.register(
+        governance::WASM,
+        (
+            s.admin.clone(),
+            s.fnft_client.address.clone(),
+            Address::generate(&s.env),
+            60u32,
+            30u32,
+            7u32,
+        ),
+    );
+    let treasury = Address::generate(&s.env);
+    let now = s.env.ledger().timestamp();
+
+    s.trading_client
+        .set_governor(&s.admin, &Some(governor.clone()));
+    s.trading_client.apply_trading_config(
+        &governor,
+        &trading::TradingConfig {
+            fee: trading::TradingFee {
+                bps: 30,
+                recipient: treasury.clone(),
+            },
+            duration_limits: s.trading_client.get_sale_duration_limits(),
+        },
+    );
+
+    // Fees on the deposit and the balance separately would round down to 3 + 11
+    let terms = ForwardTerms {
+        settle_after: now + DAY,
+        buyer_deposit: 1300,
+        cancel_cutoff: now + 3600,
+    };
+    s.escrow_client.confirm_forward_sale(
+        &s.seller,
+        &s.buyer,
+        &s.asset_id,
+        &100,
+        &5200,
+        &WEEK,
+        &terms,
+    );
+    s.escrow_client
+        .commit_forward_sale(&s.buyer, &s.seller, &s.asset_id);
+    advance_time(&s.env, DAY);
+    s.escrow_client
+        .finish_forward_sale(&s.buyer, &s.seller, &s.asset_id);
+
+    assert_eq!(s.xlm_client.balance(&treasury), 15);
+    assert_eq!(s.xlm_client.balance(&s.seller), 5185);
+    assert_eq!(s.trading_client.get_trade_record(&1).settlement.fees, 15);
+    assert_eq!(s.escrow_client.get_held_xlm(), 0);
+    assert_eq!(s.xlm_client.balance(&s.escrow_client.address), 0);
+    assert_eq!(s.xlm_client.balance(&s.trading_client.address), 0);
+}
+
+#[test]
+fn test_rescue_leaves_escrowed_fractions() {
+    let s = setup();
+    open_escrow(&s);
+    let contract_id = s.escrow_client.address.clone();
+    let admin = s.admin.clone();
+
+    // A holder sends fractions straight to the contract by mistake
+    s.fnft_client
+        .transfer(&s.seller, &contract_id, &s.asset_id, &50);
+    assert_eq!(s.escrow_client.get_rescuable_tokens(&s.asset_id), 50);
+
+    assert_eq!(
+        s.escrow_client
+            .try_rescue_fractions(&s.buyer, &s.asset_id, &s.seller, &50),
+        Err(Ok(EscrowError::Unauthorized))
+    );
+    assert_eq!(
+        s.escrow_client
+            .try_rescue_fractions(&admin, &s.asset_id, &s.seller, &51),
+        Err(Ok(EscrowError::InsufficientBalance))
+    );
+
+    s.escrow_client
+        .rescue_fractions(&admin, &s.asset_id, &s.seller, &50);
+    assert_eq!(s.fnft_client.balance_of(&s.seller, &s.asset_id), 700);
+    assert_eq!(s.fnft_client.balance_of(&contract_id, &s.asset_id), 300);
+    assert_eq!(s.escrow_client.get_rescuable_tokens(&s.asset_id), 0);
+}
@@ -1,24 +1,85 @@
-use crate::methods::{admin, approval, balance, metadata, mint, ownership, transfer};
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use crate::errors::FractcoreError;
+use crate::methods::{
+    admin, admin_log, announcements, approval, archival, balance, basket, cap_table, categories,
+    checkpoints, comanagement, concentration, consolidation, issuance, lottery, metadata, mint,
+    ownership, recipients, recovery, split, transfer, transfer_lock,
+};
+use crate::storage::{
+    AdminLogEntry, AdminLogHead, Announcement, BalanceCheckpoint, BalanceStatus, BasketComponent,
+    CapTableSnapshot, CoManagement, Concentration, Cursor, EventLevel, GuardianConfig, LotteryDraw,
+    ManagerAction, ManagerProposal, PauseState, RecoveryRequest, RemainderPolicy, StorageEntry,
+    TransferLock, UriVersion,
+};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Symbol, Val, Vec};
 
 #[contract]
 pub struct FractionalizationContract;
 
 #[contractimpl]
 impl FractionalizationContract {
-    pub fn initialize(env: Env, admin: Address) {
-        mint::initialize(env, admin);
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(env: Env, admin: Address) -> Result<(), FractcoreError> {
+        mint::construct(env, admin)
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
+    pub fn initialize(env: Env, admin: Address) -> Result<(), FractcoreError> {
+        mint::initialize(env, admin)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        mint::get_init_config_hash(env)
     }
 
     pub fn mint(env: Env, to: Address, num_tokens: u64) -> u64 {
         mint::mint(env, to, num_tokens)
     }
 
+    /// Mint a new asset with a supply cap that no later issuance can exceed
+    pub fn mint_with_max_supply(env: Env, to: Address, num_tokens: u64, max_supply: u64) -> u64 {
+        mint::mint_with_max_supply(env, to, num_tokens, max_supply)
+    }
+
+    /// Mint a new asset listed under a marketplace category, e.g. `RealEstate` or `Art`
+    pub fn mint_with_category(env: Env, to: Address, num_tokens: u64, category: Symbol) -> u64 {
+        mint::mint_with_category(env, to, num_tokens, category)
+    }
+
+    /// Mint a new asset on behalf of an issuer, recorded as its creator (issuer co-signs)
+    pub fn mint_for(env: Env, creator: Address, to: Address, num_tokens: u64) -> u64 {
+        mint::mint_for(env, creator, to, num_tokens)
+    }
+
+    /// Create a new asset distributed to its initial holders in one transaction
+    pub fn mint_distributed(
+        env: Env,
+        recipients: Vec<Address>,
+        amounts: Vec<u64>,
+        uri: Option<String>,
+    ) -> u64 {
+        mint::mint_distributed(env, recipients, amounts, uri)
+    }
+
     /// Multiple recipient minting for existing asset
     pub fn mint_to(env: Env, asset_id: u64, recipients: Vec<Address>, amounts: Vec<u64>) {
         mint::mint_to(env, asset_id, recipients, amounts);
     }
 
+    /// Close issuance of an asset for good (asset manager, e.g. governance)
+    pub fn finalize_minting(env: Env, caller: Address, asset_id: u64) {
+        issuance::finalize_minting(env, caller, asset_id);
+    }
+
+    pub fn is_minting_finalized(env: Env, asset_id: u64) -> bool {
+        issuance::is_minting_finalized(env, asset_id)
+    }
+
+    /// Supply cap set at mint time, if any (rescaled by splits and consolidations)
+    pub fn max_supply(env: Env, asset_id: u64) -> Option<u64> {
+        issuance::max_supply(env, asset_id)
+    }
+
     pub fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64 {
         balance::balance_of(env, owner, asset_id)
     }
@@ -31,6 +92,16 @@ impl FractionalizationContract {
         balance::asset_supply(env, asset_id)
     }
 
+    /// Balance that distinguishes a missing record from a zero balance
+    pub fn balance_status(env: Env, owner: Address, asset_id: u64) -> BalanceStatus {
+        archival::balance_status(env, owner, asset_id)
+    }
+
+    /// Extend TTLs for a user's balances and ownership records
+    pub fn restore_entries(env: Env, owner: Address, entries: Vec<StorageEntry>) -> Vec<bool> {
+        archival::restore_entries(env, owner, entries)
+    }
+
     /// Simple transfer (owner transfers their own tokens)
     pub fn transfer(env: Env, from: Address, to: Address, asset_id: u64, amount: u64) {
         transfer::transfer(env, from, to, asset_id, amount);
@@ -59,6 +130,64 @@ impl FractionalizationContract {
         transfer::batch_transfer_from(env, operator, from, to, asset_ids, amounts);
     }
 
+    /// Send one asset from `from` to several recipients, e.g. a funding contract
+    /// paying out fractions it holds
+    pub fn batch_transfer_to(
+        env: Env,
+        operator: Address,
+        from: Address,
+        asset_id: u64,
+        recipients: Vec<Address>,
+        amounts: Vec<u64>,
+    ) {
+        transfer::batch_transfer_to(env, operator, from, asset_id, recipients, amounts);
+    }
+
+    /// Emergency stop of every fraction transfer (admin only); `retry_after` is
+    /// when the admin expects to lift it, 0 if unknown
+    pub fn pause(env: Env, caller: Address, retry_after: u64) {
+        admin::pause(env, caller, retry_after);
+    }
+
+    pub fn unpause(env: Env, caller: Address) {
+        admin::unpause(env, caller);
+    }
+
+    /// Current emergency pause, `None` while transfers run normally
+    pub fn pause_state(env: Env) -> Option<PauseState> {
+        admin::pause_state(env)
+    }
+
+    /// One page of the admin action log, oldest first; pages older than the
+    /// retention window come back empty and are covered by the head's hash
+    pub fn get_admin_log(env: Env, page: u32) -> Vec<AdminLogEntry> {
+        admin_log::get_admin_log(env, page)
+    }
+
+    pub fn get_admin_log_head(env: Env) -> AdminLogHead {
+        admin_log::get_admin_log_head(env)
+    }
+
+    /// Replace the addresses transfers refuse to send to (admin only); this
+    /// contract alone until set
+    pub fn set_denied_recipients(env: Env, caller: Address, recipients: Vec<Address>) {
+        recipients::set_denied_recipients(env, caller, recipients);
+    }
+
+    pub fn get_denied_recipients(env: Env) -> Vec<Address> {
+        recipients::get_denied_recipients(env)
+    }
+
+    /// Fractions of an asset held by this contract outside basket custody
+    pub fn rescuable(env: Env, asset_id: u64) -> u64 {
+        recipients::rescuable(env, asset_id)
+    }
+
+    /// Return fractions sent to this contract by mistake (admin only)
+    pub fn rescue_fractions(env: Env, caller: Address, asset_id: u64, to: Address, amount: u64) {
+        recipients::rescue_fractions(env, caller, asset_id, to, amount);
+    }
+
     pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
         approval::set_approval_for_all(env, owner, operator, approved);
     }
@@ -77,6 +206,16 @@ impl FractionalizationContract {
         approval::allowance(env, owner, operator, asset_id)
     }
 
+    /// Cap what an operator may move in total across all of the owner's assets (0 revokes)
+    pub fn set_operator_limit(env: Env, owner: Address, operator: Address, max_total_amount: u64) {
+        approval::set_operator_limit(env, owner, operator, max_total_amount);
+    }
+
+    /// Remaining cross-asset amount an operator may move for the owner
+    pub fn operator_limit(env: Env, owner: Address, operator: Address) -> u64 {
+        approval::operator_limit(env, owner, operator)
+    }
+
     pub fn get_asset_owner_count(env: Env, asset_id: u64) -> u32 {
         ownership::get_asset_owner_count(env, asset_id)
     }
@@ -89,10 +228,22 @@ impl FractionalizationContract {
         ownership::has_assets(env, owner, asset_id)
     }
 
+    /// Every owner of an asset on at most MAX_ASSET_OWNERS_PAGES pages (200 owners);
+    /// larger assets fail with `TooManyOwners` and are read page by page
     pub fn asset_owners(env: Env, asset_id: u64) -> Vec<Address> {
         ownership::asset_owners(env, asset_id)
     }
 
+    /// Up to `limit` owners (capped at MAX_BATCH_SIZE) from `cursor`, with the cursor of the next page
+    pub fn get_asset_owners_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<Address>, Option<Cursor>) {
+        ownership::asset_owners_page(env, asset_id, cursor, limit)
+    }
+
     pub fn owner_assets(env: Env, owner: Address) -> Vec<u64> {
         ownership::owner_assets(env, owner)
     }
@@ -109,10 +260,27 @@ impl FractionalizationContract {
         metadata::set_asset_uri(env, caller, asset_id, uri);
     }
 
+    /// The asset's own URI, falling back to the base URI template
     pub fn asset_uri(env: Env, asset_id: u64) -> Option<String> {
         metadata::asset_uri(env, asset_id)
     }
 
+    /// Soft-delete an asset's URI (admin or creator); it stays in the history
+    pub fn remove_asset_uri(env: Env, caller: Address, asset_id: u64) {
+        metadata::remove_asset_uri(env, caller, asset_id);
+    }
+
+    /// Restore an earlier URI version (admin, creator or asset manager)
+    pub fn revert_asset_uri(env: Env, caller: Address, asset_id: u64, version: u32) {
+        metadata::revert_asset_uri(env, caller, asset_id, version);
+    }
+
+    /// Recent URI versions, oldest first; the last one is current
+    pub fn get_uri_history(env: Env, asset_id: u64) -> Vec<UriVersion> {
+        metadata::get_uri_history(env, asset_id)
+    }
+
+    /// Set the contract metadata URI; `caller` must be the admin
     pub fn set_contract_uri(env: Env, caller: Address, uri: String) {
         metadata::set_contract_uri(env, caller, uri);
     }
@@ -121,6 +289,15 @@ impl FractionalizationContract {
         metadata::contract_uri(env)
     }
 
+    /// Set or remove the `{id}` URI template for assets without their own URI (admin only)
+    pub fn set_base_uri(env: Env, template: Option<String>) {
+        metadata::set_base_uri(env, template);
+    }
+
+    pub fn base_uri(env: Env) -> Option<String> {
+        metadata::base_uri(env)
+    }
+
     pub fn get_admin(env: Env) -> Address {
         admin::get_admin(env)
     }
@@ -129,8 +306,384 @@ impl FractionalizationContract {
         metadata::get_asset_creator(env, asset_id)
     }
 
-    /// Transfer admin role
+    /// Reassign an asset's creator (admin only), e.g. to migrate admin-attributed assets
+    pub fn set_asset_creator(env: Env, asset_id: u64, creator: Address) {
+        metadata::set_asset_creator(env, asset_id, creator);
+    }
+
+    /// Average balance held since `since` (ledger timestamp), from balance checkpoints
+    pub fn time_weighted_balance(env: Env, owner: Address, asset_id: u64, since: u64) -> u64 {
+        checkpoints::time_weighted_balance(env, owner, asset_id, since)
+    }
+
+    /// Balance at the close of the ledger stamped `timestamp`, from balance
+    /// checkpoints; None once the retained history no longer reaches back that far
+    pub fn balance_at(env: Env, owner: Address, asset_id: u64, timestamp: u64) -> Option<u64> {
+        checkpoints::balance_at(env, owner, asset_id, timestamp)
+    }
+
+    /// Retained balance history of a holder, oldest first
+    pub fn balance_checkpoints(env: Env, owner: Address, asset_id: u64) -> Vec<BalanceCheckpoint> {
+        checkpoints::balance_checkpoints(env, owner, asset_id)
+    }
+
+    /// Address allowed to split the asset (manager if set, otherwise creator)
+    pub fn asset_manager(env: Env, asset_id: u64) -> Address {
+        split::asset_manager(env, asset_id)
+    }
+
+    /// Delegate asset management, e.g. to the governance contract
+    pub fn set_asset_manager(env: Env, caller: Address, asset_id: u64, manager: Address) {
+        split::set_asset_manager(env, caller, asset_id, manager);
+    }
+
+    /// Shares of the largest holder that raise `conc_alrt` events when crossed (asset manager only)
+    pub fn set_concentration_thresholds(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        thresholds: Vec<u32>,
+    ) {
+        concentration::set_concentration_thresholds(env, caller, asset_id, thresholds);
+    }
+
+    /// Largest holders' share of the supply, from balances tracked on every change
+    pub fn get_concentration(env: Env, asset_id: u64) -> Concentration {
+        concentration::get_concentration(env, asset_id)
+    }
+
+    /// Multiply all balances and supply by `factor`; returns true when complete
+    pub fn split_asset(env: Env, caller: Address, asset_id: u64, factor: u64) -> bool {
+        split::split_asset(env, caller, asset_id, factor)
+    }
+
+    /// Rescale the next batch of holders of a pending split
+    pub fn continue_split(env: Env, asset_id: u64) -> bool {
+        split::continue_split(env, asset_id)
+    }
+
+    /// Product of all completed splits, for normalizing historical prices
+    pub fn split_factor(env: Env, asset_id: u64) -> u64 {
+        split::split_factor(env, asset_id)
+    }
+
+    pub fn split_in_progress(env: Env, asset_id: u64) -> bool {
+        split::split_in_progress(&env, asset_id)
+    }
+
+    /// Divide all balances by `factor`, handling remainders per `policy`; returns true when complete
+    pub fn consolidate_asset(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        factor: u64,
+        policy: RemainderPolicy,
+    ) -> bool {
+        consolidation::consolidate_asset(env, caller, asset_id, factor, policy)
+    }
+
+    /// Divide the next batch of holders of a pending consolidation
+    pub fn continue_consolidation(env: Env, asset_id: u64) -> bool {
+        consolidation::continue_consolidation(env, asset_id)
+    }
+
+    /// Product of all completed consolidations (historical prices scale by split / consolidation)
+    pub fn consolidation_factor(env: Env, asset_id: u64) -> u64 {
+        consolidation::consolidation_factor(env, asset_id)
+    }
+
+    pub fn consolidation_in_progress(env: Env, asset_id: u64) -> bool {
+        consolidation::consolidation_in_progress(&env, asset_id)
+    }
+
+    pub fn fraction_remainder(env: Env, owner: Address, asset_id: u64) -> u64 {
+        consolidation::fraction_remainder(env, owner, asset_id)
+    }
+
+    pub fn fraction_remainder_total(env: Env, asset_id: u64) -> u64 {
+        consolidation::fraction_remainder_total(env, asset_id)
+    }
+
+    /// Clear and return an owner's unclaimed remainder units (called by funding on payout)
+    pub fn claim_fraction_remainder(env: Env, owner: Address, asset_id: u64) -> u64 {
+        consolidation::claim_fraction_remainder(env, owner, asset_id)
+    }
+
+    /// Post a notice to the asset's holders (asset manager or admin); returns its id
+    pub fn post_announcement(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        title: String,
+        content_hash: BytesN<32>,
+        uri: String,
+    ) -> u32 {
+        announcements::post_announcement(env, caller, asset_id, title, content_hash, uri)
+    }
+
+    /// Most recent announcements for an asset, newest first
+    pub fn get_announcements(env: Env, asset_id: u64) -> Vec<Announcement> {
+        announcements::get_announcements(env, asset_id)
+    }
+
+    pub fn announcement_count(env: Env, asset_id: u64) -> u32 {
+        announcements::announcement_count(env, asset_id)
+    }
+
+    /// Create a basket asset backed by fractions of other assets (admin only)
+    pub fn create_basket(env: Env, components: Vec<BasketComponent>) -> u64 {
+        basket::create_basket(env, components)
+    }
+
+    /// Deposit component fractions and receive basket tokens
+    pub fn deposit_to_basket(env: Env, owner: Address, basket_id: u64, units: u64) {
+        basket::deposit_to_basket(env, owner, basket_id, units)
+    }
+
+    /// Burn basket tokens for a pro-rata share of the components
+    pub fn redeem_from_basket(env: Env, owner: Address, basket_id: u64, units: u64) {
+        basket::redeem_from_basket(env, owner, basket_id, units)
+    }
+
+    pub fn get_basket(env: Env, basket_id: u64) -> Vec<BasketComponent> {
+        basket::get_basket(env, basket_id)
+    }
+
+    pub fn is_basket(env: Env, asset_id: u64) -> bool {
+        basket::is_basket(env, asset_id)
+    }
+
+    /// Fractions of each component backing the basket, in component order
+    pub fn basket_holdings(env: Env, basket_id: u64) -> Vec<u64> {
+        basket::basket_holdings(env, basket_id)
+    }
+
+    /// Freeze holders and balances for a cap table export (asset manager only)
+    pub fn create_cap_table_snapshot(env: Env, caller: Address, asset_id: u64) -> u32 {
+        cap_table::create_cap_table_snapshot(env, caller, asset_id)
+    }
+
+    pub fn get_cap_table_snapshot(env: Env, asset_id: u64) -> Option<CapTableSnapshot> {
+        cap_table::get_cap_table_snapshot(env, asset_id)
+    }
+
+    /// (owner, balance, basis points of supply) of the live snapshot from `cursor`,
+    /// with the cursor of the next page
+    pub fn export_cap_table(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<(Address, u64, u32)>, Option<Cursor>) {
+        cap_table::export_cap_table(env, asset_id, cursor, limit)
+    }
+
+    /// Commit to sha256(seed) for a holdings-weighted draw (asset manager); returns the draw id
+    pub fn commit_draw(env: Env, caller: Address, asset_id: u64, commitment: BytesN<32>) -> u32 {
+        lottery::commit_draw(env, caller, asset_id, commitment)
+    }
+
+    /// Reveal the seed in a later ledger and pick a holder weighted by balance
+    pub fn draw_winner(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        draw_id: u32,
+        seed: Bytes,
+    ) -> Address {
+        lottery::draw_winner(env, caller, asset_id, draw_id, seed)
+    }
+
+    pub fn get_draw(env: Env, asset_id: u64, draw_id: u32) -> Option<LotteryDraw> {
+        lottery::get_draw(env, asset_id, draw_id)
+    }
+
+    pub fn draw_count(env: Env, asset_id: u64) -> u32 {
+        lottery::draw_count(env, asset_id)
+    }
+
+    /// Change or clear an asset's marketplace category (asset manager only)
+    pub fn set_asset_category(env: Env, caller: Address, asset_id: u64, category: Option<Symbol>) {
+        categories::set_asset_category(env, caller, asset_id, category)
+    }
+
+    pub fn asset_category(env: Env, asset_id: u64) -> Option<Symbol> {
+        categories::asset_category(env, asset_id)
+    }
+
+    /// Up to 50 asset ids of a category per page, in no particular order
+    pub fn assets_by_category(env: Env, category: Symbol, page: u32) -> Vec<u64> {
+        categories::assets_by_category(env, category, page)
+    }
+
+    pub fn category_asset_count(env: Env, category: Symbol) -> u32 {
+        categories::category_asset_count(env, category)
+    }
+
+    /// Permit a contract (e.g. governance) to lock transfers during polls (asset manager only)
+    pub fn set_transfer_lock_authority(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        authority: Option<Address>,
+    ) {
+        transfer_lock::set_transfer_lock_authority(env, caller, asset_id, authority)
+    }
+
+    pub fn transfer_lock_authority(env: Env, asset_id: u64) -> Option<Address> {
+        transfer_lock::transfer_lock_authority(env, asset_id)
+    }
+
+    /// Lock authority freezes transfers for all holders, or for holders it adds, until `until`
+    pub fn open_transfer_lock(
+        env: Env,
+        authority: Address,
+        asset_id: u64,
+        lock_id: u32,
+        all_holders: bool,
+        until: u64,
+    ) {
+        transfer_lock::open_transfer_lock(env, authority, asset_id, lock_id, all_holders, until)
+    }
+
+    /// Lock authority adds a holder (e.g. a voter) to a holder-scoped lock
+    pub fn lock_holder_transfers(
+        env: Env,
+        authority: Address,
+        asset_id: u64,
+        lock_id: u32,
+        holder: Address,
+    ) {
+        transfer_lock::lock_holder_transfers(env, authority, asset_id, lock_id, holder)
+    }
+
+    /// Lock authority lifts a lock early, e.g. once the poll is finalized
+    pub fn release_transfer_lock(env: Env, authority: Address, asset_id: u64, lock_id: u32) {
+        transfer_lock::release_transfer_lock(env, authority, asset_id, lock_id)
+    }
+
+    pub fn get_transfer_lock(env: Env, asset_id: u64, lock_id: u32) -> Option<TransferLock> {
+        transfer_lock::get_transfer_lock(env, asset_id, lock_id)
+    }
+
+    pub fn is_transfer_locked(env: Env, holder: Address, asset_id: u64) -> bool {
+        transfer_lock::is_transfer_locked(env, holder, asset_id)
+    }
+
+    /// Put an asset under co-management (asset manager only); sensitive changes
+    /// then need `quorum` co-managers to confirm them
+    pub fn set_co_managers(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        managers: Vec<Address>,
+        quorum: u32,
+    ) {
+        comanagement::set_co_managers(env, caller, asset_id, managers, quorum)
+    }
+
+    pub fn get_co_managers(env: Env, asset_id: u64) -> Option<CoManagement> {
+        comanagement::get_co_managers(env, asset_id)
+    }
+
+    /// Co-manager proposes a URI, constraint, halt or co-manager change; returns its id
+    pub fn propose_manager_action(
+        env: Env,
+        manager: Address,
+        asset_id: u64,
+        action: ManagerAction,
+    ) -> u32 {
+        comanagement::propose_manager_action(env, manager, asset_id, action)
+    }
+
+    /// Co-manager confirms a pending action; returns true once it executed
+    pub fn confirm_manager_action(
+        env: Env,
+        manager: Address,
+        asset_id: u64,
+        action_id: u32,
+    ) -> bool {
+        comanagement::confirm_manager_action(env, manager, asset_id, action_id)
+    }
+
+    /// Proposer withdraws a pending action
+    pub fn cancel_manager_action(env: Env, manager: Address, asset_id: u64, action_id: u32) {
+        comanagement::cancel_manager_action(env, manager, asset_id, action_id)
+    }
+
+    pub fn get_manager_action(env: Env, asset_id: u64, action_id: u32) -> Option<ManagerProposal> {
+        comanagement::get_manager_action(env, asset_id, action_id)
+    }
+
+    pub fn get_pending_manager_actions(env: Env, asset_id: u64) -> Vec<ManagerProposal> {
+        comanagement::get_pending_manager_actions(env, asset_id)
+    }
+
+    /// Opt into social recovery; an empty guardian list opts out
+    pub fn set_guardians(env: Env, holder: Address, guardians: Vec<Address>, threshold: u32) {
+        recovery::set_guardians(env, holder, guardians, threshold)
+    }
+
+    pub fn get_guardians(env: Env, holder: Address) -> Option<GuardianConfig> {
+        recovery::get_guardians(env, holder)
+    }
+
+    /// Guardian proposes moving a holder's balances to a new address
+    pub fn propose_recovery(env: Env, guardian: Address, holder: Address, new_address: Address) {
+        recovery::propose_recovery(env, guardian, holder, new_address)
+    }
+
+    pub fn approve_recovery(env: Env, guardian: Address, holder: Address) {
+        recovery::approve_recovery(env, guardian, holder)
+    }
+
+    /// Holder rejects a pending recovery during the challenge delay
+    pub fn cancel_recovery(env: Env, holder: Address) {
+        recovery::cancel_recovery(env, holder)
+    }
+
+    /// Migrate every balance once the recovery is approved and the delay has passed
+    pub fn execute_recovery(env: Env, holder: Address) -> u32 {
+        recovery::execute_recovery(env, holder)
+    }
+
+    pub fn get_recovery(env: Env, holder: Address) -> Option<RecoveryRequest> {
+        recovery::get_recovery(env, holder)
+    }
+
+    /// Choose how many events batch mints publish: per recipient (Full), one
+    /// aggregate per call (Summary) or only the top-level action (Minimal)
+    pub fn set_event_level(env: Env, level: EventLevel) {
+        admin::set_event_level(env, level);
+    }
+
+    pub fn event_level(env: Env) -> EventLevel {
+        admin::event_level(&env)
+    }
+
+    /// Transfer admin role; `current_admin` must be the admin
     pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
         admin::transfer_admin(env, current_admin, new_admin);
     }
+
+    /// Name the governance contract that upgrades are voted through (admin for
+    /// the first hand-over, then the current governance contract)
+    pub fn set_governance_contract(env: Env, caller: Address, governance: Address) {
+        admin::set_governance_contract(env, caller, governance);
+    }
+
+    pub fn get_governance_contract(env: Env) -> Option<Address> {
+        admin::get_governance_contract(env)
+    }
+
+    /// Replace the contract code with an uploaded WASM (governance contract only)
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        admin::upgrade(env, caller, new_wasm_hash);
+    }
+
+    /// Move entries written before namespacing under their namespace, in
+    /// batches; `last` closes the migration (admin only)
+    pub fn migrate_storage(env: Env, keys: Vec<Val>, ext_keys: Vec<Val>, last: bool) -> u32 {
+        admin::migrate_storage(env, keys, ext_keys, last)
+    }
 }
@@ -0,0 +1,27 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by fractcore entrypoints
+///
+/// Initialization returns typed errors so deployment pipelines can tell an
+/// already-initialized contract apart from a failure, mint paths raise them
+/// for supply cap breaches, and admin entrypoints that name their caller raise
+/// `Unauthorized` when it does not hold the role, transfers raise `Paused`
+/// during an emergency pause so dependent contracts can recognize it, and
+/// `asset_owners` raises `TooManyOwners` rather than return a partial list,
+/// batch entrypoints raise `BatchTooLarge` above their bound before writing
+/// anything, and `set_governance_contract` raises `NotGovernanceContract` for
+/// a target that is not a governance contract built for this fractcore; other
+/// entrypoints panic with a message.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FractcoreError {
+    AlreadyInitialized = 1,
+    MintingFinalized = 2,
+    MaxSupplyExceeded = 3,
+    Unauthorized = 4,
+    Paused = 5,
+    TooManyOwners = 6,
+    BatchTooLarge = 7,
+    NotGovernanceContract = 8,
+}
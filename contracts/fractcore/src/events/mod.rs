@@ -1,4 +1,8 @@
-use soroban_sdk::{symbol_short, Address, Env, String};
+use crate::storage::{
+    AdminLogEntry, Announcement, BasketComponent, CapTableSnapshot, EventLevel, PauseState,
+    RemainderPolicy,
+};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
 pub fn emit_init(env: &Env, admin: Address) {
     env.events().publish((symbol_short!("init"),), (admin,));
@@ -14,6 +18,31 @@ pub fn emit_mint_to(env: &Env, recipient: Address, asset_id: u64, amount: u64) {
         .publish((symbol_short!("mint_to"),), (recipient, asset_id, amount));
 }
 
+/// `mint_to` batch below `EventLevel::Full`, replacing the per-recipient events
+pub fn emit_mint_summary(env: &Env, asset_id: u64, total_minted: u64, recipients: u32) {
+    env.events().publish(
+        (symbol_short!("mint_sum"),),
+        (asset_id, total_minted, recipients),
+    );
+}
+
+pub fn emit_mint_distributed(env: &Env, asset_id: u64, total_supply: u64, recipients: u32) {
+    env.events().publish(
+        (symbol_short!("mint_dist"),),
+        (asset_id, total_supply, recipients),
+    );
+}
+
+pub fn emit_max_supply(env: &Env, asset_id: u64, max_supply: u64) {
+    env.events()
+        .publish((symbol_short!("max_sup"),), (asset_id, max_supply));
+}
+
+pub fn emit_minting_finalized(env: &Env, asset_id: u64, caller: Address) {
+    env.events()
+        .publish((symbol_short!("mint_fin"),), (asset_id, caller));
+}
+
 pub fn emit_transfer(env: &Env, from: Address, to: Address, asset_id: u64, amount: u64) {
     env.events()
         .publish((symbol_short!("transfer"),), (from, to, asset_id, amount));
@@ -31,12 +60,270 @@ pub fn emit_approve(env: &Env, owner: Address, operator: Address, asset_id: u64,
     );
 }
 
+pub fn emit_operator_limit(env: &Env, owner: Address, operator: Address, amount: u64) {
+    env.events()
+        .publish((symbol_short!("op_limit"),), (owner, operator, amount));
+}
+
 pub fn emit_uri_update(env: &Env, asset_id: u64, uri: String) {
     env.events()
         .publish((symbol_short!("uri"),), (asset_id, uri));
 }
 
+/// Asset URI removed; earlier versions stay in the history
+pub fn emit_uri_removed(env: &Env, asset_id: u64, version: u32) {
+    env.events()
+        .publish((symbol_short!("uri_del"),), (asset_id, version));
+}
+
+/// Asset URI restored from `restored_version`, recorded as `version`
+pub fn emit_uri_reverted(
+    env: &Env,
+    asset_id: u64,
+    restored_version: u32,
+    version: u32,
+    uri: Option<String>,
+) {
+    env.events().publish(
+        (symbol_short!("uri_rvt"),),
+        (asset_id, restored_version, version, uri),
+    );
+}
+
+/// Base URI template set (None = removed)
+pub fn emit_base_uri(env: &Env, template: Option<String>) {
+    env.events().publish((symbol_short!("base_uri"),), template);
+}
+
 pub fn emit_admin_transfer(env: &Env, current_admin: Address, new_admin: Address) {
     env.events()
         .publish((symbol_short!("admin"),), (current_admin, new_admin));
 }
+
+/// Governance contract that upgrades are voted through
+pub fn emit_governance(env: &Env, governance: Address) {
+    env.events()
+        .publish((symbol_short!("governor"),), governance);
+}
+
+/// Code replaced through governance (old hash None = code deployed at creation)
+pub fn emit_upgraded(env: &Env, old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: BytesN<32>) {
+    env.events()
+        .publish((symbol_short!("upgraded"),), (old_wasm_hash, new_wasm_hash));
+}
+
+pub fn emit_creator_update(env: &Env, asset_id: u64, creator: Address) {
+    env.events()
+        .publish((symbol_short!("creator"),), (asset_id, creator));
+}
+
+pub fn emit_manager_update(env: &Env, asset_id: u64, manager: Address) {
+    env.events()
+        .publish((symbol_short!("manager"),), (asset_id, manager));
+}
+
+pub fn emit_split(env: &Env, asset_id: u64, factor: u64, new_supply: u64) {
+    env.events()
+        .publish((symbol_short!("split"),), (asset_id, factor, new_supply));
+}
+
+pub fn emit_split_done(env: &Env, asset_id: u64, factor: u64, cumulative_factor: u64) {
+    env.events().publish(
+        (symbol_short!("split_end"),),
+        (asset_id, factor, cumulative_factor),
+    );
+}
+
+pub fn emit_consolidation(env: &Env, asset_id: u64, factor: u64, policy: RemainderPolicy) {
+    env.events()
+        .publish((symbol_short!("consol"),), (asset_id, factor, policy));
+}
+
+pub fn emit_consolidation_done(env: &Env, asset_id: u64, factor: u64, new_supply: u64) {
+    env.events()
+        .publish((symbol_short!("cons_end"),), (asset_id, factor, new_supply));
+}
+
+pub fn emit_remainder_claim(env: &Env, owner: Address, asset_id: u64, units: u64) {
+    env.events()
+        .publish((symbol_short!("rem_claim"),), (owner, asset_id, units));
+}
+
+pub fn emit_announcement(env: &Env, asset_id: u64, announcement: Announcement) {
+    env.events()
+        .publish((symbol_short!("announce"), asset_id), announcement);
+}
+
+pub fn emit_basket_created(env: &Env, basket_id: u64, components: Vec<BasketComponent>) {
+    env.events()
+        .publish((symbol_short!("basket"), basket_id), components);
+}
+
+pub fn emit_basket_deposit(env: &Env, owner: Address, basket_id: u64, units: u64) {
+    env.events()
+        .publish((symbol_short!("bsk_dep"), basket_id), (owner, units));
+}
+
+pub fn emit_basket_redeem(env: &Env, owner: Address, basket_id: u64, units: u64) {
+    env.events()
+        .publish((symbol_short!("bsk_redm"), basket_id), (owner, units));
+}
+
+pub fn emit_guardians(env: &Env, holder: Address, guardian_count: u32, threshold: u32) {
+    env.events().publish(
+        (symbol_short!("guardians"), holder),
+        (guardian_count, threshold),
+    );
+}
+
+pub fn emit_recovery_proposed(env: &Env, holder: Address, guardian: Address, new_address: Address) {
+    env.events()
+        .publish((symbol_short!("rec_prop"), holder), (guardian, new_address));
+}
+
+pub fn emit_recovery_approved(env: &Env, holder: Address, guardian: Address, approvals: u32) {
+    env.events()
+        .publish((symbol_short!("rec_appr"), holder), (guardian, approvals));
+}
+
+pub fn emit_recovery_cancelled(env: &Env, holder: Address) {
+    env.events()
+        .publish((symbol_short!("rec_cncl"), holder), ());
+}
+
+pub fn emit_recovered(env: &Env, holder: Address, new_address: Address, assets: u32) {
+    env.events()
+        .publish((symbol_short!("recovered"), holder), (new_address, assets));
+}
+
+pub fn emit_cap_table_snapshot(env: &Env, snapshot: &CapTableSnapshot) {
+    env.events().publish(
+        (symbol_short!("cap_snap"), snapshot.asset_id),
+        (snapshot.id, snapshot.supply, snapshot.owner_count),
+    );
+}
+
+pub fn emit_draw_committed(env: &Env, asset_id: u64, draw_id: u32, commitment: &BytesN<32>) {
+    env.events().publish(
+        (symbol_short!("draw_cmt"), asset_id),
+        (draw_id, commitment.clone()),
+    );
+}
+
+pub fn emit_draw_winner(env: &Env, asset_id: u64, draw_id: u32, winner: Address, ticket: u64) {
+    env.events().publish(
+        (symbol_short!("draw_win"), asset_id),
+        (draw_id, winner, ticket),
+    );
+}
+
+pub fn emit_lock_authority(env: &Env, asset_id: u64, authority: Option<Address>) {
+    env.events()
+        .publish((symbol_short!("lock_auth"), asset_id), authority);
+}
+
+pub fn emit_lock_opened(env: &Env, asset_id: u64, lock_id: u32, all_holders: bool, until: u64) {
+    env.events().publish(
+        (symbol_short!("lock_open"), asset_id, lock_id),
+        (all_holders, until),
+    );
+}
+
+pub fn emit_holder_locked(env: &Env, asset_id: u64, lock_id: u32, holder: Address) {
+    env.events()
+        .publish((symbol_short!("lock_hold"), asset_id, lock_id), holder);
+}
+
+pub fn emit_lock_released(env: &Env, asset_id: u64, lock_id: u32) {
+    env.events()
+        .publish((symbol_short!("lock_free"), asset_id, lock_id), ());
+}
+
+/// The largest holder's share crossed a threshold, upward when `rising`
+pub fn emit_concentration_alert(
+    env: &Env,
+    asset_id: u64,
+    holder: Option<Address>,
+    share_bps: u32,
+    threshold_bps: u32,
+    rising: bool,
+) {
+    env.events().publish(
+        (symbol_short!("conc_alrt"), asset_id),
+        (holder, share_bps, threshold_bps, rising),
+    );
+}
+
+pub fn emit_concentration_thresholds(env: &Env, asset_id: u64, thresholds: Vec<u32>) {
+    env.events()
+        .publish((symbol_short!("conc_thr"), asset_id), thresholds);
+}
+
+pub fn emit_event_level(env: &Env, level: EventLevel) {
+    env.events().publish((symbol_short!("evt_level"),), level);
+}
+
+pub fn emit_co_managers(env: &Env, asset_id: u64, manager_count: u32, quorum: u32) {
+    env.events().publish(
+        (symbol_short!("co_mgrs"), asset_id),
+        (manager_count, quorum),
+    );
+}
+
+pub fn emit_manager_action_proposed(env: &Env, asset_id: u64, action_id: u32, manager: Address) {
+    env.events()
+        .publish((symbol_short!("mgr_prop"), asset_id), (action_id, manager));
+}
+
+pub fn emit_manager_action_confirmed(
+    env: &Env,
+    asset_id: u64,
+    action_id: u32,
+    manager: Address,
+    confirmations: u32,
+) {
+    env.events().publish(
+        (symbol_short!("mgr_conf"), asset_id),
+        (action_id, manager, confirmations),
+    );
+}
+
+pub fn emit_manager_action_executed(env: &Env, asset_id: u64, action_id: u32) {
+    env.events()
+        .publish((symbol_short!("mgr_exec"), asset_id), action_id);
+}
+
+pub fn emit_manager_action_cancelled(env: &Env, asset_id: u64, action_id: u32) {
+    env.events()
+        .publish((symbol_short!("mgr_cncl"), asset_id), action_id);
+}
+
+pub fn emit_transfers_halted(env: &Env, asset_id: u64, halted: bool) {
+    env.events()
+        .publish((symbol_short!("halted"), asset_id), halted);
+}
+
+pub fn emit_category(env: &Env, asset_id: u64, category: Option<Symbol>) {
+    env.events()
+        .publish((symbol_short!("category"), asset_id), category);
+}
+
+pub fn emit_paused(env: &Env, state: Option<PauseState>) {
+    env.events().publish((symbol_short!("paused"),), state);
+}
+
+pub fn emit_denied_recipients(env: &Env, recipients: Vec<Address>) {
+    env.events()
+        .publish((symbol_short!("rcpt_deny"),), recipients);
+}
+
+pub fn emit_rescued(env: &Env, asset_id: u64, to: Address, amount: u64) {
+    env.events()
+        .publish((symbol_short!("rescued"), asset_id), (to, amount));
+}
+
+/// Entry `index` of the admin action log, so archivers can keep entries after they are folded
+pub fn emit_admin_log(env: &Env, index: u32, entry: AdminLogEntry) {
+    env.events()
+        .publish((symbol_short!("admin_log"), index), entry);
+}
@@ -1,21 +1,176 @@
+use crate::errors::FractcoreError;
 use crate::events;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::methods::admin_log;
+use crate::storage::{self, DataKey, DataKeyExt, EventLevel, PauseState, PlacedKey};
+use soroban_sdk::{contractclient, panic_with_error, Address, BytesN, Env, Val, Vec};
 
-pub fn require_admin_auth(env: Env) {
-    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+// Governance contract named for upgrades; only the generated client is used
+#[allow(dead_code)]
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn get_fractcore_contract(env: Env) -> Address;
+}
+
+/// Authorize the stored admin and return it, for the admin action log
+pub fn require_admin_auth(env: Env) -> Address {
+    let admin: Address = storage::get(&env, &DataKey::Admin).unwrap();
     admin.require_auth();
+    admin
 }
 
 pub fn get_admin(env: Env) -> Address {
-    env.storage().instance().get(&DataKey::Admin).unwrap()
+    storage::get(&env, &DataKey::Admin).unwrap()
+}
+
+/// Entrypoints that name their caller authorize that address alone, then check
+/// it holds the role, so the signer and the checked address are the same
+pub fn require_caller(env: &Env, caller: &Address, authority: Option<Address>) {
+    caller.require_auth();
+
+    if authority.as_ref() != Some(caller) {
+        panic_with_error!(env, FractcoreError::Unauthorized);
+    }
+}
+
+pub fn require_admin_caller(env: &Env, caller: &Address) {
+    require_caller(env, caller, Some(get_admin(env.clone())));
 }
 
 pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
-    require_admin_auth(env.clone());
-    current_admin.require_auth();
+    require_admin_caller(&env, &current_admin);
+    admin_log::record(&env, &current_admin, "transfer_admin", (new_admin.clone(),));
 
-    env.storage().instance().set(&DataKey::Admin, &new_admin);
+    storage::set(&env, &DataKey::Admin, &new_admin);
 
     events::emit_admin_transfer(&env, current_admin, new_admin);
 }
+
+/// Name the governance contract that upgrades are voted through. The admin
+/// makes the first hand-over; after that only the current governance contract
+/// can name its successor. The target must be a contract that answers
+/// `get_fractcore_contract` with this contract, so the admin cannot hand
+/// upgrades to an account of its own.
+pub fn set_governance_contract(env: Env, caller: Address, governance: Address) {
+    let authority = get_governance_contract(env.clone()).unwrap_or_else(|| get_admin(env.clone()));
+    require_caller(&env, &caller, Some(authority));
+    let governed = GovernanceClient::new(&env, &governance).try_get_fractcore_contract();
+    if !matches!(governed, Ok(Ok(fractcore)) if fractcore == env.current_contract_address()) {
+        panic_with_error!(&env, FractcoreError::NotGovernanceContract);
+    }
+    admin_log::record(
+        &env,
+        &caller,
+        "set_governance_contract",
+        (governance.clone(),),
+    );
+
+    storage::set(&env, &DataKeyExt::GovernanceContract, &governance);
+
+    events::emit_governance(&env, governance);
+}
+
+pub fn get_governance_contract(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKeyExt::GovernanceContract)
+}
+
+/// Replace this contract's code with an uploaded WASM (governance contract only)
+pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+    require_caller(&env, &caller, get_governance_contract(env.clone()));
+    admin_log::record(&env, &caller, "upgrade", (new_wasm_hash.clone(),));
+
+    let old_wasm_hash: Option<BytesN<32>> = storage::get(&env, &DataKeyExt::WasmHash);
+    storage::set(&env, &DataKeyExt::WasmHash, &new_wasm_hash);
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    events::emit_upgraded(&env, old_wasm_hash, new_wasm_hash);
+}
+
+/// Move entries an upgraded deployment wrote before namespacing from their
+/// bare keys under their namespace (admin only). The admin lists the keys,
+/// `DataKey` ones in `keys` and `DataKeyExt` ones in `ext_keys`, over as many
+/// calls as the budget needs, and sets `last` on the final call
+/// to mark the contract namespaced, after which this entrypoint is closed.
+/// Until its own entry has moved, the admin is read from its bare key.
+/// A call takes at most MAX_MIGRATE_BATCH keys. Returns the number of entries moved.
+pub fn migrate_storage(env: Env, keys: Vec<Val>, ext_keys: Vec<Val>, last: bool) -> u32 {
+    let admin: Address = storage::get(&env, &DataKey::Admin)
+        .or_else(|| storage::get_bare(&env, &DataKey::Admin))
+        .unwrap();
+    admin.require_auth();
+    if storage::is_namespaced(&env) {
+        panic_with_error!(&env, FractcoreError::AlreadyInitialized);
+    }
+    if keys.len() + ext_keys.len() > storage::MAX_MIGRATE_BATCH {
+        panic_with_error!(&env, FractcoreError::BatchTooLarge);
+    }
+
+    let mut moved = 0;
+    for key in keys.iter() {
+        moved += storage::migrate(&env, DataKey::NAMESPACE, key) as u32;
+    }
+    for key in ext_keys.iter() {
+        moved += storage::migrate(&env, DataKeyExt::NAMESPACE, key) as u32;
+    }
+    if last {
+        // Logged by the final batch alone: until then the log's own entries may
+        // still sit under their bare keys, and moving them would drop the entry
+        admin_log::record(
+            &env,
+            &admin,
+            "migrate_storage",
+            (keys.len(), ext_keys.len()),
+        );
+        storage::mark_namespaced(&env);
+    }
+    moved
+}
+
+/// Set how many events batch mints publish (admin only)
+pub fn set_event_level(env: Env, level: EventLevel) {
+    let admin = require_admin_auth(env.clone());
+    admin_log::record(&env, &admin, "set_event_level", (level,));
+
+    storage::set(&env, &DataKey::EventLevel, &level);
+
+    events::emit_event_level(&env, level);
+}
+
+pub fn event_level(env: &Env) -> EventLevel {
+    storage::get(env, &DataKey::EventLevel).unwrap_or(EventLevel::Full)
+}
+
+/// Stop every fraction transfer across all assets (admin only). `retry_after`
+/// tells dependent contracts and frontends when to try again, 0 if unknown;
+/// pausing again only updates it.
+pub fn pause(env: Env, caller: Address, retry_after: u64) {
+    require_admin_caller(&env, &caller);
+    admin_log::record(&env, &caller, "pause", (retry_after,));
+
+    let since = pause_state(env.clone())
+        .map(|state| state.since)
+        .unwrap_or(env.ledger().timestamp());
+    let state = PauseState { since, retry_after };
+    storage::set(&env, &DataKeyExt::Paused, &state);
+
+    events::emit_paused(&env, Some(state));
+}
+
+pub fn unpause(env: Env, caller: Address) {
+    require_admin_caller(&env, &caller);
+    admin_log::record(&env, &caller, "unpause", ());
+
+    storage::remove(&env, &DataKeyExt::Paused);
+
+    events::emit_paused(&env, None);
+}
+
+pub fn pause_state(env: Env) -> Option<PauseState> {
+    storage::get(&env, &DataKeyExt::Paused)
+}
+
+pub fn require_not_paused(env: &Env) {
+    if storage::has(env, &DataKeyExt::Paused) {
+        panic_with_error!(env, FractcoreError::Paused);
+    }
+}
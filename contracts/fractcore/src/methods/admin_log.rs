@@ -0,0 +1,19 @@
+use crate::events;
+use crate::storage::{AdminLogEntry, AdminLogHead, DataKeyExt};
+use soroban_sdk::{xdr::ToXdr, Address, Env, Vec};
+
+/// Append a privileged call to the admin action log; called by role-checked
+/// entrypoints once the check has passed. `args` is hashed, not stored.
+pub fn record(env: &Env, actor: &Address, function: &str, args: impl ToXdr) {
+    let (index, entry) = ::admin_log::record::<DataKeyExt>(env, actor, function, args);
+    events::emit_admin_log(env, index, entry);
+}
+
+/// One page of the log, oldest entry first; empty once the page has been folded
+pub fn get_admin_log(env: Env, page: u32) -> Vec<AdminLogEntry> {
+    ::admin_log::get_admin_log::<DataKeyExt>(&env, page)
+}
+
+pub fn get_admin_log_head(env: Env) -> AdminLogHead {
+    ::admin_log::get_admin_log_head::<DataKeyExt>(&env)
+}
@@ -0,0 +1,70 @@
+use crate::events;
+use crate::methods::{admin, split, utils};
+use crate::storage::{self, Announcement, DataKey, MAX_ANNOUNCEMENTS};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+/// Post a notice to an asset's holders (asset manager or admin).
+///
+/// Only the latest `MAX_ANNOUNCEMENTS` are kept in storage in a ring buffer;
+/// indexers get every announcement through the `announce` event.
+pub fn post_announcement(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    title: String,
+    content_hash: BytesN<32>,
+    uri: String,
+) -> u32 {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != admin::get_admin(env.clone())
+        && caller != split::asset_manager(env.clone(), asset_id)
+    {
+        panic!("Not authorized to post announcements");
+    }
+
+    let id = announcement_count(env.clone(), asset_id);
+    let announcement = Announcement {
+        id,
+        author: caller,
+        title,
+        content_hash,
+        uri,
+        posted_at: env.ledger().timestamp(),
+    };
+
+    storage::set(
+        &env,
+        &DataKey::Announcement(asset_id, id % MAX_ANNOUNCEMENTS),
+        &announcement,
+    );
+    storage::set(&env, &DataKey::AnnouncementCount(asset_id), &(id + 1));
+
+    events::emit_announcement(&env, asset_id, announcement);
+
+    id
+}
+
+pub fn announcement_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::AnnouncementCount(asset_id)).unwrap_or(0)
+}
+
+/// Retained announcements, newest first
+pub fn get_announcements(env: Env, asset_id: u64) -> Vec<Announcement> {
+    let count = announcement_count(env.clone(), asset_id);
+    let retained = count.min(MAX_ANNOUNCEMENTS);
+
+    let mut announcements = Vec::new(&env);
+    for offset in 1..=retained {
+        let slot = (count - offset) % MAX_ANNOUNCEMENTS;
+        if let Some(announcement) = storage::get(&env, &DataKey::Announcement(asset_id, slot)) {
+            announcements.push_back(announcement);
+        }
+    }
+
+    announcements
+}
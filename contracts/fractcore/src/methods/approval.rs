@@ -1,12 +1,13 @@
 use crate::events;
-use crate::storage::DataKey;
+use crate::storage::{self, DataKey};
 use soroban_sdk::{Address, Env};
 
 pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
     owner.require_auth();
 
     // Store approval - direct storage
-    env.storage().persistent().set(
+    storage::set(
+        &env,
         &DataKey::OperatorApproval(owner.clone(), operator.clone()),
         &approved,
     );
@@ -15,17 +16,15 @@ pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approve
 }
 
 pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
-    env.storage()
-        .persistent()
-        .get(&DataKey::OperatorApproval(owner, operator))
-        .unwrap_or(false)
+    storage::get(&env, &DataKey::OperatorApproval(owner, operator)).unwrap_or(false)
 }
 
 pub fn approve(env: Env, owner: Address, operator: Address, asset_id: u64, amount: u64) {
     owner.require_auth();
 
     // Store specific allowance
-    env.storage().persistent().set(
+    storage::set(
+        &env,
         &DataKey::TokenAllowance(owner.clone(), operator.clone(), asset_id),
         &amount,
     );
@@ -34,8 +33,40 @@ pub fn approve(env: Env, owner: Address, operator: Address, asset_id: u64, amoun
 }
 
 pub fn allowance(env: Env, owner: Address, operator: Address, asset_id: u64) -> u64 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::TokenAllowance(owner, operator, asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::TokenAllowance(owner, operator, asset_id)).unwrap_or(0)
+}
+
+/// Let `operator` move up to `max_total_amount` fractions in total across any of
+/// the owner's assets. Transfers not covered by a per-asset allowance draw the
+/// limit down; zero revokes it.
+pub fn set_operator_limit(env: Env, owner: Address, operator: Address, max_total_amount: u64) {
+    owner.require_auth();
+
+    let key = DataKey::OperatorLimit(owner.clone(), operator.clone());
+    if max_total_amount == 0 {
+        storage::remove(&env, &key);
+    } else {
+        storage::set(&env, &key, &max_total_amount);
+    }
+
+    events::emit_operator_limit(&env, owner, operator, max_total_amount);
+}
+
+/// Amount the operator can still move across the owner's assets
+pub fn operator_limit(env: Env, owner: Address, operator: Address) -> u64 {
+    storage::get(&env, &DataKey::OperatorLimit(owner, operator)).unwrap_or(0)
+}
+
+pub fn spend_operator_limit(env: &Env, owner: &Address, operator: &Address, amount: u64) {
+    let remaining = operator_limit(env.clone(), owner.clone(), operator.clone());
+    if remaining < amount {
+        panic!("Insufficient allowance");
+    }
+
+    let key = DataKey::OperatorLimit(owner.clone(), operator.clone());
+    if remaining == amount {
+        storage::remove(env, &key);
+    } else {
+        storage::set(env, &key, &(remaining - amount));
+    }
 }
@@ -0,0 +1,74 @@
+use crate::methods::utils;
+use crate::storage::{
+    self, BalanceStatus, DataKey, PlacedKey, StorageEntry, PERSISTENT_BUMP_AMOUNT,
+    PERSISTENT_LIFETIME_THRESHOLD,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Extend a persistent entry's TTL if it exists; returns whether it did
+pub fn bump_persistent<K: PlacedKey>(env: &Env, key: &K) -> bool {
+    if !storage::has(env, key) {
+        return false;
+    }
+
+    storage::bump(
+        env,
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    true
+}
+
+/// Keep a holder's balance entry live after it is written
+pub fn bump_balance(env: &Env, owner: &Address, asset_id: u64) {
+    bump_persistent(env, &DataKey::Balance(owner.clone(), asset_id));
+}
+
+/// Balance view that tells "no record" apart from a recorded zero.
+///
+/// An archived entry cannot be read by a contract at all: simulation reports
+/// it in the restore preamble instead of returning zero, so integrations should
+/// restore it (RestoreFootprint) rather than trust a 0 from `balance_of`.
+pub fn balance_status(env: Env, owner: Address, asset_id: u64) -> BalanceStatus {
+    match storage::get::<DataKey, u64>(&env, &DataKey::Balance(owner, asset_id)) {
+        None => BalanceStatus::NoEntry,
+        Some(0) => BalanceStatus::Zero,
+        Some(balance) => BalanceStatus::Held(balance),
+    }
+}
+
+/// Extend the TTL of a user's balances and ownership records in one call.
+///
+/// Anyone may call this, since it only pays to keep the owner's entries live.
+/// Returns one flag per descriptor: true if the entry existed and was extended.
+pub fn restore_entries(env: Env, owner: Address, entries: Vec<StorageEntry>) -> Vec<bool> {
+    utils::require_batch_size(&env, entries.len());
+
+    storage::extend_instance(&env, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+
+    let mut restored = Vec::new(&env);
+    for entry in entries.iter() {
+        let key = match entry {
+            StorageEntry::Balance(asset_id) => DataKey::Balance(owner.clone(), asset_id),
+            StorageEntry::OwnerAsset(asset_id) => {
+                DataKey::OwnerAssetExists(owner.clone(), asset_id)
+            }
+            StorageEntry::OwnerLocation(asset_id) => {
+                DataKey::AssetOwnerLocation(asset_id, owner.clone())
+            }
+            StorageEntry::Allowance(operator, asset_id) => {
+                DataKey::TokenAllowance(owner.clone(), operator, asset_id)
+            }
+            StorageEntry::OperatorApproval(operator) => {
+                DataKey::OperatorApproval(owner.clone(), operator)
+            }
+            StorageEntry::OperatorLimit(operator) => {
+                DataKey::OperatorLimit(owner.clone(), operator)
+            }
+        };
+        restored.push_back(bump_persistent(&env, &key));
+    }
+
+    restored
+}
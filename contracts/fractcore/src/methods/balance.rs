@@ -1,11 +1,9 @@
-use crate::storage::DataKey;
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
 use soroban_sdk::{Address, Env, Vec};
 
 pub fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Balance(owner, asset_id))
-        .unwrap_or(0) // Return 0 if doesn't exist
+    storage::get(&env, &DataKey::Balance(owner, asset_id)).unwrap_or(0) // Return 0 if doesn't exist
 }
 
 pub fn balance_of_batch(env: Env, owners: Vec<Address>, asset_ids: Vec<u64>) -> Vec<u64> {
@@ -13,6 +11,8 @@ pub fn balance_of_batch(env: Env, owners: Vec<Address>, asset_ids: Vec<u64>) ->
         panic!("Owners and asset_ids length mismatch");
     }
 
+    utils::require_batch_size(&env, owners.len());
+
     let mut balances = Vec::new(&env);
     for i in 0..owners.len() {
         let owner = owners.get(i).unwrap();
@@ -25,15 +25,9 @@ pub fn balance_of_batch(env: Env, owners: Vec<Address>, asset_ids: Vec<u64>) ->
 }
 
 pub fn asset_supply(env: Env, asset_id: u64) -> u64 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetSupply(asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::AssetSupply(asset_id)).unwrap_or(0)
 }
 
 pub fn get_asset_owner_count(env: Env, asset_id: u64) -> u32 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerCount(asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::AssetOwnerCount(asset_id)).unwrap_or(0)
 }
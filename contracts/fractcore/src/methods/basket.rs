@@ -0,0 +1,212 @@
+use crate::events;
+use crate::methods::{
+    admin, admin_log, archival, balance, cap_table, checkpoints, concentration, issuance, mint,
+    split, transfer, utils,
+};
+use crate::storage::{self, BasketComponent, DataKey, MAX_BASKET_COMPONENTS};
+use soroban_sdk::{vec, Address, Env, Vec};
+
+/// Create a basket asset backed by fractions of other assets.
+///
+/// `weight` is how many fractions of the component back one basket token while
+/// the basket is empty; afterwards deposits and redemptions are pro rata to what
+/// the basket holds, so splits of a component carry through. The contract keeps
+/// the fractions and tracks each basket's share, since several baskets may hold
+/// the same asset. The basket starts with zero supply and is only minted through
+/// `deposit_to_basket`.
+pub fn create_basket(env: Env, components: Vec<BasketComponent>) -> u64 {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(&env, &admin, "create_basket", (components.clone(),));
+
+    if components.is_empty() || components.len() > MAX_BASKET_COMPONENTS {
+        panic!("Invalid number of basket components");
+    }
+
+    for (i, component) in components.iter().enumerate() {
+        if component.weight == 0 {
+            panic!("Component weight must be > 0");
+        }
+        if !utils::asset_exists(env.clone(), component.asset_id) {
+            panic!("Asset does not exist");
+        }
+        if is_basket(env.clone(), component.asset_id) {
+            panic!("Baskets cannot contain baskets");
+        }
+        if components
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.asset_id == component.asset_id)
+        {
+            panic!("Duplicate basket component");
+        }
+    }
+
+    let admin = admin::get_admin(env.clone());
+    let basket_id = mint::create_asset(&env, &admin);
+    storage::set(&env, &DataKey::AssetSupply(basket_id), &0u64);
+    storage::set(&env, &DataKey::Basket(basket_id), &components);
+
+    for component in components.iter() {
+        let key = DataKey::AssetBaskets(component.asset_id);
+        let mut baskets: Vec<u64> = storage::get(&env, &key).unwrap_or(Vec::new(&env));
+        baskets.push_back(basket_id);
+        storage::set(&env, &key, &baskets);
+    }
+
+    events::emit_basket_created(&env, basket_id, components);
+
+    basket_id
+}
+
+/// Deposit component fractions into the contract and receive `units` basket tokens.
+///
+/// Each component costs `units * weight` for an empty basket, otherwise the matching
+/// share of the basket's holdings, rounded up in favour of existing holders.
+pub fn deposit_to_basket(env: Env, owner: Address, basket_id: u64, units: u64) {
+    owner.require_auth();
+
+    let components = components_of(&env, basket_id);
+    if units == 0 {
+        panic!("Cannot mint 0 basket tokens");
+    }
+    split::require_not_restructuring(&env, basket_id);
+
+    let custody = env.current_contract_address();
+    let supply = balance::asset_supply(env.clone(), basket_id);
+    issuance::require_mintable(&env, basket_id, supply + units);
+
+    for component in components.iter() {
+        let held = holding(&env, basket_id, component.asset_id);
+        let amount = if supply == 0 {
+            units
+                .checked_mul(component.weight)
+                .expect("Deposit amount overflows")
+        } else {
+            (units as u128 * held as u128).div_ceil(supply as u128) as u64
+        };
+
+        if amount > 0 {
+            transfer::move_fractions(
+                env.clone(),
+                owner.clone(),
+                custody.clone(),
+                component.asset_id,
+                amount,
+            );
+            set_holding(&env, basket_id, component.asset_id, held + amount);
+        }
+    }
+
+    mint::credit_recipients(
+        &env,
+        basket_id,
+        &vec![&env, owner.clone()],
+        &vec![&env, units],
+    );
+    storage::set(&env, &DataKey::AssetSupply(basket_id), &(supply + units));
+
+    events::emit_basket_deposit(&env, owner, basket_id, units);
+    concentration::check(&env, basket_id);
+}
+
+/// Burn `units` basket tokens and receive the matching share of every component,
+/// rounded down
+pub fn redeem_from_basket(env: Env, owner: Address, basket_id: u64, units: u64) {
+    owner.require_auth();
+
+    let components = components_of(&env, basket_id);
+    if units == 0 {
+        panic!("Cannot redeem 0 basket tokens");
+    }
+    split::require_not_restructuring(&env, basket_id);
+
+    let owner_balance = balance::balance_of(env.clone(), owner.clone(), basket_id);
+    if owner_balance < units {
+        panic!("Insufficient balance");
+    }
+
+    let custody = env.current_contract_address();
+    let supply = balance::asset_supply(env.clone(), basket_id);
+
+    for component in components.iter() {
+        let held = holding(&env, basket_id, component.asset_id);
+        let amount = (units as u128 * held as u128 / supply as u128) as u64;
+
+        if amount > 0 {
+            transfer::transfer_internal(
+                env.clone(),
+                custody.clone(),
+                owner.clone(),
+                component.asset_id,
+                amount,
+            );
+            set_holding(&env, basket_id, component.asset_id, held - amount);
+        }
+    }
+
+    let new_balance = owner_balance - units;
+    cap_table::preserve_balance(&env, &owner, basket_id);
+    storage::set(
+        &env,
+        &DataKey::Balance(owner.clone(), basket_id),
+        &new_balance,
+    );
+    archival::bump_balance(&env, &owner, basket_id);
+    checkpoints::record(&env, &owner, basket_id, new_balance);
+    concentration::track(&env, &owner, basket_id, new_balance);
+    if new_balance == 0 {
+        utils::remove_owner_from_asset(&env, basket_id, owner.clone());
+        utils::remove_asset_from_owner(&env, owner.clone(), basket_id);
+    }
+
+    storage::set(&env, &DataKey::AssetSupply(basket_id), &(supply - units));
+
+    events::emit_basket_redeem(&env, owner, basket_id, units);
+    concentration::check(&env, basket_id);
+}
+
+/// Components of a basket; empty when the asset is not a basket
+pub fn get_basket(env: Env, basket_id: u64) -> Vec<BasketComponent> {
+    storage::get(&env, &DataKey::Basket(basket_id)).unwrap_or(Vec::new(&env))
+}
+
+pub fn is_basket(env: Env, asset_id: u64) -> bool {
+    storage::has(&env, &DataKey::Basket(asset_id))
+}
+
+/// Fractions of each component held for a basket, in component order
+pub fn basket_holdings(env: Env, basket_id: u64) -> Vec<u64> {
+    let mut holdings = Vec::new(&env);
+    for component in get_basket(env.clone(), basket_id).iter() {
+        holdings.push_back(holding(&env, basket_id, component.asset_id));
+    }
+    holdings
+}
+
+/// Scale every basket's holding of an asset by `multiply / divide` after a split or
+/// consolidation rescaled the contract's own balance. Rounds down, so the contract
+/// always holds at least what the baskets account for.
+pub fn rescale_holdings(env: &Env, asset_id: u64, multiply: u64, divide: u64) {
+    let baskets: Vec<u64> =
+        storage::get(env, &DataKey::AssetBaskets(asset_id)).unwrap_or(Vec::new(env));
+
+    for basket_id in baskets.iter() {
+        let held = holding(env, basket_id, asset_id);
+        let rescaled = (held as u128 * multiply as u128 / divide as u128) as u64;
+        set_holding(env, basket_id, asset_id, rescaled);
+    }
+}
+
+pub fn holding(env: &Env, basket_id: u64, asset_id: u64) -> u64 {
+    storage::get(env, &DataKey::BasketHolding(basket_id, asset_id)).unwrap_or(0)
+}
+
+fn set_holding(env: &Env, basket_id: u64, asset_id: u64, amount: u64) {
+    let key = DataKey::BasketHolding(basket_id, asset_id);
+    storage::set(env, &key, &amount);
+    archival::bump_persistent(env, &key);
+}
+
+fn components_of(env: &Env, basket_id: u64) -> Vec<BasketComponent> {
+    storage::get(env, &DataKey::Basket(basket_id)).expect("Asset is not a basket")
+}
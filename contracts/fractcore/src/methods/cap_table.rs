@@ -0,0 +1,144 @@
+use crate::events;
+use crate::methods::{balance, ownership, split, utils};
+use crate::storage::{self, CapTableSnapshot, Cursor, DataKey};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Freeze the holder list and balances of an asset for a cap table export.
+///
+/// Nothing is copied up front: while the snapshot is the asset's live one, the
+/// first change to a balance or owner page after it saves the old value, so
+/// `export_cap_table` reads the asset as it was at creation however many
+/// transfers happen between pages. A new snapshot replaces the previous one.
+pub fn create_cap_table_snapshot(env: Env, caller: Address, asset_id: u64) -> u32 {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can snapshot the cap table");
+    }
+
+    // Mid-split balances are in mixed units
+    split::require_not_restructuring(&env, asset_id);
+
+    let snapshot_id: u32 = storage::get(&env, &DataKey::CapTableSnapshotCount).unwrap_or(0) + 1;
+    storage::set(&env, &DataKey::CapTableSnapshotCount, &snapshot_id);
+
+    let snapshot = CapTableSnapshot {
+        id: snapshot_id,
+        asset_id,
+        supply: balance::asset_supply(env.clone(), asset_id),
+        owner_count: ownership::get_asset_owner_count(env.clone(), asset_id),
+        page_count: storage::get(&env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0),
+        ledger: env.ledger().sequence(),
+        created_at: env.ledger().timestamp(),
+    };
+    storage::set(&env, &DataKey::CapTableSnapshot(asset_id), &snapshot);
+
+    events::emit_cap_table_snapshot(&env, &snapshot);
+
+    snapshot_id
+}
+
+pub fn get_cap_table_snapshot(env: Env, asset_id: u64) -> Option<CapTableSnapshot> {
+    storage::get(&env, &DataKey::CapTableSnapshot(asset_id))
+}
+
+/// Up to `limit` owners of the asset's live snapshot from `cursor` as (owner,
+/// balance, share in basis points of the snapshot supply). Holders with no
+/// balance at the snapshot are left out, so pages can be short.
+///
+/// Pages are read as they were when the snapshot was taken; a cursor from a
+/// snapshot that has since been replaced is refused.
+pub fn export_cap_table(
+    env: Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<(Address, u64, u32)>, Option<Cursor>) {
+    let snapshot =
+        get_cap_table_snapshot(env.clone(), asset_id).expect("No cap table snapshot for asset");
+
+    let (page, position) = match cursor {
+        None => (0, 0),
+        Some(cursor) => {
+            if cursor.snapshot != snapshot.id as u64 {
+                panic!("Cap table snapshot was replaced");
+            }
+            (cursor.page, cursor.position)
+        }
+    };
+
+    let mut entries = Vec::new(&env);
+    if snapshot.supply == 0 {
+        return (entries, None);
+    }
+
+    let (owners, next) = ownership::walk_owner_pages(
+        &env,
+        page,
+        position,
+        snapshot.page_count,
+        utils::page_limit(limit),
+        |page_idx| {
+            storage::get(&env, &DataKey::SnapshotOwnersPage(snapshot.id, page_idx))
+                .or_else(|| storage::get(&env, &DataKey::AssetOwnersPage(asset_id, page_idx)))
+                .unwrap_or(Vec::new(&env))
+        },
+    );
+
+    for owner in owners.iter() {
+        let held: u64 = storage::get(&env, &DataKey::SnapshotBalance(snapshot.id, owner.clone()))
+            .unwrap_or_else(|| balance::balance_of(env.clone(), owner.clone(), asset_id));
+        if held == 0 {
+            continue;
+        }
+
+        let bps = (held as u128 * 10_000 / snapshot.supply as u128) as u32;
+        entries.push_back((owner, held, bps));
+    }
+
+    (
+        entries,
+        next.map(|(page, position)| Cursor {
+            page,
+            position,
+            snapshot: snapshot.id as u64,
+        }),
+    )
+}
+
+/// Save an owner's balance for the live snapshot before it first changes
+pub fn preserve_balance(env: &Env, owner: &Address, asset_id: u64) {
+    let snapshot = match get_cap_table_snapshot(env.clone(), asset_id) {
+        Some(snapshot) => snapshot,
+        None => return,
+    };
+
+    let key = DataKey::SnapshotBalance(snapshot.id, owner.clone());
+    if !storage::has(env, &key) {
+        let held = balance::balance_of(env.clone(), owner.clone(), asset_id);
+        storage::set(env, &key, &held);
+    }
+}
+
+/// Save an owner page for the live snapshot before it first changes. Pages created
+/// after the snapshot only hold newer owners and are not exported.
+pub fn preserve_owner_page(env: &Env, asset_id: u64, page: u32) {
+    let snapshot = match get_cap_table_snapshot(env.clone(), asset_id) {
+        Some(snapshot) => snapshot,
+        None => return,
+    };
+    if page >= snapshot.page_count {
+        return;
+    }
+
+    let key = DataKey::SnapshotOwnersPage(snapshot.id, page);
+    if !storage::has(env, &key) {
+        let owners: Vec<Address> =
+            storage::get(env, &DataKey::AssetOwnersPage(asset_id, page)).unwrap_or(Vec::new(env));
+        storage::set(env, &key, &owners);
+    }
+}
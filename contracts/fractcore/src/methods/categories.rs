@@ -0,0 +1,109 @@
+use crate::events;
+use crate::methods::{comanagement, split, utils};
+use crate::storage::{self, DataKeyExt, CATEGORY_PAGE_SIZE};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Classify an asset for marketplace browsing, or clear its category with
+/// `None` (asset manager only)
+pub fn set_asset_category(env: Env, caller: Address, asset_id: u64, category: Option<Symbol>) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can set the category");
+    }
+    comanagement::require_not_co_managed(&env, asset_id);
+
+    store_category(&env, asset_id, category);
+}
+
+/// Move the asset into `category`'s index, out of the one it was in
+pub fn store_category(env: &Env, asset_id: u64, category: Option<Symbol>) {
+    let current = asset_category(env.clone(), asset_id);
+    if current == category {
+        return;
+    }
+
+    if let Some(current) = current {
+        remove_from_index(env, &current, asset_id);
+        storage::remove(env, &DataKeyExt::AssetCategory(asset_id));
+    }
+    if let Some(category) = category.clone() {
+        add_to_index(env, &category, asset_id);
+        storage::set(env, &DataKeyExt::AssetCategory(asset_id), &category);
+    }
+
+    events::emit_category(env, asset_id, category);
+}
+
+pub fn asset_category(env: Env, asset_id: u64) -> Option<Symbol> {
+    storage::get(&env, &DataKeyExt::AssetCategory(asset_id))
+}
+
+/// One page of the category's asset ids, CATEGORY_PAGE_SIZE per page. Removing
+/// an asset moves the last id into its slot, so pages are in no particular order.
+pub fn assets_by_category(env: Env, category: Symbol, page: u32) -> Vec<u64> {
+    storage::get(&env, &DataKeyExt::CategoryAssetsPage(category, page)).unwrap_or(Vec::new(&env))
+}
+
+pub fn category_asset_count(env: Env, category: Symbol) -> u32 {
+    storage::get(&env, &DataKeyExt::CategoryAssetCount(category)).unwrap_or(0)
+}
+
+fn add_to_index(env: &Env, category: &Symbol, asset_id: u64) {
+    let count = category_asset_count(env.clone(), category.clone());
+    let page = count / CATEGORY_PAGE_SIZE;
+
+    let mut assets = assets_by_category(env.clone(), category.clone(), page);
+    assets.push_back(asset_id);
+    storage::set(
+        env,
+        &DataKeyExt::CategoryAssetsPage(category.clone(), page),
+        &assets,
+    );
+    storage::set(env, &DataKeyExt::CategoryPosition(asset_id), &count);
+    storage::set(
+        env,
+        &DataKeyExt::CategoryAssetCount(category.clone()),
+        &(count + 1),
+    );
+}
+
+fn remove_from_index(env: &Env, category: &Symbol, asset_id: u64) {
+    let count = category_asset_count(env.clone(), category.clone());
+    let position: u32 = storage::get(env, &DataKeyExt::CategoryPosition(asset_id)).unwrap();
+    let last = count - 1;
+
+    // Take the last id off its page, then put it where the removed one was
+    let last_page = last / CATEGORY_PAGE_SIZE;
+    let mut last_assets = assets_by_category(env.clone(), category.clone(), last_page);
+    let moved = last_assets.pop_back().unwrap();
+    set_page(env, category, last_page, &last_assets);
+
+    if moved != asset_id {
+        let page = position / CATEGORY_PAGE_SIZE;
+        let mut assets = assets_by_category(env.clone(), category.clone(), page);
+        assets.set(position % CATEGORY_PAGE_SIZE, moved);
+        set_page(env, category, page, &assets);
+        storage::set(env, &DataKeyExt::CategoryPosition(moved), &position);
+    }
+
+    storage::remove(env, &DataKeyExt::CategoryPosition(asset_id));
+    storage::set(
+        env,
+        &DataKeyExt::CategoryAssetCount(category.clone()),
+        &last,
+    );
+}
+
+fn set_page(env: &Env, category: &Symbol, page: u32, assets: &Vec<u64>) {
+    let key = DataKeyExt::CategoryAssetsPage(category.clone(), page);
+    if assets.is_empty() {
+        storage::remove(env, &key);
+    } else {
+        storage::set(env, &key, assets);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::methods::{archival, balance};
+use crate::storage::{self, BalanceCheckpoint, DataKey, MAX_BALANCE_CHECKPOINTS};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Record a holder's new balance; called wherever a balance is written
+pub fn record(env: &Env, owner: &Address, asset_id: u64, new_balance: u64) {
+    let key = DataKey::BalanceCheckpoints(owner.clone(), asset_id);
+    let mut checkpoints = balance_checkpoints(env.clone(), owner.clone(), asset_id);
+    let now = env.ledger().timestamp();
+
+    // Several changes in one ledger collapse into the last one
+    if let Some(last) = checkpoints.last() {
+        if last.timestamp == now {
+            checkpoints.pop_back();
+        }
+    }
+    checkpoints.push_back(BalanceCheckpoint {
+        timestamp: now,
+        balance: new_balance,
+    });
+    if checkpoints.len() > MAX_BALANCE_CHECKPOINTS {
+        checkpoints.pop_front();
+    }
+
+    storage::set(env, &key, &checkpoints);
+    archival::bump_persistent(env, &key);
+}
+
+/// Rescale a holder's history by `numerator / denominator` after a split or consolidation
+/// so time-weighted balances stay in current units
+pub fn rescale(env: &Env, owner: &Address, asset_id: u64, numerator: u64, denominator: u64) {
+    let key = DataKey::BalanceCheckpoints(owner.clone(), asset_id);
+    let checkpoints = balance_checkpoints(env.clone(), owner.clone(), asset_id);
+    if checkpoints.is_empty() {
+        return;
+    }
+
+    let mut rescaled = Vec::new(env);
+    for checkpoint in checkpoints.iter() {
+        rescaled.push_back(BalanceCheckpoint {
+            timestamp: checkpoint.timestamp,
+            balance: checkpoint.balance * numerator / denominator,
+        });
+    }
+    storage::set(env, &key, &rescaled);
+}
+
+pub fn balance_checkpoints(env: Env, owner: Address, asset_id: u64) -> Vec<BalanceCheckpoint> {
+    storage::get(&env, &DataKey::BalanceCheckpoints(owner, asset_id)).unwrap_or(Vec::new(&env))
+}
+
+/// Balance held at the close of the ledger stamped `timestamp`.
+///
+/// Holders with no checkpoints predate balance history and have held their
+/// current balance all along. Otherwise the last checkpoint at or before
+/// `timestamp` gives the balance, and `None` means the retained history does
+/// not reach back that far: the holder held nothing yet, or the checkpoints
+/// from then have been dropped.
+pub fn balance_at(env: Env, owner: Address, asset_id: u64, timestamp: u64) -> Option<u64> {
+    let checkpoints = balance_checkpoints(env.clone(), owner.clone(), asset_id);
+    if checkpoints.is_empty() {
+        return Some(balance::balance_of(env, owner, asset_id));
+    }
+
+    let mut held = None;
+    for checkpoint in checkpoints.iter() {
+        if checkpoint.timestamp > timestamp {
+            break;
+        }
+        held = Some(checkpoint.balance);
+    }
+    held
+}
+
+/// Average balance held between `since` and now.
+///
+/// Time before the oldest retained checkpoint counts as holding nothing. Holders
+/// with no checkpoints at all predate balance history and are treated as having
+/// held their current balance for the whole window.
+pub fn time_weighted_balance(env: Env, owner: Address, asset_id: u64, since: u64) -> u64 {
+    let now = env.ledger().timestamp();
+    let checkpoints = balance_checkpoints(env.clone(), owner.clone(), asset_id);
+
+    if since >= now || checkpoints.is_empty() {
+        return balance::balance_of(env, owner, asset_id);
+    }
+
+    let mut balance_seconds = 0u128;
+    for i in 0..checkpoints.len() {
+        let checkpoint = checkpoints.get(i).unwrap();
+        let segment_end = checkpoints.get(i + 1).map_or(now, |next| next.timestamp);
+        let segment_start = checkpoint.timestamp.max(since);
+
+        if segment_end > segment_start {
+            balance_seconds += checkpoint.balance as u128 * (segment_end - segment_start) as u128;
+        }
+    }
+
+    (balance_seconds / (now - since) as u128) as u64
+}
@@ -0,0 +1,283 @@
+use crate::events;
+use crate::methods::{concentration, metadata, split, transfer_lock, utils};
+use crate::storage::{
+    self, CoManagement, DataKeyExt, ManagerAction, ManagerProposal, MAX_CO_MANAGERS,
+    MAX_PENDING_MANAGER_ACTIONS,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Put an asset under co-management (asset manager only).
+///
+/// From then on URI changes, transfer lock authority and concentration thresholds
+/// can no longer be set directly: one co-manager proposes the change and it runs
+/// once `quorum` co-managers have confirmed it. Later changes to the co-manager
+/// set go through the same quorum.
+pub fn set_co_managers(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    managers: Vec<Address>,
+    quorum: u32,
+) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can set co-managers");
+    }
+    require_not_co_managed(&env, asset_id);
+
+    if managers.is_empty() {
+        panic!("No co-managers given");
+    }
+    check_co_managers(&managers, quorum);
+
+    store_co_managers(&env, asset_id, managers, quorum);
+}
+
+pub fn get_co_managers(env: Env, asset_id: u64) -> Option<CoManagement> {
+    storage::get(&env, &DataKeyExt::CoManagement(asset_id))
+}
+
+/// Single-manager paths call this before changing anything co-managers control
+pub fn require_not_co_managed(env: &Env, asset_id: u64) {
+    if storage::has(env, &DataKeyExt::CoManagement(asset_id)) {
+        panic!("Asset is co-managed; propose a manager action");
+    }
+}
+
+/// A co-manager proposes a sensitive change; the proposal counts as that
+/// manager's confirmation and runs at once if it already meets the quorum.
+/// Returns the action id.
+pub fn propose_manager_action(
+    env: Env,
+    manager: Address,
+    asset_id: u64,
+    action: ManagerAction,
+) -> u32 {
+    manager.require_auth();
+
+    let config = require_co_manager(&env, &manager, asset_id);
+    check_action(&env, asset_id, &action);
+
+    let mut pending = pending_ids(&env, asset_id);
+    if pending.len() >= MAX_PENDING_MANAGER_ACTIONS {
+        panic!("Too many pending manager actions");
+    }
+
+    let id: u32 = storage::get(&env, &DataKeyExt::ManagerActionCount(asset_id)).unwrap_or(0);
+    storage::set(&env, &DataKeyExt::ManagerActionCount(asset_id), &(id + 1));
+
+    let mut confirmations = Vec::new(&env);
+    confirmations.push_back(manager.clone());
+    let proposal = ManagerProposal {
+        id,
+        action,
+        proposer: manager.clone(),
+        confirmations,
+        proposed_at: env.ledger().timestamp(),
+    };
+
+    events::emit_manager_action_proposed(&env, asset_id, id, manager.clone());
+
+    if config.quorum <= 1 {
+        execute(&env, asset_id, proposal, &manager);
+    } else {
+        pending.push_back(id);
+        storage::set(&env, &DataKeyExt::PendingManagerActions(asset_id), &pending);
+        storage::set(&env, &DataKeyExt::ManagerAction(asset_id, id), &proposal);
+    }
+
+    id
+}
+
+/// Another co-manager confirms a pending action. Returns true when this
+/// confirmation reached the quorum and the action was executed.
+pub fn confirm_manager_action(env: Env, manager: Address, asset_id: u64, action_id: u32) -> bool {
+    manager.require_auth();
+
+    let config = require_co_manager(&env, &manager, asset_id);
+    let mut proposal =
+        get_manager_action(env.clone(), asset_id, action_id).expect("Manager action not found");
+
+    if proposal.confirmations.contains(&manager) {
+        panic!("Manager already confirmed");
+    }
+    proposal.confirmations.push_back(manager.clone());
+
+    events::emit_manager_action_confirmed(
+        &env,
+        asset_id,
+        action_id,
+        manager.clone(),
+        proposal.confirmations.len(),
+    );
+
+    if proposal.confirmations.len() < config.quorum {
+        storage::set(
+            &env,
+            &DataKeyExt::ManagerAction(asset_id, action_id),
+            &proposal,
+        );
+        return false;
+    }
+
+    // Checks made at proposal time may no longer hold, e.g. a resume after
+    // another action already resumed transfers
+    check_action(&env, asset_id, &proposal.action);
+    drop_pending(&env, asset_id, action_id);
+    execute(&env, asset_id, proposal, &manager);
+    true
+}
+
+/// The proposer withdraws a pending action
+pub fn cancel_manager_action(env: Env, manager: Address, asset_id: u64, action_id: u32) {
+    manager.require_auth();
+
+    let proposal =
+        get_manager_action(env.clone(), asset_id, action_id).expect("Manager action not found");
+    if proposal.proposer != manager {
+        panic!("Only the proposer can cancel");
+    }
+
+    drop_pending(&env, asset_id, action_id);
+
+    events::emit_manager_action_cancelled(&env, asset_id, action_id);
+}
+
+pub fn get_manager_action(env: Env, asset_id: u64, action_id: u32) -> Option<ManagerProposal> {
+    storage::get(&env, &DataKeyExt::ManagerAction(asset_id, action_id))
+}
+
+/// Actions awaiting confirmations, oldest first
+pub fn get_pending_manager_actions(env: Env, asset_id: u64) -> Vec<ManagerProposal> {
+    let mut actions = Vec::new(&env);
+    for id in pending_ids(&env, asset_id).iter() {
+        if let Some(proposal) = get_manager_action(env.clone(), asset_id, id) {
+            actions.push_back(proposal);
+        }
+    }
+    actions
+}
+
+fn require_co_manager(env: &Env, manager: &Address, asset_id: u64) -> CoManagement {
+    let config = get_co_managers(env.clone(), asset_id).expect("Asset is not co-managed");
+    if !config.managers.contains(manager) {
+        panic!("Not a co-manager of this asset");
+    }
+    config
+}
+
+fn check_co_managers(managers: &Vec<Address>, quorum: u32) {
+    if managers.len() > MAX_CO_MANAGERS {
+        panic!("Too many co-managers");
+    }
+    if quorum == 0 || quorum > managers.len() {
+        panic!("Invalid co-manager quorum");
+    }
+    for (i, manager) in managers.iter().enumerate() {
+        if managers.iter().skip(i + 1).any(|other| other == manager) {
+            panic!("Duplicate co-manager");
+        }
+    }
+}
+
+fn store_co_managers(env: &Env, asset_id: u64, managers: Vec<Address>, quorum: u32) {
+    let count = managers.len();
+    storage::set(
+        env,
+        &DataKeyExt::CoManagement(asset_id),
+        &CoManagement { managers, quorum },
+    );
+
+    events::emit_co_managers(env, asset_id, count, quorum);
+}
+
+/// Reject actions that could not execute as proposed
+fn check_action(env: &Env, asset_id: u64, action: &ManagerAction) {
+    match action {
+        ManagerAction::RemoveUri => {
+            if metadata::stored_asset_uri(env, asset_id).is_none() {
+                panic!("Asset has no URI");
+            }
+        }
+        ManagerAction::SetConcentrationThresholds(thresholds) => {
+            concentration::check_thresholds(thresholds)
+        }
+        ManagerAction::HaltTransfers => {
+            if transfer_lock::is_transfers_halted(env, asset_id) {
+                panic!("Transfers already halted");
+            }
+        }
+        ManagerAction::ResumeTransfers => {
+            if !transfer_lock::is_transfers_halted(env, asset_id) {
+                panic!("Transfers are not halted");
+            }
+        }
+        ManagerAction::SetCoManagers(managers, quorum) => {
+            if !managers.is_empty() {
+                check_co_managers(managers, *quorum);
+            }
+        }
+        ManagerAction::SetUri(_) | ManagerAction::SetTransferLockAuthority(_) => {}
+    }
+}
+
+fn execute(env: &Env, asset_id: u64, proposal: ManagerProposal, executor: &Address) {
+    match proposal.action {
+        ManagerAction::SetUri(uri) => {
+            metadata::store_asset_uri(env, asset_id, Some(uri.clone()), executor);
+            events::emit_uri_update(env, asset_id, uri);
+        }
+        ManagerAction::RemoveUri => {
+            let version = metadata::store_asset_uri(env, asset_id, None, executor);
+            events::emit_uri_removed(env, asset_id, version);
+        }
+        ManagerAction::SetTransferLockAuthority(authority) => {
+            transfer_lock::store_lock_authority(env, asset_id, authority)
+        }
+        ManagerAction::SetConcentrationThresholds(thresholds) => {
+            concentration::store_thresholds(env, asset_id, thresholds)
+        }
+        ManagerAction::HaltTransfers => {
+            storage::set(env, &DataKeyExt::TransfersHalted(asset_id), &true);
+            events::emit_transfers_halted(env, asset_id, true);
+        }
+        ManagerAction::ResumeTransfers => {
+            storage::remove(env, &DataKeyExt::TransfersHalted(asset_id));
+            events::emit_transfers_halted(env, asset_id, false);
+        }
+        ManagerAction::SetCoManagers(managers, quorum) => {
+            // Confirmations on other pending actions came from the old set
+            for id in pending_ids(env, asset_id).iter() {
+                storage::remove(env, &DataKeyExt::ManagerAction(asset_id, id));
+            }
+            storage::remove(env, &DataKeyExt::PendingManagerActions(asset_id));
+
+            if managers.is_empty() {
+                storage::remove(env, &DataKeyExt::CoManagement(asset_id));
+                events::emit_co_managers(env, asset_id, 0, 0);
+            } else {
+                store_co_managers(env, asset_id, managers, quorum);
+            }
+        }
+    }
+
+    events::emit_manager_action_executed(env, asset_id, proposal.id);
+}
+
+fn pending_ids(env: &Env, asset_id: u64) -> Vec<u32> {
+    storage::get(env, &DataKeyExt::PendingManagerActions(asset_id)).unwrap_or(Vec::new(env))
+}
+
+fn drop_pending(env: &Env, asset_id: u64, action_id: u32) {
+    let mut pending = pending_ids(env, asset_id);
+    if let Some(index) = pending.first_index_of(action_id) {
+        pending.remove(index);
+    }
+    storage::set(env, &DataKeyExt::PendingManagerActions(asset_id), &pending);
+    storage::remove(env, &DataKeyExt::ManagerAction(asset_id, action_id));
+}
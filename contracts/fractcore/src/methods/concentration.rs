@@ -0,0 +1,189 @@
+use crate::events;
+use crate::methods::{archival, balance, comanagement, split, utils};
+use crate::storage::{
+    self, Concentration, DataKeyExt, HolderBalance, CONCENTRATION_TOP_HOLDERS,
+    MAX_CONCENTRATION_THRESHOLDS, MAX_TRACKED_HOLDERS,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Keep the asset's largest balances current; called wherever a balance is written.
+///
+/// Only the MAX_TRACKED_HOLDERS largest balances seen so far are kept. A holder
+/// outside the list joins it on their next balance change once they outweigh its
+/// smallest entry, so a tracked holder that sells down can briefly rank above an
+/// untracked one.
+pub fn track(env: &Env, holder: &Address, asset_id: u64, new_balance: u64) {
+    let mut top = top_holders(env, asset_id);
+    if let Some(index) = top.iter().position(|entry| entry.holder == *holder) {
+        top.remove(index as u32);
+    }
+
+    if new_balance > 0 {
+        let mut index = top.len();
+        while index > 0 && top.get(index - 1).unwrap().balance < new_balance {
+            index -= 1;
+        }
+        if index < MAX_TRACKED_HOLDERS {
+            top.insert(
+                index,
+                HolderBalance {
+                    holder: holder.clone(),
+                    balance: new_balance,
+                },
+            );
+            if top.len() > MAX_TRACKED_HOLDERS {
+                top.pop_back();
+            }
+        }
+    }
+
+    let key = DataKeyExt::TopHolders(asset_id);
+    storage::set(env, &key, &top);
+    archival::bump_persistent(env, &key);
+}
+
+/// Multiply the tracked balances by `numerator / denominator` after a split
+pub fn rescale(env: &Env, asset_id: u64, numerator: u64, denominator: u64) {
+    let mut top = top_holders(env, asset_id);
+    for index in 0..top.len() {
+        let mut entry = top.get(index).unwrap();
+        entry.balance = entry.balance * numerator / denominator;
+        top.set(index, entry);
+    }
+    storage::set(env, &DataKeyExt::TopHolders(asset_id), &top);
+}
+
+/// Emit an alert when the largest holder's share crossed a threshold since the
+/// last check; called once the supply of a change is final
+pub fn check(env: &Env, asset_id: u64) {
+    let thresholds = thresholds(env, asset_id);
+    if thresholds.is_empty() {
+        return;
+    }
+
+    let top = top_holders(env, asset_id);
+    let share = largest_share_bps(env, asset_id, &top);
+    let level = level_of(&thresholds, share);
+    let previous = current_level(env, asset_id);
+    if level == previous {
+        return;
+    }
+
+    storage::set(env, &DataKeyExt::ConcentrationLevel(asset_id), &level);
+
+    // Report the highest threshold crossed in the direction of the move
+    let (threshold, rising) = if level > previous {
+        (thresholds.get(level - 1).unwrap(), true)
+    } else {
+        (thresholds.get(level).unwrap(), false)
+    };
+    let holder = top.get(0).map(|entry| entry.holder);
+    events::emit_concentration_alert(env, asset_id, holder, share, threshold, rising);
+}
+
+/// Configure the shares of the largest holder that raise alerts (asset manager only).
+///
+/// Thresholds are basis points in ascending order; an empty list stops alerts.
+/// The current level is recorded without an alert, so only later moves are reported.
+pub fn set_concentration_thresholds(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    thresholds: Vec<u32>,
+) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can set concentration thresholds");
+    }
+
+    comanagement::require_not_co_managed(&env, asset_id);
+
+    store_thresholds(&env, asset_id, thresholds);
+}
+
+pub fn check_thresholds(thresholds: &Vec<u32>) {
+    if thresholds.len() > MAX_CONCENTRATION_THRESHOLDS {
+        panic!("Too many concentration thresholds");
+    }
+
+    let mut previous = 0u32;
+    for threshold in thresholds.iter() {
+        if threshold <= previous || threshold > 10_000 {
+            panic!("Thresholds must be ascending and between 1 and 10000 bps");
+        }
+        previous = threshold;
+    }
+}
+
+pub fn store_thresholds(env: &Env, asset_id: u64, thresholds: Vec<u32>) {
+    check_thresholds(&thresholds);
+
+    let top = top_holders(env, asset_id);
+    let level = level_of(&thresholds, largest_share_bps(env, asset_id, &top));
+    storage::set(
+        env,
+        &DataKeyExt::ConcentrationThresholds(asset_id),
+        &thresholds,
+    );
+    storage::set(env, &DataKeyExt::ConcentrationLevel(asset_id), &level);
+
+    events::emit_concentration_thresholds(env, asset_id, thresholds);
+}
+
+/// Largest holders' share of the supply, read from the tracked balances
+pub fn get_concentration(env: Env, asset_id: u64) -> Concentration {
+    let supply = balance::asset_supply(env.clone(), asset_id);
+    let top = top_holders(&env, asset_id);
+
+    let mut top_holders = Vec::new(&env);
+    let mut top_balance = 0u64;
+    for entry in top.iter().take(CONCENTRATION_TOP_HOLDERS as usize) {
+        top_balance += entry.balance;
+        top_holders.push_back(entry);
+    }
+
+    Concentration {
+        supply,
+        largest_share_bps: largest_share_bps(&env, asset_id, &top),
+        top_share_bps: share_bps(top_balance, supply),
+        top_holders,
+        thresholds: thresholds(&env, asset_id),
+    }
+}
+
+fn top_holders(env: &Env, asset_id: u64) -> Vec<HolderBalance> {
+    storage::get(env, &DataKeyExt::TopHolders(asset_id)).unwrap_or(Vec::new(env))
+}
+
+fn thresholds(env: &Env, asset_id: u64) -> Vec<u32> {
+    storage::get(env, &DataKeyExt::ConcentrationThresholds(asset_id)).unwrap_or(Vec::new(env))
+}
+
+fn current_level(env: &Env, asset_id: u64) -> u32 {
+    storage::get(env, &DataKeyExt::ConcentrationLevel(asset_id)).unwrap_or(0)
+}
+
+fn largest_share_bps(env: &Env, asset_id: u64, top: &Vec<HolderBalance>) -> u32 {
+    let largest = top.get(0).map_or(0, |entry| entry.balance);
+    share_bps(largest, balance::asset_supply(env.clone(), asset_id))
+}
+
+/// Number of thresholds `share` has reached
+fn level_of(thresholds: &Vec<u32>, share: u32) -> u32 {
+    thresholds
+        .iter()
+        .filter(|threshold| share >= *threshold)
+        .count() as u32
+}
+
+fn share_bps(amount: u64, supply: u64) -> u32 {
+    if supply == 0 {
+        return 0;
+    }
+    (amount as u128 * 10_000 / supply as u128) as u32
+}
@@ -0,0 +1,180 @@
+use crate::events;
+use crate::methods::{
+    archival, balance, basket, cap_table, checkpoints, concentration, issuance, split, utils,
+};
+use crate::storage::{self, ConsolidationProgress, DataKey, RemainderPolicy, SPLIT_PAGES_PER_CALL};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Divide every balance of an asset by `factor` (reverse split).
+///
+/// Holders are divided a few owner pages per call and the asset is frozen until
+/// `continue_consolidation` has covered every page; supply is set from the
+/// consolidated balances once the last page is done. Holders left with nothing
+/// are removed from the owner list. Returns true when the consolidation is complete.
+pub fn consolidate_asset(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    factor: u64,
+    policy: RemainderPolicy,
+) -> bool {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can consolidate");
+    }
+
+    if factor < 2 {
+        panic!("Consolidation factor must be at least 2");
+    }
+
+    split::require_not_restructuring(&env, asset_id);
+
+    events::emit_consolidation(&env, asset_id, factor, policy);
+
+    process_consolidation(
+        &env,
+        asset_id,
+        ConsolidationProgress {
+            factor,
+            policy,
+            next_page: 0,
+            new_supply: 0,
+        },
+    )
+}
+
+/// Anyone can push a pending consolidation forward; returns true when it is complete
+pub fn continue_consolidation(env: Env, asset_id: u64) -> bool {
+    let progress: ConsolidationProgress =
+        storage::get(&env, &DataKey::PendingConsolidation(asset_id))
+            .expect("No consolidation in progress");
+
+    process_consolidation(&env, asset_id, progress)
+}
+
+pub fn consolidation_factor(env: Env, asset_id: u64) -> u64 {
+    storage::get(&env, &DataKey::ConsolidationFactor(asset_id)).unwrap_or(1)
+}
+
+pub fn consolidation_in_progress(env: &Env, asset_id: u64) -> bool {
+    storage::has(env, &DataKey::PendingConsolidation(asset_id))
+}
+
+/// Pre-consolidation units an owner lost to rounding and has not claimed yet
+pub fn fraction_remainder(env: Env, owner: Address, asset_id: u64) -> u64 {
+    storage::get(&env, &DataKey::FractionRemainder(owner, asset_id)).unwrap_or(0)
+}
+
+pub fn fraction_remainder_total(env: Env, asset_id: u64) -> u64 {
+    storage::get(&env, &DataKey::FractionRemainderTotal(asset_id)).unwrap_or(0)
+}
+
+/// Clear an owner's remainder and return it so the caller (funding) can pay it out
+pub fn claim_fraction_remainder(env: Env, owner: Address, asset_id: u64) -> u64 {
+    owner.require_auth();
+
+    let units = fraction_remainder(env.clone(), owner.clone(), asset_id);
+    if units == 0 {
+        return 0;
+    }
+
+    storage::remove(&env, &DataKey::FractionRemainder(owner.clone(), asset_id));
+
+    let total = fraction_remainder_total(env.clone(), asset_id);
+    storage::set(
+        &env,
+        &DataKey::FractionRemainderTotal(asset_id),
+        &total.saturating_sub(units),
+    );
+
+    events::emit_remainder_claim(&env, owner, asset_id, units);
+
+    units
+}
+
+fn process_consolidation(env: &Env, asset_id: u64, mut progress: ConsolidationProgress) -> bool {
+    let page_count: u32 = storage::get(env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
+    let end_page = page_count.min(progress.next_page + SPLIT_PAGES_PER_CALL);
+    let mut remainder_units = 0u64;
+
+    for page_idx in progress.next_page..end_page {
+        // Owners removed below only rewrite this page, so iterating the loaded copy is safe
+        let page: Vec<Address> = storage::get(env, &DataKey::AssetOwnersPage(asset_id, page_idx))
+            .unwrap_or(Vec::new(env));
+
+        for owner in page.iter() {
+            let balance = balance::balance_of(env.clone(), owner.clone(), asset_id);
+            let remainder = balance % progress.factor;
+            let mut consolidated = balance / progress.factor;
+
+            match progress.policy {
+                RemainderPolicy::Compensate => {
+                    if remainder > 0 {
+                        let key = DataKey::FractionRemainder(owner.clone(), asset_id);
+                        let owed: u64 = storage::get(env, &key).unwrap_or(0);
+                        storage::set(env, &key, &(owed + remainder));
+                        archival::bump_persistent(env, &key);
+                        remainder_units += remainder;
+                    }
+                }
+                RemainderPolicy::RoundDown => {}
+                RemainderPolicy::RoundNearest => {
+                    if remainder >= progress.factor - remainder {
+                        consolidated += 1;
+                    }
+                }
+            }
+
+            cap_table::preserve_balance(env, &owner, asset_id);
+            storage::set(
+                env,
+                &DataKey::Balance(owner.clone(), asset_id),
+                &consolidated,
+            );
+            archival::bump_balance(env, &owner, asset_id);
+            checkpoints::rescale(env, &owner, asset_id, 1, progress.factor);
+            checkpoints::record(env, &owner, asset_id, consolidated);
+            concentration::track(env, &owner, asset_id, consolidated);
+            progress.new_supply += consolidated;
+
+            if consolidated == 0 {
+                utils::remove_owner_from_asset(env, asset_id, owner.clone());
+                utils::remove_asset_from_owner(env, owner.clone(), asset_id);
+            }
+        }
+    }
+
+    if remainder_units > 0 {
+        let total = fraction_remainder_total(env.clone(), asset_id);
+        storage::set(
+            env,
+            &DataKey::FractionRemainderTotal(asset_id),
+            &(total + remainder_units),
+        );
+    }
+
+    if end_page < page_count {
+        progress.next_page = end_page;
+        storage::set(env, &DataKey::PendingConsolidation(asset_id), &progress);
+        return false;
+    }
+
+    storage::remove(env, &DataKey::PendingConsolidation(asset_id));
+    storage::set(env, &DataKey::AssetSupply(asset_id), &progress.new_supply);
+    issuance::rescale_max_supply(env, asset_id, 1, progress.factor);
+
+    let cumulative = consolidation_factor(env.clone(), asset_id) * progress.factor;
+    storage::set(env, &DataKey::ConsolidationFactor(asset_id), &cumulative);
+
+    basket::rescale_holdings(env, asset_id, 1, progress.factor);
+
+    events::emit_consolidation_done(env, asset_id, progress.factor, progress.new_supply);
+    concentration::check(env, asset_id);
+
+    true
+}
@@ -0,0 +1,63 @@
+use crate::errors::FractcoreError;
+use crate::events;
+use crate::methods::{split, utils};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Permanently close issuance of an asset (asset manager only, which is the
+/// governance contract once management has been delegated to it).
+///
+/// Afterwards every mint path rejects the asset with `MintingFinalized`.
+pub fn finalize_minting(env: Env, caller: Address, asset_id: u64) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can finalize minting");
+    }
+
+    if is_minting_finalized(env.clone(), asset_id) {
+        panic_with_error!(&env, FractcoreError::MintingFinalized);
+    }
+
+    storage::set(&env, &DataKey::MintingFinalized(asset_id), &true);
+
+    events::emit_minting_finalized(&env, asset_id, caller);
+}
+
+pub fn is_minting_finalized(env: Env, asset_id: u64) -> bool {
+    storage::has(&env, &DataKey::MintingFinalized(asset_id))
+}
+
+/// Supply the asset can never exceed, if one was set at mint time
+pub fn max_supply(env: Env, asset_id: u64) -> Option<u64> {
+    storage::get(&env, &DataKey::MaxSupply(asset_id))
+}
+
+pub fn set_max_supply(env: &Env, asset_id: u64, max_supply: u64) {
+    storage::set(env, &DataKey::MaxSupply(asset_id), &max_supply);
+}
+
+/// Refuse issuance that would take the asset's supply to `new_supply`
+pub fn require_mintable(env: &Env, asset_id: u64, new_supply: u64) {
+    if is_minting_finalized(env.clone(), asset_id) {
+        panic_with_error!(env, FractcoreError::MintingFinalized);
+    }
+
+    if let Some(cap) = max_supply(env.clone(), asset_id) {
+        if new_supply > cap {
+            panic_with_error!(env, FractcoreError::MaxSupplyExceeded);
+        }
+    }
+}
+
+/// Keep the cap proportional to supply when balances are rescaled by a split
+/// (`multiplier`) or consolidation (`divisor`)
+pub fn rescale_max_supply(env: &Env, asset_id: u64, multiplier: u64, divisor: u64) {
+    if let Some(cap) = max_supply(env.clone(), asset_id) {
+        set_max_supply(env, asset_id, cap.saturating_mul(multiplier) / divisor);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::events;
+use crate::methods::{balance, split, utils};
+use crate::storage::{self, DataKey, LotteryDraw, MAX_DRAW_PAGES};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+/// Asset manager commits to a secret seed for a holdings-weighted draw by
+/// publishing its SHA-256. Returns the draw id.
+///
+/// The seed is only revealed in a later ledger, where it is mixed with the
+/// network PRNG: the manager cannot pick a seed once the PRNG output is known,
+/// and the PRNG alone does not decide the winner.
+pub fn commit_draw(env: Env, caller: Address, asset_id: u64, commitment: BytesN<32>) -> u32 {
+    require_manager(&env, &caller, asset_id);
+
+    let draw_id = draw_count(env.clone(), asset_id);
+    let draw = LotteryDraw {
+        commitment,
+        committed_ledger: env.ledger().sequence(),
+        winner: None,
+        ticket: 0,
+    };
+    storage::set(&env, &DataKey::LotteryDraw(asset_id, draw_id), &draw);
+    storage::set(&env, &DataKey::LotteryDrawCount(asset_id), &(draw_id + 1));
+
+    events::emit_draw_committed(&env, asset_id, draw_id, &draw.commitment);
+
+    draw_id
+}
+
+/// Reveal the committed seed and pick a holder with probability proportional to
+/// their balance. Fractions held by the contract itself (basket custody) hold
+/// no tickets.
+pub fn draw_winner(env: Env, caller: Address, asset_id: u64, draw_id: u32, seed: Bytes) -> Address {
+    require_manager(&env, &caller, asset_id);
+
+    let key = DataKey::LotteryDraw(asset_id, draw_id);
+    let mut draw: LotteryDraw = storage::get(&env, &key).expect("Draw not found");
+
+    if draw.winner.is_some() {
+        panic!("Winner already drawn");
+    }
+
+    if env.ledger().sequence() <= draw.committed_ledger {
+        panic!("Seed must be revealed in a later ledger than the commitment");
+    }
+
+    let revealed: BytesN<32> = env.crypto().sha256(&seed).into();
+    if revealed != draw.commitment {
+        panic!("Seed does not match the commitment");
+    }
+
+    // Mid-split balances are in mixed units
+    split::require_not_restructuring(&env, asset_id);
+
+    let page_count: u32 = storage::get(&env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
+    if page_count > MAX_DRAW_PAGES {
+        panic!("Too many holders for a draw");
+    }
+
+    let custody = env.current_contract_address();
+    let tickets = balance::asset_supply(env.clone(), asset_id)
+        - balance::balance_of(env.clone(), custody.clone(), asset_id);
+    if tickets == 0 {
+        panic!("No holders to draw from");
+    }
+
+    let entropy: BytesN<32> = env.prng().gen();
+    let mut material = Bytes::from_array(&env, &entropy.to_array());
+    material.append(&seed);
+    let mixed: BytesN<32> = env.crypto().sha256(&material).into();
+    env.prng().seed(mixed.into());
+    let ticket: u64 = env.prng().gen_range(0..tickets);
+
+    let winner = ticket_holder(&env, asset_id, page_count, &custody, ticket);
+
+    draw.winner = Some(winner.clone());
+    draw.ticket = ticket;
+    storage::set(&env, &key, &draw);
+
+    events::emit_draw_winner(&env, asset_id, draw_id, winner.clone(), ticket);
+
+    winner
+}
+
+pub fn get_draw(env: Env, asset_id: u64, draw_id: u32) -> Option<LotteryDraw> {
+    storage::get(&env, &DataKey::LotteryDraw(asset_id, draw_id))
+}
+
+pub fn draw_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::LotteryDrawCount(asset_id)).unwrap_or(0)
+}
+
+fn require_manager(env: &Env, caller: &Address, asset_id: u64) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if *caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can run draws");
+    }
+}
+
+/// Holder owning ticket number `ticket`, walking the owner pages in order
+fn ticket_holder(
+    env: &Env,
+    asset_id: u64,
+    page_count: u32,
+    custody: &Address,
+    ticket: u64,
+) -> Address {
+    let mut cumulative = 0u64;
+    for page_idx in 0..page_count {
+        let page: Vec<Address> = storage::get(env, &DataKey::AssetOwnersPage(asset_id, page_idx))
+            .unwrap_or(Vec::new(env));
+
+        for owner in page.iter() {
+            if owner == *custody {
+                continue;
+            }
+            cumulative += balance::balance_of(env.clone(), owner.clone(), asset_id);
+            if ticket < cumulative {
+                return owner;
+            }
+        }
+    }
+
+    panic!("Owner pages do not cover the supply");
+}
@@ -1,51 +1,244 @@
 use crate::events;
-use crate::methods::admin;
-use crate::methods::utils;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env, String};
+use crate::methods::{admin, admin_log};
+use crate::methods::{comanagement, split, utils};
+use crate::storage::{
+    self, DataKey, Placement, UriVersion, MAX_BASE_URI_LEN, MAX_CONTRACT_URI_LEN, MAX_URI_HISTORY,
+};
+use soroban_sdk::{Address, Env, String, Vec};
 
 pub fn set_asset_uri(env: Env, caller: Address, asset_id: u64, uri: String) {
+    require_uri_setter(&env, &caller, asset_id);
+
+    store_asset_uri(&env, asset_id, Some(uri.clone()), &caller);
+
+    events::emit_uri_update(&env, asset_id, uri);
+}
+
+/// Soft-delete an asset's URI; the removed value stays in the history and can be
+/// restored with `revert_asset_uri`
+pub fn remove_asset_uri(env: Env, caller: Address, asset_id: u64) {
+    require_uri_setter(&env, &caller, asset_id);
+
+    if stored_asset_uri(&env, asset_id).is_none() {
+        panic!("Asset has no URI");
+    }
+
+    let version = store_asset_uri(&env, asset_id, None, &caller);
+
+    events::emit_uri_removed(&env, asset_id, version);
+}
+
+/// Restore a version from the history (admin, creator or asset manager, so a
+/// governance contract managing the asset can revert by poll). The restored value
+/// is recorded as a new version.
+pub fn revert_asset_uri(env: Env, caller: Address, asset_id: u64, version: u32) {
     caller.require_auth();
 
     if !utils::asset_exists(env.clone(), asset_id) {
         panic!("Asset does not exist");
     }
 
-    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-    let creator: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetCreator(asset_id))
-        .unwrap();
-
-    if caller != admin && caller != creator {
+    if caller != admin::get_admin(env.clone())
+        && Some(caller.clone()) != get_asset_creator(env.clone(), asset_id)
+        && caller != split::asset_manager(env.clone(), asset_id)
+    {
         panic!("Not authorized to set URI");
     }
+    comanagement::require_not_co_managed(&env, asset_id);
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetURI(asset_id), &uri);
+    let restored = get_uri_history(env.clone(), asset_id)
+        .iter()
+        .find(|entry| entry.version == version)
+        .expect("URI version not found");
 
-    events::emit_uri_update(&env, asset_id, uri);
+    let new_version = store_asset_uri(&env, asset_id, restored.uri.clone(), &caller);
+
+    events::emit_uri_reverted(&env, asset_id, version, new_version, restored.uri);
 }
 
+/// The asset's own URI, or else the base URI with `{id}` filled in
 pub fn asset_uri(env: Env, asset_id: u64) -> Option<String> {
-    env.storage().persistent().get(&DataKey::AssetURI(asset_id))
+    if let Some(uri) = stored_asset_uri(&env, asset_id) {
+        return Some(uri);
+    }
+    if !utils::asset_exists(env.clone(), asset_id) {
+        return None;
+    }
+    base_uri(env.clone()).map(|template| resolve_template(&env, &template, asset_id))
 }
 
-pub fn set_contract_uri(env: Env, caller: Address, uri: String) {
-    admin::require_admin_auth(env.clone());
+pub fn stored_asset_uri(env: &Env, asset_id: u64) -> Option<String> {
+    storage::get(env, &DataKey::AssetURI(asset_id))
+}
+
+/// Set the URI template used by assets without their own URI (admin only).
+///
+/// Every `{id}` is replaced ERC1155-style by the asset id as 64 lowercase hex
+/// digits, zero-padded, e.g. `ipfs://cid/{id}.json`. `None` removes the template.
+pub fn set_base_uri(env: Env, template: Option<String>) {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(&env, &admin, "set_base_uri", (template.clone(),));
+
+    match template.clone() {
+        Some(template) => {
+            if template.is_empty() || template.len() > MAX_BASE_URI_LEN {
+                panic!("Invalid base URI length");
+            }
+            storage::set(&env, &DataKey::BaseURI, &template);
+        }
+        None => storage::remove(&env, &DataKey::BaseURI),
+    }
+    storage::remove_moved(&env, &DataKey::BaseURI, Placement::Persistent);
+
+    events::emit_base_uri(&env, template);
+}
+
+pub fn base_uri(env: Env) -> Option<String> {
+    contract_setting(&env, &DataKey::BaseURI)
+}
+
+/// Copy the template, writing the hex-padded asset id in place of each `{id}`
+fn resolve_template(env: &Env, template: &String, asset_id: u64) -> String {
+    const PLACEHOLDER: &[u8] = b"{id}";
+    const HEX: &[u8] = b"0123456789abcdef";
+
+    let len = template.len() as usize;
+    let mut source = [0u8; MAX_BASE_URI_LEN as usize];
+    template.copy_into_slice(&mut source[..len]);
+
+    // Each 4-byte placeholder grows to 64 bytes
+    let mut resolved = [0u8; MAX_BASE_URI_LEN as usize * 16];
+    let mut written = 0;
+    let mut i = 0;
+    while i < len {
+        if source[i..len].starts_with(PLACEHOLDER) {
+            let digits = &mut resolved[written..written + 64];
+            digits.fill(b'0');
+            for (shift, digit) in digits[48..].iter_mut().rev().enumerate() {
+                *digit = HEX[((asset_id >> (shift * 4)) & 0xf) as usize];
+            }
+            written += 64;
+            i += PLACEHOLDER.len();
+        } else {
+            resolved[written] = source[i];
+            written += 1;
+            i += 1;
+        }
+    }
+
+    String::from_bytes(env, &resolved[..written])
+}
+
+/// Last `MAX_URI_HISTORY` values of an asset's URI, oldest first; the last entry
+/// is the current one
+pub fn get_uri_history(env: Env, asset_id: u64) -> Vec<UriVersion> {
+    storage::get(&env, &DataKey::AssetURIHistory(asset_id)).unwrap_or(Vec::new(&env))
+}
+
+/// Write the URI (None removes it) and append it to the history; returns its version.
+///
+/// A URI set before history was kept is recorded first as version 0, attributed
+/// to the asset creator with timestamp 0, so it can still be restored.
+pub fn store_asset_uri(env: &Env, asset_id: u64, uri: Option<String>, setter: &Address) -> u32 {
+    let mut history = get_uri_history(env.clone(), asset_id);
+    if history.is_empty() {
+        if let Some(legacy) = stored_asset_uri(env, asset_id) {
+            history.push_back(UriVersion {
+                version: 0,
+                uri: Some(legacy),
+                set_by: get_asset_creator(env.clone(), asset_id).unwrap_or(setter.clone()),
+                set_at: 0,
+            });
+        }
+    }
+
+    let version = match history.last() {
+        Some(latest) => latest.version + 1,
+        None => 0,
+    };
+    history.push_back(UriVersion {
+        version,
+        uri: uri.clone(),
+        set_by: setter.clone(),
+        set_at: env.ledger().timestamp(),
+    });
+    while history.len() > MAX_URI_HISTORY {
+        history.pop_front();
+    }
+
+    storage::set(env, &DataKey::AssetURIHistory(asset_id), &history);
+    match uri {
+        Some(uri) => storage::set(env, &DataKey::AssetURI(asset_id), &uri),
+        None => storage::remove(env, &DataKey::AssetURI(asset_id)),
+    }
+
+    version
+}
+
+/// URIs are set by the admin or the asset's creator, unless the asset is co-managed
+fn require_uri_setter(env: &Env, caller: &Address, asset_id: u64) {
     caller.require_auth();
 
-    env.storage().persistent().set(&DataKey::ContractURI, &uri);
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    let admin: Address = storage::get(env, &DataKey::Admin).unwrap();
+    let creator: Address = storage::get(env, &DataKey::AssetCreator(asset_id)).unwrap();
+
+    if *caller != admin && *caller != creator {
+        panic!("Not authorized to set URI");
+    }
+    comanagement::require_not_co_managed(env, asset_id);
+}
+
+/// Set the contract-level metadata URI (admin only)
+pub fn set_contract_uri(env: Env, caller: Address, uri: String) {
+    admin::require_admin_caller(&env, &caller);
+    admin_log::record(&env, &caller, "set_contract_uri", (uri.clone(),));
+
+    if uri.len() > MAX_CONTRACT_URI_LEN {
+        panic!("Invalid contract URI length");
+    }
+
+    storage::set(&env, &DataKey::ContractURI, &uri);
+    storage::remove_moved(&env, &DataKey::ContractURI, Placement::Persistent);
 }
 
 pub fn contract_uri(env: Env) -> Option<String> {
-    env.storage().persistent().get(&DataKey::ContractURI)
+    contract_setting(&env, &DataKey::ContractURI)
+}
+
+/// Contract-wide URIs moved to instance storage; ones written before the move
+/// are still read from persistent storage until they are next set
+fn contract_setting(env: &Env, key: &DataKey) -> Option<String> {
+    storage::get(env, key).or_else(|| storage::get_moved(env, key, Placement::Persistent))
 }
 
 pub fn get_asset_creator(env: Env, asset_id: u64) -> Option<Address> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetCreator(asset_id))
+    storage::get(&env, &DataKey::AssetCreator(asset_id))
+}
+
+/// Admin override of an asset's creator, e.g. to migrate assets minted before
+/// `mint_for` existed (which all recorded the admin) to their true issuer
+pub fn set_asset_creator(env: Env, asset_id: u64, creator: Address) {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(
+        &env,
+        &admin,
+        "set_asset_creator",
+        (asset_id, creator.clone()),
+    );
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if creator == env.current_contract_address() {
+        panic!("Invalid creator address");
+    }
+
+    storage::set(&env, &DataKey::AssetCreator(asset_id), &creator);
+
+    events::emit_creator_update(&env, asset_id, creator);
 }
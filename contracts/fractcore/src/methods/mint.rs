@@ -1,64 +1,150 @@
+use crate::errors::FractcoreError;
 use crate::events;
-use crate::methods::{admin, balance, utils};
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env, Vec};
+use crate::methods::{
+    admin, admin_log, archival, balance, basket, cap_table, categories, checkpoints, concentration,
+    issuance, metadata, split, utils,
+};
+use crate::storage::{self, DataKey, EventLevel};
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, BytesN, Env, String, Symbol, Vec};
 
-pub fn initialize(env: Env, admin: Address) {
+/// Set the admin; fails with `AlreadyInitialized` rather than trapping so
+/// re-run deployment pipelines can carry on
+pub fn initialize(env: Env, admin: Address) -> Result<(), FractcoreError> {
+    admin.require_auth();
+    configure(env, admin)
+}
+
+/// Deploy-time initialization. The constructor runs atomically with contract
+/// creation, so no one can initialize first and the admin's auth is not needed.
+pub fn construct(env: Env, admin: Address) -> Result<(), FractcoreError> {
+    configure(env, admin)
+}
+
+fn configure(env: Env, admin: Address) -> Result<(), FractcoreError> {
     // Reentrancy protection
-    if env.storage().instance().has(&DataKey::Admin) {
-        panic!("Contract already initialized");
+    if storage::has_or_bare(&env, &DataKey::Admin) {
+        return Err(FractcoreError::AlreadyInitialized);
     }
 
-    admin.require_auth();
+    // Nothing was stored before namespacing, so lookups need no bare-key fallback
+    storage::mark_namespaced(&env);
+
+    storage::set(&env, &DataKey::Admin, &admin);
 
-    env.storage().instance().set(&DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::NextAssetId, &1u64);
 
-    env.storage().instance().set(&DataKey::NextAssetId, &1u64);
+    let config_hash: BytesN<32> = env.crypto().sha256(&(admin.clone(),).to_xdr(&env)).into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
 
     events::emit_init(&env, admin);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
 }
 
 pub fn mint(env: Env, to: Address, num_tokens: u64) -> u64 {
-    admin::require_admin_auth(env.clone());
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(&env, &admin, "mint", (to.clone(), num_tokens));
 
-    if num_tokens == 0 {
-        panic!("Cannot mint 0 tokens");
+    issue(&env, &admin, to, num_tokens)
+}
+
+/// Mint a new asset whose supply can never exceed `max_supply`, across every
+/// later `mint_to` and split-adjusted for restructurings
+pub fn mint_with_max_supply(env: Env, to: Address, num_tokens: u64, max_supply: u64) -> u64 {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(
+        &env,
+        &admin,
+        "mint_with_max_supply",
+        (to.clone(), num_tokens, max_supply),
+    );
+
+    if num_tokens > max_supply {
+        panic_with_error!(&env, FractcoreError::MaxSupplyExceeded);
     }
 
-    let asset_id: u64 = env
-        .storage()
-        .instance()
-        .get(&DataKey::NextAssetId)
-        .unwrap_or(1);
+    let asset_id = issue(&env, &admin, to, num_tokens);
+    issuance::set_max_supply(&env, asset_id, max_supply);
 
-    env.storage()
-        .instance()
-        .set(&DataKey::NextAssetId, &(asset_id + 1));
+    events::emit_max_supply(&env, asset_id, max_supply);
+
+    asset_id
+}
+
+/// Mint a new asset listed under `category` for marketplace browsing
+pub fn mint_with_category(env: Env, to: Address, num_tokens: u64, category: Symbol) -> u64 {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(
+        &env,
+        &admin,
+        "mint_with_category",
+        (to.clone(), num_tokens, category.clone()),
+    );
+
+    let asset_id = issue(&env, &admin, to, num_tokens);
+    categories::store_category(&env, asset_id, Some(category));
+
+    asset_id
+}
+
+/// Mint a new asset on behalf of an issuer, who is recorded as its creator.
+///
+/// The issuer must co-sign so an asset cannot be attributed to them without consent.
+pub fn mint_for(env: Env, creator: Address, to: Address, num_tokens: u64) -> u64 {
+    let admin = admin::require_admin_auth(env.clone());
+    creator.require_auth();
+    admin_log::record(
+        &env,
+        &admin,
+        "mint_for",
+        (creator.clone(), to.clone(), num_tokens),
+    );
+
+    if creator == env.current_contract_address() {
+        panic!("Invalid creator address");
+    }
+
+    issue(&env, &creator, to, num_tokens)
+}
+
+fn issue(env: &Env, creator: &Address, to: Address, num_tokens: u64) -> u64 {
+    if num_tokens == 0 {
+        panic!("Cannot mint 0 tokens");
+    }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone(), asset_id), &num_tokens);
+    let asset_id = create_asset(env, creator);
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetSupply(asset_id), &num_tokens);
+    storage::set(env, &DataKey::Balance(to.clone(), asset_id), &num_tokens);
+    archival::bump_balance(env, &to, asset_id);
+    checkpoints::record(env, &to, asset_id, num_tokens);
+    concentration::track(env, &to, asset_id, num_tokens);
 
-    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetCreator(asset_id), &admin);
+    storage::set(env, &DataKey::AssetSupply(asset_id), &num_tokens);
 
-    utils::add_owner_to_asset(&env, asset_id, to.clone());
-    utils::add_asset_to_owner(&env, to.clone(), asset_id);
+    utils::add_owner_to_asset(env, asset_id, to.clone());
+    utils::add_asset_to_owner(env, to.clone(), asset_id);
 
-    events::emit_mint(&env, to, asset_id, num_tokens);
+    events::emit_mint(env, to, asset_id, num_tokens);
+    concentration::check(env, asset_id);
 
     asset_id
 }
 
 /// Allows minting to multiple recipients of an existing asset
 pub fn mint_to(env: Env, asset_id: u64, recipients: Vec<Address>, amounts: Vec<u64>) {
-    admin::require_admin_auth(env.clone());
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(
+        &env,
+        &admin,
+        "mint_to",
+        (asset_id, recipients.clone(), amounts.clone()),
+    );
 
     if asset_id == 0 {
         panic!("Asset ID cannot be 0 - use mint() to create new assets");
@@ -68,44 +154,124 @@ pub fn mint_to(env: Env, asset_id: u64, recipients: Vec<Address>, amounts: Vec<u
         panic!("Asset does not exist");
     }
 
+    split::require_not_restructuring(&env, asset_id);
+
+    if basket::is_basket(env.clone(), asset_id) {
+        panic!("Basket tokens are only minted by deposit");
+    }
+
+    let total_minted = credit_recipients(&env, asset_id, &recipients, &amounts);
+
+    // Update total supply; a breach reverts the credits above
+    let new_supply = balance::asset_supply(env.clone(), asset_id) + total_minted;
+    issuance::require_mintable(&env, asset_id, new_supply);
+    storage::set(&env, &DataKey::AssetSupply(asset_id), &new_supply);
+
+    if admin::event_level(&env) != EventLevel::Full {
+        events::emit_mint_summary(&env, asset_id, total_minted, recipients.len());
+    }
+    concentration::check(&env, asset_id);
+}
+
+/// Create an asset with its whole initial distribution (and optional URI) in one call,
+/// so supply never reflects a half-distributed asset
+pub fn mint_distributed(
+    env: Env,
+    recipients: Vec<Address>,
+    amounts: Vec<u64>,
+    uri: Option<String>,
+) -> u64 {
+    let admin = admin::require_admin_auth(env.clone());
+    admin_log::record(
+        &env,
+        &admin,
+        "mint_distributed",
+        (recipients.clone(), amounts.clone(), uri.clone()),
+    );
+
+    let asset_id = create_asset(&env, &admin);
+    let total_minted = credit_recipients(&env, asset_id, &recipients, &amounts);
+
+    storage::set(&env, &DataKey::AssetSupply(asset_id), &total_minted);
+
+    if let Some(uri) = uri {
+        metadata::store_asset_uri(&env, asset_id, Some(uri.clone()), &admin);
+        if admin::event_level(&env) != EventLevel::Minimal {
+            events::emit_uri_update(&env, asset_id, uri);
+        }
+    }
+
+    events::emit_mint_distributed(&env, asset_id, total_minted, recipients.len());
+    concentration::check(&env, asset_id);
+
+    asset_id
+}
+
+/// Allocate the next asset id and record its creator
+pub fn create_asset(env: &Env, creator: &Address) -> u64 {
+    let asset_id: u64 = storage::get(env, &DataKey::NextAssetId).unwrap_or(1);
+
+    storage::set(env, &DataKey::NextAssetId, &(asset_id + 1));
+
+    storage::set(env, &DataKey::AssetCreator(asset_id), creator);
+
+    asset_id
+}
+
+/// Add `amounts` to the recipients' balances and owner lists; returns the total minted.
+///
+/// Per-recipient `mint_to` events are only published at `EventLevel::Full`.
+pub fn credit_recipients(
+    env: &Env,
+    asset_id: u64,
+    recipients: &Vec<Address>,
+    amounts: &Vec<u64>,
+) -> u64 {
     if recipients.len() != amounts.len() {
         panic!("Recipients and amounts length mismatch");
     }
 
-    if recipients.len() == 0 {
+    if recipients.is_empty() {
         panic!("No recipients specified");
     }
 
+    utils::require_batch_size(env, recipients.len());
+
+    if amounts.contains(0) {
+        panic!("Cannot mint 0 tokens");
+    }
+
+    let per_recipient_events = admin::event_level(env) == EventLevel::Full;
     let mut total_minted = 0u64;
 
     for i in 0..recipients.len() {
         let recipient = recipients.get(i).unwrap();
         let amount = amounts.get(i).unwrap();
 
-        if amount == 0 {
-            panic!("Cannot mint 0 tokens");
-        }
-
         let current_balance = balance::balance_of(env.clone(), recipient.clone(), asset_id);
-        env.storage().persistent().set(
+        cap_table::preserve_balance(env, &recipient, asset_id);
+        storage::set(
+            env,
             &DataKey::Balance(recipient.clone(), asset_id),
             &(current_balance + amount),
         );
+        archival::bump_balance(env, &recipient, asset_id);
+        checkpoints::record(env, &recipient, asset_id, current_balance + amount);
+        concentration::track(env, &recipient, asset_id, current_balance + amount);
 
         if current_balance == 0 {
-            utils::add_owner_to_asset(&env, asset_id, recipient.clone());
-            utils::add_asset_to_owner(&env, recipient.clone(), asset_id);
+            utils::add_owner_to_asset(env, asset_id, recipient.clone());
+            utils::add_asset_to_owner(env, recipient.clone(), asset_id);
         }
 
-        total_minted += amount;
+        total_minted = total_minted
+            .checked_add(amount)
+            .expect("Minted amount overflows supply");
 
-        events::emit_mint_to(&env, recipient, asset_id, amount);
+        if per_recipient_events {
+            events::emit_mint_to(env, recipient, asset_id, amount);
+        }
     }
 
-    // Update total supply
-    let current_supply = balance::asset_supply(env.clone(), asset_id);
-    env.storage().persistent().set(
-        &DataKey::AssetSupply(asset_id),
-        &(current_supply + total_minted),
-    );
+    total_minted
 }
@@ -1,8 +1,24 @@
 pub mod admin;
+pub mod admin_log;
+pub mod announcements;
 pub mod approval;
+pub mod archival;
 pub mod balance;
+pub mod basket;
+pub mod cap_table;
+pub mod categories;
+pub mod checkpoints;
+pub mod comanagement;
+pub mod concentration;
+pub mod consolidation;
+pub mod issuance;
+pub mod lottery;
 pub mod metadata;
 pub mod mint;
 pub mod ownership;
+pub mod recipients;
+pub mod recovery;
+pub mod split;
 pub mod transfer;
+pub mod transfer_lock;
 pub mod utils;
@@ -1,43 +1,41 @@
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env, Vec};
+use crate::errors::FractcoreError;
+use crate::methods::utils;
+use crate::storage::{self, Cursor, DataKey, MAX_ASSET_OWNERS_PAGES};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 pub fn get_asset_owner_count(env: Env, asset_id: u64) -> u32 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerCount(asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::AssetOwnerCount(asset_id)).unwrap_or(0)
 }
 
 pub fn owns_asset(env: Env, owner: Address, asset_id: u64) -> bool {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerExists(asset_id, owner))
-        .unwrap_or(false)
+    storage::get(&env, &DataKey::AssetOwnerExists(asset_id, owner)).unwrap_or(false)
 }
 
 pub fn has_assets(env: Env, owner: Address, asset_id: u64) -> bool {
-    env.storage()
-        .persistent()
-        .get(&DataKey::OwnerAssetExists(owner, asset_id))
-        .unwrap_or(false)
+    storage::get(&env, &DataKey::OwnerAssetExists(owner, asset_id)).unwrap_or(false)
 }
 
+/// Every owner of an asset spread over at most MAX_ASSET_OWNERS_PAGES owner
+/// pages, kept for existing callers.
+///
+/// The owner pages are the only owner list, and a single Vec of every owner of a
+/// large asset would exceed the host's value limits, so larger assets fail with
+/// `TooManyOwners` instead of returning part of the list; use
+/// `asset_owners_page` to read every owner.
 pub fn asset_owners(env: Env, asset_id: u64) -> Vec<Address> {
-    let page_count: u32 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerPageCount(asset_id))
-        .unwrap_or(0);
+    let page_count: u32 =
+        storage::get::<DataKey, u32>(&env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
+    if page_count > MAX_ASSET_OWNERS_PAGES {
+        panic_with_error!(&env, FractcoreError::TooManyOwners);
+    }
 
     let mut all_owners = Vec::new(&env);
 
-    // Collect owners from all pages
     for page_idx in 0..page_count {
-        if let Some(page) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::AssetOwnersPage(asset_id, page_idx))
-        {
+        if let Some(page) = storage::get::<DataKey, Vec<Address>>(
+            &env,
+            &DataKey::AssetOwnersPage(asset_id, page_idx),
+        ) {
             for i in 0..page.len() {
                 all_owners.push_back(page.get(i).unwrap());
             }
@@ -47,21 +45,84 @@ pub fn asset_owners(env: Env, asset_id: u64) -> Vec<Address> {
     all_owners
 }
 
+/// Up to `limit` owners of an asset from `cursor`, in owner page order.
+///
+/// New owners only fill free slots at the end of a page, but a departing owner
+/// moves the rest of its page up. The cursor records how many owners had left
+/// when it was issued; if more have left since, the walk re-reads the cursor's
+/// page from its start, so an owner who held throughout is never skipped (one
+/// already returned may come again).
+pub fn asset_owners_page(
+    env: Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<Address>, Option<Cursor>) {
+    let removals: u64 = storage::get(&env, &DataKey::AssetOwnerRemovals(asset_id)).unwrap_or(0);
+    let (page, position) = match cursor {
+        None => (0, 0),
+        Some(cursor) if cursor.snapshot != removals => (cursor.page, 0),
+        Some(cursor) => (cursor.page, cursor.position),
+    };
+    let page_count: u32 = storage::get(&env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
+
+    let (owners, next) = walk_owner_pages(
+        &env,
+        page,
+        position,
+        page_count,
+        utils::page_limit(limit),
+        |page_idx| {
+            storage::get(&env, &DataKey::AssetOwnersPage(asset_id, page_idx))
+                .unwrap_or(Vec::new(&env))
+        },
+    );
+
+    (
+        owners,
+        next.map(|(page, position)| Cursor {
+            page,
+            position,
+            snapshot: removals,
+        }),
+    )
+}
+
+/// Up to `limit` owners from `position` of owner page `page` onwards, reading
+/// pages below `page_count` with `read_page`, and the (page, position) to resume
+/// from, or None once the last page was read
+pub fn walk_owner_pages(
+    env: &Env,
+    mut page: u32,
+    mut position: u32,
+    page_count: u32,
+    limit: u32,
+    read_page: impl Fn(u32) -> Vec<Address>,
+) -> (Vec<Address>, Option<(u32, u32)>) {
+    let mut owners = Vec::new(env);
+    while page < page_count {
+        let current = read_page(page);
+        while position < current.len() {
+            if owners.len() == limit {
+                return (owners, Some((page, position)));
+            }
+            owners.push_back(current.get_unchecked(position));
+            position += 1;
+        }
+        page += 1;
+        position = 0;
+    }
+
+    (owners, None)
+}
+
 pub fn owner_assets(env: Env, owner: Address) -> Vec<u64> {
-    let next_asset_id = env
-        .storage()
-        .instance()
-        .get(&DataKey::NextAssetId)
-        .unwrap_or(1);
+    let next_asset_id = storage::get(&env, &DataKey::NextAssetId).unwrap_or(1);
 
     let mut owned_assets = Vec::new(&env);
 
     for asset_id in 1..next_asset_id {
-        if env
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerAssetExists(owner.clone(), asset_id))
-            .unwrap_or(false)
+        if storage::get(&env, &DataKey::OwnerAssetExists(owner.clone(), asset_id)).unwrap_or(false)
         {
             owned_assets.push_back(asset_id);
         }
@@ -0,0 +1,78 @@
+use crate::events;
+use crate::methods::{admin, admin_log, balance, basket, transfer};
+use crate::storage::{self, DataKey, DataKeyExt, MAX_DENIED_RECIPIENTS};
+use soroban_sdk::{vec, Address, Env, Vec};
+
+/// Replace the addresses transfers may not send fractions to (admin only).
+///
+/// Until it is set the list holds this contract alone. Contracts that take
+/// custody through a plain `transfer`, such as trading escrows, must not be
+/// listed; fractions sent to them by mistake are recovered with their own
+/// rescue entrypoint instead.
+pub fn set_denied_recipients(env: Env, caller: Address, recipients: Vec<Address>) {
+    admin::require_admin_caller(&env, &caller);
+    admin_log::record(
+        &env,
+        &caller,
+        "set_denied_recipients",
+        (recipients.clone(),),
+    );
+
+    if recipients.len() > MAX_DENIED_RECIPIENTS {
+        panic!("Too many denied recipients");
+    }
+
+    storage::set(&env, &DataKeyExt::DeniedRecipients, &recipients);
+
+    events::emit_denied_recipients(&env, recipients);
+}
+
+pub fn get_denied_recipients(env: Env) -> Vec<Address> {
+    storage::get(&env, &DataKeyExt::DeniedRecipients)
+        .unwrap_or_else(|| vec![&env, env.current_contract_address()])
+}
+
+pub fn require_accepted(env: &Env, to: &Address) {
+    if get_denied_recipients(env.clone()).contains(to) {
+        panic!("Recipient does not accept fractions");
+    }
+}
+
+/// Fractions of an asset this contract holds beyond what baskets account for
+pub fn rescuable(env: Env, asset_id: u64) -> u64 {
+    let held = balance::balance_of(env.clone(), env.current_contract_address(), asset_id);
+
+    let baskets: Vec<u64> =
+        storage::get(&env, &DataKey::AssetBaskets(asset_id)).unwrap_or(Vec::new(&env));
+    let mut custody = 0u64;
+    for basket_id in baskets.iter() {
+        custody += basket::holding(&env, basket_id, asset_id);
+    }
+
+    held.saturating_sub(custody)
+}
+
+/// Admin returns fractions sent to this contract by mistake
+pub fn rescue_fractions(env: Env, caller: Address, asset_id: u64, to: Address, amount: u64) {
+    admin::require_admin_caller(&env, &caller);
+    admin_log::record(
+        &env,
+        &caller,
+        "rescue_fractions",
+        (asset_id, to.clone(), amount),
+    );
+
+    if amount > rescuable(env.clone(), asset_id) {
+        panic!("Amount exceeds rescuable balance");
+    }
+
+    transfer::transfer_internal(
+        env.clone(),
+        env.current_contract_address(),
+        to.clone(),
+        asset_id,
+        amount,
+    );
+
+    events::emit_rescued(&env, asset_id, to, amount);
+}
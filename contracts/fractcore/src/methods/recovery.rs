@@ -0,0 +1,171 @@
+use crate::events;
+use crate::methods::{balance, ownership, transfer};
+use crate::storage::{
+    self, DataKey, GuardianConfig, RecoveryRequest, MAX_GUARDIANS, RECOVERY_CHALLENGE_DELAY,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Holder opts into social recovery by naming guardians and how many must approve.
+///
+/// An empty guardian list opts out. Guardians cannot be changed while a recovery
+/// is pending; the holder cancels it first.
+pub fn set_guardians(env: Env, holder: Address, guardians: Vec<Address>, threshold: u32) {
+    holder.require_auth();
+
+    if get_recovery(env.clone(), holder.clone()).is_some() {
+        panic!("Recovery in progress");
+    }
+
+    if guardians.is_empty() {
+        storage::remove(&env, &DataKey::Guardians(holder.clone()));
+        events::emit_guardians(&env, holder, 0, 0);
+        return;
+    }
+
+    if guardians.len() > MAX_GUARDIANS {
+        panic!("Too many guardians");
+    }
+    if threshold == 0 || threshold > guardians.len() {
+        panic!("Invalid guardian threshold");
+    }
+    for (i, guardian) in guardians.iter().enumerate() {
+        if guardian == holder {
+            panic!("Holder cannot be their own guardian");
+        }
+        if guardians.iter().skip(i + 1).any(|other| other == guardian) {
+            panic!("Duplicate guardian");
+        }
+    }
+
+    let count = guardians.len();
+    storage::set(
+        &env,
+        &DataKey::Guardians(holder.clone()),
+        &GuardianConfig {
+            guardians,
+            threshold,
+        },
+    );
+
+    events::emit_guardians(&env, holder, count, threshold);
+}
+
+pub fn get_guardians(env: Env, holder: Address) -> Option<GuardianConfig> {
+    storage::get(&env, &DataKey::Guardians(holder))
+}
+
+pub fn get_recovery(env: Env, holder: Address) -> Option<RecoveryRequest> {
+    storage::get(&env, &DataKey::Recovery(holder))
+}
+
+/// A guardian proposes moving the holder's balances to `new_address`; the
+/// proposal counts as that guardian's approval
+pub fn propose_recovery(env: Env, guardian: Address, holder: Address, new_address: Address) {
+    guardian.require_auth();
+
+    let config = require_guardian(&env, &guardian, &holder);
+
+    if get_recovery(env.clone(), holder.clone()).is_some() {
+        panic!("Recovery already proposed");
+    }
+    if new_address == holder {
+        panic!("New address must differ from the holder");
+    }
+
+    let mut request = RecoveryRequest {
+        new_address: new_address.clone(),
+        approvals: Vec::new(&env),
+        proposed_at: env.ledger().timestamp(),
+        executable_at: None,
+    };
+    add_approval(&env, &mut request, &config, guardian.clone());
+    storage::set(&env, &DataKey::Recovery(holder.clone()), &request);
+
+    events::emit_recovery_proposed(&env, holder, guardian, new_address);
+}
+
+/// Another guardian approves the pending recovery. Reaching the threshold starts
+/// the challenge delay during which the holder can still cancel.
+pub fn approve_recovery(env: Env, guardian: Address, holder: Address) {
+    guardian.require_auth();
+
+    let config = require_guardian(&env, &guardian, &holder);
+    let mut request = get_recovery(env.clone(), holder.clone()).expect("No recovery proposed");
+
+    if request.approvals.contains(&guardian) {
+        panic!("Guardian already approved");
+    }
+
+    add_approval(&env, &mut request, &config, guardian.clone());
+    storage::set(&env, &DataKey::Recovery(holder.clone()), &request);
+
+    events::emit_recovery_approved(&env, holder, guardian, request.approvals.len());
+}
+
+/// The holder's current key rejects a pending recovery
+pub fn cancel_recovery(env: Env, holder: Address) {
+    holder.require_auth();
+
+    if get_recovery(env.clone(), holder.clone()).is_none() {
+        panic!("No recovery proposed");
+    }
+
+    storage::remove(&env, &DataKey::Recovery(holder.clone()));
+
+    events::emit_recovery_cancelled(&env, holder);
+}
+
+/// Anyone can complete an approved recovery once the challenge delay has passed.
+/// Every asset balance moves to the new address and the guardian setup is cleared.
+/// Returns the number of assets migrated.
+pub fn execute_recovery(env: Env, holder: Address) -> u32 {
+    let request = get_recovery(env.clone(), holder.clone()).expect("No recovery proposed");
+
+    match request.executable_at {
+        Some(executable_at) if env.ledger().timestamp() >= executable_at => {}
+        Some(_) => panic!("Challenge delay has not passed"),
+        None => panic!("Recovery not approved"),
+    }
+
+    storage::remove(&env, &DataKey::Recovery(holder.clone()));
+    storage::remove(&env, &DataKey::Guardians(holder.clone()));
+
+    let mut migrated = 0u32;
+    for asset_id in ownership::owner_assets(env.clone(), holder.clone()).iter() {
+        let amount = balance::balance_of(env.clone(), holder.clone(), asset_id);
+        if amount > 0 {
+            transfer::transfer_internal(
+                env.clone(),
+                holder.clone(),
+                request.new_address.clone(),
+                asset_id,
+                amount,
+            );
+            migrated += 1;
+        }
+    }
+
+    events::emit_recovered(&env, holder, request.new_address, migrated);
+
+    migrated
+}
+
+fn require_guardian(env: &Env, guardian: &Address, holder: &Address) -> GuardianConfig {
+    let config = get_guardians(env.clone(), holder.clone()).expect("Holder has no guardians");
+    if !config.guardians.contains(guardian) {
+        panic!("Not a guardian of this holder");
+    }
+    config
+}
+
+fn add_approval(
+    env: &Env,
+    request: &mut RecoveryRequest,
+    config: &GuardianConfig,
+    guardian: Address,
+) {
+    request.approvals.push_back(guardian);
+    if request.executable_at.is_none() && request.approvals.len() >= config.threshold {
+        request.executable_at = Some(env.ledger().timestamp() + RECOVERY_CHALLENGE_DELAY);
+    }
+}
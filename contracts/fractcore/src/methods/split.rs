@@ -0,0 +1,142 @@
+use crate::events;
+use crate::methods::{
+    archival, balance, basket, cap_table, checkpoints, concentration, consolidation, issuance,
+    utils,
+};
+use crate::storage::{self, DataKey, SplitProgress, SPLIT_PAGES_PER_CALL};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Address allowed to restructure an asset: the configured manager, or its creator
+pub fn asset_manager(env: Env, asset_id: u64) -> Address {
+    if let Some(manager) = storage::get(&env, &DataKey::AssetManager(asset_id)) {
+        return manager;
+    }
+
+    storage::get(&env, &DataKey::AssetCreator(asset_id)).expect("Asset does not exist")
+}
+
+/// Hand asset management to another address, typically the governance contract
+pub fn set_asset_manager(env: Env, caller: Address, asset_id: u64, manager: Address) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    let admin: Address = storage::get(&env, &DataKey::Admin).unwrap();
+    if caller != admin && caller != asset_manager(env.clone(), asset_id) {
+        panic!("Not authorized to set asset manager");
+    }
+
+    storage::set(&env, &DataKey::AssetManager(asset_id), &manager);
+
+    events::emit_manager_update(&env, asset_id, manager);
+}
+
+/// Multiply every balance and the supply of an asset by `factor`.
+///
+/// Supply is updated immediately; holders are rescaled a few owner pages per call
+/// and the asset is frozen until `continue_split` has covered every page.
+/// Returns true when the split is complete.
+pub fn split_asset(env: Env, caller: Address, asset_id: u64, factor: u64) -> bool {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can split");
+    }
+
+    if factor < 2 {
+        panic!("Split factor must be at least 2");
+    }
+
+    require_not_restructuring(&env, asset_id);
+
+    let supply = balance::asset_supply(env.clone(), asset_id);
+    let new_supply = supply
+        .checked_mul(factor)
+        .expect("Split overflows asset supply");
+    storage::set(&env, &DataKey::AssetSupply(asset_id), &new_supply);
+    issuance::rescale_max_supply(&env, asset_id, factor, 1);
+
+    events::emit_split(&env, asset_id, factor, new_supply);
+
+    process_split(
+        &env,
+        asset_id,
+        SplitProgress {
+            factor,
+            next_page: 0,
+        },
+    )
+}
+
+/// Anyone can push a pending split forward; returns true when it is complete
+pub fn continue_split(env: Env, asset_id: u64) -> bool {
+    let progress: SplitProgress =
+        storage::get(&env, &DataKey::PendingSplit(asset_id)).expect("No split in progress");
+
+    process_split(&env, asset_id, progress)
+}
+
+pub fn split_factor(env: Env, asset_id: u64) -> u64 {
+    storage::get(&env, &DataKey::SplitFactor(asset_id)).unwrap_or(1)
+}
+
+pub fn split_in_progress(env: &Env, asset_id: u64) -> bool {
+    storage::has(env, &DataKey::PendingSplit(asset_id))
+}
+
+/// Balances cannot move while holders are only partly rescaled
+pub fn require_not_restructuring(env: &Env, asset_id: u64) {
+    if split_in_progress(env, asset_id) {
+        panic!("Asset split in progress");
+    }
+    if consolidation::consolidation_in_progress(env, asset_id) {
+        panic!("Asset consolidation in progress");
+    }
+}
+
+fn process_split(env: &Env, asset_id: u64, mut progress: SplitProgress) -> bool {
+    let page_count: u32 = storage::get(env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
+    let end_page = page_count.min(progress.next_page + SPLIT_PAGES_PER_CALL);
+
+    for page_idx in progress.next_page..end_page {
+        let page: Vec<Address> = storage::get(env, &DataKey::AssetOwnersPage(asset_id, page_idx))
+            .unwrap_or(Vec::new(env));
+
+        for owner in page.iter() {
+            let balance = balance::balance_of(env.clone(), owner.clone(), asset_id);
+            cap_table::preserve_balance(env, &owner, asset_id);
+            // Cannot overflow: every balance is bounded by the rescaled supply
+            storage::set(
+                env,
+                &DataKey::Balance(owner.clone(), asset_id),
+                &(balance * progress.factor),
+            );
+            archival::bump_balance(env, &owner, asset_id);
+            checkpoints::rescale(env, &owner, asset_id, progress.factor, 1);
+        }
+    }
+
+    if end_page < page_count {
+        progress.next_page = end_page;
+        storage::set(env, &DataKey::PendingSplit(asset_id), &progress);
+        return false;
+    }
+
+    storage::remove(env, &DataKey::PendingSplit(asset_id));
+
+    let cumulative = split_factor(env.clone(), asset_id) * progress.factor;
+    storage::set(env, &DataKey::SplitFactor(asset_id), &cumulative);
+
+    basket::rescale_holdings(env, asset_id, progress.factor, 1);
+    concentration::rescale(env, asset_id, progress.factor, 1);
+
+    events::emit_split_done(env, asset_id, progress.factor, cumulative);
+
+    true
+}
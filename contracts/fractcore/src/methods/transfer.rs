@@ -1,6 +1,9 @@
 use crate::events;
-use crate::methods::{approval, balance, utils};
-use crate::storage::DataKey;
+use crate::methods::{
+    admin, approval, archival, balance, cap_table, checkpoints, concentration, recipients, split,
+    transfer_lock, utils,
+};
+use crate::storage::{self, DataKey};
 use soroban_sdk::{Address, Env, Vec};
 
 pub fn transfer(env: Env, from: Address, to: Address, asset_id: u64, amount: u64) {
@@ -21,33 +24,75 @@ pub fn transfer_from(
             approval::is_approved_for_all(env.clone(), from.clone(), operator.clone());
 
         if !approved_for_all {
-            let allowance: u64 = env
-                .storage()
-                .persistent()
-                .get(&DataKey::TokenAllowance(
-                    from.clone(),
-                    operator.clone(),
-                    asset_id,
-                ))
-                .unwrap_or(0);
-
-            if allowance < amount {
-                panic!("Insufficient allowance");
-            }
-
-            env.storage().persistent().set(
+            let allowance: u64 = storage::get(
+                &env,
                 &DataKey::TokenAllowance(from.clone(), operator.clone(), asset_id),
-                &(allowance - amount),
-            );
+            )
+            .unwrap_or(0);
+
+            // A per-asset allowance that covers the transfer is used before the
+            // operator's cross-asset limit
+            if allowance >= amount {
+                storage::set(
+                    &env,
+                    &DataKey::TokenAllowance(from.clone(), operator.clone(), asset_id),
+                    &(allowance - amount),
+                );
+            } else {
+                approval::spend_operator_limit(&env, &from, &operator, amount);
+            }
         }
     } else {
         from.require_auth();
     }
 
-    transfer_internal(env, from, to, asset_id, amount);
+    // A listed contract pulling fractions into its own custody means to
+    if operator == to {
+        move_fractions(env, from, to, asset_id, amount);
+    } else {
+        transfer_internal(env, from, to, asset_id, amount);
+    }
+}
+
+/// One asset from `from` to many recipients, each leg checked like `transfer_from`
+pub fn batch_transfer_to(
+    env: Env,
+    operator: Address,
+    from: Address,
+    asset_id: u64,
+    recipients: Vec<Address>,
+    amounts: Vec<u64>,
+) {
+    if recipients.len() != amounts.len() {
+        panic!("Recipients and amounts length mismatch");
+    }
+
+    utils::require_batch_size(&env, recipients.len());
+
+    for (to, amount) in recipients.iter().zip(amounts.iter()) {
+        transfer_from(
+            env.clone(),
+            operator.clone(),
+            from.clone(),
+            to,
+            asset_id,
+            amount,
+        );
+    }
 }
 
 pub fn transfer_internal(env: Env, from: Address, to: Address, asset_id: u64, amount: u64) {
+    recipients::require_accepted(&env, &to);
+    move_fractions(env, from, to, asset_id, amount);
+}
+
+/// `transfer_internal` without the recipient deny-list, for moves into a
+/// custody that is meant to hold the fractions
+pub fn move_fractions(env: Env, from: Address, to: Address, asset_id: u64, amount: u64) {
+    admin::require_not_paused(&env);
+    split::require_not_restructuring(&env, asset_id);
+    transfer_lock::require_not_locked(&env, &from, asset_id);
+
     if amount == 0 {
         panic!("Cannot transfer 0 tokens");
     }
@@ -66,12 +111,24 @@ pub fn transfer_internal(env: Env, from: Address, to: Address, asset_id: u64, am
     let new_from_balance = from_balance - amount;
     let new_to_balance = to_balance + amount;
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(from.clone(), asset_id), &new_from_balance);
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone(), asset_id), &new_to_balance);
+    cap_table::preserve_balance(&env, &from, asset_id);
+    cap_table::preserve_balance(&env, &to, asset_id);
+    storage::set(
+        &env,
+        &DataKey::Balance(from.clone(), asset_id),
+        &new_from_balance,
+    );
+    storage::set(
+        &env,
+        &DataKey::Balance(to.clone(), asset_id),
+        &new_to_balance,
+    );
+    archival::bump_balance(&env, &from, asset_id);
+    archival::bump_balance(&env, &to, asset_id);
+    checkpoints::record(&env, &from, asset_id, new_from_balance);
+    checkpoints::record(&env, &to, asset_id, new_to_balance);
+    concentration::track(&env, &from, asset_id, new_from_balance);
+    concentration::track(&env, &to, asset_id, new_to_balance);
 
     if to_balance == 0 {
         utils::add_owner_to_asset(&env, asset_id, to.clone());
@@ -84,6 +141,7 @@ pub fn transfer_internal(env: Env, from: Address, to: Address, asset_id: u64, am
     }
 
     events::emit_transfer(&env, from, to, asset_id, amount);
+    concentration::check(&env, asset_id);
 }
 pub fn batch_transfer_from(
     env: Env,
@@ -98,6 +156,8 @@ pub fn batch_transfer_from(
         panic!("Asset IDs and amounts length mismatch");
     }
 
+    utils::require_batch_size(&env, asset_ids.len());
+
     for i in 0..asset_ids.len() {
         let asset_id = asset_ids.get(i).unwrap();
         let amount = amounts.get(i).unwrap();
@@ -0,0 +1,170 @@
+use crate::events;
+use crate::methods::{comanagement, split, utils};
+use crate::storage::{self, DataKey, DataKeyExt, TransferLock, MAX_TRANSFER_LOCKS};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Allow a contract, typically governance, to freeze transfers of an asset during
+/// critical polls (asset manager only). `None` withdraws the permission; locks
+/// already placed still lapse at their end time.
+pub fn set_transfer_lock_authority(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    authority: Option<Address>,
+) {
+    caller.require_auth();
+
+    if !utils::asset_exists(env.clone(), asset_id) {
+        panic!("Asset does not exist");
+    }
+
+    if caller != split::asset_manager(env.clone(), asset_id) {
+        panic!("Only the asset manager can set the lock authority");
+    }
+    comanagement::require_not_co_managed(&env, asset_id);
+
+    store_lock_authority(&env, asset_id, authority);
+}
+
+pub fn store_lock_authority(env: &Env, asset_id: u64, authority: Option<Address>) {
+    match authority.clone() {
+        Some(authority) => storage::set(env, &DataKey::TransferLockAuthority(asset_id), &authority),
+        None => storage::remove(env, &DataKey::TransferLockAuthority(asset_id)),
+    }
+
+    events::emit_lock_authority(env, asset_id, authority);
+}
+
+pub fn transfer_lock_authority(env: Env, asset_id: u64) -> Option<Address> {
+    storage::get(&env, &DataKey::TransferLockAuthority(asset_id))
+}
+
+/// Open lock `lock_id` (e.g. a poll id) until `until`.
+///
+/// With `all_holders` every holder of the asset is frozen; otherwise only holders
+/// later added with `lock_holder_transfers` are.
+pub fn open_transfer_lock(
+    env: Env,
+    authority: Address,
+    asset_id: u64,
+    lock_id: u32,
+    all_holders: bool,
+    until: u64,
+) {
+    require_lock_authority(&env, &authority, asset_id);
+
+    if until <= env.ledger().timestamp() {
+        panic!("Lock must end in the future");
+    }
+
+    let key = DataKey::TransferLock(asset_id, lock_id);
+    if storage::has(&env, &key) {
+        panic!("Transfer lock already exists");
+    }
+
+    if all_holders {
+        let mut locks = live_locks(&env, asset_id, &DataKey::AssetTransferLocks(asset_id));
+        if locks.len() >= MAX_TRANSFER_LOCKS {
+            panic!("Too many transfer locks");
+        }
+        locks.push_back(lock_id);
+        storage::set(&env, &DataKey::AssetTransferLocks(asset_id), &locks);
+    }
+
+    storage::set(&env, &key, &TransferLock { all_holders, until });
+
+    events::emit_lock_opened(&env, asset_id, lock_id, all_holders, until);
+}
+
+/// Add a holder, e.g. a voter, to a holder-scoped lock
+pub fn lock_holder_transfers(
+    env: Env,
+    authority: Address,
+    asset_id: u64,
+    lock_id: u32,
+    holder: Address,
+) {
+    require_lock_authority(&env, &authority, asset_id);
+
+    let lock = get_transfer_lock(env.clone(), asset_id, lock_id).expect("Transfer lock not found");
+    if lock.all_holders {
+        return; // Already covers every holder
+    }
+
+    let key = DataKey::HolderTransferLocks(asset_id, holder.clone());
+    let mut locks = live_locks(&env, asset_id, &key);
+    if locks.contains(lock_id) {
+        return;
+    }
+    if locks.len() >= MAX_TRANSFER_LOCKS {
+        panic!("Too many transfer locks");
+    }
+    locks.push_back(lock_id);
+    storage::set(&env, &key, &locks);
+
+    events::emit_holder_locked(&env, asset_id, lock_id, holder);
+}
+
+/// Lift a lock before its end time, e.g. when the poll is finalized
+pub fn release_transfer_lock(env: Env, authority: Address, asset_id: u64, lock_id: u32) {
+    require_lock_authority(&env, &authority, asset_id);
+
+    // Per-holder lists drop the id lazily once the lock is gone
+    storage::remove(&env, &DataKey::TransferLock(asset_id, lock_id));
+
+    events::emit_lock_released(&env, asset_id, lock_id);
+}
+
+pub fn get_transfer_lock(env: Env, asset_id: u64, lock_id: u32) -> Option<TransferLock> {
+    storage::get(&env, &DataKey::TransferLock(asset_id, lock_id))
+}
+
+/// Whether a live lock or a co-manager halt currently blocks `holder` from
+/// sending the asset
+pub fn is_transfer_locked(env: Env, holder: Address, asset_id: u64) -> bool {
+    is_transfers_halted(&env, asset_id)
+        || !live_locks(&env, asset_id, &DataKey::AssetTransferLocks(asset_id)).is_empty()
+        || !live_locks(
+            &env,
+            asset_id,
+            &DataKey::HolderTransferLocks(asset_id, holder),
+        )
+        .is_empty()
+}
+
+pub fn require_not_locked(env: &Env, from: &Address, asset_id: u64) {
+    if is_transfers_halted(env, asset_id) {
+        panic!("Transfers are halted by the asset managers");
+    }
+    if is_transfer_locked(env.clone(), from.clone(), asset_id) {
+        panic!("Transfers are locked by an active poll");
+    }
+}
+
+pub fn is_transfers_halted(env: &Env, asset_id: u64) -> bool {
+    storage::has(env, &DataKeyExt::TransfersHalted(asset_id))
+}
+
+fn require_lock_authority(env: &Env, authority: &Address, asset_id: u64) {
+    authority.require_auth();
+
+    if transfer_lock_authority(env.clone(), asset_id) != Some(authority.clone()) {
+        panic!("Transfer locks are not permitted for this asset");
+    }
+}
+
+/// Lock ids under `key` that are still open and have not lapsed
+fn live_locks(env: &Env, asset_id: u64, key: &DataKey) -> Vec<u32> {
+    let ids: Vec<u32> = storage::get(env, key).unwrap_or(Vec::new(env));
+
+    let now = env.ledger().timestamp();
+    let mut live = Vec::new(env);
+    for lock_id in ids.iter() {
+        if let Some(lock) = get_transfer_lock(env.clone(), asset_id, lock_id) {
+            if lock.until > now {
+                live.push_back(lock_id);
+            }
+        }
+    }
+    live
+}
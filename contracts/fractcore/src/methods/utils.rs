@@ -1,158 +1,159 @@
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env, Vec};
+use crate::errors::FractcoreError;
+use crate::methods::cap_table;
+use crate::storage::{self, DataKey, MAX_BATCH_SIZE};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 static MAX_OWNERS_PER_PAGE: u32 = 50; // Maximum owners per page
 
 /// Next asset ID to be assigned
 pub fn next_asset_id(env: Env) -> u64 {
-    env.storage()
-        .instance()
-        .get(&DataKey::NextAssetId)
-        .unwrap_or(1)
+    storage::get(&env, &DataKey::NextAssetId).unwrap_or(1)
+}
+
+/// Items served per paginated call: `limit`, where 0 or anything above
+/// MAX_BATCH_SIZE means MAX_BATCH_SIZE
+pub fn page_limit(limit: u32) -> u32 {
+    if limit == 0 || limit > MAX_BATCH_SIZE {
+        MAX_BATCH_SIZE
+    } else {
+        limit
+    }
 }
 
 pub fn asset_exists(env: Env, asset_id: u64) -> bool {
-    env.storage()
-        .persistent()
-        .has(&DataKey::AssetSupply(asset_id))
+    storage::has(&env, &DataKey::AssetSupply(asset_id))
+}
+
+/// Reject a batch above MAX_BATCH_SIZE before any state is written
+pub fn require_batch_size(env: &Env, len: u32) {
+    if len > MAX_BATCH_SIZE {
+        panic_with_error!(env, FractcoreError::BatchTooLarge);
+    }
 }
 
 /// Add asset to owner's asset list
 pub fn add_owner_to_asset(env: &Env, asset_id: u64, owner: Address) {
     // Check if owner already exists - only add if new
-    if env
-        .storage()
-        .persistent()
-        .has(&DataKey::AssetOwnerExists(asset_id, owner.clone()))
-    {
+    if storage::has(env, &DataKey::AssetOwnerExists(asset_id, owner.clone())) {
         return; // Owner already exists, nothing to do
     }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetOwnerExists(asset_id, owner.clone()), &true);
-    env.storage()
-        .persistent()
-        .set(&DataKey::OwnerAssetExists(owner.clone(), asset_id), &true);
-
-    let current_count: u32 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerCount(asset_id))
-        .unwrap_or(0);
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetOwnerCount(asset_id), &(current_count + 1));
-
-    if let Some(hint_page) = env
-        .storage()
-        .persistent()
-        .get::<DataKey, u32>(&DataKey::AssetLastActivePage(asset_id))
+    storage::set(
+        env,
+        &DataKey::AssetOwnerExists(asset_id, owner.clone()),
+        &true,
+    );
+    storage::set(
+        env,
+        &DataKey::OwnerAssetExists(owner.clone(), asset_id),
+        &true,
+    );
+
+    let current_count: u32 = storage::get(env, &DataKey::AssetOwnerCount(asset_id)).unwrap_or(0);
+    storage::set(
+        env,
+        &DataKey::AssetOwnerCount(asset_id),
+        &(current_count + 1),
+    );
+
+    if let Some(hint_page) =
+        storage::get::<DataKey, u32>(env, &DataKey::AssetLastActivePage(asset_id))
     {
-        if let Some(mut page) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::AssetOwnersPage(asset_id, hint_page))
-        {
+        if let Some(mut page) = storage::get::<DataKey, Vec<Address>>(
+            env,
+            &DataKey::AssetOwnersPage(asset_id, hint_page),
+        ) {
             if page.len() < MAX_OWNERS_PER_PAGE {
                 // Space found in hinted page
+                cap_table::preserve_owner_page(env, asset_id, hint_page);
                 page.push_back(owner.clone());
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::AssetOwnersPage(asset_id, hint_page), &page);
+                storage::set(env, &DataKey::AssetOwnersPage(asset_id, hint_page), &page);
 
                 // Store location for fast removal
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::AssetOwnerLocation(asset_id, owner), &hint_page);
+                storage::set(
+                    env,
+                    &DataKey::AssetOwnerLocation(asset_id, owner),
+                    &hint_page,
+                );
                 return;
             }
         }
     }
 
     // Fallback: scan for first page with space
-    let page_count: u32 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerPageCount(asset_id))
-        .unwrap_or(0);
+    let page_count: u32 = storage::get(env, &DataKey::AssetOwnerPageCount(asset_id)).unwrap_or(0);
 
     for page_idx in 0..page_count {
-        if let Some(mut page) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::AssetOwnersPage(asset_id, page_idx))
-        {
+        if let Some(mut page) = storage::get::<DataKey, Vec<Address>>(
+            env,
+            &DataKey::AssetOwnersPage(asset_id, page_idx),
+        ) {
             if page.len() < MAX_OWNERS_PER_PAGE {
                 // Found space in existing page
+                cap_table::preserve_owner_page(env, asset_id, page_idx);
                 page.push_back(owner.clone());
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::AssetOwnersPage(asset_id, page_idx), &page);
+                storage::set(env, &DataKey::AssetOwnersPage(asset_id, page_idx), &page);
 
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::AssetLastActivePage(asset_id), &page_idx);
+                storage::set(env, &DataKey::AssetLastActivePage(asset_id), &page_idx);
 
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::AssetOwnerLocation(asset_id, owner), &page_idx);
+                storage::set(
+                    env,
+                    &DataKey::AssetOwnerLocation(asset_id, owner),
+                    &page_idx,
+                );
                 return;
             }
         }
     }
 
     // Create new page if all existing pages are full
-    let mut new_page = Vec::new(&env);
+    let mut new_page = Vec::new(env);
     new_page.push_back(owner.clone());
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetOwnersPage(asset_id, page_count), &new_page);
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetOwnerPageCount(asset_id), &(page_count + 1));
+    storage::set(
+        env,
+        &DataKey::AssetOwnersPage(asset_id, page_count),
+        &new_page,
+    );
+    storage::set(
+        env,
+        &DataKey::AssetOwnerPageCount(asset_id),
+        &(page_count + 1),
+    );
 
     // SET HINT to new page
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetLastActivePage(asset_id), &page_count);
+    storage::set(env, &DataKey::AssetLastActivePage(asset_id), &page_count);
 
     // Store location
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetOwnerLocation(asset_id, owner), &page_count);
+    storage::set(
+        env,
+        &DataKey::AssetOwnerLocation(asset_id, owner),
+        &page_count,
+    );
 }
 
 /// Remove owner from asset's paginated lists using location tracking
 pub fn remove_owner_from_asset(env: &Env, asset_id: u64, owner: Address) {
-    env.storage()
-        .persistent()
-        .remove(&DataKey::AssetOwnerExists(asset_id, owner.clone()));
-
-    let current_count: u32 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetOwnerCount(asset_id))
-        .unwrap_or(0);
+    storage::remove(env, &DataKey::AssetOwnerExists(asset_id, owner.clone()));
+
+    let current_count: u32 = storage::get(env, &DataKey::AssetOwnerCount(asset_id)).unwrap_or(0);
     if current_count > 0 {
-        env.storage()
-            .persistent()
-            .set(&DataKey::AssetOwnerCount(asset_id), &(current_count - 1));
+        storage::set(
+            env,
+            &DataKey::AssetOwnerCount(asset_id),
+            &(current_count - 1),
+        );
     }
 
-    if let Some(page_num) = env
-        .storage()
-        .persistent()
-        .get::<DataKey, u32>(&DataKey::AssetOwnerLocation(asset_id, owner.clone()))
+    if let Some(page_num) =
+        storage::get::<DataKey, u32>(env, &DataKey::AssetOwnerLocation(asset_id, owner.clone()))
     {
-        let page: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::AssetOwnersPage(asset_id, page_num))
-            .unwrap_or(Vec::new(&env));
+        let page: Vec<Address> = storage::get(env, &DataKey::AssetOwnersPage(asset_id, page_num))
+            .unwrap_or(Vec::new(env));
+
+        cap_table::preserve_owner_page(env, asset_id, page_num);
 
         // Remove owner from page
-        let mut new_page = Vec::new(&env);
+        let mut new_page = Vec::new(env);
         for i in 0..page.len() {
             let current_owner = page.get(i).unwrap();
             if current_owner != owner {
@@ -160,39 +161,39 @@ pub fn remove_owner_from_asset(env: &Env, asset_id: u64, owner: Address) {
             }
         }
 
-        if new_page.len() == 0 {
+        if new_page.is_empty() {
             // Page is now empty - remove it
-            env.storage()
-                .persistent()
-                .remove(&DataKey::AssetOwnersPage(asset_id, page_num));
+            storage::remove(env, &DataKey::AssetOwnersPage(asset_id, page_num));
         } else {
             // Update page with filtered content
-            env.storage()
-                .persistent()
-                .set(&DataKey::AssetOwnersPage(asset_id, page_num), &new_page);
+            storage::set(
+                env,
+                &DataKey::AssetOwnersPage(asset_id, page_num),
+                &new_page,
+            );
 
-            env.storage()
-                .persistent()
-                .set(&DataKey::AssetLastActivePage(asset_id), &page_num);
+            storage::set(env, &DataKey::AssetLastActivePage(asset_id), &page_num);
         }
 
         // Remove location tracking
-        env.storage()
-            .persistent()
-            .remove(&DataKey::AssetOwnerLocation(asset_id, owner));
+        storage::remove(env, &DataKey::AssetOwnerLocation(asset_id, owner));
+
+        // Later owners of the page moved up; open cursors re-read it
+        let removals: u64 = storage::get(env, &DataKey::AssetOwnerRemovals(asset_id)).unwrap_or(0);
+        storage::set(env, &DataKey::AssetOwnerRemovals(asset_id), &(removals + 1));
     }
 }
 
 /// Auto Cleanup: Remove asset from owner when balance = 0
 pub fn remove_asset_from_owner(env: &Env, owner: Address, asset_id: u64) {
-    env.storage()
-        .persistent()
-        .remove(&DataKey::OwnerAssetExists(owner.clone(), asset_id));
+    storage::remove(env, &DataKey::OwnerAssetExists(owner.clone(), asset_id));
 }
 
 /// Add asset to owner when they get their first tokens
 pub fn add_asset_to_owner(env: &Env, owner: Address, asset_id: u64) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::OwnerAssetExists(owner.clone(), asset_id), &true);
+    storage::set(
+        env,
+        &DataKey::OwnerAssetExists(owner.clone(), asset_id),
+        &true,
+    );
 }
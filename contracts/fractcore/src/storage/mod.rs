@@ -1,12 +1,21 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, String, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// Fractcore keeps nothing in temporary storage: balances and ownership
+// records must never expire silently.
+pub use admin_log::{AdminLogEntry, AdminLogHead, ADMIN_LOG_PAGE_SIZE, MAX_ADMIN_LOG_PAGES};
+pub use paging::Cursor;
+pub use placement::*;
 
 /// Storage key implementation for Soroban replacing Solidity's nested mappings
 /// Replaces Solidity's mapping(address => mapping(uint256 => uint256)) private _balance;
 /// Uses keys/variables that Soroban serializes automatically
-#[contracttype]
+#[contracttype(export = false)]
 pub enum DataKey {
     // Contract core data
     Admin,
+    EventLevel,     // EventLevel for batch operations (absent = Full)
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
 
     // Asset ID counter replacing id_counter from various registry implementations
     NextAssetId,
@@ -28,19 +37,471 @@ pub enum DataKey {
     AssetOwnerPageCount(u64),         // asset_id -> number_of_pages
     AssetLastActivePage(u64),         // Hint: last page with space
     AssetOwnerLocation(u64, Address), // Fast removal: owner -> page_num
+    AssetOwnerRemovals(u64),          // asset_id -> owners removed so far (pages shift on removal)
 
     // Authorization system
     // Simplification of AllowancesNestedMap from Solidity
     // Maintains ERC1155 compatibility but with simpler storage
     OperatorApproval(Address, Address), // owner -> operator -> approved_for_all
     TokenAllowance(Address, Address, u64), // owner -> operator -> asset_id -> allowance
+    OperatorLimit(Address, Address),    // owner -> operator -> amount left across all assets
 
     // Metadata support
     // Replaces mapping(uint256 => string) assetURIs; from Solidity
-    AssetURI(u64), // asset_id -> metadata_uri
-    ContractURI,   // global contract metadata
+    AssetURI(u64),        // asset_id -> metadata_uri
+    AssetURIHistory(u64), // asset_id -> Vec<UriVersion>, oldest first
+    ContractURI,          // global contract metadata
+    BaseURI,              // template with {id} for assets without their own URI
 
     // Asset management
     // New functionality - tracking who created each asset
     AssetCreator(u64), // asset_id -> creator_address
+    AssetManager(u64), // asset_id -> address allowed to restructure the asset
+
+    // Issuance limits
+    MaxSupply(u64),        // asset_id -> supply cap set at mint time
+    MintingFinalized(u64), // asset_id -> true once issuance is closed for good
+
+    // Asset splits
+    SplitFactor(u64),  // asset_id -> cumulative split factor (1 if never split)
+    PendingSplit(u64), // asset_id -> SplitProgress while holders are being rescaled
+
+    // Asset consolidations (reverse splits)
+    ConsolidationFactor(u64), // asset_id -> cumulative consolidation factor (1 if never)
+    PendingConsolidation(u64), // asset_id -> ConsolidationProgress while holders are divided
+    FractionRemainder(Address, u64), // owner -> asset_id -> unclaimed pre-consolidation units
+    FractionRemainderTotal(u64), // asset_id -> unclaimed remainder units across holders
+
+    // Balance history for time-weighted queries
+    BalanceCheckpoints(Address, u64), // owner -> asset_id -> Vec<BalanceCheckpoint>
+
+    // Holder announcements
+    AnnouncementCount(u64), // asset_id -> announcements ever posted
+    Announcement(u64, u32), // asset_id -> ring slot -> Announcement
+
+    // Basket (index) assets
+    Basket(u64),             // basket_id -> Vec<BasketComponent>
+    BasketHolding(u64, u64), // basket_id -> component asset_id -> fractions held for it
+    AssetBaskets(u64),       // asset_id -> Vec<u64> (baskets holding it)
+
+    // Social recovery
+    Guardians(Address), // holder -> GuardianConfig
+    Recovery(Address),  // holder -> pending RecoveryRequest
+
+    // Cap table snapshots (copy-on-write)
+    CapTableSnapshotCount,
+    CapTableSnapshot(u64),         // asset_id -> live CapTableSnapshot
+    SnapshotBalance(u32, Address), // snapshot_id -> owner -> balance before its first change
+    SnapshotOwnersPage(u32, u32),  // snapshot_id -> page_num -> owner page before its first change
+
+    // Holdings-weighted draws
+    LotteryDrawCount(u64), // asset_id -> draws ever committed
+    LotteryDraw(u64, u32), // asset_id -> draw_id -> LotteryDraw
+
+    // Poll transfer locks
+    TransferLockAuthority(u64), // asset_id -> contract allowed to place locks
+    TransferLock(u64, u32),     // asset_id -> lock_id -> TransferLock
+    AssetTransferLocks(u64),    // asset_id -> Vec<u32> locks covering every holder
+    HolderTransferLocks(u64, Address), // asset_id -> holder -> Vec<u32> locks the holder is in
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("core");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::EventLevel
+            | DataKey::InitConfigHash
+            | DataKey::NextAssetId
+            | DataKey::ContractURI
+            | DataKey::BaseURI
+            | DataKey::CapTableSnapshotCount => Placement::Instance,
+            DataKey::Balance(..)
+            | DataKey::AssetSupply(..)
+            | DataKey::AssetOwnerExists(..)
+            | DataKey::OwnerAssetExists(..)
+            | DataKey::AssetOwnerCount(..)
+            | DataKey::AssetOwnersPage(..)
+            | DataKey::AssetOwnerPageCount(..)
+            | DataKey::AssetLastActivePage(..)
+            | DataKey::AssetOwnerLocation(..)
+            | DataKey::AssetOwnerRemovals(..)
+            | DataKey::OperatorApproval(..)
+            | DataKey::TokenAllowance(..)
+            | DataKey::OperatorLimit(..)
+            | DataKey::AssetURI(..)
+            | DataKey::AssetURIHistory(..)
+            | DataKey::AssetCreator(..)
+            | DataKey::AssetManager(..)
+            | DataKey::MaxSupply(..)
+            | DataKey::MintingFinalized(..)
+            | DataKey::SplitFactor(..)
+            | DataKey::PendingSplit(..)
+            | DataKey::ConsolidationFactor(..)
+            | DataKey::PendingConsolidation(..)
+            | DataKey::FractionRemainder(..)
+            | DataKey::FractionRemainderTotal(..)
+            | DataKey::BalanceCheckpoints(..)
+            | DataKey::AnnouncementCount(..)
+            | DataKey::Announcement(..)
+            | DataKey::Basket(..)
+            | DataKey::BasketHolding(..)
+            | DataKey::AssetBaskets(..)
+            | DataKey::Guardians(..)
+            | DataKey::Recovery(..)
+            | DataKey::CapTableSnapshot(..)
+            | DataKey::SnapshotBalance(..)
+            | DataKey::SnapshotOwnersPage(..)
+            | DataKey::LotteryDrawCount(..)
+            | DataKey::LotteryDraw(..)
+            | DataKey::TransferLockAuthority(..)
+            | DataKey::TransferLock(..)
+            | DataKey::AssetTransferLocks(..)
+            | DataKey::HolderTransferLocks(..) => Placement::Persistent,
+        }
+    }
+}
+
+/// Storage keys added once DataKey reached the 50 variants a contracttype enum can hold
+#[contracttype(export = false)]
+pub enum DataKeyExt {
+    // Ownership concentration
+    TopHolders(u64),              // asset_id -> Vec<HolderBalance>, largest first
+    ConcentrationThresholds(u64), // asset_id -> Vec<u32> ascending thresholds in bps
+    ConcentrationLevel(u64),      // asset_id -> thresholds the largest holder has reached
+
+    // Upgrades
+    GovernanceContract, // Contract that alone may upgrade this one
+    WasmHash,           // Code installed by the last upgrade (absent = code deployed at creation)
+
+    // Asset co-management
+    CoManagement(u64),          // asset_id -> CoManagement
+    ManagerActionCount(u64),    // asset_id -> manager actions ever proposed
+    ManagerAction(u64, u32),    // asset_id -> action_id -> pending ManagerProposal
+    PendingManagerActions(u64), // asset_id -> Vec<u32> pending action ids
+    TransfersHalted(u64),       // asset_id -> true while co-managers have halted transfers
+
+    // Recipient safety
+    DeniedRecipients, // Vec<Address> transfers may not send to (absent = this contract only)
+
+    // Emergency pause
+    Paused, // PauseState while the admin has paused fraction transfers
+
+    // Marketplace categories
+    AssetCategory(u64),         // asset_id -> Symbol set at mint or by the manager
+    CategoryAssetCount(Symbol), // category -> number of assets in it
+    CategoryAssetsPage(Symbol, u32), // category -> page -> Vec<u64>, CATEGORY_PAGE_SIZE per page
+    CategoryPosition(u64),      // asset_id -> index in its category's paged list
+
+    // Admin action log
+    AdminLogHead,      // AdminLogHead: entry count and the hash of folded entries
+    AdminLogPage(u32), // page -> Vec<AdminLogEntry>, ADMIN_LOG_PAGE_SIZE per page
+}
+
+impl PlacedKey for DataKeyExt {
+    const NAMESPACE: Symbol = symbol_short!("core_ext");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKeyExt::GovernanceContract
+            | DataKeyExt::WasmHash
+            | DataKeyExt::DeniedRecipients
+            | DataKeyExt::Paused
+            | DataKeyExt::AdminLogHead => Placement::Instance,
+            DataKeyExt::TopHolders(..)
+            | DataKeyExt::ConcentrationThresholds(..)
+            | DataKeyExt::ConcentrationLevel(..)
+            | DataKeyExt::CoManagement(..)
+            | DataKeyExt::ManagerActionCount(..)
+            | DataKeyExt::ManagerAction(..)
+            | DataKeyExt::PendingManagerActions(..)
+            | DataKeyExt::TransfersHalted(..)
+            | DataKeyExt::AssetCategory(..)
+            | DataKeyExt::CategoryAssetCount(..)
+            | DataKeyExt::CategoryAssetsPage(..)
+            | DataKeyExt::CategoryPosition(..)
+            | DataKeyExt::AdminLogPage(..) => Placement::Persistent,
+        }
+    }
+}
+
+impl admin_log::AdminLogKey for DataKeyExt {
+    fn head() -> Self {
+        DataKeyExt::AdminLogHead
+    }
+
+    fn page(page: u32) -> Self {
+        DataKeyExt::AdminLogPage(page)
+    }
+}
+
+/// Emergency pause placed by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub since: u64,
+    pub retry_after: u64, // When the admin expects to unpause (0 = unknown)
+}
+
+/// Progress of a split that is applied to the owner pages in batches
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitProgress {
+    pub factor: u64,
+    pub next_page: u32, // First owner page not yet rescaled
+}
+
+/// Owner pages rescaled per split call (50 owners per page)
+pub const SPLIT_PAGES_PER_CALL: u32 = 4;
+
+/// Owner pages `asset_owners` returns (50 owners per page); it refuses larger assets,
+/// which are read with `get_asset_owners_page`
+pub const MAX_ASSET_OWNERS_PAGES: u32 = 4;
+
+/// How many events batch operations such as `mint_to` publish
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventLevel {
+    Full,    // One event per recipient (original behavior)
+    Summary, // One aggregated event per call with recipient count and total
+    Minimal, // Only the event of the top-level action
+}
+
+/// What happens to the part of a balance that does not divide by the consolidation factor
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RemainderPolicy {
+    Compensate,   // Round down; remainders become claimable for XLM compensation via funding
+    RoundDown,    // Round down; remainders are forfeited
+    RoundNearest, // Round half up to the nearest whole consolidated unit
+}
+
+/// Progress of a consolidation that is applied to the owner pages in batches
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsolidationProgress {
+    pub factor: u64,
+    pub policy: RemainderPolicy,
+    pub next_page: u32,  // First owner page not yet divided
+    pub new_supply: u64, // Sum of the consolidated balances processed so far
+}
+
+/// Balance held from `timestamp` until the next checkpoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceCheckpoint {
+    pub timestamp: u64,
+    pub balance: u64,
+}
+
+/// Checkpoints kept per holder and asset; older history is dropped
+pub const MAX_BALANCE_CHECKPOINTS: u32 = 32;
+
+/// Notice posted to an asset's holders
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Announcement {
+    pub id: u32, // Sequence number per asset, starting at 0
+    pub author: Address,
+    pub title: String,
+    pub content_hash: BytesN<32>, // Hash of the full off-chain notice
+    pub uri: String,
+    pub posted_at: u64,
+}
+
+/// Announcements kept per asset; older ones are overwritten (events keep the full history)
+pub const MAX_ANNOUNCEMENTS: u32 = 10;
+
+/// One value an asset's URI has held
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UriVersion {
+    pub version: u32,        // Increases with every change, starting at 0
+    pub uri: Option<String>, // None when the URI was removed
+    pub set_by: Address,
+    pub set_at: u64,
+}
+
+/// URI versions kept per asset, the current one included; older ones are dropped
+pub const MAX_URI_HISTORY: u32 = 10;
+
+/// Longest base URI template, in bytes
+pub const MAX_BASE_URI_LEN: u32 = 256;
+
+/// Longest contract URI, in bytes; it is kept in instance storage
+pub const MAX_CONTRACT_URI_LEN: u32 = 256;
+
+/// One asset in a basket and the fractions of it backing a basket token at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketComponent {
+    pub asset_id: u64,
+    pub weight: u64,
+}
+
+/// Point-in-time view of an asset's holders for cap table exports
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapTableSnapshot {
+    pub id: u32,
+    pub asset_id: u64,
+    pub supply: u64,
+    pub owner_count: u32,
+    pub page_count: u32, // Owner pages to export
+    pub ledger: u32,
+    pub created_at: u64,
+}
+
+/// Commit-reveal draw of a holder weighted by balance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotteryDraw {
+    pub commitment: BytesN<32>, // SHA-256 of the manager's secret seed
+    pub committed_ledger: u32,  // The seed is only accepted in a later ledger
+    pub winner: Option<Address>,
+    pub ticket: u64, // Winning unit out of the tickets in play, once drawn
+}
+
+/// Owner pages a draw walks in one call (50 holders each)
+pub const MAX_DRAW_PAGES: u32 = 10;
+
+/// Upper bound on entries per batch call (mint recipients, batch transfers, balance
+/// reads, restores). Larger jobs are split across calls, each of which is atomic.
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+/// Transfer freeze placed by an asset's lock authority, e.g. for a governance poll
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferLock {
+    pub all_holders: bool, // false: only holders added with lock_holder_transfers
+    pub until: u64,        // Lock lapses at this timestamp even if never released
+}
+
+/// Upper bound on live locks per asset and per holder
+pub const MAX_TRANSFER_LOCKS: u32 = 10;
+
+/// Upper bound on components so deposits and redemptions stay within resource limits
+pub const MAX_BASKET_COMPONENTS: u32 = 10;
+
+/// Guardians a holder trusts to recover their balances, and how many must agree
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianConfig {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Pending migration of a holder's balances to a new address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_address: Address,
+    pub approvals: Vec<Address>, // Guardians that approved, proposer first
+    pub proposed_at: u64,
+    pub executable_at: Option<u64>, // Set once the threshold is reached
+}
+
+pub const MAX_GUARDIANS: u32 = 10;
+
+/// Time the holder has to cancel an approved recovery (3 days)
+pub const RECOVERY_CHALLENGE_DELAY: u64 = 259200;
+
+/// Managers who jointly run an asset, and how many must confirm a sensitive change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoManagement {
+    pub managers: Vec<Address>,
+    pub quorum: u32,
+}
+
+/// Sensitive change to a co-managed asset, executed once a quorum confirms it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ManagerAction {
+    SetUri(String),
+    RemoveUri,
+    SetTransferLockAuthority(Option<Address>),
+    SetConcentrationThresholds(Vec<u32>),
+    HaltTransfers,
+    ResumeTransfers,
+    SetCoManagers(Vec<Address>, u32), // An empty list ends co-management
+}
+
+/// Manager action awaiting confirmations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManagerProposal {
+    pub id: u32,
+    pub action: ManagerAction,
+    pub proposer: Address,
+    pub confirmations: Vec<Address>, // Managers that confirmed, proposer first
+    pub proposed_at: u64,
+}
+
+pub const MAX_CO_MANAGERS: u32 = 10;
+
+/// Upper bound on manager actions pending per asset
+pub const MAX_PENDING_MANAGER_ACTIONS: u32 = 10;
+
+/// Upper bound on addresses in the recipient deny-list
+pub const MAX_DENIED_RECIPIENTS: u32 = 10;
+
+/// Asset ids per page of a category index
+pub const CATEGORY_PAGE_SIZE: u32 = 50;
+
+/// A holder's balance of one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderBalance {
+    pub holder: Address,
+    pub balance: u64,
+}
+
+/// Share of the supply held by an asset's largest holders
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Concentration {
+    pub supply: u64,
+    pub largest_share_bps: u32,          // Largest single holder
+    pub top_share_bps: u32,              // Holders listed in `top_holders` together
+    pub top_holders: Vec<HolderBalance>, // Up to CONCENTRATION_TOP_HOLDERS, largest first
+    pub thresholds: Vec<u32>,            // Alert thresholds in bps, ascending
+}
+
+/// Balances tracked per asset so the largest holders are known without a scan
+pub const MAX_TRACKED_HOLDERS: u32 = 10;
+
+/// Holders summed into `Concentration::top_share_bps`
+pub const CONCENTRATION_TOP_HOLDERS: u32 = 5;
+
+/// Upper bound on alert thresholds per asset
+pub const MAX_CONCENTRATION_THRESHOLDS: u32 = 5;
+
+/// TTL management for persistent entries
+/// Entries are bumped on write and through restore_entries so balances do not
+/// silently archive while their holder is inactive
+pub const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Distinguishes "never held" from "held and now zero" for integrations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BalanceStatus {
+    NoEntry,   // No balance record was ever written
+    Zero,      // Record exists with a zero balance (fully transferred out)
+    Held(u64), // Live balance
+}
+
+/// User-scoped entry descriptors accepted by restore_entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageEntry {
+    Balance(u64),              // asset_id
+    OwnerAsset(u64),           // asset_id
+    OwnerLocation(u64),        // asset_id
+    Allowance(Address, u64),   // operator, asset_id
+    OperatorApproval(Address), // operator
+    OperatorLimit(Address),    // operator
 }
@@ -0,0 +1,38 @@
+#![cfg(test)]
+//! Canonical scenarios whose events must serialize to exactly the XDR in
+//! `golden/`. A failure means a topic or payload layout changed: if that is
+//! intended, rerun with `UPDATE_GOLDEN=1` and commit the new golden file so
+//! indexers see the change in review.
+
+extern crate std;
+
+use crate::contract::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use std::string::String;
+use test_support::{assert_golden, record_events};
+
+#[test]
+fn test_golden_mint_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(FractionalizationContract, (admin.clone(),));
+    let client = FractionalizationContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let mut golden = String::new();
+
+    let asset_id = client.mint(&creator, &1000);
+    record_events(&env, &contract_id, "mint", &mut golden);
+
+    client.mint_to(&asset_id, &vec![&env, investor.clone()], &vec![&env, 250]);
+    record_events(&env, &contract_id, "mint_to", &mut golden);
+
+    client.transfer(&creator, &investor, &asset_id, &100);
+    record_events(&env, &contract_id, "transfer", &mut golden);
+
+    client.finalize_minting(&admin, &asset_id);
+    record_events(&env, &contract_id, "finalize_minting", &mut golden);
+
+    assert_golden(env!("CARGO_MANIFEST_DIR"), "mint", &golden);
+}
@@ -6,11 +6,10 @@ use soroban_sdk::{testutils::Address as _, Address, Env};
 fn setup() -> (Env, Address, FractionalizationContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(FractionalizationContract, ());
-    let client = FractionalizationContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
+    let contract_id = env.register(FractionalizationContract, (admin.clone(),));
+    let client = FractionalizationContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin);
     (env, admin, client)
 }
 
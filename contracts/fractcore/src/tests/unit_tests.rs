@@ -1,19 +1,79 @@
 #![cfg(test)]
 
 use crate::contract::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::errors::FractcoreError;
+use crate::storage::{
+    stored_key, BalanceStatus, BasketComponent, CoManagement, DataKey, DataKeyExt, EventLevel,
+    HolderBalance, ManagerAction, PauseState, PlacedKey, RemainderPolicy, StorageEntry,
+    ADMIN_LOG_PAGE_SIZE, MAX_ADMIN_LOG_PAGES, MAX_ASSET_OWNERS_PAGES, MAX_BATCH_SIZE,
+    MAX_URI_HISTORY, PERSISTENT_LIFETIME_THRESHOLD, RECOVERY_CHALLENGE_DELAY,
+};
+use placement::testutils::strip_namespaces;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
 
 fn setup() -> (Env, Address, FractionalizationContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(FractionalizationContract, ());
-    let client = FractionalizationContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
+    let contract_id = env.register(FractionalizationContract, (admin.clone(),));
+    let client = FractionalizationContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin);
     (env, admin, client)
 }
 
+// Governance stand-in built for the fractcore contract it is registered with
+mod mock_governance {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct MockGovernance;
+
+    #[contractimpl]
+    impl MockGovernance {
+        pub fn __constructor(env: Env, fractcore: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("fractcore"), &fractcore);
+        }
+
+        pub fn get_fractcore_contract(env: Env) -> Address {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("fractcore"))
+                .unwrap()
+        }
+    }
+}
+
+fn register_governance(env: &Env, fractcore: &Address) -> Address {
+    env.register(mock_governance::MockGovernance, (fractcore.clone(),))
+}
+
+/// Large holder sets are minted MAX_BATCH_SIZE recipients per call
+fn mint_to_in_batches(
+    client: &FractionalizationContractClient,
+    asset_id: u64,
+    recipients: &Vec<Address>,
+    amounts: &Vec<u64>,
+) {
+    let mut start = 0;
+    while start < recipients.len() {
+        let end = recipients.len().min(start + MAX_BATCH_SIZE);
+        client.mint_to(
+            &asset_id,
+            &recipients.slice(start..end),
+            &amounts.slice(start..end),
+        );
+        start = end;
+    }
+}
+
 // === Basic Functionality Tests ===
 
 #[test]
@@ -25,12 +85,23 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "Contract already initialized")]
 fn test_double_initialization() {
-    let (env, _admin, client) = setup();
+    let (env, admin, client) = setup();
     let new_admin = Address::generate(&env);
 
-    client.initialize(&new_admin);
+    // Re-running initialization reports the state instead of trapping
+    assert_eq!(
+        client.try_initialize(&new_admin),
+        Err(Ok(FractcoreError::AlreadyInitialized))
+    );
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(FractcoreError::AlreadyInitialized))
+    );
+
+    // Pipelines compare the recorded hash with the one of their intended config
+    let expected: BytesN<32> = env.crypto().sha256(&(admin,).to_xdr(&env)).into();
+    assert_eq!(client.get_init_config_hash(), Some(expected));
 }
 
 #[test]
@@ -132,6 +203,32 @@ fn test_transfer_to_self() {
     client.transfer(&owner, &owner, &asset_id, &30);
 }
 
+#[test]
+fn test_relayed_transfer_needs_only_the_holders_signature() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+
+    // A relayer submits the holder's signed authorization entry and pays the
+    // fee; nothing else is authorized
+    env.mock_auths(&[MockAuth {
+        address: &holder,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "transfer",
+            args: (&holder, &recipient, asset_id, 40u64).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.transfer(&holder, &recipient, &asset_id, &40);
+
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, holder);
+    assert_eq!(client.balance_of(&recipient, &asset_id), 40);
+}
+
 // === Approval System Tests ===
 
 #[test]
@@ -200,6 +297,43 @@ fn test_insufficient_allowance() {
     client.transfer_from(&operator, &owner, &recipient, &asset_id, &50);
 }
 
+#[test]
+fn test_operator_limit_across_assets() {
+    let (env, _admin, client) = setup();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let asset_a = client.mint(&owner, &100);
+    let asset_b = client.mint(&owner, &100);
+
+    client.set_operator_limit(&owner, &operator, &60);
+    client.transfer_from(&operator, &owner, &recipient, &asset_a, &40);
+    client.transfer_from(&operator, &owner, &recipient, &asset_b, &15);
+    assert_eq!(client.operator_limit(&owner, &operator), 5);
+
+    // A per-asset allowance covering the transfer is used first
+    client.approve(&owner, &operator, &asset_b, &10);
+    client.transfer_from(&operator, &owner, &recipient, &asset_b, &10);
+    assert_eq!(client.operator_limit(&owner, &operator), 5);
+    assert_eq!(client.allowance(&owner, &operator, &asset_b), 0);
+
+    assert!(client
+        .try_transfer_from(&operator, &owner, &recipient, &asset_a, &6)
+        .is_err());
+    client.transfer_from(&operator, &owner, &recipient, &asset_a, &5);
+    assert_eq!(client.operator_limit(&owner, &operator), 0);
+    assert_eq!(client.balance_of(&recipient, &asset_a), 45);
+    assert_eq!(client.balance_of(&recipient, &asset_b), 25);
+
+    // Zero revokes the limit
+    client.set_operator_limit(&owner, &operator, &50);
+    client.set_operator_limit(&owner, &operator, &0);
+    assert!(client
+        .try_transfer_from(&operator, &owner, &recipient, &asset_a, &1)
+        .is_err());
+}
+
 // === Batch Operations Tests ===
 
 #[test]
@@ -270,6 +404,20 @@ fn test_batch_transfer() {
     // Check recipient has both assets
     let recipient_assets = client.owner_assets(&recipient);
     assert_eq!(recipient_assets.len(), 2);
+
+    // One asset to several recipients, spending the same approval
+    let other = Address::generate(&env);
+    client.batch_transfer_to(
+        &operator,
+        &owner,
+        &asset1,
+        &vec![&env, recipient.clone(), other.clone()],
+        &vec![&env, 20, 10],
+    );
+    assert_eq!(client.balance_of(&owner, &asset1), 40);
+    assert_eq!(client.balance_of(&recipient, &asset1), 50);
+    assert_eq!(client.balance_of(&other, &asset1), 10);
+    assert_eq!(client.asset_owners(&asset1).len(), 3);
 }
 
 // === Metadata Tests ===
@@ -291,6 +439,85 @@ fn test_asset_metadata() {
     assert_eq!(stored_uri, uri);
 }
 
+#[test]
+fn test_uri_history_remove_and_revert() {
+    let (env, admin, client) = setup();
+    let asset_id = client.mint(&Address::generate(&env), &100);
+    let first = String::from_str(&env, "ipfs://first");
+    let second = String::from_str(&env, "ipfs://second");
+
+    client.set_asset_uri(&admin, &asset_id, &first);
+    client.set_asset_uri(&admin, &asset_id, &second);
+    client.remove_asset_uri(&admin, &asset_id);
+    assert_eq!(client.asset_uri(&asset_id), None);
+
+    let history = client.get_uri_history(&asset_id);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().uri, Some(first.clone()));
+    assert_eq!(history.get(0).unwrap().set_by, admin);
+    assert_eq!(history.get(2).unwrap().uri, None);
+
+    // Restoring version 0 brings the first URI back as a new version
+    client.revert_asset_uri(&admin, &asset_id, &0);
+    assert_eq!(client.asset_uri(&asset_id), Some(first.clone()));
+    let latest = client.get_uri_history(&asset_id).last().unwrap();
+    assert_eq!(latest.version, 3);
+    assert_eq!(latest.uri, Some(first));
+
+    // Only the latest MAX_URI_HISTORY versions are kept
+    for _ in 0..MAX_URI_HISTORY {
+        client.set_asset_uri(&admin, &asset_id, &second);
+    }
+    let history = client.get_uri_history(&asset_id);
+    assert_eq!(history.len(), MAX_URI_HISTORY);
+    assert_eq!(history.get(0).unwrap().version, 4);
+}
+
+#[test]
+#[should_panic(expected = "URI version not found")]
+fn test_revert_asset_uri_unknown_version() {
+    let (env, admin, client) = setup();
+    let asset_id = client.mint(&Address::generate(&env), &100);
+    client.set_asset_uri(&admin, &asset_id, &String::from_str(&env, "ipfs://first"));
+
+    client.revert_asset_uri(&admin, &asset_id, &5);
+}
+
+#[test]
+fn test_base_uri_template() {
+    let (env, admin, client) = setup();
+    let asset_id = client.mint(&Address::generate(&env), &100);
+    let other_id = client.mint(&Address::generate(&env), &100);
+    assert_eq!(client.asset_uri(&asset_id), None);
+
+    client.set_base_uri(&Some(String::from_str(&env, "ipfs://cid/{id}.json")));
+    let padded = |id: u64| {
+        let mut hex = [b'0'; 64];
+        for (shift, digit) in hex[48..].iter_mut().rev().enumerate() {
+            *digit = b"0123456789abcdef"[((id >> (shift * 4)) & 0xf) as usize];
+        }
+        hex
+    };
+    let mut expected = [0u8; 80];
+    expected[..11].copy_from_slice(b"ipfs://cid/");
+    expected[11..75].copy_from_slice(&padded(other_id));
+    expected[75..].copy_from_slice(b".json");
+    assert_eq!(
+        client.asset_uri(&other_id),
+        Some(String::from_bytes(&env, &expected))
+    );
+
+    // An asset's own URI wins over the template; unknown assets resolve to nothing
+    let own = String::from_str(&env, "https://example.com/1.json");
+    client.set_asset_uri(&admin, &asset_id, &own);
+    assert_eq!(client.asset_uri(&asset_id), Some(own));
+    assert_eq!(client.asset_uri(&999), None);
+
+    client.set_base_uri(&None);
+    assert_eq!(client.base_uri(), None);
+    assert_eq!(client.asset_uri(&other_id), None);
+}
+
 #[test]
 fn test_contract_metadata() {
     let (env, admin, client) = setup();
@@ -300,11 +527,171 @@ fn test_contract_metadata() {
     // Admin can set contract URI
     client.set_contract_uri(&admin, &contract_uri);
 
+    // Only the named caller is asked to sign
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+
     // Verify URI was set
     let stored_uri = client.contract_uri().unwrap();
     assert_eq!(stored_uri, contract_uri);
 }
 
+#[test]
+fn test_spoofed_caller_cannot_act_as_admin() {
+    let (env, admin, client) = setup();
+    let spoofer = Address::generate(&env);
+
+    // The spoofer signs for itself, which no longer borrows the admin's authority
+    assert_eq!(
+        client.try_set_contract_uri(&spoofer, &String::from_str(&env, "https://evil.example")),
+        Err(Ok(FractcoreError::Unauthorized.into()))
+    );
+    assert_eq!(client.contract_uri(), None);
+
+    assert_eq!(
+        client.try_transfer_admin(&spoofer, &spoofer),
+        Err(Ok(FractcoreError::Unauthorized.into()))
+    );
+    assert_eq!(
+        client.try_set_governance_contract(&spoofer, &spoofer),
+        Err(Ok(FractcoreError::Unauthorized.into()))
+    );
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_governance_contract(), None);
+}
+
+#[test]
+fn test_categories_index_assets_for_browsing() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let art = symbol_short!("Art");
+    let music = symbol_short!("Music");
+
+    let first = client.mint_with_category(&holder, &100, &art);
+    let second = client.mint_with_category(&holder, &100, &art);
+    let third = client.mint_with_category(&holder, &100, &art);
+    let uncategorized = client.mint(&holder, &100);
+    assert_eq!(client.asset_category(&first), Some(art.clone()));
+    assert_eq!(client.asset_category(&uncategorized), None);
+    assert_eq!(
+        client.assets_by_category(&art, &0),
+        vec![&env, first, second, third]
+    );
+
+    // Recategorizing moves the last id of the old category into the gap
+    client.set_asset_category(&admin, &first, &Some(music.clone()));
+    assert_eq!(
+        client.assets_by_category(&art, &0),
+        vec![&env, third, second]
+    );
+    assert_eq!(client.assets_by_category(&music, &0), vec![&env, first]);
+    assert_eq!(client.category_asset_count(&art), 2);
+
+    client.set_asset_category(&admin, &third, &None);
+    assert_eq!(client.asset_category(&third), None);
+    assert_eq!(client.assets_by_category(&art, &0), vec![&env, second]);
+    assert_eq!(client.category_asset_count(&art), 1);
+    assert_eq!(client.assets_by_category(&art, &1), vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Only the asset manager can set the category")]
+fn test_only_manager_sets_category() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    client.set_asset_category(&holder, &asset_id, &Some(symbol_short!("Esports")));
+}
+
+#[test]
+fn test_emergency_pause_stops_transfers() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    assert_eq!(
+        client.try_pause(&holder, &0),
+        Err(Ok(FractcoreError::Unauthorized.into()))
+    );
+
+    let since = env.ledger().timestamp();
+    client.pause(&admin, &(since + 3600));
+    assert_eq!(
+        client.try_transfer(&holder, &recipient, &asset_id, &10),
+        Err(Ok(FractcoreError::Paused.into()))
+    );
+
+    // Pausing again moves the retry hint but keeps when the pause began
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    client.pause(&admin, &(since + 7200));
+    assert_eq!(
+        client.pause_state(),
+        Some(PauseState {
+            since,
+            retry_after: since + 7200,
+        })
+    );
+
+    client.unpause(&admin);
+    assert_eq!(client.pause_state(), None);
+    client.transfer(&holder, &recipient, &asset_id, &10);
+    assert_eq!(client.balance_of(&recipient, &asset_id), 10);
+}
+
+#[test]
+fn test_admin_log_records_privileged_calls_and_folds_old_pages() {
+    let (env, admin, client) = setup();
+    env.cost_estimate().budget().reset_unlimited();
+    let holder = Address::generate(&env);
+    client.mint(&holder, &100);
+
+    let entry = client.get_admin_log(&0).get(0).unwrap();
+    assert_eq!(entry.actor, admin);
+    assert_eq!(entry.function, Symbol::new(&env, "mint"));
+    let args_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(holder.clone(), 100u64).to_xdr(&env))
+        .into();
+    assert_eq!(entry.args_hash, args_hash);
+
+    // Holders' own calls are not logged
+    client.transfer(&holder, &Address::generate(&env), &1, &10);
+    assert_eq!(client.get_admin_log_head().entries, 1);
+
+    // Fill the retention window; the next entry folds the oldest page
+    let capacity = ADMIN_LOG_PAGE_SIZE * MAX_ADMIN_LOG_PAGES;
+    for _ in 1..capacity {
+        client.set_event_level(&EventLevel::Full);
+    }
+    let oldest = client.get_admin_log(&0);
+    assert_eq!(oldest.len(), ADMIN_LOG_PAGE_SIZE);
+    assert_eq!(client.get_admin_log_head().folded, 0);
+
+    client.pause(&admin, &0);
+    let head = client.get_admin_log_head();
+    assert_eq!(head.entries, capacity + 1);
+    assert_eq!(head.folded, ADMIN_LOG_PAGE_SIZE);
+    assert!(client.get_admin_log(&0).is_empty());
+    assert_eq!(
+        client
+            .get_admin_log(&MAX_ADMIN_LOG_PAGES)
+            .get(0)
+            .unwrap()
+            .function,
+        Symbol::new(&env, "pause")
+    );
+
+    let mut folded_hash = BytesN::from_array(&env, &[0; 32]);
+    for entry in oldest.iter() {
+        let mut material = Bytes::from(folded_hash);
+        material.append(&entry.to_xdr(&env));
+        folded_hash = env.crypto().sha256(&material).into();
+    }
+    assert_eq!(head.folded_hash, folded_hash);
+}
+
 // === Admin Management Tests ===
 
 #[test]
@@ -322,6 +709,41 @@ fn test_admin_management() {
     assert_eq!(client.get_admin(), new_admin);
 }
 
+#[test]
+fn test_upgrades_only_through_governance() {
+    let (env, admin, client) = setup();
+    let governance = register_governance(&env, &client.address);
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+    // Without a governance contract the code cannot change
+    assert!(client.try_upgrade(&admin, &wasm_hash).is_err());
+
+    // Accounts and governance contracts of another fractcore are refused
+    let other_fractcore = Address::generate(&env);
+    for target in [
+        Address::generate(&env),
+        register_governance(&env, &other_fractcore),
+    ] {
+        assert_eq!(
+            client.try_set_governance_contract(&admin, &target),
+            Err(Ok(FractcoreError::NotGovernanceContract.into()))
+        );
+    }
+    assert_eq!(client.get_governance_contract(), None);
+
+    client.set_governance_contract(&admin, &governance);
+    assert_eq!(client.get_governance_contract(), Some(governance.clone()));
+    assert!(client.try_upgrade(&admin, &wasm_hash).is_err());
+
+    // Once named, only the governance contract names its successor
+    let successor = register_governance(&env, &client.address);
+    assert!(client
+        .try_set_governance_contract(&admin, &successor)
+        .is_err());
+    client.set_governance_contract(&governance, &successor);
+    assert_eq!(client.get_governance_contract(), Some(successor));
+}
+
 #[test]
 fn test_asset_creator_tracking() {
     let (env, admin, client) = setup();
@@ -356,3 +778,1201 @@ fn test_asset_existence_checks() {
     assert_eq!(client.get_asset_owner_count(&asset_id), 1);
     assert!(client.owns_asset(&owner, &asset_id));
 }
+
+// === Archival Helpers ===
+
+#[test]
+fn test_balance_status_distinguishes_missing_and_zero() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    assert_eq!(
+        client.balance_status(&holder, &asset_id),
+        BalanceStatus::Held(100)
+    );
+    assert_eq!(
+        client.balance_status(&other, &asset_id),
+        BalanceStatus::NoEntry
+    );
+
+    client.transfer(&holder, &other, &asset_id, &100);
+    assert_eq!(
+        client.balance_status(&holder, &asset_id),
+        BalanceStatus::Zero
+    );
+    assert_eq!(
+        client.balance_status(&other, &asset_id),
+        BalanceStatus::Held(100)
+    );
+}
+
+#[test]
+fn test_restore_entries_extends_ttl() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let operator = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    client.approve(&holder, &operator, &asset_id, &10);
+
+    let entries = vec![
+        &env,
+        StorageEntry::Balance(asset_id),
+        StorageEntry::OwnerAsset(asset_id),
+        StorageEntry::Allowance(operator.clone(), asset_id),
+        StorageEntry::OperatorApproval(operator.clone()),
+        StorageEntry::Balance(999),
+    ];
+    let restored = client.restore_entries(&holder, &entries);
+
+    assert_eq!(restored, vec![&env, true, true, true, false, false]);
+
+    let ttl = env.as_contract(&client.address, || {
+        let key = DataKey::TokenAllowance(holder.clone(), operator.clone(), asset_id);
+        env.storage().persistent().get_ttl(&stored_key(&env, &key))
+    });
+    assert!(ttl >= PERSISTENT_LIFETIME_THRESHOLD);
+}
+
+#[test]
+fn test_entries_are_namespaced_and_legacy_keys_migrate_once() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    // Fresh deployments store every entry under its namespace
+    let key = DataKey::Balance(holder.clone(), asset_id);
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&stored_key(&env, &key)));
+        assert!(!env.storage().persistent().has(&key));
+    });
+    assert_eq!(
+        client.try_migrate_storage(&vec![&env], &vec![&env], &true),
+        Err(Ok(FractcoreError::AlreadyInitialized.into()))
+    );
+
+    // A deployment from before namespacing holds every entry under its bare
+    // key, the admin's included, and reads miss until the admin moves them
+    let governance = register_governance(&env, &client.address);
+    client.approve(&holder, &operator, &asset_id, &30);
+    client.set_governance_contract(&admin, &governance);
+    let logged = client.get_admin_log_head().entries;
+    let bare = strip_namespaces(
+        &env,
+        &client.address,
+        &[DataKey::NAMESPACE, DataKeyExt::NAMESPACE],
+    );
+    let keys = bare.get(DataKey::NAMESPACE).unwrap();
+    let ext_keys = bare.get(DataKeyExt::NAMESPACE).unwrap();
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(FractcoreError::AlreadyInitialized))
+    );
+    assert!(keys.contains(IntoVal::<Env, Val>::into_val(&DataKey::Admin, &env)));
+    assert_eq!(client.allowance(&holder, &operator, &asset_id), 0);
+    assert_eq!(client.get_governance_contract(), None);
+
+    // Batches are bounded, counting both key types
+    let mut oversized = keys.clone();
+    while oversized.len() + ext_keys.len() <= crate::storage::MAX_MIGRATE_BATCH {
+        oversized.push_back(DataKey::Admin.into_val(&env));
+    }
+    assert_eq!(
+        client.try_migrate_storage(&oversized, &ext_keys, &false),
+        Err(Ok(FractcoreError::BatchTooLarge.into()))
+    );
+
+    // Keys without a bare entry are skipped, and batches leave the migration open
+    let missing = DataKey::TokenAllowance(operator.clone(), holder.clone(), asset_id);
+    let mut first_batch = keys.clone();
+    first_batch.push_back(missing.into_val(&env));
+    assert_eq!(
+        client.migrate_storage(&first_batch, &vec![&env], &false),
+        keys.len()
+    );
+    assert_eq!(client.allowance(&holder, &operator, &asset_id), 30);
+    assert_eq!(client.balance_of(&holder, &asset_id), 100);
+    assert_eq!(
+        client.migrate_storage(&vec![&env], &ext_keys, &true),
+        ext_keys.len()
+    );
+    assert_eq!(client.get_governance_contract(), Some(governance));
+    assert_eq!(client.get_admin_log_head().entries, logged + 1);
+    let key = DataKey::TokenAllowance(holder.clone(), operator.clone(), asset_id);
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&stored_key(&env, &key)));
+        assert!(!env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&DataKey::Admin));
+    });
+
+    // The admin's entry moved with the rest
+    client.transfer_admin(&admin, &operator);
+    assert_eq!(client.get_admin(), operator);
+
+    // The last batch closes the migration
+    assert_eq!(
+        client.try_migrate_storage(&keys, &vec![&env], &true),
+        Err(Ok(FractcoreError::AlreadyInitialized.into()))
+    );
+}
+
+#[test]
+fn test_max_supply_and_finalize_minting() {
+    let (env, admin, client) = setup();
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    // The initial issuance already counts against the cap
+    assert_eq!(
+        client.try_mint_with_max_supply(&owner, &101, &100),
+        Err(Ok(FractcoreError::MaxSupplyExceeded.into()))
+    );
+
+    let asset_id = client.mint_with_max_supply(&owner, &60, &100);
+    assert_eq!(client.max_supply(&asset_id), Some(100));
+    assert!(!client.is_minting_finalized(&asset_id));
+
+    client.mint_to(
+        &asset_id,
+        &vec![&env, recipient.clone()],
+        &vec![&env, 40u64],
+    );
+    assert_eq!(
+        client.try_mint_to(&asset_id, &vec![&env, recipient.clone()], &vec![&env, 1u64]),
+        Err(Ok(FractcoreError::MaxSupplyExceeded.into()))
+    );
+    assert_eq!(client.asset_supply(&asset_id), 100);
+    assert_eq!(client.balance_of(&recipient, &asset_id), 40);
+
+    // A split keeps the cap proportional to supply
+    assert!(client.split_asset(&admin, &asset_id, &2));
+    assert_eq!(client.max_supply(&asset_id), Some(200));
+
+    // Uncapped assets can still be closed by their manager for good
+    let open_id = client.mint(&owner, &10);
+    assert_eq!(client.max_supply(&open_id), None);
+    let manager = Address::generate(&env);
+    client.set_asset_manager(&admin, &open_id, &manager);
+    assert!(client.try_finalize_minting(&owner, &open_id).is_err());
+
+    client.finalize_minting(&manager, &open_id);
+    assert!(client.is_minting_finalized(&open_id));
+    assert_eq!(
+        client.try_mint_to(&open_id, &vec![&env, recipient.clone()], &vec![&env, 1u64]),
+        Err(Ok(FractcoreError::MintingFinalized.into()))
+    );
+    assert_eq!(
+        client.try_finalize_minting(&manager, &open_id),
+        Err(Ok(FractcoreError::MintingFinalized.into()))
+    );
+    assert_eq!(client.asset_supply(&open_id), 10);
+}
+
+#[test]
+fn test_holdings_weighted_draw() {
+    let (env, admin, client) = setup();
+    let whale = Address::generate(&env);
+    let minnow = Address::generate(&env);
+    let asset_id = client.mint(&whale, &99);
+    client.transfer(&whale, &minnow, &asset_id, &1);
+
+    let seed = Bytes::from_array(&env, &[7u8; 32]);
+    let commitment: BytesN<32> = env.crypto().sha256(&seed).into();
+    assert!(client
+        .try_commit_draw(&whale, &asset_id, &commitment)
+        .is_err());
+    let draw_id = client.commit_draw(&admin, &asset_id, &commitment);
+    assert_eq!(client.draw_count(&asset_id), 1);
+
+    // The seed cannot be revealed in the ledger it was committed in
+    assert!(client
+        .try_draw_winner(&admin, &asset_id, &draw_id, &seed)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let wrong_seed = Bytes::from_array(&env, &[8u8; 32]);
+    assert!(client
+        .try_draw_winner(&admin, &asset_id, &draw_id, &wrong_seed)
+        .is_err());
+
+    let winner = client.draw_winner(&admin, &asset_id, &draw_id, &seed);
+    assert!(winner == whale || winner == minnow);
+    let draw = client.get_draw(&asset_id, &draw_id).unwrap();
+    assert_eq!(draw.winner, Some(winner.clone()));
+    assert!(draw.ticket < 100);
+    assert_eq!(winner == minnow, draw.ticket == 99);
+
+    // A draw is settled once
+    assert!(client
+        .try_draw_winner(&admin, &asset_id, &draw_id, &seed)
+        .is_err());
+
+    // A sole holder always wins
+    let sole_id = client.mint(&minnow, &5);
+    let draw_id = client.commit_draw(&admin, &sole_id, &commitment);
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert_eq!(
+        client.draw_winner(&admin, &sole_id, &draw_id, &seed),
+        minnow
+    );
+}
+
+#[test]
+fn test_split_asset_small_holder_set() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    client.transfer(&holder, &other, &asset_id, &30);
+
+    assert!(client.split_asset(&admin, &asset_id, &100));
+
+    assert_eq!(client.balance_of(&holder, &asset_id), 7000);
+    assert_eq!(client.balance_of(&other, &asset_id), 3000);
+    assert_eq!(client.asset_supply(&asset_id), 10000);
+    assert_eq!(client.split_factor(&asset_id), 100);
+
+    // Splits compound
+    assert!(client.split_asset(&admin, &asset_id, &2));
+    assert_eq!(client.balance_of(&other, &asset_id), 6000);
+    assert_eq!(client.split_factor(&asset_id), 200);
+}
+
+#[test]
+fn test_split_asset_in_batches_freezes_transfers() {
+    let (env, admin, client) = setup();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let first = Address::generate(&env);
+    let asset_id = client.mint(&first, &1);
+
+    // 250 holders span 5 owner pages, more than one split call covers
+    let mut recipients = vec![&env];
+    let mut amounts = vec![&env];
+    for _ in 0..249 {
+        recipients.push_back(Address::generate(&env));
+        amounts.push_back(2u64);
+    }
+    mint_to_in_batches(&client, asset_id, &recipients, &amounts);
+    let last = recipients.get(248).unwrap();
+
+    assert!(!client.split_asset(&admin, &asset_id, &10));
+    assert!(client.split_in_progress(&asset_id));
+    assert_eq!(client.asset_supply(&asset_id), 4990);
+    assert_eq!(client.balance_of(&first, &asset_id), 10);
+    assert_eq!(client.balance_of(&last, &asset_id), 2);
+    assert_eq!(client.split_factor(&asset_id), 1);
+
+    // Balances cannot move until every holder is rescaled
+    assert!(client.try_transfer(&first, &last, &asset_id, &1).is_err());
+
+    assert!(client.continue_split(&asset_id));
+    assert!(!client.split_in_progress(&asset_id));
+    assert_eq!(client.balance_of(&last, &asset_id), 20);
+    assert_eq!(client.split_factor(&asset_id), 10);
+
+    client.transfer(&first, &last, &asset_id, &1);
+}
+
+#[test]
+#[should_panic(expected = "Only the asset manager can split")]
+fn test_split_asset_requires_manager() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    client.set_asset_manager(&admin, &asset_id, &governance);
+    assert_eq!(client.asset_manager(&asset_id), governance);
+
+    // The creator handed management over, so only the manager can split
+    client.split_asset(&admin, &asset_id, &10);
+}
+
+#[test]
+fn test_consolidate_asset_records_compensation_remainders() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    client.transfer(&holder, &other, &asset_id, &37);
+
+    assert!(client.consolidate_asset(&admin, &asset_id, &10, &RemainderPolicy::Compensate));
+
+    assert_eq!(client.balance_of(&holder, &asset_id), 6);
+    assert_eq!(client.balance_of(&other, &asset_id), 3);
+    assert_eq!(client.asset_supply(&asset_id), 9);
+    assert_eq!(client.consolidation_factor(&asset_id), 10);
+    assert_eq!(client.fraction_remainder(&holder, &asset_id), 3);
+    assert_eq!(client.fraction_remainder(&other, &asset_id), 7);
+    assert_eq!(client.fraction_remainder_total(&asset_id), 10);
+
+    assert_eq!(client.claim_fraction_remainder(&other, &asset_id), 7);
+    assert_eq!(client.fraction_remainder(&other, &asset_id), 0);
+    assert_eq!(client.fraction_remainder_total(&asset_id), 3);
+    assert_eq!(client.claim_fraction_remainder(&other, &asset_id), 0);
+}
+
+#[test]
+fn test_consolidate_asset_round_nearest_removes_dust_holders() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let dust = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    client.transfer(&holder, &dust, &asset_id, &4);
+
+    assert!(client.consolidate_asset(&admin, &asset_id, &10, &RemainderPolicy::RoundNearest));
+
+    // 96 rounds up to 10, 4 rounds down to nothing
+    assert_eq!(client.balance_of(&holder, &asset_id), 10);
+    assert_eq!(client.balance_of(&dust, &asset_id), 0);
+    assert_eq!(client.asset_supply(&asset_id), 10);
+    assert_eq!(client.get_asset_owner_count(&asset_id), 1);
+    assert!(!client.owns_asset(&dust, &asset_id));
+    assert_eq!(client.fraction_remainder_total(&asset_id), 0);
+}
+
+#[test]
+fn test_consolidate_asset_in_batches_freezes_transfers() {
+    let (env, admin, client) = setup();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let first = Address::generate(&env);
+    let asset_id = client.mint(&first, &1);
+
+    let mut recipients = vec![&env];
+    let mut amounts = vec![&env];
+    for _ in 0..249 {
+        recipients.push_back(Address::generate(&env));
+        amounts.push_back(4u64);
+    }
+    mint_to_in_batches(&client, asset_id, &recipients, &amounts);
+    let last = recipients.get(248).unwrap();
+
+    assert!(!client.consolidate_asset(&admin, &asset_id, &2, &RemainderPolicy::RoundDown));
+    assert!(client.consolidation_in_progress(&asset_id));
+    assert_eq!(client.balance_of(&last, &asset_id), 4);
+
+    // Neither transfers nor a split can start until every holder is divided
+    assert!(client.try_transfer(&last, &first, &asset_id, &1).is_err());
+    assert!(client.try_split_asset(&admin, &asset_id, &2).is_err());
+
+    assert!(client.continue_consolidation(&asset_id));
+    assert!(!client.consolidation_in_progress(&asset_id));
+    assert_eq!(client.balance_of(&last, &asset_id), 2);
+    assert_eq!(client.balance_of(&first, &asset_id), 0);
+    assert_eq!(client.asset_supply(&asset_id), 498);
+    assert_eq!(client.get_asset_owner_count(&asset_id), 249);
+}
+
+#[test]
+fn test_announcements_ring_buffer() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+    let uri = String::from_str(&env, "ipfs://notice");
+
+    for _ in 0..12 {
+        client.post_announcement(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Distribution upcoming"),
+            &hash,
+            &uri,
+        );
+    }
+
+    assert_eq!(client.announcement_count(&asset_id), 12);
+    let feed = client.get_announcements(&asset_id);
+    assert_eq!(feed.len(), 10);
+    // Newest first; the two oldest were overwritten
+    assert_eq!(feed.get(0).unwrap().id, 11);
+    assert_eq!(feed.get(9).unwrap().id, 2);
+    assert_eq!(feed.get(0).unwrap().author, admin);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to post announcements")]
+fn test_announcement_requires_manager() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    client.post_announcement(
+        &holder,
+        &asset_id,
+        &String::from_str(&env, "Fake notice"),
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "ipfs://fake"),
+    );
+}
+
+#[test]
+fn test_mint_distributed_creates_asset_atomically() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://asset");
+
+    let asset_id = client.mint_distributed(
+        &vec![&env, alice.clone(), bob.clone(), alice.clone()],
+        &vec![&env, 600u64, 300u64, 100u64],
+        &Some(uri.clone()),
+    );
+
+    assert_eq!(asset_id, 1);
+    assert_eq!(client.next_asset_id(), 2);
+    assert_eq!(client.asset_supply(&asset_id), 1000);
+    assert_eq!(client.balance_of(&alice, &asset_id), 700);
+    assert_eq!(client.balance_of(&bob, &asset_id), 300);
+    assert_eq!(client.get_asset_owner_count(&asset_id), 2);
+    assert_eq!(client.asset_uri(&asset_id), Some(uri));
+    assert_eq!(client.get_asset_creator(&asset_id), Some(admin));
+}
+
+#[test]
+#[should_panic(expected = "Cannot mint 0 tokens")]
+fn test_mint_distributed_rejects_zero_amount() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.mint_distributed(&vec![&env, alice, bob], &vec![&env, 600u64, 0u64], &None);
+}
+
+#[test]
+fn test_mint_for_records_issuer_as_creator() {
+    let (env, _admin, client) = setup();
+    let issuer = Address::generate(&env);
+    let holder = Address::generate(&env);
+
+    let asset_id = client.mint_for(&issuer, &holder, &100);
+
+    assert_eq!(client.get_asset_creator(&asset_id), Some(issuer.clone()));
+    // The issuer, not the admin, now manages the asset and its metadata
+    assert_eq!(client.asset_manager(&asset_id), issuer);
+    client.set_asset_uri(&issuer, &asset_id, &String::from_str(&env, "ipfs://issuer"));
+}
+
+#[test]
+fn test_set_asset_creator_migrates_existing_asset() {
+    let (env, admin, client) = setup();
+    let issuer = Address::generate(&env);
+    let holder = Address::generate(&env);
+
+    let asset_id = client.mint(&holder, &100);
+    assert_eq!(client.get_asset_creator(&asset_id), Some(admin));
+
+    client.set_asset_creator(&asset_id, &issuer);
+    assert_eq!(client.get_asset_creator(&asset_id), Some(issuer));
+}
+
+#[test]
+fn test_time_weighted_balance_from_checkpoints() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let asset_id = client.mint(&alice, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.transfer(&alice, &bob, &asset_id, &50);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    // Alice held 100 then 50, Bob only held 50 for the second half
+    assert_eq!(client.time_weighted_balance(&alice, &asset_id, &1000), 75);
+    assert_eq!(client.time_weighted_balance(&bob, &asset_id, &1000), 25);
+    assert_eq!(client.time_weighted_balance(&bob, &asset_id, &2000), 50);
+    assert_eq!(client.balance_checkpoints(&alice, &asset_id).len(), 2);
+
+    // Point-in-time balances, unknown before a holder's history starts
+    assert_eq!(client.balance_at(&alice, &asset_id, &1500), Some(100));
+    assert_eq!(client.balance_at(&alice, &asset_id, &2000), Some(50));
+    assert_eq!(client.balance_at(&bob, &asset_id, &1500), None);
+
+    // Splits rescale history so the average stays in current units
+    client.split_asset(&admin, &asset_id, &2);
+    assert_eq!(client.time_weighted_balance(&alice, &asset_id, &1000), 150);
+}
+
+#[test]
+fn test_basket_deposit_and_redeem() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let gold = client.mint(&alice, &1000);
+    let land = client.mint(&alice, &500);
+    client.transfer(&alice, &bob, &gold, &300);
+    client.transfer(&alice, &bob, &land, &150);
+
+    let components = vec![
+        &env,
+        BasketComponent {
+            asset_id: gold,
+            weight: 2,
+        },
+        BasketComponent {
+            asset_id: land,
+            weight: 1,
+        },
+    ];
+    let basket_id = client.create_basket(&components);
+    assert!(client.is_basket(&basket_id));
+    assert_eq!(client.get_basket(&basket_id), components);
+    assert_eq!(client.asset_supply(&basket_id), 0);
+
+    // First deposit uses the weights: 100 tokens take 200 gold and 100 land
+    client.deposit_to_basket(&alice, &basket_id, &100);
+    assert_eq!(client.balance_of(&alice, &basket_id), 100);
+    assert_eq!(client.balance_of(&alice, &gold), 500);
+    assert_eq!(client.balance_of(&alice, &land), 250);
+    assert_eq!(client.basket_holdings(&basket_id), vec![&env, 200, 100]);
+
+    // A split of a component doubles what backs every basket token
+    client.split_asset(&admin, &gold, &2);
+    assert_eq!(client.basket_holdings(&basket_id), vec![&env, 400, 100]);
+
+    // Later deposits match the current holdings per token
+    client.deposit_to_basket(&bob, &basket_id, &50);
+    assert_eq!(client.balance_of(&bob, &gold), 400);
+    assert_eq!(client.balance_of(&bob, &land), 100);
+    assert_eq!(client.asset_supply(&basket_id), 150);
+
+    client.redeem_from_basket(&alice, &basket_id, &100);
+    assert_eq!(client.balance_of(&alice, &basket_id), 0);
+    assert_eq!(client.balance_of(&alice, &gold), 1400);
+    assert_eq!(client.balance_of(&alice, &land), 350);
+    assert_eq!(client.basket_holdings(&basket_id), vec![&env, 200, 50]);
+    assert_eq!(client.asset_owners(&basket_id), vec![&env, bob]);
+}
+
+#[test]
+#[should_panic(expected = "Basket tokens are only minted by deposit")]
+fn test_basket_cannot_be_minted_directly() {
+    let (env, _admin, client) = setup();
+    let alice = Address::generate(&env);
+    let gold = client.mint(&alice, &1000);
+    let basket_id = client.create_basket(&vec![
+        &env,
+        BasketComponent {
+            asset_id: gold,
+            weight: 1,
+        },
+    ]);
+
+    client.mint_to(&basket_id, &vec![&env, alice], &vec![&env, 10]);
+}
+
+#[test]
+fn test_social_recovery_migrates_balances() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let new_key = Address::generate(&env);
+    let guardian1 = Address::generate(&env);
+    let guardian2 = Address::generate(&env);
+    let guardian3 = Address::generate(&env);
+    let gold = client.mint(&holder, &100);
+    let land = client.mint(&holder, &40);
+
+    client.set_guardians(
+        &holder,
+        &vec![&env, guardian1.clone(), guardian2.clone(), guardian3],
+        &2,
+    );
+    assert_eq!(client.get_guardians(&holder).unwrap().threshold, 2);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.propose_recovery(&guardian1, &holder, &new_key);
+    assert_eq!(client.get_recovery(&holder).unwrap().executable_at, None);
+
+    client.approve_recovery(&guardian2, &holder);
+    let request = client.get_recovery(&holder).unwrap();
+    assert_eq!(request.executable_at, Some(1000 + RECOVERY_CHALLENGE_DELAY));
+    assert!(client.try_execute_recovery(&holder).is_err());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + RECOVERY_CHALLENGE_DELAY);
+    assert_eq!(client.execute_recovery(&holder), 2);
+
+    assert_eq!(client.balance_of(&new_key, &gold), 100);
+    assert_eq!(client.balance_of(&new_key, &land), 40);
+    assert_eq!(client.balance_of(&holder, &gold), 0);
+    assert_eq!(client.asset_owners(&gold), vec![&env, new_key]);
+    assert_eq!(client.get_recovery(&holder), None);
+    assert_eq!(client.get_guardians(&holder), None);
+}
+
+#[test]
+fn test_holder_cancels_recovery_during_delay() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let thief = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    client.set_guardians(&holder, &vec![&env, guardian.clone()], &1);
+
+    // Only guardians can start a recovery
+    assert!(client
+        .try_propose_recovery(&outsider, &holder, &thief)
+        .is_err());
+
+    client.propose_recovery(&guardian, &holder, &thief);
+    assert!(client
+        .get_recovery(&holder)
+        .unwrap()
+        .executable_at
+        .is_some());
+
+    // Guardians cannot be swapped out while a recovery is pending
+    assert!(client
+        .try_set_guardians(&holder, &vec![&env, outsider], &1)
+        .is_err());
+
+    client.cancel_recovery(&holder);
+    env.ledger()
+        .with_mut(|li| li.timestamp = RECOVERY_CHALLENGE_DELAY + 1);
+    assert!(client.try_execute_recovery(&holder).is_err());
+    assert_eq!(client.balance_of(&holder, &asset_id), 100);
+}
+
+#[test]
+fn test_cap_table_export_is_stable_across_transfers() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let asset_id = client.mint(&alice, &600);
+    client.transfer(&alice, &bob, &asset_id, &300);
+    client.transfer(&alice, &carol, &asset_id, &100);
+
+    // Only the asset manager (the issuer) can snapshot
+    assert!(client
+        .try_create_cap_table_snapshot(&bob, &asset_id)
+        .is_err());
+
+    let snapshot_id = client.create_cap_table_snapshot(&admin, &asset_id);
+    let snapshot = client.get_cap_table_snapshot(&asset_id).unwrap();
+    assert_eq!(snapshot.id, snapshot_id);
+    assert_eq!(snapshot.supply, 600);
+    assert_eq!(snapshot.owner_count, 3);
+    assert_eq!(snapshot.page_count, 1);
+
+    let expected = vec![
+        &env,
+        (alice.clone(), 200u64, 3333u32),
+        (bob.clone(), 300u64, 5000u32),
+        (carol.clone(), 100u64, 1666u32),
+    ];
+    assert_eq!(
+        client.export_cap_table(&asset_id, &None, &0),
+        (expected.clone(), None)
+    );
+
+    // A new holder arrives and carol sells out between two pages of the export
+    let (first, cursor) = client.export_cap_table(&asset_id, &None, &2);
+    assert_eq!(first, expected.slice(0..2));
+    client.transfer(&alice, &dave, &asset_id, &200);
+    client.transfer(&carol, &bob, &asset_id, &100);
+    assert_eq!(
+        client.export_cap_table(&asset_id, &cursor, &2),
+        (expected.slice(2..3), None)
+    );
+    assert_eq!(client.export_cap_table(&asset_id, &None, &0).0, expected);
+
+    // A fresh snapshot reflects the new holdings and refuses the old cursor
+    client.create_cap_table_snapshot(&admin, &asset_id);
+    assert!(client.try_export_cap_table(&asset_id, &cursor, &2).is_err());
+    assert_eq!(
+        client.export_cap_table(&asset_id, &None, &0).0,
+        vec![
+            &env,
+            (bob.clone(), 400u64, 6666u32),
+            (dave.clone(), 200u64, 3333u32),
+        ]
+    );
+}
+
+#[test]
+fn test_owner_cursor_survives_departing_holders() {
+    let (env, _admin, client) = setup();
+    let holders = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let asset_id = client.mint(&holders[0], &400);
+    for holder in holders.iter().skip(1) {
+        client.transfer(&holders[0], holder, &asset_id, &100);
+    }
+
+    let (first, cursor) = client.get_asset_owners_page(&asset_id, &None, &2);
+    assert_eq!(first, vec![&env, holders[0].clone(), holders[1].clone()]);
+    assert_eq!(cursor.clone().unwrap().position, 2);
+
+    // holders[0] sells out, moving the rest of the page up: the walk re-reads
+    // the page rather than skip holders[2]
+    client.transfer(&holders[0], &holders[1], &asset_id, &100);
+    let (second, cursor) = client.get_asset_owners_page(&asset_id, &cursor, &2);
+    assert_eq!(second, vec![&env, holders[1].clone(), holders[2].clone()]);
+    let (last, cursor) = client.get_asset_owners_page(&asset_id, &cursor, &2);
+    assert_eq!(last, vec![&env, holders[3].clone()]);
+    assert_eq!(cursor, None);
+
+    // Without changes a walk returns each owner once
+    let (all, cursor) = client.get_asset_owners_page(&asset_id, &None, &0);
+    assert_eq!(all, client.asset_owners(&asset_id));
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_asset_owners_refuses_assets_past_first_pages() {
+    let (env, _admin, client) = setup();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let first = Address::generate(&env);
+    let asset_id = client.mint(&first, &1);
+    let mut recipients = vec![&env];
+    let mut amounts = vec![&env];
+    for _ in 0..MAX_ASSET_OWNERS_PAGES * MAX_BATCH_SIZE {
+        recipients.push_back(Address::generate(&env));
+        amounts.push_back(1u64);
+    }
+    mint_to_in_batches(&client, asset_id, &recipients, &amounts);
+
+    let owner_count = MAX_ASSET_OWNERS_PAGES * MAX_BATCH_SIZE + 1;
+    assert_eq!(client.get_asset_owner_count(&asset_id), owner_count);
+
+    // The shim refuses rather than drop the last owner, who is reachable by paging
+    assert_eq!(
+        client.try_asset_owners(&asset_id),
+        Err(Ok(FractcoreError::TooManyOwners.into()))
+    );
+    let last = recipients.last().unwrap();
+
+    let mut paged = vec![&env];
+    let mut cursor = None;
+    loop {
+        let (page, next) = client.get_asset_owners_page(&asset_id, &cursor, &0);
+        paged.append(&page);
+        if next.is_none() {
+            break;
+        }
+        cursor = next;
+    }
+    assert_eq!(paged.len(), owner_count);
+    assert_eq!(paged.get(0).unwrap(), first);
+    assert_eq!(paged.last().unwrap(), last);
+}
+
+#[test]
+fn test_batch_inputs_are_bounded() {
+    let (env, _admin, client) = setup();
+    let holder = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    let mut recipients = vec![&env];
+    let mut amounts = vec![&env];
+    let mut asset_ids = vec![&env];
+    for _ in 0..=MAX_BATCH_SIZE {
+        recipients.push_back(Address::generate(&env));
+        amounts.push_back(1u64);
+        asset_ids.push_back(asset_id);
+    }
+
+    // One entry over the limit is rejected before anything is written
+    let too_large = Some(Ok(FractcoreError::BatchTooLarge.into()));
+    assert_eq!(
+        client.try_mint_to(&asset_id, &recipients, &amounts).err(),
+        too_large
+    );
+    assert_eq!(
+        client
+            .try_mint_distributed(&recipients, &amounts, &None)
+            .err(),
+        too_large
+    );
+    assert_eq!(
+        client.try_balance_of_batch(&recipients, &asset_ids).err(),
+        too_large
+    );
+    assert_eq!(
+        client
+            .try_batch_transfer_from(&holder, &holder, &holder, &asset_ids, &amounts)
+            .err(),
+        too_large
+    );
+    assert_eq!(
+        client
+            .try_batch_transfer_to(&holder, &holder, &asset_id, &recipients, &amounts)
+            .err(),
+        too_large
+    );
+    assert_eq!(client.asset_supply(&asset_id), 100);
+    assert_eq!(client.get_asset_owner_count(&asset_id), 1);
+
+    // Exactly MAX_BATCH_SIZE fits in one call
+    recipients.pop_back();
+    amounts.pop_back();
+    client.mint_to(&asset_id, &recipients, &amounts);
+    assert_eq!(client.asset_supply(&asset_id), 100 + MAX_BATCH_SIZE as u64);
+}
+
+#[test]
+fn test_transfer_locks_block_holders_until_released() {
+    let (env, admin, client) = setup();
+    let governance = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let other = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let asset_id = client.mint(&voter, &100);
+    client.transfer(&voter, &other, &asset_id, &40);
+
+    // Locks need the asset manager's permission first
+    assert!(client
+        .try_open_transfer_lock(&governance, &asset_id, &1, &false, &1000)
+        .is_err());
+    client.set_transfer_lock_authority(&admin, &asset_id, &Some(governance.clone()));
+
+    // Holder-scoped lock: only the added voter is frozen
+    client.open_transfer_lock(&governance, &asset_id, &1, &false, &1000);
+    client.lock_holder_transfers(&governance, &asset_id, &1, &voter);
+    assert!(client.is_transfer_locked(&voter, &asset_id));
+    assert!(client.try_transfer(&voter, &buyer, &asset_id, &1).is_err());
+    client.transfer(&other, &buyer, &asset_id, &1);
+
+    client.release_transfer_lock(&governance, &asset_id, &1);
+    client.transfer(&voter, &buyer, &asset_id, &1);
+
+    // Asset-wide lock freezes everyone and lapses at its end time
+    client.open_transfer_lock(&governance, &asset_id, &2, &true, &1000);
+    assert!(client.try_transfer(&other, &buyer, &asset_id, &1).is_err());
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert!(!client.is_transfer_locked(&other, &asset_id));
+    client.transfer(&other, &buyer, &asset_id, &1);
+    assert_eq!(client.balance_of(&buyer, &asset_id), 3);
+}
+
+#[test]
+fn test_co_managers_change_asset_on_quorum() {
+    let (env, admin, client) = setup();
+    let holder = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let asset_id = client.mint(&holder, &100);
+
+    let managers = vec![&env, alice.clone(), bob.clone(), carol.clone()];
+    assert!(client
+        .try_set_co_managers(&alice, &asset_id, &managers, &2)
+        .is_err());
+    assert!(client
+        .try_set_co_managers(&admin, &asset_id, &managers, &4)
+        .is_err());
+    client.set_co_managers(&admin, &asset_id, &managers, &2);
+    assert_eq!(
+        client.get_co_managers(&asset_id),
+        Some(CoManagement {
+            managers: managers.clone(),
+            quorum: 2,
+        })
+    );
+
+    // Direct single-manager changes are closed once the asset is co-managed
+    let uri = String::from_str(&env, "ipfs://joint");
+    assert!(client.try_set_asset_uri(&admin, &asset_id, &uri).is_err());
+    assert!(client
+        .try_set_concentration_thresholds(&admin, &asset_id, &vec![&env, 5000u32])
+        .is_err());
+    assert!(client
+        .try_propose_manager_action(&holder, &asset_id, &ManagerAction::HaltTransfers)
+        .is_err());
+
+    let uri_action =
+        client.propose_manager_action(&alice, &asset_id, &ManagerAction::SetUri(uri.clone()));
+    let halt_action = client.propose_manager_action(&bob, &asset_id, &ManagerAction::HaltTransfers);
+    let pending = client.get_pending_manager_actions(&asset_id);
+    assert_eq!(pending.len(), 2);
+    assert_eq!(
+        pending.get(0).unwrap().confirmations,
+        vec![&env, alice.clone()]
+    );
+    assert_eq!(client.asset_uri(&asset_id), None);
+    assert!(client
+        .try_confirm_manager_action(&alice, &asset_id, &uri_action)
+        .is_err());
+
+    assert!(client.confirm_manager_action(&carol, &asset_id, &uri_action));
+    assert_eq!(client.asset_uri(&asset_id), Some(uri));
+    assert_eq!(client.get_manager_action(&asset_id, &uri_action), None);
+
+    // A halt blocks every holder until the co-managers resume transfers
+    client.confirm_manager_action(&alice, &asset_id, &halt_action);
+    assert!(client.is_transfer_locked(&holder, &asset_id));
+    assert!(client.try_transfer(&holder, &buyer, &asset_id, &1).is_err());
+    let resume = client.propose_manager_action(&carol, &asset_id, &ManagerAction::ResumeTransfers);
+    client.confirm_manager_action(&bob, &asset_id, &resume);
+    client.transfer(&holder, &buyer, &asset_id, &1);
+
+    // Changing the set drops actions confirmed by the old one
+    let stale = client.propose_manager_action(
+        &alice,
+        &asset_id,
+        &ManagerAction::SetTransferLockAuthority(Some(buyer.clone())),
+    );
+    let replace = client.propose_manager_action(
+        &bob,
+        &asset_id,
+        &ManagerAction::SetCoManagers(vec![&env, alice.clone()], 1),
+    );
+    client.confirm_manager_action(&carol, &asset_id, &replace);
+    assert_eq!(client.get_manager_action(&asset_id, &stale), None);
+    assert!(client.get_pending_manager_actions(&asset_id).is_empty());
+    assert_eq!(client.get_co_managers(&asset_id).unwrap().quorum, 1);
+
+    // With a quorum of one a proposal executes at once; an empty set ends co-management
+    client.propose_manager_action(
+        &alice,
+        &asset_id,
+        &ManagerAction::SetCoManagers(Vec::new(&env), 0),
+    );
+    assert_eq!(client.get_co_managers(&asset_id), None);
+    client.set_concentration_thresholds(&admin, &asset_id, &vec![&env, 5000u32]);
+}
+
+#[test]
+fn test_manager_action_proposer_cancels() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let asset_id = client.mint(&admin, &100);
+    client.set_co_managers(
+        &admin,
+        &asset_id,
+        &vec![&env, alice.clone(), bob.clone()],
+        &2,
+    );
+
+    // Actions that could not run are refused when proposed
+    assert!(client
+        .try_propose_manager_action(&alice, &asset_id, &ManagerAction::RemoveUri)
+        .is_err());
+    assert!(client
+        .try_propose_manager_action(
+            &alice,
+            &asset_id,
+            &ManagerAction::SetConcentrationThresholds(vec![&env, 6000u32, 5000])
+        )
+        .is_err());
+
+    let action_id = client.propose_manager_action(&alice, &asset_id, &ManagerAction::HaltTransfers);
+    assert!(client
+        .try_cancel_manager_action(&bob, &asset_id, &action_id)
+        .is_err());
+    client.cancel_manager_action(&alice, &asset_id, &action_id);
+    assert!(client.get_pending_manager_actions(&asset_id).is_empty());
+    assert!(client
+        .try_confirm_manager_action(&bob, &asset_id, &action_id)
+        .is_err());
+    assert!(!client.is_transfer_locked(&admin, &asset_id));
+}
+
+#[test]
+fn test_denied_recipients_and_rescue() {
+    let (env, admin, client) = setup();
+    let alice = Address::generate(&env);
+    let gold = client.mint(&alice, &1000);
+    let custody = client.address.clone();
+
+    // The contract refuses fractions sent to itself by default
+    assert_eq!(client.get_denied_recipients(), vec![&env, custody.clone()]);
+    assert!(client.try_transfer(&alice, &custody, &gold, &10).is_err());
+
+    // Baskets still take custody of their components
+    let basket_id = client.create_basket(&vec![
+        &env,
+        BasketComponent {
+            asset_id: gold,
+            weight: 2,
+        },
+    ]);
+    client.deposit_to_basket(&alice, &basket_id, &100);
+    assert_eq!(client.balance_of(&custody, &gold), 200);
+    assert_eq!(client.rescuable(&gold), 0);
+
+    // With the list cleared a mistaken transfer goes through and can be rescued
+    client.set_denied_recipients(&admin, &Vec::new(&env));
+    client.transfer(&alice, &custody, &gold, &30);
+    assert_eq!(client.rescuable(&gold), 30);
+
+    assert_eq!(
+        client.try_rescue_fractions(&alice, &gold, &alice, &30),
+        Err(Ok(FractcoreError::Unauthorized.into()))
+    );
+    assert!(client
+        .try_rescue_fractions(&admin, &gold, &alice, &31)
+        .is_err());
+    client.rescue_fractions(&admin, &gold, &alice, &30);
+    assert_eq!(client.balance_of(&alice, &gold), 800);
+    assert_eq!(client.basket_holdings(&basket_id), vec![&env, 200]);
+
+    // Listed addresses refuse plain transfers and third-party transfer_from
+    let vault = Address::generate(&env);
+    client.set_denied_recipients(&admin, &vec![&env, vault.clone()]);
+    assert!(client.try_transfer(&alice, &vault, &gold, &1).is_err());
+    client.approve(&alice, &admin, &gold, &5);
+    assert!(client
+        .try_transfer_from(&admin, &alice, &vault, &gold, &5)
+        .is_err());
+
+    // A listed address pulling into its own custody is allowed
+    client.approve(&alice, &vault, &gold, &5);
+    client.transfer_from(&vault, &alice, &vault, &gold, &5);
+    assert_eq!(client.balance_of(&vault, &gold), 5);
+}
+
+/// Events of the last invocation whose first topic is `name`
+fn count_events(env: &Env, name: Symbol) -> u32 {
+    let name: Val = name.into_val(env);
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        if topics.get(0).map(|topic| topic.get_payload()) == Some(name.get_payload()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_event_level_controls_batch_mint_events() {
+    let (env, _admin, client) = setup();
+    let owner = Address::generate(&env);
+    let recipients = vec![&env, Address::generate(&env), Address::generate(&env)];
+    let amounts = vec![&env, 10u64, 20u64];
+    let asset_id = client.mint(&owner, &100);
+    assert_eq!(client.event_level(), EventLevel::Full);
+
+    client.mint_to(&asset_id, &recipients, &amounts);
+    assert_eq!(count_events(&env, symbol_short!("mint_to")), 2);
+    assert_eq!(count_events(&env, symbol_short!("mint_sum")), 0);
+
+    client.set_event_level(&EventLevel::Summary);
+    client.mint_to(&asset_id, &recipients, &amounts);
+    assert_eq!(count_events(&env, symbol_short!("mint_to")), 0);
+    assert_eq!(count_events(&env, symbol_short!("mint_sum")), 1);
+
+    // Minimal drops the URI event of a distributed mint but keeps the mint itself
+    client.set_event_level(&EventLevel::Minimal);
+    client.mint_distributed(
+        &recipients,
+        &amounts,
+        &Some(String::from_str(&env, "ipfs://asset")),
+    );
+    assert_eq!(count_events(&env, symbol_short!("mint_to")), 0);
+    assert_eq!(count_events(&env, symbol_short!("uri")), 0);
+    assert_eq!(count_events(&env, symbol_short!("mint_dist")), 1);
+
+    // Balances are credited the same way at every level
+    assert_eq!(
+        client.balance_of(&recipients.get(1).unwrap(), &asset_id),
+        40
+    );
+}
+
+/// Data of the `conc_alrt` event of the last invocation, if any
+fn concentration_alert(env: &Env) -> Option<(Option<Address>, u32, u32, bool)> {
+    let name: Val = symbol_short!("conc_alrt").into_val(env);
+    env.events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics.get(0).map(|topic| topic.get_payload()) == Some(name.get_payload())
+        })
+        .map(|(_, _, data)| data.into_val(env))
+}
+
+#[test]
+fn test_concentration_alerts_on_threshold_crossings() {
+    let (env, admin, client) = setup();
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let other = Address::generate(&env);
+    let asset_id = client.mint(&creator, &1000);
+
+    // Already above both thresholds: recorded without an alert
+    client.set_concentration_thresholds(&admin, &asset_id, &vec![&env, 2500u32, 5000]);
+    assert_eq!(concentration_alert(&env), None);
+
+    client.transfer(&creator, &buyer, &asset_id, &300);
+    assert_eq!(concentration_alert(&env), None);
+
+    // Creator falls from 70% to 40%
+    client.transfer(&creator, &other, &asset_id, &300);
+    assert_eq!(
+        concentration_alert(&env),
+        Some((Some(creator.clone()), 4000, 5000, false))
+    );
+
+    // The buyer now holds 60%
+    client.transfer(&creator, &buyer, &asset_id, &300);
+    assert_eq!(
+        concentration_alert(&env),
+        Some((Some(buyer.clone()), 6000, 5000, true))
+    );
+
+    let view = client.get_concentration(&asset_id);
+    assert_eq!(view.supply, 1000);
+    assert_eq!((view.largest_share_bps, view.top_share_bps), (6000, 10000));
+    assert_eq!(
+        view.top_holders,
+        vec![
+            &env,
+            HolderBalance {
+                holder: buyer.clone(),
+                balance: 600,
+            },
+            HolderBalance {
+                holder: other.clone(),
+                balance: 300,
+            },
+            HolderBalance {
+                holder: creator.clone(),
+                balance: 100,
+            },
+        ]
+    );
+
+    // Dilution by minting is a downward crossing too
+    let newcomers = vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.mint_to(&asset_id, &newcomers, &vec![&env, 500u64, 500]);
+    assert_eq!(
+        concentration_alert(&env),
+        Some((Some(buyer.clone()), 3000, 5000, false))
+    );
+
+    // A split leaves the shares unchanged
+    client.split_asset(&admin, &asset_id, &2);
+    let view = client.get_concentration(&asset_id);
+    assert_eq!(view.largest_share_bps, 3000);
+    assert_eq!(view.top_holders.get(0).unwrap().balance, 1200);
+
+    assert!(client
+        .try_set_concentration_thresholds(&buyer, &asset_id, &vec![&env, 5000u32])
+        .is_err());
+    assert!(client
+        .try_set_concentration_thresholds(&admin, &asset_id, &vec![&env, 5000u32, 5000])
+        .is_err());
+}
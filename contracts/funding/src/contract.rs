@@ -1,23 +1,96 @@
-use crate::methods::{admin, distribution, funds, initialization, management, queries};
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use crate::errors::FundingError;
+use crate::methods::{
+    admin, admin_log, analytics, batches, compensation, distribution, earmarks, emergency, fees,
+    fractions, funds, initialization, invariants, ledger, management, petty_cash, queries, tips,
+};
+use crate::storage::{
+    AccountTotals, AdminLogEntry, AdminLogHead, BatchedDistribution, ConvertedTotals, Cursor,
+    DistributionMode, DistributionOutcome, DistributionPlan, DistributionPreview,
+    DistributionRecord, Earmark, EmergencyWithdrawal, EventLevel, FractionReward, HolderPayout,
+    PettyCashConfig, PettySpend, ProtocolFeeConfig, PurposeTotals, ReleaseCondition,
+    SupporterTotals, TipReceipt, UpstreamPause,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Val, Vec};
 
 #[contract]
 pub struct FundingContract;
 
 #[contractimpl]
 impl FundingContract {
-    /// TODO: Emergency withdraw from asset's SAC by Poll
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        fnft_contract: Address,
+    ) -> Result<(), FundingError> {
+        initialization::construct(env, admin, fnft_contract)
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fnft_contract: Address,
+    ) -> Result<(), FundingError> {
+        initialization::initialize(env, admin, fnft_contract)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Name the governance contract (admin for the first hand-over, then the
+    /// current governance contract)
+    pub fn set_governance_contract(
+        env: Env,
+        caller: Address,
+        governance_contract: Address,
+    ) -> Result<(), FundingError> {
+        admin::set_governance_contract(env, caller, governance_contract)
+    }
+
+    /// Replace the contract code with an uploaded WASM (governance contract only)
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), FundingError> {
+        admin::upgrade(env, caller, new_wasm_hash)
+    }
 
-    pub fn initialize(env: Env, admin: Address, fnft_contract: Address) {
-        initialization::initialize(env, admin, fnft_contract);
+    /// Move entries written before namespacing under their namespace, in
+    /// batches; `last` closes the migration (admin only)
+    pub fn migrate_storage(
+        env: Env,
+        admin: Address,
+        keys: Vec<Val>,
+        last: bool,
+    ) -> Result<u32, FundingError> {
+        admin::migrate_storage(env, admin, keys, last)
     }
 
-    pub fn set_governance_contract(env: Env, admin: Address, governance_contract: Address) {
-        admin::set_governance_contract(env, admin, governance_contract);
+    /// Choose how many events distributions publish: per holder (Full), the
+    /// aggregate only (Summary) or just the distribution itself (Minimal)
+    pub fn set_event_level(
+        env: Env,
+        admin: Address,
+        level: EventLevel,
+    ) -> Result<(), FundingError> {
+        admin::set_event_level(env, admin, level)
+    }
+
+    pub fn event_level(env: Env) -> EventLevel {
+        admin::event_level(&env)
     }
     /// Register SAC address for an asset
-    pub fn register_asset_sac(env: Env, caller: Address, asset_id: u64, sac_address: Address) {
-        management::register_asset_sac(env, caller, asset_id, sac_address);
+    pub fn register_asset_sac(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        sac_address: Address,
+    ) -> Result<(), FundingError> {
+        management::register_asset_sac(env, caller, asset_id, sac_address)
     }
 
     /// Get the SAC address for an asset
@@ -31,8 +104,13 @@ impl FundingContract {
     }
 
     /// Deposit funds to asset's SAC (with tracking)
-    pub fn deposit_funds(env: Env, depositor: Address, asset_id: u64, amount: i128) {
-        funds::deposit_funds(env, depositor, asset_id, amount);
+    pub fn deposit_funds(
+        env: Env,
+        depositor: Address,
+        asset_id: u64,
+        amount: i128,
+    ) -> Result<(), FundingError> {
+        funds::deposit_funds(env, depositor, asset_id, amount)
     }
 
     /// Distribute funds from asset's SAC to Asset Owners
@@ -42,8 +120,131 @@ impl FundingContract {
         asset_id: u64,
         amount: u128,
         description: String,
-    ) {
-        distribution::distribute_funds(env, caller, asset_id, amount, description);
+    ) -> Result<DistributionOutcome, FundingError> {
+        distribution::distribute_funds(env, caller, asset_id, amount, description)
+    }
+
+    /// Distribute with an explicit weighting strategy, e.g. time-weighted balances or equal
+    /// shares per holder (admin/governance only)
+    pub fn distribute_funds_with_mode(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        amount: u128,
+        description: String,
+        mode: DistributionMode,
+    ) -> Result<DistributionOutcome, FundingError> {
+        distribution::distribute_funds_with_mode(env, caller, asset_id, amount, description, mode)
+    }
+
+    /// Distribute to every holder except `excluded`, renormalizing shares (admin/governance only)
+    pub fn distribute_funds_excluding(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        amount: u128,
+        description: String,
+        excluded: Vec<Address>,
+    ) -> Result<DistributionOutcome, FundingError> {
+        distribution::distribute_funds_excluding(
+            env,
+            caller,
+            asset_id,
+            amount,
+            description,
+            excluded,
+        )
+    }
+
+    /// Distribute fractions of another (or the same) asset held by `reward.source`
+    /// instead of XLM (admin/governance only)
+    pub fn distribute_fractions(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        reward: FractionReward,
+        description: String,
+        mode: DistributionMode,
+    ) -> Result<DistributionOutcome, FundingError> {
+        fractions::distribute_fractions(env, caller, asset_id, reward, description, mode)
+    }
+
+    /// Fractions asset a distribution paid out, `None` for XLM distributions
+    pub fn get_distribution_reward_asset(
+        env: Env,
+        asset_id: u64,
+        distribution_id: u32,
+    ) -> Option<u64> {
+        fractions::get_reward_asset(env, asset_id, distribution_id)
+    }
+
+    /// Dry run of a distribution: recipients, fee, dust and the largest payouts
+    pub fn preview_distribution(
+        env: Env,
+        asset_id: u64,
+        amount: u128,
+        min_payout: u128,
+    ) -> Result<DistributionPreview, FundingError> {
+        distribution::preview_distribution(env, asset_id, amount, min_payout)
+    }
+
+    /// Set how many owner pages one distribution batch pays (admin only, at most MAX_BATCH_PAGES)
+    pub fn set_distribution_batch_pages(
+        env: Env,
+        admin: Address,
+        pages: u32,
+    ) -> Result<(), FundingError> {
+        batches::set_batch_pages(env, admin, pages)
+    }
+
+    pub fn get_distribution_batch_pages(env: Env) -> u32 {
+        batches::get_batch_pages(env)
+    }
+
+    /// Recipients, batches and owner page ranges of a distribution under the configured
+    /// batch size; one batch means it fits in a single transaction
+    pub fn plan_distribution(
+        env: Env,
+        asset_id: u64,
+        amount: u128,
+    ) -> Result<DistributionPlan, FundingError> {
+        batches::plan_distribution(env, asset_id, amount)
+    }
+
+    /// Start a distribution paid batch by batch along `plan` (admin/governance only);
+    /// returns its distribution id
+    pub fn start_batched_distribution(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        amount: u128,
+        description: String,
+        plan: DistributionPlan,
+    ) -> Result<u32, FundingError> {
+        batches::start_batched_distribution(env, caller, asset_id, amount, description, plan)
+    }
+
+    /// Pay the next batch of the asset's batched distribution; returns the outcome after
+    /// the last batch
+    pub fn run_distribution_batch(
+        env: Env,
+        asset_id: u64,
+        batch_index: u32,
+    ) -> Result<Option<DistributionOutcome>, FundingError> {
+        batches::run_distribution_batch(env, asset_id, batch_index)
+    }
+
+    /// Record a batched distribution with what its batches paid so far (admin/governance only)
+    pub fn cancel_batched_distribution(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+    ) -> Result<DistributionOutcome, FundingError> {
+        batches::cancel_batched_distribution(env, caller, asset_id)
+    }
+
+    pub fn get_batched_distribution(env: Env, asset_id: u64) -> Option<BatchedDistribution> {
+        batches::get_batched_distribution(env, asset_id)
     }
 
     /// Allow asset owners to distribute funds
@@ -53,12 +254,12 @@ impl FundingContract {
         asset_id: u64,
         amount: u128,
         description: String,
-    ) {
-        distribution::owner_distribute_funds(env, caller, asset_id, amount, description);
+    ) -> Result<DistributionOutcome, FundingError> {
+        distribution::owner_distribute_funds(env, caller, asset_id, amount, description)
     }
 
     /// Get SAC balance for an asset
-    pub fn asset_funds(env: Env, asset_id: u64) -> u128 {
+    pub fn asset_funds(env: Env, asset_id: u64) -> Result<u128, FundingError> {
         queries::asset_funds(env, asset_id)
     }
 
@@ -67,25 +268,366 @@ impl FundingContract {
         queries::total_distributed(env, asset_id)
     }
 
+    /// Set or clear (`None`) the oracle converting totals into a reference currency (admin only)
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Option<Address>,
+    ) -> Result<(), FundingError> {
+        analytics::set_price_oracle(env, admin, oracle)
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        analytics::get_price_oracle(env)
+    }
+
+    /// Distributed and held totals of up to MAX_PAGE_SIZE assets in their own tokens and
+    /// in the oracle's reference currency, with the rates and timestamps used
+    pub fn get_converted_totals(
+        env: Env,
+        asset_ids: Vec<u64>,
+    ) -> Result<ConvertedTotals, FundingError> {
+        analytics::get_converted_totals(env, asset_ids)
+    }
+
     /// Get number of distributions for an asset
     pub fn get_distribution_count(env: Env, asset_id: u64) -> u32 {
         queries::get_distribution_count(env, asset_id)
     }
 
-    pub fn get_fnft_contract_address(env: Env) -> Address {
+    /// Accounting for a past distribution (0-based index)
+    pub fn get_distribution(env: Env, asset_id: u64, index: u32) -> Option<DistributionRecord> {
+        queries::get_distribution(env, asset_id, index)
+    }
+
+    /// Up to `limit` (index, record) pairs (capped at MAX_PAGE_SIZE) from `cursor`, with the
+    /// cursor of the next page
+    pub fn get_distributions_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<(u32, DistributionRecord)>, Option<Cursor>) {
+        queries::get_distributions_page(env, asset_id, cursor, limit)
+    }
+
+    /// Up to `limit` distribution payouts (capped at MAX_PAGE_SIZE) `holder` received,
+    /// oldest first, from `cursor`, with the cursor of the next page
+    pub fn get_holder_payouts_page(
+        env: Env,
+        holder: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<HolderPayout>, Option<Cursor>) {
+        queries::get_holder_payouts_page(env, holder, cursor, limit)
+    }
+
+    /// Set the protocol fee taken from distributions (admin only, capped at MAX_FEE_BPS)
+    pub fn set_protocol_fee(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+        fee_bps: u32,
+        exempt_governance: bool,
+    ) -> Result<(), FundingError> {
+        fees::set_protocol_fee(env, admin, treasury, fee_bps, exempt_governance)
+    }
+
+    /// Per-asset fee override in basis points; `None` falls back to the default
+    pub fn set_asset_fee_override(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        fee_bps: Option<u32>,
+    ) -> Result<(), FundingError> {
+        fees::set_asset_fee_override(env, admin, asset_id, fee_bps)
+    }
+
+    pub fn get_protocol_fee(env: Env) -> Option<ProtocolFeeConfig> {
+        fees::get_protocol_fee(env)
+    }
+
+    /// Effective fee for an asset's distributions
+    pub fn asset_fee_bps(env: Env, asset_id: u64) -> u32 {
+        fees::asset_fee_bps(env, asset_id)
+    }
+
+    /// Total protocol fees taken from an asset's distributions
+    pub fn total_fees(env: Env, asset_id: u64) -> u128 {
+        fees::total_fees(env, asset_id)
+    }
+
+    /// Debits and credits booked to each ledger account of an asset, for a trial balance
+    pub fn get_ledger_totals(env: Env, asset_id: u64) -> Vec<AccountTotals> {
+        ledger::get_ledger_totals(env, asset_id)
+    }
+
+    pub fn get_fnft_contract_address(env: Env) -> Result<Address, FundingError> {
         queries::get_fnft_contract_address(env)
     }
 
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, FundingError> {
         admin::get_admin(env)
     }
 
     /// Check if an address can distribute funds for an asset
-    pub fn can_distribute(env: Env, caller: Address, asset_id: u64) -> bool {
+    pub fn can_distribute(env: Env, caller: Address, asset_id: u64) -> Result<bool, FundingError> {
         queries::can_distribute(env, caller, asset_id)
     }
 
-    pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
-        admin::transfer_admin(env, current_admin, new_admin);
+    /// Fund XLM compensation for consolidation remainders at `rate_per_unit` (asset manager or admin)
+    pub fn fund_remainder_compensation(
+        env: Env,
+        issuer: Address,
+        asset_id: u64,
+        rate_per_unit: i128,
+    ) -> Result<i128, FundingError> {
+        compensation::fund_remainder_compensation(env, issuer, asset_id, rate_per_unit)
+    }
+
+    /// Claim XLM for the caller's consolidation remainder
+    pub fn claim_remainder_compensation(
+        env: Env,
+        holder: Address,
+        asset_id: u64,
+    ) -> Result<i128, FundingError> {
+        compensation::claim_remainder_compensation(env, holder, asset_id)
+    }
+
+    /// Claim a holder's remainder compensation through a relayer that pays the
+    /// transaction fee; the holder signs the call and `relayer_fee` comes out of the payout
+    pub fn relay_claim_compensation(
+        env: Env,
+        relayer: Address,
+        holder: Address,
+        asset_id: u64,
+        relayer_fee: i128,
+    ) -> Result<i128, FundingError> {
+        compensation::relay_claim_compensation(env, relayer, holder, asset_id, relayer_fee)
+    }
+
+    pub fn compensation_rate(env: Env, asset_id: u64) -> Option<i128> {
+        compensation::compensation_rate(env, asset_id)
+    }
+
+    pub fn compensation_pool(env: Env, asset_id: u64) -> i128 {
+        compensation::compensation_pool(env, asset_id)
+    }
+
+    /// Pause or unpause an asset's distributions and claims (admin or governance)
+    pub fn set_asset_paused(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        paused: bool,
+    ) -> Result<(), FundingError> {
+        invariants::set_asset_paused(env, caller, asset_id, paused)
+    }
+
+    /// Whether the asset is paused, e.g. after a failed invariant check
+    pub fn is_asset_paused(env: Env, asset_id: u64) -> bool {
+        invariants::is_asset_paused(env, asset_id)
+    }
+
+    /// Fractcore's emergency pause, during which distributions fail with
+    /// `UpstreamPaused`; `None` when fractcore is running
+    pub fn get_upstream_pause(env: Env) -> Result<Option<UpstreamPause>, FundingError> {
+        invariants::get_upstream_pause(env)
+    }
+
+    /// One page of the admin action log, oldest first; pages older than the
+    /// retention window come back empty and are covered by the head's hash
+    pub fn get_admin_log(env: Env, page: u32) -> Vec<AdminLogEntry> {
+        admin_log::get_admin_log(env, page)
+    }
+
+    pub fn get_admin_log_head(env: Env) -> AdminLogHead {
+        admin_log::get_admin_log_head(env)
+    }
+
+    /// Set an asset's petty cash threshold and rolling monthly cap (governance poll only)
+    pub fn set_petty_cash(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        threshold: u128,
+        monthly_cap: u128,
+    ) -> Result<(), FundingError> {
+        petty_cash::set_petty_cash(env, caller, asset_id, threshold, monthly_cap)
+    }
+
+    /// Asset manager distributes to holders without a poll, within the petty cash limits
+    pub fn petty_cash_distribute(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        amount: u128,
+        description: String,
+    ) -> Result<DistributionOutcome, FundingError> {
+        petty_cash::petty_cash_distribute(env, caller, asset_id, amount, description)
+    }
+
+    /// Asset manager pays a recipient from the SAC without a poll, within the petty cash limits
+    pub fn petty_cash_spend(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        recipient: Address,
+        amount: u128,
+        memo: String,
+    ) -> Result<(), FundingError> {
+        petty_cash::petty_cash_spend(env, caller, asset_id, recipient, amount, memo)
+    }
+
+    pub fn get_petty_cash(env: Env, asset_id: u64) -> Option<PettyCashConfig> {
+        petty_cash::get_petty_cash(env, asset_id)
+    }
+
+    /// Amount the asset manager can still spend in the current 30-day window
+    pub fn petty_cash_available(env: Env, asset_id: u64) -> u128 {
+        petty_cash::petty_cash_available(env, asset_id)
+    }
+
+    pub fn get_petty_spend_count(env: Env, asset_id: u64) -> u32 {
+        petty_cash::get_petty_spend_count(env, asset_id)
+    }
+
+    /// A logged petty cash spend (0-based index)
+    pub fn get_petty_spend(env: Env, asset_id: u64, index: u32) -> Option<PettySpend> {
+        petty_cash::get_petty_spend(env, asset_id, index)
+    }
+
+    /// Deposit into the asset's SAC for one purpose; kept out of distributions until spent
+    pub fn deposit_earmarked(
+        env: Env,
+        depositor: Address,
+        asset_id: u64,
+        amount: i128,
+        purpose: Symbol,
+        condition: ReleaseCondition,
+    ) -> Result<u32, FundingError> {
+        earmarks::deposit_earmarked(env, depositor, asset_id, amount, purpose, condition)
+    }
+
+    /// Release an earmark (asset manager or governance, depending on its condition)
+    pub fn release_earmark(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        earmark_id: u32,
+    ) -> Result<(), FundingError> {
+        earmarks::release_earmark(env, caller, asset_id, earmark_id)
+    }
+
+    /// Asset manager pays a recipient out of a released earmark
+    pub fn spend_earmark(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        earmark_id: u32,
+        recipient: Address,
+        amount: u128,
+        memo: String,
+    ) -> Result<(), FundingError> {
+        earmarks::spend_earmark(env, caller, asset_id, earmark_id, recipient, amount, memo)
+    }
+
+    pub fn get_earmark(env: Env, asset_id: u64, earmark_id: u32) -> Option<Earmark> {
+        earmarks::get_earmark(env, asset_id, earmark_id)
+    }
+
+    pub fn get_earmark_count(env: Env, asset_id: u64) -> u32 {
+        earmarks::get_earmark_count(env, asset_id)
+    }
+
+    /// SAC balance reserved for unspent earmarks
+    pub fn earmarked_total(env: Env, asset_id: u64) -> i128 {
+        earmarks::earmarked_total(env, asset_id)
+    }
+
+    /// Deposited and spent earmarked amounts for one purpose
+    pub fn get_purpose_totals(env: Env, asset_id: u64, purpose: Symbol) -> PurposeTotals {
+        earmarks::get_purpose_totals(env, asset_id, purpose)
+    }
+
+    /// Tip an asset into its SAC; returns the receipt id and ranks the tipper
+    /// among the asset's supporters
+    pub fn tip(
+        env: Env,
+        tipper: Address,
+        asset_id: u64,
+        amount: i128,
+        message: String,
+    ) -> Result<u32, FundingError> {
+        tips::tip(env, tipper, asset_id, amount, message)
+    }
+
+    pub fn get_tip_receipt(env: Env, asset_id: u64, receipt_id: u32) -> Option<TipReceipt> {
+        tips::get_tip_receipt(env, asset_id, receipt_id)
+    }
+
+    pub fn get_tip_count(env: Env, asset_id: u64) -> u32 {
+        tips::get_tip_count(env, asset_id)
+    }
+
+    pub fn get_supporter(env: Env, asset_id: u64, supporter: Address) -> Option<SupporterTotals> {
+        tips::get_supporter(env, asset_id, supporter)
+    }
+
+    /// Up to 10 supporters with the largest cumulative tips, largest first
+    pub fn top_supporters(env: Env, asset_id: u64) -> Vec<SupporterTotals> {
+        tips::top_supporters(env, asset_id)
+    }
+
+    /// Admin announces a withdrawal from an asset's SAC; holders have
+    /// EMERGENCY_WITHDRAW_DELAY to veto it through a governance poll
+    pub fn announce_emergency_withdraw(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        amount: u128,
+        reason: String,
+    ) -> Result<u32, FundingError> {
+        emergency::announce_emergency_withdraw(env, admin, asset_id, amount, reason)
+    }
+
+    /// Block a pending emergency withdrawal (governance contract only)
+    pub fn veto_emergency_withdraw(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        withdrawal_id: u32,
+    ) -> Result<(), FundingError> {
+        emergency::veto_emergency_withdraw(env, caller, asset_id, withdrawal_id)
+    }
+
+    /// Admin executes an announced withdrawal after the timelock, unless vetoed
+    pub fn emergency_withdraw(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        withdrawal_id: u32,
+    ) -> Result<(), FundingError> {
+        emergency::emergency_withdraw(env, admin, asset_id, withdrawal_id)
+    }
+
+    pub fn get_emergency_withdrawal(
+        env: Env,
+        asset_id: u64,
+        withdrawal_id: u32,
+    ) -> Option<EmergencyWithdrawal> {
+        emergency::get_emergency_withdrawal(env, asset_id, withdrawal_id)
+    }
+
+    pub fn get_emergency_withdrawal_count(env: Env, asset_id: u64) -> u32 {
+        emergency::get_emergency_withdrawal_count(env, asset_id)
+    }
+
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), FundingError> {
+        admin::transfer_admin(env, current_admin, new_admin)
     }
 }
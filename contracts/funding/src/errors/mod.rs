@@ -0,0 +1,42 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by funding entrypoints
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FundingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    AssetNotFound = 4,
+    InvalidAmount = 5,
+    NotAssetOwner = 6,
+    SacNotRegistered = 7,
+    SacAlreadyRegistered = 8,
+    InvalidSacAddress = 9,
+    NoSupply = 10,
+    NoOwners = 11,
+    InsufficientSacBalance = 12,
+    TokenTransferFailed = 13,
+    CompensationNotFunded = 14,
+    NoRemainder = 15,
+    InvalidFee = 16,
+    AssetPaused = 17,
+    TooManyExclusions = 18,
+    PettyCashNotEnabled = 19,
+    PettyCashLimitExceeded = 20,
+    EarmarkNotFound = 21,
+    EarmarkNotReleased = 22,
+    WithdrawalNotFound = 23,
+    WithdrawalNotPending = 24,
+    TimelockActive = 25,
+    InvalidTiers = 26,
+    DistributionInProgress = 27,
+    NoDistributionInProgress = 28,
+    DistributionPlanOutdated = 29,
+    DistributionTooLarge = 30,
+    InsufficientFractions = 31,
+    UpstreamPaused = 32,
+    BatchTooLarge = 33,
+    NotGovernanceContract = 34,
+}
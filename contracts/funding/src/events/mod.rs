@@ -1,4 +1,5 @@
-use soroban_sdk::{symbol_short, Address, Env, String};
+use crate::storage::{AdminLogEntry, EventLevel, LedgerEntry, ProtocolFeeConfig};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol};
 
 /// Contract initialization
 pub fn emit_init(env: &Env, admin: Address, fnft_contract: Address) {
@@ -50,6 +51,33 @@ pub fn emit_admin_transfer(env: &Env, old_admin: Address, new_admin: Address) {
         .publish((symbol_short!("admin"), old_admin, new_admin), ());
 }
 
+/// Code replaced through governance (old hash None = code deployed at creation)
+pub fn emit_upgraded(env: &Env, old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: BytesN<32>) {
+    env.events()
+        .publish((symbol_short!("upgraded"),), (old_wasm_hash, new_wasm_hash));
+}
+
+/// Emergency withdrawal announced; executable once the timelock ends
+pub fn emit_emergency_announced(
+    env: &Env,
+    asset_id: u64,
+    withdrawal_id: u32,
+    amount: u128,
+    executable_at: u64,
+    reason: String,
+) {
+    env.events().publish(
+        (symbol_short!("ew_annc"), asset_id),
+        (withdrawal_id, amount, executable_at, reason),
+    );
+}
+
+/// Emergency withdrawal vetoed by a holder poll
+pub fn emit_emergency_vetoed(env: &Env, asset_id: u64, withdrawal_id: u32) {
+    env.events()
+        .publish((symbol_short!("ew_veto"), asset_id), withdrawal_id);
+}
+
 /// Emergency withdrawal event (from SAC)
 pub fn emit_emergency(env: &Env, asset_id: u64, admin: Address, amount: u128, reason: String) {
     env.events().publish(
@@ -57,3 +85,200 @@ pub fn emit_emergency(env: &Env, asset_id: u64, admin: Address, amount: u128, re
         (),
     );
 }
+
+/// Consolidation remainder compensation funded
+pub fn emit_compensation_funded(
+    env: &Env,
+    asset_id: u64,
+    issuer: Address,
+    rate_per_unit: i128,
+    deposited: i128,
+) {
+    env.events().publish(
+        (symbol_short!("comp_fund"), asset_id, issuer),
+        (rate_per_unit, deposited),
+    );
+}
+
+/// Relayer paid its fee out of a claim it submitted for `holder`
+pub fn emit_relayer_paid(env: &Env, asset_id: u64, relayer: Address, holder: Address, fee: i128) {
+    env.events().publish(
+        (symbol_short!("relay_fee"), asset_id, relayer),
+        (holder, fee),
+    );
+}
+
+/// Consolidation remainder paid out
+pub fn emit_compensation_claimed(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    units: u64,
+    payout: i128,
+) {
+    env.events().publish(
+        (symbol_short!("comp_paid"), asset_id, holder),
+        (units, payout),
+    );
+}
+
+/// Protocol fee configuration change
+pub fn emit_fee_config(env: &Env, config: ProtocolFeeConfig) {
+    env.events().publish((symbol_short!("fee_cfg"),), config);
+}
+
+/// Protocol fee taken from a distribution
+pub fn emit_fee(env: &Env, asset_id: u64, treasury: Address, fee: u128) {
+    env.events()
+        .publish((symbol_short!("fee"), asset_id, treasury), fee);
+}
+
+/// Asset paused or unpaused by admin or governance
+pub fn emit_asset_paused(env: &Env, asset_id: u64, paused: bool) {
+    env.events()
+        .publish((symbol_short!("paused"), asset_id), paused);
+}
+
+/// SAC balance disagreed with the books; the asset has been paused
+pub fn emit_invariant_violation(
+    env: &Env,
+    asset_id: u64,
+    expected_balance: i128,
+    actual_balance: i128,
+    reserved: i128,
+) {
+    env.events().publish(
+        (symbol_short!("inv_alert"), asset_id),
+        (expected_balance, actual_balance, reserved),
+    );
+}
+
+/// Petty cash limits set by governance (zero threshold = disabled)
+pub fn emit_petty_cash_config(env: &Env, asset_id: u64, threshold: u128, monthly_cap: u128) {
+    env.events().publish(
+        (symbol_short!("petty_cfg"), asset_id),
+        (threshold, monthly_cap),
+    );
+}
+
+/// Asset manager spent without a poll; `recipient` is None for a distribution
+pub fn emit_petty_spend(
+    env: &Env,
+    asset_id: u64,
+    spender: Address,
+    recipient: Option<Address>,
+    amount: u128,
+    index: u32,
+) {
+    env.events().publish(
+        (symbol_short!("petty"), asset_id, spender),
+        (recipient, amount, index),
+    );
+}
+
+/// Sponsor deposit reserved for a purpose
+pub fn emit_earmark_deposit(
+    env: &Env,
+    asset_id: u64,
+    depositor: Address,
+    earmark_id: u32,
+    purpose: Symbol,
+    amount: i128,
+) {
+    env.events().publish(
+        (symbol_short!("earmark"), asset_id, depositor),
+        (earmark_id, purpose, amount),
+    );
+}
+
+/// Earmark released by the asset manager or a governance poll
+pub fn emit_earmark_released(env: &Env, asset_id: u64, earmark_id: u32) {
+    env.events()
+        .publish((symbol_short!("em_rel"), asset_id), earmark_id);
+}
+
+/// Asset manager spent earmarked funds against their purpose
+pub fn emit_earmark_spend(
+    env: &Env,
+    asset_id: u64,
+    earmark_id: u32,
+    recipient: Address,
+    amount: u128,
+    memo: String,
+) {
+    env.events().publish(
+        (symbol_short!("em_spend"), asset_id, recipient),
+        (earmark_id, amount, memo),
+    );
+}
+
+/// Standardized bookkeeping entry for a movement of the asset's SAC balance
+pub fn emit_ledger_entry(env: &Env, asset_id: u64, entry: LedgerEntry) {
+    env.events()
+        .publish((symbol_short!("ledger"), asset_id, entry.account), entry);
+}
+
+/// Distribution event level changed by the admin
+pub fn emit_event_level(env: &Env, level: EventLevel) {
+    env.events().publish((symbol_short!("evt_level"),), level);
+}
+
+/// Price oracle for converted totals set or cleared by the admin
+pub fn emit_price_oracle(env: &Env, oracle: Option<Address>) {
+    env.events().publish((symbol_short!("oracle"),), oracle);
+}
+
+/// Tip into the asset's SAC, acknowledged by a receipt
+pub fn emit_tip(
+    env: &Env,
+    asset_id: u64,
+    tipper: Address,
+    receipt_id: u32,
+    amount: i128,
+    message_hash: BytesN<32>,
+) {
+    env.events().publish(
+        (symbol_short!("tip"), asset_id, tipper),
+        (receipt_id, amount, message_hash),
+    );
+}
+
+/// Owner pages per distribution batch set by the admin
+pub fn emit_batch_pages(env: &Env, pages: u32) {
+    env.events().publish((symbol_short!("batch_cfg"),), pages);
+}
+
+/// Batched distribution started along a plan of `batches` batches
+pub fn emit_batched_distribution(
+    env: &Env,
+    asset_id: u64,
+    distribution_id: u32,
+    amount: u128,
+    batches: u32,
+) {
+    env.events().publish(
+        (symbol_short!("dist_plan"), asset_id),
+        (distribution_id, amount, batches),
+    );
+}
+
+/// One batch of a batched distribution paid
+pub fn emit_distribution_batch(
+    env: &Env,
+    asset_id: u64,
+    distribution_id: u32,
+    batch_index: u32,
+    paid: u128,
+    recipients: u32,
+) {
+    env.events().publish(
+        (symbol_short!("dist_bat"), asset_id),
+        (distribution_id, batch_index, paid, recipients),
+    );
+}
+
+/// Entry `index` of the admin action log, so archivers can keep entries after they are folded
+pub fn emit_admin_log(env: &Env, index: u32, entry: AdminLogEntry) {
+    env.events()
+        .publish((symbol_short!("admin_log"), index), entry);
+}
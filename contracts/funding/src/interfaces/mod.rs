@@ -1,3 +1,4 @@
+use crate::storage::{Cursor, OracleAsset, PriceData, UpstreamPause};
 use soroban_sdk::{contractclient, Address, Env, Vec};
 
 // Import FNFT contract interface for cross-contract calls
@@ -5,10 +6,35 @@ use soroban_sdk::{contractclient, Address, Env, Vec};
 pub trait FNFTInterface {
     fn asset_exists(env: Env, asset_id: u64) -> bool;
     fn asset_supply(env: Env, asset_id: u64) -> u64;
-    fn asset_owners(env: Env, asset_id: u64) -> Vec<Address>;
+    fn get_asset_owners_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<Address>, Option<Cursor>);
     fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64;
     fn get_admin(env: Env) -> Address;
     fn owns_asset(env: Env, owner: Address, asset_id: u64) -> bool;
+    fn asset_manager(env: Env, asset_id: u64) -> Address;
+    fn time_weighted_balance(env: Env, owner: Address, asset_id: u64, since: u64) -> u64;
+    fn balance_at(env: Env, owner: Address, asset_id: u64, timestamp: u64) -> Option<u64>;
+    fn fraction_remainder_total(env: Env, asset_id: u64) -> u64;
+    fn claim_fraction_remainder(env: Env, owner: Address, asset_id: u64) -> u64;
+    fn batch_transfer_to(
+        env: Env,
+        operator: Address,
+        from: Address,
+        asset_id: u64,
+        recipients: Vec<Address>,
+        amounts: Vec<u64>,
+    );
+    fn pause_state(env: Env) -> Option<UpstreamPause>;
+}
+
+// Governance contract proposed for upgrades, asked which fractcore it governs
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn get_fractcore_contract(env: Env) -> Address;
 }
 
 // Stellar Asset Contract interface for XLM transfers
@@ -17,3 +43,10 @@ pub trait TokenInterface {
     fn transfer(env: Env, from: Address, to: Address, amount: i128);
     fn balance(env: Env, id: Address) -> i128;
 }
+
+// Price oracle following Reflector's SEP-40 interface
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData>;
+}
@@ -1,6 +1,7 @@
 #![no_std]
 
 pub mod contract;
+pub mod errors;
 pub mod events;
 pub mod interfaces;
 pub mod methods;
@@ -10,3 +11,4 @@ pub mod storage;
 pub mod tests;
 
 pub use contract::FundingContract;
+pub use errors::FundingError;
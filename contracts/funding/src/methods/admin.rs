@@ -1,61 +1,162 @@
+use crate::errors::FundingError;
 use crate::events;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::interfaces::GovernanceClient;
+use crate::methods::{admin_log, utils};
+use crate::storage::{self, DataKey, EventLevel, PlacedKey};
+use soroban_sdk::{Address, BytesN, Env, Val, Vec};
 
-pub fn get_admin(env: Env) -> Address {
-    env.storage().instance().get(&DataKey::Admin).unwrap()
+pub fn get_admin(env: Env) -> Result<Address, FundingError> {
+    storage::get(&env, &DataKey::Admin).ok_or(FundingError::NotInitialized)
 }
 
 /// Transfer admin role (only current admin)
-pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
-    current_admin.require_auth();
+pub fn transfer_admin(
+    env: Env,
+    current_admin: Address,
+    new_admin: Address,
+) -> Result<(), FundingError> {
+    require_admin_auth(env.clone(), current_admin.clone())?;
+    admin_log::record(&env, &current_admin, "transfer_admin", (new_admin.clone(),));
 
-    let admin = get_admin(env.clone());
-    if current_admin != admin {
-        panic!("Only current admin can transfer admin role");
-    }
-
-    env.storage().instance().set(&DataKey::Admin, &new_admin);
+    storage::set(&env, &DataKey::Admin, &new_admin);
 
     events::emit_admin_transfer(&env, current_admin, new_admin);
+
+    Ok(())
 }
 
-pub fn require_admin_auth(env: Env, caller: Address) {
-    let admin = get_admin(env);
+/// Authorize `caller` and check it is the admin; the signer is the checked address
+pub fn require_admin_auth(env: Env, caller: Address) -> Result<(), FundingError> {
+    caller.require_auth();
+
+    let admin = get_admin(env)?;
     if caller != admin {
-        panic!("Only admin can perform this action");
+        return Err(FundingError::Unauthorized);
     }
+    Ok(())
 }
 
-pub fn set_governance_contract(env: Env, admin: Address, governance_contract: Address) {
+/// Name the governance contract. The admin makes the first hand-over; after
+/// that only the current governance contract can name its successor. The
+/// target must answer `get_fractcore_contract` with the fractcore linked here,
+/// so the admin cannot swap in an address of its own to upgrade the code.
+pub fn set_governance_contract(
+    env: Env,
+    caller: Address,
+    governance_contract: Address,
+) -> Result<(), FundingError> {
+    caller.require_auth();
+
+    let authority = match get_governance_contract(env.clone()) {
+        Some(current) => current,
+        None => get_admin(env.clone())?,
+    };
+    if caller != authority {
+        return Err(FundingError::Unauthorized);
+    }
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    let governed = GovernanceClient::new(&env, &governance_contract).try_get_fractcore_contract();
+    if !matches!(governed, Ok(Ok(fractcore)) if fractcore == fnft_contract) {
+        return Err(FundingError::NotGovernanceContract);
+    }
+    admin_log::record(
+        &env,
+        &caller,
+        "set_governance_contract",
+        (governance_contract.clone(),),
+    );
+
+    storage::set(&env, &DataKey::GovernanceContract, &governance_contract);
+
+    Ok(())
+}
+
+/// Replace this contract's code with an uploaded WASM (governance contract only)
+pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), FundingError> {
+    caller.require_auth();
+    if get_governance_contract(env.clone()) != Some(caller.clone()) {
+        return Err(FundingError::Unauthorized);
+    }
+    admin_log::record(&env, &caller, "upgrade", (new_wasm_hash.clone(),));
+
+    let old_wasm_hash: Option<BytesN<32>> = storage::get(&env, &DataKey::WasmHash);
+    storage::set(&env, &DataKey::WasmHash, &new_wasm_hash);
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    events::emit_upgraded(&env, old_wasm_hash, new_wasm_hash);
+
+    Ok(())
+}
+
+/// Move entries an upgraded deployment wrote before namespacing from their
+/// bare keys under their namespace (admin only). The admin lists the keys,
+/// over as many calls as the budget needs, and sets `last` on the final call
+/// to mark the contract namespaced, after which this entrypoint is closed.
+/// Until its own entry has moved, the admin is read from its bare key.
+/// A call takes at most MAX_MIGRATE_BATCH keys. Returns the number of entries moved.
+pub fn migrate_storage(
+    env: Env,
+    admin: Address,
+    keys: Vec<Val>,
+    last: bool,
+) -> Result<u32, FundingError> {
     admin.require_auth();
+    let stored_admin: Address = storage::get(&env, &DataKey::Admin)
+        .or_else(|| storage::get_bare(&env, &DataKey::Admin))
+        .ok_or(FundingError::NotInitialized)?;
+    if admin != stored_admin {
+        return Err(FundingError::Unauthorized);
+    }
+    if storage::is_namespaced(&env) {
+        return Err(FundingError::AlreadyInitialized);
+    }
+    if keys.len() > storage::MAX_MIGRATE_BATCH {
+        return Err(FundingError::BatchTooLarge);
+    }
 
-    let current_admin = get_admin(env.clone());
-    if admin != current_admin {
-        panic!("Only admin can set governance contract");
+    let mut moved = 0;
+    for key in keys.iter() {
+        moved += storage::migrate(&env, DataKey::NAMESPACE, key) as u32;
+    }
+    if last {
+        // Logged by the final batch alone: until then the log's own entries may
+        // still sit under their bare keys, and moving them would drop the entry
+        admin_log::record(&env, &admin, "migrate_storage", (keys.len(),));
+        storage::mark_namespaced(&env);
     }
+    Ok(moved)
+}
 
-    env.storage()
-        .instance()
-        .set(&DataKey::GovernanceContract, &governance_contract);
+/// Set how many events distributions publish (admin only)
+pub fn set_event_level(env: Env, admin: Address, level: EventLevel) -> Result<(), FundingError> {
+    require_admin_auth(env.clone(), admin.clone())?;
+    admin_log::record(&env, &admin, "set_event_level", (level,));
+
+    storage::set(&env, &DataKey::EventLevel, &level);
+
+    events::emit_event_level(&env, level);
+
+    Ok(())
+}
+
+pub fn event_level(env: &Env) -> EventLevel {
+    storage::get(env, &DataKey::EventLevel).unwrap_or(EventLevel::Full)
 }
 
 pub fn get_governance_contract(env: Env) -> Option<Address> {
-    env.storage().instance().get(&DataKey::GovernanceContract)
+    storage::get(&env, &DataKey::GovernanceContract)
 }
 
-/// Check if caller is authorized (admin or governance contract)
-pub fn require_authorized_auth(env: Env, caller: Address) {
-    let admin = get_admin(env.clone());
+/// Authorize `caller` and check it is the admin or the governance contract
+pub fn require_authorized_auth(env: Env, caller: Address) -> Result<(), FundingError> {
+    caller.require_auth();
+
+    let admin = get_admin(env.clone())?;
     let governance_contract = get_governance_contract(env);
 
-    if caller != admin {
-        if let Some(gov_contract) = governance_contract {
-            if caller != gov_contract {
-                panic!("Only admin or governance contract can perform this action");
-            }
-        } else {
-            panic!("Only admin can perform this action");
-        }
+    if caller != admin && governance_contract != Some(caller) {
+        return Err(FundingError::Unauthorized);
     }
+    Ok(())
 }
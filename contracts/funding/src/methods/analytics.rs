@@ -0,0 +1,125 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{PriceOracleClient, TokenClient};
+use crate::methods::{admin, admin_log, queries, utils};
+use crate::storage::{self, ConvertedTotal, ConvertedTotals, DataKey, OracleAsset, MAX_PAGE_SIZE};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Admin sets (or clears with `None`) the oracle pricing SAC tokens
+pub fn set_price_oracle(
+    env: Env,
+    admin: Address,
+    oracle: Option<Address>,
+) -> Result<(), FundingError> {
+    admin::require_admin_auth(env.clone(), admin.clone())?;
+    admin_log::record(&env, &admin, "set_price_oracle", (oracle.clone(),));
+
+    match &oracle {
+        Some(contract) => storage::set(&env, &DataKey::PriceOracle, contract),
+        None => storage::remove(&env, &DataKey::PriceOracle),
+    }
+
+    events::emit_price_oracle(&env, oracle);
+
+    Ok(())
+}
+
+pub fn get_price_oracle(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKey::PriceOracle)
+}
+
+/// Distributed and held totals of up to MAX_PAGE_SIZE assets, each in its SAC
+/// token and converted into the oracle's reference currency.
+///
+/// Every line carries the rate and oracle timestamp it was converted at, so a
+/// dashboard can show how current an "≈ total" is. Tokens the oracle has no
+/// positive price for are listed unconverted and left out of the sums. Rates
+/// are not rejected for age: this is a reporting view, not a settlement.
+pub fn get_converted_totals(
+    env: Env,
+    asset_ids: Vec<u64>,
+) -> Result<ConvertedTotals, FundingError> {
+    let oracle = get_price_oracle(env.clone()).ok_or(FundingError::NotInitialized)?;
+    if asset_ids.len() > MAX_PAGE_SIZE {
+        return Err(FundingError::BatchTooLarge);
+    }
+
+    let oracle_client = PriceOracleClient::new(&env, &oracle);
+    let rate_decimals = match oracle_client.try_decimals() {
+        Ok(Ok(decimals)) => Some(decimals),
+        _ => None,
+    };
+    let scale = rate_decimals.and_then(|decimals| 10u128.checked_pow(decimals));
+
+    let mut summary = ConvertedTotals {
+        totals: Vec::new(&env),
+        distributed_value: 0,
+        held_value: 0,
+        rate_decimals: rate_decimals.unwrap_or(0),
+        oldest_rate_at: None,
+        unpriced: 0,
+    };
+
+    for asset_id in asset_ids.iter() {
+        let token = utils::get_asset_sac(&env, asset_id)?;
+        let distributed = queries::total_distributed(env.clone(), asset_id);
+        let held = TokenClient::new(&env, &token).balance(&token).max(0) as u128;
+
+        let price = match (
+            scale,
+            oracle_client.try_lastprice(&OracleAsset::Stellar(token.clone())),
+        ) {
+            (Some(scale), Ok(Ok(Some(data)))) if data.price > 0 => Some((data, scale)),
+            _ => None,
+        };
+
+        let mut line = ConvertedTotal {
+            asset_id,
+            token,
+            distributed,
+            held,
+            rate: None,
+            rate_timestamp: None,
+            distributed_value: None,
+            held_value: None,
+        };
+
+        match price {
+            Some((data, scale)) => {
+                let distributed_value = convert(distributed, data.price, scale)?;
+                let held_value = convert(held, data.price, scale)?;
+                summary.distributed_value = summary
+                    .distributed_value
+                    .checked_add(distributed_value)
+                    .ok_or(FundingError::InvalidAmount)?;
+                summary.held_value = summary
+                    .held_value
+                    .checked_add(held_value)
+                    .ok_or(FundingError::InvalidAmount)?;
+                summary.oldest_rate_at = Some(
+                    summary
+                        .oldest_rate_at
+                        .map_or(data.timestamp, |oldest| oldest.min(data.timestamp)),
+                );
+
+                line.rate = Some(data.price);
+                line.rate_timestamp = Some(data.timestamp);
+                line.distributed_value = Some(distributed_value);
+                line.held_value = Some(held_value);
+            }
+            None => summary.unpriced += 1,
+        }
+
+        summary.totals.push_back(line);
+    }
+
+    Ok(summary)
+}
+
+/// `amount × rate / scale`, rounded down
+fn convert(amount: u128, rate: i128, scale: u128) -> Result<u128, FundingError> {
+    amount
+        .checked_mul(rate as u128)
+        .map(|value| value / scale)
+        .ok_or(FundingError::InvalidAmount)
+}
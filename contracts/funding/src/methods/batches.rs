@@ -0,0 +1,348 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::{admin, admin_log, distribution, invariants, queries, utils};
+use crate::storage::{
+    self, BatchedDistribution, Cursor, DataKey, DistributionBatch, DistributionMode,
+    DistributionOutcome, DistributionPlan, DistributionRecord, DEFAULT_BATCH_PAGES,
+    MAX_BATCH_PAGES, MAX_DISTRIBUTION_BATCHES, OWNER_PAGE_SIZE,
+};
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Set how many owner pages one batch of a batched distribution pays (admin only)
+pub fn set_batch_pages(env: Env, admin: Address, pages: u32) -> Result<(), FundingError> {
+    admin::require_admin_auth(env.clone(), admin.clone())?;
+    admin_log::record(&env, &admin, "set_batch_pages", (pages,));
+
+    if pages == 0 || pages > MAX_BATCH_PAGES {
+        return Err(FundingError::InvalidAmount);
+    }
+    storage::set(&env, &DataKey::BatchPages, &pages);
+
+    events::emit_batch_pages(&env, pages);
+
+    Ok(())
+}
+
+pub fn get_batch_pages(env: Env) -> u32 {
+    storage::get(&env, &DataKey::BatchPages).unwrap_or(DEFAULT_BATCH_PAGES)
+}
+
+/// Split a distribution of `amount` into batches of the configured number of
+/// owner pages.
+///
+/// Only the owner lists are read, not balances, so planning stays cheap next
+/// to paying. A plan with one batch fits in a single transaction.
+pub fn plan_distribution(
+    env: Env,
+    asset_id: u64,
+    amount: u128,
+) -> Result<DistributionPlan, FundingError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+    if fnft_client.asset_supply(&asset_id) == 0 {
+        return Err(FundingError::NoSupply);
+    }
+    if amount > i128::MAX as u128 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let batch_pages = get_batch_pages(env.clone());
+    let mut batches: Vec<DistributionBatch> = Vec::new(&env);
+    let mut recipients = 0u32;
+    let mut page_index = 0u32;
+    let mut cursor = None;
+
+    loop {
+        let (owners, next) =
+            fnft_client.get_asset_owners_page(&asset_id, &cursor, &OWNER_PAGE_SIZE);
+        if owners.is_empty() {
+            break;
+        }
+
+        if page_index.is_multiple_of(batch_pages) {
+            if batches.len() == MAX_DISTRIBUTION_BATCHES {
+                return Err(FundingError::DistributionTooLarge);
+            }
+            batches.push_back(DistributionBatch {
+                first_page: page_index,
+                pages: 0,
+                owners: 0,
+                start: cursor.clone().unwrap_or(Cursor {
+                    page: 0,
+                    position: 0,
+                    snapshot: 0,
+                }),
+            });
+        }
+        let last = batches.len() - 1;
+        let mut batch = batches.get(last).unwrap();
+        batch.pages += 1;
+        batch.owners += owners.len();
+        batches.set(last, batch);
+
+        recipients += owners.len();
+        page_index += 1;
+        if next.is_none() {
+            break;
+        }
+        cursor = next;
+    }
+
+    if recipients == 0 {
+        return Err(FundingError::NoOwners);
+    }
+
+    Ok(DistributionPlan {
+        asset_id,
+        amount,
+        recipients,
+        batch_pages,
+        batches,
+    })
+}
+
+/// Start paying out `amount` along a plan from `plan_distribution` (admin/governance only).
+///
+/// The plan must still match the owner list, otherwise it is outdated and has
+/// to be made again. The fee is taken now and the rest stays reserved in the
+/// SAC until the batches have paid it. Returns the distribution id.
+pub fn start_batched_distribution(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    amount: u128,
+    description: String,
+    plan: DistributionPlan,
+) -> Result<u32, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "start_batched_distribution",
+        (asset_id, amount, description.clone(), plan.clone()),
+    );
+
+    invariants::require_not_paused(&env, asset_id)?;
+    invariants::require_upstream_active(&env)?;
+    require_none_in_progress(&env, asset_id)?;
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+
+    if plan != plan_distribution(env.clone(), asset_id, amount)? {
+        return Err(FundingError::DistributionPlanOutdated);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let total_supply = fnft_client.asset_supply(&asset_id);
+
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+    if (amount as i128) > balance_before - invariants::reserved_balance(&env, asset_id) {
+        return Err(FundingError::InsufficientSacBalance);
+    }
+
+    let is_governance = utils::get_governance_contract(&env) == Some(caller);
+    let distribution_id = queries::get_distribution_count(env.clone(), asset_id);
+    let fee = distribution::take_fee(
+        &env,
+        asset_id,
+        &sac_address,
+        amount,
+        is_governance,
+        Some(distribution_id),
+    )?;
+
+    let batch_count = plan.batches.len();
+    storage::set(
+        &env,
+        &DataKey::BatchedDistribution(asset_id),
+        &BatchedDistribution {
+            distribution_id,
+            requested: amount,
+            fee,
+            total_supply,
+            description,
+            batches: plan.batches,
+            next_batch: 0,
+            distributed: 0,
+            recipients: 0,
+            started_at: env.ledger().timestamp(),
+        },
+    );
+
+    events::emit_batched_distribution(&env, asset_id, distribution_id, amount, batch_count);
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, fee as i128);
+
+    Ok(distribution_id)
+}
+
+/// Pay the next batch of an asset's batched distribution.
+///
+/// Anyone may run it: who gets what is fixed by the plan and the holders'
+/// balances at the close of the ledger the distribution started in, read from
+/// fractcore's balance history, so fractions that change hands between batches
+/// are paid once. A holder whose retained history no longer reaches back to the
+/// start is paid nothing and the share is left as dust. Batches run in plan
+/// order, so `batch_index` must be the next one. Shares are fractions of the
+/// supply at the start, never more than is left unpaid. If the owner pages no longer line up with the plan the batch is
+/// refused, and the admin or governance cancels the distribution. Returns the
+/// outcome once the last batch has run.
+pub fn run_distribution_batch(
+    env: Env,
+    asset_id: u64,
+    batch_index: u32,
+) -> Result<Option<DistributionOutcome>, FundingError> {
+    let mut state = get_batched_distribution(env.clone(), asset_id)
+        .ok_or(FundingError::NoDistributionInProgress)?;
+    invariants::require_not_paused(&env, asset_id)?;
+    invariants::require_upstream_active(&env)?;
+
+    // A stale index comes from an outdated view of the distribution
+    if batch_index != state.next_batch {
+        return Err(FundingError::DistributionPlanOutdated);
+    }
+    let batch = state.batches.get(batch_index).unwrap();
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+
+    let net_amount = state.requested - state.fee;
+    let mut paid = 0u128;
+    let mut recipients = 0u32;
+    let mut cursor = Some(batch.start);
+
+    for _ in 0..batch.pages {
+        let (owners, next) =
+            fnft_client.get_asset_owners_page(&asset_id, &cursor, &OWNER_PAGE_SIZE);
+        for owner in owners.iter() {
+            let balance = fnft_client
+                .balance_at(&owner, &asset_id, &state.started_at)
+                .unwrap_or(0);
+            let unpaid = net_amount - state.distributed - paid;
+            let share = (net_amount * balance as u128 / state.total_supply as u128).min(unpaid);
+            if share == 0 {
+                continue;
+            }
+
+            distribution::pay_holder(
+                &env,
+                asset_id,
+                &sac_address,
+                state.distribution_id,
+                &owner,
+                share,
+            )?;
+            paid += share;
+            recipients += 1;
+        }
+
+        cursor = next;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let expected = state
+        .batches
+        .get(batch_index + 1)
+        .map(|next_batch| next_batch.start);
+    if cursor != expected {
+        return Err(FundingError::DistributionPlanOutdated);
+    }
+
+    state.distributed += paid;
+    state.recipients += recipients;
+    state.next_batch += 1;
+
+    events::emit_distribution_batch(
+        &env,
+        asset_id,
+        state.distribution_id,
+        batch_index,
+        paid,
+        recipients,
+    );
+
+    let outcome = if state.next_batch == state.batches.len() {
+        storage::remove(&env, &DataKey::BatchedDistribution(asset_id));
+        Some(finish(&env, asset_id, state))
+    } else {
+        storage::set(&env, &DataKey::BatchedDistribution(asset_id), &state);
+        None
+    };
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, paid as i128);
+
+    Ok(outcome)
+}
+
+/// Stop a batched distribution after the batches run so far (admin/governance only).
+///
+/// It is recorded with what was paid; the unpaid rest is booked as dust and can
+/// be distributed again.
+pub fn cancel_batched_distribution(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+) -> Result<DistributionOutcome, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(&env, &caller, "cancel_batched_distribution", (asset_id,));
+
+    let state = get_batched_distribution(env.clone(), asset_id)
+        .ok_or(FundingError::NoDistributionInProgress)?;
+    storage::remove(&env, &DataKey::BatchedDistribution(asset_id));
+
+    Ok(finish(&env, asset_id, state))
+}
+
+pub fn get_batched_distribution(env: Env, asset_id: u64) -> Option<BatchedDistribution> {
+    storage::get(&env, &DataKey::BatchedDistribution(asset_id))
+}
+
+/// Other distributions wait until a batched one has finished, so the batches
+/// keep the distribution index they were started with
+pub fn require_none_in_progress(env: &Env, asset_id: u64) -> Result<(), FundingError> {
+    if storage::has(env, &DataKey::BatchedDistribution(asset_id)) {
+        return Err(FundingError::DistributionInProgress);
+    }
+    Ok(())
+}
+
+/// Net amount of the batched distribution in progress that is not yet paid
+pub fn unpaid(env: &Env, asset_id: u64) -> i128 {
+    match get_batched_distribution(env.clone(), asset_id) {
+        Some(state) => (state.requested - state.fee - state.distributed) as i128,
+        None => 0,
+    }
+}
+
+fn finish(env: &Env, asset_id: u64, state: BatchedDistribution) -> DistributionOutcome {
+    let dust = distribution::book_distribution(
+        env,
+        asset_id,
+        state.distribution_id,
+        DistributionRecord {
+            requested: state.requested,
+            fee: state.fee,
+            distributed: state.distributed,
+            recipients: state.recipients,
+            timestamp: env.ledger().timestamp(),
+            mode: DistributionMode::Snapshot,
+            excluded: Vec::new(env),
+        },
+        state.description,
+    );
+
+    DistributionOutcome {
+        distribution_id: state.distribution_id,
+        requested: state.requested,
+        fee: state.fee,
+        distributed: state.distributed,
+        recipients: state.recipients,
+        dust,
+    }
+}
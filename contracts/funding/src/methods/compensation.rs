@@ -0,0 +1,179 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::{admin, invariants, ledger, utils};
+use crate::storage::{self, DataKey, EntryDirection, LedgerAccount};
+use soroban_sdk::{Address, Env};
+
+/// Fund XLM compensation for the remainders left by a fractcore consolidation.
+///
+/// `rate_per_unit` is paid per pre-consolidation unit. The issuer (asset manager)
+/// or admin tops the pool up to cover every unclaimed remainder at that rate;
+/// the pool is held in the asset's SAC but kept out of distributions.
+/// Returns the amount deposited.
+pub fn fund_remainder_compensation(
+    env: Env,
+    issuer: Address,
+    asset_id: u64,
+    rate_per_unit: i128,
+) -> Result<i128, FundingError> {
+    issuer.require_auth();
+
+    if rate_per_unit <= 0 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    let fnft_client = FNFTClient::new(&env, &fnft_contract);
+
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+
+    let admin = admin::get_admin(env.clone())?;
+    if issuer != admin && issuer != fnft_client.asset_manager(&asset_id) {
+        return Err(FundingError::Unauthorized);
+    }
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+
+    let outstanding = fnft_client.fraction_remainder_total(&asset_id) as i128;
+    let required = outstanding
+        .checked_mul(rate_per_unit)
+        .ok_or(FundingError::InvalidAmount)?;
+    let pool = compensation_pool(env.clone(), asset_id);
+    let deposit = (required - pool).max(0);
+
+    if deposit > 0 {
+        utils::token_transfer(&env, &sac_address, &issuer, &sac_address, deposit)?;
+        storage::set(
+            &env,
+            &DataKey::CompensationPool(asset_id),
+            &(pool + deposit),
+        );
+        ledger::post(
+            &env,
+            asset_id,
+            LedgerAccount::Compensation,
+            EntryDirection::Debit,
+            deposit as u128,
+            Some(issuer.clone()),
+            None,
+        );
+    }
+    storage::set(&env, &DataKey::CompensationRate(asset_id), &rate_per_unit);
+
+    events::emit_compensation_funded(&env, asset_id, issuer, rate_per_unit, deposit);
+
+    Ok(deposit)
+}
+
+/// Pay a holder for their unclaimed consolidation remainder; returns the payout
+pub fn claim_remainder_compensation(
+    env: Env,
+    holder: Address,
+    asset_id: u64,
+) -> Result<i128, FundingError> {
+    holder.require_auth();
+
+    pay_remainder(env, &holder, asset_id, None)
+}
+
+/// Claim submitted by a relayer on the holder's behalf; returns the holder's share.
+///
+/// The holder signs this invocation, relayer and fee included, so the relayer
+/// cannot change either. The fee is taken out of the payout.
+pub fn relay_claim_compensation(
+    env: Env,
+    relayer: Address,
+    holder: Address,
+    asset_id: u64,
+    relayer_fee: i128,
+) -> Result<i128, FundingError> {
+    holder.require_auth();
+
+    if relayer_fee < 0 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    pay_remainder(env, &holder, asset_id, Some((relayer, relayer_fee)))
+}
+
+fn pay_remainder(
+    env: Env,
+    holder: &Address,
+    asset_id: u64,
+    relay: Option<(Address, i128)>,
+) -> Result<i128, FundingError> {
+    invariants::require_not_paused(&env, asset_id)?;
+
+    let rate =
+        compensation_rate(env.clone(), asset_id).ok_or(FundingError::CompensationNotFunded)?;
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    let fnft_client = FNFTClient::new(&env, &fnft_contract);
+
+    let units = fnft_client.claim_fraction_remainder(holder, &asset_id);
+    if units == 0 {
+        return Err(FundingError::NoRemainder);
+    }
+
+    // Remainders recorded after the last top-up are not covered yet
+    let payout = (units as i128) * rate;
+    let pool = compensation_pool(env.clone(), asset_id);
+    if payout > pool {
+        return Err(FundingError::CompensationNotFunded);
+    }
+
+    let relayer_fee = relay.as_ref().map(|(_, fee)| *fee).unwrap_or(0);
+    if relayer_fee > payout {
+        return Err(FundingError::InvalidAmount);
+    }
+    let holder_share = payout - relayer_fee;
+
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+    storage::set(&env, &DataKey::CompensationPool(asset_id), &(pool - payout));
+    if holder_share > 0 {
+        utils::token_transfer(&env, &sac_address, &sac_address, holder, holder_share)?;
+        ledger::post(
+            &env,
+            asset_id,
+            LedgerAccount::Compensation,
+            EntryDirection::Credit,
+            holder_share as u128,
+            Some(holder.clone()),
+            None,
+        );
+    }
+    if let Some((relayer, fee)) = relay {
+        if fee > 0 {
+            utils::token_transfer(&env, &sac_address, &sac_address, &relayer, fee)?;
+            ledger::post(
+                &env,
+                asset_id,
+                LedgerAccount::Compensation,
+                EntryDirection::Credit,
+                fee as u128,
+                Some(relayer.clone()),
+                None,
+            );
+        }
+        events::emit_relayer_paid(&env, asset_id, relayer, holder.clone(), fee);
+    }
+
+    events::emit_compensation_claimed(&env, asset_id, holder.clone(), units, payout);
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, payout);
+
+    Ok(holder_share)
+}
+
+pub fn compensation_rate(env: Env, asset_id: u64) -> Option<i128> {
+    storage::get(&env, &DataKey::CompensationRate(asset_id))
+}
+
+/// SAC balance reserved for unclaimed remainder compensation
+pub fn compensation_pool(env: Env, asset_id: u64) -> i128 {
+    storage::get(&env, &DataKey::CompensationPool(asset_id)).unwrap_or(0)
+}
@@ -1,8 +1,15 @@
+use crate::errors::FundingError;
 use crate::events;
 use crate::interfaces::{FNFTClient, TokenClient};
-use crate::methods::{admin, queries, utils};
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env, String};
+use crate::methods::{
+    admin, admin_log, batches, fees, invariants, ledger, queries, strategy, utils,
+};
+use crate::storage::{
+    self, DataKey, DistributionMode, DistributionOutcome, DistributionPreview, DistributionRecord,
+    EntryDirection, EventLevel, HolderPayout, LedgerAccount, Payout, MAX_EXCLUDED_HOLDERS,
+    PREVIEW_TOP_HOLDERS,
+};
+use soroban_sdk::{Address, Env, String, Vec};
 
 /// Distribute funds from asset's SAC to asset owners (admin/governance only)
 pub fn distribute_funds(
@@ -11,23 +18,95 @@ pub fn distribute_funds(
     asset_id: u64,
     amount: u128,
     description: String,
-) {
-    let admin = admin::get_admin(env.clone());
-    let governance_contract = utils::get_governance_contract(&env);
-
-    let is_admin = caller == admin;
-    let is_governance = if let Some(gov) = governance_contract {
-        caller == gov
-    } else {
-        false
-    };
-    if !is_admin && !is_governance {
-        panic!("Only admin or governance can distribute funds");
-    }
+) -> Result<DistributionOutcome, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "distribute_funds",
+        (asset_id, amount, description.clone()),
+    );
 
-    caller.require_auth();
+    let is_governance = utils::get_governance_contract(&env) == Some(caller);
+    let excluded = Vec::new(&env);
+    execute_sac_distribution(
+        env,
+        asset_id,
+        amount,
+        description,
+        is_governance,
+        DistributionMode::Snapshot,
+        excluded,
+    )
+}
 
-    execute_sac_distribution(env, asset_id, amount, description);
+/// Admin/governance distribution with an explicit weighting strategy
+pub fn distribute_funds_with_mode(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    amount: u128,
+    description: String,
+    mode: DistributionMode,
+) -> Result<DistributionOutcome, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "distribute_funds_with_mode",
+        (asset_id, amount, description.clone(), mode.clone()),
+    );
+
+    strategy::validate(&mode)?;
+
+    let is_governance = utils::get_governance_contract(&env) == Some(caller);
+    let excluded = Vec::new(&env);
+    execute_sac_distribution(
+        env,
+        asset_id,
+        amount,
+        description,
+        is_governance,
+        mode,
+        excluded,
+    )
+}
+
+/// Admin/governance distribution that leaves out some holders, e.g. the issuer's
+/// treasury or team wallets.
+///
+/// Excluded balances are removed from the supply the shares are divided by, so the
+/// remaining holders split the full amount. The exclusions are kept in the record.
+pub fn distribute_funds_excluding(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    amount: u128,
+    description: String,
+    excluded: Vec<Address>,
+) -> Result<DistributionOutcome, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "distribute_funds_excluding",
+        (asset_id, amount, description.clone(), excluded.clone()),
+    );
+
+    if excluded.len() > MAX_EXCLUDED_HOLDERS {
+        return Err(FundingError::TooManyExclusions);
+    }
+
+    let is_governance = utils::get_governance_contract(&env) == Some(caller);
+    execute_sac_distribution(
+        env,
+        asset_id,
+        amount,
+        description,
+        is_governance,
+        DistributionMode::Snapshot,
+        excluded,
+    )
 }
 
 /// Allow asset owners to distribute funds (democratic distribution)
@@ -37,88 +116,433 @@ pub fn owner_distribute_funds(
     asset_id: u64,
     amount: u128,
     description: String,
-) {
+) -> Result<DistributionOutcome, FundingError> {
     caller.require_auth();
 
-    let fnft_contract = utils::get_fnft_contract(&env);
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
 
     if !fnft_client.owns_asset(&caller, &asset_id) {
-        panic!("Caller does not own tokens of this asset");
+        return Err(FundingError::NotAssetOwner);
     }
 
-    execute_sac_distribution(env, asset_id, amount, description);
+    let excluded = Vec::new(&env);
+    execute_sac_distribution(
+        env,
+        asset_id,
+        amount,
+        description,
+        false,
+        DistributionMode::Snapshot,
+        excluded,
+    )
 }
 
 /// Internal distribution logic - pulls from SAC and distributes to asset owners
-fn execute_sac_distribution(env: Env, asset_id: u64, amount: u128, description: String) {
-    let sac_address: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetSAC(asset_id))
-        .expect("Asset must have a registered SAC");
-
-    let fnft_contract = utils::get_fnft_contract(&env);
+pub(crate) fn execute_sac_distribution(
+    env: Env,
+    asset_id: u64,
+    amount: u128,
+    description: String,
+    by_governance: bool,
+    mode: DistributionMode,
+    excluded: Vec<Address>,
+) -> Result<DistributionOutcome, FundingError> {
+    invariants::require_not_paused(&env, asset_id)?;
+    invariants::require_upstream_active(&env)?;
+    batches::require_none_in_progress(&env, asset_id)?;
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
 
     if !fnft_client.asset_exists(&asset_id) {
-        panic!("Asset does not exist");
+        return Err(FundingError::AssetNotFound);
     }
 
     let total_supply = fnft_client.asset_supply(&asset_id);
     if total_supply == 0 {
-        panic!("Asset has no supply");
+        return Err(FundingError::NoSupply);
     }
 
-    let owners = fnft_client.asset_owners(&asset_id);
-    if owners.len() == 0 {
-        panic!("No asset owners found");
+    let owners = utils::asset_owners(&env, &fnft_client, asset_id)?;
+    if owners.is_empty() {
+        return Err(FundingError::NoOwners);
+    }
+
+    if amount > i128::MAX as u128 {
+        return Err(FundingError::InvalidAmount);
     }
 
     let sac_client = TokenClient::new(&env, &sac_address);
-    let sac_balance = sac_client.balance(&sac_address);
+    // Remainder compensation and earmarks share the SAC but are not distributable
+    let balance_before = sac_client.balance(&sac_address);
+    let sac_balance = balance_before - invariants::reserved_balance(&env, asset_id);
 
     if (amount as i128) > sac_balance {
-        panic!("Insufficient balance in asset SAC");
+        return Err(FundingError::InsufficientSacBalance);
     }
 
+    let distribution_count = queries::get_distribution_count(env.clone(), asset_id);
+
+    // The protocol fee comes off the top before holders are paid
+    let fee = take_fee(
+        &env,
+        asset_id,
+        &sac_address,
+        amount,
+        by_governance,
+        Some(distribution_count),
+    )?;
+    let net_amount = amount - fee;
+
+    let (mut weights, mut total_weight) =
+        strategy::holder_weights(&env, &fnft_client, asset_id, &owners, total_supply, &mode)?;
+
+    // Excluded holders get nothing and their weight no longer dilutes the others
+    for (index, owner) in owners.iter().enumerate() {
+        if excluded.contains(&owner) {
+            total_weight -= weights.get(index as u32).unwrap();
+            weights.set(index as u32, 0);
+        }
+    }
+    if total_weight == 0 {
+        return Err(FundingError::NoSupply);
+    }
+
+    let shares = strategy::shares(&env, net_amount, &weights, total_weight);
+
     let mut total_distributed = 0u128;
     let mut recipients_count = 0u32;
 
-    for owner in owners {
-        let balance = fnft_client.balance_of(&owner, &asset_id);
+    for (owner, owner_share) in owners.iter().zip(shares.iter()) {
+        if owner_share > 0 {
+            pay_holder(
+                &env,
+                asset_id,
+                &sac_address,
+                distribution_count,
+                &owner,
+                owner_share,
+            )?;
+            total_distributed += owner_share;
+            recipients_count += 1;
+        }
+    }
 
-        if balance > 0 {
-            let owner_share = (amount * balance as u128) / total_supply as u128;
+    let dust = book_distribution(
+        &env,
+        asset_id,
+        distribution_count,
+        DistributionRecord {
+            requested: amount,
+            fee,
+            distributed: total_distributed,
+            recipients: recipients_count,
+            timestamp: env.ledger().timestamp(),
+            mode,
+            excluded,
+        },
+        description,
+    );
+
+    invariants::check_sac_invariants(
+        &env,
+        asset_id,
+        &sac_address,
+        balance_before,
+        (fee + total_distributed) as i128,
+    );
 
-            if owner_share > 0 {
-                sac_client.transfer(&sac_address, &owner, &(owner_share as i128));
+    Ok(DistributionOutcome {
+        distribution_id: distribution_count,
+        requested: amount,
+        fee,
+        distributed: total_distributed,
+        recipients: recipients_count,
+        dust,
+    })
+}
 
-                total_distributed += owner_share;
-                recipients_count += 1;
+/// Send the protocol fee on `amount` to the treasury, if one applies; returns the fee
+pub(crate) fn take_fee(
+    env: &Env,
+    asset_id: u64,
+    sac_address: &Address,
+    amount: u128,
+    by_governance: bool,
+    reference: Option<u32>,
+) -> Result<u128, FundingError> {
+    let Some((treasury, fee)) = fees::fee_for(env, asset_id, amount, by_governance) else {
+        return Ok(0);
+    };
 
-                events::emit_received(&env, asset_id, owner, owner_share);
-            }
-        }
+    utils::token_transfer(env, sac_address, sac_address, &treasury, fee as i128)?;
+    ledger::post(
+        env,
+        asset_id,
+        LedgerAccount::Fees,
+        EntryDirection::Credit,
+        fee,
+        Some(treasury.clone()),
+        reference,
+    );
+    if admin::event_level(env) != EventLevel::Minimal {
+        events::emit_fee(env, asset_id, treasury, fee);
+    }
+    Ok(fee)
+}
+
+/// Pay one holder their share, recording it for statements and, at the Full
+/// event level, in the ledger
+pub(crate) fn pay_holder(
+    env: &Env,
+    asset_id: u64,
+    sac_address: &Address,
+    distribution_id: u32,
+    owner: &Address,
+    share: u128,
+) -> Result<(), FundingError> {
+    utils::token_transfer(env, sac_address, sac_address, owner, share as i128)?;
+    record_holder_payout(env, owner, asset_id, distribution_id, share);
+
+    if admin::event_level(env) == EventLevel::Full {
+        ledger::emit(
+            env,
+            asset_id,
+            LedgerAccount::Distributions,
+            EntryDirection::Credit,
+            share,
+            Some(owner.clone()),
+            Some(distribution_id),
+        );
+        events::emit_received(env, asset_id, owner.clone(), share);
     }
+    Ok(())
+}
+
+/// Book a paid-out distribution: ledger entries, dust, totals and its record.
+/// Returns the dust left in the SAC.
+pub(crate) fn book_distribution(
+    env: &Env,
+    asset_id: u64,
+    distribution_id: u32,
+    record: DistributionRecord,
+    description: String,
+) -> u128 {
+    let reference = Some(distribution_id);
+    let total_distributed = record.distributed;
+
+    // Below Full the payouts are booked as one entry without a counterparty
+    if admin::event_level(env) != EventLevel::Full && total_distributed > 0 {
+        ledger::emit(
+            env,
+            asset_id,
+            LedgerAccount::Distributions,
+            EntryDirection::Credit,
+            total_distributed,
+            None,
+            reference,
+        );
+    }
+    ledger::add_to_totals(
+        env,
+        asset_id,
+        LedgerAccount::Distributions,
+        EntryDirection::Credit,
+        total_distributed,
+    );
+
+    // Dust is allocated to the distribution but stays in the SAC
+    let dust = record.requested - record.fee - total_distributed;
+    ledger::post(
+        env,
+        asset_id,
+        LedgerAccount::Distributions,
+        EntryDirection::Credit,
+        dust,
+        None,
+        reference,
+    );
+    ledger::post(
+        env,
+        asset_id,
+        LedgerAccount::Dust,
+        EntryDirection::Debit,
+        dust,
+        None,
+        reference,
+    );
 
     let current_distributed = queries::total_distributed(env.clone(), asset_id);
-    env.storage().persistent().set(
+    storage::set(
+        env,
         &DataKey::TotalDistributed(asset_id),
         &(current_distributed + total_distributed),
     );
 
-    let distribution_count = queries::get_distribution_count(env.clone(), asset_id);
-    env.storage().persistent().set(
+    if record.fee > 0 {
+        let fees_taken = fees::total_fees(env.clone(), asset_id);
+        storage::set(
+            env,
+            &DataKey::TotalFees(asset_id),
+            &(fees_taken + record.fee),
+        );
+    }
+
+    store_record(env, asset_id, distribution_id, record, description);
+
+    dust
+}
+
+/// Keep a distribution's record under the next distribution index and announce it
+pub(crate) fn store_record(
+    env: &Env,
+    asset_id: u64,
+    distribution_id: u32,
+    record: DistributionRecord,
+    description: String,
+) {
+    let (distributed, recipients) = (record.distributed, record.recipients);
+    storage::set(
+        env,
+        &DataKey::Distribution(asset_id, distribution_id),
+        &record,
+    );
+    storage::set(
+        env,
         &DataKey::DistributionCount(asset_id),
-        &(distribution_count + 1),
+        &(distribution_id + 1),
+    );
+
+    events::emit_distribution(env, asset_id, distributed, description, recipients);
+}
+
+/// Append a payout to the holder's history for account statements
+pub(crate) fn record_holder_payout(
+    env: &Env,
+    holder: &Address,
+    asset_id: u64,
+    distribution_id: u32,
+    amount: u128,
+) {
+    let count = queries::get_holder_payout_count(env.clone(), holder.clone());
+    storage::set(
+        env,
+        &DataKey::HolderPayout(holder.clone(), count),
+        &HolderPayout {
+            asset_id,
+            distribution_id,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    storage::set(
+        env,
+        &DataKey::HolderPayoutCount(holder.clone()),
+        &(count + 1),
     );
+}
+
+/// Dry run of a distribution of `amount`, using the same fee and share math as execution.
+///
+/// The fee is computed as for a governance-executed distribution, since previews
+/// feed poll descriptions. `min_payout` only affects reporting: recipients whose
+/// share is below it are counted in `below_min_payout` (they are still paid).
+pub fn preview_distribution(
+    env: Env,
+    asset_id: u64,
+    amount: u128,
+    min_payout: u128,
+) -> Result<DistributionPreview, FundingError> {
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    let fnft_client = FNFTClient::new(&env, &fnft_contract);
+
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+
+    let total_supply = fnft_client.asset_supply(&asset_id);
+    if total_supply == 0 {
+        return Err(FundingError::NoSupply);
+    }
+
+    let owners = utils::asset_owners(&env, &fnft_client, asset_id)?;
+    if owners.is_empty() {
+        return Err(FundingError::NoOwners);
+    }
+
+    if amount > i128::MAX as u128 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let funded = match queries::get_asset_sac(env.clone(), asset_id) {
+        Some(sac_address) => {
+            let available = TokenClient::new(&env, &sac_address).balance(&sac_address)
+                - invariants::reserved_balance(&env, asset_id);
+            (amount as i128) <= available
+        }
+        None => false,
+    };
+
+    let fee = fees::fee_for(&env, asset_id, amount, true).map_or(0, |(_, fee)| fee);
+    let net_amount = amount - fee;
 
-    events::emit_distribution(
+    let mut preview = DistributionPreview {
+        recipients: 0,
+        below_min_payout: 0,
+        fee,
+        distributed: 0,
+        dust: 0,
+        funded,
+        top_payouts: Vec::new(&env),
+    };
+
+    let (weights, total_weight) = strategy::holder_weights(
         &env,
+        &fnft_client,
         asset_id,
-        total_distributed,
-        description,
-        recipients_count,
-    );
+        &owners,
+        total_supply,
+        &DistributionMode::Snapshot,
+    )?;
+    let shares = strategy::shares(&env, net_amount, &weights, total_weight);
+
+    for (owner, share) in owners.iter().zip(shares.iter()) {
+        if share == 0 {
+            continue;
+        }
+
+        preview.recipients += 1;
+        preview.distributed += share;
+        if share < min_payout {
+            preview.below_min_payout += 1;
+        }
+        insert_top_payout(
+            &mut preview.top_payouts,
+            Payout {
+                recipient: owner,
+                amount: share,
+            },
+        );
+    }
+
+    preview.dust = net_amount - preview.distributed;
+
+    Ok(preview)
+}
+
+/// Keep `top` sorted by amount (largest first) and at most PREVIEW_TOP_HOLDERS long
+fn insert_top_payout(top: &mut Vec<Payout>, payout: Payout) {
+    let mut index = top.len();
+    while index > 0 && top.get(index - 1).unwrap().amount < payout.amount {
+        index -= 1;
+    }
+
+    if index < PREVIEW_TOP_HOLDERS {
+        top.insert(index, payout);
+        if top.len() > PREVIEW_TOP_HOLDERS {
+            top.pop_back();
+        }
+    }
 }
@@ -0,0 +1,208 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::{invariants, ledger, utils};
+use crate::storage::{
+    self, DataKey, Earmark, EntryDirection, LedgerAccount, PurposeTotals, ReleaseCondition,
+};
+use soroban_sdk::{Address, Env, String, Symbol};
+
+/// Deposit into the asset's SAC for a single purpose, e.g. a sponsor covering travel.
+///
+/// Earmarked funds stay out of distributions and petty cash until the asset
+/// manager spends them with `spend_earmark`. Unless the condition is `Immediate`,
+/// they must first be released by the asset manager or a governance poll.
+/// Returns the earmark id.
+pub fn deposit_earmarked(
+    env: Env,
+    depositor: Address,
+    asset_id: u64,
+    amount: i128,
+    purpose: Symbol,
+    condition: ReleaseCondition,
+) -> Result<u32, FundingError> {
+    depositor.require_auth();
+
+    if amount <= 0 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    if !FNFTClient::new(&env, &fnft_contract).asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    utils::token_transfer(&env, &sac_address, &depositor, &sac_address, amount)?;
+
+    let earmark_id = get_earmark_count(env.clone(), asset_id);
+    storage::set(
+        &env,
+        &DataKey::Earmark(asset_id, earmark_id),
+        &Earmark {
+            depositor: depositor.clone(),
+            purpose: purpose.clone(),
+            condition,
+            released: condition == ReleaseCondition::Immediate,
+            deposited: amount as u128,
+            spent: 0,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    storage::set(&env, &DataKey::EarmarkCount(asset_id), &(earmark_id + 1));
+
+    set_earmarked_total(
+        &env,
+        asset_id,
+        earmarked_total(env.clone(), asset_id) + amount,
+    );
+
+    let mut totals = get_purpose_totals(env.clone(), asset_id, purpose.clone());
+    totals.deposited += amount as u128;
+    storage::set(
+        &env,
+        &DataKey::EarmarkPurpose(asset_id, purpose.clone()),
+        &totals,
+    );
+
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::Earmarks,
+        EntryDirection::Debit,
+        amount as u128,
+        Some(depositor.clone()),
+        Some(earmark_id),
+    );
+    events::emit_earmark_deposit(&env, asset_id, depositor, earmark_id, purpose, amount);
+
+    Ok(earmark_id)
+}
+
+/// Release an earmark so it can be spent: the asset manager confirms a
+/// `ManagerConfirmation` earmark, the governance contract a `PollApproval` one
+pub fn release_earmark(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    earmark_id: u32,
+) -> Result<(), FundingError> {
+    caller.require_auth();
+
+    let mut earmark = require_earmark(&env, asset_id, earmark_id)?;
+    if earmark.released {
+        return Ok(());
+    }
+
+    let authorized = match earmark.condition {
+        ReleaseCondition::Immediate => true,
+        ReleaseCondition::ManagerConfirmation => caller == asset_manager(&env, asset_id)?,
+        ReleaseCondition::PollApproval => utils::get_governance_contract(&env) == Some(caller),
+    };
+    if !authorized {
+        return Err(FundingError::Unauthorized);
+    }
+
+    earmark.released = true;
+    storage::set(&env, &DataKey::Earmark(asset_id, earmark_id), &earmark);
+
+    events::emit_earmark_released(&env, asset_id, earmark_id);
+
+    Ok(())
+}
+
+/// Asset manager pays `recipient` out of a released earmark, up to what is left of it
+pub fn spend_earmark(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    earmark_id: u32,
+    recipient: Address,
+    amount: u128,
+    memo: String,
+) -> Result<(), FundingError> {
+    caller.require_auth();
+    invariants::require_not_paused(&env, asset_id)?;
+
+    let mut earmark = require_earmark(&env, asset_id, earmark_id)?;
+    if caller != asset_manager(&env, asset_id)? {
+        return Err(FundingError::Unauthorized);
+    }
+    if !earmark.released {
+        return Err(FundingError::EarmarkNotReleased);
+    }
+    if amount == 0 || amount > earmark.deposited - earmark.spent {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    earmark.spent += amount;
+    storage::set(&env, &DataKey::Earmark(asset_id, earmark_id), &earmark);
+
+    // The spent amount stops being reserved before the invariant check runs
+    set_earmarked_total(
+        &env,
+        asset_id,
+        earmarked_total(env.clone(), asset_id) - amount as i128,
+    );
+
+    let mut totals = get_purpose_totals(env.clone(), asset_id, earmark.purpose.clone());
+    totals.spent += amount;
+    storage::set(
+        &env,
+        &DataKey::EarmarkPurpose(asset_id, earmark.purpose),
+        &totals,
+    );
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+    utils::token_transfer(&env, &sac_address, &sac_address, &recipient, amount as i128)?;
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, amount as i128);
+
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::Earmarks,
+        EntryDirection::Credit,
+        amount,
+        Some(recipient.clone()),
+        Some(earmark_id),
+    );
+    events::emit_earmark_spend(&env, asset_id, earmark_id, recipient, amount, memo);
+
+    Ok(())
+}
+
+pub fn get_earmark(env: Env, asset_id: u64, earmark_id: u32) -> Option<Earmark> {
+    storage::get(&env, &DataKey::Earmark(asset_id, earmark_id))
+}
+
+pub fn get_earmark_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::EarmarkCount(asset_id)).unwrap_or(0)
+}
+
+/// SAC balance still reserved for earmarks (not distributable)
+pub fn earmarked_total(env: Env, asset_id: u64) -> i128 {
+    storage::get(&env, &DataKey::EarmarkedTotal(asset_id)).unwrap_or(0)
+}
+
+/// Deposited and spent amounts for one purpose of an asset
+pub fn get_purpose_totals(env: Env, asset_id: u64, purpose: Symbol) -> PurposeTotals {
+    storage::get(&env, &DataKey::EarmarkPurpose(asset_id, purpose)).unwrap_or(PurposeTotals {
+        deposited: 0,
+        spent: 0,
+    })
+}
+
+fn require_earmark(env: &Env, asset_id: u64, earmark_id: u32) -> Result<Earmark, FundingError> {
+    get_earmark(env.clone(), asset_id, earmark_id).ok_or(FundingError::EarmarkNotFound)
+}
+
+fn asset_manager(env: &Env, asset_id: u64) -> Result<Address, FundingError> {
+    let fnft_contract = utils::get_fnft_contract(env)?;
+    Ok(FNFTClient::new(env, &fnft_contract).asset_manager(&asset_id))
+}
+
+fn set_earmarked_total(env: &Env, asset_id: u64, total: i128) {
+    storage::set(env, &DataKey::EarmarkedTotal(asset_id), &total);
+}
@@ -0,0 +1,176 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::{admin, admin_log, invariants, ledger, utils};
+use crate::storage::{
+    self, DataKey, EmergencyWithdrawal, EntryDirection, LedgerAccount, WithdrawalStatus,
+    EMERGENCY_WITHDRAW_DELAY,
+};
+use soroban_sdk::{Address, Env, String};
+
+/// Admin announces a withdrawal from an asset's SAC.
+///
+/// Nothing moves until `EMERGENCY_WITHDRAW_DELAY` has passed; until then the
+/// asset's holders can block it through a governance veto poll. Returns the
+/// withdrawal id.
+pub fn announce_emergency_withdraw(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    amount: u128,
+    reason: String,
+) -> Result<u32, FundingError> {
+    admin::require_admin_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "announce_emergency_withdraw",
+        (asset_id, amount, reason.clone()),
+    );
+
+    if amount == 0 || amount > i128::MAX as u128 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    if !FNFTClient::new(&env, &fnft_contract).asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+    utils::get_asset_sac(&env, asset_id)?;
+
+    let announced_at = env.ledger().timestamp();
+    let executable_at = announced_at + EMERGENCY_WITHDRAW_DELAY;
+
+    let withdrawal_id = get_emergency_withdrawal_count(env.clone(), asset_id);
+    storage::set(
+        &env,
+        &DataKey::EmergencyWithdrawal(asset_id, withdrawal_id),
+        &EmergencyWithdrawal {
+            admin: caller,
+            amount,
+            reason: reason.clone(),
+            announced_at,
+            executable_at,
+            status: WithdrawalStatus::Pending,
+        },
+    );
+    storage::set(
+        &env,
+        &DataKey::EmergencyWithdrawalCount(asset_id),
+        &(withdrawal_id + 1),
+    );
+
+    events::emit_emergency_announced(&env, asset_id, withdrawal_id, amount, executable_at, reason);
+
+    Ok(withdrawal_id)
+}
+
+/// Block a pending withdrawal (governance contract only, after a holder poll)
+pub fn veto_emergency_withdraw(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    withdrawal_id: u32,
+) -> Result<(), FundingError> {
+    if utils::get_governance_contract(&env) != Some(caller.clone()) {
+        return Err(FundingError::Unauthorized);
+    }
+    caller.require_auth();
+    admin_log::record(
+        &env,
+        &caller,
+        "veto_emergency_withdraw",
+        (asset_id, withdrawal_id),
+    );
+
+    let mut withdrawal = require_pending(&env, asset_id, withdrawal_id)?;
+    withdrawal.status = WithdrawalStatus::Vetoed;
+    storage::set(
+        &env,
+        &DataKey::EmergencyWithdrawal(asset_id, withdrawal_id),
+        &withdrawal,
+    );
+
+    events::emit_emergency_vetoed(&env, asset_id, withdrawal_id);
+
+    Ok(())
+}
+
+/// Admin executes an announced withdrawal once its timelock has passed without a veto.
+///
+/// Funds reserved for remainder compensation or earmarks are never withdrawn.
+pub fn emergency_withdraw(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    withdrawal_id: u32,
+) -> Result<(), FundingError> {
+    admin::require_admin_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "emergency_withdraw",
+        (asset_id, withdrawal_id),
+    );
+
+    let mut withdrawal = require_pending(&env, asset_id, withdrawal_id)?;
+    if env.ledger().timestamp() < withdrawal.executable_at {
+        return Err(FundingError::TimelockActive);
+    }
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+    let amount = withdrawal.amount as i128;
+    if amount > balance_before - invariants::reserved_balance(&env, asset_id) {
+        return Err(FundingError::InsufficientSacBalance);
+    }
+
+    withdrawal.status = WithdrawalStatus::Executed;
+    storage::set(
+        &env,
+        &DataKey::EmergencyWithdrawal(asset_id, withdrawal_id),
+        &withdrawal,
+    );
+
+    utils::token_transfer(&env, &sac_address, &sac_address, &caller, amount)?;
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, amount);
+
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::EmergencyWithdrawals,
+        EntryDirection::Credit,
+        withdrawal.amount,
+        Some(caller.clone()),
+        Some(withdrawal_id),
+    );
+    events::emit_emergency(&env, asset_id, caller, withdrawal.amount, withdrawal.reason);
+
+    Ok(())
+}
+
+pub fn get_emergency_withdrawal(
+    env: Env,
+    asset_id: u64,
+    withdrawal_id: u32,
+) -> Option<EmergencyWithdrawal> {
+    storage::get(&env, &DataKey::EmergencyWithdrawal(asset_id, withdrawal_id))
+}
+
+pub fn get_emergency_withdrawal_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::EmergencyWithdrawalCount(asset_id)).unwrap_or(0)
+}
+
+fn require_pending(
+    env: &Env,
+    asset_id: u64,
+    withdrawal_id: u32,
+) -> Result<EmergencyWithdrawal, FundingError> {
+    let withdrawal = get_emergency_withdrawal(env.clone(), asset_id, withdrawal_id)
+        .ok_or(FundingError::WithdrawalNotFound)?;
+    if withdrawal.status != WithdrawalStatus::Pending {
+        return Err(FundingError::WithdrawalNotPending);
+    }
+    Ok(withdrawal)
+}
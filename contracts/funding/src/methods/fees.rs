@@ -0,0 +1,94 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::methods::{admin, admin_log};
+use crate::storage::{self, DataKey, ProtocolFeeConfig, MAX_FEE_BPS};
+use soroban_sdk::{Address, Env};
+
+/// Configure the protocol fee (admin only); `fee_bps` of 0 disables it
+pub fn set_protocol_fee(
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    fee_bps: u32,
+    exempt_governance: bool,
+) -> Result<(), FundingError> {
+    admin::require_admin_auth(env.clone(), admin.clone())?;
+    admin_log::record(
+        &env,
+        &admin,
+        "set_protocol_fee",
+        (treasury.clone(), fee_bps, exempt_governance),
+    );
+
+    if fee_bps > MAX_FEE_BPS {
+        return Err(FundingError::InvalidFee);
+    }
+
+    let config = ProtocolFeeConfig {
+        treasury,
+        fee_bps,
+        exempt_governance,
+    };
+    storage::set(&env, &DataKey::ProtocolFee, &config);
+
+    events::emit_fee_config(&env, config);
+
+    Ok(())
+}
+
+/// Replace the default fee for one asset, or clear the override with `None`
+pub fn set_asset_fee_override(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    fee_bps: Option<u32>,
+) -> Result<(), FundingError> {
+    admin::require_admin_auth(env.clone(), admin.clone())?;
+    admin_log::record(&env, &admin, "set_asset_fee_override", (asset_id, fee_bps));
+
+    match fee_bps {
+        Some(bps) if bps > MAX_FEE_BPS => return Err(FundingError::InvalidFee),
+        Some(bps) => storage::set(&env, &DataKey::AssetFeeOverride(asset_id), &bps),
+        None => storage::remove(&env, &DataKey::AssetFeeOverride(asset_id)),
+    }
+
+    Ok(())
+}
+
+pub fn get_protocol_fee(env: Env) -> Option<ProtocolFeeConfig> {
+    storage::get(&env, &DataKey::ProtocolFee)
+}
+
+/// Fee in basis points that applies to the asset's distributions
+pub fn asset_fee_bps(env: Env, asset_id: u64) -> u32 {
+    let config = match get_protocol_fee(env.clone()) {
+        Some(config) => config,
+        None => return 0,
+    };
+
+    storage::get(&env, &DataKey::AssetFeeOverride(asset_id)).unwrap_or(config.fee_bps)
+}
+
+/// Treasury and fee owed on a distribution of `amount`; None when no fee applies
+pub fn fee_for(
+    env: &Env,
+    asset_id: u64,
+    amount: u128,
+    by_governance: bool,
+) -> Option<(Address, u128)> {
+    let config = get_protocol_fee(env.clone())?;
+    if by_governance && config.exempt_governance {
+        return None;
+    }
+
+    let fee = amount * asset_fee_bps(env.clone(), asset_id) as u128 / 10_000;
+    if fee == 0 {
+        return None;
+    }
+
+    Some((config.treasury, fee))
+}
+
+pub fn total_fees(env: Env, asset_id: u64) -> u128 {
+    storage::get(&env, &DataKey::TotalFees(asset_id)).unwrap_or(0)
+}
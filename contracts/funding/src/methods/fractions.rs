@@ -0,0 +1,168 @@
+use crate::errors::FundingError;
+use crate::interfaces::FNFTClient;
+use crate::methods::{
+    admin, admin_log, batches, distribution, invariants, queries, strategy, utils,
+};
+use crate::storage::{
+    self, DataKey, DistributionMode, DistributionOutcome, DistributionRecord, FractionReward,
+    FRACTION_TRANSFER_BATCH,
+};
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Distribute fractions instead of XLM (admin/governance only): `reward.amount`
+/// fractions of `reward.asset_id`, split among the holders of `asset_id` under
+/// `mode` and sent from `reward.source`.
+///
+/// The source and this contract are left out of the split. No protocol fee is
+/// taken, and the rounding dust stays with the source. The distribution gets
+/// the next index, a record and per-holder payouts like a monetary one, but it
+/// does not enter the XLM ledger or totals.
+pub fn distribute_fractions(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    reward: FractionReward,
+    description: String,
+    mode: DistributionMode,
+) -> Result<DistributionOutcome, FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(
+        &env,
+        &caller,
+        "distribute_fractions",
+        (asset_id, reward.clone(), description.clone(), mode.clone()),
+    );
+
+    strategy::validate(&mode)?;
+    invariants::require_not_paused(&env, asset_id)?;
+    invariants::require_upstream_active(&env)?;
+    batches::require_none_in_progress(&env, asset_id)?;
+
+    if reward.amount == 0 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+
+    if !fnft_client.asset_exists(&asset_id) || !fnft_client.asset_exists(&reward.asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+
+    let total_supply = fnft_client.asset_supply(&asset_id);
+    if total_supply == 0 {
+        return Err(FundingError::NoSupply);
+    }
+
+    let owners = utils::asset_owners(&env, &fnft_client, asset_id)?;
+    if owners.is_empty() {
+        return Err(FundingError::NoOwners);
+    }
+
+    if fnft_client.balance_of(&reward.source, &reward.asset_id) < reward.amount {
+        return Err(FundingError::InsufficientFractions);
+    }
+
+    let mut excluded = Vec::new(&env);
+    excluded.push_back(reward.source.clone());
+    let this = env.current_contract_address();
+    if this != reward.source {
+        excluded.push_back(this.clone());
+    }
+
+    let (mut weights, mut total_weight) =
+        strategy::holder_weights(&env, &fnft_client, asset_id, &owners, total_supply, &mode)?;
+    for (index, owner) in owners.iter().enumerate() {
+        if excluded.contains(&owner) {
+            total_weight -= weights.get(index as u32).unwrap();
+            weights.set(index as u32, 0);
+        }
+    }
+    if total_weight == 0 {
+        return Err(FundingError::NoSupply);
+    }
+
+    let shares = strategy::shares(&env, reward.amount as u128, &weights, total_weight);
+    let distribution_id = queries::get_distribution_count(env.clone(), asset_id);
+
+    let mut total_distributed = 0u128;
+    let mut recipients_count = 0u32;
+    let mut recipients = Vec::new(&env);
+    let mut amounts = Vec::new(&env);
+
+    for (owner, share) in owners.iter().zip(shares.iter()) {
+        if share == 0 {
+            continue;
+        }
+
+        distribution::record_holder_payout(&env, &owner, asset_id, distribution_id, share);
+        recipients.push_back(owner);
+        amounts.push_back(share as u64);
+        total_distributed += share;
+        recipients_count += 1;
+
+        if recipients.len() == FRACTION_TRANSFER_BATCH {
+            send(&fnft_client, &this, &reward, &recipients, &amounts)?;
+            recipients = Vec::new(&env);
+            amounts = Vec::new(&env);
+        }
+    }
+    if !recipients.is_empty() {
+        send(&fnft_client, &this, &reward, &recipients, &amounts)?;
+    }
+
+    storage::set(
+        &env,
+        &DataKey::RewardAsset(asset_id, distribution_id),
+        &reward.asset_id,
+    );
+    distribution::store_record(
+        &env,
+        asset_id,
+        distribution_id,
+        DistributionRecord {
+            requested: reward.amount as u128,
+            fee: 0,
+            distributed: total_distributed,
+            recipients: recipients_count,
+            timestamp: env.ledger().timestamp(),
+            mode,
+            excluded,
+        },
+        description,
+    );
+
+    Ok(DistributionOutcome {
+        distribution_id,
+        requested: reward.amount as u128,
+        fee: 0,
+        distributed: total_distributed,
+        recipients: recipients_count,
+        dust: reward.amount as u128 - total_distributed,
+    })
+}
+
+/// Fractions asset the asset's `distribution_id`-th distribution paid; `None`
+/// when it paid XLM
+pub fn get_reward_asset(env: Env, asset_id: u64, distribution_id: u32) -> Option<u64> {
+    storage::get(&env, &DataKey::RewardAsset(asset_id, distribution_id))
+}
+
+/// One batched transfer from the reward source, with this contract as operator
+fn send(
+    fnft_client: &FNFTClient,
+    operator: &Address,
+    reward: &FractionReward,
+    recipients: &Vec<Address>,
+    amounts: &Vec<u64>,
+) -> Result<(), FundingError> {
+    match fnft_client.try_batch_transfer_to(
+        operator,
+        &reward.source,
+        &reward.asset_id,
+        recipients,
+        amounts,
+    ) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(FundingError::TokenTransferFailed),
+    }
+}
@@ -1,32 +1,43 @@
+use crate::errors::FundingError;
 use crate::events;
-use crate::interfaces::{FNFTClient, TokenClient};
-use crate::methods::utils;
-use crate::storage::DataKey;
+use crate::interfaces::FNFTClient;
+use crate::methods::{ledger, utils};
+use crate::storage::{EntryDirection, LedgerAccount};
 use soroban_sdk::{Address, Env};
 
 /// Deposit XLM funds to asset's SAC (with tracking)
-pub fn deposit_funds(env: Env, depositor: Address, asset_id: u64, amount: i128) {
+pub fn deposit_funds(
+    env: Env,
+    depositor: Address,
+    asset_id: u64,
+    amount: i128,
+) -> Result<(), FundingError> {
     depositor.require_auth();
 
     if amount <= 0 {
-        panic!("Deposit amount must be > 0");
+        return Err(FundingError::InvalidAmount);
     }
 
-    let fnft_contract = utils::get_fnft_contract(&env);
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
 
     if !fnft_client.asset_exists(&asset_id) {
-        panic!("Asset does not exist");
+        return Err(FundingError::AssetNotFound);
     }
 
-    let sac_address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetSAC(asset_id))
-        .expect("Asset must have a registered SAC to use funding features");
-
-    let sac_client = TokenClient::new(&env, &sac_address);
-    sac_client.transfer(&depositor, &sac_address, &amount);
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    utils::token_transfer(&env, &sac_address, &depositor, &sac_address, amount)?;
 
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::Deposits,
+        EntryDirection::Debit,
+        amount as u128,
+        Some(depositor.clone()),
+        None,
+    );
     events::emit_deposit(&env, asset_id, depositor, amount);
+
+    Ok(())
 }
@@ -1,20 +1,44 @@
+use crate::errors::FundingError;
 use crate::events;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
 
 /// Initialize the funding contract
-pub fn initialize(env: Env, admin: Address, fnft_contract: Address) {
+pub fn initialize(env: Env, admin: Address, fnft_contract: Address) -> Result<(), FundingError> {
     admin.require_auth();
+    configure(env, admin, fnft_contract)
+}
+
+/// Deploy-time initialization. The constructor runs atomically with contract
+/// creation, so no one can initialize first and the admin's auth is not needed.
+pub fn construct(env: Env, admin: Address, fnft_contract: Address) -> Result<(), FundingError> {
+    configure(env, admin, fnft_contract)
+}
 
-    if env.storage().instance().has(&DataKey::Admin) {
-        panic!("Contract already initialized");
+fn configure(env: Env, admin: Address, fnft_contract: Address) -> Result<(), FundingError> {
+    if storage::has_or_bare(&env, &DataKey::Admin) {
+        return Err(FundingError::AlreadyInitialized);
     }
 
+    storage::mark_namespaced(&env);
+
     // Store core addresses
-    env.storage().instance().set(&DataKey::Admin, &admin);
-    env.storage()
-        .instance()
-        .set(&DataKey::FNFTContract, &fnft_contract);
+    storage::set(&env, &DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::FNFTContract, &fnft_contract);
+
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(admin.clone(), fnft_contract.clone()).to_xdr(&env))
+        .into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
 
     events::emit_init(&env, admin, fnft_contract);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
 }
@@ -0,0 +1,102 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::interfaces::TokenClient;
+use crate::methods::{admin, admin_log, batches, compensation, earmarks, utils};
+use crate::storage::{self, DataKey, UpstreamPause};
+use soroban_sdk::{Address, Env};
+
+/// Admin or governance pauses or unpauses an asset's distributions and claims.
+///
+/// Assets are paused automatically when a post-distribution invariant check
+/// fails; unpausing is how an investigated asset is put back into service.
+pub fn set_asset_paused(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    paused: bool,
+) -> Result<(), FundingError> {
+    admin::require_authorized_auth(env.clone(), caller.clone())?;
+    admin_log::record(&env, &caller, "set_asset_paused", (asset_id, paused));
+
+    if paused {
+        storage::set(&env, &DataKey::AssetPaused(asset_id), &true);
+    } else {
+        storage::remove(&env, &DataKey::AssetPaused(asset_id));
+    }
+
+    events::emit_asset_paused(&env, asset_id, paused);
+
+    Ok(())
+}
+
+pub fn is_asset_paused(env: Env, asset_id: u64) -> bool {
+    storage::get(&env, &DataKey::AssetPaused(asset_id)).unwrap_or(false)
+}
+
+pub fn require_not_paused(env: &Env, asset_id: u64) -> Result<(), FundingError> {
+    if is_asset_paused(env.clone(), asset_id) {
+        return Err(FundingError::AssetPaused);
+    }
+    Ok(())
+}
+
+/// Fractcore's emergency pause, if any. Fails closed: a fractcore whose pause
+/// state cannot be read counts as paused and fails with `UpstreamPaused`.
+pub fn get_upstream_pause(env: Env) -> Result<Option<UpstreamPause>, FundingError> {
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    match FNFTClient::new(&env, &fnft_contract).try_pause_state() {
+        Ok(Ok(pause)) => Ok(pause),
+        _ => Err(FundingError::UpstreamPaused),
+    }
+}
+
+/// Distributions weigh holders by fractcore balances, so none starts while
+/// fractcore is paused; `get_upstream_pause` says when to retry
+pub fn require_upstream_active(env: &Env) -> Result<(), FundingError> {
+    if get_upstream_pause(env.clone())?.is_some() {
+        return Err(FundingError::UpstreamPaused);
+    }
+    Ok(())
+}
+
+/// Part of the asset's SAC balance that is not distributable: unclaimed
+/// remainder compensation, unspent earmarked deposits and what a batched
+/// distribution has yet to pay
+pub fn reserved_balance(env: &Env, asset_id: u64) -> i128 {
+    compensation::compensation_pool(env.clone(), asset_id)
+        + earmarks::earmarked_total(env.clone(), asset_id)
+        + batches::unpaid(env, asset_id)
+}
+
+/// Check the asset's SAC after funds left it and pause the asset on a mismatch.
+///
+/// The SAC must have lost exactly `expected_outflow` and must still cover the
+/// reserved balance. A violation does not revert the call, otherwise the pause
+/// would be rolled back with it; the asset stays paused until admin or
+/// governance unpauses it. Returns whether the invariants held.
+pub fn check_sac_invariants(
+    env: &Env,
+    asset_id: u64,
+    sac_address: &Address,
+    balance_before: i128,
+    expected_outflow: i128,
+) -> bool {
+    let balance_after = TokenClient::new(env, sac_address).balance(sac_address);
+    let reserved = reserved_balance(env, asset_id);
+
+    if balance_before - balance_after == expected_outflow && balance_after >= reserved {
+        return true;
+    }
+
+    storage::set(env, &DataKey::AssetPaused(asset_id), &true);
+    events::emit_invariant_violation(
+        env,
+        asset_id,
+        balance_before - expected_outflow,
+        balance_after,
+        reserved,
+    );
+
+    false
+}
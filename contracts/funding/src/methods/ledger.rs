@@ -0,0 +1,92 @@
+use crate::events;
+use crate::storage::{
+    self, AccountTotals, DataKey, EntryDirection, LedgerAccount, LedgerEntry, LEDGER_ACCOUNTS,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Publish a ledger entry and add it to the account's running totals
+pub fn post(
+    env: &Env,
+    asset_id: u64,
+    account: LedgerAccount,
+    direction: EntryDirection,
+    amount: u128,
+    counterparty: Option<Address>,
+    reference: Option<u32>,
+) {
+    if amount == 0 {
+        return;
+    }
+    emit(
+        env,
+        asset_id,
+        account,
+        direction,
+        amount,
+        counterparty,
+        reference,
+    );
+    add_to_totals(env, asset_id, account, direction, amount);
+}
+
+/// Publish a ledger entry whose amount the caller adds to the totals itself, so
+/// per-holder payouts cost one totals write per distribution
+pub fn emit(
+    env: &Env,
+    asset_id: u64,
+    account: LedgerAccount,
+    direction: EntryDirection,
+    amount: u128,
+    counterparty: Option<Address>,
+    reference: Option<u32>,
+) {
+    events::emit_ledger_entry(
+        env,
+        asset_id,
+        LedgerEntry {
+            account,
+            direction,
+            amount,
+            counterparty,
+            reference,
+        },
+    );
+}
+
+pub fn add_to_totals(
+    env: &Env,
+    asset_id: u64,
+    account: LedgerAccount,
+    direction: EntryDirection,
+    amount: u128,
+) {
+    if amount == 0 {
+        return;
+    }
+    let mut totals = account_totals(env, asset_id, account);
+    match direction {
+        EntryDirection::Debit => totals.debits += amount,
+        EntryDirection::Credit => totals.credits += amount,
+    }
+    storage::set(env, &DataKey::LedgerTotals(asset_id, account), &totals);
+}
+
+/// Running totals of every account of an asset.
+///
+/// Debits minus credits over all accounts is the SAC balance the contract
+/// moved: XLM sent straight to the SAC is not booked.
+pub fn get_ledger_totals(env: Env, asset_id: u64) -> Vec<AccountTotals> {
+    let mut totals = Vec::new(&env);
+    for account in LEDGER_ACCOUNTS {
+        totals.push_back(account_totals(&env, asset_id, account));
+    }
+    totals
+}
+
+fn account_totals(env: &Env, asset_id: u64, account: LedgerAccount) -> AccountTotals {
+    storage::get(env, &DataKey::LedgerTotals(asset_id, account)).unwrap_or(AccountTotals {
+        account,
+        debits: 0,
+        credits: 0,
+    })
+}
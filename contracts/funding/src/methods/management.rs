@@ -1,38 +1,42 @@
+use crate::errors::FundingError;
 use crate::events;
 use crate::interfaces::FNFTClient;
 use crate::methods::utils;
-use crate::storage::DataKey;
+use crate::storage::{self, DataKey};
 use soroban_sdk::{Address, Env};
 
 /// Register a SAC address for an asset (any asset owner can register)
-pub fn register_asset_sac(env: Env, caller: Address, asset_id: u64, sac_address: Address) {
+pub fn register_asset_sac(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    sac_address: Address,
+) -> Result<(), FundingError> {
     caller.require_auth();
 
-    let fnft_contract = utils::get_fnft_contract(&env);
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
 
     if !fnft_client.asset_exists(&asset_id) {
-        panic!("Asset does not exist");
+        return Err(FundingError::AssetNotFound);
     }
 
     if !fnft_client.owns_asset(&caller, &asset_id) {
-        panic!("Only asset owners can register SAC");
+        return Err(FundingError::NotAssetOwner);
     }
 
-    if env.storage().persistent().has(&DataKey::AssetSAC(asset_id)) {
-        panic!("Asset already has a registered SAC");
+    if storage::has(&env, &DataKey::AssetSAC(asset_id)) {
+        return Err(FundingError::SacAlreadyRegistered);
     }
 
     if sac_address == env.current_contract_address() {
-        panic!("SAC address cannot be the funding contract itself");
+        return Err(FundingError::InvalidSacAddress);
     }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetSAC(asset_id), &sac_address);
-    env.storage()
-        .persistent()
-        .set(&DataKey::SACToAsset(sac_address.clone()), &asset_id);
+    storage::set(&env, &DataKey::AssetSAC(asset_id), &sac_address);
+    storage::set(&env, &DataKey::SACToAsset(sac_address.clone()), &asset_id);
 
     events::emit_sac_registered(&env, asset_id, sac_address);
+
+    Ok(())
 }
@@ -1,7 +1,20 @@
 pub mod admin;
+pub mod admin_log;
+pub mod analytics;
+pub mod batches;
+pub mod compensation;
 pub mod distribution;
+pub mod earmarks;
+pub mod emergency;
+pub mod fees;
+pub mod fractions;
 pub mod funds;
 pub mod initialization;
+pub mod invariants;
+pub mod ledger;
 pub mod management;
+pub mod petty_cash;
 pub mod queries;
+pub mod strategy;
+pub mod tips;
 pub mod utils;
@@ -0,0 +1,242 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::{admin_log, distribution, invariants, ledger, utils};
+use crate::storage::{
+    self, DataKey, DistributionMode, DistributionOutcome, EntryDirection, LedgerAccount,
+    PettyCashConfig, PettySpend, Placement, MAX_PETTY_SPENDS_PER_WINDOW, PETTY_CASH_WINDOW,
+    PETTY_WINDOW_TTL,
+};
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Set an asset's petty cash limits (governance contract only).
+///
+/// Only an executed poll can change the limits, so the asset manager cannot
+/// raise its own allowance. A zero threshold disables petty cash for the asset.
+pub fn set_petty_cash(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    threshold: u128,
+    monthly_cap: u128,
+) -> Result<(), FundingError> {
+    if utils::get_governance_contract(&env) != Some(caller.clone()) {
+        return Err(FundingError::Unauthorized);
+    }
+    caller.require_auth();
+    admin_log::record(
+        &env,
+        &caller,
+        "set_petty_cash",
+        (asset_id, threshold, monthly_cap),
+    );
+
+    if threshold == 0 {
+        storage::remove(&env, &DataKey::PettyCash(asset_id));
+    } else {
+        if threshold > monthly_cap {
+            return Err(FundingError::InvalidAmount);
+        }
+        storage::set(
+            &env,
+            &DataKey::PettyCash(asset_id),
+            &PettyCashConfig {
+                threshold,
+                monthly_cap,
+            },
+        );
+    }
+
+    events::emit_petty_cash_config(&env, asset_id, threshold, monthly_cap);
+
+    Ok(())
+}
+
+/// Asset manager distributes to holders without a poll, within the petty cash limits
+pub fn petty_cash_distribute(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    amount: u128,
+    description: String,
+) -> Result<DistributionOutcome, FundingError> {
+    require_asset_manager(&env, &caller, asset_id)?;
+
+    record_spend(&env, asset_id, &caller, None, amount, description.clone())?;
+
+    let excluded = Vec::new(&env);
+    distribution::execute_sac_distribution(
+        env,
+        asset_id,
+        amount,
+        description,
+        false,
+        DistributionMode::Snapshot,
+        excluded,
+    )
+}
+
+/// Asset manager pays `recipient` from the asset's SAC without a poll, within the
+/// petty cash limits. Funds reserved for remainder compensation or earmarks
+/// cannot be spent.
+pub fn petty_cash_spend(
+    env: Env,
+    caller: Address,
+    asset_id: u64,
+    recipient: Address,
+    amount: u128,
+    memo: String,
+) -> Result<(), FundingError> {
+    require_asset_manager(&env, &caller, asset_id)?;
+    invariants::require_not_paused(&env, asset_id)?;
+
+    if amount > i128::MAX as u128 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    let balance_before = TokenClient::new(&env, &sac_address).balance(&sac_address);
+    let available = balance_before - invariants::reserved_balance(&env, asset_id);
+    if (amount as i128) > available {
+        return Err(FundingError::InsufficientSacBalance);
+    }
+
+    let index = record_spend(
+        &env,
+        asset_id,
+        &caller,
+        Some(recipient.clone()),
+        amount,
+        memo,
+    )?;
+
+    utils::token_transfer(&env, &sac_address, &sac_address, &recipient, amount as i128)?;
+
+    invariants::check_sac_invariants(&env, asset_id, &sac_address, balance_before, amount as i128);
+
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::PettyCash,
+        EntryDirection::Credit,
+        amount,
+        Some(recipient),
+        Some(index),
+    );
+
+    Ok(())
+}
+
+pub fn get_petty_cash(env: Env, asset_id: u64) -> Option<PettyCashConfig> {
+    storage::get(&env, &DataKey::PettyCash(asset_id))
+}
+
+pub fn get_petty_spend_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::PettySpendCount(asset_id)).unwrap_or(0)
+}
+
+/// A logged petty cash spend (0-based index)
+pub fn get_petty_spend(env: Env, asset_id: u64, index: u32) -> Option<PettySpend> {
+    storage::get(&env, &DataKey::PettySpend(asset_id, index))
+}
+
+/// What the asset manager can still spend in the current window (0 when disabled)
+pub fn petty_cash_available(env: Env, asset_id: u64) -> u128 {
+    match get_petty_cash(env.clone(), asset_id) {
+        Some(config) => {
+            let spent = window_total(&current_window(&env, asset_id));
+            config.monthly_cap.saturating_sub(spent)
+        }
+        None => 0,
+    }
+}
+
+fn require_asset_manager(env: &Env, caller: &Address, asset_id: u64) -> Result<(), FundingError> {
+    caller.require_auth();
+
+    let fnft_contract = utils::get_fnft_contract(env)?;
+    let fnft_client = FNFTClient::new(env, &fnft_contract);
+
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+    if *caller != fnft_client.asset_manager(&asset_id) {
+        return Err(FundingError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Check a spend against the limits, then add it to the window and the log;
+/// returns the spend index
+fn record_spend(
+    env: &Env,
+    asset_id: u64,
+    spender: &Address,
+    recipient: Option<Address>,
+    amount: u128,
+    memo: String,
+) -> Result<u32, FundingError> {
+    if amount == 0 {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let config = get_petty_cash(env.clone(), asset_id).ok_or(FundingError::PettyCashNotEnabled)?;
+
+    let mut window = current_window(env, asset_id);
+    if amount > config.threshold
+        || window_total(&window) + amount > config.monthly_cap
+        || window.len() >= MAX_PETTY_SPENDS_PER_WINDOW
+    {
+        return Err(FundingError::PettyCashLimitExceeded);
+    }
+
+    let timestamp = env.ledger().timestamp();
+    window.push_back((timestamp, amount));
+    let window_key = DataKey::PettyWindow(asset_id);
+    storage::set(env, &window_key, &window);
+    storage::bump(env, &window_key, PETTY_WINDOW_TTL, PETTY_WINDOW_TTL);
+    storage::remove_moved(env, &window_key, Placement::Persistent);
+
+    let index = get_petty_spend_count(env.clone(), asset_id);
+    storage::set(
+        env,
+        &DataKey::PettySpend(asset_id, index),
+        &PettySpend {
+            spender: spender.clone(),
+            recipient: recipient.clone(),
+            amount,
+            memo,
+            timestamp,
+        },
+    );
+    storage::set(env, &DataKey::PettySpendCount(asset_id), &(index + 1));
+
+    events::emit_petty_spend(env, asset_id, spender.clone(), recipient, amount, index);
+
+    Ok(index)
+}
+
+/// Spends still inside the rolling window, oldest first.
+///
+/// The window lives in temporary storage: once it has expired every spend in
+/// it is older than PETTY_CASH_WINDOW. Windows written before it moved there
+/// are read from persistent storage until the next spend.
+fn current_window(env: &Env, asset_id: u64) -> Vec<(u64, u128)> {
+    let key = DataKey::PettyWindow(asset_id);
+    let window: Vec<(u64, u128)> = storage::get(env, &key)
+        .or_else(|| storage::get_moved(env, &key, Placement::Persistent))
+        .unwrap_or(Vec::new(env));
+
+    let now = env.ledger().timestamp();
+    let mut live = Vec::new(env);
+    for (timestamp, amount) in window.iter() {
+        if timestamp + PETTY_CASH_WINDOW > now {
+            live.push_back((timestamp, amount));
+        }
+    }
+    live
+}
+
+fn window_total(window: &Vec<(u64, u128)>) -> u128 {
+    window.iter().map(|(_, amount)| amount).sum()
+}
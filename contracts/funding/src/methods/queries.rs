@@ -1,71 +1,134 @@
+use crate::errors::FundingError;
 use crate::interfaces::{FNFTClient, TokenClient};
 use crate::methods::utils;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::storage::{self, Cursor, DataKey, DistributionRecord, HolderPayout, MAX_PAGE_SIZE};
+use soroban_sdk::{Address, Env, Vec};
 
 /// Get the SAC address for an asset
 pub fn get_asset_sac(env: Env, asset_id: u64) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::AssetSAC(asset_id))
+    storage::get(&env, &DataKey::AssetSAC(asset_id))
 }
 
 /// Get asset ID from SAC address (reverse lookup)
 pub fn get_asset_by_sac(env: Env, sac_address: Address) -> Option<u64> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::SACToAsset(sac_address))
+    storage::get(&env, &DataKey::SACToAsset(sac_address))
 }
 
 /// Get SAC balance for an asset
-pub fn asset_funds(env: Env, asset_id: u64) -> u128 {
-    let sac_address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetSAC(asset_id))
-        .expect("Asset must have a registered SAC");
+pub fn asset_funds(env: Env, asset_id: u64) -> Result<u128, FundingError> {
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
 
     let sac_client = TokenClient::new(&env, &sac_address);
-    sac_client.balance(&sac_address) as u128
+    Ok(sac_client.balance(&sac_address) as u128)
 }
 
 /// Get total amount distributed for an asset (analytics)
 pub fn total_distributed(env: Env, asset_id: u64) -> u128 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::TotalDistributed(asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::TotalDistributed(asset_id)).unwrap_or(0)
+}
+
+/// Accounting record of the asset's `index`-th distribution
+pub fn get_distribution(env: Env, asset_id: u64, index: u32) -> Option<DistributionRecord> {
+    storage::get(&env, &DataKey::Distribution(asset_id, index))
+}
+
+/// Up to `limit` (index, record) pairs of an asset's distributions from
+/// `cursor`, oldest first.
+///
+/// Distributions are only ever appended, so the cursor records the count when
+/// the walk started and distributions executed meanwhile wait for the next walk.
+pub fn get_distributions_page(
+    env: Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<(u32, DistributionRecord)>, Option<Cursor>) {
+    let count = get_distribution_count(env.clone(), asset_id);
+    let (start, end, next) = window(count, cursor, limit);
+
+    let mut records = Vec::new(&env);
+    for index in start..end {
+        if let Some(record) = get_distribution(env.clone(), asset_id, index) {
+            records.push_back((index, record));
+        }
+    }
+    (records, next)
+}
+
+/// Up to `limit` distribution payouts `holder` received, oldest first, from `cursor`
+pub fn get_holder_payouts_page(
+    env: Env,
+    holder: Address,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<HolderPayout>, Option<Cursor>) {
+    let count = get_holder_payout_count(env.clone(), holder.clone());
+    let (start, end, next) = window(count, cursor, limit);
+
+    let mut payouts = Vec::new(&env);
+    for index in start..end {
+        if let Some(payout) = storage::get(&env, &DataKey::HolderPayout(holder.clone(), index)) {
+            payouts.push_back(payout);
+        }
+    }
+    (payouts, next)
+}
+
+/// Number of distribution payouts a holder has received
+pub fn get_holder_payout_count(env: Env, holder: Address) -> u32 {
+    storage::get(&env, &DataKey::HolderPayoutCount(holder)).unwrap_or(0)
+}
+
+/// Bounds of the next page of an append-only list of `len` items, and the
+/// cursor after it. The walk stops at the length it started on.
+fn window(len: u32, cursor: Option<Cursor>, limit: u32) -> (u32, u32, Option<Cursor>) {
+    let snapshot = cursor
+        .as_ref()
+        .map_or(len as u64, |cursor| cursor.snapshot)
+        .min(len as u64) as u32;
+    let start = cursor.map_or(0, |cursor| cursor.position).min(snapshot);
+    let limit = if limit == 0 || limit > MAX_PAGE_SIZE {
+        MAX_PAGE_SIZE
+    } else {
+        limit
+    };
+    let end = snapshot.min(start + limit);
+
+    let next = (end < snapshot).then_some(Cursor {
+        page: 0,
+        position: end,
+        snapshot: snapshot as u64,
+    });
+    (start, end, next)
 }
 
 /// Get number of distributions for an asset (analytics)
 pub fn get_distribution_count(env: Env, asset_id: u64) -> u32 {
-    env.storage()
-        .persistent()
-        .get(&DataKey::DistributionCount(asset_id))
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::DistributionCount(asset_id)).unwrap_or(0)
 }
 
-pub fn get_fnft_contract_address(env: Env) -> Address {
+pub fn get_fnft_contract_address(env: Env) -> Result<Address, FundingError> {
     utils::get_fnft_contract(&env)
 }
 
 /// Check if an address can distribute funds for an asset
-pub fn can_distribute(env: Env, caller: Address, asset_id: u64) -> bool {
-    let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+pub fn can_distribute(env: Env, caller: Address, asset_id: u64) -> Result<bool, FundingError> {
+    let admin: Option<Address> = storage::get(&env, &DataKey::Admin);
     if let Some(admin_addr) = admin {
         if caller == admin_addr {
-            return true;
+            return Ok(true);
         }
     }
 
-    let governance_contract: Option<Address> =
-        env.storage().instance().get(&DataKey::GovernanceContract);
+    let governance_contract: Option<Address> = storage::get(&env, &DataKey::GovernanceContract);
     if let Some(gov_addr) = governance_contract {
         if caller == gov_addr {
-            return true;
+            return Ok(true);
         }
     }
 
-    let fnft_contract = utils::get_fnft_contract(&env);
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
 
-    fnft_client.owns_asset(&caller, &asset_id)
+    Ok(fnft_client.owns_asset(&caller, &asset_id))
 }
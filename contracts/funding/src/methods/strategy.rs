@@ -0,0 +1,99 @@
+use crate::errors::FundingError;
+use crate::interfaces::FNFTClient;
+use crate::storage::{DistributionMode, MAX_DISTRIBUTION_TIERS};
+use soroban_sdk::{Address, Env, Vec};
+
+// Every distribution runs the same pipeline: snapshot the holders' weights
+// under its strategy, turn the weights into shares, then pay the shares out.
+// A new strategy only needs a validation rule and a weighting rule here.
+
+/// Reject strategy parameters that could not produce a meaningful split
+pub fn validate(mode: &DistributionMode) -> Result<(), FundingError> {
+    match mode {
+        DistributionMode::Snapshot | DistributionMode::EqualPerHolder => Ok(()),
+        DistributionMode::TimeWeighted(lookback) => {
+            if *lookback == 0 {
+                return Err(FundingError::InvalidAmount);
+            }
+            Ok(())
+        }
+        DistributionMode::Tiered(tiers) => {
+            if tiers.is_empty() || tiers.len() > MAX_DISTRIBUTION_TIERS {
+                return Err(FundingError::InvalidTiers);
+            }
+            let mut previous: Option<u64> = None;
+            for tier in tiers.iter() {
+                if tier.weight == 0 || previous.is_some_and(|min| tier.min_balance <= min) {
+                    return Err(FundingError::InvalidTiers);
+                }
+                previous = Some(tier.min_balance);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Weight of each owner under `mode`, plus the total the shares are divided by.
+///
+/// Snapshot weights are current balances over total supply. Time-weighted weights
+/// are average balances over the lookback window, divided by their own sum. Equal
+/// weights give every current holder one unit. Tiered weights are balances times
+/// the weight of the highest tier the balance reaches; balances below every tier
+/// weigh nothing.
+pub fn holder_weights(
+    env: &Env,
+    fnft_client: &FNFTClient,
+    asset_id: u64,
+    owners: &Vec<Address>,
+    total_supply: u64,
+    mode: &DistributionMode,
+) -> Result<(Vec<u64>, u64), FundingError> {
+    let mut weights = Vec::new(env);
+    let mut total_weight = 0u64;
+
+    for owner in owners.iter() {
+        let weight = match mode {
+            DistributionMode::Snapshot => fnft_client.balance_of(&owner, &asset_id),
+            DistributionMode::TimeWeighted(lookback) => {
+                let since = env.ledger().timestamp().saturating_sub(*lookback);
+                fnft_client.time_weighted_balance(&owner, &asset_id, &since)
+            }
+            DistributionMode::EqualPerHolder => {
+                (fnft_client.balance_of(&owner, &asset_id) > 0) as u64
+            }
+            DistributionMode::Tiered(tiers) => {
+                let balance = fnft_client.balance_of(&owner, &asset_id);
+                let weight = tiers
+                    .iter()
+                    .filter(|tier| balance >= tier.min_balance)
+                    .last()
+                    .map_or(0, |tier| tier.weight);
+                balance
+                    .checked_mul(weight as u64)
+                    .ok_or(FundingError::InvalidAmount)?
+            }
+        };
+        total_weight = total_weight
+            .checked_add(weight)
+            .ok_or(FundingError::InvalidAmount)?;
+        weights.push_back(weight);
+    }
+
+    // Snapshot shares stay fractions of the total supply
+    if *mode == DistributionMode::Snapshot {
+        total_weight = total_supply;
+    }
+    if total_weight == 0 {
+        return Err(FundingError::NoSupply);
+    }
+    Ok((weights, total_weight))
+}
+
+/// Share of `net_amount` for every weight; remainders are left in the SAC as dust
+pub fn shares(env: &Env, net_amount: u128, weights: &Vec<u64>, total_weight: u64) -> Vec<u128> {
+    let mut shares = Vec::new(env);
+    for weight in weights.iter() {
+        shares.push_back((net_amount * weight as u128) / total_weight as u128);
+    }
+    shares
+}
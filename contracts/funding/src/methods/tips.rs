@@ -0,0 +1,129 @@
+use crate::errors::FundingError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{ledger, utils};
+use crate::storage::{
+    self, DataKey, EntryDirection, LedgerAccount, SupporterTotals, TipReceipt, MAX_TIP_MESSAGE_LEN,
+    MAX_TOP_SUPPORTERS,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
+
+/// A fan tips an asset: `amount` moves into the asset's SAC as revenue and the
+/// tipper's cumulative total is updated. Only the SHA-256 of the message is kept,
+/// in the receipt and the `tip` event. Returns the receipt id.
+pub fn tip(
+    env: Env,
+    tipper: Address,
+    asset_id: u64,
+    amount: i128,
+    message: String,
+) -> Result<u32, FundingError> {
+    tipper.require_auth();
+
+    if amount <= 0 || message.len() > MAX_TIP_MESSAGE_LEN {
+        return Err(FundingError::InvalidAmount);
+    }
+
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    if !FNFTClient::new(&env, &fnft_contract).asset_exists(&asset_id) {
+        return Err(FundingError::AssetNotFound);
+    }
+
+    let sac_address = utils::get_asset_sac(&env, asset_id)?;
+    utils::token_transfer(&env, &sac_address, &tipper, &sac_address, amount)?;
+
+    let now = env.ledger().timestamp();
+    let message_hash = message_hash(&env, &message);
+
+    let receipt_id = get_tip_count(env.clone(), asset_id);
+    storage::set(
+        &env,
+        &DataKey::TipReceipt(asset_id, receipt_id),
+        &TipReceipt {
+            tipper: tipper.clone(),
+            amount: amount as u128,
+            message_hash: message_hash.clone(),
+            timestamp: now,
+        },
+    );
+    storage::set(&env, &DataKey::TipCount(asset_id), &(receipt_id + 1));
+
+    let mut totals =
+        get_supporter(env.clone(), asset_id, tipper.clone()).unwrap_or(SupporterTotals {
+            supporter: tipper.clone(),
+            total: 0,
+            tips: 0,
+            last_tip_at: now,
+        });
+    totals.total += amount as u128;
+    totals.tips += 1;
+    totals.last_tip_at = now;
+    storage::set(&env, &DataKey::Supporter(asset_id, tipper.clone()), &totals);
+    rank_supporter(&env, asset_id, totals);
+
+    ledger::post(
+        &env,
+        asset_id,
+        LedgerAccount::Deposits,
+        EntryDirection::Debit,
+        amount as u128,
+        Some(tipper.clone()),
+        Some(receipt_id),
+    );
+    events::emit_tip(&env, asset_id, tipper, receipt_id, amount, message_hash);
+
+    Ok(receipt_id)
+}
+
+pub fn get_tip_receipt(env: Env, asset_id: u64, receipt_id: u32) -> Option<TipReceipt> {
+    storage::get(&env, &DataKey::TipReceipt(asset_id, receipt_id))
+}
+
+pub fn get_tip_count(env: Env, asset_id: u64) -> u32 {
+    storage::get(&env, &DataKey::TipCount(asset_id)).unwrap_or(0)
+}
+
+pub fn get_supporter(env: Env, asset_id: u64, supporter: Address) -> Option<SupporterTotals> {
+    storage::get(&env, &DataKey::Supporter(asset_id, supporter))
+}
+
+/// The asset's largest supporters by cumulative tips, largest first
+pub fn top_supporters(env: Env, asset_id: u64) -> Vec<SupporterTotals> {
+    storage::get(&env, &DataKey::TopSupporters(asset_id)).unwrap_or(Vec::new(&env))
+}
+
+/// SHA-256 of the message's UTF-8 bytes, so anyone holding the text can check it
+fn message_hash(env: &Env, message: &String) -> BytesN<32> {
+    let mut buffer = [0u8; MAX_TIP_MESSAGE_LEN as usize];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buffer[..len]);
+    env.crypto()
+        .sha256(&Bytes::from_slice(env, &buffer[..len]))
+        .to_bytes()
+}
+
+/// Move the supporter to its place on the leaderboard. Totals only grow, so a
+/// supporter that drops off never outranks those kept.
+fn rank_supporter(env: &Env, asset_id: u64, totals: SupporterTotals) {
+    let mut top = top_supporters(env.clone(), asset_id);
+    if let Some(index) = top
+        .iter()
+        .position(|entry| entry.supporter == totals.supporter)
+    {
+        top.remove(index as u32);
+    }
+
+    let rank = top
+        .iter()
+        .position(|entry| entry.total < totals.total)
+        .map_or(top.len(), |index| index as u32);
+    if rank >= MAX_TOP_SUPPORTERS {
+        return;
+    }
+    top.insert(rank, totals);
+    while top.len() > MAX_TOP_SUPPORTERS {
+        top.pop_back();
+    }
+
+    storage::set(env, &DataKey::TopSupporters(asset_id), &top);
+}
@@ -1,13 +1,57 @@
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::errors::FundingError;
+use crate::interfaces::{FNFTClient, TokenClient};
+use crate::methods::batches;
+use crate::storage::{self, DataKey, OWNER_PAGE_SIZE};
+use soroban_sdk::{Address, Env, Vec};
 
-pub fn get_fnft_contract(env: &Env) -> Address {
-    env.storage()
-        .instance()
-        .get(&DataKey::FNFTContract)
-        .unwrap()
+pub fn get_fnft_contract(env: &Env) -> Result<Address, FundingError> {
+    storage::get(env, &DataKey::FNFTContract).ok_or(FundingError::NotInitialized)
 }
 
 pub fn get_governance_contract(env: &Env) -> Option<Address> {
-    env.storage().instance().get(&DataKey::GovernanceContract)
+    storage::get(env, &DataKey::GovernanceContract)
+}
+
+/// Every owner of an asset, read page by page, for paying in a single call.
+///
+/// A single call reads at most the owner pages one batch of a batched
+/// distribution pays; larger assets fail with `DistributionTooLarge` and are
+/// paid through `start_batched_distribution`.
+pub fn asset_owners(
+    env: &Env,
+    fnft_client: &FNFTClient,
+    asset_id: u64,
+) -> Result<Vec<Address>, FundingError> {
+    let max_pages = batches::get_batch_pages(env.clone());
+    let mut owners = Vec::new(env);
+    let mut cursor = None;
+    for _ in 0..max_pages {
+        let (page, next) = fnft_client.get_asset_owners_page(&asset_id, &cursor, &OWNER_PAGE_SIZE);
+        owners.append(&page);
+        if next.is_none() {
+            return Ok(owners);
+        }
+        cursor = next;
+    }
+    Err(FundingError::DistributionTooLarge)
+}
+
+/// SAC registered for an asset
+pub fn get_asset_sac(env: &Env, asset_id: u64) -> Result<Address, FundingError> {
+    storage::get(env, &DataKey::AssetSAC(asset_id)).ok_or(FundingError::SacNotRegistered)
+}
+
+/// Token transfer that surfaces a failing token contract as TokenTransferFailed
+pub fn token_transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), FundingError> {
+    let client = TokenClient::new(env, token);
+    match client.try_transfer(from, to, &amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(FundingError::TokenTransferFailed),
+    }
 }
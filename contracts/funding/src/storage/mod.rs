@@ -1,12 +1,20 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, String, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+pub use admin_log::{AdminLogEntry, AdminLogHead, ADMIN_LOG_PAGE_SIZE, MAX_ADMIN_LOG_PAGES};
+pub use paging::Cursor;
+pub use placement::*;
 
 /// Storage keys for funding contract data
-#[contracttype]
+#[contracttype(export = false)]
 pub enum DataKey {
     // Core contract data
     Admin,
-    GovernanceContract,
+    InitConfigHash,     // SHA-256 of the XDR-encoded initialization arguments
+    GovernanceContract, // Set by the admin once, then replaced only by itself; alone may upgrade
     FNFTContract,
+    EventLevel, // EventLevel for distributions (absent = Full)
+    WasmHash,   // Code installed by the last upgrade (absent = code deployed at creation)
 
     // SAC Management
     AssetSAC(u64),       // asset_id → sac_contract_address
@@ -15,4 +23,502 @@ pub enum DataKey {
     // Analytics
     TotalDistributed(u64),  // asset_id → total_xlm_distributed
     DistributionCount(u64), // asset_id → number_of_distributions
+
+    // Consolidation remainder compensation
+    CompensationRate(u64), // asset_id → XLM paid per pre-consolidation remainder unit
+    CompensationPool(u64), // asset_id → SAC balance reserved for unclaimed remainders
+
+    // Protocol fee
+    ProtocolFee,            // ProtocolFeeConfig (absent = no fee)
+    AssetFeeOverride(u64),  // asset_id → fee in basis points replacing the default
+    TotalFees(u64),         // asset_id → total fees taken from distributions
+    Distribution(u64, u32), // asset_id → distribution index → DistributionRecord
+    RewardAsset(u64, u32),  // asset_id → distribution index → fractions asset paid instead of XLM
+
+    // Circuit breaker
+    AssetPaused(u64), // asset_id → true while distributions and claims are halted
+
+    // Petty cash (manager spends without a poll)
+    PettyCash(u64),       // asset_id → PettyCashConfig, set by governance poll
+    PettyWindow(u64),     // asset_id → Vec<(timestamp, amount)> spent in the rolling window
+    PettySpend(u64, u32), // asset_id → spend index → PettySpend
+    PettySpendCount(u64), // asset_id → number of petty cash spends
+
+    // Earmarked deposits (sponsor funds reserved for one purpose)
+    Earmark(u64, u32),           // asset_id → earmark_id → Earmark
+    EarmarkCount(u64),           // asset_id → number of earmarks
+    EarmarkedTotal(u64),         // asset_id → unspent earmarked SAC balance
+    EarmarkPurpose(u64, Symbol), // asset_id → purpose → PurposeTotals
+
+    // Timelocked emergency withdrawals
+    EmergencyWithdrawal(u64, u32), // asset_id → withdrawal_id → EmergencyWithdrawal
+    EmergencyWithdrawalCount(u64), // asset_id → number of announced withdrawals
+
+    // Accounting export
+    LedgerTotals(u64, LedgerAccount), // asset_id → account → AccountTotals
+
+    // Per-holder payout history
+    HolderPayout(Address, u32), // holder → index → HolderPayout
+    HolderPayoutCount(Address), // holder → number of payouts received
+
+    // Multi-currency analytics
+    PriceOracle, // Oracle (Reflector interface) pricing SAC tokens in a reference currency
+
+    // Tips from supporters
+    TipCount(u64),           // asset_id → number of tips received
+    TipReceipt(u64, u32),    // asset_id → receipt id → TipReceipt
+    Supporter(u64, Address), // asset_id → tipper → SupporterTotals
+    TopSupporters(u64),      // asset_id → Vec<SupporterTotals>, largest total first
+
+    // Batched distributions
+    BatchPages,               // Owner pages paid per batch (absent = DEFAULT_BATCH_PAGES)
+    BatchedDistribution(u64), // asset_id → BatchedDistribution in progress
+
+    // Admin action log
+    AdminLogHead,      // AdminLogHead: entry count and the hash of folded entries
+    AdminLogPage(u32), // page → Vec<AdminLogEntry>, ADMIN_LOG_PAGE_SIZE per page
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("fund");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::GovernanceContract
+            | DataKey::FNFTContract
+            | DataKey::EventLevel
+            | DataKey::WasmHash
+            | DataKey::ProtocolFee
+            | DataKey::PriceOracle
+            | DataKey::BatchPages
+            | DataKey::AdminLogHead => Placement::Instance,
+            DataKey::AssetSAC(..)
+            | DataKey::SACToAsset(..)
+            | DataKey::TotalDistributed(..)
+            | DataKey::DistributionCount(..)
+            | DataKey::CompensationRate(..)
+            | DataKey::CompensationPool(..)
+            | DataKey::AssetFeeOverride(..)
+            | DataKey::TotalFees(..)
+            | DataKey::Distribution(..)
+            | DataKey::RewardAsset(..)
+            | DataKey::AssetPaused(..)
+            | DataKey::PettyCash(..)
+            | DataKey::PettySpend(..)
+            | DataKey::PettySpendCount(..)
+            | DataKey::Earmark(..)
+            | DataKey::EarmarkCount(..)
+            | DataKey::EarmarkedTotal(..)
+            | DataKey::EarmarkPurpose(..)
+            | DataKey::EmergencyWithdrawal(..)
+            | DataKey::EmergencyWithdrawalCount(..)
+            | DataKey::LedgerTotals(..)
+            | DataKey::HolderPayout(..)
+            | DataKey::HolderPayoutCount(..)
+            | DataKey::TipCount(..)
+            | DataKey::TipReceipt(..)
+            | DataKey::Supporter(..)
+            | DataKey::TopSupporters(..)
+            | DataKey::BatchedDistribution(..)
+            | DataKey::AdminLogPage(..) => Placement::Persistent,
+            DataKey::PettyWindow(..) => Placement::Temporary,
+        }
+    }
+}
+
+impl admin_log::AdminLogKey for DataKey {
+    fn head() -> Self {
+        DataKey::AdminLogHead
+    }
+
+    fn page(page: u32) -> Self {
+        DataKey::AdminLogPage(page)
+    }
+}
+
+/// Protocol fee taken from every distribution and sent to the treasury
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFeeConfig {
+    pub treasury: Address,
+    pub fee_bps: u32,
+    pub exempt_governance: bool, // Skip the fee for governance-executed distributions
+}
+
+/// Accounting for a single executed distribution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionRecord {
+    pub requested: u128,   // Amount taken from the SAC
+    pub fee: u128,         // Part of `requested` sent to the protocol treasury
+    pub distributed: u128, // Paid to holders (rounding dust stays in the SAC)
+    pub recipients: u32,
+    pub timestamp: u64,
+    pub mode: DistributionMode,
+    pub excluded: Vec<Address>, // Holders left out, shares renormalized over the rest
+}
+
+/// Fractions a distribution pays instead of XLM: `amount` of the fractcore
+/// asset `asset_id`, taken from `source`. The source is this contract or a
+/// holder that approved it as operator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionReward {
+    pub source: Address,
+    pub asset_id: u64,
+    pub amount: u64,
+}
+
+/// What an executed distribution did, returned to the caller (e.g. governance)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionOutcome {
+    pub distribution_id: u32, // Index for get_distribution
+    pub requested: u128,
+    pub fee: u128,
+    pub distributed: u128,
+    pub recipients: u32,
+    pub dust: u128, // Rounding remainder left in the SAC
+}
+
+/// Strategy a distribution weights holders by, kept in its record
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DistributionMode {
+    Snapshot,                // Pro rata to current balances
+    TimeWeighted(u64),       // Average balance over the last N seconds (rewards long-term holders)
+    EqualPerHolder,          // Same amount for every current holder
+    Tiered(Vec<WeightTier>), // Balance times the weight of the highest tier reached
+}
+
+/// Balance threshold of a tiered distribution and the weight applied from it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightTier {
+    pub min_balance: u64,
+    pub weight: u32, // Multiplier applied to the whole balance
+}
+
+/// How many events a distribution publishes
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventLevel {
+    Full,    // A `received` event per holder (original behavior)
+    Summary, // Only the aggregated `distrib` event with recipient count and total, plus the fee
+    Minimal, // Only the `distrib` event
+}
+
+/// Limits for asset manager spends that skip the poll
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PettyCashConfig {
+    pub threshold: u128,   // Largest single spend
+    pub monthly_cap: u128, // Total over any PETTY_CASH_WINDOW seconds
+}
+
+/// Log entry for a spend made under the petty cash limits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PettySpend {
+    pub spender: Address,
+    pub recipient: Option<Address>, // None for a distribution to holders
+    pub amount: u128,
+    pub memo: String,
+    pub timestamp: u64,
+}
+
+/// What must happen before earmarked funds can be spent
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReleaseCondition {
+    Immediate,           // Spendable as soon as it is deposited
+    ManagerConfirmation, // The asset manager confirms the release
+    PollApproval,        // A governance poll releases it
+}
+
+/// Sponsor deposit that can only be spent against its purpose
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Earmark {
+    pub depositor: Address,
+    pub purpose: Symbol,
+    pub condition: ReleaseCondition,
+    pub released: bool,
+    pub deposited: u128,
+    pub spent: u128,
+    pub timestamp: u64,
+}
+
+/// Earmark analytics per asset and purpose, kept apart from distributions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurposeTotals {
+    pub deposited: u128,
+    pub spent: u128,
+}
+
+/// Where an announced emergency withdrawal stands
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithdrawalStatus {
+    Pending,  // Waiting out the timelock; holders can still veto
+    Vetoed,   // Blocked by a governance poll
+    Executed, // Paid out to the admin
+}
+
+/// Admin withdrawal from an asset's SAC, announced ahead of execution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyWithdrawal {
+    pub admin: Address, // Announcing admin, paid on execution
+    pub amount: u128,
+    pub reason: String,
+    pub announced_at: u64,
+    pub executable_at: u64,
+    pub status: WithdrawalStatus,
+}
+
+/// Bookkeeping account a movement of an asset's SAC balance is booked to
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LedgerAccount {
+    Deposits,             // deposit_funds and tips
+    Distributions,        // Net amount allocated to holders: payouts plus dust
+    Fees,                 // Protocol fees sent to the treasury
+    Dust,                 // Rounding remainders carried back into the SAC
+    EmergencyWithdrawals, // Executed emergency withdrawals
+    Compensation,         // Remainder compensation funded and claimed
+    Earmarks,             // Earmarked deposits and spends
+    PettyCash,            // Petty cash spends to a recipient
 }
+
+/// Every account, in the order get_ledger_totals reports them
+pub const LEDGER_ACCOUNTS: [LedgerAccount; 8] = [
+    LedgerAccount::Deposits,
+    LedgerAccount::Distributions,
+    LedgerAccount::Fees,
+    LedgerAccount::Dust,
+    LedgerAccount::EmergencyWithdrawals,
+    LedgerAccount::Compensation,
+    LedgerAccount::Earmarks,
+    LedgerAccount::PettyCash,
+];
+
+/// Side of a ledger entry, seen from the asset's SAC balance
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryDirection {
+    Debit,  // Into the SAC balance
+    Credit, // Out of the SAC balance
+}
+
+/// One ledger entry, published as the `ledger` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerEntry {
+    pub account: LedgerAccount,
+    pub direction: EntryDirection,
+    pub amount: u128,
+    pub counterparty: Option<Address>, // None for aggregated and internal entries
+    pub reference: Option<u32>, // Distribution, withdrawal, earmark, petty spend or tip receipt id
+}
+
+/// Running totals of one account of an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountTotals {
+    pub account: LedgerAccount,
+    pub debits: u128,
+    pub credits: u128,
+}
+
+/// Amount a single holder would receive
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payout {
+    pub recipient: Address,
+    pub amount: u128,
+}
+
+/// Distribution payout a holder received
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderPayout {
+    pub asset_id: u64,
+    pub distribution_id: u32,
+    pub amount: u128, // In XLM, or fractions of the distribution's reward asset if it has one
+    pub timestamp: u64,
+}
+
+/// Acknowledgement of a tip, kept per asset under a sequential receipt id
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipReceipt {
+    pub tipper: Address,
+    pub amount: u128,
+    pub message_hash: BytesN<32>, // SHA-256 of the message; the text itself is not stored
+    pub timestamp: u64,
+}
+
+/// Cumulative tips one supporter gave an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupporterTotals {
+    pub supporter: Address,
+    pub total: u128,
+    pub tips: u32,
+    pub last_tip_at: u64,
+}
+
+/// Result of a distribution dry run
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionPreview {
+    pub recipients: u32,       // Holders with a non-zero share
+    pub below_min_payout: u32, // Recipients whose share is below the requested minimum
+    pub fee: u128,
+    pub distributed: u128,
+    pub dust: u128,               // Rounding remainder that stays in the SAC
+    pub funded: bool,             // Whether the SAC currently covers the amount
+    pub top_payouts: Vec<Payout>, // Largest payouts first
+}
+
+/// Consecutive owner pages one batch of a distribution pays
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionBatch {
+    pub first_page: u32, // Index of the batch's first owner page
+    pub pages: u32,      // Owner pages of up to OWNER_PAGE_SIZE owners each
+    pub owners: u32,     // Owners on those pages
+    pub start: Cursor,   // Owner cursor the batch starts at (page 0, position 0 for the first)
+}
+
+/// How a distribution splits into transactions under the configured batch size
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionPlan {
+    pub asset_id: u64,
+    pub amount: u128,
+    pub recipients: u32, // Owners of the asset; those whose share rounds to zero are skipped
+    pub batch_pages: u32, // Owner pages per batch the plan was made with
+    pub batches: Vec<DistributionBatch>, // One per transaction, in execution order
+}
+
+/// A distribution paid out batch by batch along a plan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchedDistribution {
+    pub distribution_id: u32, // Index the record gets once the last batch ran
+    pub requested: u128,
+    pub fee: u128,         // Taken when the distribution started
+    pub total_supply: u64, // Supply the shares are fractions of, fixed at the start
+    pub description: String,
+    pub batches: Vec<DistributionBatch>,
+    pub next_batch: u32,
+    pub distributed: u128, // Paid by the batches run so far
+    pub recipients: u32,
+    pub started_at: u64,
+}
+
+/// Reflector asset identifier, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Reflector price record, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128, // Reference currency per token, scaled by the oracle's decimals
+    pub timestamp: u64,
+}
+
+/// Fractcore's emergency pause, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpstreamPause {
+    pub since: u64,
+    pub retry_after: u64, // When fractcore's admin expects to unpause (0 = unknown)
+}
+
+/// One asset's totals in its own SAC token and in the reference currency
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvertedTotal {
+    pub asset_id: u64,
+    pub token: Address,                  // The asset's SAC
+    pub distributed: u128,               // total_distributed, in the token
+    pub held: u128,                      // SAC balance, in the token
+    pub rate: Option<i128>,              // Oracle rate used; None when the token is unpriced
+    pub rate_timestamp: Option<u64>,     // When the oracle recorded that rate
+    pub distributed_value: Option<u128>, // distributed × rate, in the reference currency
+    pub held_value: Option<u128>,        // held × rate, in the reference currency
+}
+
+/// Totals of several assets converted into the oracle's reference currency
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvertedTotals {
+    pub totals: Vec<ConvertedTotal>,
+    pub distributed_value: u128,     // Sum over priced assets only
+    pub held_value: u128,            // Sum over priced assets only
+    pub rate_decimals: u32,          // Decimals of every rate above
+    pub oldest_rate_at: Option<u64>, // Earliest rate timestamp the sums rely on
+    pub unpriced: u32,               // Assets left out of the sums for want of a rate
+}
+
+/// Number of largest payouts listed in a preview
+pub const PREVIEW_TOP_HOLDERS: u32 = 10;
+
+/// Upper bound on items per paginated call
+pub const MAX_PAGE_SIZE: u32 = 20;
+
+/// Maximum number of tiers of a tiered distribution
+pub const MAX_DISTRIBUTION_TIERS: u32 = 5;
+
+/// Maximum number of holders a single distribution can exclude
+pub const MAX_EXCLUDED_HOLDERS: u32 = 20;
+
+/// Rolling window the petty cash cap applies to (30 days)
+pub const PETTY_CASH_WINDOW: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of petty cash spends inside one window
+pub const MAX_PETTY_SPENDS_PER_WINDOW: u32 = 50;
+
+/// Ledgers a petty cash window stays in temporary storage after its last spend:
+/// the window at 4-second ledgers, so it outlives every spend in it even when
+/// ledgers close faster than the usual 5 seconds
+pub const PETTY_WINDOW_TTL: u32 = (PETTY_CASH_WINDOW / 4) as u32;
+
+/// Delay between announcing an emergency withdrawal and executing it (3 days),
+/// long enough for holders to run a one-day veto poll
+pub const EMERGENCY_WITHDRAW_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// Upper bound for any protocol fee (10%)
+pub const MAX_FEE_BPS: u32 = 1000;
+
+/// Supporters kept on each asset's tip leaderboard
+pub const MAX_TOP_SUPPORTERS: u32 = 10;
+
+/// Longest tip message accepted, in bytes
+pub const MAX_TIP_MESSAGE_LEN: u32 = 280;
+
+/// Owners per page read from the fractions contract (its largest page)
+pub const OWNER_PAGE_SIZE: u32 = 50;
+
+/// Owner pages one batch pays when the admin has not configured it
+pub const DEFAULT_BATCH_PAGES: u32 = 2;
+
+/// Upper bound for the configured owner pages per batch
+pub const MAX_BATCH_PAGES: u32 = 10;
+
+/// Most batches a plan may split a distribution into
+pub const MAX_DISTRIBUTION_BATCHES: u32 = 20;
+
+/// Recipients per fractions transfer call (the fractions contract's batch limit)
+pub const FRACTION_TRANSFER_BATCH: u32 = 50;
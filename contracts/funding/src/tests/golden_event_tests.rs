@@ -0,0 +1,52 @@
+#![cfg(test)]
+//! Canonical distribution scenarios whose events must serialize to exactly the
+//! XDR in `golden/`. A failure means a topic or payload layout changed: if that
+//! is intended, rerun with `UPDATE_GOLDEN=1` and commit the new golden file so
+//! indexers see the change in review.
+
+extern crate std;
+
+use crate::contract::*;
+use crate::tests::unit_tests::mock_sac;
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString};
+use std::string::String;
+use test_support::{assert_golden, record_events};
+
+mod fnft {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+#[test]
+fn test_golden_distribution_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let admin = Address::generate(&env);
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
+    let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
+    let sac_contract_id = env.register(mock_sac::MockSAC, ());
+    let contract_id = env.register(FundingContract, (admin.clone(), fnft_contract_id));
+    let client = FundingContractClient::new(&env, &contract_id);
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let asset_id = fnft_client.mint(&owner1, &600);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &400);
+    let mut golden = String::new();
+
+    client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+    record_events(&env, &contract_id, "register_asset_sac", &mut golden);
+
+    client.deposit_funds(&depositor, &asset_id, &5000);
+    record_events(&env, &contract_id, "deposit_funds", &mut golden);
+
+    client.distribute_funds(
+        &admin,
+        &asset_id,
+        &1000u128,
+        &SorobanString::from_str(&env, "Rent"),
+    );
+    record_events(&env, &contract_id, "distribute_funds", &mut golden);
+
+    assert_golden(env!("CARGO_MANIFEST_DIR"), "distribution", &golden);
+}
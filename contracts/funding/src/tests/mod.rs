@@ -1 +1,4 @@
+// Tests seed and inspect storage directly, whatever class a key is placed in
+#![allow(clippy::disallowed_methods)]
+pub mod golden_event_tests;
 pub mod unit_tests;
@@ -1,15 +1,50 @@
 #![cfg(test)]
 
 use crate::contract::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::errors::FundingError;
+use crate::storage::{
+    stored_key, DataKey, DistributionMode, EntryDirection, EventLevel, FractionReward,
+    LedgerAccount, LedgerEntry, OracleAsset, PlacedKey, ReleaseCondition, SupporterTotals,
+    UpstreamPause, WeightTier, WithdrawalStatus, DEFAULT_BATCH_PAGES, EMERGENCY_WITHDRAW_DELAY,
+    MAX_TOP_SUPPORTERS, PETTY_WINDOW_TTL,
+};
+use placement::testutils::strip_namespaces;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Temporary as _, Address as _, Events, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val,
+};
 
 // Import the FNFT contract for testing
 mod fnft {
     soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
 }
 
-// Create a mock SAC contract for testing
-mod mock_sac {
+// Governance contract upgrades are handed to, built for the fractcore funding uses
+#[allow(clippy::too_many_arguments)]
+mod governance {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/governance.wasm");
+}
+
+/// A governance contract over `fnft_contract_id`, to name with `set_governance_contract`
+fn register_governance(env: &Env, admin: &Address, fnft_contract_id: &Address) -> Address {
+    env.register(
+        governance::WASM,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            Address::generate(env),
+            60u32,
+            30u32,
+            7u32,
+        ),
+    )
+}
+
+// Create a mock SAC contract for testing (shared with the golden event tests)
+pub mod mock_sac {
     use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
 
     #[contract]
@@ -20,6 +55,7 @@ mod mock_sac {
     #[contracttype]
     pub enum DataKey {
         Balance(Address),
+        Skim, // Extra amount silently taken from every sender
     }
 
     #[contractimpl]
@@ -34,7 +70,8 @@ mod mock_sac {
             if from_balance < amount {
                 panic!("Insufficient balance");
             }
-            from_balance -= amount;
+            let skim: i128 = env.storage().instance().get(&DataKey::Skim).unwrap_or(0);
+            from_balance -= amount + skim;
             to_balance += amount;
             env.storage()
                 .persistent()
@@ -51,6 +88,11 @@ mod mock_sac {
                 .unwrap_or(1000000i128) // Default large balance for testing
         }
 
+        /// Simulate a misbehaving token that loses funds on every transfer
+        pub fn set_skim(env: Env, amount: i128) {
+            env.storage().instance().set(&DataKey::Skim, &amount);
+        }
+
         pub fn mint(env: Env, to: Address, amount: i128) {
             if amount < 0 {
                 panic!("Cannot mint negative amount");
@@ -63,6 +105,32 @@ mod mock_sac {
     }
 }
 
+// Reflector-style oracle with 14 decimals and per-token prices
+mod mock_oracle {
+    use crate::storage::{OracleAsset, PriceData};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, asset: OracleAsset, price: i128, timestamp: u64) {
+            env.storage()
+                .persistent()
+                .set(&asset, &PriceData { price, timestamp });
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            14
+        }
+
+        pub fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData> {
+            env.storage().persistent().get(&asset)
+        }
+    }
+}
+
 fn setup() -> (
     Env,
     Address,
@@ -74,27 +142,23 @@ fn setup() -> (
 ) {
     let env = Env::default();
     env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
 
-    // Deploy FNFT contract
-    let fnft_contract_id = env.register(fnft::WASM, ());
+    // Deploy and initialize FNFT contract
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
     let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
 
     // Deploy mock SAC contract
     let sac_contract_id = env.register(mock_sac::MockSAC, ());
     let sac_client = mock_sac::MockSACClient::new(&env, &sac_contract_id);
 
-    // Deploy Funding contract
-    let funding_contract_id = env.register(FundingContract, ());
+    // Deploy and initialize Funding contract (NO xlm_token needed now)
+    let funding_contract_id =
+        env.register(FundingContract, (admin.clone(), fnft_contract_id.clone()));
     let funding_client = FundingContractClient::new(&env, &funding_contract_id);
 
-    let admin = Address::generate(&env);
-
-    // Initialize FNFT contract
-    fnft_client.initialize(&admin);
-
-    // Initialize Funding contract (NO xlm_token needed now)
-    funding_client.initialize(&admin, &fnft_contract_id);
-
     (
         env,
         admin,
@@ -123,7 +187,7 @@ fn test_initialize_funding_contract() {
 }
 
 #[test]
-#[should_panic(expected = "Contract already initialized")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_double_initialization() {
     let (
         env,
@@ -140,6 +204,43 @@ fn test_double_initialization() {
     funding_client.initialize(&new_admin, &fnft_contract_id);
 }
 
+#[test]
+fn test_spoofed_caller_rejected_by_admin_checks() {
+    let (env, admin, _fnft_contract_id, _sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let spoofer = Address::generate(&env);
+    let asset_id = fnft_client.mint(&spoofer, &100);
+
+    // The spoofer's own signature is all that is asked for, and it is not the admin's
+    assert_eq!(
+        funding_client.try_transfer_admin(&spoofer, &spoofer),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert_eq!(
+        funding_client.try_set_event_level(&spoofer, &EventLevel::Minimal),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert_eq!(
+        funding_client.try_set_asset_paused(&spoofer, &asset_id, &true),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert_eq!(
+        funding_client.try_distribute_funds(
+            &spoofer,
+            &asset_id,
+            &100,
+            &String::from_str(&env, "Spoofed")
+        ),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert!(!funding_client.is_asset_paused(&asset_id));
+
+    funding_client.set_asset_paused(&admin, &asset_id, &true);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(funding_client.get_admin(), admin);
+}
+
 #[test]
 fn test_register_asset_sac() {
     let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
@@ -164,7 +265,7 @@ fn test_register_asset_sac() {
 }
 
 #[test]
-#[should_panic(expected = "Only asset owners can register SAC")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_register_sac_unauthorized() {
     let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
         setup();
@@ -179,7 +280,7 @@ fn test_register_sac_unauthorized() {
 }
 
 #[test]
-#[should_panic(expected = "Asset already has a registered SAC")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_register_sac_already_exists() {
     let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
         setup();
@@ -222,7 +323,7 @@ fn test_deposit_funds_to_sac() {
 }
 
 #[test]
-#[should_panic(expected = "Asset must have a registered SAC to use funding features")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_deposit_without_sac() {
     let (
         env,
@@ -243,6 +344,28 @@ fn test_deposit_without_sac() {
     funding_client.deposit_funds(&depositor, &asset_id, &1000i128);
 }
 
+#[test]
+fn test_deposit_token_failure_returns_typed_error() {
+    let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let team_owner = Address::generate(&env);
+    let depositor = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&team_owner, &100);
+    funding_client.register_asset_sac(&team_owner, &asset_id, &sac_contract_id);
+
+    // The mock SAC traps on overdraft; funding reports it as a token failure
+    let overdraft = sac_client.balance(&depositor) + 1;
+    assert_eq!(
+        funding_client.try_deposit_funds(&depositor, &asset_id, &overdraft),
+        Err(Ok(FundingError::TokenTransferFailed))
+    );
+    assert_eq!(
+        funding_client.try_asset_funds(&999u64),
+        Err(Ok(FundingError::SacNotRegistered))
+    );
+}
+
 #[test]
 fn test_asset_funds_from_sac() {
     let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
@@ -283,14 +406,101 @@ fn test_distribute_from_sac() {
     let description = String::from_str(&env, "Test distribution from SAC");
 
     // Distribute from SAC
-    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    let outcome = funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(outcome.distribution_id, 0);
+    assert_eq!(outcome.requested, 1000);
+    assert_eq!(outcome.recipients, 2);
+    assert_eq!(outcome.distributed + outcome.fee + outcome.dust, 1000);
 
     // Check analytics updated (allow for dust: distributed may be less than requested)
     let distributed = funding_client.total_distributed(&asset_id);
-    assert!(distributed <= 1000u128 && distributed >= 999u128);
+    assert!((999u128..=1000u128).contains(&distributed));
+    assert_eq!(distributed, outcome.distributed);
     assert_eq!(funding_client.get_distribution_count(&asset_id), 1u32);
 }
 
+#[test]
+fn test_converted_totals_mark_rates_and_unpriced_tokens() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let priced = fnft_client.mint(&owner, &100);
+    let unpriced = fnft_client.mint(&owner, &100);
+    let other_sac = env.register(mock_sac::MockSAC, ());
+    funding_client.register_asset_sac(&owner, &priced, &sac_contract_id);
+    funding_client.register_asset_sac(&owner, &unpriced, &other_sac);
+
+    // Both SACs start at the mock's default balance of 1,000,000
+    sac_client.mint(&sac_contract_id, &1000i128);
+    let description = String::from_str(&env, "Rent");
+    funding_client.distribute_funds(&admin, &priced, &1000u128, &description);
+
+    let asset_ids = vec![&env, priced, unpriced];
+    assert_eq!(
+        funding_client.try_get_converted_totals(&asset_ids).err(),
+        Some(Ok(FundingError::NotInitialized))
+    );
+
+    let oracle_id = env.register(mock_oracle::MockOracle, ());
+    let oracle = mock_oracle::MockOracleClient::new(&env, &oracle_id);
+    funding_client.set_price_oracle(&admin, &Some(oracle_id.clone()));
+    assert_eq!(funding_client.get_price_oracle(), Some(oracle_id));
+
+    // 0.25 of the reference currency per token
+    oracle.set_price(
+        &OracleAsset::Stellar(sac_contract_id.clone()),
+        &25_000_000_000_000,
+        &500,
+    );
+
+    let totals = funding_client.get_converted_totals(&asset_ids);
+    assert_eq!(totals.rate_decimals, 14);
+    assert_eq!(totals.unpriced, 1);
+    assert_eq!(totals.oldest_rate_at, Some(500));
+
+    let line = totals.totals.get(0).unwrap();
+    assert_eq!(line.token, sac_contract_id);
+    assert_eq!(line.distributed, 1000);
+    assert_eq!(line.held, 1_000_000);
+    assert_eq!(line.rate, Some(25_000_000_000_000));
+    assert_eq!(line.rate_timestamp, Some(500));
+    assert_eq!(line.distributed_value, Some(250));
+    assert_eq!(line.held_value, Some(250_000));
+
+    // The unpriced token is listed in its own units and left out of the sums
+    let line = totals.totals.get(1).unwrap();
+    assert_eq!(line.held, 1_000_000);
+    assert_eq!(line.rate, None);
+    assert_eq!(line.held_value, None);
+    assert_eq!(totals.distributed_value, 250);
+    assert_eq!(totals.held_value, 250_000);
+}
+
+#[test]
+fn test_distribution_reaches_owners_past_first_owner_page() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+
+    // 60 holders span two FNFT owner pages of 50
+    let first = Address::generate(&env);
+    let asset_id = fnft_client.mint(&first, &60);
+    let mut last = first.clone();
+    for _ in 1..60 {
+        last = Address::generate(&env);
+        fnft_client.transfer(&first, &last, &asset_id, &1);
+    }
+    funding_client.register_asset_sac(&first, &asset_id, &sac_contract_id);
+    sac_client.mint(&sac_contract_id, &600i128);
+
+    let preview = funding_client.preview_distribution(&asset_id, &600u128, &0u128);
+    assert_eq!(preview.recipients, 60);
+
+    let description = String::from_str(&env, "Paged owners");
+    let outcome = funding_client.distribute_funds(&admin, &asset_id, &600u128, &description);
+    assert_eq!(outcome.recipients, 60);
+    assert_eq!(sac_client.balance(&last), 1_000_010);
+}
+
 // #[test]
 // fn test_emergency_withdraw_from_sac() {
 //     let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
@@ -314,7 +524,7 @@ fn test_distribute_from_sac() {
 // }
 
 #[test]
-#[should_panic(expected = "Insufficient balance in asset SAC")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_distribute_insufficient_sac_balance() {
     let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
         setup();
@@ -404,3 +614,1378 @@ fn test_view_functions() {
         Some(asset_id)
     );
 }
+
+#[test]
+fn test_consolidation_remainder_compensation() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner1, &100);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &37);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+
+    // 63 -> 6 (remainder 3), 37 -> 3 (remainder 7)
+    fnft_client.consolidate_asset(&admin, &asset_id, &10, &fnft::RemainderPolicy::Compensate);
+
+    // Nothing to claim before the issuer funds the pool
+    assert_eq!(
+        funding_client.try_claim_remainder_compensation(&owner2, &asset_id),
+        Err(Ok(FundingError::CompensationNotFunded))
+    );
+
+    assert_eq!(
+        funding_client.fund_remainder_compensation(&admin, &asset_id, &5),
+        50
+    );
+    assert_eq!(funding_client.compensation_pool(&asset_id), 50);
+
+    let before = sac_client.balance(&owner2);
+    assert_eq!(
+        funding_client.claim_remainder_compensation(&owner2, &asset_id),
+        35
+    );
+    assert_eq!(sac_client.balance(&owner2), before + 35);
+    assert_eq!(funding_client.compensation_pool(&asset_id), 15);
+    assert_eq!(fnft_client.fraction_remainder(&owner2, &asset_id), 0);
+
+    assert_eq!(
+        funding_client.try_claim_remainder_compensation(&owner2, &asset_id),
+        Err(Ok(FundingError::NoRemainder))
+    );
+
+    // The reserved pool is not distributable
+    let sac_balance = sac_client.balance(&sac_contract_id);
+    let description = String::from_str(&env, "Over-distribution");
+    assert_eq!(
+        funding_client.try_distribute_funds(
+            &admin,
+            &asset_id,
+            &((sac_balance - 14) as u128),
+            &description
+        ),
+        Err(Ok(FundingError::InsufficientSacBalance))
+    );
+}
+
+#[test]
+fn test_relayed_compensation_claim_pays_relayer() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let relayer = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner1, &100);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &37);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+    fnft_client.consolidate_asset(&admin, &asset_id, &10, &fnft::RemainderPolicy::Compensate);
+    funding_client.fund_remainder_compensation(&admin, &asset_id, &5);
+
+    // The fee cannot exceed what the claim pays out
+    assert_eq!(
+        funding_client.try_relay_claim_compensation(&relayer, &owner2, &asset_id, &36),
+        Err(Ok(FundingError::InvalidAmount))
+    );
+
+    let holder_before = sac_client.balance(&owner2);
+    let relayer_before = sac_client.balance(&relayer);
+    assert_eq!(
+        funding_client.relay_claim_compensation(&relayer, &owner2, &asset_id, &5),
+        30
+    );
+    assert_eq!(sac_client.balance(&owner2), holder_before + 30);
+    assert_eq!(sac_client.balance(&relayer), relayer_before + 5);
+    assert_eq!(funding_client.compensation_pool(&asset_id), 15);
+}
+
+#[test]
+fn test_distribution_protocol_fee() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner1, &600);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &400);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+
+    // Fees above the cap are rejected
+    assert_eq!(
+        funding_client.try_set_protocol_fee(&admin, &treasury, &1001, &true),
+        Err(Ok(FundingError::InvalidFee))
+    );
+    funding_client.set_protocol_fee(&admin, &treasury, &200, &true);
+    assert_eq!(funding_client.asset_fee_bps(&asset_id), 200);
+
+    let treasury_before = sac_client.balance(&treasury);
+    let owner2_before = sac_client.balance(&owner2);
+    let description = String::from_str(&env, "Rent with fee");
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+
+    // 2% to the treasury, the remaining 980 pro rata (owner2 holds 400 of 600)
+    assert_eq!(sac_client.balance(&treasury), treasury_before + 20);
+    assert_eq!(sac_client.balance(&owner2), owner2_before + 653);
+    assert_eq!(funding_client.total_fees(&asset_id), 20);
+
+    let record = funding_client.get_distribution(&asset_id, &0).unwrap();
+    assert_eq!(record.requested, 1000);
+    assert_eq!(record.fee, 20);
+    assert_eq!(record.distributed, 979); // 326 + 653, one unit of rounding dust
+    assert_eq!(record.recipients, 2);
+
+    // A per-asset override replaces the default
+    funding_client.set_asset_fee_override(&admin, &asset_id, &Some(0));
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(
+        funding_client.get_distribution(&asset_id, &1).unwrap().fee,
+        0
+    );
+    assert_eq!(funding_client.total_fees(&asset_id), 20);
+}
+
+#[test]
+fn test_upgrades_only_through_governance() {
+    let (env, admin, fnft_contract_id, _sac_contract_id, funding_client, _fnft_client, _sac_client) =
+        setup();
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+    assert_eq!(
+        funding_client.try_upgrade(&admin, &wasm_hash),
+        Err(Ok(FundingError::Unauthorized))
+    );
+
+    // The admin hands over once; it cannot swap in an address of its own later
+    funding_client.set_governance_contract(&admin, &governance);
+    assert_eq!(
+        funding_client.try_set_governance_contract(&admin, &admin),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert_eq!(
+        funding_client.try_upgrade(&admin, &wasm_hash),
+        Err(Ok(FundingError::Unauthorized))
+    );
+
+    // Only governance contracts built for the linked fractcore are accepted
+    let other_fractcore = env.register(fnft::WASM, (admin.clone(),));
+    for target in [
+        Address::generate(&env),
+        register_governance(&env, &admin, &other_fractcore),
+    ] {
+        assert_eq!(
+            funding_client.try_set_governance_contract(&governance, &target),
+            Err(Ok(FundingError::NotGovernanceContract))
+        );
+    }
+    let successor = register_governance(&env, &admin, &fnft_contract_id);
+    funding_client.set_governance_contract(&governance, &successor);
+    assert_eq!(
+        funding_client.try_set_governance_contract(&governance, &governance),
+        Err(Ok(FundingError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_governance_distribution_fee_exemption() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_governance_contract(&admin, &governance);
+    funding_client.set_protocol_fee(&admin, &treasury, &500, &true);
+
+    let description = String::from_str(&env, "Governance payout");
+    funding_client.distribute_funds(&governance, &asset_id, &1000u128, &description);
+    assert_eq!(
+        funding_client.get_distribution(&asset_id, &0).unwrap().fee,
+        0
+    );
+
+    // Without the exemption governance pays the fee too
+    funding_client.set_protocol_fee(&admin, &treasury, &500, &false);
+    funding_client.distribute_funds(&governance, &asset_id, &1000u128, &description);
+    assert_eq!(
+        funding_client.get_distribution(&asset_id, &1).unwrap().fee,
+        50
+    );
+}
+
+#[test]
+fn test_preview_distribution_matches_execution() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner1, &600);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &400);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+    funding_client.set_protocol_fee(&admin, &treasury, &200, &false);
+
+    let preview = funding_client.preview_distribution(&asset_id, &1000u128, &400u128);
+    assert_eq!(preview.recipients, 2);
+    assert_eq!(preview.below_min_payout, 1);
+    assert_eq!(preview.fee, 20);
+    assert_eq!(preview.distributed, 979);
+    assert_eq!(preview.dust, 1);
+    assert!(preview.funded);
+    assert_eq!(preview.top_payouts.len(), 2);
+    let largest = preview.top_payouts.get(0).unwrap();
+    assert_eq!(largest.recipient, owner2);
+    assert_eq!(largest.amount, 653);
+    assert_eq!(preview.top_payouts.get(1).unwrap().amount, 326);
+
+    let description = String::from_str(&env, "Previewed distribution");
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    let record = funding_client.get_distribution(&asset_id, &0).unwrap();
+    assert_eq!(record.fee, preview.fee);
+    assert_eq!(record.distributed, preview.distributed);
+    assert_eq!(record.recipients, preview.recipients);
+
+    // More than the SAC holds is reported as unfunded rather than rejected
+    assert!(
+        !funding_client
+            .preview_distribution(&asset_id, &2000000u128, &0u128)
+            .funded
+    );
+}
+
+#[test]
+fn test_time_weighted_distribution() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let long_holder = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let asset_id = fnft_client.mint(&long_holder, &100);
+    funding_client.register_asset_sac(&long_holder, &asset_id, &sac_contract_id);
+
+    // The newcomer buys half halfway through the window
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    fnft_client.transfer(&long_holder, &newcomer, &asset_id, &50);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    let long_before = sac_client.balance(&long_holder);
+    let new_before = sac_client.balance(&newcomer);
+    funding_client.distribute_funds_with_mode(
+        &admin,
+        &asset_id,
+        &1000u128,
+        &String::from_str(&env, "Loyalty payout"),
+        &DistributionMode::TimeWeighted(2000),
+    );
+
+    // Average balances 75 and 25 instead of the instantaneous 50/50
+    assert_eq!(sac_client.balance(&long_holder), long_before + 750);
+    assert_eq!(sac_client.balance(&newcomer), new_before + 250);
+
+    let record = funding_client.get_distribution(&asset_id, &0).unwrap();
+    assert_eq!(record.mode, DistributionMode::TimeWeighted(2000));
+    assert_eq!(record.distributed, 1000);
+}
+
+#[test]
+fn test_equal_and_tiered_distribution_strategies() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let whale = Address::generate(&env);
+    let small = Address::generate(&env);
+    let dust = Address::generate(&env);
+    let description = String::from_str(&env, "Strategy payout");
+
+    let asset_id = fnft_client.mint(&whale, &900);
+    fnft_client.transfer(&whale, &small, &asset_id, &95);
+    fnft_client.transfer(&whale, &dust, &asset_id, &5);
+    funding_client.register_asset_sac(&whale, &asset_id, &sac_contract_id);
+    let balances = || [&whale, &small, &dust].map(|holder| sac_client.balance(holder));
+
+    // Every holder gets the same amount whatever their balance
+    let before = balances();
+    funding_client.distribute_funds_with_mode(
+        &admin,
+        &asset_id,
+        &900u128,
+        &description,
+        &DistributionMode::EqualPerHolder,
+    );
+    let after = balances();
+    assert_eq!(
+        [
+            after[0] - before[0],
+            after[1] - before[1],
+            after[2] - before[2]
+        ],
+        [300, 300, 300]
+    );
+
+    // Below 10 weighs nothing, from 10 counts once, from 500 counts twice:
+    // weights 1600 and 95 out of 1695
+    let tiers = vec![
+        &env,
+        WeightTier {
+            min_balance: 10,
+            weight: 1,
+        },
+        WeightTier {
+            min_balance: 500,
+            weight: 2,
+        },
+    ];
+    let before = balances();
+    let outcome = funding_client.distribute_funds_with_mode(
+        &admin,
+        &asset_id,
+        &1695u128,
+        &description,
+        &DistributionMode::Tiered(tiers.clone()),
+    );
+    let after = balances();
+    assert_eq!(
+        [
+            after[0] - before[0],
+            after[1] - before[1],
+            after[2] - before[2]
+        ],
+        [1600, 95, 0]
+    );
+    assert_eq!(outcome.recipients, 2);
+    assert_eq!(
+        funding_client.get_distribution(&asset_id, &1).unwrap().mode,
+        DistributionMode::Tiered(tiers)
+    );
+
+    let unordered = vec![
+        &env,
+        WeightTier {
+            min_balance: 500,
+            weight: 2,
+        },
+        WeightTier {
+            min_balance: 10,
+            weight: 1,
+        },
+    ];
+    for mode in [
+        DistributionMode::Tiered(vec![&env]),
+        DistributionMode::Tiered(unordered),
+        DistributionMode::Tiered(vec![
+            &env,
+            WeightTier {
+                min_balance: 0,
+                weight: 0,
+            },
+        ]),
+    ] {
+        assert_eq!(
+            funding_client.try_distribute_funds_with_mode(
+                &admin,
+                &asset_id,
+                &100u128,
+                &description,
+                &mode
+            ),
+            Err(Ok(FundingError::InvalidTiers))
+        );
+    }
+}
+
+#[test]
+fn test_distribution_excluding_holders() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let treasury = Address::generate(&env);
+    let investor1 = Address::generate(&env);
+    let investor2 = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&treasury, &600);
+    fnft_client.transfer(&treasury, &investor1, &asset_id, &300);
+    fnft_client.transfer(&treasury, &investor2, &asset_id, &100);
+    funding_client.register_asset_sac(&treasury, &asset_id, &sac_contract_id);
+
+    let treasury_before = sac_client.balance(&treasury);
+    let investor1_before = sac_client.balance(&investor1);
+    let investor2_before = sac_client.balance(&investor2);
+    let description = String::from_str(&env, "Investor dividend");
+    funding_client.distribute_funds_excluding(
+        &admin,
+        &asset_id,
+        &1000u128,
+        &description,
+        &vec![&env, treasury.clone()],
+    );
+
+    // Shares are renormalized over the 400 fractions left
+    assert_eq!(sac_client.balance(&treasury), treasury_before);
+    assert_eq!(sac_client.balance(&investor1), investor1_before + 750);
+    assert_eq!(sac_client.balance(&investor2), investor2_before + 250);
+
+    let record = funding_client.get_distribution(&asset_id, &0).unwrap();
+    assert_eq!(record.distributed, 1000);
+    assert_eq!(record.recipients, 2);
+    assert_eq!(record.excluded, vec![&env, treasury.clone()]);
+
+    // Excluding every holder leaves no one to pay
+    assert_eq!(
+        funding_client.try_distribute_funds_excluding(
+            &admin,
+            &asset_id,
+            &1000u128,
+            &description,
+            &vec![&env, treasury.clone(), investor1.clone(), investor2.clone()],
+        ),
+        Err(Ok(FundingError::NoSupply))
+    );
+    assert_eq!(
+        funding_client.try_distribute_funds_excluding(
+            &investor1,
+            &asset_id,
+            &1000u128,
+            &description,
+            &vec![&env, treasury],
+        ),
+        Err(Ok(FundingError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_fraction_distributions_pay_holders_in_fractions() {
+    let (env, admin, _fnft_contract_id, _sac_contract_id, funding_client, fnft_client, _) = setup();
+    let treasury = Address::generate(&env);
+    let investor1 = Address::generate(&env);
+    let investor2 = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&treasury, &600);
+    fnft_client.transfer(&treasury, &investor1, &asset_id, &300);
+    fnft_client.transfer(&treasury, &investor2, &asset_id, &100);
+    let reward_id = fnft_client.mint(&funding_client.address, &1000);
+
+    // Fractions the funding contract holds, split over every holder
+    let outcome = funding_client.distribute_fractions(
+        &admin,
+        &asset_id,
+        &FractionReward {
+            source: funding_client.address.clone(),
+            asset_id: reward_id,
+            amount: 1000,
+        },
+        &String::from_str(&env, "Bonus fractions"),
+        &DistributionMode::Snapshot,
+    );
+    assert_eq!(outcome.distributed, 999);
+    assert_eq!(outcome.dust, 1);
+    assert_eq!(outcome.fee, 0);
+    assert_eq!(outcome.recipients, 3);
+    assert_eq!(fnft_client.balance_of(&treasury, &reward_id), 333);
+    assert_eq!(fnft_client.balance_of(&investor1, &reward_id), 500);
+    assert_eq!(fnft_client.balance_of(&investor2, &reward_id), 166);
+    assert_eq!(
+        fnft_client.balance_of(&funding_client.address, &reward_id),
+        1
+    );
+    assert_eq!(
+        funding_client.get_distribution_reward_asset(&asset_id, &0),
+        Some(reward_id)
+    );
+
+    // A treasury that approved the contract pays out its own fractions of the
+    // asset; it is left out of the split and keeps the dust
+    fnft_client.set_approval_for_all(&treasury, &funding_client.address, &true);
+    let outcome = funding_client.distribute_fractions(
+        &admin,
+        &asset_id,
+        &FractionReward {
+            source: treasury.clone(),
+            asset_id,
+            amount: 40,
+        },
+        &String::from_str(&env, "Treasury release"),
+        &DistributionMode::Snapshot,
+    );
+    assert_eq!(outcome.distribution_id, 1);
+    assert_eq!(outcome.dust, 0);
+    assert_eq!(fnft_client.balance_of(&treasury, &asset_id), 160);
+    assert_eq!(fnft_client.balance_of(&investor1, &asset_id), 330);
+    assert_eq!(fnft_client.balance_of(&investor2, &asset_id), 110);
+
+    let record = funding_client.get_distribution(&asset_id, &1).unwrap();
+    assert_eq!(record.recipients, 2);
+    assert_eq!(
+        record.excluded,
+        vec![&env, treasury.clone(), funding_client.address.clone()]
+    );
+    let (payouts, _) = funding_client.get_holder_payouts_page(&investor2, &None, &0);
+    assert_eq!(payouts.len(), 2);
+    assert_eq!(payouts.get(1).unwrap().amount, 10);
+
+    // Fractions are not XLM: neither the totals nor the ledger move
+    assert_eq!(funding_client.total_distributed(&asset_id), 0);
+
+    assert_eq!(
+        funding_client.try_distribute_fractions(
+            &admin,
+            &asset_id,
+            &FractionReward {
+                source: treasury,
+                asset_id,
+                amount: 161,
+            },
+            &String::from_str(&env, "Too much"),
+            &DistributionMode::Snapshot,
+        ),
+        Err(Ok(FundingError::InsufficientFractions))
+    );
+}
+
+#[test]
+fn test_invariant_violation_pauses_asset() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    let description = String::from_str(&env, "Rent");
+
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert!(!funding_client.is_asset_paused(&asset_id));
+
+    // The SAC loses more than the distribution accounted for
+    sac_client.set_skim(&7);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert!(funding_client.is_asset_paused(&asset_id));
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 2);
+
+    assert_eq!(
+        funding_client.try_distribute_funds(&admin, &asset_id, &1000u128, &description),
+        Err(Ok(FundingError::AssetPaused))
+    );
+    assert_eq!(
+        funding_client.try_set_asset_paused(&owner, &asset_id, &false),
+        Err(Ok(FundingError::Unauthorized))
+    );
+
+    // Once investigated, admin puts the asset back into service
+    sac_client.set_skim(&0);
+    funding_client.set_asset_paused(&admin, &asset_id, &false);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert!(!funding_client.is_asset_paused(&asset_id));
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 3);
+}
+
+#[test]
+fn test_fractcore_pause_fails_distributions_early() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    let description = String::from_str(&env, "Rent");
+
+    fnft_client.pause(&admin, &0);
+    assert_eq!(
+        funding_client.try_distribute_funds(&admin, &asset_id, &1000u128, &description),
+        Err(Ok(FundingError::UpstreamPaused))
+    );
+    assert_eq!(
+        funding_client.get_upstream_pause(),
+        Some(UpstreamPause {
+            since: env.ledger().timestamp(),
+            retry_after: 0,
+        })
+    );
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 0);
+
+    fnft_client.unpause(&admin);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 1);
+}
+
+#[test]
+fn test_admin_log_records_admin_and_governance_actions() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let treasury = Address::generate(&env);
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+
+    // Rejected calls leave nothing behind
+    assert_eq!(
+        funding_client.try_set_protocol_fee(&owner, &treasury, &200, &true),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    assert_eq!(funding_client.get_admin_log_head().entries, 0);
+
+    funding_client.set_protocol_fee(&admin, &treasury, &200, &true);
+    funding_client.set_governance_contract(&admin, &governance);
+    funding_client.set_petty_cash(&governance, &asset_id, &500u128, &1000u128);
+
+    let log = funding_client.get_admin_log(&0);
+    assert_eq!(log.len(), 3);
+    assert_eq!(funding_client.get_admin_log_head().entries, 3);
+
+    let fee = log.get(0).unwrap();
+    assert_eq!(fee.actor, admin);
+    assert_eq!(fee.function, Symbol::new(&env, "set_protocol_fee"));
+    let args_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(treasury, 200u32, true).to_xdr(&env))
+        .into();
+    assert_eq!(fee.args_hash, args_hash);
+
+    let petty = log.get(2).unwrap();
+    assert_eq!(petty.actor, governance);
+    assert_eq!(petty.function, Symbol::new(&env, "set_petty_cash"));
+    assert_eq!(petty.timestamp, env.ledger().timestamp());
+}
+
+#[test]
+fn test_petty_cash_spends_without_poll() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let vendor = Address::generate(&env);
+
+    // The fractcore admin minted the asset, so it is the asset manager
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_governance_contract(&admin, &governance);
+    let memo = String::from_str(&env, "Plumber");
+
+    assert_eq!(
+        funding_client.try_petty_cash_spend(&admin, &asset_id, &vendor, &100u128, &memo),
+        Err(Ok(FundingError::PettyCashNotEnabled))
+    );
+    // Only a governance poll can set the limits
+    assert_eq!(
+        funding_client.try_set_petty_cash(&admin, &asset_id, &500u128, &1000u128),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    funding_client.set_petty_cash(&governance, &asset_id, &500u128, &1000u128);
+
+    let vendor_before = sac_client.balance(&vendor);
+    funding_client.petty_cash_spend(&admin, &asset_id, &vendor, &400u128, &memo);
+    assert_eq!(sac_client.balance(&vendor), vendor_before + 400);
+    funding_client.petty_cash_distribute(
+        &admin,
+        &asset_id,
+        &500u128,
+        &String::from_str(&env, "Small dividend"),
+    );
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 1);
+    assert_eq!(funding_client.petty_cash_available(&asset_id), 100);
+
+    // Over the single-spend threshold, over the cap, and not the manager
+    assert_eq!(
+        funding_client.try_petty_cash_spend(&admin, &asset_id, &vendor, &501u128, &memo),
+        Err(Ok(FundingError::PettyCashLimitExceeded))
+    );
+    assert_eq!(
+        funding_client.try_petty_cash_spend(&admin, &asset_id, &vendor, &101u128, &memo),
+        Err(Ok(FundingError::PettyCashLimitExceeded))
+    );
+    assert_eq!(
+        funding_client.try_petty_cash_spend(&owner, &asset_id, &vendor, &50u128, &memo),
+        Err(Ok(FundingError::Unauthorized))
+    );
+
+    assert_eq!(funding_client.get_petty_spend_count(&asset_id), 2);
+    let spend = funding_client.get_petty_spend(&asset_id, &0).unwrap();
+    assert_eq!(spend.spender, admin);
+    assert_eq!(spend.recipient, Some(vendor.clone()));
+    assert_eq!(spend.amount, 400);
+    assert_eq!(
+        funding_client
+            .get_petty_spend(&asset_id, &1)
+            .unwrap()
+            .recipient,
+        None
+    );
+
+    // The cap rolls: spends older than 30 days no longer count
+    env.ledger()
+        .with_mut(|li| li.timestamp += 30 * 24 * 60 * 60);
+    assert_eq!(funding_client.petty_cash_available(&asset_id), 1000);
+    funding_client.petty_cash_spend(&admin, &asset_id, &vendor, &500u128, &memo);
+
+    // A zero threshold switches petty cash off again
+    funding_client.set_petty_cash(&governance, &asset_id, &0u128, &0u128);
+    assert_eq!(funding_client.get_petty_cash(&asset_id), None);
+    assert_eq!(funding_client.petty_cash_available(&asset_id), 0);
+}
+
+#[test]
+fn test_petty_window_moves_to_temporary_storage() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let vendor = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_governance_contract(&admin, &governance);
+    funding_client.set_petty_cash(&governance, &asset_id, &500u128, &1000u128);
+
+    // A window written to persistent storage before it moved still counts
+    let key = DataKey::PettyWindow(asset_id);
+    let now = env.ledger().timestamp();
+    env.as_contract(&funding_client.address, || {
+        env.storage()
+            .persistent()
+            .set(&stored_key(&env, &key), &vec![&env, (now, 300u128)]);
+    });
+    assert_eq!(funding_client.petty_cash_available(&asset_id), 700);
+
+    funding_client.petty_cash_spend(
+        &admin,
+        &asset_id,
+        &vendor,
+        &100u128,
+        &String::from_str(&env, "Locksmith"),
+    );
+    assert_eq!(funding_client.petty_cash_available(&asset_id), 600);
+    env.as_contract(&funding_client.address, || {
+        let stored = stored_key(&env, &key);
+        assert!(!env.storage().persistent().has(&stored));
+        assert!(env.storage().temporary().has(&stored));
+        assert!(env.storage().temporary().get_ttl(&stored) >= PETTY_WINDOW_TTL);
+    });
+}
+
+#[test]
+fn test_migrate_storage_moves_bare_entries_once() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let asset_id = fnft_client.mint(&owner, &100);
+    assert_eq!(
+        funding_client.try_migrate_storage(&admin, &vec![&env], &true),
+        Err(Ok(FundingError::AlreadyInitialized))
+    );
+
+    // A deployment upgraded from before namespacing holds every entry under its
+    // bare key, the admin's included, and reads miss until the admin moves them
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_event_level(&admin, &EventLevel::Minimal);
+    let logged = funding_client.get_admin_log_head().entries;
+    let keys = strip_namespaces(&env, &funding_client.address, &[DataKey::NAMESPACE])
+        .get(DataKey::NAMESPACE)
+        .unwrap();
+    assert!(keys.contains(IntoVal::<Env, Val>::into_val(&DataKey::Admin, &env)));
+    assert_eq!(funding_client.get_asset_sac(&asset_id), None);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        funding_client.try_initialize(&stranger, &stranger),
+        Err(Ok(FundingError::AlreadyInitialized))
+    );
+    assert_eq!(funding_client.event_level(), EventLevel::Full);
+
+    assert_eq!(
+        funding_client.try_migrate_storage(&Address::generate(&env), &keys, &true),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    let mut oversized = keys.clone();
+    while oversized.len() <= crate::storage::MAX_MIGRATE_BATCH {
+        oversized.push_back(DataKey::Admin.into_val(&env));
+    }
+    assert_eq!(
+        funding_client.try_migrate_storage(&admin, &oversized, &true),
+        Err(Ok(FundingError::BatchTooLarge))
+    );
+    assert_eq!(
+        funding_client.migrate_storage(&admin, &keys, &true),
+        keys.len()
+    );
+    assert_eq!(
+        funding_client.get_asset_sac(&asset_id),
+        Some(sac_contract_id)
+    );
+    assert_eq!(funding_client.event_level(), EventLevel::Minimal);
+    assert_eq!(funding_client.get_admin_log_head().entries, logged + 1);
+    env.as_contract(&funding_client.address, || {
+        let stored = stored_key(&env, &DataKey::Admin);
+        assert_eq!(env.storage().instance().get(&stored), Some(admin.clone()));
+        assert!(!env.storage().instance().has(&DataKey::Admin));
+    });
+    assert_eq!(
+        funding_client.try_migrate_storage(&admin, &keys, &true),
+        Err(Ok(FundingError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_earmarked_deposits_reserved_for_their_purpose() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let airline = Address::generate(&env);
+
+    // The fractcore admin minted the asset, so it is the asset manager
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_governance_contract(&admin, &governance);
+    let travel = symbol_short!("travel");
+    let memo = String::from_str(&env, "Flights");
+
+    let general = funding_client.asset_funds(&asset_id);
+    let earmark_id = funding_client.deposit_earmarked(
+        &sponsor,
+        &asset_id,
+        &5000,
+        &travel,
+        &ReleaseCondition::ManagerConfirmation,
+    );
+    assert_eq!(funding_client.earmarked_total(&asset_id), 5000);
+
+    // Only the general balance can be distributed
+    assert_eq!(
+        funding_client.try_distribute_funds(
+            &admin,
+            &asset_id,
+            &(general + 1),
+            &String::from_str(&env, "Too much"),
+        ),
+        Err(Ok(FundingError::InsufficientSacBalance))
+    );
+
+    // Spending waits for the manager to confirm the release
+    assert_eq!(
+        funding_client.try_spend_earmark(&admin, &asset_id, &earmark_id, &airline, &3000, &memo),
+        Err(Ok(FundingError::EarmarkNotReleased))
+    );
+    assert_eq!(
+        funding_client.try_release_earmark(&owner, &asset_id, &earmark_id),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    funding_client.release_earmark(&admin, &asset_id, &earmark_id);
+
+    let airline_before = sac_client.balance(&airline);
+    funding_client.spend_earmark(&admin, &asset_id, &earmark_id, &airline, &3000, &memo);
+    assert_eq!(sac_client.balance(&airline), airline_before + 3000);
+    assert_eq!(
+        funding_client.try_spend_earmark(&admin, &asset_id, &earmark_id, &airline, &2001, &memo),
+        Err(Ok(FundingError::InvalidAmount))
+    );
+    assert_eq!(funding_client.earmarked_total(&asset_id), 2000);
+    assert_eq!(
+        funding_client
+            .get_earmark(&asset_id, &earmark_id)
+            .unwrap()
+            .spent,
+        3000
+    );
+
+    // A poll-approved earmark is released by the governance contract only
+    let second = funding_client.deposit_earmarked(
+        &sponsor,
+        &asset_id,
+        &1000,
+        &travel,
+        &ReleaseCondition::PollApproval,
+    );
+    assert_eq!(
+        funding_client.try_release_earmark(&admin, &asset_id, &second),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    funding_client.release_earmark(&governance, &asset_id, &second);
+    assert!(
+        funding_client
+            .get_earmark(&asset_id, &second)
+            .unwrap()
+            .released
+    );
+
+    let totals = funding_client.get_purpose_totals(&asset_id, &travel);
+    assert_eq!(totals.deposited, 6000);
+    assert_eq!(totals.spent, 3000);
+    assert_eq!(
+        funding_client.try_release_earmark(&admin, &asset_id, &7),
+        Err(Ok(FundingError::EarmarkNotFound))
+    );
+}
+
+/// Events of the last invocation whose first topic is `name`
+#[test]
+fn test_emergency_withdraw_timelock_and_veto() {
+    let (env, admin, fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let governance = register_governance(&env, &admin, &fnft_contract_id);
+    let reason = String::from_str(&env, "Custodian breach");
+
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    funding_client.set_governance_contract(&admin, &governance);
+
+    assert_eq!(
+        funding_client.try_announce_emergency_withdraw(&owner, &asset_id, &500, &reason),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    let first = funding_client.announce_emergency_withdraw(&admin, &asset_id, &500, &reason);
+    let second = funding_client.announce_emergency_withdraw(&admin, &asset_id, &700, &reason);
+    assert_eq!(funding_client.get_emergency_withdrawal_count(&asset_id), 2);
+
+    // Nothing can move while the timelock runs
+    assert_eq!(
+        funding_client.try_emergency_withdraw(&admin, &asset_id, &first),
+        Err(Ok(FundingError::TimelockActive))
+    );
+
+    // Holders veto the second one through governance
+    assert_eq!(
+        funding_client.try_veto_emergency_withdraw(&admin, &asset_id, &second),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    funding_client.veto_emergency_withdraw(&governance, &asset_id, &second);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += EMERGENCY_WITHDRAW_DELAY);
+    let admin_before = sac_client.balance(&admin);
+    funding_client.emergency_withdraw(&admin, &asset_id, &first);
+    assert_eq!(sac_client.balance(&admin), admin_before + 500);
+    assert_eq!(
+        funding_client
+            .get_emergency_withdrawal(&asset_id, &first)
+            .unwrap()
+            .status,
+        WithdrawalStatus::Executed
+    );
+
+    assert_eq!(
+        funding_client.try_emergency_withdraw(&admin, &asset_id, &second),
+        Err(Ok(FundingError::WithdrawalNotPending))
+    );
+    assert_eq!(
+        funding_client.try_emergency_withdraw(&admin, &asset_id, &first),
+        Err(Ok(FundingError::WithdrawalNotPending))
+    );
+    assert_eq!(
+        funding_client.try_emergency_withdraw(&admin, &asset_id, &9),
+        Err(Ok(FundingError::WithdrawalNotFound))
+    );
+}
+
+fn count_events(env: &Env, name: Symbol) -> u32 {
+    let name: Val = name.into_val(env);
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        if topics.get(0).map(|topic| topic.get_payload()) == Some(name.get_payload()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_event_level_controls_distribution_events() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&owner1, &600);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &400);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+    funding_client.set_protocol_fee(&admin, &treasury, &100u32, &false);
+    let description = String::from_str(&env, "Quarterly dividend");
+
+    assert_eq!(funding_client.event_level(), EventLevel::Full);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(count_events(&env, symbol_short!("received")), 2);
+    assert_eq!(count_events(&env, symbol_short!("fee")), 1);
+    assert_eq!(count_events(&env, symbol_short!("distrib")), 1);
+
+    assert_eq!(
+        funding_client.try_set_event_level(&owner1, &EventLevel::Summary),
+        Err(Ok(FundingError::Unauthorized))
+    );
+    funding_client.set_event_level(&admin, &EventLevel::Summary);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(count_events(&env, symbol_short!("received")), 0);
+    assert_eq!(count_events(&env, symbol_short!("fee")), 1);
+    assert_eq!(count_events(&env, symbol_short!("distrib")), 1);
+
+    funding_client.set_event_level(&admin, &EventLevel::Minimal);
+    funding_client.distribute_funds(&admin, &asset_id, &1000u128, &description);
+    assert_eq!(count_events(&env, symbol_short!("fee")), 0);
+    assert_eq!(count_events(&env, symbol_short!("distrib")), 1);
+    assert_eq!(funding_client.get_distribution_count(&asset_id), 3);
+}
+
+#[test]
+fn test_ledger_entries_build_a_trial_balance() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let reason = String::from_str(&env, "Custodian breach");
+
+    let asset_id = fnft_client.mint(&owner1, &600);
+    fnft_client.transfer(&owner1, &owner2, &asset_id, &400);
+    funding_client.register_asset_sac(&owner1, &asset_id, &sac_contract_id);
+    funding_client.set_protocol_fee(&admin, &treasury, &200, &true);
+    let sac_before = sac_client.balance(&sac_contract_id);
+
+    funding_client.deposit_funds(&depositor, &asset_id, &5000);
+    assert_eq!(count_events(&env, symbol_short!("ledger")), 1);
+
+    // Fee, one payout per holder, and the dust carried back into the SAC
+    funding_client.distribute_funds(
+        &admin,
+        &asset_id,
+        &1000u128,
+        &String::from_str(&env, "Rent"),
+    );
+    assert_eq!(count_events(&env, symbol_short!("ledger")), 5);
+    let payout = LedgerEntry {
+        account: LedgerAccount::Distributions,
+        direction: EntryDirection::Credit,
+        amount: 653,
+        counterparty: Some(owner2.clone()),
+        reference: Some(0),
+    };
+    assert!(env.events().all().iter().any(|(_, topics, data)| {
+        topics
+            == vec![
+                &env,
+                symbol_short!("ledger").into_val(&env),
+                asset_id.into_val(&env),
+                LedgerAccount::Distributions.into_val(&env),
+            ]
+            && LedgerEntry::try_from_val(&env, &data).ok() == Some(payout.clone())
+    }));
+
+    let id = funding_client.announce_emergency_withdraw(&admin, &asset_id, &500, &reason);
+    env.ledger()
+        .with_mut(|li| li.timestamp += EMERGENCY_WITHDRAW_DELAY);
+    funding_client.emergency_withdraw(&admin, &asset_id, &id);
+
+    let totals = funding_client.get_ledger_totals(&asset_id);
+    let find = |account: LedgerAccount| {
+        let entry = totals.iter().find(|t| t.account == account).unwrap();
+        (entry.debits, entry.credits)
+    };
+    assert_eq!(find(LedgerAccount::Deposits), (5000, 0));
+    assert_eq!(find(LedgerAccount::Fees), (0, 20));
+    assert_eq!(find(LedgerAccount::Distributions), (0, 980));
+    assert_eq!(find(LedgerAccount::Dust), (1, 0));
+    assert_eq!(find(LedgerAccount::EmergencyWithdrawals), (0, 500));
+    assert_eq!(find(LedgerAccount::PettyCash), (0, 0));
+
+    // Debits minus credits over every account is the change in the SAC balance
+    let net: i128 = totals
+        .iter()
+        .map(|t| t.debits as i128 - t.credits as i128)
+        .sum();
+    assert_eq!(sac_client.balance(&sac_contract_id) - sac_before, net);
+}
+
+#[test]
+fn test_distribution_cursor_stops_at_walk_start() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let description = String::from_str(&env, "Rent");
+
+    let asset_id = fnft_client.mint(&owner, &100);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    for amount in [100u128, 200, 300] {
+        funding_client.distribute_funds(&admin, &asset_id, &amount, &description);
+    }
+
+    let (first, cursor) = funding_client.get_distributions_page(&asset_id, &None, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(1).unwrap().0, 1);
+    assert_eq!(first.get(1).unwrap().1.requested, 200);
+
+    // A distribution executed mid-walk is left for the next walk
+    funding_client.distribute_funds(&admin, &asset_id, &400u128, &description);
+    let (rest, cursor) = funding_client.get_distributions_page(&asset_id, &cursor, &2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().1.requested, 300);
+    assert_eq!(cursor, None);
+    assert_eq!(
+        funding_client
+            .get_distributions_page(&asset_id, &None, &0)
+            .0
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn test_holder_payouts_listed_per_holder() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, _sac_client) =
+        setup();
+    let owner = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let description = String::from_str(&env, "Rent");
+
+    let asset_id = fnft_client.mint(&owner, &100);
+    fnft_client.transfer(&owner, &holder, &asset_id, &25);
+    funding_client.register_asset_sac(&owner, &asset_id, &sac_contract_id);
+    for (at, amount) in [(10u64, 400u128), (20, 800), (30, 1200)] {
+        env.ledger().set_timestamp(at);
+        funding_client.distribute_funds(&admin, &asset_id, &amount, &description);
+    }
+
+    let (first, cursor) = funding_client.get_holder_payouts_page(&holder, &None, &2);
+    assert_eq!(first.len(), 2);
+    let second = first.get(1).unwrap();
+    assert_eq!(second.asset_id, asset_id);
+    assert_eq!(second.distribution_id, 1);
+    assert_eq!((second.amount, second.timestamp), (200, 20));
+
+    let (rest, cursor) = funding_client.get_holder_payouts_page(&holder, &cursor, &2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().amount, 300);
+    assert_eq!(cursor, None);
+    assert_eq!(
+        funding_client
+            .get_holder_payouts_page(&owner, &None, &0)
+            .0
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_tips_rank_supporters_and_issue_receipts() {
+    let (env, _admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let team_owner = Address::generate(&env);
+    let fan = Address::generate(&env);
+    let asset_id = fnft_client.mint(&team_owner, &100);
+
+    let message = String::from_str(&env, "Go team!");
+    assert_eq!(
+        funding_client.try_tip(&fan, &asset_id, &100, &message),
+        Err(Ok(FundingError::SacNotRegistered))
+    );
+    funding_client.register_asset_sac(&team_owner, &asset_id, &sac_contract_id);
+    sac_client.mint(&fan, &1000i128);
+    assert_eq!(
+        funding_client.try_tip(&fan, &asset_id, &0, &message),
+        Err(Ok(FundingError::InvalidAmount))
+    );
+
+    assert_eq!(funding_client.tip(&fan, &asset_id, &100, &message), 0);
+    let hash: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, b"Go team!"))
+        .into();
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("tip"), asset_id, fan.clone()).into_val(&env)
+    );
+    assert_eq!(
+        <(u32, i128, BytesN<32>)>::try_from_val(&env, &data).unwrap(),
+        (0, 100, hash.clone())
+    );
+
+    let receipt = funding_client.get_tip_receipt(&asset_id, &0).unwrap();
+    assert_eq!((receipt.tipper, receipt.amount), (fan.clone(), 100));
+    assert_eq!(receipt.message_hash, hash);
+
+    funding_client.tip(&fan, &asset_id, &50, &String::from_str(&env, ""));
+    assert_eq!(funding_client.get_tip_count(&asset_id), 2);
+    assert_eq!(
+        funding_client.get_supporter(&asset_id, &fan),
+        Some(SupporterTotals {
+            supporter: fan.clone(),
+            total: 150,
+            tips: 2,
+            last_tip_at: env.ledger().timestamp(),
+        })
+    );
+
+    // The leaderboard keeps the largest cumulative totals, largest first
+    for amount in 1..=MAX_TOP_SUPPORTERS as i128 {
+        let other = Address::generate(&env);
+        sac_client.mint(&other, &1000i128);
+        funding_client.tip(&other, &asset_id, &(amount * 20), &message);
+    }
+    let top = funding_client.top_supporters(&asset_id);
+    assert_eq!(top.len(), MAX_TOP_SUPPORTERS);
+    assert_eq!(top.get(0).unwrap().total, 200);
+    assert_eq!(top.get(3).unwrap().supporter, fan);
+    assert_eq!(top.last().unwrap().total, 40);
+
+    // Tips are revenue: they count as deposits in the ledger
+    let deposits = funding_client
+        .get_ledger_totals(&asset_id)
+        .iter()
+        .find(|totals| totals.account == LedgerAccount::Deposits)
+        .unwrap();
+    assert_eq!(deposits.debits, 150 + 20 * 55);
+}
+
+#[test]
+fn test_batched_distribution_follows_plan() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let issuer = Address::generate(&env);
+    let description = String::from_str(&env, "Rent");
+
+    // 120 owners fill three owner pages of 50
+    let asset_id = fnft_client.mint(&issuer, &10_000);
+    let mut holders = vec![&env];
+    for _ in 0..119 {
+        let holder = Address::generate(&env);
+        fnft_client.transfer(&issuer, &holder, &asset_id, &10);
+        holders.push_back(holder);
+    }
+    funding_client.register_asset_sac(&issuer, &asset_id, &sac_contract_id);
+
+    let plan = funding_client.plan_distribution(&asset_id, &12_000u128);
+    assert_eq!(plan.recipients, 120);
+    assert_eq!(plan.batch_pages, DEFAULT_BATCH_PAGES);
+    assert_eq!(plan.batches.len(), 2);
+
+    funding_client.set_distribution_batch_pages(&admin, &1);
+    let plan = funding_client.plan_distribution(&asset_id, &12_000u128);
+    assert_eq!(plan.batches.len(), 3);
+    let last = plan.batches.last().unwrap();
+    assert_eq!((last.first_page, last.pages, last.owners), (2, 1, 20));
+
+    // A plan made before the owners changed is refused
+    let late = Address::generate(&env);
+    fnft_client.transfer(&issuer, &late, &asset_id, &10);
+    assert_eq!(
+        funding_client.try_start_batched_distribution(
+            &admin,
+            &asset_id,
+            &12_000u128,
+            &description,
+            &plan
+        ),
+        Err(Ok(FundingError::DistributionPlanOutdated))
+    );
+    fnft_client.transfer(&late, &issuer, &asset_id, &10);
+    let plan = funding_client.plan_distribution(&asset_id, &12_000u128);
+
+    let holder = holders.get(0).unwrap();
+    let before = sac_client.balance(&holder);
+    let distribution_id = funding_client.start_batched_distribution(
+        &admin,
+        &asset_id,
+        &12_000u128,
+        &description,
+        &plan,
+    );
+    assert_eq!(distribution_id, 0);
+
+    // The unpaid amount stays reserved and batches run in plan order
+    assert_eq!(
+        funding_client.try_distribute_funds(&admin, &asset_id, &100u128, &description),
+        Err(Ok(FundingError::DistributionInProgress))
+    );
+    assert_eq!(
+        funding_client.try_run_distribution_batch(&asset_id, &1),
+        Err(Ok(FundingError::DistributionPlanOutdated))
+    );
+
+    assert_eq!(funding_client.run_distribution_batch(&asset_id, &0), None);
+    assert_eq!(funding_client.run_distribution_batch(&asset_id, &1), None);
+    let outcome = funding_client
+        .run_distribution_batch(&asset_id, &2)
+        .unwrap();
+    assert_eq!(outcome.distributed, 12_000);
+    assert_eq!(outcome.recipients, 120);
+    assert_eq!(sac_client.balance(&holder) - before, 12);
+    assert_eq!(funding_client.get_batched_distribution(&asset_id), None);
+    assert_eq!(funding_client.total_distributed(&asset_id), 12_000);
+    assert_eq!(
+        funding_client
+            .get_distribution(&asset_id, &0)
+            .unwrap()
+            .recipients,
+        120
+    );
+
+    // Cancelling records what the batches paid and frees the rest
+    let plan = funding_client.plan_distribution(&asset_id, &1_000u128);
+    funding_client.start_batched_distribution(&admin, &asset_id, &1_000u128, &description, &plan);
+    funding_client.run_distribution_batch(&asset_id, &0);
+    let cancelled = funding_client.cancel_batched_distribution(&admin, &asset_id);
+    assert_eq!(cancelled.distribution_id, 1);
+    assert_eq!(cancelled.recipients, 50);
+    assert_eq!(cancelled.distributed + cancelled.dust, 1_000);
+    assert_eq!(
+        funding_client.try_run_distribution_batch(&asset_id, &1),
+        Err(Ok(FundingError::NoDistributionInProgress))
+    );
+
+    // Paying in one call reads no more owner pages than one batch pays
+    assert_eq!(
+        funding_client.try_distribute_funds(&admin, &asset_id, &100u128, &description),
+        Err(Ok(FundingError::DistributionTooLarge))
+    );
+    assert_eq!(
+        funding_client.try_preview_distribution(&asset_id, &100u128, &0u128),
+        Err(Ok(FundingError::DistributionTooLarge))
+    );
+    funding_client.set_distribution_batch_pages(&admin, &3);
+    funding_client.distribute_funds(&admin, &asset_id, &100u128, &description);
+}
+
+#[test]
+fn test_batched_distribution_pays_fractions_moved_between_batches_once() {
+    let (env, admin, _fnft_contract_id, sac_contract_id, funding_client, fnft_client, sac_client) =
+        setup();
+    let issuer = Address::generate(&env);
+    let description = String::from_str(&env, "Rent");
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    // 120 owners over three owner pages, paid one page per batch
+    let asset_id = fnft_client.mint(&issuer, &10_000);
+    let mut holders = vec![&env];
+    for _ in 0..119 {
+        let holder = Address::generate(&env);
+        fnft_client.transfer(&issuer, &holder, &asset_id, &10);
+        holders.push_back(holder);
+    }
+    funding_client.register_asset_sac(&issuer, &asset_id, &sac_contract_id);
+    funding_client.set_distribution_batch_pages(&admin, &1);
+
+    let plan = funding_client.plan_distribution(&asset_id, &12_000u128);
+    funding_client.start_batched_distribution(&admin, &asset_id, &12_000u128, &description, &plan);
+    funding_client.run_distribution_batch(&asset_id, &0);
+
+    // A holder paid in the first batch moves fractions to one paid in the last
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    let sender = holders.get(0).unwrap();
+    let receiver = holders.get(110).unwrap();
+    fnft_client.transfer(&sender, &receiver, &asset_id, &5);
+    let last = holders.get(118).unwrap();
+    let before = (sac_client.balance(&receiver), sac_client.balance(&last));
+
+    funding_client.run_distribution_batch(&asset_id, &1);
+    let outcome = funding_client
+        .run_distribution_batch(&asset_id, &2)
+        .unwrap();
+
+    // The receiver is paid for what it held at the start, and nobody is shorted
+    assert_eq!(sac_client.balance(&receiver) - before.0, 12);
+    assert_eq!(sac_client.balance(&last) - before.1, 12);
+    assert_eq!(outcome.distributed, 12_000);
+    assert_eq!(outcome.recipients, 120);
+    assert_eq!(outcome.dust, 0);
+}
@@ -1,8 +1,12 @@
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, Map, String, Vec,
+    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, Map, String,
+    Val, Vec,
 };
 
-use crate::methods::{admin, polls, queries, utils, voting};
+use crate::methods::{
+    actions, activity, admin, admin_log, keeper, migration, polls, queries, quorum, relay,
+    templates, utils, voting,
+};
 use crate::storage;
 
 #[contracterror]
@@ -23,14 +27,58 @@ pub enum GovernanceError {
     InvalidDuration = 12,
     CannotExecuteYet = 13,
     CrossContractCallFailed = 14,
+    CertificateNotFound = 15,
+    ReceiptNotFound = 16,
+    ActionNotAllowed = 17,
+    TransferLockNotPermitted = 18,
+    ExecutionRecordNotFound = 19,
+    TemplateNotFound = 20,
+    UnsupportedAction = 21,
+    ImportMismatch = 22,
+    BatchTooLarge = 23,
 }
 
+/// What an approved poll executes.
+///
+/// Stored polls and templates decode their action by variant name, so a variant
+/// is never renamed, removed or given other fields; new actions are appended.
+/// `Reserved` is where a later version puts the actions it adds: polls it
+/// writes as `Reserved` with the action's kind and XDR-encoded arguments still
+/// decode here after a rollback, while a poll stored under a new variant does
+/// not. `Reserved` polls can neither be created nor executed here.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum PollAction {
     NoExecution,
     DistributeFunds(u128, String),
     TransferTokens(Address, u64),
+    SplitAsset(u64), // Split factor; governance must be the fractcore asset manager
+    PostAnnouncement(String, BytesN<32>, String), // Title, content hash, URI
+    SetAllowedActions(u32), // Meta-poll replacing the asset's action allowlist
+    SetPettyCash(u128, u128), // Funding petty cash threshold and rolling monthly cap
+    RevertAssetUri(u32), // Restore a fractcore URI version; governance must be the asset manager
+    ReleaseEarmark(u32), // Release a funding earmark deposited with the PollApproval condition
+    VetoEmergencyWithdraw(u32), // Block an admin emergency withdrawal still in its funding timelock
+    SetTradingConfig(u32, Address, u64, u64), // Trading fee bps, fee recipient, min and max sale duration; platform asset only
+    UpgradeContract(Address, BytesN<32>), // Fractcore, funding or trading contract and uploaded WASM hash; platform asset only
+    SetExitQueue(u64, u64, u64), // Trading exit queue size threshold, tranche and window seconds; tranche 0 removes it
+    Reserved(u32, Bytes),        // Action kind of a later version and its XDR-encoded arguments
+}
+
+/// Supply an asset's quorum is measured against
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum QuorumMode {
+    TotalSupply,                // Every fraction in circulation
+    ActiveSupply(Vec<Address>), // Minus these holders (treasury, escrow, dead wallets)
+}
+
+/// Holders whose transfers a locked poll freezes until it is finalized
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TransferLockScope {
+    Voters,     // Each holder from the moment they vote
+    AllHolders, // Every holder from poll creation
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -48,6 +96,67 @@ pub struct Poll {
     pub is_active: bool,
     pub votes: Map<Address, Vote>,
     pub total_voters: u32,
+    pub weights: Vec<VoteWeight>, // Empty: one vote per fraction of asset_id
+}
+
+/// A poll as stored before weighted voting added `weights` (record version 1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollV1 {
+    pub id: u32,
+    pub asset_id: u64,
+    pub creator: Address,
+    pub title: String,
+    pub description: String,
+    pub options: Vec<String>,
+    pub action: PollAction,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub is_active: bool,
+    pub votes: Map<Address, Vote>,
+    pub total_voters: u32,
+}
+
+impl PollV1 {
+    /// Current shape of the poll: one vote per fraction of asset_id
+    pub fn upgrade(self, env: &Env) -> Poll {
+        Poll {
+            id: self.id,
+            asset_id: self.asset_id,
+            creator: self.creator,
+            title: self.title,
+            description: self.description,
+            options: self.options,
+            action: self.action,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            is_active: self.is_active,
+            votes: self.votes,
+            total_voters: self.total_voters,
+            weights: Vec::new(env),
+        }
+    }
+}
+
+/// Reusable settings for a decision an asset takes again and again
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollTemplate {
+    pub asset_id: u64,
+    pub creator: Address,
+    pub title_pattern: String, // The first "{n}" becomes the number of the poll made from it
+    pub description: String,
+    pub action: PollAction,
+    pub duration_days: u32,
+    pub uses: u32, // Polls created from the template so far
+}
+
+/// Voting weight of one share class in a weighted poll
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VoteWeight {
+    pub asset_id: u64,
+    pub multiplier: u32,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -82,14 +191,304 @@ pub struct VoteResults {
 pub struct ExecutionResult {
     pub should_execute: bool,
     pub approval_percentage: u32,
+    pub participation_percentage: u32, // Of total_supply
+    pub active_participation: u32,     // Of active_supply, the quorum base
+    pub total_supply: u64,
+    pub active_supply: u64, // Equals total_supply unless the asset uses ActiveSupply
+}
+
+/// Outcome a poll would reach if the simulated holders voted as given
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SimulatedOutcome {
+    pub poll_id: u32,
+    pub vote_counts: Vec<u64>, // [0] = Deny, [1] = Approve, simulated votes included
+    pub total_voters: u32,
+    pub winning_option: u32,
+    pub execution: ExecutionResult,
+    pub approve_power_needed: Option<u64>, // Further Approve power to execute; None if no amount suffices
+}
+
+/// Trading contract whose platform parameters the platform asset's holders vote on
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PlatformGovernance {
+    pub trading_contract: Address, // Must name this contract as its governor
+    pub asset_id: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct KeeperConfig {
+    pub token: Address,
+    pub bounty: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RelayConfig {
+    pub token: Address,
+    pub fee: i128, // Paid to the relayer per relayed vote
+}
+
+/// Outcome of one item in `vote_batch` or `execute_polls`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BatchItemResult {
+    pub poll_id: u32,
+    pub success: bool,
+    pub executed: bool,          // Poll action ran as a result of this item
+    pub error_code: Option<u32>, // GovernanceError code when the item failed
+}
+
+/// Fractcore figures cached for a poll so execution avoids repeated cross-contract reads
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollSnapshot {
+    pub total_supply: u64,  // Voting supply, weighted across classes
+    pub active_supply: u64, // Quorum base: total_supply minus excluded holders
+    pub owner_count: u32,
+    pub ledger: u32, // Ledger the figures were read at
+    pub captured_at: u64,
+}
+
+/// Immutable record of a finalized poll for off-chain consumers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollCertificate {
+    pub poll_id: u32,
+    pub asset_id: u64,
+    pub snapshot_ledger: u32, // Ledger whose balances and supply the outcome was computed from
+    pub total_supply: u64,
+    pub owner_count: u32,
+    pub vote_counts: Vec<u64>, // [0] = Deny, [1] = Approve
+    pub total_voters: u32,
+    pub winning_option: u32,
+    pub approval_percentage: u32,
     pub participation_percentage: u32,
+    pub executed: bool,
+    pub action_hash: Option<BytesN<32>>, // SHA-256 of the executed action's XDR
+    pub finalized_at: u64,
+}
+
+/// How an executed poll's action turned out
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ExecutionStatus {
+    Executed,
+    ExecutedWithWarnings, // Ran, but the result strayed from what was approved
+}
+
+/// Funding's report of the distribution a DistributeFunds poll ran
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionOutcome {
+    pub distribution_id: u32, // Index in funding's get_distribution
+    pub requested: u128,
+    pub fee: u128,
+    pub distributed: u128, // Paid to holders
+    pub recipients: u32,
+    pub dust: u128, // Rounding remainder left in the asset SAC
+}
+
+/// What an executed action reported back
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ActionOutput {
+    None,
+    Distribution(DistributionOutcome), // DistributeFunds polls
+}
+
+/// Result of running an approved poll's action
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ExecutionRecord {
+    pub poll_id: u32,
+    pub status: ExecutionStatus,
+    pub output: ActionOutput,
+    pub executed_at: u64,
+}
+
+/// Governance activity of one asset, kept up to date on poll creation and
+/// finalization so dashboards need not walk the asset's polls
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AssetGovernanceSummary {
+    pub asset_id: u64,
+    pub total_polls: u32,
+    pub active_polls: u32,
+    pub executed_polls: u32,
+    pub rejected_polls: u32,
+    pub last_poll_at: u64,     // 0 if no poll was created
+    pub last_executed_at: u64, // 0 if no poll was executed
+}
+
+/// Turnout history of one asset, updated as its polls are finalized
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GovernanceStats {
+    pub asset_id: u64,
+    pub finalized_polls: u32,
+    pub passed_polls: u32,               // Executed
+    pub rejected_polls: u32,             // Quorum met but not approved
+    pub expired_polls: u32,              // Ended short of quorum
+    pub participation_total: u64,        // Sum of the finalized polls' participation percentages
+    pub average_participation: u32,      // Percentage of total supply, rounded down
+    pub voter_buckets: Vec<VoterBucket>, // Oldest first, at most MAX_STATS_BUCKETS
+}
+
+/// Finalized polls and distinct voters of an asset over one STATS_BUCKET_SECONDS
+/// window, by finalization time
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VoterBucket {
+    pub start: u64,
+    pub polls: u32,
+    pub unique_voters: u32,
+    pub participation_total: u64,
+}
+
+pub use ::admin_log::{AdminLogEntry, AdminLogHead};
+pub use paging::Cursor;
+
+/// Entry of the platform-wide list of recently executed polls
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ExecutedPoll {
+    pub poll_id: u32,
+    pub asset_id: u64,
+    pub executed_at: u64,
 }
 
+/// Proof of a single vote, kept outside the poll so it can be read on its own
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VoteReceipt {
+    pub poll_id: u32,
+    pub voter: Address,
+    pub option_index: u32,
+    pub voting_power: u64,
+    pub timestamp: u64,
+}
+
+/// A poll as exported for another governance instance; its votes travel in
+/// `poll.votes`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollExport {
+    pub poll: Poll,
+    pub created_ledger: u32, // 0 for polls created before it was recorded
+    pub checkpoint_interval: Option<u64>,
+}
+
+/// One page of `export_polls`: up to POLL_EXPORT_PAGE_SIZE polls in id order,
+/// with the records kept beside them keyed by poll id
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PollExportPage {
+    pub first_poll_id: u32,
+    pub poll_count: u32, // Polls the exporting instance held in all
+    pub polls: Vec<PollExport>,
+    pub snapshots: Map<u32, PollSnapshot>,
+    pub transfer_locks: Map<u32, TransferLockScope>,
+    pub certificates: Map<u32, PollCertificate>,
+    pub executions: Map<u32, ExecutionRecord>,
+}
+
+/// Shape of the polls this version writes, stored alongside each new poll.
+/// Polls without a stored version predate it and are decoded by shape.
+pub const POLL_RECORD_VERSION: u32 = 2;
+
+/// Voters per storage page, and the most `get_voters_for_option` returns per call
+pub const VOTERS_PAGE_SIZE: u32 = 50;
+
+/// Active poll ids per storage page
+pub const ACTIVE_POLLS_PAGE_SIZE: u32 = 50;
+
+/// Pages of active polls `get_active_polls` returns; larger sets are read with
+/// `get_active_polls_page`
+pub const MAX_ACTIVE_POLLS_PAGES: u32 = 4;
+
+/// Polls per page of `export_polls`
+pub const POLL_EXPORT_PAGE_SIZE: u32 = 10;
+
+/// Asset ids per storage page of the governed and active asset lists
+pub const ASSET_REGISTRY_PAGE_SIZE: u32 = 50;
+
+/// Executed polls kept in the recently executed list, oldest dropped first
+pub const MAX_RECENT_EXECUTIONS: u32 = 50;
+
+/// Width of the time buckets unique voters are counted in (30 days)
+pub const STATS_BUCKET_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Voter buckets kept per asset, oldest dropped first
+pub const MAX_STATS_BUCKETS: u32 = 12;
+
+/// Executed polls `recently_executed` returns per page
+pub const RECENT_EXECUTIONS_PAGE_SIZE: u32 = 10;
+
+/// Upper bound on items per batch call so one transaction stays within resource limits
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+/// Relayed votes one relayer is reimbursed for per day; later ones in the day
+/// are still cast, unpaid
+pub const MAX_RELAYER_PAYOUTS_PER_DAY: u32 = 20;
+
+/// Upper bound on share classes contributing to a weighted poll
+pub const MAX_VOTE_CLASSES: u32 = 5;
+
+/// Upper bound on poll templates kept per asset
+pub const MAX_TEMPLATES_PER_ASSET: u32 = 10;
+
+/// Longest title pattern a template accepts, in bytes
+pub const MAX_TEMPLATE_TITLE_LEN: u32 = 64;
+
+/// Upper bound on addresses excluded from an asset's active supply
+pub const MAX_QUORUM_EXCLUSIONS: u32 = 10;
+
+/// Shortfall between an approved distribution and what holders plus the protocol
+/// fee received, in basis points, before the poll is marked ExecutedWithWarnings
+pub const DISTRIBUTION_TOLERANCE_BPS: u128 = 100;
+
+/// Allowlist bits, one per executable poll action (see `actions::action_bit`)
+pub const ACTION_NO_EXECUTION: u32 = 1;
+pub const ACTION_DISTRIBUTE_FUNDS: u32 = 1 << 1;
+pub const ACTION_TRANSFER_TOKENS: u32 = 1 << 2;
+pub const ACTION_SPLIT_ASSET: u32 = 1 << 3;
+pub const ACTION_POST_ANNOUNCEMENT: u32 = 1 << 4;
+pub const ACTION_REVERT_ASSET_URI: u32 = 1 << 5;
+pub const ACTION_RELEASE_EARMARK: u32 = 1 << 6;
+
+/// Allowlist of an asset that has never been restricted
+pub const ALL_POLL_ACTIONS: u32 = (1 << 7) - 1;
+
 #[contract]
 pub struct GovernanceContract;
 
 #[contractimpl]
 impl GovernanceContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        fractcore_contract: Address,
+        funding_contract: Address,
+        default_threshold: u32,
+        default_quorum: u32,
+        default_expiry_days: u32,
+    ) -> Result<(), GovernanceError> {
+        admin::initialize(
+            &env,
+            &admin,
+            &fractcore_contract,
+            &funding_contract,
+            default_threshold,
+            default_quorum,
+            default_expiry_days,
+        )
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -130,6 +529,80 @@ impl GovernanceContract {
         )
     }
 
+    /// Create a poll where each share class votes with its own multiplier
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_weighted_poll(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        title: String,
+        description: String,
+        action: PollAction,
+        duration_days: Option<u32>,
+        weights: Vec<VoteWeight>,
+    ) -> Result<u32, GovernanceError> {
+        polls::create_weighted_poll(
+            &env,
+            &caller,
+            asset_id,
+            &title,
+            &description,
+            &action,
+            duration_days,
+            &weights,
+        )
+    }
+
+    /// Create a poll that freezes transfers of the asset until it is finalized, against
+    /// vote buying. Governance must be the asset's transfer lock authority in fractcore.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_locked_poll(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        title: String,
+        description: String,
+        action: PollAction,
+        duration_days: Option<u32>,
+        scope: TransferLockScope,
+    ) -> Result<u32, GovernanceError> {
+        polls::create_locked_poll(
+            &env,
+            &caller,
+            asset_id,
+            &title,
+            &description,
+            &action,
+            duration_days,
+            scope,
+        )
+    }
+
+    /// Create a poll that re-snapshots balances every `checkpoint_interval_days`;
+    /// voting power is the lower of the balance at creation and at the latest checkpoint
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_checkpointed_poll(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        title: String,
+        description: String,
+        action: PollAction,
+        duration_days: Option<u32>,
+        checkpoint_interval_days: u32,
+    ) -> Result<u32, GovernanceError> {
+        polls::create_checkpointed_poll(
+            &env,
+            &caller,
+            asset_id,
+            &title,
+            &description,
+            &action,
+            duration_days,
+            checkpoint_interval_days,
+        )
+    }
+
     pub fn vote(
         env: Env,
         voter: Address,
@@ -139,6 +612,15 @@ impl GovernanceContract {
         voting::vote(&env, &voter, poll_id, option_index)
     }
 
+    /// Cast several votes in one transaction; failed items do not abort the batch
+    pub fn vote_batch(
+        env: Env,
+        voter: Address,
+        votes: Vec<(u32, u32)>,
+    ) -> Result<Vec<BatchItemResult>, GovernanceError> {
+        voting::vote_batch(&env, &voter, &votes)
+    }
+
     /// Update governance parameters (admin only)
     pub fn update_governance_params(
         env: Env,
@@ -161,6 +643,14 @@ impl GovernanceContract {
         polls::check_and_execute_poll(&env, poll_id)
     }
 
+    /// Check and execute several polls; failed items do not abort the batch
+    pub fn execute_polls(
+        env: Env,
+        poll_ids: Vec<u32>,
+    ) -> Result<Vec<BatchItemResult>, GovernanceError> {
+        polls::execute_polls(&env, &poll_ids)
+    }
+
     /// Admin function to update governance parameters
     pub fn set_governance_params(
         env: Env,
@@ -170,30 +660,442 @@ impl GovernanceContract {
         admin::set_governance_params(&env, &admin, &new_params)
     }
 
+    /// Finalize up to `limit` expired polls, paying the keeper a bounty per poll
+    pub fn finalize_expired_polls(
+        env: Env,
+        keeper: Address,
+        limit: u32,
+    ) -> Result<u32, GovernanceError> {
+        keeper::finalize_expired_polls(&env, &keeper, limit)
+    }
+
+    /// Configure the keeper bounty token and amount (admin only)
+    pub fn set_keeper_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        bounty: i128,
+    ) -> Result<(), GovernanceError> {
+        keeper::set_keeper_config(&env, &admin, &token, bounty)
+    }
+
+    /// Registry contract whose keeper schedule lists poll expiries, or None to stop (admin only)
+    pub fn set_schedule_registry(
+        env: Env,
+        admin: Address,
+        registry: Option<Address>,
+    ) -> Result<(), GovernanceError> {
+        keeper::set_schedule_registry(&env, &admin, &registry)
+    }
+
+    /// One page of every poll this instance holds, with its votes and per-poll
+    /// state, for moving governance to a new deployment (admin only)
+    pub fn export_polls(
+        env: Env,
+        admin: Address,
+        page: u32,
+    ) -> Result<PollExportPage, GovernanceError> {
+        migration::export_polls(&env, &admin, page)
+    }
+
+    /// Recreate an exported page under the same poll ids (admin only). Pages go
+    /// in order into an instance that has created no polls of its own; returns
+    /// the number of polls imported.
+    pub fn import_polls(
+        env: Env,
+        admin: Address,
+        data: PollExportPage,
+    ) -> Result<u32, GovernanceError> {
+        migration::import_polls(&env, &admin, &data)
+    }
+
+    /// Move entries written before namespacing under their namespace, in
+    /// batches; `last` closes the migration (admin only)
+    pub fn migrate_storage(
+        env: Env,
+        admin: Address,
+        keys: Vec<Val>,
+        last: bool,
+    ) -> Result<u32, GovernanceError> {
+        admin::migrate_storage(&env, &admin, &keys, last)
+    }
+
+    pub fn get_schedule_registry(env: Env) -> Option<Address> {
+        storage::get_schedule_registry(&env)
+    }
+
+    /// Deposit bounty tokens into the keeper pool
+    pub fn fund_keeper_pool(
+        env: Env,
+        funder: Address,
+        amount: i128,
+    ) -> Result<(), GovernanceError> {
+        keeper::fund_keeper_pool(&env, &funder, amount)
+    }
+
+    /// Cast a vote the voter signed off-chain; the relayer signs too, pays the
+    /// transaction fee and is reimbursed from the relay pool
+    pub fn relay_vote(
+        env: Env,
+        relayer: Address,
+        voter: Address,
+        poll_id: u32,
+        option_index: u32,
+    ) -> Result<(), GovernanceError> {
+        relay::relay_vote(&env, &relayer, &voter, poll_id, option_index)
+    }
+
+    /// Configure the relayer reimbursement token and fee per vote (admin only)
+    pub fn set_relay_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        fee: i128,
+    ) -> Result<(), GovernanceError> {
+        relay::set_relay_config(&env, &admin, &token, fee)
+    }
+
+    /// Deposit fee tokens into the relay pool
+    pub fn fund_relay_pool(env: Env, funder: Address, amount: i128) -> Result<(), GovernanceError> {
+        relay::fund_relay_pool(&env, &funder, amount)
+    }
+
+    /// Restrict which poll actions can be proposed for an asset (fractcore asset manager only)
+    pub fn set_allowed_actions(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        allowed: u32,
+    ) -> Result<(), GovernanceError> {
+        actions::set_allowed_actions(&env, &caller, asset_id, allowed)
+    }
+
+    /// Measure an asset's quorum against total or active supply (fractcore asset manager only)
+    pub fn set_quorum_mode(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        mode: QuorumMode,
+    ) -> Result<(), GovernanceError> {
+        quorum::set_quorum_mode(&env, &caller, asset_id, &mode)
+    }
+
+    pub fn get_quorum_mode(env: Env, asset_id: u64) -> QuorumMode {
+        storage::get_quorum_mode(&env, asset_id)
+    }
+
+    /// Transfer lock a poll was created with, if any
+    pub fn get_poll_transfer_lock(env: Env, poll_id: u32) -> Option<TransferLockScope> {
+        storage::get_poll_transfer_lock(&env, poll_id)
+    }
+
+    /// Designate the platform asset whose holders vote on the trading
+    /// contract's fee and sale duration limits (admin only)
+    pub fn set_platform_governance(
+        env: Env,
+        admin: Address,
+        trading_contract: Address,
+        asset_id: u64,
+    ) -> Result<(), GovernanceError> {
+        actions::set_platform_governance(&env, &admin, &trading_contract, asset_id)
+    }
+
+    pub fn get_platform_governance(env: Env) -> Option<PlatformGovernance> {
+        storage::get_platform_governance(&env)
+    }
+
+    /// Store a reusable poll (title pattern, action, duration) for the asset; returns
+    /// the template ID
+    pub fn create_poll_template(
+        env: Env,
+        caller: Address,
+        asset_id: u64,
+        title_pattern: String,
+        description: String,
+        action: PollAction,
+        duration_days: Option<u32>,
+    ) -> Result<u32, GovernanceError> {
+        templates::create_poll_template(
+            &env,
+            &caller,
+            asset_id,
+            &title_pattern,
+            &description,
+            &action,
+            duration_days,
+        )
+    }
+
+    /// Create a poll from a template with fresh snapshots; `amount_override` replaces
+    /// the amount of a DistributeFunds action
+    pub fn create_poll_from_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+        amount_override: Option<u128>,
+    ) -> Result<u32, GovernanceError> {
+        templates::create_poll_from_template(&env, &caller, template_id, amount_override)
+    }
+
+    /// Delete a template (its creator or the admin)
+    pub fn remove_poll_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+    ) -> Result<(), GovernanceError> {
+        templates::remove_poll_template(&env, &caller, template_id)
+    }
+
+    pub fn get_poll_template(env: Env, template_id: u32) -> Option<PollTemplate> {
+        storage::get_poll_template(&env, template_id)
+    }
+
+    /// Template IDs of an asset, oldest first
+    pub fn get_asset_templates(env: Env, asset_id: u64) -> Vec<u32> {
+        storage::get_asset_templates(&env, asset_id)
+    }
+
+    /// Bitmask of `ACTION_*` flags that polls on the asset may carry
+    pub fn get_allowed_actions(env: Env, asset_id: u64) -> u32 {
+        storage::get_allowed_actions(&env, asset_id)
+    }
+
+    pub fn get_keeper_config(env: Env) -> Option<KeeperConfig> {
+        storage::get_keeper_config(&env)
+    }
+
+    pub fn get_keeper_pool(env: Env) -> i128 {
+        storage::get_keeper_pool(&env)
+    }
+
+    pub fn get_relay_config(env: Env) -> Option<RelayConfig> {
+        storage::get_relay_config(&env)
+    }
+
+    pub fn get_relay_pool(env: Env) -> i128 {
+        storage::get_relay_pool(&env)
+    }
+
     pub fn get_poll(env: Env, poll_id: u32) -> Result<Poll, GovernanceError> {
         queries::get_poll(&env, poll_id)
     }
 
+    /// Up to MAX_BATCH_SIZE polls in one call, `None` for unknown poll ids
+    pub fn get_polls_bulk(
+        env: Env,
+        poll_ids: Vec<u32>,
+    ) -> Result<Vec<Option<Poll>>, GovernanceError> {
+        queries::get_polls_bulk(&env, &poll_ids)
+    }
+
     pub fn get_asset_polls(env: Env, asset_id: u64) -> Vec<u32> {
         queries::get_asset_polls(&env, asset_id)
     }
 
+    /// Up to `limit` poll ids of an asset (capped at MAX_BATCH_SIZE) from `cursor`, with the
+    /// cursor of the next page
+    pub fn get_asset_polls_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<u32>, Option<Cursor>) {
+        queries::get_asset_polls_page(&env, asset_id, cursor, limit)
+    }
+
+    /// Up to MAX_BATCH_SIZE ids of polls created in or after a ledger, oldest first
+    pub fn polls_created_since(env: Env, ledger_seq: u32) -> Vec<u32> {
+        queries::polls_created_since(&env, ledger_seq)
+    }
+
+    /// Active poll ids in the first MAX_ACTIVE_POLLS_PAGES storage pages, in no
+    /// particular order
     pub fn get_active_polls(env: Env) -> Vec<u32> {
         queries::get_active_polls(&env)
     }
 
+    pub fn get_active_poll_count(env: Env) -> u32 {
+        queries::get_active_poll_count(&env)
+    }
+
+    /// One storage page of active poll ids (ACTIVE_POLLS_PAGE_SIZE per page)
+    pub fn get_active_polls_page(env: Env, page: u32) -> Vec<u32> {
+        queries::get_active_polls_page(&env, page)
+    }
+
+    /// One storage page of asset ids with at least one active poll
+    /// (ASSET_REGISTRY_PAGE_SIZE per page), in no particular order
+    pub fn assets_with_active_polls(env: Env, page: u32) -> Vec<u64> {
+        storage::get_active_assets_page(&env, page)
+    }
+
+    pub fn get_active_asset_count(env: Env) -> u32 {
+        storage::get_active_asset_count(&env)
+    }
+
+    /// One storage page of asset ids that ever had a poll or governance settings
+    /// (ASSET_REGISTRY_PAGE_SIZE per page), in the order they were first seen
+    pub fn governed_assets(env: Env, page: u32) -> Vec<u64> {
+        storage::get_governed_assets_page(&env, page)
+    }
+
+    pub fn get_governed_asset_count(env: Env) -> u32 {
+        storage::get_governed_asset_count(&env)
+    }
+
+    /// Up to RECENT_EXECUTIONS_PAGE_SIZE executed polls across all assets, newest
+    /// first; only the last MAX_RECENT_EXECUTIONS executions are kept
+    pub fn recently_executed(env: Env, page: u32) -> Vec<ExecutedPoll> {
+        activity::recently_executed(&env, page)
+    }
+
+    /// Poll counts and latest activity of an asset
+    pub fn get_asset_governance_summary(env: Env, asset_id: u64) -> AssetGovernanceSummary {
+        activity::get_asset_summary(&env, asset_id)
+    }
+
+    /// Turnout history of an asset: poll outcomes, average participation and
+    /// unique voters per STATS_BUCKET_SECONDS window
+    pub fn get_governance_stats(env: Env, asset_id: u64) -> GovernanceStats {
+        activity::get_governance_stats(&env, asset_id)
+    }
+
+    /// Summaries for up to MAX_BATCH_SIZE assets, in the order given
+    pub fn get_asset_governance_summaries(
+        env: Env,
+        asset_ids: Vec<u64>,
+    ) -> Result<Vec<AssetGovernanceSummary>, GovernanceError> {
+        activity::get_asset_summaries(&env, &asset_ids)
+    }
+
     pub fn get_vote_results(env: Env, poll_id: u32) -> Result<VoteResults, GovernanceError> {
         queries::get_vote_results(&env, poll_id)
     }
 
+    /// Up to MAX_BATCH_SIZE vote results in one call, `None` for unknown poll ids
+    pub fn get_vote_results_bulk(
+        env: Env,
+        poll_ids: Vec<u32>,
+    ) -> Result<Vec<Option<VoteResults>>, GovernanceError> {
+        queries::get_vote_results_bulk(&env, &poll_ids)
+    }
+
     pub fn get_governance_params(env: Env) -> GovernanceParams {
         queries::get_governance_params(&env)
     }
 
+    /// Fractcore contract this instance governs; fractcore asks before it
+    /// accepts the instance as its governance contract
+    pub fn get_fractcore_contract(env: Env) -> Address {
+        storage::get_fractcore_contract(&env)
+    }
+
+    /// One page of the admin action log, oldest first; pages older than the
+    /// retention window come back empty and are covered by the head's hash
+    pub fn get_admin_log(env: Env, page: u32) -> Vec<AdminLogEntry> {
+        admin_log::get_admin_log(&env, page)
+    }
+
+    pub fn get_admin_log_head(env: Env) -> AdminLogHead {
+        admin_log::get_admin_log_head(&env)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        queries::get_init_config_hash(&env)
+    }
+
+    /// Cached supply and owner count used to evaluate the poll
+    pub fn get_poll_snapshot(env: Env, poll_id: u32) -> Result<PollSnapshot, GovernanceError> {
+        queries::get_poll_snapshot(&env, poll_id)
+    }
+
+    /// Re-read supply and owner count for an active poll (admin or poll creator)
+    pub fn refresh_poll_snapshot(
+        env: Env,
+        caller: Address,
+        poll_id: u32,
+    ) -> Result<PollSnapshot, GovernanceError> {
+        polls::refresh_poll_snapshot(&env, &caller, poll_id)
+    }
+
+    /// Certified outcome of a finalized poll
+    pub fn get_certificate(env: Env, poll_id: u32) -> Result<PollCertificate, GovernanceError> {
+        queries::get_certificate(&env, poll_id)
+    }
+
+    /// Status and distribution outcome of an executed poll
+    pub fn get_execution_record(
+        env: Env,
+        poll_id: u32,
+    ) -> Result<ExecutionRecord, GovernanceError> {
+        queries::get_execution_record(&env, poll_id)
+    }
+
+    /// Option, voting power and time of a voter's vote on a poll
+    pub fn get_vote_receipt(
+        env: Env,
+        poll_id: u32,
+        voter: Address,
+    ) -> Result<VoteReceipt, GovernanceError> {
+        queries::get_vote_receipt(&env, poll_id, &voter)
+    }
+
+    /// Up to `limit` receipts of an address's votes (capped at MAX_BATCH_SIZE) in voting
+    /// order from `cursor`, with the cursor of the next page
+    pub fn get_voter_receipts_page(
+        env: Env,
+        voter: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<VoteReceipt>, Option<Cursor>) {
+        queries::get_voter_receipts_page(&env, &voter, cursor, limit)
+    }
+
+    /// Up to `limit` voters for an option (capped at VOTERS_PAGE_SIZE) in voting order from
+    /// `cursor`, with the cursor of the next page
+    pub fn get_voters_for_option(
+        env: Env,
+        poll_id: u32,
+        option_index: u32,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> Result<(Vec<Address>, Option<Cursor>), GovernanceError> {
+        queries::get_voters_for_option(&env, poll_id, option_index, cursor, limit)
+    }
+
+    pub fn get_option_voter_count(env: Env, poll_id: u32, option_index: u32) -> u32 {
+        storage::get_option_voter_count(&env, poll_id, option_index)
+    }
+
+    /// Seconds between balance checkpoints; None for polls that use live balances
+    pub fn get_poll_checkpoint_interval(env: Env, poll_id: u32) -> Option<u64> {
+        storage::get_poll_checkpoint_interval(&env, poll_id)
+    }
+
+    /// Power `voter` would vote with in the poll right now
+    pub fn get_poll_voting_power(
+        env: Env,
+        poll_id: u32,
+        voter: Address,
+    ) -> Result<u64, GovernanceError> {
+        queries::get_poll_voting_power(&env, poll_id, &voter)
+    }
+
     pub fn can_vote(env: Env, voter: Address, poll_id: u32) -> Result<bool, GovernanceError> {
         voting::can_vote(&env, &voter, poll_id)
     }
 
+    /// Tallies, participation and executability if each `(holder, option)` in
+    /// `additional_votes` also voted with their current poll voting power; nothing is stored
+    pub fn simulate_outcome(
+        env: Env,
+        poll_id: u32,
+        additional_votes: Vec<(Address, u32)>,
+    ) -> Result<SimulatedOutcome, GovernanceError> {
+        queries::simulate_outcome(&env, poll_id, &additional_votes)
+    }
+
     /// Check poll execution criteria without executing
     pub fn check_poll_execution(
         env: Env,
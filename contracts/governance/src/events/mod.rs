@@ -1,6 +1,9 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, BytesN, Env, String};
 
-use crate::contract::PollAction;
+use crate::contract::{
+    AdminLogEntry, PlatformGovernance, Poll, PollAction, PollCertificate, PollSnapshot, QuorumMode,
+    TransferLockScope,
+};
 
 // Event topics
 const POLL_CREATED: &str = "poll_created";
@@ -8,6 +11,24 @@ const VOTE_CAST: &str = "vote_cast";
 const POLL_EXECUTED: &str = "poll_executed";
 const POLL_REJECTED: &str = "poll_rejected";
 const PARAMS_UPDATED: &str = "params_updated";
+const KEEPER_PAID: &str = "keeper_paid";
+const POLL_CERTIFIED: &str = "poll_certified";
+const SNAPSHOT_REFRESHED: &str = "snapshot_refreshed";
+const ACTIONS_UPDATED: &str = "actions_updated";
+const POLL_TRANSFER_LOCK: &str = "poll_transfer_lock";
+const RELAYER_PAID: &str = "relayer_paid";
+const QUORUM_MODE_UPDATED: &str = "quorum_mode_updated";
+const EXECUTION_WARNING: &str = "execution_warning";
+const POLL_NOTICE: &str = "poll_notice";
+const POLL_CHECKPOINTS: &str = "poll_checkpoints";
+const PLATFORM_GOVERNANCE: &str = "platform_governance";
+const TEMPLATE_CREATED: &str = "template_created";
+const TEMPLATE_USED: &str = "template_used";
+const TEMPLATE_REMOVED: &str = "template_removed";
+const SCHEDULE_REGISTRY: &str = "schedule_registry";
+const ADMIN_LOG: &str = "admin_log";
+const POLL_IMPORTED: &str = "poll_imported";
+const UPGRADED: &str = "upgraded";
 
 pub fn emit_poll_created(env: &Env, poll_id: u32, asset_id: u64, creator: &Address) {
     env.events().publish(
@@ -16,6 +37,33 @@ pub fn emit_poll_created(env: &Env, poll_id: u32, asset_id: u64, creator: &Addre
     );
 }
 
+/// Poll recreated from another instance's export under its original id
+pub fn emit_poll_imported(env: &Env, poll_id: u32, asset_id: u64) {
+    env.events()
+        .publish((String::from_str(env, POLL_IMPORTED),), (poll_id, asset_id));
+}
+
+pub fn emit_upgraded(env: &Env, old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: BytesN<32>) {
+    env.events().publish(
+        (String::from_str(env, UPGRADED),),
+        (old_wasm_hash, new_wasm_hash),
+    );
+}
+
+/// Holder-facing notice of a new poll, keyed by asset so wallets can subscribe
+/// to the assets they hold: (poll_id, owner_count, snapshot ledger, end_time)
+pub fn emit_poll_notice(env: &Env, poll: &Poll, snapshot: &PollSnapshot) {
+    env.events().publish(
+        (String::from_str(env, POLL_NOTICE), poll.asset_id),
+        (
+            poll.id,
+            snapshot.owner_count,
+            snapshot.ledger,
+            poll.end_time,
+        ),
+    );
+}
+
 pub fn emit_vote_cast(
     env: &Env,
     poll_id: u32,
@@ -42,6 +90,13 @@ pub fn emit_poll_executed(
     );
 }
 
+pub fn emit_execution_warning(env: &Env, poll_id: u32, approved: u128, paid: u128) {
+    env.events().publish(
+        (String::from_str(env, EXECUTION_WARNING),),
+        (poll_id, approved, paid),
+    );
+}
+
 pub fn emit_poll_rejected(env: &Env, poll_id: u32, approval_percentage: u32) {
     env.events().publish(
         (String::from_str(env, POLL_REJECTED),),
@@ -55,3 +110,100 @@ pub fn emit_params_updated(env: &Env, threshold_percentage: u32, quorum_percenta
         (threshold_percentage, quorum_percentage),
     );
 }
+
+pub fn emit_keeper_paid(env: &Env, keeper: &Address, polls_finalized: u32, amount: i128) {
+    env.events().publish(
+        (String::from_str(env, KEEPER_PAID),),
+        (keeper, polls_finalized, amount),
+    );
+}
+
+pub fn emit_relayer_paid(
+    env: &Env,
+    relayer: &Address,
+    voter: &Address,
+    poll_id: u32,
+    amount: i128,
+) {
+    env.events().publish(
+        (String::from_str(env, RELAYER_PAID),),
+        (relayer, voter, poll_id, amount),
+    );
+}
+
+pub fn emit_poll_certified(env: &Env, certificate: &PollCertificate) {
+    env.events().publish(
+        (String::from_str(env, POLL_CERTIFIED), certificate.poll_id),
+        certificate.clone(),
+    );
+}
+
+pub fn emit_poll_snapshot_refreshed(env: &Env, poll_id: u32, snapshot: &PollSnapshot) {
+    env.events().publish(
+        (String::from_str(env, SNAPSHOT_REFRESHED), poll_id),
+        (snapshot.total_supply, snapshot.owner_count),
+    );
+}
+
+pub fn emit_poll_checkpoints(env: &Env, poll_id: u32, interval: u64) {
+    env.events()
+        .publish((String::from_str(env, POLL_CHECKPOINTS), poll_id), interval);
+}
+
+pub fn emit_allowed_actions_updated(env: &Env, asset_id: u64, allowed: u32) {
+    env.events()
+        .publish((String::from_str(env, ACTIONS_UPDATED), asset_id), allowed);
+}
+
+pub fn emit_platform_governance(env: &Env, platform: &PlatformGovernance) {
+    env.events().publish(
+        (String::from_str(env, PLATFORM_GOVERNANCE),),
+        platform.clone(),
+    );
+}
+
+pub fn emit_template_created(env: &Env, template_id: u32, asset_id: u64, creator: &Address) {
+    env.events().publish(
+        (String::from_str(env, TEMPLATE_CREATED), asset_id),
+        (template_id, creator),
+    );
+}
+
+pub fn emit_template_used(env: &Env, template_id: u32, poll_id: u32) {
+    env.events()
+        .publish((String::from_str(env, TEMPLATE_USED), template_id), poll_id);
+}
+
+pub fn emit_template_removed(env: &Env, template_id: u32, asset_id: u64) {
+    env.events().publish(
+        (String::from_str(env, TEMPLATE_REMOVED), asset_id),
+        template_id,
+    );
+}
+
+pub fn emit_quorum_mode_updated(env: &Env, asset_id: u64, mode: &QuorumMode) {
+    env.events().publish(
+        (String::from_str(env, QUORUM_MODE_UPDATED), asset_id),
+        mode.clone(),
+    );
+}
+
+pub fn emit_poll_transfer_lock(env: &Env, poll_id: u32, asset_id: u64, scope: TransferLockScope) {
+    env.events().publish(
+        (String::from_str(env, POLL_TRANSFER_LOCK),),
+        (poll_id, asset_id, scope),
+    );
+}
+
+pub fn emit_schedule_registry(env: &Env, registry: &Option<Address>) {
+    env.events().publish(
+        (String::from_str(env, SCHEDULE_REGISTRY),),
+        registry.clone(),
+    );
+}
+
+/// Entry `index` of the admin action log, so archivers can keep entries after they are folded
+pub fn emit_admin_log(env: &Env, index: u32, entry: &AdminLogEntry) {
+    env.events()
+        .publish((String::from_str(env, ADMIN_LOG), index), entry.clone());
+}
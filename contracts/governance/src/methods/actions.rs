@@ -0,0 +1,163 @@
+use soroban_sdk::{Address, Env};
+
+use crate::contract::{
+    GovernanceError, PlatformGovernance, PollAction, ACTION_DISTRIBUTE_FUNDS, ACTION_NO_EXECUTION,
+    ACTION_POST_ANNOUNCEMENT, ACTION_RELEASE_EARMARK, ACTION_REVERT_ASSET_URI, ACTION_SPLIT_ASSET,
+    ACTION_TRANSFER_TOKENS, ALL_POLL_ACTIONS,
+};
+use crate::events;
+use crate::methods::{activity, admin_log, utils};
+use crate::storage;
+
+/// Bit of an action in the per-asset allowlist.
+///
+/// `SetAllowedActions`, `SetPettyCash` and `VetoEmergencyWithdraw` have no bit:
+/// the community can always vote to change the allowlist or the manager's petty
+/// cash limits, or to block an emergency withdrawal, or to pace large sellers
+/// with `SetExitQueue`, so a manager cannot lock them out. Neither have
+/// `SetTradingConfig` and `UpgradeContract`, which only the platform asset may
+/// carry, nor `Reserved`, which is refused before the allowlist is read.
+pub fn action_bit(action: &PollAction) -> u32 {
+    match action {
+        PollAction::NoExecution => ACTION_NO_EXECUTION,
+        PollAction::DistributeFunds(_, _) => ACTION_DISTRIBUTE_FUNDS,
+        PollAction::TransferTokens(_, _) => ACTION_TRANSFER_TOKENS,
+        PollAction::SplitAsset(_) => ACTION_SPLIT_ASSET,
+        PollAction::PostAnnouncement(_, _, _) => ACTION_POST_ANNOUNCEMENT,
+        PollAction::RevertAssetUri(_) => ACTION_REVERT_ASSET_URI,
+        PollAction::ReleaseEarmark(_) => ACTION_RELEASE_EARMARK,
+        PollAction::SetAllowedActions(_)
+        | PollAction::SetPettyCash(_, _)
+        | PollAction::VetoEmergencyWithdraw(_)
+        | PollAction::SetExitQueue(_, _, _)
+        | PollAction::SetTradingConfig(_, _, _, _)
+        | PollAction::UpgradeContract(_, _)
+        | PollAction::Reserved(_, _) => 0,
+    }
+}
+
+pub fn require_action_allowed(
+    env: &Env,
+    asset_id: u64,
+    action: &PollAction,
+) -> Result<(), GovernanceError> {
+    match action {
+        PollAction::SetTradingConfig(_, _, _, _) => {
+            platform_trading_contract(env, asset_id)?;
+        }
+        PollAction::UpgradeContract(target, _) => require_upgrade_target(env, asset_id, target)?,
+        PollAction::SetExitQueue(_, _, _) => {
+            trading_contract(env)?;
+        }
+        PollAction::Reserved(_, _) => return Err(GovernanceError::UnsupportedAction),
+        _ => {}
+    }
+
+    let bit = action_bit(action);
+    if bit != 0 && storage::get_allowed_actions(env, asset_id) & bit == 0 {
+        return Err(GovernanceError::ActionNotAllowed);
+    }
+    Ok(())
+}
+
+/// Designate the platform asset and the trading contract its polls configure (admin only)
+pub fn set_platform_governance(
+    env: &Env,
+    admin: &Address,
+    trading_contract: &Address,
+    asset_id: u64,
+) -> Result<(), GovernanceError> {
+    admin.require_auth();
+
+    if *admin != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    admin_log::record(
+        env,
+        admin,
+        "set_platform_governance",
+        (trading_contract.clone(), asset_id),
+    );
+
+    let platform = PlatformGovernance {
+        trading_contract: trading_contract.clone(),
+        asset_id,
+    };
+    storage::set_platform_governance(env, &platform);
+    events::emit_platform_governance(env, &platform);
+
+    Ok(())
+}
+
+/// Trading contract a `SetTradingConfig` poll on `asset_id` configures; fails
+/// unless `asset_id` is the designated platform asset
+pub fn platform_trading_contract(env: &Env, asset_id: u64) -> Result<Address, GovernanceError> {
+    match storage::get_platform_governance(env) {
+        Some(platform) if platform.asset_id == asset_id => Ok(platform.trading_contract),
+        _ => Err(GovernanceError::ActionNotAllowed),
+    }
+}
+
+/// Trading contract named with `set_platform_governance`, which `SetExitQueue`
+/// polls on any asset configure
+pub fn trading_contract(env: &Env) -> Result<Address, GovernanceError> {
+    match storage::get_platform_governance(env) {
+        Some(platform) => Ok(platform.trading_contract),
+        None => Err(GovernanceError::ActionNotAllowed),
+    }
+}
+
+/// Check that an `UpgradeContract` poll on `asset_id` names one of the platform's
+/// contracts, governance itself included; fails unless `asset_id` is the
+/// designated platform asset
+pub fn require_upgrade_target(
+    env: &Env,
+    asset_id: u64,
+    target: &Address,
+) -> Result<(), GovernanceError> {
+    let trading_contract = platform_trading_contract(env, asset_id)?;
+    if *target == trading_contract
+        || *target == env.current_contract_address()
+        || *target == storage::get_fractcore_contract(env)
+        || *target == storage::get_funding_contract(env)
+    {
+        Ok(())
+    } else {
+        Err(GovernanceError::InvalidParameters)
+    }
+}
+
+/// Restrict the poll actions available for an asset (fractcore asset manager only)
+pub fn set_allowed_actions(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    allowed: u32,
+) -> Result<(), GovernanceError> {
+    caller.require_auth();
+
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let manager = utils::call_fractcore_asset_manager(env, &fractcore_contract, asset_id)?;
+    if *caller != manager {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    store_allowed_actions(env, asset_id, allowed)
+}
+
+/// Store a validated allowlist; also the execution path of a `SetAllowedActions` poll
+pub fn store_allowed_actions(
+    env: &Env,
+    asset_id: u64,
+    allowed: u32,
+) -> Result<(), GovernanceError> {
+    if allowed & !ALL_POLL_ACTIONS != 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    storage::set_allowed_actions(env, asset_id, allowed);
+    activity::note_asset(env, asset_id);
+    events::emit_allowed_actions_updated(env, asset_id, allowed);
+
+    Ok(())
+}
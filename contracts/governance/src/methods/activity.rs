@@ -0,0 +1,181 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::contract::{
+    AssetGovernanceSummary, ExecutedPoll, ExecutionRecord, ExecutionResult, GovernanceError,
+    GovernanceStats, Poll, VoterBucket, MAX_BATCH_SIZE, MAX_STATS_BUCKETS,
+    RECENT_EXECUTIONS_PAGE_SIZE, STATS_BUCKET_SECONDS,
+};
+use crate::storage;
+
+/// Add an asset to the governed list when it is first given governance
+/// settings, before any poll exists
+pub fn note_asset(env: &Env, asset_id: u64) {
+    if storage::get_asset_summary(env, asset_id).is_none() {
+        storage::set_asset_summary(env, &empty_summary(asset_id));
+    }
+}
+
+pub fn record_poll_created(env: &Env, poll: &Poll) {
+    let mut summary = get_asset_summary(env, poll.asset_id);
+    summary.total_polls += 1;
+    summary.active_polls += 1;
+    summary.last_poll_at = poll.start_time;
+    storage::set_asset_summary(env, &summary);
+    storage::add_active_asset(env, poll.asset_id);
+}
+
+/// Count a poll imported from another instance as it stood there. Its turnout
+/// is not replayed into the asset's statistics or the recent executions.
+pub fn record_poll_imported(env: &Env, poll: &Poll, execution: Option<&ExecutionRecord>) {
+    let mut summary = get_asset_summary(env, poll.asset_id);
+    summary.total_polls += 1;
+    summary.last_poll_at = summary.last_poll_at.max(poll.start_time);
+    if poll.is_active {
+        summary.active_polls += 1;
+        storage::add_active_asset(env, poll.asset_id);
+    } else if let Some(execution) = execution {
+        summary.executed_polls += 1;
+        summary.last_executed_at = summary.last_executed_at.max(execution.executed_at);
+    } else {
+        summary.rejected_polls += 1;
+    }
+    storage::set_asset_summary(env, &summary);
+}
+
+/// Count a finalized poll. Polls created before summaries were kept have no
+/// active count behind them, so it saturates at zero.
+pub fn record_poll_finalized(env: &Env, poll: &Poll, executed: bool) {
+    let mut summary = get_asset_summary(env, poll.asset_id);
+    summary.active_polls = summary.active_polls.saturating_sub(1);
+    if executed {
+        let executed_at = env.ledger().timestamp();
+        summary.executed_polls += 1;
+        summary.last_executed_at = executed_at;
+        storage::add_recent_execution(
+            env,
+            &ExecutedPoll {
+                poll_id: poll.id,
+                asset_id: poll.asset_id,
+                executed_at,
+            },
+        );
+    } else {
+        summary.rejected_polls += 1;
+    }
+    storage::set_asset_summary(env, &summary);
+
+    if summary.active_polls == 0 {
+        storage::remove_active_asset(env, poll.asset_id);
+    }
+}
+
+/// Fold a finalized poll into its asset's turnout statistics. A voter counts
+/// once per bucket however many of the asset's polls they voted in there.
+pub fn record_poll_stats(env: &Env, poll: &Poll, result: &ExecutionResult, quorum_met: bool) {
+    let mut stats = get_governance_stats(env, poll.asset_id);
+    let participation = result.participation_percentage as u64;
+
+    stats.finalized_polls += 1;
+    if result.should_execute {
+        stats.passed_polls += 1;
+    } else if quorum_met {
+        stats.rejected_polls += 1;
+    } else {
+        stats.expired_polls += 1;
+    }
+    stats.participation_total += participation;
+    stats.average_participation = (stats.participation_total / stats.finalized_polls as u64) as u32;
+
+    let start = env.ledger().timestamp() / STATS_BUCKET_SECONDS * STATS_BUCKET_SECONDS;
+    let mut new_voters = 0u32;
+    for voter in poll.votes.keys() {
+        if storage::get_voter_last_bucket(env, poll.asset_id, &voter) != Some(start) {
+            storage::set_voter_last_bucket(env, poll.asset_id, &voter, start);
+            new_voters += 1;
+        }
+    }
+
+    let mut bucket = match stats.voter_buckets.last() {
+        Some(last) if last.start == start => stats.voter_buckets.pop_back().unwrap(),
+        _ => VoterBucket {
+            start,
+            polls: 0,
+            unique_voters: 0,
+            participation_total: 0,
+        },
+    };
+    bucket.polls += 1;
+    bucket.unique_voters += new_voters;
+    bucket.participation_total += participation;
+    stats.voter_buckets.push_back(bucket);
+    if stats.voter_buckets.len() > MAX_STATS_BUCKETS {
+        stats.voter_buckets.pop_front();
+    }
+
+    storage::set_governance_stats(env, &stats);
+}
+
+/// Turnout statistics of an asset, all zero if none of its polls was finalized
+pub fn get_governance_stats(env: &Env, asset_id: u64) -> GovernanceStats {
+    storage::get_governance_stats(env, asset_id).unwrap_or_else(|| GovernanceStats {
+        asset_id,
+        finalized_polls: 0,
+        passed_polls: 0,
+        rejected_polls: 0,
+        expired_polls: 0,
+        participation_total: 0,
+        average_participation: 0,
+        voter_buckets: Vec::new(env),
+    })
+}
+
+/// Summary of an asset, all zero if it never had governance activity
+pub fn get_asset_summary(env: &Env, asset_id: u64) -> AssetGovernanceSummary {
+    storage::get_asset_summary(env, asset_id).unwrap_or_else(|| empty_summary(asset_id))
+}
+
+/// Summaries for up to MAX_BATCH_SIZE assets, in the order given
+pub fn get_asset_summaries(
+    env: &Env,
+    asset_ids: &Vec<u64>,
+) -> Result<Vec<AssetGovernanceSummary>, GovernanceError> {
+    if asset_ids.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut summaries = Vec::new(env);
+    for asset_id in asset_ids.iter() {
+        summaries.push_back(get_asset_summary(env, asset_id));
+    }
+    Ok(summaries)
+}
+
+/// One page of executed polls, newest first; page 0 holds the latest
+/// RECENT_EXECUTIONS_PAGE_SIZE executions
+pub fn recently_executed(env: &Env, page: u32) -> Vec<ExecutedPoll> {
+    let recent = storage::get_recent_executions(env);
+    let skip = page.saturating_mul(RECENT_EXECUTIONS_PAGE_SIZE);
+
+    let mut executed = Vec::new(env);
+    if skip >= recent.len() {
+        return executed;
+    }
+    let end = recent.len() - skip;
+    let start = end.saturating_sub(RECENT_EXECUTIONS_PAGE_SIZE);
+    for index in (start..end).rev() {
+        executed.push_back(recent.get(index).unwrap());
+    }
+    executed
+}
+
+fn empty_summary(asset_id: u64) -> AssetGovernanceSummary {
+    AssetGovernanceSummary {
+        asset_id,
+        total_polls: 0,
+        active_polls: 0,
+        executed_polls: 0,
+        rejected_polls: 0,
+        last_poll_at: 0,
+        last_executed_at: 0,
+    }
+}
@@ -1,8 +1,9 @@
-use soroban_sdk::{panic_with_error, Address, Env};
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, BytesN, Env, Val, Vec};
 
 use crate::contract::{GovernanceError, GovernanceParams};
 use crate::events;
-use crate::storage;
+use crate::methods::admin_log;
+use crate::storage::{self, DataKey, PlacedKey};
 
 /// Initialize the governance contract
 pub fn initialize(
@@ -15,18 +16,20 @@ pub fn initialize(
     default_expiry_days: u32,
 ) -> Result<(), GovernanceError> {
     if storage::is_initialized(env) {
-        panic_with_error!(env, GovernanceError::AlreadyInitialized);
+        return Err(GovernanceError::AlreadyInitialized);
     }
 
     // Validate parameters: percentages must be <= 100, expiry between 1-365 days
     if default_threshold > 100 || default_quorum > 100 {
-        panic_with_error!(env, GovernanceError::InvalidParameters);
+        return Err(GovernanceError::InvalidParameters);
     }
 
     if default_expiry_days == 0 || default_expiry_days > 365 {
-        panic_with_error!(env, GovernanceError::InvalidParameters);
+        return Err(GovernanceError::InvalidParameters);
     }
 
+    storage::mark_namespaced(env);
+
     // Store contract references and admin address
     storage::set_admin(env, admin);
     storage::set_fractcore_contract(env, fractcore_contract);
@@ -40,6 +43,22 @@ pub fn initialize(
     };
     storage::set_governance_params(env, &params);
 
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(
+            &(
+                admin.clone(),
+                fractcore_contract.clone(),
+                funding_contract.clone(),
+                default_threshold,
+                default_quorum,
+                default_expiry_days,
+            )
+                .to_xdr(env),
+        )
+        .into();
+    storage::set_init_config_hash(env, &config_hash);
+
     storage::set_initialized(env);
     Ok(())
 }
@@ -56,6 +75,7 @@ pub fn set_governance_params(
     if *admin != stored_admin {
         panic_with_error!(env, GovernanceError::Unauthorized);
     }
+    admin_log::record(env, admin, "set_governance_params", (new_params.clone(),));
 
     // Percentages must be <= 100, expiry between 1-365 days
     if new_params.threshold_percentage > 100 || new_params.quorum_percentage > 100 {
@@ -92,3 +112,54 @@ pub fn update_governance_params(
     };
     set_governance_params(env, admin, &params)
 }
+
+/// Replace this contract's code with an uploaded WASM. Only reached by
+/// executing an `UpgradeContract` poll of the platform asset that names this
+/// contract, so governance is upgraded the way it upgrades the others.
+pub fn upgrade(env: &Env, new_wasm_hash: &BytesN<32>) {
+    let old_wasm_hash = storage::get_wasm_hash(env);
+    storage::set_wasm_hash(env, new_wasm_hash);
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    events::emit_upgraded(env, old_wasm_hash, new_wasm_hash.clone());
+}
+
+/// Move entries an upgraded deployment wrote before namespacing from their
+/// bare keys under their namespace (admin only). The admin lists the keys,
+/// over as many calls as the budget needs, and sets `last` on the final call
+/// to mark the contract namespaced, after which this entrypoint is closed.
+/// Until its own entry has moved, the admin is read from its bare key.
+/// A call takes at most MAX_MIGRATE_BATCH keys. Returns the number of entries moved.
+pub fn migrate_storage(
+    env: &Env,
+    admin: &Address,
+    keys: &Vec<Val>,
+    last: bool,
+) -> Result<u32, GovernanceError> {
+    admin.require_auth();
+    let stored_admin: Address = storage::get(env, &DataKey::Admin)
+        .or_else(|| storage::get_bare(env, &DataKey::Admin))
+        .ok_or(GovernanceError::NotInitialized)?;
+    if *admin != stored_admin {
+        return Err(GovernanceError::Unauthorized);
+    }
+    if storage::is_namespaced(env) {
+        return Err(GovernanceError::AlreadyInitialized);
+    }
+    if keys.len() > storage::MAX_MIGRATE_BATCH {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut moved = 0;
+    for key in keys.iter() {
+        moved += storage::migrate(env, DataKey::NAMESPACE, key) as u32;
+    }
+    if last {
+        // Logged by the final batch alone: until then the log's own entries may
+        // still sit under their bare keys, and moving them would drop the entry
+        admin_log::record(env, admin, "migrate_storage", (keys.len(),));
+        storage::mark_namespaced(env);
+    }
+    Ok(moved)
+}
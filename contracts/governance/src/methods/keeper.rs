@@ -0,0 +1,171 @@
+use soroban_sdk::{symbol_short, token::TokenClient, Address, Env, Symbol, Vec};
+
+use crate::contract::{GovernanceError, KeeperConfig, Poll, ACTIVE_POLLS_PAGE_SIZE};
+use crate::events;
+use crate::methods::utils::ScheduleClient;
+use crate::methods::{admin_log, polls};
+use crate::storage;
+
+/// Action type of a poll's expiry in the registry's keeper schedule
+pub const POLL_END_ACTION: Symbol = symbol_short!("poll_end");
+
+/// Configure which token pays keeper bounties and how much per finalized poll
+pub fn set_keeper_config(
+    env: &Env,
+    admin: &Address,
+    token: &Address,
+    bounty: i128,
+) -> Result<(), GovernanceError> {
+    admin.require_auth();
+
+    if *admin != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    admin_log::record(env, admin, "set_keeper_config", (token.clone(), bounty));
+
+    if bounty < 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    if let Some(current) = storage::get_keeper_config(env) {
+        // Pool balance is denominated in the old token
+        if current.token != *token && storage::get_keeper_pool(env) > 0 {
+            return Err(GovernanceError::InvalidParameters);
+        }
+    }
+
+    storage::set_keeper_config(
+        env,
+        &KeeperConfig {
+            token: token.clone(),
+            bounty,
+        },
+    );
+
+    Ok(())
+}
+
+/// Point poll expiries at the registry's keeper schedule, or stop (admin only).
+///
+/// The registry admin must approve this contract as a scheduler. Polls
+/// created before the registry was set are not listed.
+pub fn set_schedule_registry(
+    env: &Env,
+    admin: &Address,
+    registry: &Option<Address>,
+) -> Result<(), GovernanceError> {
+    admin.require_auth();
+
+    if *admin != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    admin_log::record(env, admin, "set_schedule_registry", (registry.clone(),));
+
+    storage::set_schedule_registry(env, registry);
+    events::emit_schedule_registry(env, registry);
+
+    Ok(())
+}
+
+/// List a new poll's expiry for keepers. Best effort: a registry that refuses
+/// the entry (not approved, bucket full) does not block the poll.
+pub fn schedule_poll_end(env: &Env, poll: &Poll) {
+    if let Some(registry) = storage::get_schedule_registry(env) {
+        let _ = ScheduleClient::new(env, &registry).try_schedule(
+            &env.current_contract_address(),
+            &POLL_END_ACTION,
+            &(poll.id as u64),
+            &poll.end_time,
+        );
+    }
+}
+
+/// Take a finalized poll off the keeper schedule, best effort as when listing it
+pub fn unschedule_poll_end(env: &Env, poll_id: u32) {
+    if let Some(registry) = storage::get_schedule_registry(env) {
+        let _ = ScheduleClient::new(env, &registry).try_unschedule(
+            &env.current_contract_address(),
+            &POLL_END_ACTION,
+            &(poll_id as u64),
+        );
+    }
+}
+
+/// Anyone can top up the keeper pool in the configured bounty token
+pub fn fund_keeper_pool(env: &Env, funder: &Address, amount: i128) -> Result<(), GovernanceError> {
+    funder.require_auth();
+
+    if amount <= 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let config = storage::get_keeper_config(env).ok_or(GovernanceError::NotInitialized)?;
+    TokenClient::new(env, &config.token).transfer(funder, &env.current_contract_address(), &amount);
+
+    storage::set_keeper_pool(env, storage::get_keeper_pool(env) + amount);
+
+    Ok(())
+}
+
+/// Finalize expired polls so their outcome is executed without waiting for a voter.
+///
+/// The keeper is paid once per poll actually finalized in this call, excluding
+/// polls they created themselves, and never more than the pool holds.
+pub fn finalize_expired_polls(
+    env: &Env,
+    keeper: &Address,
+    limit: u32,
+) -> Result<u32, GovernanceError> {
+    keeper.require_auth();
+
+    let now = env.ledger().timestamp();
+    let mut finalized = 0u32;
+    let mut rewarded = 0u32;
+
+    // Collected before finalizing, since finalizing reorders the active pages
+    let mut due = Vec::new(env);
+    let pages = storage::get_active_poll_count(env).div_ceil(ACTIVE_POLLS_PAGE_SIZE);
+    for page in 0..pages {
+        for poll_id in storage::get_active_polls_page(env, page).iter() {
+            if due.len() >= limit {
+                break;
+            }
+            if let Some(poll) = storage::get_poll(env, poll_id) {
+                if poll.is_active && now >= poll.end_time {
+                    due.push_back(poll);
+                }
+            }
+        }
+    }
+
+    for poll in due.iter() {
+        polls::check_and_execute_poll(env, poll.id)?;
+        finalized += 1;
+
+        if poll.creator != *keeper {
+            rewarded += 1;
+        }
+    }
+
+    pay_keeper(env, keeper, finalized, rewarded);
+
+    Ok(finalized)
+}
+
+fn pay_keeper(env: &Env, keeper: &Address, finalized: u32, rewarded: u32) {
+    let config = match storage::get_keeper_config(env) {
+        Some(config) => config,
+        None => return,
+    };
+
+    let pool = storage::get_keeper_pool(env);
+    let payout = config.bounty.saturating_mul(rewarded as i128).min(pool);
+    if payout <= 0 {
+        return;
+    }
+
+    storage::set_keeper_pool(env, pool - payout);
+    TokenClient::new(env, &config.token).transfer(&env.current_contract_address(), keeper, &payout);
+
+    events::emit_keeper_paid(env, keeper, finalized, payout);
+}
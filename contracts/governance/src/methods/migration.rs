@@ -0,0 +1,159 @@
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::contract::{
+    GovernanceError, PollExport, PollExportPage, VoteReceipt, POLL_EXPORT_PAGE_SIZE,
+};
+use crate::events;
+use crate::methods::{activity, admin_log, keeper};
+use crate::storage;
+
+/// Export one page of polls by id, with their votes and per-poll state (admin only)
+pub fn export_polls(
+    env: &Env,
+    admin: &Address,
+    page: u32,
+) -> Result<PollExportPage, GovernanceError> {
+    require_admin(env, admin)?;
+
+    let poll_count = storage::get_next_poll_id(env) - 1;
+    let first_poll_id = page
+        .checked_mul(POLL_EXPORT_PAGE_SIZE)
+        .ok_or(GovernanceError::InvalidParameters)?
+        + 1;
+    let last_poll_id = poll_count.min(first_poll_id + POLL_EXPORT_PAGE_SIZE - 1);
+
+    let mut export = PollExportPage {
+        first_poll_id,
+        poll_count,
+        polls: Vec::new(env),
+        snapshots: Map::new(env),
+        transfer_locks: Map::new(env),
+        certificates: Map::new(env),
+        executions: Map::new(env),
+    };
+    for poll_id in first_poll_id..=last_poll_id {
+        let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+        export.polls.push_back(PollExport {
+            poll,
+            created_ledger: storage::get_poll_created_ledger(env, poll_id),
+            checkpoint_interval: storage::get_poll_checkpoint_interval(env, poll_id),
+        });
+        if let Some(snapshot) = storage::get_poll_snapshot(env, poll_id) {
+            export.snapshots.set(poll_id, snapshot);
+        }
+        if let Some(scope) = storage::get_poll_transfer_lock(env, poll_id) {
+            export.transfer_locks.set(poll_id, scope);
+        }
+        if let Some(certificate) = storage::get_certificate(env, poll_id) {
+            export.certificates.set(poll_id, certificate);
+        }
+        if let Some(execution) = storage::get_execution_record(env, poll_id) {
+            export.executions.set(poll_id, execution);
+        }
+    }
+
+    Ok(export)
+}
+
+/// Recreate an exported page under its poll ids (admin only).
+///
+/// The page must continue this instance's poll ids exactly, so page 0 only goes
+/// into an instance without polls and later pages follow in order. The admin
+/// signs for both instances, so the page is taken as exported; its poll count
+/// is checked against the polls it carries, and each poll's voter count
+/// against its votes. Vote receipts and voter lists are rebuilt from the
+/// votes, with each option's voters in address order. Active polls are listed
+/// and scheduled again; transfer locks resume only once the asset manager makes
+/// this instance the lock authority.
+pub fn import_polls(
+    env: &Env,
+    admin: &Address,
+    data: &PollExportPage,
+) -> Result<u32, GovernanceError> {
+    require_admin(env, admin)?;
+    admin_log::record(
+        env,
+        admin,
+        "import_polls",
+        (data.first_poll_id, data.poll_count),
+    );
+
+    if data.first_poll_id != storage::get_next_poll_id(env) {
+        return Err(GovernanceError::ImportMismatch);
+    }
+    let expected = (data.poll_count + 1)
+        .saturating_sub(data.first_poll_id)
+        .min(POLL_EXPORT_PAGE_SIZE);
+    if data.polls.len() != expected {
+        return Err(GovernanceError::ImportMismatch);
+    }
+
+    for (offset, record) in data.polls.iter().enumerate() {
+        let poll = &record.poll;
+        if poll.id != data.first_poll_id + offset as u32 || poll.votes.len() != poll.total_voters {
+            return Err(GovernanceError::ImportMismatch);
+        }
+        import_poll(env, data, &record);
+    }
+
+    Ok(data.polls.len())
+}
+
+fn import_poll(env: &Env, data: &PollExportPage, record: &PollExport) {
+    let poll = &record.poll;
+    let poll_id = poll.id;
+
+    storage::set_poll(env, poll_id, poll);
+    storage::set_poll_version(env, poll_id);
+    storage::set_poll_created_ledger(env, poll_id, record.created_ledger);
+    storage::add_asset_poll(env, poll.asset_id, poll_id);
+    if let Some(interval) = record.checkpoint_interval {
+        storage::set_poll_checkpoint_interval(env, poll_id, interval);
+    }
+    if let Some(snapshot) = data.snapshots.get(poll_id) {
+        storage::set_poll_snapshot(env, poll_id, &snapshot);
+    }
+    if let Some(scope) = data.transfer_locks.get(poll_id) {
+        storage::set_poll_transfer_lock(env, poll_id, scope);
+    }
+    if let Some(certificate) = data.certificates.get(poll_id) {
+        storage::set_certificate(env, &certificate);
+    }
+    let execution = data.executions.get(poll_id);
+    if let Some(execution) = &execution {
+        storage::set_execution_record(env, execution);
+    }
+
+    for vote in poll.votes.values() {
+        storage::set_vote_receipt(
+            env,
+            &VoteReceipt {
+                poll_id,
+                voter: vote.voter.clone(),
+                option_index: vote.option_index,
+                voting_power: vote.voting_power,
+                timestamp: vote.timestamp,
+            },
+        );
+        storage::add_option_voter(env, poll_id, vote.option_index, &vote.voter);
+        storage::add_voter_poll(env, &vote.voter, poll_id);
+    }
+
+    activity::record_poll_imported(env, poll, execution.as_ref());
+    if poll.is_active {
+        storage::add_active_poll(env, poll_id);
+        keeper::schedule_poll_end(env, poll);
+    }
+    storage::increment_poll_counter(env);
+
+    events::emit_poll_imported(env, poll_id, poll.asset_id);
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), GovernanceError> {
+    admin.require_auth();
+
+    if *admin != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    Ok(())
+}
@@ -1,5 +1,13 @@
+pub mod actions;
+pub mod activity;
 pub mod admin;
+pub mod admin_log;
+pub mod keeper;
+pub mod migration;
 pub mod polls;
-pub mod voting;
 pub mod queries;
+pub mod quorum;
+pub mod relay;
+pub mod templates;
 pub mod utils;
+pub mod voting;
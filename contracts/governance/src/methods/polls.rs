@@ -1,8 +1,12 @@
-use soroban_sdk::{panic_with_error, Address, Env, Map, String, Vec};
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, Env, Map, String, Vec};
 
-use crate::contract::{GovernanceError, Poll, PollAction};
+use crate::contract::{
+    ActionOutput, BatchItemResult, ExecutionRecord, ExecutionResult, ExecutionStatus,
+    GovernanceError, Poll, PollAction, PollCertificate, PollSnapshot, TransferLockScope,
+    VoteWeight, DISTRIBUTION_TOLERANCE_BPS, MAX_BATCH_SIZE,
+};
 use crate::events;
-use crate::methods::utils;
+use crate::methods::{actions, activity, keeper, utils};
 use crate::storage;
 
 pub fn create_poll(
@@ -13,14 +17,153 @@ pub fn create_poll(
     description: &String,
     action: &PollAction,
     duration_days: Option<u32>,
+) -> Result<u32, GovernanceError> {
+    store_poll(
+        env,
+        caller,
+        asset_id,
+        title,
+        description,
+        action,
+        duration_days,
+        &Vec::new(env),
+    )
+}
+
+/// Create a poll whose voting power is aggregated across several share classes
+#[allow(clippy::too_many_arguments)]
+pub fn create_weighted_poll(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    title: &String,
+    description: &String,
+    action: &PollAction,
+    duration_days: Option<u32>,
+    weights: &Vec<VoteWeight>,
+) -> Result<u32, GovernanceError> {
+    utils::validate_vote_weights(env, weights)?;
+
+    store_poll(
+        env,
+        caller,
+        asset_id,
+        title,
+        description,
+        action,
+        duration_days,
+        weights,
+    )
+}
+
+/// Create a poll that freezes transfers of the asset in fractcore until it is finalized.
+///
+/// The asset manager must have made this contract the asset's transfer lock
+/// authority; otherwise creation fails with `TransferLockNotPermitted`. The lock
+/// ends at the poll's end time at the latest and is released early on finalization.
+#[allow(clippy::too_many_arguments)]
+pub fn create_locked_poll(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    title: &String,
+    description: &String,
+    action: &PollAction,
+    duration_days: Option<u32>,
+    scope: TransferLockScope,
+) -> Result<u32, GovernanceError> {
+    let poll_id = store_poll(
+        env,
+        caller,
+        asset_id,
+        title,
+        description,
+        action,
+        duration_days,
+        &Vec::new(env),
+    )?;
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+
+    utils::call_fractcore_open_transfer_lock(
+        env,
+        &storage::get_fractcore_contract(env),
+        asset_id,
+        poll_id,
+        scope == TransferLockScope::AllHolders,
+        poll.end_time,
+    )?;
+    storage::set_poll_transfer_lock(env, poll_id, scope);
+
+    events::emit_poll_transfer_lock(env, poll_id, asset_id, scope);
+
+    Ok(poll_id)
+}
+
+/// Create a poll that re-snapshots balances every `checkpoint_interval_days`.
+///
+/// A voter's power is the lower of their balance at creation and at the most
+/// recent checkpoint: fractions bought after creation never count, fractions
+/// sold stop counting from the next checkpoint, and transfers that are undone
+/// between two checkpoints go unnoticed. The interval must be shorter than the
+/// poll.
+#[allow(clippy::too_many_arguments)]
+pub fn create_checkpointed_poll(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    title: &String,
+    description: &String,
+    action: &PollAction,
+    duration_days: Option<u32>,
+    checkpoint_interval_days: u32,
+) -> Result<u32, GovernanceError> {
+    if checkpoint_interval_days == 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let poll_id = store_poll(
+        env,
+        caller,
+        asset_id,
+        title,
+        description,
+        action,
+        duration_days,
+        &Vec::new(env),
+    )?;
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+
+    let interval = checkpoint_interval_days as u64 * 24 * 60 * 60;
+    if interval >= poll.end_time - poll.start_time {
+        return Err(GovernanceError::InvalidParameters);
+    }
+    storage::set_poll_checkpoint_interval(env, poll_id, interval);
+
+    events::emit_poll_checkpoints(env, poll_id, interval);
+
+    Ok(poll_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn store_poll(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    title: &String,
+    description: &String,
+    action: &PollAction,
+    duration_days: Option<u32>,
+    weights: &Vec<VoteWeight>,
 ) -> Result<u32, GovernanceError> {
     caller.require_auth();
 
-    let fractcore_contract = storage::get_fractcore_contract(env);
-    let balance = utils::call_fractcore_balance(env, &fractcore_contract, caller, asset_id)?;
+    actions::require_action_allowed(env, asset_id, action)?;
+
+    let classes = utils::vote_classes(env, asset_id, weights);
+    let voting_power = utils::voting_power(env, &classes, caller)?;
     let admin = storage::get_admin(env);
 
-    if balance == 0 && *caller != admin {
+    if voting_power == 0 && *caller != admin {
         panic_with_error!(env, GovernanceError::InsufficientVotingPower);
     }
 
@@ -51,14 +194,21 @@ pub fn create_poll(
         is_active: true,
         votes: Map::new(env),
         total_voters: 0,
+        weights: weights.clone(),
     };
 
     storage::set_poll(env, poll_id, &poll);
+    storage::set_poll_version(env, poll_id);
+    let snapshot = utils::capture_poll_snapshot(env, &poll)?;
+    storage::set_poll_created_ledger(env, poll_id, env.ledger().sequence());
     storage::add_asset_poll(env, asset_id, poll_id);
     storage::add_active_poll(env, poll_id);
+    activity::record_poll_created(env, &poll);
     storage::increment_poll_counter(env);
+    keeper::schedule_poll_end(env, &poll);
 
     events::emit_poll_created(env, poll_id, asset_id, caller);
+    events::emit_poll_notice(env, &poll, &snapshot);
 
     Ok(poll_id)
 }
@@ -71,9 +221,8 @@ pub fn check_and_execute_poll(env: &Env, poll_id: u32) -> Result<bool, Governanc
     }
 
     let current_time = env.ledger().timestamp();
-    let fractcore_contract = storage::get_fractcore_contract(env);
-    let total_asset_owners =
-        utils::call_fractcore_owner_count(env, &fractcore_contract, poll.asset_id)?;
+    let snapshot = utils::poll_snapshot(env, &poll)?;
+    let total_asset_owners = snapshot.owner_count;
 
     let time_expired = current_time >= poll.end_time;
     let all_owners_voted = poll.total_voters >= total_asset_owners;
@@ -88,14 +237,37 @@ pub fn check_and_execute_poll(env: &Env, poll_id: u32) -> Result<bool, Governanc
 
     let execution_result = utils::check_execution_criteria(env, &poll, &vote_counts, &params)?;
 
+    // Run the action before touching poll state so a failed action leaves the
+    // poll active and retryable, including inside execute_polls batches
+    if execution_result.should_execute {
+        let governance_contract = env.current_contract_address();
+        let output =
+            utils::execute_poll_action(env, &poll.action, poll.asset_id, &governance_contract)?;
+        record_execution(env, &poll, output);
+    }
+
     poll.is_active = false;
     storage::set_poll(env, poll_id, &poll);
     storage::remove_active_poll(env, poll_id);
+    activity::record_poll_finalized(env, &poll, execution_result.should_execute);
+    activity::record_poll_stats(
+        env,
+        &poll,
+        &execution_result,
+        execution_result.active_participation >= params.quorum_percentage,
+    );
+    keeper::unschedule_poll_end(env, poll_id);
 
-    if execution_result.should_execute {
-        let governance_contract = env.current_contract_address();
-        utils::execute_poll_action(env, &poll.action, poll.asset_id, &governance_contract)?;
+    if storage::get_poll_transfer_lock(env, poll_id).is_some() {
+        utils::call_fractcore_release_transfer_lock(
+            env,
+            &storage::get_fractcore_contract(env),
+            poll.asset_id,
+            poll_id,
+        );
+    }
 
+    if execution_result.should_execute {
         events::emit_poll_executed(
             env,
             poll_id,
@@ -107,5 +279,132 @@ pub fn check_and_execute_poll(env: &Env, poll_id: u32) -> Result<bool, Governanc
         events::emit_poll_rejected(env, poll_id, execution_result.approval_percentage);
     }
 
+    certify_poll(
+        env,
+        &poll,
+        &snapshot,
+        vote_counts,
+        winning_option,
+        &execution_result,
+    );
+
     Ok(execution_result.should_execute)
 }
+
+/// Store what the poll's action did, flagging distributions that fell short of
+/// the approved amount by more than DISTRIBUTION_TOLERANCE_BPS
+fn record_execution(env: &Env, poll: &Poll, output: ActionOutput) {
+    let mut status = ExecutionStatus::Executed;
+    if let (PollAction::DistributeFunds(approved, _), ActionOutput::Distribution(outcome)) =
+        (&poll.action, &output)
+    {
+        let paid = outcome.distributed + outcome.fee;
+        let deviation = approved.abs_diff(paid);
+        if outcome.requested != *approved
+            || deviation.saturating_mul(10_000)
+                > approved.saturating_mul(DISTRIBUTION_TOLERANCE_BPS)
+        {
+            status = ExecutionStatus::ExecutedWithWarnings;
+            events::emit_execution_warning(env, poll.id, *approved, paid);
+        }
+    }
+
+    storage::set_execution_record(
+        env,
+        &ExecutionRecord {
+            poll_id: poll.id,
+            status,
+            output,
+            executed_at: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Store the immutable certificate for a poll that was just finalized
+fn certify_poll(
+    env: &Env,
+    poll: &Poll,
+    snapshot: &PollSnapshot,
+    vote_counts: Vec<u64>,
+    winning_option: u32,
+    execution_result: &ExecutionResult,
+) {
+    let action_hash = if execution_result.should_execute {
+        Some(env.crypto().sha256(&poll.action.clone().to_xdr(env)).into())
+    } else {
+        None
+    };
+
+    let certificate = PollCertificate {
+        poll_id: poll.id,
+        asset_id: poll.asset_id,
+        snapshot_ledger: snapshot.ledger,
+        total_supply: snapshot.total_supply,
+        owner_count: snapshot.owner_count,
+        vote_counts,
+        total_voters: poll.total_voters,
+        winning_option,
+        approval_percentage: execution_result.approval_percentage,
+        participation_percentage: execution_result.participation_percentage,
+        executed: execution_result.should_execute,
+        action_hash,
+        finalized_at: env.ledger().timestamp(),
+    };
+
+    storage::set_certificate(env, &certificate);
+    events::emit_poll_certified(env, &certificate);
+}
+
+/// Replace a poll's cached supply and owner count with live fractcore values
+pub fn refresh_poll_snapshot(
+    env: &Env,
+    caller: &Address,
+    poll_id: u32,
+) -> Result<PollSnapshot, GovernanceError> {
+    caller.require_auth();
+
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+
+    if *caller != poll.creator && *caller != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    if !poll.is_active {
+        return Err(GovernanceError::PollNotActive);
+    }
+
+    let snapshot = utils::capture_poll_snapshot(env, &poll)?;
+    events::emit_poll_snapshot_refreshed(env, poll_id, &snapshot);
+
+    Ok(snapshot)
+}
+
+/// Check and execute each poll independently, reporting per-item results
+pub fn execute_polls(
+    env: &Env,
+    poll_ids: &Vec<u32>,
+) -> Result<Vec<BatchItemResult>, GovernanceError> {
+    if poll_ids.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut results = Vec::new(env);
+    for poll_id in poll_ids.iter() {
+        let result = match check_and_execute_poll(env, poll_id) {
+            Ok(executed) => BatchItemResult {
+                poll_id,
+                success: true,
+                executed,
+                error_code: None,
+            },
+            Err(err) => BatchItemResult {
+                poll_id,
+                success: false,
+                executed: false,
+                error_code: Some(err as u32),
+            },
+        };
+        results.push_back(result);
+    }
+
+    Ok(results)
+}
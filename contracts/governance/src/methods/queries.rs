@@ -1,6 +1,10 @@
-use soroban_sdk::{Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Vec};
 
-use crate::contract::{GovernanceError, GovernanceParams, Poll, VoteResults};
+use crate::contract::{
+    Cursor, ExecutionRecord, ExecutionResult, GovernanceError, GovernanceParams, Poll,
+    PollCertificate, PollSnapshot, SimulatedOutcome, Vote, VoteReceipt, VoteResults,
+    MAX_BATCH_SIZE, VOTERS_PAGE_SIZE,
+};
 use crate::methods::utils;
 use crate::storage;
 
@@ -12,16 +16,44 @@ pub fn get_asset_polls(env: &Env, asset_id: u64) -> Vec<u32> {
     storage::get_asset_polls(env, asset_id)
 }
 
+/// Up to `limit` poll ids of an asset from `cursor`, oldest first.
+///
+/// An asset's poll list only grows, so the cursor records its length when the
+/// walk started and polls created meanwhile wait for the next walk.
+pub fn get_asset_polls_page(
+    env: &Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<u32>, Option<Cursor>) {
+    let polls = storage::get_asset_polls(env, asset_id);
+    let (start, end, next) = window(cursor, polls.len(), limit, MAX_BATCH_SIZE, u32::MAX);
+    (polls.slice(start..end), next)
+}
+
 pub fn get_active_polls(env: &Env) -> Vec<u32> {
     storage::get_active_polls(env)
 }
 
+pub fn get_active_poll_count(env: &Env) -> u32 {
+    storage::get_active_poll_count(env)
+}
+
+pub fn get_active_polls_page(env: &Env, page: u32) -> Vec<u32> {
+    storage::get_active_poll_count(env);
+    storage::get_active_polls_page(env, page)
+}
+
 pub fn get_vote_results(env: &Env, poll_id: u32) -> Result<VoteResults, GovernanceError> {
     let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
-    let (winning_option, vote_counts) = utils::calculate_vote_results(env, &poll)?;
+    vote_results(env, &poll)
+}
+
+fn vote_results(env: &Env, poll: &Poll) -> Result<VoteResults, GovernanceError> {
+    let (winning_option, vote_counts) = utils::calculate_vote_results(env, poll)?;
 
     Ok(VoteResults {
-        poll_id,
+        poll_id: poll.id,
         vote_counts,
         winning_option,
         total_voters: poll.total_voters,
@@ -29,6 +61,288 @@ pub fn get_vote_results(env: &Env, poll_id: u32) -> Result<VoteResults, Governan
     })
 }
 
+/// Ids of up to MAX_BATCH_SIZE polls created in or after ledger `ledger_seq`, oldest first.
+///
+/// Poll ids are sequential, so a light client that gets a full page reads the
+/// rest from the last id returned up to `get_next_poll_id`.
+pub fn polls_created_since(env: &Env, ledger_seq: u32) -> Vec<u32> {
+    let next_id = storage::get_next_poll_id(env);
+
+    // Creation ledgers never decrease with the poll id
+    let (mut low, mut high) = (1u32, next_id);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if storage::get_poll_created_ledger(env, mid) < ledger_seq {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    let mut poll_ids = Vec::new(env);
+    for poll_id in low..next_id.min(low + MAX_BATCH_SIZE) {
+        poll_ids.push_back(poll_id);
+    }
+    poll_ids
+}
+
+/// Polls for up to MAX_BATCH_SIZE ids, `None` for unknown ids, in id order
+pub fn get_polls_bulk(
+    env: &Env,
+    poll_ids: &Vec<u32>,
+) -> Result<Vec<Option<Poll>>, GovernanceError> {
+    if poll_ids.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut polls = Vec::new(env);
+    for poll_id in poll_ids.iter() {
+        polls.push_back(storage::get_poll(env, poll_id));
+    }
+    Ok(polls)
+}
+
+/// Vote results for up to MAX_BATCH_SIZE polls, `None` for unknown ids, in id order
+pub fn get_vote_results_bulk(
+    env: &Env,
+    poll_ids: &Vec<u32>,
+) -> Result<Vec<Option<VoteResults>>, GovernanceError> {
+    if poll_ids.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut results = Vec::new(env);
+    for poll_id in poll_ids.iter() {
+        match storage::get_poll(env, poll_id) {
+            Some(poll) => results.push_back(Some(vote_results(env, &poll)?)),
+            None => results.push_back(None),
+        }
+    }
+    Ok(results)
+}
+
+/// Outcome of an active poll if up to MAX_BATCH_SIZE more holders voted as given.
+///
+/// Each simulated vote is checked as `vote` would check it and counted with the
+/// holder's poll voting power (checkpoint-capped for checkpointed polls), then the
+/// tallies go through the same execution criteria as a real finalization.
+pub fn simulate_outcome(
+    env: &Env,
+    poll_id: u32,
+    additional_votes: &Vec<(Address, u32)>,
+) -> Result<SimulatedOutcome, GovernanceError> {
+    let mut poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+
+    if !poll.is_active {
+        return Err(GovernanceError::PollNotActive);
+    }
+    if env.ledger().timestamp() >= poll.end_time {
+        return Err(GovernanceError::PollExpired);
+    }
+    if additional_votes.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    for (voter, option_index) in additional_votes.iter() {
+        if option_index >= poll.options.len() || option_index > 1 {
+            return Err(GovernanceError::InvalidOption);
+        }
+        // Covers holders listed twice as well as those who already voted
+        if poll.votes.contains_key(voter.clone()) {
+            return Err(GovernanceError::AlreadyVoted);
+        }
+
+        let voting_power = utils::poll_voting_power(env, &poll, &voter)?;
+        if voting_power == 0 {
+            return Err(GovernanceError::InsufficientVotingPower);
+        }
+
+        poll.votes.set(
+            voter.clone(),
+            Vote {
+                voter,
+                option_index,
+                voting_power,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        poll.total_voters += 1;
+    }
+
+    let params = storage::get_governance_params(env);
+    let (winning_option, vote_counts) = utils::calculate_vote_results(env, &poll)?;
+    let execution = utils::check_execution_criteria(env, &poll, &vote_counts, &params)?;
+    let approve_power_needed = approve_power_needed(&vote_counts, &execution, &params);
+
+    Ok(SimulatedOutcome {
+        poll_id,
+        vote_counts,
+        total_voters: poll.total_voters,
+        winning_option,
+        execution,
+        approve_power_needed,
+    })
+}
+
+/// Smallest further Approve power that makes the poll executable: Approve must
+/// outweigh Deny and reach the threshold share, and turnout must reach quorum of
+/// the active supply. None when a 100% threshold already has Deny votes against it.
+fn approve_power_needed(
+    vote_counts: &Vec<u64>,
+    execution: &ExecutionResult,
+    params: &GovernanceParams,
+) -> Option<u64> {
+    if execution.should_execute {
+        return Some(0);
+    }
+
+    let deny = vote_counts.get(0).unwrap_or(0) as u128;
+    let approve = vote_counts.get(1).unwrap_or(0) as u128;
+    let threshold = params.threshold_percentage as u128;
+    let quorum = params.quorum_percentage as u128;
+
+    let majority = deny + 1;
+    let turnout = (quorum * execution.active_supply as u128).div_ceil(100);
+    let approval = if threshold >= 100 {
+        if deny > 0 {
+            return None;
+        }
+        0
+    } else {
+        (threshold * deny).div_ceil(100 - threshold)
+    };
+
+    // Turnout counts Deny votes too
+    let target = majority.max(approval).max(turnout.saturating_sub(deny));
+    Some(target.saturating_sub(approve).min(u64::MAX as u128) as u64)
+}
+
 pub fn get_governance_params(env: &Env) -> GovernanceParams {
     storage::get_governance_params(env)
 }
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: &Env) -> Option<BytesN<32>> {
+    storage::get_init_config_hash(env)
+}
+
+pub fn get_certificate(env: &Env, poll_id: u32) -> Result<PollCertificate, GovernanceError> {
+    storage::get_certificate(env, poll_id).ok_or(GovernanceError::CertificateNotFound)
+}
+
+pub fn get_execution_record(env: &Env, poll_id: u32) -> Result<ExecutionRecord, GovernanceError> {
+    storage::get_execution_record(env, poll_id).ok_or(GovernanceError::ExecutionRecordNotFound)
+}
+
+pub fn get_poll_snapshot(env: &Env, poll_id: u32) -> Result<PollSnapshot, GovernanceError> {
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+    utils::poll_snapshot(env, &poll)
+}
+
+pub fn get_poll_voting_power(
+    env: &Env,
+    poll_id: u32,
+    voter: &Address,
+) -> Result<u64, GovernanceError> {
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+    utils::poll_voting_power(env, &poll, voter)
+}
+
+pub fn get_vote_receipt(
+    env: &Env,
+    poll_id: u32,
+    voter: &Address,
+) -> Result<VoteReceipt, GovernanceError> {
+    storage::get_vote_receipt(env, poll_id, voter).ok_or(GovernanceError::ReceiptNotFound)
+}
+
+/// Up to `limit` receipts of the votes an address cast, from `cursor`, in voting
+/// order. Votes cast before the per-voter index existed are not listed.
+pub fn get_voter_receipts_page(
+    env: &Env,
+    voter: &Address,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<VoteReceipt>, Option<Cursor>) {
+    let count = storage::get_voter_poll_count(env, voter);
+    let (start, end, next) = window(cursor, count, limit, MAX_BATCH_SIZE, u32::MAX);
+
+    let mut receipts = Vec::new(env);
+    for index in start..end {
+        if let Some(receipt) = storage::get_voter_poll(env, voter, index)
+            .and_then(|poll_id| storage::get_vote_receipt(env, poll_id, voter))
+        {
+            receipts.push_back(receipt);
+        }
+    }
+    (receipts, next)
+}
+
+/// Up to `limit` voters for an option from `cursor`, in voting order. Voters are
+/// only appended, so the walk stops at the count it started on.
+pub fn get_voters_for_option(
+    env: &Env,
+    poll_id: u32,
+    option_index: u32,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<(Vec<Address>, Option<Cursor>), GovernanceError> {
+    let poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
+    if option_index >= poll.options.len() {
+        return Err(GovernanceError::InvalidOption);
+    }
+
+    let count = storage::get_option_voter_count(env, poll_id, option_index);
+    let (start, end, next) = window(cursor, count, limit, VOTERS_PAGE_SIZE, VOTERS_PAGE_SIZE);
+
+    let mut voters = Vec::new(env);
+    let mut page = Vec::new(env);
+    for index in start..end {
+        let position = index % VOTERS_PAGE_SIZE;
+        if index == start || position == 0 {
+            page = storage::get_option_voters(env, poll_id, option_index, index / VOTERS_PAGE_SIZE);
+        }
+        if let Some(voter) = page.get(position) {
+            voters.push_back(voter);
+        }
+    }
+    Ok((voters, next))
+}
+
+/// Items `[start, end)` of an append-only list of `len` items, stored `page_size`
+/// per page, to serve from `cursor`, and the cursor after them. The walk never
+/// passes the length it started on. `limit` 0 means `max`.
+fn window(
+    cursor: Option<Cursor>,
+    len: u32,
+    limit: u32,
+    max: u32,
+    page_size: u32,
+) -> (u32, u32, Option<Cursor>) {
+    let snapshot = cursor
+        .as_ref()
+        .map_or(len as u64, |cursor| cursor.snapshot)
+        .min(len as u64) as u32;
+    let start = cursor
+        .map_or(0, |cursor| {
+            cursor
+                .page
+                .saturating_mul(page_size)
+                .saturating_add(cursor.position)
+        })
+        .min(snapshot);
+    let limit = if limit == 0 || limit > max {
+        max
+    } else {
+        limit
+    };
+    let end = snapshot.min(start + limit);
+
+    let next = (end < snapshot).then_some(Cursor {
+        page: end / page_size,
+        position: end % page_size,
+        snapshot: snapshot as u64,
+    });
+    (start, end, next)
+}
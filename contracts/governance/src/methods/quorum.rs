@@ -0,0 +1,59 @@
+use soroban_sdk::{Address, Env};
+
+use crate::contract::{GovernanceError, Poll, QuorumMode, MAX_QUORUM_EXCLUSIONS};
+use crate::events;
+use crate::methods::{activity, utils};
+use crate::storage;
+
+/// Choose the supply an asset's quorum is measured against (fractcore asset manager only).
+///
+/// Polls capture the figure in their snapshot, so a change only affects polls
+/// created (or snapshots refreshed) afterwards.
+pub fn set_quorum_mode(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    mode: &QuorumMode,
+) -> Result<(), GovernanceError> {
+    caller.require_auth();
+
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let manager = utils::call_fractcore_asset_manager(env, &fractcore_contract, asset_id)?;
+    if *caller != manager {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    if let QuorumMode::ActiveSupply(excluded) = mode {
+        if excluded.is_empty() || excluded.len() > MAX_QUORUM_EXCLUSIONS {
+            return Err(GovernanceError::InvalidParameters);
+        }
+        for (i, address) in excluded.iter().enumerate() {
+            if excluded.iter().skip(i + 1).any(|other| other == address) {
+                return Err(GovernanceError::InvalidParameters);
+            }
+        }
+    }
+
+    storage::set_quorum_mode(env, asset_id, mode);
+    activity::note_asset(env, asset_id);
+    events::emit_quorum_mode_updated(env, asset_id, mode);
+
+    Ok(())
+}
+
+/// Voting supply minus the voting power of the asset's excluded addresses; the
+/// full voting supply when the asset uses `TotalSupply`
+pub fn active_supply(env: &Env, poll: &Poll, total_supply: u64) -> Result<u64, GovernanceError> {
+    let excluded = match storage::get_quorum_mode(env, poll.asset_id) {
+        QuorumMode::TotalSupply => return Ok(total_supply),
+        QuorumMode::ActiveSupply(excluded) => excluded,
+    };
+
+    let classes = utils::vote_classes(env, poll.asset_id, &poll.weights);
+    let mut inactive = 0u64;
+    for address in excluded.iter() {
+        inactive = inactive.saturating_add(utils::voting_power(env, &classes, &address)?);
+    }
+
+    Ok(total_supply.saturating_sub(inactive))
+}
@@ -0,0 +1,118 @@
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+use crate::contract::{GovernanceError, RelayConfig, MAX_RELAYER_PAYOUTS_PER_DAY};
+use crate::events;
+use crate::methods::{admin_log, voting};
+use crate::storage;
+
+/// Configure which token reimburses relayers and how much per relayed vote
+pub fn set_relay_config(
+    env: &Env,
+    admin: &Address,
+    token: &Address,
+    fee: i128,
+) -> Result<(), GovernanceError> {
+    admin.require_auth();
+
+    if *admin != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+    admin_log::record(env, admin, "set_relay_config", (token.clone(), fee));
+
+    if fee < 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    if let Some(current) = storage::get_relay_config(env) {
+        // Pool balance is denominated in the old token
+        if current.token != *token && storage::get_relay_pool(env) > 0 {
+            return Err(GovernanceError::InvalidParameters);
+        }
+    }
+
+    storage::set_relay_config(
+        env,
+        &RelayConfig {
+            token: token.clone(),
+            fee,
+        },
+    );
+
+    Ok(())
+}
+
+/// Anyone can top up the relay pool in the configured fee token
+pub fn fund_relay_pool(env: &Env, funder: &Address, amount: i128) -> Result<(), GovernanceError> {
+    funder.require_auth();
+
+    if amount <= 0 {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let config = storage::get_relay_config(env).ok_or(GovernanceError::NotInitialized)?;
+    TokenClient::new(env, &config.token).transfer(funder, &env.current_contract_address(), &amount);
+
+    storage::set_relay_pool(env, storage::get_relay_pool(env) + amount);
+
+    Ok(())
+}
+
+/// Vote submitted by a relayer that pays the transaction fee.
+///
+/// The voter signs this invocation off-chain, relayer included, and the
+/// signature and nonce are checked by the voter's authorization entry; the
+/// relayer signs it as well, so no one is paid under an address they do not
+/// control. Each vote can only be cast once, so the relayer is reimbursed at
+/// most once per voter and poll, for at most MAX_RELAYER_PAYOUTS_PER_DAY votes
+/// a day, and never more than the pool holds.
+pub fn relay_vote(
+    env: &Env,
+    relayer: &Address,
+    voter: &Address,
+    poll_id: u32,
+    option_index: u32,
+) -> Result<(), GovernanceError> {
+    relayer.require_auth();
+
+    voting::vote(env, voter, poll_id, option_index)?;
+
+    pay_relayer(env, relayer, voter, poll_id);
+
+    Ok(())
+}
+
+fn pay_relayer(env: &Env, relayer: &Address, voter: &Address, poll_id: u32) {
+    let config = match storage::get_relay_config(env) {
+        Some(config) => config,
+        None => return,
+    };
+
+    // Voting for yourself through your own relayer earns nothing
+    if relayer == voter {
+        return;
+    }
+
+    let pool = storage::get_relay_pool(env);
+    let payout = config.fee.min(pool);
+    if payout <= 0 {
+        return;
+    }
+
+    // Votes past the relayer's daily allowance are cast unpaid
+    let day = env.ledger().timestamp() / (24 * 60 * 60);
+    let (last_day, payouts) = storage::get_relayer_payouts(env, relayer);
+    let payouts = if last_day == day { payouts } else { 0 };
+    if payouts >= MAX_RELAYER_PAYOUTS_PER_DAY {
+        return;
+    }
+    storage::set_relayer_payouts(env, relayer, day, payouts + 1);
+
+    storage::set_relay_pool(env, pool - payout);
+    TokenClient::new(env, &config.token).transfer(
+        &env.current_contract_address(),
+        relayer,
+        &payout,
+    );
+
+    events::emit_relayer_paid(env, relayer, voter, poll_id, payout);
+}
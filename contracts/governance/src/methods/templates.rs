@@ -0,0 +1,173 @@
+use soroban_sdk::{Address, Env, String, Vec};
+
+use crate::contract::{
+    GovernanceError, PollAction, PollTemplate, MAX_TEMPLATES_PER_ASSET, MAX_TEMPLATE_TITLE_LEN,
+};
+use crate::events;
+use crate::methods::{actions, activity, polls, utils};
+use crate::storage;
+
+/// Store a reusable poll for the asset.
+///
+/// Anyone who could create the poll itself (a holder of the asset or the admin)
+/// can store it. The action is checked against the asset's allowlist now and
+/// again whenever a poll is made from the template.
+pub fn create_poll_template(
+    env: &Env,
+    caller: &Address,
+    asset_id: u64,
+    title_pattern: &String,
+    description: &String,
+    action: &PollAction,
+    duration_days: Option<u32>,
+) -> Result<u32, GovernanceError> {
+    caller.require_auth();
+
+    actions::require_action_allowed(env, asset_id, action)?;
+
+    let classes = utils::vote_classes(env, asset_id, &Vec::new(env));
+    if utils::voting_power(env, &classes, caller)? == 0 && *caller != storage::get_admin(env) {
+        return Err(GovernanceError::InsufficientVotingPower);
+    }
+
+    if title_pattern.is_empty() || title_pattern.len() > MAX_TEMPLATE_TITLE_LEN {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let duration_days =
+        duration_days.unwrap_or(storage::get_governance_params(env).default_expiry_days);
+    if duration_days == 0 || duration_days > 365 {
+        return Err(GovernanceError::InvalidDuration);
+    }
+
+    let mut templates = storage::get_asset_templates(env, asset_id);
+    if templates.len() >= MAX_TEMPLATES_PER_ASSET {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let template_id = storage::next_template_id(env);
+    storage::set_poll_template(
+        env,
+        template_id,
+        &PollTemplate {
+            asset_id,
+            creator: caller.clone(),
+            title_pattern: title_pattern.clone(),
+            description: description.clone(),
+            action: action.clone(),
+            duration_days,
+            uses: 0,
+        },
+    );
+    templates.push_back(template_id);
+    storage::set_asset_templates(env, asset_id, &templates);
+    activity::note_asset(env, asset_id);
+
+    events::emit_template_created(env, template_id, asset_id, caller);
+
+    Ok(template_id)
+}
+
+/// Create a poll from a template, as `create_poll` would with the template's
+/// settings: the caller needs voting power and the poll gets fresh snapshots.
+///
+/// `amount_override` replaces the amount of a DistributeFunds action and is
+/// refused for any other action.
+pub fn create_poll_from_template(
+    env: &Env,
+    caller: &Address,
+    template_id: u32,
+    amount_override: Option<u128>,
+) -> Result<u32, GovernanceError> {
+    let mut template =
+        storage::get_poll_template(env, template_id).ok_or(GovernanceError::TemplateNotFound)?;
+
+    let action = match (amount_override, &template.action) {
+        (None, action) => action.clone(),
+        (Some(amount), PollAction::DistributeFunds(_, description)) if amount > 0 => {
+            PollAction::DistributeFunds(amount, description.clone())
+        }
+        _ => return Err(GovernanceError::InvalidParameters),
+    };
+
+    let title = render_title(env, &template.title_pattern, template.uses + 1);
+    let poll_id = polls::create_poll(
+        env,
+        caller,
+        template.asset_id,
+        &title,
+        &template.description,
+        &action,
+        Some(template.duration_days),
+    )?;
+
+    template.uses += 1;
+    storage::set_poll_template(env, template_id, &template);
+
+    events::emit_template_used(env, template_id, poll_id);
+
+    Ok(poll_id)
+}
+
+/// Delete a template; polls already made from it are unaffected
+pub fn remove_poll_template(
+    env: &Env,
+    caller: &Address,
+    template_id: u32,
+) -> Result<(), GovernanceError> {
+    caller.require_auth();
+
+    let template =
+        storage::get_poll_template(env, template_id).ok_or(GovernanceError::TemplateNotFound)?;
+    if *caller != template.creator && *caller != storage::get_admin(env) {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    storage::remove_poll_template(env, template_id, template.asset_id);
+
+    events::emit_template_removed(env, template_id, template.asset_id);
+
+    Ok(())
+}
+
+/// The pattern with its first "{n}" replaced by `number`
+fn render_title(env: &Env, pattern: &String, number: u32) -> String {
+    const PLACEHOLDER: &[u8] = b"{n}";
+
+    let len = pattern.len() as usize;
+    let mut source = [0u8; MAX_TEMPLATE_TITLE_LEN as usize];
+    pattern.copy_into_slice(&mut source[..len]);
+
+    let position = match source[..len]
+        .windows(PLACEHOLDER.len())
+        .position(|window| window == PLACEHOLDER)
+    {
+        Some(position) => position,
+        None => return pattern.clone(),
+    };
+
+    let mut digits = [0u8; 10];
+    let mut digit_count = 0;
+    let mut rest = number;
+    loop {
+        digits[digit_count] = b'0' + (rest % 10) as u8;
+        digit_count += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+
+    let mut title = [0u8; MAX_TEMPLATE_TITLE_LEN as usize + 10];
+    title[..position].copy_from_slice(&source[..position]);
+    let mut end = position;
+    for digit in digits[..digit_count].iter().rev() {
+        title[end] = *digit;
+        end += 1;
+    }
+    let suffix = &source[position + PLACEHOLDER.len()..len];
+    title[end..end + suffix.len()].copy_from_slice(suffix);
+    end += suffix.len();
+
+    String::from_bytes(env, &title[..end])
+}
@@ -1,6 +1,10 @@
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{contractclient, Address, BytesN, Env, String, Symbol, Vec};
 
-use crate::contract::{ExecutionResult, GovernanceError, GovernanceParams, Poll, PollAction};
+use crate::contract::{
+    ActionOutput, DistributionOutcome, ExecutionResult, GovernanceError, GovernanceParams, Poll,
+    PollAction, PollSnapshot, VoteWeight, MAX_VOTE_CLASSES,
+};
+use crate::methods::{actions, admin, quorum};
 use crate::storage;
 
 // Cross-contract modules
@@ -12,9 +16,38 @@ mod funding_import {
     soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/funding.wasm");
 }
 
+// Some trading entry points take more arguments than clippy allows
+#[allow(clippy::too_many_arguments)]
+mod trading_import {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/trading.wasm");
+}
+
+// Keeper schedule kept by the registry contract; only the generated client is used
+#[allow(dead_code)]
+#[contractclient(name = "ScheduleClient")]
+pub trait ScheduleInterface {
+    fn schedule(env: Env, contract: Address, action_type: Symbol, key: u64, due_at: u64);
+    fn unschedule(env: Env, contract: Address, action_type: Symbol, key: u64) -> bool;
+}
+
 // Cross-contract clients
 pub type FractcoreClient<'a> = fractcore_import::Client<'a>;
 pub type FundingClient<'a> = funding_import::Client<'a>;
+pub type TradingClient<'a> = trading_import::Client<'a>;
+
+/// Outcome of a call whose callee could not be invoked at all. Unit tests
+/// register governance against placeholder addresses and read `stand_in`;
+/// deployed contracts treat it as a failed call, so no vote or action ever
+/// rests on a made-up balance or a skipped transfer.
+#[cfg(test)]
+fn unreachable_callee<T>(stand_in: T) -> Result<T, GovernanceError> {
+    Ok(stand_in)
+}
+
+#[cfg(not(test))]
+fn unreachable_callee<T>(_stand_in: T) -> Result<T, GovernanceError> {
+    Err(GovernanceError::CrossContractCallFailed)
+}
 
 // Cross-contract calls
 pub fn call_fractcore_balance(
@@ -27,9 +60,28 @@ pub fn call_fractcore_balance(
     match client.try_balance_of(owner, &asset_id) {
         Ok(Ok(balance)) => Ok(balance),
         Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
-        Err(_) => {
-            Ok(1000) // Fallback for unit tests only
+        Err(_) => unreachable_callee(1000),
+    }
+}
+
+/// A holder's recorded balance changes; empty for holders that predate balance history
+pub fn call_fractcore_balance_checkpoints(
+    env: &Env,
+    fractcore_contract: &Address,
+    owner: &Address,
+    asset_id: u64,
+) -> Result<Vec<(u64, u64)>, GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_balance_checkpoints(owner, &asset_id) {
+        Ok(Ok(checkpoints)) => {
+            let mut history = Vec::new(env);
+            for checkpoint in checkpoints.iter() {
+                history.push_back((checkpoint.timestamp, checkpoint.balance));
+            }
+            Ok(history)
         }
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(Vec::new(env)),
     }
 }
 
@@ -42,9 +94,7 @@ pub fn call_fractcore_owner_count(
     match client.try_get_asset_owner_count(&asset_id) {
         Ok(Ok(count)) => Ok(count),
         Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
-        Err(_) => {
-            Ok(10) // Fallback for unit tests only
-        }
+        Err(_) => unreachable_callee(10),
     }
 }
 
@@ -57,7 +107,20 @@ pub fn call_fractcore_total_supply(
     match client.try_asset_supply(&asset_id) {
         Ok(Ok(supply)) => Ok(supply),
         Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
-        Err(_) => Ok(10000), // Fallback for unit tests only
+        Err(_) => unreachable_callee(10000),
+    }
+}
+
+pub fn call_fractcore_asset_exists(
+    env: &Env,
+    fractcore_contract: &Address,
+    asset_id: u64,
+) -> Result<bool, GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_asset_exists(&asset_id) {
+        Ok(Ok(exists)) => Ok(exists),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(true),
     }
 }
 
@@ -79,7 +142,119 @@ pub fn call_fractcore_transfer(
     ) {
         Ok(Ok(_)) => Ok(()),
         Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
-        Err(_) => Ok(()), // Fallback for unit tests only
+        Err(_) => unreachable_callee(()),
+    }
+}
+
+pub fn call_fractcore_asset_manager(
+    env: &Env,
+    fractcore_contract: &Address,
+    asset_id: u64,
+) -> Result<Address, GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_asset_manager(&asset_id) {
+        Ok(Ok(manager)) => Ok(manager),
+        // No fallback: an unknown manager must not be treated as authorized
+        _ => Err(GovernanceError::CrossContractCallFailed),
+    }
+}
+
+pub fn call_fractcore_open_transfer_lock(
+    env: &Env,
+    fractcore_contract: &Address,
+    asset_id: u64,
+    poll_id: u32,
+    all_holders: bool,
+    until: u64,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_open_transfer_lock(
+        &env.current_contract_address(),
+        &asset_id,
+        &poll_id,
+        &all_holders,
+        &until,
+    ) {
+        Ok(Ok(())) => Ok(()),
+        // No fallback: a poll advertised as locked must actually lock transfers
+        _ => Err(GovernanceError::TransferLockNotPermitted),
+    }
+}
+
+pub fn call_fractcore_lock_holder(
+    env: &Env,
+    fractcore_contract: &Address,
+    asset_id: u64,
+    poll_id: u32,
+    holder: &Address,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_lock_holder_transfers(
+        &env.current_contract_address(),
+        &asset_id,
+        &poll_id,
+        holder,
+    ) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(GovernanceError::TransferLockNotPermitted),
+    }
+}
+
+/// Best effort: a lock that cannot be released still lapses at the poll's end time
+pub fn call_fractcore_release_transfer_lock(
+    env: &Env,
+    fractcore_contract: &Address,
+    asset_id: u64,
+    poll_id: u32,
+) {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    let _ = client.try_release_transfer_lock(&env.current_contract_address(), &asset_id, &poll_id);
+}
+
+pub fn call_fractcore_split(
+    env: &Env,
+    fractcore_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    factor: u64,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_split_asset(caller, &asset_id, &factor) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(()),
+    }
+}
+
+pub fn call_fractcore_revert_uri(
+    env: &Env,
+    fractcore_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    version: u32,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_revert_asset_uri(caller, &asset_id, &version) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(()),
+    }
+}
+
+pub fn call_fractcore_announce(
+    env: &Env,
+    fractcore_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    title: &String,
+    content_hash: &BytesN<32>,
+    uri: &String,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, fractcore_contract);
+    match client.try_post_announcement(caller, &asset_id, title, content_hash, uri) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(()),
     }
 }
 
@@ -90,12 +265,129 @@ pub fn call_funding_distribute(
     asset_id: u64,
     amount: u128,
     description: String,
-) -> Result<(), GovernanceError> {
+) -> Result<ActionOutput, GovernanceError> {
     let client = FundingClient::new(env, funding_contract);
     match client.try_distribute_funds(caller, &asset_id, &amount, &description) {
+        Ok(Ok(outcome)) => Ok(ActionOutput::Distribution(DistributionOutcome {
+            distribution_id: outcome.distribution_id,
+            requested: outcome.requested,
+            fee: outcome.fee,
+            distributed: outcome.distributed,
+            recipients: outcome.recipients,
+            dust: outcome.dust,
+        })),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(ActionOutput::None),
+    }
+}
+
+pub fn call_funding_set_petty_cash(
+    env: &Env,
+    funding_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    threshold: u128,
+    monthly_cap: u128,
+) -> Result<(), GovernanceError> {
+    let client = FundingClient::new(env, funding_contract);
+    match client.try_set_petty_cash(caller, &asset_id, &threshold, &monthly_cap) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn call_trading_apply_config(
+    env: &Env,
+    trading_contract: &Address,
+    caller: &Address,
+    fee_bps: u32,
+    fee_recipient: &Address,
+    min_seconds: u64,
+    max_seconds: u64,
+) -> Result<(), GovernanceError> {
+    let config = trading_import::TradingConfig {
+        fee: trading_import::TradingFee {
+            bps: fee_bps,
+            recipient: fee_recipient.clone(),
+        },
+        duration_limits: trading_import::SaleDurationLimits {
+            min_seconds,
+            max_seconds,
+        },
+    };
+    let client = TradingClient::new(env, trading_contract);
+    match client.try_apply_trading_config(caller, &config) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(GovernanceError::CrossContractCallFailed),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn call_trading_set_exit_queue(
+    env: &Env,
+    trading_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    size_threshold: u64,
+    tranche: u64,
+    window_seconds: u64,
+) -> Result<(), GovernanceError> {
+    let config = (tranche > 0).then_some(trading_import::ExitQueueConfig {
+        size_threshold,
+        tranche,
+        window_seconds,
+    });
+    let client = TradingClient::new(env, trading_contract);
+    match client.try_set_exit_queue(caller, &asset_id, &config) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(GovernanceError::CrossContractCallFailed),
+    }
+}
+
+/// Fractcore, funding and trading share the `upgrade(caller, new_wasm_hash)`
+/// entry point, so one client reaches any of them
+pub fn call_contract_upgrade(
+    env: &Env,
+    target: &Address,
+    caller: &Address,
+    new_wasm_hash: &BytesN<32>,
+) -> Result<(), GovernanceError> {
+    let client = FractcoreClient::new(env, target);
+    match client.try_upgrade(caller, new_wasm_hash) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(GovernanceError::CrossContractCallFailed),
+    }
+}
+
+pub fn call_funding_release_earmark(
+    env: &Env,
+    funding_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    earmark_id: u32,
+) -> Result<(), GovernanceError> {
+    let client = FundingClient::new(env, funding_contract);
+    match client.try_release_earmark(caller, &asset_id, &earmark_id) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
+        Err(_) => unreachable_callee(()),
+    }
+}
+
+pub fn call_funding_veto_emergency_withdraw(
+    env: &Env,
+    funding_contract: &Address,
+    caller: &Address,
+    asset_id: u64,
+    withdrawal_id: u32,
+) -> Result<(), GovernanceError> {
+    let client = FundingClient::new(env, funding_contract);
+    match client.try_veto_emergency_withdraw(caller, &asset_id, &withdrawal_id) {
         Ok(Ok(_)) => Ok(()),
         Ok(Err(_)) => Err(GovernanceError::CrossContractCallFailed),
-        Err(_) => Ok(()), // Fallback for unit tests only
+        Err(_) => unreachable_callee(()),
     }
 }
 
@@ -124,6 +416,153 @@ pub fn calculate_vote_results(env: &Env, poll: &Poll) -> Result<(u32, Vec<u64>),
     Ok((winning_option, vote_counts))
 }
 
+/// Share classes that carry voting power: the configured weights, or the
+/// poll's own asset at weight 1
+pub fn vote_classes(env: &Env, asset_id: u64, weights: &Vec<VoteWeight>) -> Vec<VoteWeight> {
+    if !weights.is_empty() {
+        return weights.clone();
+    }
+
+    let mut classes = Vec::new(env);
+    classes.push_back(VoteWeight {
+        asset_id,
+        multiplier: 1,
+    });
+    classes
+}
+
+/// Weights must name distinct, existing assets with a non-zero multiplier
+pub fn validate_vote_weights(env: &Env, weights: &Vec<VoteWeight>) -> Result<(), GovernanceError> {
+    if weights.is_empty() || weights.len() > MAX_VOTE_CLASSES {
+        return Err(GovernanceError::InvalidParameters);
+    }
+
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    for (i, weight) in weights.iter().enumerate() {
+        if weight.multiplier == 0 {
+            return Err(GovernanceError::InvalidParameters);
+        }
+        for other in weights.iter().skip(i + 1) {
+            if other.asset_id == weight.asset_id {
+                return Err(GovernanceError::InvalidParameters);
+            }
+        }
+        if !call_fractcore_asset_exists(env, &fractcore_contract, weight.asset_id)? {
+            return Err(GovernanceError::InvalidParameters);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sum of `balance(asset) * multiplier` over every share class
+pub fn voting_power(
+    env: &Env,
+    classes: &Vec<VoteWeight>,
+    voter: &Address,
+) -> Result<u64, GovernanceError> {
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let mut power = 0u64;
+    for class in classes.iter() {
+        let balance = call_fractcore_balance(env, &fractcore_contract, voter, class.asset_id)?;
+        power = power.saturating_add(balance.saturating_mul(class.multiplier as u64));
+    }
+    Ok(power)
+}
+
+/// Voting power of a voter in a poll: live balances, or for checkpointed polls the
+/// lower of the balance at creation and at the latest checkpoint, per share class
+pub fn poll_voting_power(env: &Env, poll: &Poll, voter: &Address) -> Result<u64, GovernanceError> {
+    let classes = vote_classes(env, poll.asset_id, &poll.weights);
+    let interval = match storage::get_poll_checkpoint_interval(env, poll.id) {
+        Some(interval) => interval,
+        None => return voting_power(env, &classes, voter),
+    };
+
+    let checkpoint = latest_checkpoint(env, poll, interval);
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let mut power = 0u64;
+    for class in classes.iter() {
+        let history =
+            call_fractcore_balance_checkpoints(env, &fractcore_contract, voter, class.asset_id)?;
+        let balance = if history.is_empty() {
+            call_fractcore_balance(env, &fractcore_contract, voter, class.asset_id)?
+        } else {
+            balance_at(&history, poll.start_time).min(balance_at(&history, checkpoint))
+        };
+        power = power.saturating_add(balance.saturating_mul(class.multiplier as u64));
+    }
+    Ok(power)
+}
+
+/// Most recent checkpoint time of a poll: its start time plus whole intervals
+pub fn latest_checkpoint(env: &Env, poll: &Poll, interval: u64) -> u64 {
+    let elapsed = env.ledger().timestamp().saturating_sub(poll.start_time);
+    poll.start_time + elapsed / interval * interval
+}
+
+/// Balance after the last change at or before `time`; nothing before the oldest
+/// retained change, as fractcore treats it for time-weighted balances
+fn balance_at(history: &Vec<(u64, u64)>, time: u64) -> u64 {
+    let mut balance = 0;
+    for (timestamp, recorded) in history.iter() {
+        if timestamp > time {
+            break;
+        }
+        balance = recorded;
+    }
+    balance
+}
+
+/// Voting power held by all holders, used as the quorum base
+pub fn voting_supply(env: &Env, poll: &Poll) -> Result<u64, GovernanceError> {
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let mut supply = 0u64;
+    for class in vote_classes(env, poll.asset_id, &poll.weights).iter() {
+        let class_supply = call_fractcore_total_supply(env, &fractcore_contract, class.asset_id)?;
+        supply = supply.saturating_add(class_supply.saturating_mul(class.multiplier as u64));
+    }
+    Ok(supply)
+}
+
+/// Owners across all share classes; holders of several classes are counted once
+/// per class, so this never ends a poll early
+pub fn voter_count(env: &Env, poll: &Poll) -> Result<u32, GovernanceError> {
+    let fractcore_contract = storage::get_fractcore_contract(env);
+    let mut owners = 0u32;
+    for class in vote_classes(env, poll.asset_id, &poll.weights).iter() {
+        owners = owners.saturating_add(call_fractcore_owner_count(
+            env,
+            &fractcore_contract,
+            class.asset_id,
+        )?);
+    }
+    Ok(owners)
+}
+
+/// Read supply and owner count from fractcore and cache them for the poll
+pub fn capture_poll_snapshot(env: &Env, poll: &Poll) -> Result<PollSnapshot, GovernanceError> {
+    let total_supply = voting_supply(env, poll)?;
+    let snapshot = PollSnapshot {
+        total_supply,
+        active_supply: quorum::active_supply(env, poll, total_supply)?,
+        owner_count: voter_count(env, poll)?,
+        ledger: env.ledger().sequence(),
+        captured_at: env.ledger().timestamp(),
+    };
+    storage::set_poll_snapshot(env, poll.id, &snapshot);
+    Ok(snapshot)
+}
+
+/// Cached figures for the poll, captured on first use for polls created before
+/// snapshots existed
+pub fn poll_snapshot(env: &Env, poll: &Poll) -> Result<PollSnapshot, GovernanceError> {
+    match storage::get_poll_snapshot(env, poll.id) {
+        Some(snapshot) => Ok(snapshot),
+        None => capture_poll_snapshot(env, poll),
+    }
+}
+
 pub fn check_execution_criteria(
     env: &Env,
     poll: &Poll,
@@ -134,21 +573,17 @@ pub fn check_execution_criteria(
     let approve_votes = vote_counts.get(1).unwrap_or(0);
     let total_votes = deny_votes + approve_votes;
 
-    let approval_percentage = if total_votes > 0 {
-        (approve_votes * 100) / total_votes
-    } else {
-        0
-    };
+    let approval_percentage = (approve_votes * 100).checked_div(total_votes).unwrap_or(0);
 
-    let fractcore_contract = storage::get_fractcore_contract(env);
-    let total_supply = call_fractcore_total_supply(env, &fractcore_contract, poll.asset_id)?;
-    let participation_percentage = if total_supply > 0 {
-        (total_votes * 100) / total_supply
-    } else {
-        0
-    };
+    let snapshot = poll_snapshot(env, poll)?;
+    let participation_percentage = (total_votes * 100)
+        .checked_div(snapshot.total_supply)
+        .unwrap_or(0);
+    let active_participation = (total_votes * 100)
+        .checked_div(snapshot.active_supply)
+        .unwrap_or(0);
 
-    let meets_quorum = participation_percentage >= params.quorum_percentage as u64;
+    let meets_quorum = active_participation >= params.quorum_percentage as u64;
     let meets_threshold = approval_percentage >= params.threshold_percentage as u64;
 
     // Only execute if Approve wins AND meets quorum/threshold requirements
@@ -158,6 +593,9 @@ pub fn check_execution_criteria(
         should_execute: approve_wins && meets_quorum && meets_threshold,
         approval_percentage: approval_percentage as u32,
         participation_percentage: participation_percentage as u32,
+        active_participation: active_participation as u32,
+        total_supply: snapshot.total_supply,
+        active_supply: snapshot.active_supply,
     })
 }
 
@@ -166,9 +604,9 @@ pub fn execute_poll_action(
     action: &PollAction,
     asset_id: u64,
     governance_contract: &Address,
-) -> Result<(), GovernanceError> {
+) -> Result<ActionOutput, GovernanceError> {
     match action {
-        PollAction::NoExecution => Ok(()),
+        PollAction::NoExecution => Ok(ActionOutput::None),
         PollAction::DistributeFunds(amount, description) => {
             let funding_contract = storage::get_funding_contract(env);
             call_funding_distribute(
@@ -178,8 +616,7 @@ pub fn execute_poll_action(
                 asset_id,
                 *amount,
                 description.clone(),
-            )?;
-            Ok(())
+            )
         }
         PollAction::TransferTokens(to, amount) => {
             let fractcore_contract = storage::get_fractcore_contract(env);
@@ -193,7 +630,120 @@ pub fn execute_poll_action(
                 asset_id,
                 *amount,
             )?;
-            Ok(())
+            Ok(ActionOutput::None)
+        }
+        PollAction::SplitAsset(factor) => {
+            let fractcore_contract = storage::get_fractcore_contract(env);
+            // Large holder sets finish through fractcore's continue_split
+            call_fractcore_split(
+                env,
+                &fractcore_contract,
+                governance_contract,
+                asset_id,
+                *factor,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::SetAllowedActions(allowed) => {
+            actions::store_allowed_actions(env, asset_id, *allowed)?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::SetPettyCash(threshold, monthly_cap) => {
+            let funding_contract = storage::get_funding_contract(env);
+            call_funding_set_petty_cash(
+                env,
+                &funding_contract,
+                governance_contract,
+                asset_id,
+                *threshold,
+                *monthly_cap,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::PostAnnouncement(title, content_hash, uri) => {
+            let fractcore_contract = storage::get_fractcore_contract(env);
+            call_fractcore_announce(
+                env,
+                &fractcore_contract,
+                governance_contract,
+                asset_id,
+                title,
+                content_hash,
+                uri,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::RevertAssetUri(version) => {
+            let fractcore_contract = storage::get_fractcore_contract(env);
+            call_fractcore_revert_uri(
+                env,
+                &fractcore_contract,
+                governance_contract,
+                asset_id,
+                *version,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::ReleaseEarmark(earmark_id) => {
+            let funding_contract = storage::get_funding_contract(env);
+            call_funding_release_earmark(
+                env,
+                &funding_contract,
+                governance_contract,
+                asset_id,
+                *earmark_id,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::VetoEmergencyWithdraw(withdrawal_id) => {
+            let funding_contract = storage::get_funding_contract(env);
+            call_funding_veto_emergency_withdraw(
+                env,
+                &funding_contract,
+                governance_contract,
+                asset_id,
+                *withdrawal_id,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::SetTradingConfig(fee_bps, fee_recipient, min_seconds, max_seconds) => {
+            // The platform asset may have been reassigned since the poll was created
+            let trading_contract = actions::platform_trading_contract(env, asset_id)?;
+            call_trading_apply_config(
+                env,
+                &trading_contract,
+                governance_contract,
+                *fee_bps,
+                fee_recipient,
+                *min_seconds,
+                *max_seconds,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::SetExitQueue(size_threshold, tranche, window_seconds) => {
+            let trading_contract = actions::trading_contract(env)?;
+            call_trading_set_exit_queue(
+                env,
+                &trading_contract,
+                governance_contract,
+                asset_id,
+                *size_threshold,
+                *tranche,
+                *window_seconds,
+            )?;
+            Ok(ActionOutput::None)
+        }
+        PollAction::UpgradeContract(target, new_wasm_hash) => {
+            // The platform asset or contracts may have been reassigned since the poll was created
+            actions::require_upgrade_target(env, asset_id, target)?;
+            if *target == env.current_contract_address() {
+                admin::upgrade(env, new_wasm_hash);
+            } else {
+                call_contract_upgrade(env, target, governance_contract, new_wasm_hash)?;
+            }
+            Ok(ActionOutput::None)
         }
+        // Left active so the version that wrote it can execute it after an upgrade
+        PollAction::Reserved(_, _) => Err(GovernanceError::UnsupportedAction),
     }
 }
@@ -1,6 +1,8 @@
-use soroban_sdk::{panic_with_error, Address, Env};
+use soroban_sdk::{Address, Env, Vec};
 
-use crate::contract::{GovernanceError, Vote};
+use crate::contract::{
+    BatchItemResult, GovernanceError, TransferLockScope, Vote, VoteReceipt, MAX_BATCH_SIZE,
+};
 use crate::events;
 use crate::methods::{polls, utils};
 use crate::storage;
@@ -13,31 +15,90 @@ pub fn vote(
 ) -> Result<(), GovernanceError> {
     voter.require_auth();
 
+    record_vote(env, voter, poll_id, option_index)?;
+
+    polls::check_and_execute_poll(env, poll_id)?;
+
+    Ok(())
+}
+
+/// Cast several votes for one voter. Each item is validated and recorded on its
+/// own, so one bad item only fails that item. A poll whose execution fails after
+/// the vote is recorded stays active and can be executed later.
+pub fn vote_batch(
+    env: &Env,
+    voter: &Address,
+    votes: &Vec<(u32, u32)>,
+) -> Result<Vec<BatchItemResult>, GovernanceError> {
+    voter.require_auth();
+
+    if votes.len() > MAX_BATCH_SIZE {
+        return Err(GovernanceError::BatchTooLarge);
+    }
+
+    let mut results = Vec::new(env);
+    for (poll_id, option_index) in votes.iter() {
+        let result = match record_vote(env, voter, poll_id, option_index) {
+            Ok(()) => BatchItemResult {
+                poll_id,
+                success: true,
+                executed: matches!(polls::check_and_execute_poll(env, poll_id), Ok(true)),
+                error_code: None,
+            },
+            Err(err) => BatchItemResult {
+                poll_id,
+                success: false,
+                executed: false,
+                error_code: Some(err as u32),
+            },
+        };
+        results.push_back(result);
+    }
+
+    Ok(results)
+}
+
+/// Validate and store a vote; all checks run before anything is written
+fn record_vote(
+    env: &Env,
+    voter: &Address,
+    poll_id: u32,
+    option_index: u32,
+) -> Result<(), GovernanceError> {
     let mut poll = storage::get_poll(env, poll_id).ok_or(GovernanceError::PollNotFound)?;
 
     if !poll.is_active {
-        panic_with_error!(env, GovernanceError::PollNotActive);
+        return Err(GovernanceError::PollNotActive);
     }
 
     if env.ledger().timestamp() >= poll.end_time {
-        panic_with_error!(env, GovernanceError::PollExpired);
+        return Err(GovernanceError::PollExpired);
     }
 
     if option_index >= poll.options.len() || option_index > 1 {
-        panic_with_error!(env, GovernanceError::InvalidOption);
+        return Err(GovernanceError::InvalidOption);
     }
 
     if poll.votes.contains_key(voter.clone()) {
-        panic_with_error!(env, GovernanceError::AlreadyVoted);
+        return Err(GovernanceError::AlreadyVoted);
     }
 
-    let fractcore_contract = storage::get_fractcore_contract(env);
-    let voting_power =
-        utils::call_fractcore_balance(env, &fractcore_contract, voter, poll.asset_id)
-            .map_err(|_| GovernanceError::CrossContractCallFailed)?;
+    let voting_power = utils::poll_voting_power(env, &poll, voter)
+        .map_err(|_| GovernanceError::CrossContractCallFailed)?;
 
     if voting_power == 0 {
-        panic_with_error!(env, GovernanceError::InsufficientVotingPower);
+        return Err(GovernanceError::InsufficientVotingPower);
+    }
+
+    // Last fallible step, so a rejected lock leaves nothing written
+    if storage::get_poll_transfer_lock(env, poll_id) == Some(TransferLockScope::Voters) {
+        utils::call_fractcore_lock_holder(
+            env,
+            &storage::get_fractcore_contract(env),
+            poll.asset_id,
+            poll_id,
+            voter,
+        )?;
     }
 
     let vote = Vote {
@@ -51,11 +112,21 @@ pub fn vote(
     poll.total_voters += 1;
 
     storage::set_poll(env, poll_id, &poll);
+    storage::set_vote_receipt(
+        env,
+        &VoteReceipt {
+            poll_id,
+            voter: voter.clone(),
+            option_index,
+            voting_power,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    storage::add_option_voter(env, poll_id, option_index, voter);
+    storage::add_voter_poll(env, voter, poll_id);
 
     events::emit_vote_cast(env, poll_id, voter, option_index, voting_power);
 
-    polls::check_and_execute_poll(env, poll_id)?;
-
     Ok(())
 }
 
@@ -70,9 +141,7 @@ pub fn can_vote(env: &Env, voter: &Address, poll_id: u32) -> Result<bool, Govern
         return Ok(false);
     }
 
-    let fractcore_contract = storage::get_fractcore_contract(env);
-    let voting_power =
-        utils::call_fractcore_balance(env, &fractcore_contract, voter, poll.asset_id)?;
+    let voting_power = utils::poll_voting_power(env, &poll, voter)?;
 
     Ok(voting_power > 0)
 }
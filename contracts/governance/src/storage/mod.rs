@@ -1,12 +1,27 @@
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, Address, BytesN, Env, Map, Symbol, TryFromVal, Val, Vec,
+};
 
-use crate::contract::{GovernanceParams, Poll};
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// Governance keeps nothing in temporary storage: polls, receipts and
+// certificates stay readable after they close.
+pub use placement::*;
 
-// Storage keys
+use crate::contract::{
+    AssetGovernanceSummary, ExecutedPoll, ExecutionRecord, GovernanceParams, GovernanceStats,
+    KeeperConfig, PlatformGovernance, Poll, PollCertificate, PollSnapshot, PollTemplate, PollV1,
+    QuorumMode, RelayConfig, TransferLockScope, VoteReceipt, ACTIVE_POLLS_PAGE_SIZE,
+    ALL_POLL_ACTIONS, ASSET_REGISTRY_PAGE_SIZE, MAX_ACTIVE_POLLS_PAGES, MAX_RECENT_EXECUTIONS,
+    POLL_RECORD_VERSION, VOTERS_PAGE_SIZE,
+};
+
+// Storage keys. A key is encoded by variant name, so a variant that has held
+// data is never renamed or given other fields; retired ones stay in place.
 #[derive(Clone)]
-#[contracttype]
+#[contracttype(export = false)]
 pub enum DataKey {
     Initialized,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
     Admin,
     FractcoreContract,
     FundingContract,
@@ -14,138 +29,651 @@ pub enum DataKey {
     PollCounter,
     Poll(u32),
     AssetPolls(u64),
-    ActivePolls,
+    ActivePolls,             // Unpaged list kept before paging, moved on first use
+    ActivePollCount,         // Number of active polls
+    ActivePollsPage(u32),    // page → Vec<poll_id>, ACTIVE_POLLS_PAGE_SIZE per page
+    ActivePollPosition(u32), // poll_id → index in the paged list
+    KeeperConfig,
+    KeeperPool,
+    RelayConfig,
+    RelayPool,
+    RelayerPayouts(Address), // relayer → (day, payouts that day)
+    Certificate(u32),
+    PollSnapshot(u32),
+    VoteReceipt(u32, Address),   // (poll_id, voter)
+    OptionVoterCount(u32, u32),  // (poll_id, option_index)
+    OptionVoters(u32, u32, u32), // (poll_id, option_index, page)
+    AllowedActions(u64),         // asset_id → ACTION_* bitmask
+    PollTransferLock(u32),       // poll_id → TransferLockScope
+    QuorumMode(u64),             // asset_id → QuorumMode
+    ExecutionRecord(u32),        // poll_id → ExecutionRecord
+    PollCreatedLedger(u32),      // poll_id → ledger sequence the poll was created in
+    PollVersion(u32),            // poll_id → POLL_RECORD_VERSION the poll was created with
+    PollCheckpointInterval(u32), // poll_id → seconds between balance checkpoints
+    PlatformGovernance,          // Trading contract and platform asset for SetTradingConfig
+    VoterPollCount(Address),     // voter → number of polls voted in
+    VoterPoll(Address, u32),     // (voter, index) → poll_id, in voting order
+    TemplateCounter,
+    PollTemplate(u32),             // template_id → PollTemplate
+    AssetTemplates(u64),           // asset_id → Vec<template_id>
+    ScheduleRegistry,              // Registry contract keeping the suite's keeper schedule
+    AssetSummary(u64),             // asset_id → AssetGovernanceSummary
+    GovernedAssetCount,            // Number of assets with polls or governance settings
+    GovernedAssetsPage(u32),       // page → Vec<asset_id>, ASSET_REGISTRY_PAGE_SIZE per page
+    ActiveAssetCount,              // Number of assets with at least one active poll
+    ActiveAssetsPage(u32),         // page → Vec<asset_id>, ASSET_REGISTRY_PAGE_SIZE per page
+    ActiveAssetPosition(u64),      // asset_id → index in the paged active asset list
+    RecentExecutions,              // Vec<ExecutedPoll>, newest last, MAX_RECENT_EXECUTIONS
+    GovernanceStats(u64),          // asset_id → GovernanceStats
+    VoterLastBucket(u64, Address), // (asset_id, voter) → start of the last bucket they were counted in
+    AdminLogHead,                  // AdminLogHead: entry count and the hash of folded entries
+    AdminLogPage(u32),             // page → Vec<AdminLogEntry>, ADMIN_LOG_PAGE_SIZE per page
+    WasmHash, // Code installed by the last upgrade (absent = code deployed at creation)
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("gov");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Initialized
+            | DataKey::InitConfigHash
+            | DataKey::Admin
+            | DataKey::FractcoreContract
+            | DataKey::FundingContract
+            | DataKey::GovernanceParams
+            | DataKey::PollCounter
+            | DataKey::ActivePollCount
+            | DataKey::KeeperConfig
+            | DataKey::KeeperPool
+            | DataKey::RelayConfig
+            | DataKey::RelayPool
+            | DataKey::PlatformGovernance
+            | DataKey::TemplateCounter
+            | DataKey::ScheduleRegistry
+            | DataKey::GovernedAssetCount
+            | DataKey::ActiveAssetCount
+            | DataKey::AdminLogHead
+            | DataKey::WasmHash => Placement::Instance,
+            DataKey::Poll(..)
+            | DataKey::AssetPolls(..)
+            | DataKey::ActivePolls
+            | DataKey::ActivePollsPage(..)
+            | DataKey::ActivePollPosition(..)
+            | DataKey::Certificate(..)
+            | DataKey::PollSnapshot(..)
+            | DataKey::VoteReceipt(..)
+            | DataKey::OptionVoterCount(..)
+            | DataKey::OptionVoters(..)
+            | DataKey::AllowedActions(..)
+            | DataKey::PollTransferLock(..)
+            | DataKey::QuorumMode(..)
+            | DataKey::ExecutionRecord(..)
+            | DataKey::PollCreatedLedger(..)
+            | DataKey::PollVersion(..)
+            | DataKey::PollCheckpointInterval(..)
+            | DataKey::VoterPollCount(..)
+            | DataKey::VoterPoll(..)
+            | DataKey::PollTemplate(..)
+            | DataKey::AssetTemplates(..)
+            | DataKey::AssetSummary(..)
+            | DataKey::GovernedAssetsPage(..)
+            | DataKey::ActiveAssetsPage(..)
+            | DataKey::ActiveAssetPosition(..)
+            | DataKey::RecentExecutions
+            | DataKey::GovernanceStats(..)
+            | DataKey::VoterLastBucket(..)
+            | DataKey::RelayerPayouts(..)
+            | DataKey::AdminLogPage(..) => Placement::Persistent,
+        }
+    }
+}
+
+impl admin_log::AdminLogKey for DataKey {
+    fn head() -> Self {
+        DataKey::AdminLogHead
+    }
+
+    fn page(page: u32) -> Self {
+        DataKey::AdminLogPage(page)
+    }
 }
 
 // Initialization
+/// Also true for a deployment upgraded from before namespacing that has not
+/// migrated its bare entries yet
 pub fn is_initialized(env: &Env) -> bool {
-    env.storage().instance().has(&DataKey::Initialized)
+    has_or_bare(env, &DataKey::Initialized)
 }
 
 pub fn set_initialized(env: &Env) {
-    env.storage().instance().set(&DataKey::Initialized, &true);
+    set(env, &DataKey::Initialized, &true);
+}
+
+pub fn get_init_config_hash(env: &Env) -> Option<BytesN<32>> {
+    get(env, &DataKey::InitConfigHash)
+}
+
+pub fn set_init_config_hash(env: &Env, hash: &BytesN<32>) {
+    set(env, &DataKey::InitConfigHash, hash);
+}
+
+pub fn get_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+    get(env, &DataKey::WasmHash)
+}
+
+pub fn set_wasm_hash(env: &Env, hash: &BytesN<32>) {
+    set(env, &DataKey::WasmHash, hash);
 }
 
 // Admin
 pub fn get_admin(env: &Env) -> Address {
-    env.storage().instance().get(&DataKey::Admin).unwrap()
+    get(env, &DataKey::Admin).unwrap()
 }
 
 pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&DataKey::Admin, admin);
+    set(env, &DataKey::Admin, admin);
 }
 
 // Contract addresses
 pub fn get_fractcore_contract(env: &Env) -> Address {
-    env.storage()
-        .instance()
-        .get(&DataKey::FractcoreContract)
-        .unwrap()
+    get(env, &DataKey::FractcoreContract).unwrap()
 }
 
 pub fn set_fractcore_contract(env: &Env, contract: &Address) {
-    env.storage()
-        .instance()
-        .set(&DataKey::FractcoreContract, contract);
+    set(env, &DataKey::FractcoreContract, contract);
 }
 
 pub fn get_funding_contract(env: &Env) -> Address {
-    env.storage()
-        .instance()
-        .get(&DataKey::FundingContract)
-        .unwrap()
+    get(env, &DataKey::FundingContract).unwrap()
 }
 
 pub fn set_funding_contract(env: &Env, contract: &Address) {
-    env.storage()
-        .instance()
-        .set(&DataKey::FundingContract, contract);
+    set(env, &DataKey::FundingContract, contract);
 }
 
 // Governance parameters
 pub fn get_governance_params(env: &Env) -> GovernanceParams {
-    env.storage()
-        .instance()
-        .get(&DataKey::GovernanceParams)
-        .unwrap()
+    get(env, &DataKey::GovernanceParams).unwrap()
 }
 
 pub fn set_governance_params(env: &Env, params: &GovernanceParams) {
-    env.storage()
-        .instance()
-        .set(&DataKey::GovernanceParams, params);
+    set(env, &DataKey::GovernanceParams, params);
 }
 
 // Poll management
 pub fn get_next_poll_id(env: &Env) -> u32 {
-    env.storage()
-        .instance()
-        .get(&DataKey::PollCounter)
-        .unwrap_or(1u32)
+    get(env, &DataKey::PollCounter).unwrap_or(1u32)
 }
 
 pub fn increment_poll_counter(env: &Env) {
     let current = get_next_poll_id(env);
-    env.storage()
-        .instance()
-        .set(&DataKey::PollCounter, &(current + 1));
+    set(env, &DataKey::PollCounter, &(current + 1));
 }
 
 pub fn get_poll(env: &Env, poll_id: u32) -> Option<Poll> {
-    env.storage().persistent().get(&DataKey::Poll(poll_id))
+    if get(env, &DataKey::PollVersion(poll_id)) == Some(POLL_RECORD_VERSION) {
+        return get(env, &DataKey::Poll(poll_id));
+    }
+
+    // Polls created before versioning: version 1 polls lack `weights`, later
+    // ones already have the current shape
+    let value: Val = get(env, &DataKey::Poll(poll_id))?;
+    let has_weights = Map::<Symbol, Val>::try_from_val(env, &value)
+        .is_ok_and(|fields| fields.contains_key(symbol_short!("weights")));
+    let poll = if has_weights {
+        Poll::try_from_val(env, &value).ok()
+    } else {
+        PollV1::try_from_val(env, &value)
+            .ok()
+            .map(|poll| poll.upgrade(env))
+    };
+    Some(poll.expect("stored poll has an unknown shape"))
 }
 
 pub fn set_poll(env: &Env, poll_id: u32, poll: &Poll) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::Poll(poll_id), poll);
+    set(env, &DataKey::Poll(poll_id), poll);
 }
 
 // Asset polls tracking
 pub fn get_asset_polls(env: &Env, asset_id: u64) -> Vec<u32> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetPolls(asset_id))
-        .unwrap_or_else(|| Vec::new(env))
+    get(env, &DataKey::AssetPolls(asset_id)).unwrap_or_else(|| Vec::new(env))
 }
 
 pub fn add_asset_poll(env: &Env, asset_id: u64, poll_id: u32) {
     let mut polls = get_asset_polls(env, asset_id);
     polls.push_back(poll_id);
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetPolls(asset_id), &polls);
+    set(env, &DataKey::AssetPolls(asset_id), &polls);
 }
 
-// Active polls tracking
+// Active polls, paged. Removing a poll moves the last id into its slot, so
+// the order is not meaningful.
+pub fn get_active_poll_count(env: &Env) -> u32 {
+    match get(env, &DataKey::ActivePollCount) {
+        Some(count) => count,
+        None => migrate_active_polls(env),
+    }
+}
+
+/// One storage page of active poll ids; call `get_active_poll_count` first so
+/// a list kept before paging has been moved into pages
+pub fn get_active_polls_page(env: &Env, page: u32) -> Vec<u32> {
+    get(env, &DataKey::ActivePollsPage(page)).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Active poll ids in the first MAX_ACTIVE_POLLS_PAGES pages
 pub fn get_active_polls(env: &Env) -> Vec<u32> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::ActivePolls)
-        .unwrap_or_else(|| Vec::new(env))
+    let pages = get_active_poll_count(env).div_ceil(ACTIVE_POLLS_PAGE_SIZE);
+    let mut polls = Vec::new(env);
+    for page in 0..pages.min(MAX_ACTIVE_POLLS_PAGES) {
+        polls.append(&get_active_polls_page(env, page));
+    }
+    polls
 }
 
 pub fn add_active_poll(env: &Env, poll_id: u32) {
-    let mut active_polls = get_active_polls(env);
-    active_polls.push_back(poll_id);
-    env.storage()
-        .persistent()
-        .set(&DataKey::ActivePolls, &active_polls);
+    let count = get_active_poll_count(env);
+    let page = count / ACTIVE_POLLS_PAGE_SIZE;
+
+    let mut polls = get_active_polls_page(env, page);
+    polls.push_back(poll_id);
+    set(env, &DataKey::ActivePollsPage(page), &polls);
+    set(env, &DataKey::ActivePollPosition(poll_id), &count);
+    set(env, &DataKey::ActivePollCount, &(count + 1));
 }
 
 pub fn remove_active_poll(env: &Env, poll_id: u32) {
-    let active_polls = get_active_polls(env);
-    let mut new_polls = Vec::new(env);
-
-    for i in 0..active_polls.len() {
-        if let Some(id) = active_polls.get(i) {
-            if id != poll_id {
-                new_polls.push_back(id);
-            }
+    let count = get_active_poll_count(env);
+    let position: u32 = match get(env, &DataKey::ActivePollPosition(poll_id)) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = count - 1;
+    let last_page = last / ACTIVE_POLLS_PAGE_SIZE;
+    let mut tail = get_active_polls_page(env, last_page);
+    let moved = tail.pop_back().unwrap();
+
+    if position != last {
+        let page = position / ACTIVE_POLLS_PAGE_SIZE;
+        if page == last_page {
+            tail.set(position % ACTIVE_POLLS_PAGE_SIZE, moved);
+        } else {
+            let mut polls = get_active_polls_page(env, page);
+            polls.set(position % ACTIVE_POLLS_PAGE_SIZE, moved);
+            set(env, &DataKey::ActivePollsPage(page), &polls);
         }
+        set(env, &DataKey::ActivePollPosition(moved), &position);
+    }
+
+    if tail.is_empty() {
+        remove(env, &DataKey::ActivePollsPage(last_page));
+    } else {
+        set(env, &DataKey::ActivePollsPage(last_page), &tail);
     }
+    remove(env, &DataKey::ActivePollPosition(poll_id));
+    set(env, &DataKey::ActivePollCount, &last);
+}
+
+/// Move the unpaged list written before active polls were paged; returns the
+/// number of polls moved
+fn migrate_active_polls(env: &Env) -> u32 {
+    let legacy: Vec<u32> = match get(env, &DataKey::ActivePolls) {
+        Some(legacy) => legacy,
+        None => return 0,
+    };
+
+    remove(env, &DataKey::ActivePolls);
+    set(env, &DataKey::ActivePollCount, &0u32);
+    for poll_id in legacy.iter() {
+        add_active_poll(env, poll_id);
+    }
+    legacy.len()
+}
+
+// Per-asset governance summaries. An asset is added to the governed list the
+// first time its summary is written and never leaves it.
+pub fn get_asset_summary(env: &Env, asset_id: u64) -> Option<AssetGovernanceSummary> {
+    get(env, &DataKey::AssetSummary(asset_id))
+}
+
+pub fn set_asset_summary(env: &Env, summary: &AssetGovernanceSummary) {
+    let key = DataKey::AssetSummary(summary.asset_id);
+    if !has(env, &key) {
+        let count = get_governed_asset_count(env);
+        let page = count / ASSET_REGISTRY_PAGE_SIZE;
+        let mut assets = get_governed_assets_page(env, page);
+        assets.push_back(summary.asset_id);
+        set(env, &DataKey::GovernedAssetsPage(page), &assets);
+        set(env, &DataKey::GovernedAssetCount, &(count + 1));
+    }
+    set(env, &key, summary);
+}
+
+// Turnout statistics
+pub fn get_governance_stats(env: &Env, asset_id: u64) -> Option<GovernanceStats> {
+    get(env, &DataKey::GovernanceStats(asset_id))
+}
+
+pub fn set_governance_stats(env: &Env, stats: &GovernanceStats) {
+    set(env, &DataKey::GovernanceStats(stats.asset_id), stats);
+}
+
+pub fn get_voter_last_bucket(env: &Env, asset_id: u64, voter: &Address) -> Option<u64> {
+    get(env, &DataKey::VoterLastBucket(asset_id, voter.clone()))
+}
+
+pub fn set_voter_last_bucket(env: &Env, asset_id: u64, voter: &Address, bucket: u64) {
+    set(
+        env,
+        &DataKey::VoterLastBucket(asset_id, voter.clone()),
+        &bucket,
+    );
+}
+
+pub fn get_governed_asset_count(env: &Env) -> u32 {
+    get(env, &DataKey::GovernedAssetCount).unwrap_or(0)
+}
+
+pub fn get_governed_assets_page(env: &Env, page: u32) -> Vec<u64> {
+    get(env, &DataKey::GovernedAssetsPage(page)).unwrap_or_else(|| Vec::new(env))
+}
+
+// Assets with active polls, paged like the active polls themselves
+pub fn get_active_asset_count(env: &Env) -> u32 {
+    get(env, &DataKey::ActiveAssetCount).unwrap_or(0)
+}
+
+pub fn get_active_assets_page(env: &Env, page: u32) -> Vec<u64> {
+    get(env, &DataKey::ActiveAssetsPage(page)).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_active_asset(env: &Env, asset_id: u64) {
+    if has(env, &DataKey::ActiveAssetPosition(asset_id)) {
+        return;
+    }
+    let count = get_active_asset_count(env);
+    let page = count / ASSET_REGISTRY_PAGE_SIZE;
+
+    let mut assets = get_active_assets_page(env, page);
+    assets.push_back(asset_id);
+    set(env, &DataKey::ActiveAssetsPage(page), &assets);
+    set(env, &DataKey::ActiveAssetPosition(asset_id), &count);
+    set(env, &DataKey::ActiveAssetCount, &(count + 1));
+}
+
+pub fn remove_active_asset(env: &Env, asset_id: u64) {
+    let position: u32 = match get(env, &DataKey::ActiveAssetPosition(asset_id)) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = get_active_asset_count(env) - 1;
+    let last_page = last / ASSET_REGISTRY_PAGE_SIZE;
+    let mut tail = get_active_assets_page(env, last_page);
+    let moved = tail.pop_back().unwrap();
+
+    if position != last {
+        let page = position / ASSET_REGISTRY_PAGE_SIZE;
+        if page == last_page {
+            tail.set(position % ASSET_REGISTRY_PAGE_SIZE, moved);
+        } else {
+            let mut assets = get_active_assets_page(env, page);
+            assets.set(position % ASSET_REGISTRY_PAGE_SIZE, moved);
+            set(env, &DataKey::ActiveAssetsPage(page), &assets);
+        }
+        set(env, &DataKey::ActiveAssetPosition(moved), &position);
+    }
+
+    if tail.is_empty() {
+        remove(env, &DataKey::ActiveAssetsPage(last_page));
+    } else {
+        set(env, &DataKey::ActiveAssetsPage(last_page), &tail);
+    }
+    remove(env, &DataKey::ActiveAssetPosition(asset_id));
+    set(env, &DataKey::ActiveAssetCount, &last);
+}
+
+// Recently executed polls across all assets
+pub fn get_recent_executions(env: &Env) -> Vec<ExecutedPoll> {
+    get(env, &DataKey::RecentExecutions).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_recent_execution(env: &Env, executed: &ExecutedPoll) {
+    let mut recent = get_recent_executions(env);
+    if recent.len() >= MAX_RECENT_EXECUTIONS {
+        recent.pop_front();
+    }
+    recent.push_back(executed.clone());
+    set(env, &DataKey::RecentExecutions, &recent);
+}
+
+// Keeper incentives
+pub fn get_platform_governance(env: &Env) -> Option<PlatformGovernance> {
+    get(env, &DataKey::PlatformGovernance)
+}
+
+pub fn set_platform_governance(env: &Env, platform: &PlatformGovernance) {
+    set(env, &DataKey::PlatformGovernance, platform);
+}
+
+pub fn get_keeper_config(env: &Env) -> Option<KeeperConfig> {
+    get(env, &DataKey::KeeperConfig)
+}
+
+pub fn set_keeper_config(env: &Env, config: &KeeperConfig) {
+    set(env, &DataKey::KeeperConfig, config);
+}
+
+pub fn get_schedule_registry(env: &Env) -> Option<Address> {
+    get(env, &DataKey::ScheduleRegistry)
+}
+
+pub fn set_schedule_registry(env: &Env, registry: &Option<Address>) {
+    match registry {
+        Some(registry) => set(env, &DataKey::ScheduleRegistry, registry),
+        None => remove(env, &DataKey::ScheduleRegistry),
+    }
+}
+
+pub fn get_keeper_pool(env: &Env) -> i128 {
+    get(env, &DataKey::KeeperPool).unwrap_or(0)
+}
+
+pub fn set_keeper_pool(env: &Env, amount: i128) {
+    set(env, &DataKey::KeeperPool, &amount);
+}
+
+// Relayer reimbursement for gasless votes
+pub fn get_relay_config(env: &Env) -> Option<RelayConfig> {
+    get(env, &DataKey::RelayConfig)
+}
+
+pub fn set_relay_config(env: &Env, config: &RelayConfig) {
+    set(env, &DataKey::RelayConfig, config);
+}
+
+pub fn get_relay_pool(env: &Env) -> i128 {
+    get(env, &DataKey::RelayPool).unwrap_or(0)
+}
+
+pub fn set_relay_pool(env: &Env, amount: i128) {
+    set(env, &DataKey::RelayPool, &amount);
+}
+
+pub fn get_relayer_payouts(env: &Env, relayer: &Address) -> (u64, u32) {
+    get(env, &DataKey::RelayerPayouts(relayer.clone())).unwrap_or((0, 0))
+}
+
+pub fn set_relayer_payouts(env: &Env, relayer: &Address, day: u64, payouts: u32) {
+    set(
+        env,
+        &DataKey::RelayerPayouts(relayer.clone()),
+        &(day, payouts),
+    );
+}
+
+// Poll certificates, written once when a poll is finalized
+pub fn get_certificate(env: &Env, poll_id: u32) -> Option<PollCertificate> {
+    get(env, &DataKey::Certificate(poll_id))
+}
+
+pub fn set_certificate(env: &Env, certificate: &PollCertificate) {
+    let key = DataKey::Certificate(certificate.poll_id);
+    if !has(env, &key) {
+        set(env, &key, certificate);
+    }
+}
+
+// Outcome of executed poll actions
+pub fn get_execution_record(env: &Env, poll_id: u32) -> Option<ExecutionRecord> {
+    get(env, &DataKey::ExecutionRecord(poll_id))
+}
+
+pub fn set_execution_record(env: &Env, record: &ExecutionRecord) {
+    set(env, &DataKey::ExecutionRecord(record.poll_id), record);
+}
+
+// Creation ledger per poll; polls created before it was recorded read as 0
+pub fn get_poll_created_ledger(env: &Env, poll_id: u32) -> u32 {
+    get(env, &DataKey::PollCreatedLedger(poll_id)).unwrap_or(0)
+}
+
+pub fn set_poll_created_ledger(env: &Env, poll_id: u32, ledger: u32) {
+    set(env, &DataKey::PollCreatedLedger(poll_id), &ledger);
+}
+
+pub fn set_poll_version(env: &Env, poll_id: u32) {
+    set(env, &DataKey::PollVersion(poll_id), &POLL_RECORD_VERSION);
+}
+
+// Balance re-snapshot interval of checkpointed polls
+pub fn get_poll_checkpoint_interval(env: &Env, poll_id: u32) -> Option<u64> {
+    get(env, &DataKey::PollCheckpointInterval(poll_id))
+}
+
+pub fn set_poll_checkpoint_interval(env: &Env, poll_id: u32, interval: u64) {
+    set(env, &DataKey::PollCheckpointInterval(poll_id), &interval);
+}
+
+// Cached fractcore figures per poll
+pub fn get_poll_snapshot(env: &Env, poll_id: u32) -> Option<PollSnapshot> {
+    get(env, &DataKey::PollSnapshot(poll_id))
+}
+
+pub fn set_poll_snapshot(env: &Env, poll_id: u32, snapshot: &PollSnapshot) {
+    set(env, &DataKey::PollSnapshot(poll_id), snapshot);
+}
+
+// Vote receipts and per-option voter lists
+pub fn get_vote_receipt(env: &Env, poll_id: u32, voter: &Address) -> Option<VoteReceipt> {
+    get(env, &DataKey::VoteReceipt(poll_id, voter.clone()))
+}
+
+pub fn set_vote_receipt(env: &Env, receipt: &VoteReceipt) {
+    set(
+        env,
+        &DataKey::VoteReceipt(receipt.poll_id, receipt.voter.clone()),
+        receipt,
+    );
+}
+
+pub fn get_voter_poll_count(env: &Env, voter: &Address) -> u32 {
+    get(env, &DataKey::VoterPollCount(voter.clone())).unwrap_or(0)
+}
+
+pub fn get_voter_poll(env: &Env, voter: &Address, index: u32) -> Option<u32> {
+    get(env, &DataKey::VoterPoll(voter.clone(), index))
+}
+
+pub fn add_voter_poll(env: &Env, voter: &Address, poll_id: u32) {
+    let count = get_voter_poll_count(env, voter);
+    set(env, &DataKey::VoterPoll(voter.clone(), count), &poll_id);
+    set(env, &DataKey::VoterPollCount(voter.clone()), &(count + 1));
+}
+
+pub fn get_option_voter_count(env: &Env, poll_id: u32, option_index: u32) -> u32 {
+    get(env, &DataKey::OptionVoterCount(poll_id, option_index)).unwrap_or(0)
+}
+
+pub fn get_option_voters(env: &Env, poll_id: u32, option_index: u32, page: u32) -> Vec<Address> {
+    get(env, &DataKey::OptionVoters(poll_id, option_index, page)).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_option_voter(env: &Env, poll_id: u32, option_index: u32, voter: &Address) {
+    let count = get_option_voter_count(env, poll_id, option_index);
+    let page = count / VOTERS_PAGE_SIZE;
+
+    let mut voters = get_option_voters(env, poll_id, option_index, page);
+    voters.push_back(voter.clone());
+    set(
+        env,
+        &DataKey::OptionVoters(poll_id, option_index, page),
+        &voters,
+    );
+    set(
+        env,
+        &DataKey::OptionVoterCount(poll_id, option_index),
+        &(count + 1),
+    );
+}
+
+// Per-asset poll action allowlist
+pub fn get_allowed_actions(env: &Env, asset_id: u64) -> u32 {
+    get(env, &DataKey::AllowedActions(asset_id)).unwrap_or(ALL_POLL_ACTIONS)
+}
+
+pub fn set_allowed_actions(env: &Env, asset_id: u64, allowed: u32) {
+    set(env, &DataKey::AllowedActions(asset_id), &allowed);
+}
+
+// Quorum base per asset
+pub fn get_quorum_mode(env: &Env, asset_id: u64) -> QuorumMode {
+    get(env, &DataKey::QuorumMode(asset_id)).unwrap_or(QuorumMode::TotalSupply)
+}
+
+pub fn set_quorum_mode(env: &Env, asset_id: u64, mode: &QuorumMode) {
+    set(env, &DataKey::QuorumMode(asset_id), mode);
+}
+
+// Poll transfer locks
+pub fn get_poll_transfer_lock(env: &Env, poll_id: u32) -> Option<TransferLockScope> {
+    get(env, &DataKey::PollTransferLock(poll_id))
+}
+
+pub fn set_poll_transfer_lock(env: &Env, poll_id: u32, scope: TransferLockScope) {
+    set(env, &DataKey::PollTransferLock(poll_id), &scope);
+}
+
+// Poll templates
+pub fn next_template_id(env: &Env) -> u32 {
+    let template_id = get(env, &DataKey::TemplateCounter).unwrap_or(0u32) + 1;
+    set(env, &DataKey::TemplateCounter, &template_id);
+    template_id
+}
+
+pub fn get_poll_template(env: &Env, template_id: u32) -> Option<PollTemplate> {
+    get(env, &DataKey::PollTemplate(template_id))
+}
+
+pub fn set_poll_template(env: &Env, template_id: u32, template: &PollTemplate) {
+    set(env, &DataKey::PollTemplate(template_id), template);
+}
+
+pub fn remove_poll_template(env: &Env, template_id: u32, asset_id: u64) {
+    remove(env, &DataKey::PollTemplate(template_id));
+
+    let mut templates = get_asset_templates(env, asset_id);
+    if let Some(index) = templates.first_index_of(template_id) {
+        templates.remove(index);
+    }
+    set_asset_templates(env, asset_id, &templates);
+}
+
+pub fn get_asset_templates(env: &Env, asset_id: u64) -> Vec<u32> {
+    get(env, &DataKey::AssetTemplates(asset_id)).unwrap_or_else(|| Vec::new(env))
+}
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::ActivePolls, &new_polls);
+pub fn set_asset_templates(env: &Env, asset_id: u64, templates: &Vec<u32>) {
+    set(env, &DataKey::AssetTemplates(asset_id), templates);
 }
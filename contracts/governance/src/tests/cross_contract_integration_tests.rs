@@ -2,8 +2,9 @@
 mod cross_contract_integration_tests {
     use crate::contract::*;
     use soroban_sdk::{
+        symbol_short,
         testutils::{Address as _, Ledger},
-        Address, Env, String, Vec,
+        Address, BytesN, Env, String, Vec,
     };
 
     mod fractcore {
@@ -54,6 +55,47 @@ mod cross_contract_integration_tests {
         }
     }
 
+    // Asset SAC stand-in whose transfers move balances, so distributions settle
+    mod asset_sac {
+        use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+        #[contract]
+        pub struct MockSac;
+
+        #[contracttype]
+        pub enum DataKey {
+            Balance(Address),
+        }
+
+        #[contractimpl]
+        impl MockSac {
+            pub fn balance(env: Env, id: Address) -> i128 {
+                env.storage()
+                    .instance()
+                    .get(&DataKey::Balance(id))
+                    .unwrap_or(0)
+            }
+
+            pub fn mint(env: Env, to: Address, amount: i128) {
+                let balance = Self::balance(env.clone(), to.clone());
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Balance(to), &(balance + amount));
+            }
+
+            pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+                let from_balance = Self::balance(env.clone(), from.clone());
+                if from_balance < amount {
+                    panic!("Insufficient balance");
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Balance(from), &(from_balance - amount));
+                Self::mint(env, to, amount);
+            }
+        }
+    }
+
     fn setup_full_contracts() -> (
         Env,
         Address, // Admin
@@ -68,6 +110,7 @@ mod cross_contract_integration_tests {
     ) {
         let env = Env::default();
         env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
 
         let admin = Address::generate(&env);
 
@@ -76,33 +119,33 @@ mod cross_contract_integration_tests {
         let sac_client = token::MockTokenClient::new(&env, &xlm_token_id);
 
         // Deploy fractcore contract
-        let fractcore_contract_id = env.register(fractcore::WASM, ());
+        let fractcore_contract_id = env.register(fractcore::WASM, (admin.clone(),));
         let fractcore_client = fractcore::Client::new(&env, &fractcore_contract_id);
 
         // Deploy funding contract
-        let funding_contract_id = env.register(funding::WASM, ());
+        let funding_contract_id = env.register(
+            funding::WASM,
+            (admin.clone(), fractcore_contract_id.clone()),
+        );
         let funding_client = funding::Client::new(&env, &funding_contract_id);
 
         // Deploy governance contract
-        let governance_contract_id = env.register(GovernanceContract, ());
+        let governance_contract_id = env.register(
+            GovernanceContract,
+            (
+                admin.clone(),
+                fractcore_contract_id.clone(),
+                funding_contract_id.clone(),
+                51u32, // threshold - 51% approval needed
+                30u32, // quorum - 30% participation needed
+                7u32,  // expiry days
+            ),
+        );
         let governance_client = GovernanceContractClient::new(&env, &governance_contract_id);
 
-        // Initialize contracts
-        fractcore_client.initialize(&admin);
-        funding_client.initialize(&admin, &fractcore_contract_id);
-
         // Set governance contract in funding contract so it can trigger distributions
         funding_client.set_governance_contract(&admin, &governance_contract_id);
 
-        governance_client.initialize(
-            &admin,
-            &fractcore_contract_id,
-            &funding_contract_id,
-            &51u32, // threshold - 51% approval needed
-            &30u32, // quorum - 30% participation needed
-            &7u32,  // expiry days
-        );
-
         (
             env,
             admin,
@@ -246,7 +289,7 @@ mod cross_contract_integration_tests {
         // If we get here, automatic execution didn't happen, so try manual execution
         // Advance time to make poll executable
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60); // 8 days later
+            li.timestamp += 8 * 24 * 60 * 60; // 8 days later
         });
 
         // Execute the poll - this should trigger cross-contract call to funding contract
@@ -309,7 +352,7 @@ mod cross_contract_integration_tests {
 
         // Advance time
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60);
+            li.timestamp += 8 * 24 * 60 * 60;
         });
 
         // Execute the poll
@@ -370,7 +413,7 @@ mod cross_contract_integration_tests {
 
         // Advance time
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60);
+            li.timestamp += 8 * 24 * 60 * 60;
         });
 
         // Execute poll
@@ -394,7 +437,7 @@ mod cross_contract_integration_tests {
 
         // Advance time
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60);
+            li.timestamp += 8 * 24 * 60 * 60;
         });
 
         // This should still pass as we're using test fallbacks
@@ -452,7 +495,7 @@ mod cross_contract_integration_tests {
 
         // Advance time to make poll expired (polls last 7 days by default)
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60); // 8 days later
+            li.timestamp += 8 * 24 * 60 * 60; // 8 days later
         });
 
         // Check vote results
@@ -550,7 +593,7 @@ mod cross_contract_integration_tests {
 
         // Advance time
         env.ledger().with_mut(|li| {
-            li.timestamp = li.timestamp + (8 * 24 * 60 * 60);
+            li.timestamp += 8 * 24 * 60 * 60;
         });
 
         // Check execution result details
@@ -584,11 +627,7 @@ mod cross_contract_integration_tests {
             let approve_votes = results.vote_counts.get(1).unwrap_or(0);
             let deny_votes = results.vote_counts.get(0).unwrap_or(0);
             let total_votes = approve_votes + deny_votes;
-            let approval_pct = if total_votes > 0 {
-                (approve_votes * 100) / total_votes
-            } else {
-                0
-            };
+            let approval_pct = (approve_votes * 100).checked_div(total_votes).unwrap_or(0);
 
             // Debug: check if poll meets criteria manually
             // With 100% participation and 100% approval, this should definitely pass
@@ -620,4 +659,361 @@ mod cross_contract_integration_tests {
     }
 
     // Polls are automatically executed when all owners vote.
+
+    #[test]
+    fn test_poll_snapshot_cached_until_refreshed() {
+        let (
+            env,
+            admin,
+            _governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            _xlm_token_id,
+            governance_client,
+            fractcore_client,
+            _funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &400);
+        fractcore_client.transfer(&voter, &holder, &asset_id, &100);
+
+        let poll_id = governance_client.create_poll(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Snapshot Poll"),
+            &String::from_str(&env, "Supply is read once at creation"),
+            &PollAction::NoExecution,
+            &None,
+        );
+        let snapshot = governance_client.get_poll_snapshot(&poll_id);
+        assert_eq!(snapshot.total_supply, 400);
+        assert_eq!(snapshot.owner_count, 2);
+
+        // Later issuance does not change the cached quorum base
+        let newcomer = Address::generate(&env);
+        fractcore_client.mint_to(
+            &asset_id,
+            &Vec::from_array(&env, [newcomer.clone()]),
+            &Vec::from_array(&env, [400u64]),
+        );
+        governance_client.vote(&voter, &poll_id, &1u32);
+        assert_eq!(
+            governance_client
+                .check_poll_execution(&poll_id)
+                .participation_percentage,
+            75
+        );
+        assert!(governance_client.get_poll(&poll_id).is_active);
+
+        // Only the creator or admin can pull live figures
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            governance_client.try_refresh_poll_snapshot(&outsider, &poll_id),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+
+        let refreshed = governance_client.refresh_poll_snapshot(&admin, &poll_id);
+        assert_eq!(refreshed.total_supply, 800);
+        assert_eq!(refreshed.owner_count, 3);
+        assert_eq!(
+            governance_client
+                .check_poll_execution(&poll_id)
+                .participation_percentage,
+            37
+        );
+    }
+
+    #[test]
+    fn test_split_asset_poll_action() {
+        let (
+            env,
+            admin,
+            governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            _xlm_token_id,
+            governance_client,
+            fractcore_client,
+            _funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &100);
+
+        // The issuer hands asset management to governance
+        fractcore_client.set_asset_manager(&admin, &asset_id, &governance_contract_id);
+
+        let poll_id = governance_client.create_poll(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Split 1:100"),
+            &String::from_str(&env, "Increase granularity for trading"),
+            &PollAction::SplitAsset(100),
+            &None,
+        );
+
+        // The only holder voting ends the poll and runs the split
+        governance_client.vote(&voter, &poll_id, &1u32);
+        assert!(!governance_client.get_poll(&poll_id).is_active);
+
+        assert_eq!(fractcore_client.balance_of(&voter, &asset_id), 10000);
+        assert_eq!(fractcore_client.asset_supply(&asset_id), 10000);
+        assert_eq!(fractcore_client.split_factor(&asset_id), 100);
+    }
+
+    #[test]
+    fn test_post_announcement_poll_action() {
+        let (
+            env,
+            admin,
+            governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            _xlm_token_id,
+            governance_client,
+            fractcore_client,
+            _funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &100);
+        fractcore_client.set_asset_manager(&admin, &asset_id, &governance_contract_id);
+
+        let content_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let poll_id = governance_client.create_poll(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Publish AGM notice"),
+            &String::from_str(&env, "Announce the annual meeting to holders"),
+            &PollAction::PostAnnouncement(
+                String::from_str(&env, "Annual meeting"),
+                content_hash.clone(),
+                String::from_str(&env, "ipfs://agm"),
+            ),
+            &None,
+        );
+        governance_client.vote(&voter, &poll_id, &1u32);
+
+        let feed = fractcore_client.get_announcements(&asset_id);
+        assert_eq!(feed.len(), 1);
+        let announcement = feed.get(0).unwrap();
+        assert_eq!(announcement.author, governance_contract_id);
+        assert_eq!(announcement.content_hash, content_hash);
+    }
+
+    #[test]
+    fn test_revert_asset_uri_poll_action() {
+        let (
+            env,
+            admin,
+            governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            _xlm_token_id,
+            governance_client,
+            fractcore_client,
+            _funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &100);
+        let original = String::from_str(&env, "ipfs://original");
+        fractcore_client.set_asset_uri(&admin, &asset_id, &original);
+        fractcore_client.set_asset_uri(&admin, &asset_id, &String::from_str(&env, "ipfs://bad"));
+        fractcore_client.set_asset_manager(&admin, &asset_id, &governance_contract_id);
+
+        let poll_id = governance_client.create_poll(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Restore metadata"),
+            &String::from_str(&env, "Undo the last URI change"),
+            &PollAction::RevertAssetUri(0),
+            &None,
+        );
+        governance_client.vote(&voter, &poll_id, &1u32);
+
+        assert_eq!(fractcore_client.asset_uri(&asset_id), Some(original));
+        let latest = fractcore_client.get_uri_history(&asset_id).last().unwrap();
+        assert_eq!(latest.version, 2);
+        assert_eq!(latest.set_by, governance_contract_id);
+    }
+
+    #[test]
+    fn test_release_earmark_poll_action() {
+        let (
+            env,
+            admin,
+            _governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            xlm_token_id,
+            governance_client,
+            fractcore_client,
+            funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &100);
+        funding_client.register_asset_sac(&voter, &asset_id, &xlm_token_id);
+
+        let earmark_id = funding_client.deposit_earmarked(
+            &sponsor,
+            &asset_id,
+            &2000i128,
+            &symbol_short!("travel"),
+            &funding::ReleaseCondition::PollApproval,
+        );
+
+        let poll_id = governance_client.create_poll(
+            &admin,
+            &asset_id,
+            &String::from_str(&env, "Release travel budget"),
+            &String::from_str(&env, "Let the manager book the site visit"),
+            &PollAction::ReleaseEarmark(earmark_id),
+            &None,
+        );
+        governance_client.vote(&voter, &poll_id, &1u32);
+
+        let earmark = funding_client.get_earmark(&asset_id, &earmark_id).unwrap();
+        assert!(earmark.released);
+        assert_eq!(funding_client.earmarked_total(&asset_id), 2000);
+    }
+
+    #[test]
+    fn test_veto_emergency_withdraw_poll_action() {
+        let (
+            env,
+            admin,
+            _governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            xlm_token_id,
+            governance_client,
+            fractcore_client,
+            funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let voter = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&voter, &100);
+        funding_client.register_asset_sac(&voter, &asset_id, &xlm_token_id);
+
+        let withdrawal_id = funding_client.announce_emergency_withdraw(
+            &admin,
+            &asset_id,
+            &1000u128,
+            &String::from_str(&env, "Move reserves"),
+        );
+
+        // A one-day poll settles well inside the timelock
+        let poll_id = governance_client.create_poll(
+            &voter,
+            &asset_id,
+            &String::from_str(&env, "Veto withdrawal"),
+            &String::from_str(&env, "Keep the reserves in the asset SAC"),
+            &PollAction::VetoEmergencyWithdraw(withdrawal_id),
+            &Some(1),
+        );
+        governance_client.vote(&voter, &poll_id, &1u32);
+
+        let withdrawal = funding_client
+            .get_emergency_withdrawal(&asset_id, &withdrawal_id)
+            .unwrap();
+        assert_eq!(withdrawal.status, funding::WithdrawalStatus::Vetoed);
+    }
+
+    #[test]
+    fn test_distribute_funds_execution_record() {
+        let (
+            env,
+            _admin,
+            _governance_contract_id,
+            _fractcore_contract_id,
+            _funding_contract_id,
+            _xlm_token_id,
+            governance_client,
+            fractcore_client,
+            funding_client,
+            _sac_client,
+        ) = setup_full_contracts();
+
+        let holders = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        let asset_id = fractcore_client.mint(&holders[0], &1);
+        fractcore_client.mint_to(
+            &asset_id,
+            &Vec::from_array(&env, [holders[1].clone(), holders[2].clone()]),
+            &Vec::from_array(&env, [1u64, 1u64]),
+        );
+
+        let sac_id = env.register(asset_sac::MockSac, ());
+        let sac_client = asset_sac::MockSacClient::new(&env, &sac_id);
+        sac_client.mint(&sac_id, &310);
+        funding_client.register_asset_sac(&holders[0], &asset_id, &sac_id);
+
+        let distribute = |amount: u128| {
+            let poll_id = governance_client.create_poll(
+                &holders[0],
+                &asset_id,
+                &String::from_str(&env, "Distribute"),
+                &String::from_str(&env, "Pay out rental income"),
+                &PollAction::DistributeFunds(amount, String::from_str(&env, "Rent")),
+                &None,
+            );
+            for holder in holders.iter() {
+                governance_client.vote(holder, &poll_id, &1u32);
+            }
+            governance_client.get_execution_record(&poll_id)
+        };
+
+        // Three equal holders split 300 exactly
+        let record = distribute(300);
+        assert_eq!(record.status, ExecutionStatus::Executed);
+        let ActionOutput::Distribution(outcome) = record.output else {
+            panic!("Expected a distribution outcome");
+        };
+        assert_eq!(outcome.distribution_id, 0);
+        assert_eq!(outcome.distributed, 300);
+        assert_eq!(outcome.recipients, 3);
+        assert_eq!(outcome.dust, 0);
+        assert_eq!(sac_client.balance(&holders[1]), 100);
+
+        // 10 leaves 1 as dust, a 10% shortfall
+        let record = distribute(10);
+        assert_eq!(record.status, ExecutionStatus::ExecutedWithWarnings);
+        let ActionOutput::Distribution(outcome) = record.output else {
+            panic!("Expected a distribution outcome");
+        };
+        assert_eq!(outcome.distribution_id, 1);
+        assert_eq!(outcome.distributed, 9);
+        assert_eq!(outcome.dust, 1);
+
+        // Polls that did not execute have no record
+        let rejected = governance_client.create_poll(
+            &holders[0],
+            &asset_id,
+            &String::from_str(&env, "Distribute"),
+            &String::from_str(&env, "Pay out again"),
+            &PollAction::DistributeFunds(3, String::from_str(&env, "Rent")),
+            &None,
+        );
+        for holder in holders.iter() {
+            governance_client.vote(holder, &rejected, &0u32);
+        }
+        assert_eq!(
+            governance_client.try_get_execution_record(&rejected),
+            Err(Ok(GovernanceError::ExecutionRecordNotFound))
+        );
+    }
 }
@@ -8,19 +8,20 @@ mod edge_case_tests {
     }
 
     fn setup_governance_contract(env: &Env) -> (Address, Address, Address, Address) {
-        let contract_id = env.register(GovernanceContract, ());
         let admin = Address::generate(env);
         let fractcore_contract = Address::generate(env);
         let funding_contract = Address::generate(env);
 
-        let client = GovernanceContractClient::new(env, &contract_id);
-        client.initialize(
-            &admin,
-            &fractcore_contract,
-            &funding_contract,
-            &60u32, // default threshold
-            &40u32, // default quorum
-            &7u32,  // default expiry days
+        let contract_id = env.register(
+            GovernanceContract,
+            (
+                admin.clone(),
+                fractcore_contract.clone(),
+                funding_contract.clone(),
+                60u32, // default threshold
+                40u32, // default quorum
+                7u32,  // default expiry days
+            ),
         );
 
         (contract_id, admin, fractcore_contract, funding_contract)
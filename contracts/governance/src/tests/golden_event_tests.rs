@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod golden_event_tests {
+    //! Canonical poll lifecycle whose events must serialize to exactly the XDR
+    //! in `golden/`. A failure means a topic or payload layout changed: if that
+    //! is intended, rerun with `UPDATE_GOLDEN=1` and commit the new golden file
+    //! so indexers see the change in review.
+
+    extern crate std;
+
+    use crate::contract::*;
+    use crate::tests::setup::{setup, Setup};
+    use soroban_sdk::{testutils::Address as _, Address, String as SorobanString};
+    use std::string::String;
+    use test_support::{assert_golden, record_events};
+
+    #[test]
+    fn test_golden_poll_lifecycle_events() {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            ..
+        } = setup();
+        let contract_id = client.address.clone();
+        let holder1 = Address::generate(&env);
+        let holder2 = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&holder1, &600);
+        fractcore_client.transfer(&holder1, &holder2, &asset_id, &400);
+        let mut golden = String::new();
+
+        let poll_id = client.create_poll(
+            &holder1,
+            &asset_id,
+            &SorobanString::from_str(&env, "Repaint"),
+            &SorobanString::from_str(&env, "Repaint the facade"),
+            &PollAction::NoExecution,
+            &Some(3),
+        );
+        record_events(&env, &contract_id, "create_poll", &mut golden);
+
+        client.vote(&holder1, &poll_id, &1);
+        record_events(&env, &contract_id, "vote", &mut golden);
+
+        // The last holder's vote executes the poll
+        client.vote(&holder2, &poll_id, &1);
+        record_events(&env, &contract_id, "vote_and_execute", &mut golden);
+
+        assert_golden(env!("CARGO_MANIFEST_DIR"), "poll_lifecycle", &golden);
+    }
+}
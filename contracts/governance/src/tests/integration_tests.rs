@@ -1,7 +1,14 @@
 #[cfg(test)]
 mod integration_tests {
     use crate::contract::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+    use crate::storage::{self, DataKey, PlacedKey, MAX_MIGRATE_BATCH};
+    use crate::tests::setup::{fractcore, setup, Setup};
+    use placement::testutils::strip_namespaces;
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Events, Ledger},
+        vec, Address, Bytes, BytesN, Env, IntoVal, Map, String, TryFromVal, Val, Vec,
+    };
 
     fn create_test_env() -> Env {
         Env::default()
@@ -18,7 +25,18 @@ mod integration_tests {
     }
 
     fn _init_test_contract(env: &Env) -> Address {
-        env.register(GovernanceContract, ())
+        let (admin, fractcore_contract, funding_contract, _, _) = _create_test_addresses(env);
+        env.register(
+            GovernanceContract,
+            (
+                admin,
+                fractcore_contract,
+                funding_contract,
+                60u32,
+                40u32,
+                7u32,
+            ),
+        )
     }
 
     #[test]
@@ -39,10 +57,10 @@ mod integration_tests {
 
         // Test NoExecution action
         let action1 = PollAction::NoExecution;
-        match action1 {
-            PollAction::NoExecution => assert!(true),
-            _ => panic!("Expected NoExecution"),
-        }
+        assert!(
+            matches!(action1, PollAction::NoExecution),
+            "Expected NoExecution"
+        );
 
         // Test DistributeFunds action
         let action2 =
@@ -88,6 +106,7 @@ mod integration_tests {
             is_active: true,
             votes: soroban_sdk::Map::new(&env),
             total_voters: 0,
+            weights: Vec::new(&env),
         };
 
         assert_eq!(poll.id, 1);
@@ -121,6 +140,9 @@ mod integration_tests {
             should_execute: true,
             approval_percentage: 75,
             participation_percentage: 80,
+            active_participation: 80,
+            total_supply: 1000,
+            active_supply: 1000,
         };
 
         assert!(result.should_execute);
@@ -221,6 +243,7 @@ mod integration_tests {
             is_active: true,
             votes: soroban_sdk::Map::new(&env),
             total_voters: 0,
+            weights: Vec::new(&env),
         };
 
         // Verify the tournament poll structure
@@ -267,4 +290,1872 @@ mod integration_tests {
         assert_eq!(distribution_call.0, asset_id);
         assert_eq!(distribution_call.1, 5000u128);
     }
+
+    // Action allowlists
+
+    struct AllowlistSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        admin: Address, // Also the fractcore asset manager
+        asset_id: u64,
+        holder: Address,
+    }
+
+    fn allowlist_setup() -> AllowlistSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            admin,
+        } = setup();
+        let holder = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&holder, &100);
+
+        AllowlistSetup {
+            env,
+            client,
+            admin,
+            asset_id,
+            holder,
+        }
+    }
+
+    fn try_create_allowed(s: &AllowlistSetup, action: &PollAction) -> Result<u32, GovernanceError> {
+        match s.client.try_create_poll(
+            &s.holder,
+            &s.asset_id,
+            &String::from_str(&s.env, "Allowlist poll"),
+            &String::from_str(&s.env, "Is this action allowed"),
+            action,
+            &Some(1),
+        ) {
+            Ok(Ok(poll_id)) => Ok(poll_id),
+            Err(Ok(err)) => Err(err),
+            _ => panic!("unexpected create_poll result"),
+        }
+    }
+
+    #[test]
+    fn test_all_actions_allowed_by_default() {
+        let s = allowlist_setup();
+        assert_eq!(s.client.get_allowed_actions(&s.asset_id), ALL_POLL_ACTIONS);
+        assert!(try_create_allowed(&s, &PollAction::TransferTokens(s.admin.clone(), 10)).is_ok());
+    }
+
+    #[test]
+    fn test_restricted_action_rejected_at_create_poll() {
+        let s = allowlist_setup();
+        s.client.set_allowed_actions(
+            &s.admin,
+            &s.asset_id,
+            &(ACTION_NO_EXECUTION | ACTION_DISTRIBUTE_FUNDS),
+        );
+
+        assert_eq!(
+            try_create_allowed(&s, &PollAction::TransferTokens(s.admin.clone(), 10)),
+            Err(GovernanceError::ActionNotAllowed)
+        );
+        assert_eq!(
+            try_create_allowed(&s, &PollAction::SplitAsset(2)),
+            Err(GovernanceError::ActionNotAllowed)
+        );
+        assert!(try_create_allowed(&s, &PollAction::NoExecution).is_ok());
+        assert!(try_create_allowed(
+            &s,
+            &PollAction::DistributeFunds(100, String::from_str(&s.env, "Rent"))
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_only_asset_manager_sets_allowlist() {
+        let s = allowlist_setup();
+        assert_eq!(
+            s.client
+                .try_set_allowed_actions(&s.holder, &s.asset_id, &ACTION_NO_EXECUTION),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+        assert_eq!(
+            s.client
+                .try_set_allowed_actions(&s.admin, &s.asset_id, &(ALL_POLL_ACTIONS + 1)),
+            Err(Ok(GovernanceError::InvalidParameters))
+        );
+        assert_eq!(s.client.get_allowed_actions(&s.asset_id), ALL_POLL_ACTIONS);
+    }
+
+    #[test]
+    fn test_meta_poll_updates_allowlist() {
+        let s = allowlist_setup();
+        s.client
+            .set_allowed_actions(&s.admin, &s.asset_id, &ACTION_NO_EXECUTION);
+
+        // Meta-polls stay available even when every other action is disabled
+        let poll_id = try_create_allowed(
+            &s,
+            &PollAction::SetAllowedActions(ACTION_NO_EXECUTION | ACTION_TRANSFER_TOKENS),
+        )
+        .unwrap();
+        // The only holder votes, so the poll executes right away
+        s.client.vote(&s.holder, &poll_id, &1);
+        assert!(!s.client.get_poll(&poll_id).is_active);
+
+        assert_eq!(
+            s.client.get_allowed_actions(&s.asset_id),
+            ACTION_NO_EXECUTION | ACTION_TRANSFER_TOKENS
+        );
+        assert!(try_create_allowed(&s, &PollAction::TransferTokens(s.admin.clone(), 10)).is_ok());
+    }
+
+    #[test]
+    fn test_petty_cash_poll_always_allowed() {
+        let s = allowlist_setup();
+        s.client
+            .set_allowed_actions(&s.admin, &s.asset_id, &ACTION_NO_EXECUTION);
+
+        // Holders can always vote the manager's petty cash limits down
+        let poll_id = try_create_allowed(&s, &PollAction::SetPettyCash(0, 0)).unwrap();
+        s.client.vote(&s.holder, &poll_id, &1);
+        assert!(!s.client.get_poll(&poll_id).is_active);
+    }
+
+    // Checkpointed polls
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    /// Starts at timestamp 1000, so balance checkpoints have a past to look at
+    fn checkpoint_setup() -> Setup {
+        let s = setup();
+        s.env.ledger().with_mut(|li| li.timestamp = 1000);
+        s
+    }
+
+    fn create_checkpointed_poll(
+        s: &Setup,
+        asset_id: u64,
+        duration_days: u32,
+        interval_days: u32,
+    ) -> Result<u32, GovernanceError> {
+        match s.client.try_create_checkpointed_poll(
+            &s.admin,
+            &asset_id,
+            &String::from_str(&s.env, "Long poll"),
+            &String::from_str(&s.env, "Balances re-snapshotted weekly"),
+            &PollAction::NoExecution,
+            &Some(duration_days),
+            &interval_days,
+        ) {
+            Ok(Ok(poll_id)) => Ok(poll_id),
+            Err(Ok(err)) => Err(err),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    fn advance(env: &Env, seconds: u64) {
+        env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
+
+    #[test]
+    fn test_power_is_min_of_creation_and_latest_checkpoint() {
+        let s = checkpoint_setup();
+        let holder = Address::generate(&s.env);
+        let seller = Address::generate(&s.env);
+        let buyer = Address::generate(&s.env);
+
+        let asset_id = s.fractcore_client.mint(&holder, &1000);
+        s.fractcore_client
+            .transfer(&holder, &seller, &asset_id, &500);
+
+        advance(&s.env, 100);
+        let poll_id = create_checkpointed_poll(&s, asset_id, 28, 7).unwrap();
+        assert_eq!(
+            s.client.get_poll_checkpoint_interval(&poll_id),
+            Some(7 * DAY)
+        );
+
+        // Fractions bought after creation never carry power
+        advance(&s.env, 100);
+        s.fractcore_client
+            .transfer(&seller, &buyer, &asset_id, &300);
+        assert_eq!(s.client.get_poll_voting_power(&poll_id, &buyer), 0);
+        assert!(!s.client.can_vote(&buyer, &poll_id));
+
+        // Until the first checkpoint the seller keeps the creation balance
+        assert_eq!(s.client.get_poll_voting_power(&poll_id, &seller), 500);
+
+        // A holder who lends fractions out and gets them back between checkpoints
+        // is unaffected
+        s.fractcore_client
+            .transfer(&holder, &buyer, &asset_id, &100);
+        s.fractcore_client
+            .transfer(&buyer, &holder, &asset_id, &100);
+
+        advance(&s.env, 7 * DAY);
+        assert_eq!(s.client.get_poll_voting_power(&poll_id, &seller), 200);
+        assert_eq!(s.client.get_poll_voting_power(&poll_id, &holder), 500);
+        assert_eq!(s.client.get_poll_voting_power(&poll_id, &buyer), 0);
+
+        s.client.vote(&seller, &poll_id, &1u32);
+        s.client.vote(&holder, &poll_id, &0u32);
+        let poll = s.client.get_poll(&poll_id);
+        assert_eq!(poll.votes.get(seller).unwrap().voting_power, 200);
+        assert_eq!(poll.votes.get(holder).unwrap().voting_power, 500);
+
+        // Plain polls still use live balances
+        let live_poll = s.client.create_poll(
+            &s.admin,
+            &asset_id,
+            &String::from_str(&s.env, "Short poll"),
+            &String::from_str(&s.env, "Live balances"),
+            &PollAction::NoExecution,
+            &Some(1),
+        );
+        assert_eq!(s.client.get_poll_checkpoint_interval(&live_poll), None);
+        assert_eq!(s.client.get_poll_voting_power(&live_poll, &buyer), 300);
+    }
+
+    #[test]
+    fn test_voters_without_power_are_rejected() {
+        let s = checkpoint_setup();
+        let holder = Address::generate(&s.env);
+        let buyer = Address::generate(&s.env);
+        let stranger = Address::generate(&s.env);
+
+        let asset_id = s.fractcore_client.mint(&holder, &1000);
+        let poll_id = create_checkpointed_poll(&s, asset_id, 28, 7).unwrap();
+
+        // A live balance is not enough once the poll has snapshotted holders
+        advance(&s.env, 100);
+        s.fractcore_client
+            .transfer(&holder, &buyer, &asset_id, &400);
+        for voter in [&buyer, &stranger] {
+            assert_eq!(
+                s.client.try_vote(voter, &poll_id, &1u32),
+                Err(Ok(GovernanceError::InsufficientVotingPower))
+            );
+        }
+        let poll = s.client.get_poll(&poll_id);
+        assert_eq!(poll.total_voters, 0);
+        assert!(poll.votes.is_empty());
+
+        s.client.vote(&holder, &poll_id, &1u32);
+        assert_eq!(s.client.get_poll(&poll_id).total_voters, 1);
+    }
+
+    #[test]
+    fn test_checkpoint_interval_must_fit_in_poll() {
+        let s = checkpoint_setup();
+        let holder = Address::generate(&s.env);
+        let asset_id = s.fractcore_client.mint(&holder, &1000);
+
+        assert_eq!(
+            create_checkpointed_poll(&s, asset_id, 28, 0),
+            Err(GovernanceError::InvalidParameters)
+        );
+        assert_eq!(
+            create_checkpointed_poll(&s, asset_id, 7, 7),
+            Err(GovernanceError::InvalidParameters)
+        );
+        assert!(create_checkpointed_poll(&s, asset_id, 8, 7).is_ok());
+    }
+
+    // Upgrades, storage migration and poll export
+
+    #[allow(clippy::too_many_arguments)]
+    mod governance {
+        soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/governance.wasm");
+    }
+
+    struct MigrationSetup {
+        env: Env,
+        old: GovernanceContractClient<'static>,
+        new: GovernanceContractClient<'static>,
+        admin: Address,
+        asset_id: u64,
+        holders: Vec<Address>,
+    }
+
+    /// Two governance instances over one fractcore, and an asset split evenly
+    /// between three holders
+    fn migration_setup() -> MigrationSetup {
+        let s = setup();
+        let new = s.register_governance();
+        let (asset_id, holders) = s.split_asset(3);
+        let Setup {
+            env,
+            client: old,
+            admin,
+            ..
+        } = s;
+
+        MigrationSetup {
+            env,
+            old,
+            new,
+            admin,
+            asset_id,
+            holders,
+        }
+    }
+
+    fn create_migrated_poll(s: &MigrationSetup, client: &GovernanceContractClient) -> u32 {
+        client.create_poll(
+            &s.admin,
+            &s.asset_id,
+            &String::from_str(&s.env, "Migrated poll"),
+            &String::from_str(&s.env, "Carried across a redeployment"),
+            &PollAction::NoExecution,
+            &Some(3),
+        )
+    }
+
+    /// Replace an instance's code the only way governance allows: an executed
+    /// `UpgradeContract` poll of the platform asset naming the instance
+    fn upgrade_by_poll(
+        s: &MigrationSetup,
+        client: &GovernanceContractClient,
+        wasm_hash: &BytesN<32>,
+    ) {
+        client.set_platform_governance(&s.admin, &Address::generate(&s.env), &s.asset_id);
+        let poll_id = client.create_poll(
+            &s.admin,
+            &s.asset_id,
+            &String::from_str(&s.env, "Upgrade governance"),
+            &String::from_str(&s.env, "Install the uploaded build"),
+            &PollAction::UpgradeContract(client.address.clone(), wasm_hash.clone()),
+            &Some(3),
+        );
+        for holder in s.holders.iter() {
+            client.vote(&holder, &poll_id, &1);
+        }
+        assert_eq!(
+            client.get_execution_record(&poll_id).status,
+            ExecutionStatus::Executed
+        );
+    }
+
+    /// Move `keys` in batches of at most `MAX_MIGRATE_BATCH`, closing the
+    /// migration with the last one; returns the number of entries moved
+    fn migrate_all(s: &MigrationSetup, client: &GovernanceContractClient, keys: &Vec<Val>) -> u32 {
+        let mut moved = 0;
+        let mut start = 0;
+        while start < keys.len() {
+            let end = (start + MAX_MIGRATE_BATCH).min(keys.len());
+            let batch = keys.slice(start..end);
+            moved += client.migrate_storage(&s.admin, &batch, &(end == keys.len()));
+            start = end;
+        }
+        moved
+    }
+
+    #[test]
+    fn test_upgrade_then_migrate_storage_keeps_polls() {
+        let s = migration_setup();
+        let poll_id = create_migrated_poll(&s, &s.old);
+        s.old.vote(&s.holders.get(0).unwrap(), &poll_id, &1);
+        let poll = s.old.get_poll(&poll_id);
+
+        // A platform poll replaces the code, and the entries stay where they are
+        let wasm_hash = s.env.deployer().upload_contract_wasm(governance::WASM);
+        let stranger = Address::generate(&s.env);
+        upgrade_by_poll(&s, &s.old, &wasm_hash);
+        assert_eq!(s.old.get_poll(&poll_id), poll);
+        assert_eq!(
+            s.old
+                .try_migrate_storage(&s.admin, &Vec::new(&s.env), &true),
+            Err(Ok(GovernanceError::AlreadyInitialized))
+        );
+
+        // A deployment from before namespacing holds every entry under its bare
+        // key, the admin's included, and reads miss until the admin moves them
+        let keys = strip_namespaces(&s.env, &s.old.address, &[DataKey::NAMESPACE])
+            .get(DataKey::NAMESPACE)
+            .unwrap();
+        assert_eq!(
+            s.old.try_get_poll(&poll_id),
+            Err(Ok(GovernanceError::PollNotFound))
+        );
+        assert_eq!(
+            s.old
+                .try_initialize(&stranger, &stranger, &stranger, &60, &30, &7),
+            Err(Ok(GovernanceError::AlreadyInitialized))
+        );
+        assert_eq!(
+            s.old.try_migrate_storage(&stranger, &keys, &true),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+        assert_eq!(migrate_all(&s, &s.old, &keys), keys.len());
+
+        // The poll carries on where it stopped
+        assert_eq!(s.old.get_poll(&poll_id), poll);
+        assert_eq!(
+            s.old.try_vote(&s.holders.get(0).unwrap(), &poll_id, &0),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+        s.old.vote(&s.holders.get(1).unwrap(), &poll_id, &1);
+        assert_eq!(
+            s.old.try_migrate_storage(&s.admin, &keys, &true),
+            Err(Ok(GovernanceError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_migrate_storage_upgrades_version_one_polls() {
+        let s = migration_setup();
+        let poll_id = create_migrated_poll(&s, &s.old);
+        s.old.vote(&s.holders.get(0).unwrap(), &poll_id, &1);
+        let poll = s.old.get_poll(&poll_id);
+
+        // A deployment from before weighted voting and namespacing stored the
+        // poll under its bare key, without `weights` and without a version
+        let legacy = PollV1 {
+            id: poll.id,
+            asset_id: poll.asset_id,
+            creator: poll.creator.clone(),
+            title: poll.title.clone(),
+            description: poll.description.clone(),
+            options: poll.options.clone(),
+            action: poll.action.clone(),
+            start_time: poll.start_time,
+            end_time: poll.end_time,
+            is_active: poll.is_active,
+            votes: poll.votes.clone(),
+            total_voters: poll.total_voters,
+        };
+        let wasm_hash = s.env.deployer().upload_contract_wasm(governance::WASM);
+        upgrade_by_poll(&s, &s.old, &wasm_hash);
+        let keys = strip_namespaces(&s.env, &s.old.address, &[DataKey::NAMESPACE])
+            .get(DataKey::NAMESPACE)
+            .unwrap();
+        s.env.as_contract(&s.old.address, || {
+            let storage = s.env.storage().persistent();
+            storage.set(&DataKey::Poll(poll_id), &legacy);
+            storage.remove(&DataKey::PollVersion(poll_id));
+        });
+
+        // The migrated entry is read through the version 1 shape
+        assert_eq!(migrate_all(&s, &s.old, &keys), keys.len() - 1);
+        assert_eq!(s.old.get_poll(&poll_id), legacy.upgrade(&s.env));
+        assert_eq!(
+            s.old.try_vote(&s.holders.get(0).unwrap(), &poll_id, &0),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+        s.old.vote(&s.holders.get(1).unwrap(), &poll_id, &1);
+        assert_eq!(s.old.get_poll(&poll_id).total_voters, 2);
+    }
+
+    #[test]
+    fn test_export_import_preserves_polls_and_votes() {
+        let s = migration_setup();
+        let poll_count = POLL_EXPORT_PAGE_SIZE + 2;
+        for _ in 0..poll_count {
+            create_migrated_poll(&s, &s.old);
+        }
+
+        // Poll 1 is finalized once every holder voted; the last poll is in flight
+        for holder in s.holders.iter() {
+            s.old.vote(&holder, &1, &1);
+        }
+        assert!(!s.old.get_poll(&1).is_active);
+        s.old.vote(&s.holders.get(0).unwrap(), &poll_count, &0);
+        s.old.vote(&s.holders.get(1).unwrap(), &poll_count, &1);
+
+        let first = s.old.export_polls(&s.admin, &0);
+        let second = s.old.export_polls(&s.admin, &1);
+        assert_eq!(first.polls.len(), POLL_EXPORT_PAGE_SIZE);
+        assert_eq!(second.polls.len(), 2);
+        assert_eq!(first.poll_count, poll_count);
+        assert!(first.certificates.contains_key(1));
+        assert_eq!(s.old.export_polls(&s.admin, &2).polls.len(), 0);
+
+        // Pages go in order
+        assert_eq!(
+            s.new.try_import_polls(&s.admin, &second),
+            Err(Ok(GovernanceError::ImportMismatch))
+        );
+        assert_eq!(s.new.import_polls(&s.admin, &first), POLL_EXPORT_PAGE_SIZE);
+        assert_eq!(s.new.import_polls(&s.admin, &second), 2);
+
+        for poll_id in 1..=poll_count {
+            assert_eq!(s.new.get_poll(&poll_id), s.old.get_poll(&poll_id));
+        }
+        assert_eq!(s.new.get_certificate(&1), s.old.get_certificate(&1));
+        for holder in s.holders.iter() {
+            assert_eq!(
+                s.new.get_vote_receipt(&1, &holder),
+                s.old.get_vote_receipt(&1, &holder)
+            );
+        }
+        assert_eq!(
+            s.new.get_vote_results(&poll_count),
+            s.old.get_vote_results(&poll_count)
+        );
+        assert_eq!(
+            s.new.get_active_polls().len(),
+            s.old.get_active_polls().len()
+        );
+        assert_eq!(
+            s.new.get_asset_governance_summary(&s.asset_id),
+            s.old.get_asset_governance_summary(&s.asset_id)
+        );
+
+        // The in-flight poll carries on where it stopped, and new ids follow on
+        let voter = s.holders.get(0).unwrap();
+        assert_eq!(
+            s.new.try_vote(&voter, &poll_count, &1),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+        s.new.vote(&s.holders.get(2).unwrap(), &poll_count, &1);
+        assert!(!s.new.get_poll(&poll_count).is_active);
+        assert_eq!(create_migrated_poll(&s, &s.new), poll_count + 1);
+    }
+
+    #[test]
+    fn test_import_rejects_inconsistent_or_misplaced_pages() {
+        let s = migration_setup();
+        create_migrated_poll(&s, &s.old);
+        create_migrated_poll(&s, &s.old);
+        s.old.vote(&s.holders.get(0).unwrap(), &1, &1);
+        let page = s.old.export_polls(&s.admin, &0);
+
+        // Reads and writes of the snapshot are the admin's alone
+        let outsider = Address::generate(&s.env);
+        assert_eq!(
+            s.old.try_export_polls(&outsider, &0),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+        assert_eq!(
+            s.new.try_import_polls(&outsider, &page),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+
+        // A poll whose voter count disagrees with its votes is refused
+        let mut inconsistent = page.clone();
+        let mut record = inconsistent.polls.get(1).unwrap();
+        record.poll.total_voters = 1;
+        inconsistent.polls.set(1, record);
+        assert_eq!(
+            s.new.try_import_polls(&s.admin, &inconsistent),
+            Err(Ok(GovernanceError::ImportMismatch))
+        );
+
+        // So is a page missing polls its count promises
+        let mut short = page.clone();
+        short.poll_count = 3;
+        assert_eq!(
+            s.new.try_import_polls(&s.admin, &short),
+            Err(Ok(GovernanceError::ImportMismatch))
+        );
+
+        // Once imported a page cannot be replayed
+        s.new.import_polls(&s.admin, &page);
+        assert_eq!(
+            s.new.try_import_polls(&s.admin, &page),
+            Err(Ok(GovernanceError::ImportMismatch))
+        );
+
+        // An instance with polls of its own is not fresh
+        let third = GovernanceContractClient::new(
+            &s.env,
+            &s.env.register(
+                GovernanceContract,
+                (
+                    s.admin.clone(),
+                    Address::generate(&s.env),
+                    Address::generate(&s.env),
+                    60u32,
+                    30u32,
+                    7u32,
+                ),
+            ),
+        );
+        create_migrated_poll(&s, &third);
+        assert_eq!(
+            third.try_import_polls(&s.admin, &page),
+            Err(Ok(GovernanceError::ImportMismatch))
+        );
+    }
+
+    // Platform asset polls
+
+    #[allow(clippy::too_many_arguments)]
+    mod trading {
+        soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/trading.wasm");
+    }
+
+    struct PlatformSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        fractcore_client: fractcore::Client<'static>,
+        trading_client: trading::Client<'static>,
+        admin: Address,
+        platform_asset: u64,
+        other_asset: u64,
+        holder: Address,
+    }
+
+    /// Governance as trading's governor, voting with the platform asset
+    fn platform_setup() -> PlatformSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            admin,
+        } = setup();
+
+        let trading_contract_id = env.register(
+            trading::WASM,
+            (
+                admin.clone(),
+                fractcore_client.address.clone(),
+                Address::generate(&env),
+            ),
+        );
+        let trading_client = trading::Client::new(&env, &trading_contract_id);
+
+        let holder = Address::generate(&env);
+        let platform_asset = fractcore_client.mint(&holder, &100);
+        let other_asset = fractcore_client.mint(&holder, &100);
+
+        trading_client.set_governor(&admin, &Some(client.address.clone()));
+        client.set_platform_governance(&admin, &trading_contract_id, &platform_asset);
+
+        PlatformSetup {
+            env,
+            client,
+            fractcore_client,
+            trading_client,
+            admin,
+            platform_asset,
+            other_asset,
+            holder,
+        }
+    }
+
+    fn try_create_platform(
+        s: &PlatformSetup,
+        asset_id: u64,
+        action: &PollAction,
+    ) -> Result<u32, GovernanceError> {
+        match s.client.try_create_poll(
+            &s.holder,
+            &asset_id,
+            &String::from_str(&s.env, "Trading fee"),
+            &String::from_str(&s.env, "Charge 2% on sales"),
+            action,
+            &Some(1),
+        ) {
+            Ok(Ok(poll_id)) => Ok(poll_id),
+            Err(Ok(err)) => Err(err),
+            _ => panic!("unexpected create_poll result"),
+        }
+    }
+
+    #[test]
+    fn test_platform_asset_vote_sets_trading_config() {
+        let s = platform_setup();
+        let treasury = Address::generate(&s.env);
+        let action = PollAction::SetTradingConfig(200, treasury.clone(), 1800, 1209600);
+
+        let poll_id = try_create_platform(&s, s.platform_asset, &action).unwrap();
+        // The only holder votes, so the poll executes right away
+        s.client.vote(&s.holder, &poll_id, &1);
+        assert!(!s.client.get_poll(&poll_id).is_active);
+
+        let fee = s.trading_client.get_trading_fee().unwrap();
+        assert_eq!(fee.bps, 200);
+        assert_eq!(fee.recipient, treasury);
+        let limits = s.trading_client.get_sale_duration_limits();
+        assert_eq!((limits.min_seconds, limits.max_seconds), (1800, 1209600));
+    }
+
+    #[test]
+    fn test_trading_config_only_on_platform_asset() {
+        let s = platform_setup();
+        let action = PollAction::SetTradingConfig(200, s.admin.clone(), 1800, 1209600);
+
+        assert_eq!(
+            try_create_platform(&s, s.other_asset, &action),
+            Err(GovernanceError::ActionNotAllowed)
+        );
+
+        // Asset managers cannot lock the platform asset's holders out
+        s.client
+            .set_allowed_actions(&s.admin, &s.platform_asset, &ACTION_NO_EXECUTION);
+        assert!(try_create_platform(&s, s.platform_asset, &action).is_ok());
+    }
+
+    #[test]
+    fn test_only_admin_designates_platform_asset() {
+        let s = platform_setup();
+        let trading_contract = s.trading_client.address.clone();
+
+        assert_eq!(
+            s.client
+                .try_set_platform_governance(&s.holder, &trading_contract, &s.other_asset),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+        assert_eq!(
+            s.client.get_platform_governance(),
+            Some(PlatformGovernance {
+                trading_contract,
+                asset_id: s.platform_asset,
+            })
+        );
+    }
+
+    #[test]
+    fn test_platform_asset_vote_upgrades_contract() {
+        let s = platform_setup();
+        let trading_contract = s.trading_client.address.clone();
+        let wasm_hash = s.env.deployer().upload_contract_wasm(trading::WASM);
+
+        // Governance is the only path: the admin cannot upgrade directly
+        assert!(s.trading_client.try_upgrade(&s.admin, &wasm_hash).is_err());
+
+        let action = PollAction::UpgradeContract(trading_contract.clone(), wasm_hash.clone());
+        let poll_id = try_create_platform(&s, s.platform_asset, &action).unwrap();
+        s.client.vote(&s.holder, &poll_id, &1);
+
+        let upgraded = s
+            .env
+            .events()
+            .all()
+            .iter()
+            .find(|(contract, topics, _)| {
+                *contract == trading_contract
+                    && *topics == (symbol_short!("upgraded"),).into_val(&s.env)
+            })
+            .map(|(_, _, data)| data)
+            .unwrap();
+        let hashes = <(Option<BytesN<32>>, BytesN<32>)>::try_from_val(&s.env, &upgraded).unwrap();
+        assert_eq!(hashes, (None, wasm_hash));
+        assert_eq!(
+            s.client.get_execution_record(&poll_id).status,
+            ExecutionStatus::Executed
+        );
+
+        // The upgraded contract keeps its storage
+        assert_eq!(
+            s.trading_client.get_governor(),
+            Some(s.client.address.clone())
+        );
+    }
+
+    #[test]
+    fn test_fractcore_accepts_the_governance_contract_built_for_it() {
+        let s = platform_setup();
+
+        // Fractcore asks the target which fractcore it governs before the hand-over
+        assert_eq!(
+            s.client.get_fractcore_contract(),
+            s.fractcore_client.address
+        );
+        s.fractcore_client
+            .set_governance_contract(&s.admin, &s.client.address);
+        assert_eq!(
+            s.fractcore_client.get_governance_contract(),
+            Some(s.client.address.clone())
+        );
+    }
+
+    #[test]
+    fn test_upgrade_only_platform_contracts_on_platform_asset() {
+        let s = platform_setup();
+        let wasm_hash = s.env.deployer().upload_contract_wasm(trading::WASM);
+        let trading_upgrade =
+            PollAction::UpgradeContract(s.trading_client.address.clone(), wasm_hash.clone());
+
+        assert_eq!(
+            try_create_platform(&s, s.other_asset, &trading_upgrade),
+            Err(GovernanceError::ActionNotAllowed)
+        );
+
+        // Governance itself is upgraded only by a platform poll as well
+        let self_upgrade = PollAction::UpgradeContract(s.client.address.clone(), wasm_hash.clone());
+        assert_eq!(
+            try_create_platform(&s, s.other_asset, &self_upgrade),
+            Err(GovernanceError::ActionNotAllowed)
+        );
+        assert!(try_create_platform(&s, s.platform_asset, &self_upgrade).is_ok());
+
+        let stranger = PollAction::UpgradeContract(Address::generate(&s.env), wasm_hash);
+        assert_eq!(
+            try_create_platform(&s, s.platform_asset, &stranger),
+            Err(GovernanceError::InvalidParameters)
+        );
+    }
+
+    #[test]
+    fn test_asset_vote_sets_exit_queue() {
+        let s = platform_setup();
+        let action = PollAction::SetExitQueue(50, 10, 86400);
+
+        // Any asset's holders may pace their own large sellers
+        let poll_id = try_create_platform(&s, s.other_asset, &action).unwrap();
+        s.client.vote(&s.holder, &poll_id, &1);
+
+        assert_eq!(
+            s.trading_client.get_exit_queue_config(&s.other_asset),
+            Some(trading::ExitQueueConfig {
+                size_threshold: 50,
+                tranche: 10,
+                window_seconds: 86400,
+            })
+        );
+        assert_eq!(
+            s.trading_client.get_exit_queue_config(&s.platform_asset),
+            None
+        );
+
+        // A zero tranche removes the queue
+        let poll_id =
+            try_create_platform(&s, s.other_asset, &PollAction::SetExitQueue(0, 0, 0)).unwrap();
+        s.client.vote(&s.holder, &poll_id, &1);
+        assert_eq!(s.trading_client.get_exit_queue_config(&s.other_asset), None);
+    }
+
+    // Quorum modes
+
+    struct QuorumSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        admin: Address, // Also the fractcore asset manager
+        asset_id: u64,
+        treasury: Address,
+        voter: Address,
+    }
+
+    fn quorum_setup() -> QuorumSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            admin,
+        } = setup();
+
+        // 80% of supply sits idle in the treasury
+        let treasury = Address::generate(&env);
+        let voter = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&treasury, &1000);
+        fractcore_client.transfer(&treasury, &voter, &asset_id, &200);
+
+        QuorumSetup {
+            env,
+            client,
+            admin,
+            asset_id,
+            treasury,
+            voter,
+        }
+    }
+
+    fn approve_and_expire(s: &QuorumSetup) -> u32 {
+        let poll_id = s.client.create_poll(
+            &s.voter,
+            &s.asset_id,
+            &String::from_str(&s.env, "Quorum poll"),
+            &String::from_str(&s.env, "Only the active holder votes"),
+            &PollAction::NoExecution,
+            &Some(1),
+        );
+        s.client.vote(&s.voter, &poll_id, &1);
+        s.env
+            .ledger()
+            .with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        poll_id
+    }
+
+    #[test]
+    fn test_total_supply_quorum_unreachable_with_idle_treasury() {
+        let s = quorum_setup();
+        let poll_id = approve_and_expire(&s);
+
+        let result = s.client.check_poll_execution(&poll_id);
+        assert_eq!(result.participation_percentage, 20);
+        assert_eq!(result.active_participation, 20);
+        assert_eq!(result.active_supply, result.total_supply);
+        assert!(!s.client.check_and_execute_poll(&poll_id));
+    }
+
+    #[test]
+    fn test_active_supply_quorum_excludes_treasury() {
+        let s = quorum_setup();
+        let mode = QuorumMode::ActiveSupply(vec![&s.env, s.treasury.clone()]);
+        s.client.set_quorum_mode(&s.admin, &s.asset_id, &mode);
+        assert_eq!(s.client.get_quorum_mode(&s.asset_id), mode);
+
+        let poll_id = approve_and_expire(&s);
+
+        // Both figures are reported; quorum uses the active one
+        let result = s.client.check_poll_execution(&poll_id);
+        assert_eq!(result.total_supply, 1000);
+        assert_eq!(result.active_supply, 200);
+        assert_eq!(result.participation_percentage, 20);
+        assert_eq!(result.active_participation, 100);
+        assert!(result.should_execute);
+        assert!(s.client.check_and_execute_poll(&poll_id));
+    }
+
+    #[test]
+    fn test_set_quorum_mode_validation() {
+        let s = quorum_setup();
+
+        assert_eq!(
+            s.client
+                .try_set_quorum_mode(&s.voter, &s.asset_id, &QuorumMode::TotalSupply),
+            Err(Ok(GovernanceError::Unauthorized))
+        );
+        assert_eq!(
+            s.client.try_set_quorum_mode(
+                &s.admin,
+                &s.asset_id,
+                &QuorumMode::ActiveSupply(vec![&s.env])
+            ),
+            Err(Ok(GovernanceError::InvalidParameters))
+        );
+        assert_eq!(
+            s.client.try_set_quorum_mode(
+                &s.admin,
+                &s.asset_id,
+                &QuorumMode::ActiveSupply(vec![&s.env, s.treasury.clone(), s.treasury.clone()])
+            ),
+            Err(Ok(GovernanceError::InvalidParameters))
+        );
+    }
+
+    // Vote receipts and voter lists
+
+    struct ReceiptSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        admin: Address,
+        asset_id: u64,
+        holders: Vec<Address>,
+    }
+
+    /// One asset split evenly between `holder_count` holders
+    fn receipt_setup(holder_count: u32) -> ReceiptSetup {
+        let s = setup();
+        let (asset_id, holders) = s.split_asset(holder_count);
+        let Setup {
+            env, client, admin, ..
+        } = s;
+
+        ReceiptSetup {
+            env,
+            client,
+            admin,
+            asset_id,
+            holders,
+        }
+    }
+
+    fn create_audited_poll(s: &ReceiptSetup) -> u32 {
+        s.client.create_poll(
+            &s.admin,
+            &s.asset_id,
+            &String::from_str(&s.env, "Audit poll"),
+            &String::from_str(&s.env, "Who voted for what"),
+            &PollAction::NoExecution,
+            &Some(1),
+        )
+    }
+
+    #[test]
+    fn test_vote_receipt_recorded() {
+        let s = receipt_setup(3);
+        let poll_id = create_audited_poll(&s);
+        let voter = s.holders.get(1).unwrap();
+
+        assert_eq!(
+            s.client.try_get_vote_receipt(&poll_id, &voter),
+            Err(Ok(GovernanceError::ReceiptNotFound))
+        );
+
+        s.client.vote(&voter, &poll_id, &1);
+
+        let receipt = s.client.get_vote_receipt(&poll_id, &voter);
+        assert_eq!(receipt.poll_id, poll_id);
+        assert_eq!(receipt.voter, voter);
+        assert_eq!(receipt.option_index, 1);
+        assert_eq!(receipt.voting_power, 10);
+        assert_eq!(s.client.get_option_voter_count(&poll_id, &1), 1);
+        assert_eq!(s.client.get_option_voter_count(&poll_id, &0), 0);
+    }
+
+    #[test]
+    fn test_bulk_poll_reads() {
+        let s = receipt_setup(3);
+        let first = create_audited_poll(&s);
+        let second = create_audited_poll(&s);
+        s.client.vote(&s.holders.get(0).unwrap(), &second, &1);
+
+        let ids = Vec::from_array(&s.env, [second, 99, first]);
+        let polls = s.client.get_polls_bulk(&ids);
+        assert_eq!(polls.len(), 3);
+        assert_eq!(polls.get(0).unwrap().unwrap().id, second);
+        assert!(polls.get(1).unwrap().is_none());
+        assert_eq!(polls.get(2).unwrap().unwrap().id, first);
+
+        let results = s.client.get_vote_results_bulk(&ids);
+        let second_results = results.get(0).unwrap().unwrap();
+        assert_eq!(second_results.poll_id, second);
+        assert_eq!(second_results.vote_counts, Vec::from_array(&s.env, [0, 10]));
+        assert!(results.get(1).unwrap().is_none());
+        assert_eq!(results.get(2).unwrap().unwrap().total_voters, 0);
+
+        let mut too_many = Vec::new(&s.env);
+        for id in 0..=MAX_BATCH_SIZE {
+            too_many.push_back(id);
+        }
+        assert_eq!(
+            s.client.try_get_polls_bulk(&too_many),
+            Err(Ok(GovernanceError::BatchTooLarge))
+        );
+    }
+
+    #[test]
+    fn test_polls_created_since_and_notice() {
+        let s = receipt_setup(3);
+        let start = s.env.ledger().sequence();
+        let first = create_audited_poll(&s);
+
+        // The notice is keyed by asset and carries the holder count and snapshot ledger
+        let (_, topics, data) = s.env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (String::from_str(&s.env, "poll_notice"), s.asset_id).into_val(&s.env)
+        );
+        let poll = s.client.get_poll(&first);
+        let notice: (u32, u32, u32, u64) = data.into_val(&s.env);
+        assert_eq!(notice, (first, 3, start, poll.end_time));
+
+        s.env.ledger().set_sequence_number(start + 10);
+        let second = create_audited_poll(&s);
+        let third = create_audited_poll(&s);
+
+        assert_eq!(
+            s.client.polls_created_since(&start),
+            Vec::from_array(&s.env, [first, second, third])
+        );
+        assert_eq!(
+            s.client.polls_created_since(&(start + 1)),
+            Vec::from_array(&s.env, [second, third])
+        );
+        assert!(s.client.polls_created_since(&(start + 11)).is_empty());
+
+        // Pages are capped at MAX_BATCH_SIZE
+        for _ in 0..MAX_BATCH_SIZE {
+            create_audited_poll(&s);
+        }
+        let page = s.client.polls_created_since(&start);
+        assert_eq!(page.len(), MAX_BATCH_SIZE);
+        assert_eq!(page.first().unwrap(), first);
+    }
+
+    #[test]
+    fn test_asset_polls_cursor_stops_at_walk_start() {
+        let s = receipt_setup(1);
+        let polls = [
+            create_audited_poll(&s),
+            create_audited_poll(&s),
+            create_audited_poll(&s),
+        ];
+
+        let (first, cursor) = s.client.get_asset_polls_page(&s.asset_id, &None, &2);
+        assert_eq!(first, Vec::from_array(&s.env, [polls[0], polls[1]]));
+
+        // A poll created mid-walk is left for the next walk
+        let newer = create_audited_poll(&s);
+        let (rest, cursor) = s.client.get_asset_polls_page(&s.asset_id, &cursor, &2);
+        assert_eq!(rest, Vec::from_array(&s.env, [polls[2]]));
+        assert_eq!(cursor, None);
+        let (all, _) = s.client.get_asset_polls_page(&s.asset_id, &None, &0);
+        assert_eq!(all.last(), Some(newer));
+    }
+
+    #[test]
+    fn test_voters_for_option_paginated() {
+        let voter_count = VOTERS_PAGE_SIZE + 2;
+        // One holder abstains so the poll stays open
+        let s = receipt_setup(voter_count + 2);
+        let poll_id = create_audited_poll(&s);
+
+        for i in 0..voter_count {
+            s.client.vote(&s.holders.get(i).unwrap(), &poll_id, &1);
+        }
+        s.client
+            .vote(&s.holders.get(voter_count).unwrap(), &poll_id, &0);
+
+        let (first_page, cursor) = s.client.get_voters_for_option(&poll_id, &1, &None, &0);
+        let (second_page, end) = s.client.get_voters_for_option(&poll_id, &1, &cursor, &0);
+        assert_eq!(first_page.len(), VOTERS_PAGE_SIZE);
+        assert_eq!(second_page.len(), 2);
+        assert_eq!(first_page.get(0), s.holders.get(0));
+        assert_eq!(second_page.get(1), s.holders.get(voter_count - 1));
+        assert_eq!(end, None);
+        assert_eq!(s.client.get_option_voter_count(&poll_id, &1), voter_count);
+
+        // Smaller pages cross the storage page boundary
+        let (_, cursor) = s.client.get_voters_for_option(&poll_id, &1, &None, &48);
+        let (across, _) = s.client.get_voters_for_option(&poll_id, &1, &cursor, &3);
+        assert_eq!(across.get(0), s.holders.get(48));
+        assert_eq!(across.get(2), s.holders.get(50));
+
+        let (deny, _) = s.client.get_voters_for_option(&poll_id, &0, &None, &0);
+        assert_eq!(deny.len(), 1);
+        assert_eq!(deny.get(0), s.holders.get(voter_count));
+
+        assert_eq!(
+            s.client.try_get_voters_for_option(&poll_id, &2, &None, &0),
+            Err(Ok(GovernanceError::InvalidOption))
+        );
+    }
+
+    #[test]
+    fn test_voter_receipts_listed_in_voting_order() {
+        let s = receipt_setup(3);
+        let voter = s.holders.get(1).unwrap();
+        let polls = [
+            create_audited_poll(&s),
+            create_audited_poll(&s),
+            create_audited_poll(&s),
+        ];
+
+        for (i, poll_id) in polls.iter().rev().enumerate() {
+            s.env.ledger().set_timestamp(100 * (i as u64 + 1));
+            s.client.vote(&voter, poll_id, &(i as u32 % 2));
+        }
+
+        let (first, cursor) = s.client.get_voter_receipts_page(&voter, &None, &2);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.get(0).unwrap().poll_id, polls[2]);
+        assert_eq!(first.get(1).unwrap().option_index, 1);
+        assert_eq!(first.get(1).unwrap().timestamp, 200);
+
+        let (rest, end) = s.client.get_voter_receipts_page(&voter, &cursor, &2);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest.get(0).unwrap().poll_id, polls[0]);
+        assert_eq!(end, None);
+
+        let outsider = s.holders.get(2).unwrap();
+        assert_eq!(
+            s.client
+                .get_voter_receipts_page(&outsider, &None, &0)
+                .0
+                .len(),
+            0
+        );
+    }
+
+    // Poll record versions
+
+    struct VersionSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        asset_id: u64,
+        holder: Address,
+    }
+
+    fn version_setup() -> VersionSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            ..
+        } = setup();
+
+        let holder = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&holder, &100);
+        // A second holder keeps a single vote from settling the poll at once
+        fractcore_client.transfer(&holder, &Address::generate(&env), &asset_id, &40);
+
+        VersionSetup {
+            env,
+            client,
+            asset_id,
+            holder,
+        }
+    }
+
+    fn legacy_poll(s: &VersionSetup, poll_id: u32, action: PollAction) -> PollV1 {
+        let env = &s.env;
+        PollV1 {
+            id: poll_id,
+            asset_id: s.asset_id,
+            creator: s.holder.clone(),
+            title: String::from_str(env, "Legacy poll"),
+            description: String::from_str(env, "Stored before weighted voting"),
+            options: Vec::from_array(
+                env,
+                [
+                    String::from_str(env, "Deny"),
+                    String::from_str(env, "Approve"),
+                ],
+            ),
+            action,
+            start_time: env.ledger().timestamp(),
+            end_time: env.ledger().timestamp() + 7 * 24 * 60 * 60,
+            is_active: true,
+            votes: Map::new(env),
+            total_voters: 0,
+        }
+    }
+
+    #[test]
+    fn test_version_one_poll_decodes_and_is_rewritten_in_the_current_shape() {
+        let s = version_setup();
+        let action = PollAction::DistributeFunds(500, String::from_str(&s.env, "Q1 revenue"));
+        let stored = legacy_poll(&s, 1, action.clone());
+        s.env.as_contract(&s.client.address, || {
+            storage::set(&s.env, &DataKey::Poll(1), &stored);
+            storage::set(&s.env, &DataKey::PollCounter, &2u32);
+        });
+
+        let poll = s.client.get_poll(&1);
+        assert_eq!(poll.title, stored.title);
+        assert_eq!(poll.action, action);
+        assert!(poll.weights.is_empty());
+
+        // Voting writes the poll back with `weights`, still without a version
+        s.client.vote(&s.holder, &1, &1);
+        let poll = s.client.get_poll(&1);
+        assert_eq!(poll.total_voters, 1);
+        assert!(poll.weights.is_empty());
+        s.env.as_contract(&s.client.address, || {
+            assert!(!storage::has(&s.env, &DataKey::PollVersion(1)));
+        });
+    }
+
+    #[test]
+    fn test_untagged_current_shape_poll_still_decodes() {
+        let s = version_setup();
+        let poll_id = s.client.create_poll(
+            &s.holder,
+            &s.asset_id,
+            &String::from_str(&s.env, "Opinion"),
+            &String::from_str(&s.env, "No action"),
+            &PollAction::NoExecution,
+            &None,
+        );
+        let created = s.client.get_poll(&poll_id);
+
+        // Polls created between weighted voting and versioning carry no version
+        s.env.as_contract(&s.client.address, || {
+            assert_eq!(
+                storage::get::<_, u32>(&s.env, &DataKey::PollVersion(poll_id)),
+                Some(POLL_RECORD_VERSION)
+            );
+            storage::remove(&s.env, &DataKey::PollVersion(poll_id));
+        });
+        assert_eq!(s.client.get_poll(&poll_id), created);
+    }
+
+    #[test]
+    fn test_reserved_actions_decode_but_are_never_created_or_executed() {
+        let s = version_setup();
+        let reserved = PollAction::Reserved(7, Bytes::from_array(&s.env, &[1, 2, 3]));
+        assert_eq!(
+            s.client.try_create_poll(
+                &s.holder,
+                &s.asset_id,
+                &String::from_str(&s.env, "Future action"),
+                &String::from_str(&s.env, "Unknown here"),
+                &reserved,
+                &None,
+            ),
+            Err(Ok(GovernanceError::UnsupportedAction))
+        );
+
+        // A poll a later version wrote, read back after a rollback
+        let mut stored = legacy_poll(&s, 1, reserved.clone()).upgrade(&s.env);
+        stored.title = String::from_str(&s.env, "Written by a later version");
+        s.env.as_contract(&s.client.address, || {
+            storage::set_poll(&s.env, 1, &stored);
+            storage::set_poll_version(&s.env, 1);
+            storage::set(&s.env, &DataKey::PollCounter, &2u32);
+        });
+        assert_eq!(s.client.get_poll(&1).action, reserved);
+
+        s.client.vote(&s.holder, &1, &1);
+        s.env
+            .ledger()
+            .with_mut(|li| li.timestamp += 8 * 24 * 60 * 60);
+        assert_eq!(
+            s.client.try_check_and_execute_poll(&1),
+            Err(Ok(GovernanceError::UnsupportedAction))
+        );
+        assert!(s.client.get_poll(&1).is_active);
+    }
+
+    // Outcome simulation
+
+    struct SimulationSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        alice: Address, // 100 fractions
+        bob: Address,   // 200
+        carol: Address, // 300
+        dave: Address,  // 400
+        poll_id: u32,
+    }
+
+    // 60% threshold, 30% quorum; Alice has already voted Deny
+    fn simulation_setup() -> SimulationSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            ..
+        } = setup();
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let dave = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&dave, &1000);
+        fractcore_client.transfer(&dave, &alice, &asset_id, &100);
+        fractcore_client.transfer(&dave, &bob, &asset_id, &200);
+        fractcore_client.transfer(&dave, &carol, &asset_id, &300);
+
+        let poll_id = client.create_poll(
+            &alice,
+            &asset_id,
+            &String::from_str(&env, "Roof repair"),
+            &String::from_str(&env, "How far from passing"),
+            &PollAction::NoExecution,
+            &Some(7),
+        );
+        client.vote(&alice, &poll_id, &0);
+
+        SimulationSetup {
+            env,
+            client,
+            alice,
+            bob,
+            carol,
+            dave,
+            poll_id,
+        }
+    }
+
+    #[test]
+    fn test_simulate_reports_power_needed_without_voting() {
+        let s = simulation_setup();
+
+        let current = s.client.simulate_outcome(&s.poll_id, &vec![&s.env]);
+        assert_eq!(current.vote_counts, vec![&s.env, 100u64, 0]);
+        assert_eq!(current.total_voters, 1);
+        assert!(!current.execution.should_execute);
+        // 60% approval against 100 Deny takes 150; 30% quorum of 1000 takes 200 more
+        assert_eq!(current.approve_power_needed, Some(200));
+
+        let with_bob = s
+            .client
+            .simulate_outcome(&s.poll_id, &vec![&s.env, (s.bob.clone(), 1u32)]);
+        assert_eq!(with_bob.vote_counts, vec![&s.env, 100u64, 200]);
+        assert_eq!(with_bob.total_voters, 2);
+        assert_eq!(with_bob.winning_option, 1);
+        assert_eq!(with_bob.execution.approval_percentage, 66);
+        assert_eq!(with_bob.execution.participation_percentage, 30);
+        assert!(with_bob.execution.should_execute);
+        assert_eq!(with_bob.approve_power_needed, Some(0));
+
+        // Nothing was recorded
+        let results = s.client.get_vote_results(&s.poll_id);
+        assert_eq!(results.vote_counts, vec![&s.env, 100u64, 0]);
+        assert_eq!(results.total_voters, 1);
+        assert!(s.client.can_vote(&s.bob, &s.poll_id));
+    }
+
+    #[test]
+    fn test_simulate_deny_votes_raise_power_needed() {
+        let s = simulation_setup();
+
+        let contested = s.client.simulate_outcome(
+            &s.poll_id,
+            &vec![&s.env, (s.carol.clone(), 0u32), (s.bob.clone(), 1u32)],
+        );
+        assert_eq!(contested.vote_counts, vec![&s.env, 400u64, 200]);
+        assert_eq!(contested.winning_option, 0);
+        assert_eq!(contested.approve_power_needed, Some(400));
+
+        // Dave holds exactly the power still needed
+        let decided = s.client.simulate_outcome(
+            &s.poll_id,
+            &vec![
+                &s.env,
+                (s.carol.clone(), 0u32),
+                (s.bob.clone(), 1u32),
+                (s.dave.clone(), 1u32),
+            ],
+        );
+        assert_eq!(decided.vote_counts, vec![&s.env, 400u64, 600]);
+        assert_eq!(decided.execution.approval_percentage, 60);
+        assert!(decided.execution.should_execute);
+    }
+
+    #[test]
+    fn test_simulate_rejects_votes_vote_would_reject() {
+        let s = simulation_setup();
+        let outsider = Address::generate(&s.env);
+
+        assert_eq!(
+            s.client
+                .try_simulate_outcome(&s.poll_id, &vec![&s.env, (s.alice.clone(), 1u32)]),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+        assert_eq!(
+            s.client.try_simulate_outcome(
+                &s.poll_id,
+                &vec![&s.env, (s.bob.clone(), 1u32), (s.bob.clone(), 0u32)]
+            ),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+        assert_eq!(
+            s.client
+                .try_simulate_outcome(&s.poll_id, &vec![&s.env, (s.bob.clone(), 2u32)]),
+            Err(Ok(GovernanceError::InvalidOption))
+        );
+        assert_eq!(
+            s.client
+                .try_simulate_outcome(&s.poll_id, &vec![&s.env, (outsider, 1u32)]),
+            Err(Ok(GovernanceError::InsufficientVotingPower))
+        );
+        assert_eq!(
+            s.client.try_simulate_outcome(&99, &vec![&s.env]),
+            Err(Ok(GovernanceError::PollNotFound))
+        );
+
+        s.env
+            .ledger()
+            .with_mut(|li| li.timestamp += 8 * 24 * 60 * 60);
+        assert_eq!(
+            s.client.try_simulate_outcome(&s.poll_id, &vec![&s.env]),
+            Err(Ok(GovernanceError::PollExpired))
+        );
+    }
+
+    // Poll templates
+
+    struct TemplateSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        admin: Address,
+        asset_id: u64,
+        holder: Address,
+        other_holder: Address,
+    }
+
+    fn template_setup() -> TemplateSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client,
+            admin,
+        } = setup();
+
+        let holder = Address::generate(&env);
+        let other_holder = Address::generate(&env);
+        let asset_id = fractcore_client.mint(&holder, &100);
+        fractcore_client.transfer(&holder, &other_holder, &asset_id, &40);
+
+        TemplateSetup {
+            env,
+            client,
+            admin,
+            asset_id,
+            holder,
+            other_holder,
+        }
+    }
+
+    fn revenue_template(s: &TemplateSetup, title_pattern: &str) -> u32 {
+        s.client.create_poll_template(
+            &s.holder,
+            &s.asset_id,
+            &String::from_str(&s.env, title_pattern),
+            &String::from_str(&s.env, "Pay out last month's rent"),
+            &PollAction::DistributeFunds(1000, String::from_str(&s.env, "Monthly revenue")),
+            &Some(3),
+        )
+    }
+
+    #[test]
+    fn test_poll_from_template_numbers_title_and_overrides_amount() {
+        let s = template_setup();
+        let template_id = revenue_template(&s, "Distribute revenue #{n} of the year");
+        assert_eq!(
+            s.client.get_asset_templates(&s.asset_id),
+            soroban_sdk::vec![&s.env, template_id]
+        );
+
+        // Any holder can instantiate it
+        let first = s
+            .client
+            .create_poll_from_template(&s.other_holder, &template_id, &None);
+        let poll = s.client.get_poll(&first);
+        assert_eq!(
+            poll.title,
+            String::from_str(&s.env, "Distribute revenue #1 of the year")
+        );
+        assert_eq!(
+            poll.description,
+            String::from_str(&s.env, "Pay out last month's rent")
+        );
+        assert_eq!(
+            poll.action,
+            PollAction::DistributeFunds(1000, String::from_str(&s.env, "Monthly revenue"))
+        );
+        assert_eq!(poll.end_time - poll.start_time, 3 * 24 * 60 * 60);
+        assert_eq!(poll.creator, s.other_holder);
+
+        let second = s
+            .client
+            .create_poll_from_template(&s.holder, &template_id, &Some(2500));
+        let poll = s.client.get_poll(&second);
+        assert_eq!(
+            poll.title,
+            String::from_str(&s.env, "Distribute revenue #2 of the year")
+        );
+        assert_eq!(
+            poll.action,
+            PollAction::DistributeFunds(2500, String::from_str(&s.env, "Monthly revenue"))
+        );
+        assert_eq!(s.client.get_poll_template(&template_id).unwrap().uses, 2);
+
+        // Outsiders still need voting power, as with create_poll
+        assert_eq!(
+            s.client
+                .try_create_poll_from_template(&Address::generate(&s.env), &template_id, &None)
+                .err(),
+            Some(Ok(GovernanceError::InsufficientVotingPower))
+        );
+        assert_eq!(
+            s.client
+                .try_create_poll_from_template(&s.holder, &template_id, &Some(0))
+                .err(),
+            Some(Ok(GovernanceError::InvalidParameters))
+        );
+    }
+
+    #[test]
+    fn test_template_validation_and_removal() {
+        let s = template_setup();
+
+        assert_eq!(
+            s.client
+                .try_create_poll_template(
+                    &Address::generate(&s.env),
+                    &s.asset_id,
+                    &String::from_str(&s.env, "Opinion"),
+                    &String::from_str(&s.env, ""),
+                    &PollAction::NoExecution,
+                    &None,
+                )
+                .err(),
+            Some(Ok(GovernanceError::InsufficientVotingPower))
+        );
+
+        // No placeholder: the title is used as is; the default duration applies
+        let opinion = s.client.create_poll_template(
+            &s.admin,
+            &s.asset_id,
+            &String::from_str(&s.env, "Opinion"),
+            &String::from_str(&s.env, ""),
+            &PollAction::NoExecution,
+            &None,
+        );
+        assert_eq!(
+            s.client.get_poll_template(&opinion).unwrap().duration_days,
+            7
+        );
+        let poll_id = s
+            .client
+            .create_poll_from_template(&s.holder, &opinion, &None);
+        assert_eq!(
+            s.client.get_poll(&poll_id).title,
+            String::from_str(&s.env, "Opinion")
+        );
+
+        // Overrides only apply to distributions
+        assert_eq!(
+            s.client
+                .try_create_poll_from_template(&s.holder, &opinion, &Some(10))
+                .err(),
+            Some(Ok(GovernanceError::InvalidParameters))
+        );
+
+        for _ in 1..MAX_TEMPLATES_PER_ASSET {
+            revenue_template(&s, "Revenue {n}");
+        }
+        assert_eq!(
+            s.client
+                .try_create_poll_template(
+                    &s.holder,
+                    &s.asset_id,
+                    &String::from_str(&s.env, "One too many"),
+                    &String::from_str(&s.env, ""),
+                    &PollAction::NoExecution,
+                    &None,
+                )
+                .err(),
+            Some(Ok(GovernanceError::InvalidParameters))
+        );
+
+        // Only the creator or the admin can remove a template
+        let revenue = s.client.get_asset_templates(&s.asset_id).get(1).unwrap();
+        assert_eq!(
+            s.client
+                .try_remove_poll_template(&s.other_holder, &revenue)
+                .err(),
+            Some(Ok(GovernanceError::Unauthorized))
+        );
+        s.client.remove_poll_template(&s.holder, &revenue);
+        s.client.remove_poll_template(&s.admin, &opinion);
+        assert_eq!(
+            s.client.get_asset_templates(&s.asset_id).len(),
+            MAX_TEMPLATES_PER_ASSET - 2
+        );
+        assert!(s.client.get_poll_template(&revenue).is_none());
+        assert_eq!(
+            s.client
+                .try_create_poll_from_template(&s.holder, &revenue, &None)
+                .err(),
+            Some(Ok(GovernanceError::TemplateNotFound))
+        );
+        // Polls made from a removed template stay as they were
+        assert!(s.client.get_poll(&poll_id).is_active);
+    }
+
+    // Transfer locks
+
+    struct LockSetup {
+        env: Env,
+        client: GovernanceContractClient<'static>,
+        fractcore: fractcore::Client<'static>,
+        governance: Address,
+        admin: Address, // Also the fractcore asset manager
+        asset_id: u64,
+        holder1: Address,
+        holder2: Address,
+    }
+
+    fn lock_setup() -> LockSetup {
+        let Setup {
+            env,
+            client,
+            fractcore_client: fractcore,
+            admin,
+        } = setup();
+        let governance = client.address.clone();
+
+        let holder1 = Address::generate(&env);
+        let holder2 = Address::generate(&env);
+        let asset_id = fractcore.mint(&holder1, &100);
+        fractcore.transfer(&holder1, &holder2, &asset_id, &40);
+
+        LockSetup {
+            env,
+            client,
+            fractcore,
+            governance,
+            admin,
+            asset_id,
+            holder1,
+            holder2,
+        }
+    }
+
+    fn create_locked(s: &LockSetup, scope: TransferLockScope) -> Result<u32, GovernanceError> {
+        match s.client.try_create_locked_poll(
+            &s.holder1,
+            &s.asset_id,
+            &String::from_str(&s.env, "Sell the building"),
+            &String::from_str(&s.env, "No vote buying while this is open"),
+            &PollAction::NoExecution,
+            &Some(1),
+            &scope,
+        ) {
+            Ok(Ok(poll_id)) => Ok(poll_id),
+            Err(Ok(err)) => Err(err),
+            _ => panic!("unexpected create_locked_poll result"),
+        }
+    }
+
+    #[test]
+    fn test_locked_poll_requires_asset_permission() {
+        let s = lock_setup();
+        assert_eq!(
+            create_locked(&s, TransferLockScope::Voters),
+            Err(GovernanceError::TransferLockNotPermitted)
+        );
+    }
+
+    #[test]
+    fn test_voters_locked_until_poll_finalizes() {
+        let s = lock_setup();
+        s.fractcore
+            .set_transfer_lock_authority(&s.admin, &s.asset_id, &Some(s.governance.clone()));
+
+        let poll_id = create_locked(&s, TransferLockScope::Voters).unwrap();
+        assert_eq!(
+            s.client.get_poll_transfer_lock(&poll_id),
+            Some(TransferLockScope::Voters)
+        );
+
+        // Only holders who voted are frozen
+        s.client.vote(&s.holder1, &poll_id, &1);
+        assert!(s.fractcore.is_transfer_locked(&s.holder1, &s.asset_id));
+        assert!(!s.fractcore.is_transfer_locked(&s.holder2, &s.asset_id));
+        assert!(s
+            .fractcore
+            .try_transfer(&s.holder1, &s.holder2, &s.asset_id, &1)
+            .is_err());
+
+        // The last vote finalizes the poll, which releases the lock
+        s.client.vote(&s.holder2, &poll_id, &1);
+        assert!(!s.client.get_poll(&poll_id).is_active);
+        assert!(!s.fractcore.is_transfer_locked(&s.holder1, &s.asset_id));
+        s.fractcore
+            .transfer(&s.holder1, &s.holder2, &s.asset_id, &1);
+    }
+
+    #[test]
+    fn test_all_holders_locked_until_end_time() {
+        let s = lock_setup();
+        s.fractcore
+            .set_transfer_lock_authority(&s.admin, &s.asset_id, &Some(s.governance.clone()));
+
+        let poll_id = create_locked(&s, TransferLockScope::AllHolders).unwrap();
+        assert!(s.fractcore.is_transfer_locked(&s.holder2, &s.asset_id));
+
+        let end_time = s.client.get_poll(&poll_id).end_time;
+        s.env.ledger().with_mut(|li| li.timestamp = end_time);
+        assert!(!s.fractcore.is_transfer_locked(&s.holder2, &s.asset_id));
+        s.fractcore
+            .transfer(&s.holder2, &s.holder1, &s.asset_id, &1);
+    }
+
+    // Weighted voting
+
+    fn create_weighted_poll(s: &Setup, asset_id: u64, weights: &Vec<VoteWeight>) -> u32 {
+        s.client.create_weighted_poll(
+            &s.admin,
+            &asset_id,
+            &String::from_str(&s.env, "Weighted poll"),
+            &String::from_str(&s.env, "Founder shares vote 10x"),
+            &PollAction::NoExecution,
+            &Some(1),
+            weights,
+        )
+    }
+
+    #[test]
+    fn test_vote_power_aggregates_share_classes() {
+        let s = setup();
+        let founder = Address::generate(&s.env);
+        let member = Address::generate(&s.env);
+
+        // Common shares: member 800, founder 100. Founder shares: founder 50
+        let common = s.fractcore_client.mint(&member, &900);
+        s.fractcore_client
+            .transfer(&member, &founder, &common, &100);
+        let founder_shares = s.fractcore_client.mint(&founder, &50);
+
+        let weights = vec![
+            &s.env,
+            VoteWeight {
+                asset_id: common,
+                multiplier: 1,
+            },
+            VoteWeight {
+                asset_id: founder_shares,
+                multiplier: 10,
+            },
+        ];
+        let poll_id = create_weighted_poll(&s, common, &weights);
+        assert_eq!(s.client.get_poll(&poll_id).weights, weights);
+
+        // A founder-only holder can vote even with no common shares
+        let founder_only = Address::generate(&s.env);
+        s.fractcore_client
+            .transfer(&founder, &founder_only, &founder_shares, &10);
+        assert!(s.client.can_vote(&founder_only, &poll_id));
+
+        s.client.vote(&founder, &poll_id, &1u32);
+        s.client.vote(&member, &poll_id, &0u32);
+
+        let poll = s.client.get_poll(&poll_id);
+        assert_eq!(
+            poll.votes.get(founder.clone()).unwrap().voting_power,
+            100 + 40 * 10
+        );
+        assert_eq!(poll.votes.get(member.clone()).unwrap().voting_power, 800);
+
+        // 500 approve vs 800 deny out of 900 + 50 * 10 weighted supply
+        let results = s.client.get_vote_results(&poll_id);
+        assert_eq!(results.vote_counts, vec![&s.env, 800u64, 500u64]);
+        let execution = s.client.check_poll_execution(&poll_id);
+        assert_eq!(execution.participation_percentage, 92);
+        assert!(!execution.should_execute);
+
+        s.env
+            .ledger()
+            .with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        assert!(!s.client.check_and_execute_poll(&poll_id));
+        assert_eq!(s.client.get_certificate(&poll_id).total_supply, 1400);
+    }
+
+    #[test]
+    fn test_invalid_weights_rejected() {
+        let s = setup();
+        let holder = Address::generate(&s.env);
+        let common = s.fractcore_client.mint(&holder, &100);
+
+        let zero_multiplier = vec![
+            &s.env,
+            VoteWeight {
+                asset_id: common,
+                multiplier: 0,
+            },
+        ];
+        let duplicate = vec![
+            &s.env,
+            VoteWeight {
+                asset_id: common,
+                multiplier: 1,
+            },
+            VoteWeight {
+                asset_id: common,
+                multiplier: 2,
+            },
+        ];
+        let missing_asset = vec![
+            &s.env,
+            VoteWeight {
+                asset_id: common,
+                multiplier: 1,
+            },
+            VoteWeight {
+                asset_id: 999,
+                multiplier: 5,
+            },
+        ];
+
+        for weights in [Vec::new(&s.env), zero_multiplier, duplicate, missing_asset] {
+            assert_eq!(
+                s.client.try_create_weighted_poll(
+                    &s.admin,
+                    &common,
+                    &String::from_str(&s.env, "Weighted poll"),
+                    &String::from_str(&s.env, "Invalid weights"),
+                    &PollAction::NoExecution,
+                    &Some(1),
+                    &weights,
+                ),
+                Err(Ok(GovernanceError::InvalidParameters))
+            );
+        }
+    }
 }
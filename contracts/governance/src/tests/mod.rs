@@ -1,7 +1,20 @@
+#![allow(clippy::module_inception)]
+// Tests seed and inspect storage directly, whatever class a key is placed in
+#![allow(clippy::disallowed_methods)]
+// With `sdk-compat` only the funding/fractcore WASM suite is built, see scripts/sdk-compat.sh
+
+#[cfg(not(feature = "sdk-compat"))]
 pub mod comprehensive_funding_tests;
 pub mod cross_contract_integration_tests;
+#[cfg(not(feature = "sdk-compat"))]
 pub mod edge_case_tests;
+#[cfg(not(feature = "sdk-compat"))]
 pub mod funding_integration_tests;
+#[cfg(not(feature = "sdk-compat"))]
+pub mod golden_event_tests;
+#[cfg(not(feature = "sdk-compat"))]
 pub mod integration_tests;
-#[cfg(test)]
+#[cfg(all(test, not(feature = "sdk-compat")))]
+pub mod setup;
+#[cfg(all(test, not(feature = "sdk-compat")))]
 pub mod unit_tests;
@@ -0,0 +1,122 @@
+//! Fixtures shared by the governance test modules
+use crate::contract::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+pub mod fractcore {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+/// Governance over a deployed fractcore with a 60% threshold, 30% quorum and
+/// 7 day polls. `admin` administers both, so it manages every asset minted.
+pub struct Setup {
+    pub env: Env,
+    pub client: GovernanceContractClient<'static>,
+    pub fractcore_client: fractcore::Client<'static>,
+    pub admin: Address,
+}
+
+pub fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let fractcore_contract_id = env.register(fractcore::WASM, (admin.clone(),));
+    let fractcore_client = fractcore::Client::new(&env, &fractcore_contract_id);
+    let client = register_governance(&env, &admin, &fractcore_contract_id);
+
+    Setup {
+        env,
+        client,
+        fractcore_client,
+        admin,
+    }
+}
+
+impl Setup {
+    /// Another governance instance over the same fractcore
+    pub fn register_governance(&self) -> GovernanceContractClient<'static> {
+        register_governance(&self.env, &self.admin, &self.fractcore_client.address)
+    }
+
+    /// One asset split evenly between `holder_count` holders, 10 fractions each
+    pub fn split_asset(&self, holder_count: u32) -> (u64, Vec<Address>) {
+        let first = Address::generate(&self.env);
+        let asset_id = self
+            .fractcore_client
+            .mint(&first, &(holder_count as u64 * 10));
+        let mut holders = Vec::new(&self.env);
+        holders.push_back(first.clone());
+        for _ in 1..holder_count {
+            let holder = Address::generate(&self.env);
+            self.fractcore_client
+                .transfer(&first, &holder, &asset_id, &10);
+            holders.push_back(holder);
+        }
+        (asset_id, holders)
+    }
+}
+
+fn register_governance(
+    env: &Env,
+    admin: &Address,
+    fractcore: &Address,
+) -> GovernanceContractClient<'static> {
+    let contract_id = env.register(
+        GovernanceContract,
+        (
+            admin.clone(),
+            fractcore.clone(),
+            Address::generate(env),
+            60u32,
+            30u32,
+            7u32,
+        ),
+    );
+    GovernanceContractClient::new(env, &contract_id)
+}
+
+/// Governance alone with a 60% threshold, the given quorum and 7 day polls.
+/// Its fractcore and funding addresses are unregistered, so calls to them use
+/// the unit test fallbacks; returns the client and the admin.
+pub fn unit_setup(env: &Env, quorum: u32) -> (GovernanceContractClient<'static>, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(
+        GovernanceContract,
+        (
+            admin.clone(),
+            Address::generate(env),
+            Address::generate(env),
+            60u32,
+            quorum,
+            7u32,
+        ),
+    );
+
+    (GovernanceContractClient::new(env, &contract_id), admin)
+}
+
+/// A `NoExecution` poll on asset 1 lasting `days`
+pub fn create_poll(
+    env: &Env,
+    client: &GovernanceContractClient,
+    creator: &Address,
+    days: u32,
+) -> u32 {
+    client.create_poll(
+        creator,
+        &1u64,
+        &String::from_str(env, "Test poll"),
+        &String::from_str(env, "Test poll description"),
+        &PollAction::NoExecution,
+        &Some(days),
+    )
+}
+
+/// A Stellar asset contract standing in for XLM
+pub fn register_xlm(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
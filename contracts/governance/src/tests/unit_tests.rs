@@ -1,7 +1,19 @@
 #[cfg(test)]
 mod tests {
-    use crate::contract::{GovernanceParams, PollAction};
-    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+    use crate::contract::*;
+    use crate::storage::{stored_key, DataKey};
+    use crate::tests::setup::{create_poll, register_xlm, unit_setup};
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Events, Ledger},
+        token, vec,
+        xdr::ToXdr,
+        Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+    };
+
+    mod registry {
+        soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/registry.wasm");
+    }
 
     #[test]
     fn test_governance_params_creation() {
@@ -27,22 +39,588 @@ mod tests {
         let addr = Address::generate(&env);
         let action3 = PollAction::TransferTokens(addr, 500);
 
-        match action1 {
-            PollAction::NoExecution => assert!(true),
-            _ => assert!(false),
-        }
+        assert!(matches!(action1, PollAction::NoExecution));
 
         match action2 {
             PollAction::DistributeFunds(amount, description) => {
                 assert_eq!(amount, 1000);
                 assert_eq!(description, String::from_str(&env, "Test distribution"));
             }
-            _ => assert!(false),
+            _ => panic!("unexpected poll action"),
         }
 
         match action3 {
             PollAction::TransferTokens(_, amount) => assert_eq!(amount, 500),
-            _ => assert!(false),
+            _ => panic!("unexpected poll action"),
+        }
+    }
+
+    // Batches and activity
+
+    #[test]
+    fn test_vote_batch_reports_per_item_results() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let first = create_poll(&env, &client, &admin, 1);
+        let second = create_poll(&env, &client, &admin, 1);
+
+        let votes = vec![
+            &env,
+            (first, 1u32),
+            (999u32, 1u32),
+            (second, 5u32),
+            (first, 0u32),
+        ];
+        let results = client.vote_batch(&voter, &votes);
+
+        assert_eq!(results.len(), 4);
+        assert!(results.get(0).unwrap().success);
+        assert_eq!(
+            results.get(1).unwrap().error_code,
+            Some(GovernanceError::PollNotFound as u32)
+        );
+        assert_eq!(
+            results.get(2).unwrap().error_code,
+            Some(GovernanceError::InvalidOption as u32)
+        );
+        assert_eq!(
+            results.get(3).unwrap().error_code,
+            Some(GovernanceError::AlreadyVoted as u32)
+        );
+
+        // Only the valid vote was recorded
+        assert_eq!(client.get_poll(&first).total_voters, 1);
+        assert_eq!(client.get_poll(&second).total_voters, 0);
+    }
+
+    #[test]
+    fn test_execute_polls_processes_each_poll() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let approved = create_poll(&env, &client, &admin, 1);
+        let rejected = create_poll(&env, &client, &admin, 1);
+        client.vote(&voter, &approved, &1u32);
+        client.vote(&voter, &rejected, &0u32);
+
+        // Before expiry nothing is executable yet
+        let results = client.execute_polls(&vec![&env, approved]);
+        assert!(results.get(0).unwrap().success);
+        assert!(!results.get(0).unwrap().executed);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+
+        let results = client.execute_polls(&vec![&env, approved, rejected, 42u32]);
+        assert_eq!(
+            results.get(0).unwrap(),
+            BatchItemResult {
+                poll_id: approved,
+                success: true,
+                executed: true,
+                error_code: None,
+            }
+        );
+        assert!(results.get(1).unwrap().success);
+        assert!(!results.get(1).unwrap().executed);
+        assert_eq!(
+            results.get(2).unwrap().error_code,
+            Some(GovernanceError::PollNotFound as u32)
+        );
+
+        assert!(!client.get_poll(&approved).is_active);
+        assert!(!client.get_poll(&rejected).is_active);
+    }
+
+    #[test]
+    fn test_batches_are_size_limited() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let mut poll_ids = Vec::new(&env);
+        let mut votes = Vec::new(&env);
+        for poll_id in 0..=MAX_BATCH_SIZE {
+            poll_ids.push_back(poll_id);
+            votes.push_back((poll_id, 1u32));
+        }
+
+        assert_eq!(
+            client.try_execute_polls(&poll_ids),
+            Err(Ok(GovernanceError::BatchTooLarge))
+        );
+        assert_eq!(
+            client.try_vote_batch(&voter, &votes),
+            Err(Ok(GovernanceError::BatchTooLarge))
+        );
+        assert_eq!(
+            client.try_get_polls_bulk(&poll_ids),
+            Err(Ok(GovernanceError::BatchTooLarge))
+        );
+        assert_eq!(
+            client.try_get_vote_results_bulk(&poll_ids),
+            Err(Ok(GovernanceError::BatchTooLarge))
+        );
+    }
+
+    #[test]
+    fn test_activity_registry_tracks_assets_and_executions() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let approved = create_poll(&env, &client, &admin, 1);
+        let rejected = create_poll(&env, &client, &admin, 1);
+        let other = client.create_poll(
+            &admin,
+            &2u64,
+            &String::from_str(&env, "Other asset"),
+            &String::from_str(&env, "Other asset poll"),
+            &PollAction::NoExecution,
+            &Some(3),
+        );
+        client.vote(&voter, &approved, &1u32);
+        client.vote(&voter, &rejected, &0u32);
+        client.vote(&voter, &other, &1u32);
+
+        assert_eq!(client.get_active_asset_count(), 2);
+        assert_eq!(client.assets_with_active_polls(&0), vec![&env, 1u64, 2u64]);
+        assert_eq!(client.governed_assets(&0), vec![&env, 1u64, 2u64]);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        client.execute_polls(&vec![&env, approved, rejected, other]);
+
+        // Asset 1 has no active poll left; asset 2's poll runs another day
+        assert_eq!(client.assets_with_active_polls(&0), vec![&env, 2u64]);
+        assert_eq!(client.governed_assets(&0), vec![&env, 1u64, 2u64]);
+
+        let summary = client.get_asset_governance_summary(&1u64);
+        assert_eq!(summary.total_polls, 2);
+        assert_eq!(summary.active_polls, 0);
+        assert_eq!(summary.executed_polls, 1);
+        assert_eq!(summary.rejected_polls, 1);
+        assert_eq!(summary.last_executed_at, env.ledger().timestamp());
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        client.execute_polls(&vec![&env, other]);
+
+        assert_eq!(client.get_active_asset_count(), 0);
+        let executed = client.recently_executed(&0);
+        assert_eq!(executed.len(), 2);
+        assert_eq!(executed.get(0).unwrap().poll_id, other);
+        assert_eq!(executed.get(1).unwrap().poll_id, approved);
+        assert_eq!(client.recently_executed(&1).len(), 0);
+
+        let summaries = client.get_asset_governance_summaries(&vec![&env, 2u64, 7u64]);
+        assert_eq!(summaries.get(0).unwrap().executed_polls, 1);
+        assert_eq!(summaries.get(1).unwrap().total_polls, 0);
+    }
+
+    #[test]
+    fn test_governance_stats_track_outcomes_and_unique_voters() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+        let other_voter = Address::generate(&env);
+
+        let passed = create_poll(&env, &client, &admin, 1);
+        let rejected = create_poll(&env, &client, &admin, 1);
+        let expired = create_poll(&env, &client, &admin, 1);
+        client.vote(&voter, &passed, &1u32);
+        client.vote(&voter, &rejected, &0u32);
+        client.vote(&other_voter, &rejected, &0u32);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        client.execute_polls(&vec![&env, passed, rejected, expired]);
+
+        // The unit test fallback gives each voter 10% of the supply
+        let stats = client.get_governance_stats(&1u64);
+        assert_eq!(stats.finalized_polls, 3);
+        assert_eq!(stats.passed_polls, 1);
+        assert_eq!(stats.rejected_polls, 1);
+        assert_eq!(stats.expired_polls, 1);
+        assert_eq!(stats.participation_total, 30);
+        assert_eq!(stats.average_participation, 10);
+        assert_eq!(stats.voter_buckets.len(), 1);
+        let bucket = stats.voter_buckets.get(0).unwrap();
+        assert_eq!(bucket.polls, 3);
+        assert_eq!(bucket.unique_voters, 2);
+
+        // A returning voter counts again in a later bucket
+        env.ledger()
+            .with_mut(|li| li.timestamp += STATS_BUCKET_SECONDS);
+        let later = create_poll(&env, &client, &admin, 1);
+        client.vote(&voter, &later, &1u32);
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        client.execute_polls(&vec![&env, later]);
+
+        let stats = client.get_governance_stats(&1u64);
+        assert_eq!(stats.passed_polls, 2);
+        assert_eq!(stats.voter_buckets.len(), 2);
+        let bucket = stats.voter_buckets.get(1).unwrap();
+        assert_eq!(bucket.polls, 1);
+        assert_eq!(bucket.unique_voters, 1);
+        assert!(bucket.start > stats.voter_buckets.get(0).unwrap().start);
+
+        assert_eq!(client.get_governance_stats(&2u64).finalized_polls, 0);
+    }
+
+    // Poll certificates
+
+    #[test]
+    fn test_certificate_issued_on_execution() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let poll_id = create_poll(&env, &client, &admin, 1);
+        client.vote(&voter, &poll_id, &1u32);
+
+        // No certificate while the poll is open
+        assert_eq!(
+            client.try_get_certificate(&poll_id),
+            Err(Ok(GovernanceError::CertificateNotFound))
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        assert!(client.check_and_execute_poll(&poll_id));
+
+        // The certificate is published as a single event
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (String::from_str(&env, "poll_certified"), poll_id).into_val(&env)
+        );
+        let published: PollCertificate = data.into_val(&env);
+
+        let certificate = client.get_certificate(&poll_id);
+        let expected_hash: BytesN<32> = env
+            .crypto()
+            .sha256(&PollAction::NoExecution.to_xdr(&env))
+            .into();
+
+        assert_eq!(certificate.poll_id, poll_id);
+        assert_eq!(certificate.asset_id, 1);
+        assert_eq!(certificate.snapshot_ledger, env.ledger().sequence());
+        assert_eq!(certificate.total_supply, 10000);
+        assert_eq!(certificate.vote_counts, vec![&env, 0u64, 1000u64]);
+        assert_eq!(certificate.total_voters, 1);
+        assert_eq!(certificate.winning_option, 1);
+        assert_eq!(certificate.approval_percentage, 100);
+        assert_eq!(certificate.participation_percentage, 10);
+        assert!(certificate.executed);
+        assert_eq!(certificate.action_hash, Some(expected_hash));
+        assert_eq!(certificate.finalized_at, env.ledger().timestamp());
+        assert_eq!(published, certificate);
+    }
+
+    #[test]
+    fn test_rejected_poll_certificate_has_no_action_hash() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 5);
+        let voter = Address::generate(&env);
+
+        let poll_id = create_poll(&env, &client, &admin, 1);
+        client.vote(&voter, &poll_id, &0u32);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        assert!(!client.check_and_execute_poll(&poll_id));
+
+        let certificate = client.get_certificate(&poll_id);
+        assert!(!certificate.executed);
+        assert_eq!(certificate.action_hash, None);
+        assert_eq!(certificate.winning_option, 0);
+
+        // Later calls do not rewrite the certificate
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        assert!(!client.check_and_execute_poll(&poll_id));
+        assert_eq!(client.get_certificate(&poll_id), certificate);
+    }
+
+    // Keeper bounties
+
+    #[test]
+    fn test_finalize_expired_polls_pays_bounty() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+        let keeper = Address::generate(&env);
+        let creator = Address::generate(&env);
+
+        client.set_keeper_config(&admin, &xlm, &10i128);
+        token::StellarAssetClient::new(&env, &xlm).mint(&admin, &100i128);
+        client.fund_keeper_pool(&admin, &100i128);
+
+        let first = create_poll(&env, &client, &creator, 1);
+        let second = create_poll(&env, &client, &creator, 1);
+        let own = create_poll(&env, &client, &keeper, 1);
+
+        // Nothing has expired yet, so no work and no payout
+        assert_eq!(client.finalize_expired_polls(&keeper, &10u32), 0);
+        assert_eq!(client.get_keeper_pool(), 100);
+
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+
+        assert_eq!(client.finalize_expired_polls(&keeper, &10u32), 3);
+        assert!(!client.get_poll(&first).is_active);
+        assert!(!client.get_poll(&second).is_active);
+        assert!(!client.get_poll(&own).is_active);
+        assert_eq!(client.get_active_polls().len(), 0);
+
+        // The keeper's own poll is finalized but not rewarded
+        assert_eq!(token::Client::new(&env, &xlm).balance(&keeper), 20);
+        assert_eq!(client.get_keeper_pool(), 80);
+    }
+
+    #[test]
+    fn test_finalize_expired_polls_respects_limit() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 40);
+        let keeper = Address::generate(&env);
+        let creator = Address::generate(&env);
+
+        create_poll(&env, &client, &creator, 1);
+        create_poll(&env, &client, &creator, 1);
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+
+        // No keeper config: polls still finalize, just without a bounty
+        assert_eq!(client.finalize_expired_polls(&keeper, &1u32), 1);
+        assert_eq!(client.get_active_polls().len(), 1);
+    }
+
+    #[test]
+    fn test_active_polls_are_paged_and_legacy_list_is_moved() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 40);
+        env.cost_estimate().budget().reset_unlimited();
+        let keeper = Address::generate(&env);
+        let creator = Address::generate(&env);
+
+        // Active polls of a deployment from before paging, with no poll records
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(
+                &stored_key(&env, &DataKey::ActivePolls),
+                &vec![&env, 900u32, 901u32],
+            );
+        });
+
+        for _ in 0..ACTIVE_POLLS_PAGE_SIZE {
+            create_poll(&env, &client, &creator, 1);
+        }
+        assert_eq!(client.get_active_poll_count(), ACTIVE_POLLS_PAGE_SIZE + 2);
+        assert_eq!(
+            client.get_active_polls_page(&0).len(),
+            ACTIVE_POLLS_PAGE_SIZE
+        );
+        assert_eq!(client.get_active_polls_page(&1).len(), 2);
+        assert_eq!(client.get_active_polls().len(), ACTIVE_POLLS_PAGE_SIZE + 2);
+        env.as_contract(&client.address, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&stored_key(&env, &DataKey::ActivePolls)));
+        });
+
+        // Finalizing swaps the last ids into the freed slots
+        env.ledger().with_mut(|li| li.timestamp += 2 * 24 * 60 * 60);
+        assert_eq!(
+            client.finalize_expired_polls(&keeper, &(ACTIVE_POLLS_PAGE_SIZE + 2)),
+            ACTIVE_POLLS_PAGE_SIZE
+        );
+        assert_eq!(client.get_active_poll_count(), 2);
+        assert_eq!(client.get_active_polls_page(&0), vec![&env, 900u32, 901u32]);
+        assert_eq!(client.get_active_polls_page(&1).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_set_keeper_config_unauthorized() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+
+        client.set_keeper_config(&Address::generate(&env), &xlm, &10i128);
+    }
+
+    #[test]
+    fn test_admin_configuration_is_logged() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+        let creator = Address::generate(&env);
+
+        client.set_keeper_config(&admin, &xlm, &10i128);
+        create_poll(&env, &client, &creator, 1);
+        client.update_governance_params(&admin, &70u32, &40u32, &7u32);
+
+        // Poll creation is not an admin action
+        let head = client.get_admin_log_head();
+        assert_eq!(head.entries, 2);
+        assert_eq!(head.folded, 0);
+
+        let log = client.get_admin_log(&0);
+        let keeper = log.get(0).unwrap();
+        assert_eq!(keeper.actor, admin);
+        assert_eq!(keeper.function, Symbol::new(&env, "set_keeper_config"));
+        let args_hash: BytesN<32> = env.crypto().sha256(&(xlm, 10i128).to_xdr(&env)).into();
+        assert_eq!(keeper.args_hash, args_hash);
+        assert_eq!(
+            log.get(1).unwrap().function,
+            Symbol::new(&env, "set_governance_params")
+        );
+    }
+
+    #[test]
+    fn test_poll_expiry_listed_in_registry_schedule() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+        let keeper = Address::generate(&env);
+        let creator = Address::generate(&env);
+
+        let registry_id = env.register(registry::WASM, (admin.clone(), xlm, 0i128));
+        let registry = registry::Client::new(&env, &registry_id);
+        registry.set_scheduler(&admin, &client.address, &true);
+        client.set_schedule_registry(&admin, &Some(registry_id.clone()));
+        assert_eq!(client.get_schedule_registry(), Some(registry_id));
+
+        let poll_id = create_poll(&env, &client, &creator, 1);
+        let end_time = client.get_poll(&poll_id).end_time;
+        assert_eq!(
+            registry.get_scheduled(
+                &client.address,
+                &symbol_short!("poll_end"),
+                &(poll_id as u64)
+            ),
+            Some(end_time)
+        );
+        assert_eq!(registry.due_actions(&(end_time - 1), &0).len(), 0);
+
+        let due = registry.due_actions(&end_time, &0);
+        assert_eq!(due.len(), 1);
+        let action = due.get(0).unwrap();
+        assert_eq!(action.contract, client.address);
+        assert_eq!(action.key, poll_id as u64);
+
+        // Finalizing takes the poll off the schedule
+        env.ledger().with_mut(|li| li.timestamp = end_time);
+        assert_eq!(client.finalize_expired_polls(&keeper, &10u32), 1);
+        assert_eq!(registry.due_actions(&end_time, &0).len(), 0);
+
+        // A registry that has not approved governance does not block poll creation
+        registry.set_scheduler(&admin, &client.address, &false);
+        let unlisted = create_poll(&env, &client, &creator, 1);
+        assert!(client.get_poll(&unlisted).is_active);
+        assert_eq!(
+            registry.get_scheduled(
+                &client.address,
+                &symbol_short!("poll_end"),
+                &(unlisted as u64)
+            ),
+            None
+        );
+    }
+
+    // Relayed votes
+
+    #[test]
+    fn test_relay_vote_reimburses_relayer() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+        let relayer = Address::generate(&env);
+        let voter = Address::generate(&env);
+        let poll_id = create_poll(&env, &client, &Address::generate(&env), 7);
+
+        client.set_relay_config(&admin, &xlm, &3i128);
+        token::StellarAssetClient::new(&env, &xlm).mint(&admin, &5i128);
+        client.fund_relay_pool(&admin, &5i128);
+
+        client.relay_vote(&relayer, &voter, &poll_id, &0u32);
+        // Both the relayer and the voter sign the call
+        let auths = env.auths();
+        assert_eq!(auths.len(), 2);
+        assert!(auths.iter().any(|(signer, _)| *signer == relayer));
+        assert!(auths.iter().any(|(signer, _)| *signer == voter));
+        assert_eq!(client.get_vote_receipt(&poll_id, &voter).option_index, 0);
+        assert_eq!(token::Client::new(&env, &xlm).balance(&relayer), 3);
+        assert_eq!(client.get_relay_pool(), 2);
+
+        // The same signed vote cannot be replayed for another fee
+        assert_eq!(
+            client.try_relay_vote(&relayer, &voter, &poll_id, &0u32),
+            Err(Ok(GovernanceError::AlreadyVoted))
+        );
+
+        // Payout is capped by what is left in the pool
+        client.relay_vote(&relayer, &Address::generate(&env), &poll_id, &1u32);
+        assert_eq!(token::Client::new(&env, &xlm).balance(&relayer), 5);
+        assert_eq!(client.get_relay_pool(), 0);
+    }
+
+    #[test]
+    fn test_relayer_payouts_are_capped_per_day() {
+        let env = Env::default();
+        let (client, admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+        let relayer = Address::generate(&env);
+        let creator = Address::generate(&env);
+        // One poll per vote, so that no poll settles on its early votes
+        let relay = |relayer: &Address| {
+            let poll_id = create_poll(&env, &client, &creator, 7);
+            client.relay_vote(relayer, &Address::generate(&env), &poll_id, &1u32);
+        };
+
+        let pool = MAX_RELAYER_PAYOUTS_PER_DAY as i128 + 1;
+        client.set_relay_config(&admin, &xlm, &1i128);
+        token::StellarAssetClient::new(&env, &xlm).mint(&admin, &pool);
+        client.fund_relay_pool(&admin, &pool);
+
+        // Votes past the daily allowance are still cast, unpaid
+        for _ in 0..=MAX_RELAYER_PAYOUTS_PER_DAY {
+            relay(&relayer);
         }
+        let xlm_client = token::Client::new(&env, &xlm);
+        assert_eq!(
+            xlm_client.balance(&relayer),
+            MAX_RELAYER_PAYOUTS_PER_DAY as i128
+        );
+        assert_eq!(client.get_relay_pool(), 1);
+
+        // Other relayers and the next day are paid again
+        let other = Address::generate(&env);
+        relay(&other);
+        assert_eq!(xlm_client.balance(&other), 1);
+        token::StellarAssetClient::new(&env, &xlm).mint(&admin, &1i128);
+        client.fund_relay_pool(&admin, &1i128);
+        env.ledger().with_mut(|li| li.timestamp += 24 * 60 * 60);
+        relay(&relayer);
+        assert_eq!(
+            xlm_client.balance(&relayer),
+            MAX_RELAYER_PAYOUTS_PER_DAY as i128 + 1
+        );
+    }
+
+    #[test]
+    fn test_relay_vote_without_config_still_votes() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 40);
+        let voter = Address::generate(&env);
+        let poll_id = create_poll(&env, &client, &Address::generate(&env), 7);
+
+        client.relay_vote(&Address::generate(&env), &voter, &poll_id, &1u32);
+        assert_eq!(client.get_vote_receipt(&poll_id, &voter).option_index, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_set_relay_config_unauthorized() {
+        let env = Env::default();
+        let (client, _admin) = unit_setup(&env, 40);
+        let xlm = register_xlm(&env);
+
+        client.set_relay_config(&Address::generate(&env), &xlm, &3i128);
     }
 }
@@ -0,0 +1,334 @@
+use crate::errors::MarketError;
+use crate::methods::{
+    basket, buyer_offers, custody, execution, initialization, market, offerings, primary, queries,
+    subscriptions, utils,
+};
+use crate::storage::{
+    BasketValuation, BestExecutionReport, BuyerOffer, Cursor, ExecutionPolicy,
+    MarketExecutionReport, Offering, OfferingPhase, PrimaryAllocation, SellerCommitments,
+    Subscription,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+
+#[contract]
+pub struct MarketContract;
+
+#[contractimpl]
+impl MarketContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        trading_contract: Address,
+    ) -> Result<(), MarketError> {
+        initialization::construct(env, admin, trading_contract)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Buyer finishes a trading proposal after checking for cheaper offerings of the
+    /// asset; `policy` decides whether to warn, abort or route the purchase to one
+    #[allow(clippy::too_many_arguments)]
+    pub fn finish_best_execution(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        policy: ExecutionPolicy,
+    ) -> Result<BestExecutionReport, MarketError> {
+        execution::finish_best_execution(env, buyer, seller, asset_id, token_amount, price, policy)
+    }
+
+    /// Seller lists fractions reserved for a preferred buyer before opening to everyone
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_offering(
+        env: Env,
+        seller: Address,
+        preferred_buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        reservation_seconds: u64,
+        duration_seconds: u64,
+    ) -> Result<u32, MarketError> {
+        offerings::create_offering(
+            env,
+            seller,
+            preferred_buyer,
+            asset_id,
+            token_amount,
+            price,
+            reservation_seconds,
+            duration_seconds,
+        )
+    }
+
+    /// Buyer fills an offering; returns the trade ID
+    pub fn fill_offering(
+        env: Env,
+        buyer: Address,
+        offering_id: u32,
+        token_amount: u64,
+        price: u128,
+    ) -> Result<u32, MarketError> {
+        offerings::fill_offering(env, buyer, offering_id, token_amount, price)
+    }
+
+    /// Buyer fills an offering and deposits into a basket holding that asset in
+    /// one authorized call; returns the trade ID
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_and_stake(
+        env: Env,
+        buyer: Address,
+        offering_id: u32,
+        token_amount: u64,
+        price: u128,
+        basket_id: u64,
+        basket_units: u64,
+    ) -> Result<u32, MarketError> {
+        offerings::buy_and_stake(
+            env,
+            buyer,
+            offering_id,
+            token_amount,
+            price,
+            basket_id,
+            basket_units,
+        )
+    }
+
+    /// Emit the opening event once the reservation window has passed
+    pub fn open_offering(env: Env, offering_id: u32) -> Result<(), MarketError> {
+        offerings::open_offering(env, offering_id)
+    }
+
+    pub fn withdraw_offering(
+        env: Env,
+        seller: Address,
+        offering_id: u32,
+    ) -> Result<(), MarketError> {
+        offerings::withdraw_offering(env, seller, offering_id)
+    }
+
+    /// Buyer bids for fractions of an asset, escrowing the XLM price until
+    /// a holder accepts, the buyer cancels or the offer expires
+    pub fn make_offer(
+        env: Env,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        duration_seconds: u64,
+    ) -> Result<u32, MarketError> {
+        buyer_offers::make_offer(env, buyer, asset_id, token_amount, price, duration_seconds)
+    }
+
+    /// Holder sells into an open buyer offer; returns the trade ID
+    pub fn accept_offer(
+        env: Env,
+        seller: Address,
+        offer_id: u32,
+        expected_token_amount: u64,
+        expected_price: u128,
+    ) -> Result<u32, MarketError> {
+        buyer_offers::accept_offer(env, seller, offer_id, expected_token_amount, expected_price)
+    }
+
+    /// Buy from the cheapest offerings per fraction until `max_total` XLM is spent
+    /// or no offering fits what is left; returns the fills
+    pub fn market_buy(
+        env: Env,
+        buyer: Address,
+        asset_id: u64,
+        max_total: u128,
+    ) -> Result<MarketExecutionReport, MarketError> {
+        market::market_buy(env, buyer, asset_id, max_total)
+    }
+
+    /// Sell up to `amount` fractions into the best buyer offers per fraction, failing
+    /// unless they pay at least `min_total` XLM; returns the fills
+    pub fn market_sell(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+        amount: u64,
+        min_total: u128,
+    ) -> Result<MarketExecutionReport, MarketError> {
+        market::market_sell(env, seller, asset_id, amount, min_total)
+    }
+
+    /// Buyer withdraws an open offer and gets the escrowed XLM back
+    pub fn cancel_offer(env: Env, buyer: Address, offer_id: u32) -> Result<(), MarketError> {
+        buyer_offers::cancel_offer(env, buyer, offer_id)
+    }
+
+    /// Permissionless sweep returning escrowed XLM of expired offers to their buyers
+    pub fn refund_expired_offers(env: Env, limit: u32) -> Result<u32, MarketError> {
+        buyer_offers::refund_expired_offers(env, limit)
+    }
+
+    /// Buyer deposits `budget * max_periods` XLM to buy from open offerings every interval
+    pub fn create_subscription(
+        env: Env,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: Option<u64>,
+        budget: u128,
+        interval: u64,
+        max_periods: u32,
+    ) -> Result<u32, MarketError> {
+        subscriptions::create_subscription(
+            env,
+            buyer,
+            asset_id,
+            token_amount,
+            budget,
+            interval,
+            max_periods,
+        )
+    }
+
+    /// Buy (or skip) a subscription's due period (keepers earn the trading bounty)
+    pub fn run_subscription(
+        env: Env,
+        keeper: Address,
+        subscription_id: u32,
+    ) -> Result<i128, MarketError> {
+        subscriptions::run_subscription(env, keeper, subscription_id)
+    }
+
+    /// Buyer stops a subscription and gets the unspent deposit back
+    pub fn cancel_subscription(
+        env: Env,
+        buyer: Address,
+        subscription_id: u32,
+    ) -> Result<(), MarketError> {
+        subscriptions::cancel_subscription(env, buyer, subscription_id)
+    }
+
+    /// Mint a new asset split across paying investors and pay the issuer, all or
+    /// nothing; the fractcore admin co-signs the mint. Returns the new asset ID
+    pub fn primary_sale(
+        env: Env,
+        issuer: Address,
+        allocations: Vec<PrimaryAllocation>,
+        uri: Option<String>,
+    ) -> Result<u64, MarketError> {
+        primary::primary_sale(env, issuer, allocations, uri)
+    }
+
+    /// Up to `limit` live offering ids (capped at MAX_PAGE_SIZE) from `cursor`, with the
+    /// cursor of the next page
+    pub fn get_asset_offerings_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<u32>, Option<Cursor>) {
+        queries::get_asset_offerings_page(env, asset_id, cursor, limit)
+    }
+
+    /// Unexpired offerings across all assets, optionally only those of a
+    /// fractcore category, paged like `get_asset_offerings_page`; a page may be
+    /// short while its cursor is set
+    pub fn discover_offerings(
+        env: Env,
+        category: Option<Symbol>,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> Result<(Vec<u32>, Option<Cursor>), MarketError> {
+        queries::discover_offerings(env, category, cursor, limit)
+    }
+
+    /// Total price of `amount` fractions at a unit price with `decimals` decimal
+    /// places, rounded up
+    pub fn quote_total(
+        _env: Env,
+        price_per_token: u128,
+        amount: u64,
+        decimals: u32,
+    ) -> Result<u128, MarketError> {
+        Ok(pricing::quote_total(price_per_token, amount, decimals)?)
+    }
+
+    /// Unit price with `decimals` decimal places of `amount` fractions sold for
+    /// `total`, rounded down
+    pub fn price_per_token_from_total(
+        _env: Env,
+        total: u128,
+        amount: u64,
+        decimals: u32,
+    ) -> Result<u128, MarketError> {
+        Ok(pricing::price_per_token_from_total(
+            total, amount, decimals,
+        )?)
+    }
+
+    /// Net asset value of a fractcore basket at its components' last traded prices
+    pub fn basket_nav(env: Env, basket_id: u64) -> Result<BasketValuation, MarketError> {
+        basket::basket_nav(env, basket_id)
+    }
+
+    pub fn get_subscription(env: Env, subscription_id: u32) -> Result<Subscription, MarketError> {
+        subscriptions::get_subscription(env, subscription_id)
+    }
+
+    /// Buyer subscriptions that still hold a deposit
+    pub fn get_buyer_subscriptions(env: Env, buyer: Address) -> Vec<u32> {
+        subscriptions::get_buyer_subscriptions(env, buyer)
+    }
+
+    pub fn get_offer(env: Env, offer_id: u32) -> Result<BuyerOffer, MarketError> {
+        buyer_offers::get_offer(env, offer_id)
+    }
+
+    /// Buyer offers whose XLM is still held by the contract
+    pub fn get_open_offers(env: Env, buyer: Address) -> Vec<u32> {
+        buyer_offers::get_open_offers(env, buyer)
+    }
+
+    /// XLM held for a buyer's expired offers that a refund would return
+    pub fn get_refundable_amount(env: Env, buyer: Address) -> Result<u128, MarketError> {
+        buyer_offers::get_refundable_amount(env, buyer)
+    }
+
+    pub fn get_refund_cursor(env: Env) -> u32 {
+        buyer_offers::get_refund_cursor(env)
+    }
+
+    pub fn get_offering(env: Env, offering_id: u32) -> Result<Offering, MarketError> {
+        queries::get_offering(env, offering_id)
+    }
+
+    pub fn get_offering_phase(env: Env, offering_id: u32) -> Result<OfferingPhase, MarketError> {
+        offerings::get_offering_phase(env, offering_id)
+    }
+
+    pub fn get_seller_offerings(env: Env, seller: Address) -> Vec<u32> {
+        queries::get_seller_offerings(env, seller)
+    }
+
+    /// Offerings backed by the seller's allowance to this contract, with the
+    /// total committed and any uncommitted allowance or shortfall
+    pub fn get_seller_commitments(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<SellerCommitments, MarketError> {
+        queries::get_seller_commitments(env, seller, asset_id)
+    }
+
+    /// XLM held for open buyer offers and subscription deposits
+    pub fn get_held_xlm(env: Env) -> i128 {
+        custody::get_held_xlm(env)
+    }
+
+    pub fn get_trading_contract(env: Env) -> Result<Address, MarketError> {
+        utils::get_trading_contract(&env)
+    }
+}
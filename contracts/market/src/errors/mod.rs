@@ -0,0 +1,58 @@
+use pricing::PriceError;
+use soroban_sdk::contracterror;
+
+/// Error codes returned by market entrypoints
+///
+/// Codes shared with the trading contract keep its numbers, so a failure the
+/// trading hooks report reads the same here; codes from 100 are the market's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MarketError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidTokenAmount = 3,
+    InvalidPrice = 4,
+    SelfTrade = 5,
+    InvalidDuration = 6,
+    AssetNotFound = 7,
+    InsufficientBalance = 8,
+    SaleNotFound = 10,
+    SaleNotActive = 11,
+    UnauthorizedBuyer = 12,
+    UnauthorizedSeller = 13,
+    SaleExpired = 14,
+    TokenAmountMismatch = 16,
+    PriceMismatch = 17,
+    SellerInsufficientBalance = 18,
+    BuyerInsufficientFunds = 19,
+    InsufficientAllowance = 20,
+    PriceOverflow = 21,
+    Unauthorized = 23,
+    InvalidAmount = 24,
+    OfferingNotFound = 44,
+    ReservationActive = 45,
+    AddressBlocked = 46,
+    ScreeningFailed = 47,
+    AssetPaused = 48,
+    NotABasket = 49,
+    NoPriceData = 50,
+    UpstreamPaused = 51,
+    ExceedsReleasedTranche = 53,
+    BuyerNotEligible = 54,
+    TooManyOffers = 100,
+    SubscriptionNotFound = 101,
+    SubscriptionInactive = 102,
+    SubscriptionNotDue = 103,
+    TooManySubscriptions = 104,
+}
+
+impl From<PriceError> for MarketError {
+    fn from(err: PriceError) -> Self {
+        match err {
+            PriceError::InvalidTokenAmount => MarketError::InvalidTokenAmount,
+            PriceError::InvalidPrice => MarketError::InvalidPrice,
+            PriceError::PriceOverflow => MarketError::PriceOverflow,
+        }
+    }
+}
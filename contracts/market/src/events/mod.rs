@@ -0,0 +1,161 @@
+use crate::storage::{BuyerOffer, Offering, Subscription};
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// contract initialization event
+pub fn emit_init_event(env: &Env, admin: &Address, trading_contract: &Address) {
+    env.events().publish(
+        (symbol_short!("init"),),
+        (admin.clone(), trading_contract.clone()),
+    );
+}
+
+/// offering with a preferred buyer reservation created event
+pub fn emit_offering_created_event(env: &Env, offering_id: u32, offering: &Offering) {
+    env.events().publish(
+        (symbol_short!("off_new"), offering_id),
+        (
+            offering.seller.clone(),
+            offering.preferred_buyer.clone(),
+            offering.asset_id,
+            offering.token_amount,
+            offering.price,
+            offering.reserved_until,
+            offering.expires_at,
+        ),
+    );
+}
+
+/// offering reservation window ended, open to all buyers event
+pub fn emit_offering_opened_event(env: &Env, offering_id: u32) {
+    env.events()
+        .publish((symbol_short!("off_open"), offering_id), ());
+}
+
+/// offering filled by a buyer event
+pub fn emit_offering_filled_event(env: &Env, offering_id: u32, buyer: &Address, trade_id: u32) {
+    env.events().publish(
+        (symbol_short!("off_fill"), offering_id),
+        (buyer.clone(), trade_id),
+    );
+}
+
+/// offering withdrawn by the seller event
+pub fn emit_offering_withdrawn_event(env: &Env, offering_id: u32, seller: &Address) {
+    env.events()
+        .publish((symbol_short!("off_wdrw"), offering_id), seller.clone());
+}
+
+/// buyer offer with escrowed XLM created event
+pub fn emit_buyer_offer_created_event(env: &Env, offer_id: u32, offer: &BuyerOffer) {
+    env.events().publish(
+        (symbol_short!("ofr_new"), offer_id),
+        (
+            offer.buyer.clone(),
+            offer.asset_id,
+            offer.token_amount,
+            offer.price,
+            offer.expires_at,
+        ),
+    );
+}
+
+/// buyer offer accepted by a holder event
+pub fn emit_buyer_offer_accepted_event(env: &Env, offer_id: u32, seller: &Address, trade_id: u32) {
+    env.events().publish(
+        (symbol_short!("ofr_fill"), offer_id),
+        (seller.clone(), trade_id),
+    );
+}
+
+/// buyer offer withdrawn and refunded by the buyer event
+pub fn emit_buyer_offer_cancelled_event(env: &Env, offer_id: u32) {
+    env.events()
+        .publish((symbol_short!("ofr_cncl"), offer_id), ());
+}
+
+/// expired buyer offer refunded by the sweep event
+pub fn emit_buyer_offer_refunded_event(env: &Env, offer_id: u32, buyer: &Address, amount: u128) {
+    env.events().publish(
+        (symbol_short!("ofr_rfnd"), offer_id),
+        (buyer.clone(), amount),
+    );
+}
+
+/// refund sweep finished a batch event, with the next offer id it will check
+pub fn emit_offer_sweep_event(env: &Env, refunded: u32, cursor: u32) {
+    env.events()
+        .publish((symbol_short!("ofr_sweep"),), (refunded, cursor));
+}
+
+/// recurring purchase set up with its deposit event
+pub fn emit_subscription_created_event(
+    env: &Env,
+    subscription_id: u32,
+    subscription: &Subscription,
+) {
+    env.events().publish(
+        (symbol_short!("sub_new"), subscription_id),
+        (
+            subscription.buyer.clone(),
+            subscription.asset_id,
+            subscription.token_amount,
+            subscription.budget,
+            subscription.interval,
+            subscription.max_periods,
+        ),
+    );
+}
+
+/// keeper bought a subscription period from an offering event
+pub fn emit_subscription_bought_event(
+    env: &Env,
+    subscription_id: u32,
+    period: u32,
+    trade_id: u32,
+    token_amount: u64,
+    price: u128,
+) {
+    env.events().publish(
+        (symbol_short!("sub_buy"), subscription_id),
+        (period, trade_id, token_amount, price),
+    );
+}
+
+/// subscription period passed with no offering within its limits event
+pub fn emit_subscription_skipped_event(env: &Env, subscription_id: u32, period: u32) {
+    env.events()
+        .publish((symbol_short!("sub_skip"), subscription_id), period);
+}
+
+/// subscription ran its last period and returned the rest of the deposit event
+pub fn emit_subscription_finished_event(env: &Env, subscription_id: u32, refunded: u128) {
+    env.events()
+        .publish((symbol_short!("sub_done"), subscription_id), refunded);
+}
+
+/// buyer cancelled a subscription and got the deposit back event
+pub fn emit_subscription_cancelled_event(env: &Env, subscription_id: u32, refunded: u128) {
+    env.events()
+        .publish((symbol_short!("sub_cncl"), subscription_id), refunded);
+}
+
+/// new asset minted to its investors and the issuer paid event
+pub fn emit_primary_sale_event(
+    env: &Env,
+    asset_id: u64,
+    issuer: &Address,
+    investors: u32,
+    total_tokens: u64,
+    total_price: u128,
+) {
+    env.events().publish(
+        (symbol_short!("primary"), asset_id),
+        (issuer.clone(), investors, total_tokens, total_price),
+    );
+}
+
+/// held XLM no longer covered by the contract's balance event
+pub fn emit_invariant_violation_event(env: &Env, asset_id: u64, xlm: (i128, i128)) {
+    env.events()
+        .publish((symbol_short!("inv_alert"), asset_id), xlm);
+}
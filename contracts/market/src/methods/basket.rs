@@ -0,0 +1,52 @@
+use crate::errors::MarketError;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::BasketValuation;
+use pricing::MAX_PRICE_DECIMALS;
+use soroban_sdk::Env;
+
+/// Value a fractcore basket from the last traded price of each component.
+///
+/// Prices are per fraction, taken from the component's most recent trade on
+/// the trading contract; a component that has never traded cannot be valued.
+pub fn basket_nav(env: Env, basket_id: u64) -> Result<BasketValuation, MarketError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+
+    let components = fnft_client.get_basket(&basket_id);
+    if components.is_empty() {
+        return Err(MarketError::NotABasket);
+    }
+    let holdings = fnft_client.basket_holdings(&basket_id);
+
+    let mut total_value = 0u128;
+    for (component, held) in components.iter().zip(holdings.iter()) {
+        let (price, token_amount) = last_trade_price(&env, component.asset_id)?;
+        let unit_price =
+            pricing::price_per_token_from_total(price, token_amount, MAX_PRICE_DECIMALS)?;
+        total_value += pricing::quote_total(unit_price, held, MAX_PRICE_DECIMALS)?;
+    }
+
+    let supply = fnft_client.asset_supply(&basket_id);
+    let nav_per_token = if supply == 0 {
+        0
+    } else {
+        pricing::price_per_token_from_total(total_value, supply, 0)?
+    };
+
+    Ok(BasketValuation {
+        total_value,
+        supply,
+        nav_per_token,
+    })
+}
+
+/// (price, token_amount) of the most recent trade of an asset
+fn last_trade_price(env: &Env, asset_id: u64) -> Result<(u128, u64), MarketError> {
+    let trading = utils::trading(env)?;
+    let trade_id = match trading.get_asset_trades(&asset_id).last() {
+        Some(trade_id) => trade_id,
+        None => return Err(MarketError::NoPriceData),
+    };
+    let trade = trading.get_trade_history(&trade_id);
+    Ok((trade.price, trade.token_amount))
+}
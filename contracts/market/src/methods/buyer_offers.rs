@@ -0,0 +1,266 @@
+use crate::errors::MarketError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{custody, utils};
+use crate::storage::{
+    self, BuyerOffer, BuyerOfferStatus, DataKey, MAX_OFFER_REFUND_BATCH, MAX_OPEN_OFFERS_PER_BUYER,
+};
+use soroban_sdk::{token::TokenClient, Address, Env, Vec};
+
+/// Buyer bids `price` XLM for `token_amount` fractions of an asset.
+///
+/// The price is moved into the contract straight away, so any holder can accept
+/// without waiting on the buyer. Unaccepted offers are refunded by the buyer
+/// with `cancel_offer` or by anyone with `refund_expired_offers` once expired.
+/// Returns the offer ID.
+pub fn make_offer(
+    env: Env,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    duration_seconds: u64,
+) -> Result<u32, MarketError> {
+    buyer.require_auth();
+
+    if token_amount == 0 {
+        return Err(MarketError::InvalidTokenAmount);
+    }
+    if price == 0 {
+        return Err(MarketError::InvalidPrice);
+    }
+    if price > i128::MAX as u128 {
+        return Err(MarketError::PriceOverflow);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+    utils::require_not_paused(&env, asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(MarketError::AssetNotFound);
+    }
+
+    let mut open = get_open_offers(env.clone(), buyer.clone());
+    if open.len() >= MAX_OPEN_OFFERS_PER_BUYER {
+        return Err(MarketError::TooManyOffers);
+    }
+
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    if xlm_client.balance(&buyer) < price as i128 {
+        return Err(MarketError::BuyerInsufficientFunds);
+    }
+    xlm_client.transfer(&buyer, &env.current_contract_address(), &(price as i128));
+    custody::hold(&env, price as i128);
+
+    let offer_id: u32 = storage::get(&env, &DataKey::BuyerOfferCounter).unwrap_or(0) + 1;
+
+    let now = env.ledger().timestamp();
+    let offer = BuyerOffer {
+        buyer: buyer.clone(),
+        asset_id,
+        token_amount,
+        price,
+        created_at: now,
+        expires_at: now + duration_seconds,
+        status: BuyerOfferStatus::Open,
+    };
+
+    storage::set(&env, &DataKey::BuyerOffer(offer_id), &offer);
+    storage::set(&env, &DataKey::BuyerOfferCounter, &offer_id);
+
+    open.push_back(offer_id);
+    storage::set(&env, &DataKey::OpenBuyerOffers(buyer), &open);
+
+    events::emit_buyer_offer_created_event(&env, offer_id, &offer);
+
+    Ok(offer_id)
+}
+
+/// A holder sells into an open offer: fractions go to the buyer and the escrowed
+/// price to the seller. The expected terms guard against a replaced offer.
+/// Returns the trade ID.
+pub fn accept_offer(
+    env: Env,
+    seller: Address,
+    offer_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, MarketError> {
+    seller.require_auth();
+
+    settle_offer(env, seller, offer_id, expected_token_amount, expected_price)
+}
+
+/// `accept_offer` once the seller has authorized
+pub fn settle_offer(
+    env: Env,
+    seller: Address,
+    offer_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, MarketError> {
+    let mut offer = get_offer(env.clone(), offer_id)?;
+    if offer.status != BuyerOfferStatus::Open {
+        return Err(MarketError::SaleNotActive);
+    }
+    if env.ledger().timestamp() >= offer.expires_at {
+        return Err(MarketError::SaleExpired);
+    }
+    if seller == offer.buyer {
+        return Err(MarketError::SelfTrade);
+    }
+    if offer.token_amount != expected_token_amount {
+        return Err(MarketError::TokenAmountMismatch);
+    }
+    if offer.price != expected_price {
+        return Err(MarketError::PriceMismatch);
+    }
+
+    // The seller picked this buyer, so only screening and pauses apply
+    utils::trading(&env)?.check_parties(&seller, &offer.buyer, &offer.asset_id);
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let current_allowance = fnft_client.allowance(&seller, &contract_id, &offer.asset_id);
+    fnft_client.approve(
+        &seller,
+        &contract_id,
+        &offer.asset_id,
+        &(current_allowance + offer.token_amount),
+    );
+
+    offer.status = BuyerOfferStatus::Accepted;
+    close_offer(&env, offer_id, &offer);
+    custody::release(&env, offer.price as i128);
+
+    let trade_id = utils::swap(
+        &env,
+        &seller,
+        &offer.buyer,
+        &contract_id,
+        offer.asset_id,
+        offer.token_amount,
+        offer.price,
+    )?;
+    custody::check_invariants(&env, offer.asset_id)?;
+
+    events::emit_buyer_offer_accepted_event(&env, offer_id, &seller, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Buyer withdraws an open offer, expired or not, and gets the XLM back
+pub fn cancel_offer(env: Env, buyer: Address, offer_id: u32) -> Result<(), MarketError> {
+    buyer.require_auth();
+
+    let mut offer = get_offer(env.clone(), offer_id)?;
+    if offer.buyer != buyer {
+        return Err(MarketError::UnauthorizedBuyer);
+    }
+    if offer.status != BuyerOfferStatus::Open {
+        return Err(MarketError::SaleNotActive);
+    }
+
+    offer.status = BuyerOfferStatus::Cancelled;
+    refund(&env, offer_id, &offer)?;
+
+    events::emit_buyer_offer_cancelled_event(&env, offer_id);
+
+    Ok(())
+}
+
+/// Anyone returns the XLM of expired offers to their buyers.
+///
+/// Checks up to `limit` offers (at most MAX_OFFER_REFUND_BATCH) from a stored
+/// cursor that only moves past offers already closed, so offers still open are
+/// checked again on the next call. Returns the number of offers refunded.
+pub fn refund_expired_offers(env: Env, limit: u32) -> Result<u32, MarketError> {
+    if limit == 0 {
+        return Err(MarketError::InvalidAmount);
+    }
+
+    let offer_count: u32 = storage::get(&env, &DataKey::BuyerOfferCounter).unwrap_or(0);
+    let start = get_refund_cursor(env.clone());
+    let end = offer_count.min(start - 1 + limit.min(MAX_OFFER_REFUND_BATCH));
+
+    let now = env.ledger().timestamp();
+    let mut refunded = 0u32;
+    let mut cursor = start;
+    let mut closed_prefix = true;
+    for offer_id in start..=end {
+        let mut offer = get_offer(env.clone(), offer_id)?;
+        if offer.status == BuyerOfferStatus::Open {
+            if now >= offer.expires_at {
+                offer.status = BuyerOfferStatus::Refunded;
+                refund(&env, offer_id, &offer)?;
+                events::emit_buyer_offer_refunded_event(&env, offer_id, &offer.buyer, offer.price);
+                refunded += 1;
+            } else {
+                closed_prefix = false;
+            }
+        }
+        if closed_prefix {
+            cursor = offer_id + 1;
+        }
+    }
+
+    storage::set(&env, &DataKey::OfferRefundCursor, &cursor);
+
+    events::emit_offer_sweep_event(&env, refunded, cursor);
+
+    Ok(refunded)
+}
+
+pub fn get_offer(env: Env, offer_id: u32) -> Result<BuyerOffer, MarketError> {
+    match storage::get(&env, &DataKey::BuyerOffer(offer_id)) {
+        Some(offer) => Ok(offer),
+        None => Err(MarketError::SaleNotFound),
+    }
+}
+
+/// IDs of a buyer's offers whose XLM is still held, expired or not
+pub fn get_open_offers(env: Env, buyer: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::OpenBuyerOffers(buyer)).unwrap_or(Vec::new(&env))
+}
+
+/// XLM held for a buyer's expired offers, waiting to be refunded
+pub fn get_refundable_amount(env: Env, buyer: Address) -> Result<u128, MarketError> {
+    let now = env.ledger().timestamp();
+    let mut total = 0u128;
+    for offer_id in get_open_offers(env.clone(), buyer).iter() {
+        let offer = get_offer(env.clone(), offer_id)?;
+        if now >= offer.expires_at {
+            total += offer.price;
+        }
+    }
+    Ok(total)
+}
+
+/// First offer ID refund_expired_offers will check
+pub fn get_refund_cursor(env: Env) -> u32 {
+    storage::get(&env, &DataKey::OfferRefundCursor).unwrap_or(1)
+}
+
+fn refund(env: &Env, offer_id: u32, offer: &BuyerOffer) -> Result<(), MarketError> {
+    let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+    xlm_client.transfer(
+        &env.current_contract_address(),
+        &offer.buyer,
+        &(offer.price as i128),
+    );
+    custody::release(env, offer.price as i128);
+
+    close_offer(env, offer_id, offer);
+
+    Ok(())
+}
+
+fn close_offer(env: &Env, offer_id: u32, offer: &BuyerOffer) {
+    storage::set(env, &DataKey::BuyerOffer(offer_id), offer);
+
+    let mut open = get_open_offers(env.clone(), offer.buyer.clone());
+    if let Some(index) = open.first_index_of(offer_id) {
+        open.remove(index);
+    }
+    storage::set(env, &DataKey::OpenBuyerOffers(offer.buyer.clone()), &open);
+}
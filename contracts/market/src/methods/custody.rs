@@ -0,0 +1,43 @@
+use crate::errors::MarketError;
+use crate::events;
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Env};
+
+/// XLM the contract holds for open buyer offers and subscription deposits
+pub fn get_held_xlm(env: Env) -> i128 {
+    storage::get(&env, &DataKey::HeldXlm).unwrap_or(0)
+}
+
+/// Record XLM that entered the contract and is owed to a buyer
+pub fn hold(env: &Env, xlm: i128) {
+    let held = get_held_xlm(env.clone()) + xlm;
+    storage::set(env, &DataKey::HeldXlm, &held);
+}
+
+/// Record XLM paid out of the contract
+pub fn release(env: &Env, xlm: i128) {
+    let held = get_held_xlm(env.clone()) - xlm;
+    storage::set(env, &DataKey::HeldXlm, &held.max(0));
+}
+
+/// Check the contract's XLM balance still covers the held XLM after a payout.
+///
+/// A violation has trading pause the asset and emits an alert instead of
+/// reverting, so the pause survives; it stays paused until the trading admin
+/// unpauses it. Returns whether the invariant held.
+pub fn check_invariants(env: &Env, asset_id: u64) -> Result<bool, MarketError> {
+    let contract_id = env.current_contract_address();
+    let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+    let xlm_balance = xlm_client.balance(&contract_id);
+    let xlm_owed = get_held_xlm(env.clone());
+
+    if xlm_balance >= xlm_owed {
+        return Ok(true);
+    }
+
+    utils::trading(env)?.pause_asset(&contract_id, &asset_id);
+    events::emit_invariant_violation_event(env, asset_id, (xlm_owed, xlm_balance));
+
+    Ok(false)
+}
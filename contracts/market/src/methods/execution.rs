@@ -0,0 +1,120 @@
+use crate::errors::MarketError;
+use crate::methods::{offerings, utils};
+use crate::storage::{BestExecutionReport, ExecutionPolicy, SaleProposal};
+use soroban_sdk::{Address, Env};
+
+/// Settle a direct proposal on trading after comparing it with the offerings the
+/// buyer could fill instead.
+///
+/// A cheaper offering per fraction is handled according to `policy`: `Warn`
+/// settles anyway and reports it, `Abort` fails with `PriceMismatch`, and `Route`
+/// fills the cheapest offering of the same token amount in place of the
+/// proposal, which stays open. When nothing can be routed, `Route` behaves like
+/// `Warn`.
+pub fn finish_best_execution(
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    asset_id: u64,
+    expected_token_amount: u64,
+    expected_price: u128,
+    policy: ExecutionPolicy,
+) -> Result<BestExecutionReport, MarketError> {
+    buyer.require_auth();
+
+    let trading = utils::trading(&env)?;
+    let proposal = open_proposal(
+        &env,
+        &buyer,
+        &seller,
+        asset_id,
+        expected_token_amount,
+        expected_price,
+    )?;
+
+    let best_offering = offerings::cheapest_offering(&env, &buyer, asset_id, None, None)
+        .filter(|(_, offering)| {
+            offerings::is_cheaper(
+                offering.price,
+                offering.token_amount,
+                proposal.price,
+                proposal.token_amount,
+            )
+        })
+        .map(|(offering_id, _)| offering_id);
+
+    if best_offering.is_some() {
+        match policy {
+            ExecutionPolicy::Abort => return Err(MarketError::PriceMismatch),
+            ExecutionPolicy::Route => {
+                let same_size = offerings::cheapest_offering(
+                    &env,
+                    &buyer,
+                    asset_id,
+                    Some(proposal.token_amount),
+                    None,
+                )
+                .filter(|(_, offering)| offering.price < proposal.price);
+                if let Some((offering_id, offering)) = same_size {
+                    let trade_id = offerings::settle_offering(
+                        env.clone(),
+                        buyer.clone(),
+                        offering_id,
+                        offering.token_amount,
+                        offering.price,
+                    )?;
+                    return Ok(BestExecutionReport {
+                        trade_id,
+                        best_offering: Some(offering_id),
+                        routed: true,
+                    });
+                }
+            }
+            ExecutionPolicy::Warn => {}
+        }
+    }
+
+    trading.finish_transaction(
+        &buyer,
+        &seller,
+        &asset_id,
+        &proposal.token_amount,
+        &proposal.price,
+    );
+
+    Ok(BestExecutionReport {
+        trade_id: trading.get_trade_count(),
+        best_offering,
+        routed: false,
+    })
+}
+
+/// Read the proposal from trading and check it is still fillable on the terms
+/// the buyer expects, so a stale proposal fails before any offering is compared
+fn open_proposal(
+    env: &Env,
+    buyer: &Address,
+    seller: &Address,
+    asset_id: u64,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<SaleProposal, MarketError> {
+    let proposal = match utils::trading(env)?.try_get_sale_proposal(seller, buyer, &asset_id) {
+        Ok(Ok(proposal)) => proposal,
+        _ => return Err(MarketError::SaleNotFound),
+    };
+    if !proposal.is_active {
+        return Err(MarketError::SaleNotActive);
+    }
+    if env.ledger().timestamp() > proposal.expires_at {
+        return Err(MarketError::SaleExpired);
+    }
+    if proposal.token_amount != expected_token_amount {
+        return Err(MarketError::TokenAmountMismatch);
+    }
+    if proposal.price != expected_price {
+        return Err(MarketError::PriceMismatch);
+    }
+
+    Ok(proposal)
+}
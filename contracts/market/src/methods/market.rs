@@ -0,0 +1,183 @@
+use crate::errors::MarketError;
+use crate::interfaces::FNFTClient;
+use crate::methods::{buyer_offers, offerings, utils};
+use crate::storage::{
+    self, BuyerOffer, BuyerOfferStatus, DataKey, MarketExecutionReport, MarketFill,
+    MAX_MARKET_BID_SCAN, MAX_MARKET_FILLS,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+// Offerings and buyer offers are filled whole, so a market order is a run of
+// complete fills at the best price per fraction, in one transaction: if any
+// fill fails, or the slippage bound is missed, nothing is settled.
+
+/// Buy as many fractions as `max_total` XLM pays for, cheapest offering per
+/// fraction first.
+///
+/// Offerings are drawn from the newest `MAX_BEST_EXECUTION_SCAN` of the asset,
+/// up to `MAX_MARKET_FILLS` of them; one that no longer fits the remaining
+/// budget is passed over for a cheaper one that does. Fails with
+/// `OfferingNotFound` when nothing could be bought.
+pub fn market_buy(
+    env: Env,
+    buyer: Address,
+    asset_id: u64,
+    max_total: u128,
+) -> Result<MarketExecutionReport, MarketError> {
+    buyer.require_auth();
+
+    if max_total == 0 {
+        return Err(MarketError::InvalidPrice);
+    }
+    utils::require_not_paused(&env, asset_id)?;
+
+    let mut report = empty_report(&env);
+    while report.fills.len() < MAX_MARKET_FILLS {
+        let remaining = max_total - report.total;
+        let Some((offering_id, offering)) =
+            offerings::cheapest_offering(&env, &buyer, asset_id, None, Some(remaining))
+        else {
+            break;
+        };
+
+        let trade_id = offerings::settle_offering(
+            env.clone(),
+            buyer.clone(),
+            offering_id,
+            offering.token_amount,
+            offering.price,
+        )?;
+        add_fill(
+            &mut report,
+            MarketFill {
+                order_id: offering_id,
+                trade_id,
+                token_amount: offering.token_amount,
+                price: offering.price,
+            },
+        );
+    }
+
+    if report.fills.is_empty() {
+        return Err(MarketError::OfferingNotFound);
+    }
+    Ok(report)
+}
+
+/// Sell up to `amount` fractions into the best buyer offers per fraction,
+/// receiving at least `min_total` XLM in all.
+///
+/// Offers are drawn from the newest `MAX_MARKET_BID_SCAN`, up to
+/// `MAX_MARKET_FILLS` of them, and only those for no more than the fractions
+/// left to sell, so fewer than `amount` may be sold. Fails with
+/// `SaleNotFound` when no offer could be filled and `PriceMismatch` when the
+/// fills fall short of `min_total`.
+pub fn market_sell(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+    amount: u64,
+    min_total: u128,
+) -> Result<MarketExecutionReport, MarketError> {
+    seller.require_auth();
+
+    if amount == 0 {
+        return Err(MarketError::InvalidTokenAmount);
+    }
+    utils::require_not_paused(&env, asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if fnft_client.balance_of(&seller, &asset_id) < amount {
+        return Err(MarketError::SellerInsufficientBalance);
+    }
+
+    let mut report = empty_report(&env);
+    while report.fills.len() < MAX_MARKET_FILLS {
+        let unsold = amount - report.token_amount;
+        let Some((offer_id, offer)) = best_offer(&env, &seller, asset_id, unsold) else {
+            break;
+        };
+
+        let trade_id = buyer_offers::settle_offer(
+            env.clone(),
+            seller.clone(),
+            offer_id,
+            offer.token_amount,
+            offer.price,
+        )?;
+        add_fill(
+            &mut report,
+            MarketFill {
+                order_id: offer_id,
+                trade_id,
+                token_amount: offer.token_amount,
+                price: offer.price,
+            },
+        );
+    }
+
+    if report.fills.is_empty() {
+        return Err(MarketError::SaleNotFound);
+    }
+    if report.total < min_total {
+        return Err(MarketError::PriceMismatch);
+    }
+    Ok(report)
+}
+
+/// Open offer for the asset paying the most per fraction that `seller` could
+/// accept with `max_amount` fractions, among the `MAX_MARKET_BID_SCAN` newest
+fn best_offer(
+    env: &Env,
+    seller: &Address,
+    asset_id: u64,
+    max_amount: u64,
+) -> Option<(u32, BuyerOffer)> {
+    let newest: u32 = storage::get(env, &DataKey::BuyerOfferCounter).unwrap_or(0);
+    let oldest = newest.saturating_sub(MAX_MARKET_BID_SCAN) + 1;
+    let now = env.ledger().timestamp();
+    let mut best: Option<(u32, BuyerOffer)> = None;
+
+    for offer_id in (oldest..=newest).rev() {
+        let Some(offer) = storage::get::<_, BuyerOffer>(env, &DataKey::BuyerOffer(offer_id)) else {
+            continue;
+        };
+        if offer.asset_id != asset_id
+            || offer.status != BuyerOfferStatus::Open
+            || now >= offer.expires_at
+            || offer.buyer == *seller
+            || offer.token_amount > max_amount
+        {
+            continue;
+        }
+
+        let better = match &best {
+            Some((_, current)) => offerings::is_cheaper(
+                current.price,
+                current.token_amount,
+                offer.price,
+                offer.token_amount,
+            ),
+            None => true,
+        };
+        if better {
+            best = Some((offer_id, offer));
+        }
+    }
+
+    best
+}
+
+fn empty_report(env: &Env) -> MarketExecutionReport {
+    MarketExecutionReport {
+        fills: Vec::new(env),
+        token_amount: 0,
+        total: 0,
+    }
+}
+
+fn add_fill(report: &mut MarketExecutionReport, fill: MarketFill) {
+    report.token_amount += fill.token_amount;
+    report.total += fill.price;
+    report.fills.push_back(fill);
+}
@@ -0,0 +1,11 @@
+pub mod basket;
+pub mod buyer_offers;
+pub mod custody;
+pub mod execution;
+pub mod initialization;
+pub mod market;
+pub mod offerings;
+pub mod primary;
+pub mod queries;
+pub mod subscriptions;
+pub mod utils;
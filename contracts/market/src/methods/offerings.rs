@@ -0,0 +1,334 @@
+use crate::errors::MarketError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{self, Conduct, DataKey, Offering, OfferingPhase, MAX_BEST_EXECUTION_SCAN};
+use soroban_sdk::{Address, Env};
+
+/// Seller lists fractions with a right of first refusal: only the preferred buyer
+/// can fill during the reservation window, then the offering opens to everyone
+#[allow(clippy::too_many_arguments)]
+pub fn create_offering(
+    env: Env,
+    seller: Address,
+    preferred_buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    reservation_seconds: u64,
+    duration_seconds: u64,
+) -> Result<u32, MarketError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(MarketError::InvalidTokenAmount);
+    }
+    if price == 0 {
+        return Err(MarketError::InvalidPrice);
+    }
+    if seller == preferred_buyer {
+        return Err(MarketError::SelfTrade);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+    // The public phase must last at least one second
+    if reservation_seconds == 0 || reservation_seconds >= duration_seconds {
+        return Err(MarketError::InvalidDuration);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(MarketError::AssetNotFound);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < token_amount {
+        return Err(MarketError::InsufficientBalance);
+    }
+
+    let contract_id = env.current_contract_address();
+    let current_allowance = fnft_client.allowance(&seller, &contract_id, &asset_id);
+    fnft_client.approve(
+        &seller,
+        &contract_id,
+        &asset_id,
+        &(current_allowance + token_amount),
+    );
+
+    let offering_id: u32 = storage::get(&env, &DataKey::OfferingCounter).unwrap_or(0) + 1;
+
+    let now = env.ledger().timestamp();
+    let offering = Offering {
+        seller: seller.clone(),
+        preferred_buyer,
+        asset_id,
+        token_amount,
+        price,
+        created_at: now,
+        reserved_until: now + reservation_seconds,
+        expires_at: now + duration_seconds,
+        opened: false,
+    };
+
+    storage::set(&env, &DataKey::Offering(offering_id), &offering);
+    storage::set(&env, &DataKey::OfferingCounter, &offering_id);
+    utils::add_to_seller_offerings(&env, seller, offering_id);
+    utils::add_to_asset_offerings(&env, asset_id, offering_id);
+
+    events::emit_offering_created_event(&env, offering_id, &offering);
+
+    Ok(offering_id)
+}
+
+/// Buyer fills an offering at the advertised terms; returns the trade ID
+pub fn fill_offering(
+    env: Env,
+    buyer: Address,
+    offering_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, MarketError> {
+    buyer.require_auth();
+
+    settle_offering(
+        env,
+        buyer,
+        offering_id,
+        expected_token_amount,
+        expected_price,
+    )
+}
+
+/// Fill an offering and deposit into a fractcore basket in the same call.
+///
+/// The buyer authorizes once; fractcore takes the component fractions for
+/// `basket_units` from the buyer's balance, which already includes the purchase.
+/// If the deposit fails the purchase is reverted with it. Returns the trade ID.
+pub fn buy_and_stake(
+    env: Env,
+    buyer: Address,
+    offering_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+    basket_id: u64,
+    basket_units: u64,
+) -> Result<u32, MarketError> {
+    buyer.require_auth();
+
+    if basket_units == 0 {
+        return Err(MarketError::InvalidTokenAmount);
+    }
+
+    let offering = utils::get_offering(&env, offering_id)?;
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client
+        .get_basket(&basket_id)
+        .iter()
+        .any(|component| component.asset_id == offering.asset_id)
+    {
+        return Err(MarketError::NotABasket);
+    }
+
+    let trade_id = settle_offering(
+        env.clone(),
+        buyer.clone(),
+        offering_id,
+        expected_token_amount,
+        expected_price,
+    )?;
+
+    fnft_client.deposit_to_basket(&buyer, &basket_id, &basket_units);
+
+    Ok(trade_id)
+}
+
+pub fn settle_offering(
+    env: Env,
+    buyer: Address,
+    offering_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, MarketError> {
+    settle_offering_from(
+        env,
+        buyer.clone(),
+        &buyer,
+        offering_id,
+        expected_token_amount,
+        expected_price,
+    )
+}
+
+/// `settle_offering` with the price paid by `payer` (see `utils::swap`)
+pub fn settle_offering_from(
+    env: Env,
+    buyer: Address,
+    payer: &Address,
+    offering_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, MarketError> {
+    let offering = utils::get_offering(&env, offering_id)?;
+
+    match phase(&env, &offering) {
+        OfferingPhase::Expired => return Err(MarketError::SaleExpired),
+        OfferingPhase::Reserved if buyer != offering.preferred_buyer => {
+            return Err(MarketError::UnauthorizedBuyer)
+        }
+        OfferingPhase::Public if !offering.opened => {
+            events::emit_offering_opened_event(&env, offering_id);
+        }
+        _ => {}
+    }
+
+    if buyer == offering.seller {
+        return Err(MarketError::SelfTrade);
+    }
+
+    // Validate buyer's expected terms to prevent bait-and-switch attacks
+    if offering.token_amount != expected_token_amount {
+        return Err(MarketError::TokenAmountMismatch);
+    }
+    if offering.price != expected_price {
+        return Err(MarketError::PriceMismatch);
+    }
+
+    utils::trading(&env)?.check_trade(&offering.seller, &buyer, &offering.asset_id);
+
+    // Delist before the external calls so a re-entrant call finds nothing to fill
+    remove_offering(&env, &offering, offering_id);
+
+    let trade_id = utils::swap(
+        &env,
+        &offering.seller,
+        &buyer,
+        payer,
+        offering.asset_id,
+        offering.token_amount,
+        offering.price,
+    )?;
+
+    events::emit_offering_filled_event(&env, offering_id, &buyer, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Anyone can mark the end of the reservation window so indexers see the
+/// offering go public; filling works either way once the window has passed
+pub fn open_offering(env: Env, offering_id: u32) -> Result<(), MarketError> {
+    let mut offering = utils::get_offering(&env, offering_id)?;
+
+    match phase(&env, &offering) {
+        OfferingPhase::Reserved => return Err(MarketError::ReservationActive),
+        OfferingPhase::Expired => return Err(MarketError::SaleExpired),
+        OfferingPhase::Public => {}
+    }
+
+    if !offering.opened {
+        offering.opened = true;
+        storage::set(&env, &DataKey::Offering(offering_id), &offering);
+        events::emit_offering_opened_event(&env, offering_id);
+    }
+
+    Ok(())
+}
+
+/// Seller withdraws an unfilled offering and its allowance, in any phase
+pub fn withdraw_offering(env: Env, seller: Address, offering_id: u32) -> Result<(), MarketError> {
+    seller.require_auth();
+
+    let offering = utils::get_offering(&env, offering_id)?;
+
+    if offering.seller != seller {
+        return Err(MarketError::UnauthorizedSeller);
+    }
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let current_allowance = fnft_client.allowance(&seller, &contract_id, &offering.asset_id);
+    fnft_client.approve(
+        &seller,
+        &contract_id,
+        &offering.asset_id,
+        &current_allowance.saturating_sub(offering.token_amount),
+    );
+
+    remove_offering(&env, &offering, offering_id);
+    utils::trading(&env)?.record_conduct(&contract_id, &seller, &Conduct::Cancellation);
+
+    events::emit_offering_withdrawn_event(&env, offering_id, &seller);
+
+    Ok(())
+}
+
+pub fn get_offering_phase(env: Env, offering_id: u32) -> Result<OfferingPhase, MarketError> {
+    let offering = utils::get_offering(&env, offering_id)?;
+    Ok(phase(&env, &offering))
+}
+
+pub(crate) fn phase(env: &Env, offering: &Offering) -> OfferingPhase {
+    let now = env.ledger().timestamp();
+    if now > offering.expires_at {
+        OfferingPhase::Expired
+    } else if now <= offering.reserved_until {
+        OfferingPhase::Reserved
+    } else {
+        OfferingPhase::Public
+    }
+}
+
+/// Cheapest offering per fraction that `buyer` could fill right now, among the
+/// `MAX_BEST_EXECUTION_SCAN` newest for the asset; `token_amount` restricts it to
+/// offerings of that size and `max_price` to offerings costing at most that much
+pub fn cheapest_offering(
+    env: &Env,
+    buyer: &Address,
+    asset_id: u64,
+    token_amount: Option<u64>,
+    max_price: Option<u128>,
+) -> Option<(u32, Offering)> {
+    let ids = utils::get_asset_offerings(env, asset_id);
+    let mut best: Option<(u32, Offering)> = None;
+
+    for offering_id in ids.iter().rev().take(MAX_BEST_EXECUTION_SCAN as usize) {
+        let offering = match utils::get_offering(env, offering_id) {
+            Ok(offering) => offering,
+            Err(_) => continue,
+        };
+        let fillable = match phase(env, &offering) {
+            OfferingPhase::Expired => false,
+            OfferingPhase::Reserved => *buyer == offering.preferred_buyer,
+            OfferingPhase::Public => true,
+        };
+        if !fillable
+            || offering.seller == *buyer
+            || token_amount.is_some_and(|amount| amount != offering.token_amount)
+            || max_price.is_some_and(|max| offering.price > max)
+        {
+            continue;
+        }
+
+        let cheaper = match &best {
+            Some((_, current)) => is_cheaper(
+                offering.price,
+                offering.token_amount,
+                current.price,
+                current.token_amount,
+            ),
+            None => true,
+        };
+        if cheaper {
+            best = Some((offering_id, offering));
+        }
+    }
+
+    best
+}
+
+/// Whether `price / token_amount` is strictly below `other_price / other_amount`
+pub fn is_cheaper(price: u128, token_amount: u64, other_price: u128, other_amount: u64) -> bool {
+    price.saturating_mul(other_amount as u128) < other_price.saturating_mul(token_amount as u128)
+}
+
+fn remove_offering(env: &Env, offering: &Offering, offering_id: u32) {
+    storage::remove(env, &DataKey::Offering(offering_id));
+    utils::remove_from_seller_offerings(env, offering.seller.clone(), offering_id);
+    utils::remove_from_asset_offerings(env, offering.asset_id, offering_id);
+}
@@ -0,0 +1,108 @@
+use crate::errors::MarketError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{PrimaryAllocation, MAX_PRIMARY_ALLOCATIONS};
+use soroban_sdk::{token::TokenClient, Address, Env, String, Vec};
+
+/// Sell a new asset to several investors at once: the asset is minted straight
+/// to the investors per `allocations`, and trading takes each investor's XLM
+/// price and pays it to the issuer (less the trading fee).
+///
+/// Each investor appears once. Everything happens in one invocation, so if any
+/// leg fails — an investor without funds or auth, a blocked address, the mint
+/// itself — nothing moves.
+/// The mint is fractcore's admin-only `mint_distributed`, so the fractcore
+/// admin has to authorize the same transaction. Each leg is recorded on trading
+/// as a trade from the issuer. Returns the new asset ID.
+pub fn primary_sale(
+    env: Env,
+    issuer: Address,
+    allocations: Vec<PrimaryAllocation>,
+    uri: Option<String>,
+) -> Result<u64, MarketError> {
+    issuer.require_auth();
+
+    if allocations.is_empty() || allocations.len() > MAX_PRIMARY_ALLOCATIONS {
+        return Err(MarketError::InvalidAmount);
+    }
+
+    let mut recipients = Vec::new(&env);
+    let mut amounts = Vec::new(&env);
+    let mut total_tokens: u64 = 0;
+    let mut total_price: u128 = 0;
+    for allocation in allocations.iter() {
+        if allocation.token_amount == 0 {
+            return Err(MarketError::InvalidTokenAmount);
+        }
+        if allocation.price == 0 {
+            return Err(MarketError::InvalidPrice);
+        }
+        if allocation.investor == issuer {
+            return Err(MarketError::SelfTrade);
+        }
+        // One leg per investor, so each signs for exactly one payment
+        if recipients.contains(&allocation.investor) {
+            return Err(MarketError::InvalidAmount);
+        }
+
+        total_tokens = match total_tokens.checked_add(allocation.token_amount) {
+            Some(total) => total,
+            None => return Err(MarketError::InvalidTokenAmount),
+        };
+        total_price = match total_price.checked_add(allocation.price) {
+            Some(total) if total <= i128::MAX as u128 => total,
+            _ => return Err(MarketError::PriceOverflow),
+        };
+
+        recipients.push_back(allocation.investor.clone());
+        amounts.push_back(allocation.token_amount);
+    }
+
+    let trading = utils::trading(&env)?;
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    for allocation in allocations.iter() {
+        allocation.investor.require_auth();
+        if xlm_client.balance(&allocation.investor) < allocation.price as i128 {
+            return Err(MarketError::BuyerInsufficientFunds);
+        }
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let asset_id = fnft_client.mint_distributed(&recipients, &amounts, &uri);
+
+    // Screening is checked against the new asset, and a failure reverts the mint
+    for investor in recipients.iter() {
+        trading.check_parties(&issuer, &investor, &asset_id);
+    }
+
+    let contract_id = env.current_contract_address();
+    for allocation in allocations.iter() {
+        trading.pay_seller(
+            &contract_id,
+            &allocation.investor,
+            &issuer,
+            &asset_id,
+            &(allocation.price as i128),
+        );
+        trading.record_trade(
+            &contract_id,
+            &issuer,
+            &allocation.investor,
+            &asset_id,
+            &allocation.token_amount,
+            &allocation.price,
+        );
+    }
+
+    events::emit_primary_sale_event(
+        &env,
+        asset_id,
+        &issuer,
+        allocations.len(),
+        total_tokens,
+        total_price,
+    );
+
+    Ok(asset_id)
+}
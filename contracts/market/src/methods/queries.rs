@@ -0,0 +1,177 @@
+use crate::errors::MarketError;
+use crate::interfaces::FNFTClient;
+use crate::methods::{offerings, utils};
+use crate::storage::{
+    self, Commitment, Cursor, DataKey, Offering, OfferingPhase, SellerCommitments,
+    MAX_DISCOVERY_SCAN, MAX_PAGE_SIZE,
+};
+use soroban_sdk::{Address, Env, Map, Symbol, Vec};
+
+pub fn get_offering(env: Env, offering_id: u32) -> Result<Offering, MarketError> {
+    utils::get_offering(&env, offering_id)
+}
+
+/// Live offering IDs created by the seller
+pub fn get_seller_offerings(env: Env, seller: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::SellerOfferings(seller)).unwrap_or(Vec::new(&env))
+}
+
+/// Up to `limit` live offering ids of an asset from `cursor`, oldest first.
+///
+/// The cursor holds the next offering id rather than a list index, so offerings
+/// filled or withdrawn meanwhile never shift the walk, and ids above the
+/// offering counter at the start are left for the next walk.
+pub fn get_asset_offerings_page(
+    env: Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<u32>, Option<Cursor>) {
+    let snapshot = cursor
+        .as_ref()
+        .map_or_else(|| offering_counter(&env), |cursor| cursor.snapshot);
+    let from = cursor.map_or(0, |cursor| cursor.position);
+    let limit = page_limit(limit);
+
+    let mut offering_ids = Vec::new(&env);
+    for offering_id in utils::get_asset_offerings(&env, asset_id).iter() {
+        if offering_id < from || offering_id as u64 > snapshot {
+            continue;
+        }
+        if offering_ids.len() == limit {
+            return (
+                offering_ids,
+                Some(Cursor {
+                    page: 0,
+                    position: offering_id,
+                    snapshot,
+                }),
+            );
+        }
+        offering_ids.push_back(offering_id);
+    }
+
+    (offering_ids, None)
+}
+
+/// Unexpired offerings across all assets, oldest first, with the cursor of the
+/// next page; with `category`, only offerings of assets fractcore lists under it.
+///
+/// At most MAX_DISCOVERY_SCAN offering ids are examined per call, so a page can
+/// come back short, even empty, with a cursor to carry on from.
+pub fn discover_offerings(
+    env: Env,
+    category: Option<Symbol>,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<(Vec<u32>, Option<Cursor>), MarketError> {
+    let snapshot = cursor
+        .as_ref()
+        .map_or_else(|| offering_counter(&env), |cursor| cursor.snapshot);
+    let from = cursor.map_or(1, |cursor| cursor.position);
+    let end = (snapshot as u32).min(from.saturating_add(MAX_DISCOVERY_SCAN - 1));
+    let limit = page_limit(limit);
+
+    let fnft_client = match category {
+        Some(_) => Some(FNFTClient::new(&env, &utils::get_fnft_contract(&env)?)),
+        None => None,
+    };
+    let mut in_category: Map<u64, bool> = Map::new(&env);
+
+    let mut offering_ids = Vec::new(&env);
+    for offering_id in from..=end {
+        if offering_ids.len() == limit {
+            return Ok((
+                offering_ids,
+                Some(Cursor {
+                    page: 0,
+                    position: offering_id,
+                    snapshot,
+                }),
+            ));
+        }
+
+        let Ok(offering) = utils::get_offering(&env, offering_id) else {
+            continue;
+        };
+        if offerings::phase(&env, &offering) == OfferingPhase::Expired {
+            continue;
+        }
+        if let (Some(category), Some(fnft_client)) = (&category, &fnft_client) {
+            let matches = in_category.get(offering.asset_id).unwrap_or_else(|| {
+                let matches =
+                    fnft_client.asset_category(&offering.asset_id).as_ref() == Some(category);
+                in_category.set(offering.asset_id, matches);
+                matches
+            });
+            if !matches {
+                continue;
+            }
+        }
+        offering_ids.push_back(offering_id);
+    }
+
+    let next = ((end as u64) < snapshot).then_some(Cursor {
+        page: 0,
+        position: end + 1,
+        snapshot,
+    });
+    Ok((offering_ids, next))
+}
+
+fn offering_counter(env: &Env) -> u64 {
+    storage::get::<DataKey, u32>(env, &DataKey::OfferingCounter).unwrap_or(0) as u64
+}
+
+/// Items served per paginated call: `limit`, where 0 or anything above
+/// MAX_PAGE_SIZE means MAX_PAGE_SIZE
+fn page_limit(limit: u32) -> u32 {
+    if limit == 0 || limit > MAX_PAGE_SIZE {
+        MAX_PAGE_SIZE
+    } else {
+        limit
+    }
+}
+
+/// What a seller's allowance to this contract for an asset is reserved for,
+/// and how it compares with the allowance fractcore actually holds.
+///
+/// Offerings stay committed after they expire until they are withdrawn, so
+/// `expires_at` lets wallets spot stale ones. A non-zero `uncommitted` is
+/// orphaned allowance the seller may want to reset. Proposals on the trading
+/// contract are under a separate allowance and not listed here.
+pub fn get_seller_commitments(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+) -> Result<SellerCommitments, MarketError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let allowance = fnft_client.allowance(&seller, &env.current_contract_address(), &asset_id);
+    let mut commitments = Vec::new(&env);
+
+    for offering_id in get_seller_offerings(env.clone(), seller).iter() {
+        if let Ok(offering) = utils::get_offering(&env, offering_id) {
+            if offering.asset_id == asset_id {
+                commitments.push_back(Commitment {
+                    counterparty: offering.preferred_buyer,
+                    id: offering_id,
+                    token_amount: offering.token_amount,
+                    expires_at: offering.expires_at,
+                });
+            }
+        }
+    }
+
+    let total_committed = commitments
+        .iter()
+        .map(|commitment| commitment.token_amount)
+        .sum::<u64>();
+
+    Ok(SellerCommitments {
+        commitments,
+        total_committed,
+        allowance,
+        uncommitted: allowance.saturating_sub(total_committed),
+        shortfall: total_committed.saturating_sub(allowance),
+    })
+}
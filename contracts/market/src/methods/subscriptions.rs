@@ -0,0 +1,241 @@
+use crate::errors::MarketError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{custody, offerings, utils};
+use crate::storage::{
+    self, DataKey, Subscription, MAX_SUBSCRIPTIONS_PER_BUYER, MAX_SUBSCRIPTION_PERIODS,
+    MIN_SUBSCRIPTION_INTERVAL,
+};
+use soroban_sdk::{token::TokenClient, Address, Env, Vec};
+
+/// Buyer sets up a recurring purchase of an asset's fractions.
+///
+/// `budget * max_periods` XLM is moved into the contract now, so keepers can
+/// buy each period without the buyer signing again. Each period buys the
+/// cheapest open offering per fraction costing at most `budget`, of exactly
+/// `token_amount` fractions when given. The first period is due at once.
+/// Returns the subscription ID.
+pub fn create_subscription(
+    env: Env,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: Option<u64>,
+    budget: u128,
+    interval: u64,
+    max_periods: u32,
+) -> Result<u32, MarketError> {
+    buyer.require_auth();
+
+    if token_amount == Some(0) {
+        return Err(MarketError::InvalidTokenAmount);
+    }
+    if budget == 0 {
+        return Err(MarketError::InvalidPrice);
+    }
+    if interval < MIN_SUBSCRIPTION_INTERVAL {
+        return Err(MarketError::InvalidDuration);
+    }
+    if max_periods == 0 || max_periods > MAX_SUBSCRIPTION_PERIODS {
+        return Err(MarketError::InvalidAmount);
+    }
+    let deposit = match budget.checked_mul(max_periods as u128) {
+        Some(deposit) if deposit <= i128::MAX as u128 => deposit,
+        _ => return Err(MarketError::PriceOverflow),
+    };
+    utils::require_not_paused(&env, asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(MarketError::AssetNotFound);
+    }
+
+    let mut open = get_buyer_subscriptions(env.clone(), buyer.clone());
+    if open.len() >= MAX_SUBSCRIPTIONS_PER_BUYER {
+        return Err(MarketError::TooManySubscriptions);
+    }
+
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract(&env)?);
+    if xlm_client.balance(&buyer) < deposit as i128 {
+        return Err(MarketError::BuyerInsufficientFunds);
+    }
+    xlm_client.transfer(&buyer, &env.current_contract_address(), &(deposit as i128));
+    custody::hold(&env, deposit as i128);
+
+    let subscription_id: u32 = storage::get(&env, &DataKey::SubscriptionCounter).unwrap_or(0) + 1;
+
+    let subscription = Subscription {
+        buyer: buyer.clone(),
+        asset_id,
+        token_amount,
+        budget,
+        interval,
+        max_periods,
+        periods_run: 0,
+        next_due_at: env.ledger().timestamp(),
+        escrowed: deposit,
+        bought: 0,
+        spent: 0,
+        skipped: Vec::new(&env),
+        active: true,
+    };
+
+    storage::set(&env, &DataKey::Subscription(subscription_id), &subscription);
+    storage::set(&env, &DataKey::SubscriptionCounter, &subscription_id);
+
+    open.push_back(subscription_id);
+    storage::set(&env, &DataKey::BuyerSubscriptions(buyer), &open);
+
+    events::emit_subscription_created_event(&env, subscription_id, &subscription);
+
+    Ok(subscription_id)
+}
+
+/// Keeper runs the subscription's next due period: buys from the cheapest
+/// offering within the limits, or records the period as skipped when none is
+/// open. A keeper that falls behind catches up one period per call. The
+/// deposit left after the last period goes back to the buyer. Returns the
+/// bounty paid to the keeper.
+pub fn run_subscription(
+    env: Env,
+    keeper: Address,
+    subscription_id: u32,
+) -> Result<i128, MarketError> {
+    keeper.require_auth();
+
+    let mut subscription = get_subscription(env.clone(), subscription_id)?;
+
+    if !subscription.active {
+        return Err(MarketError::SubscriptionInactive);
+    }
+    if env.ledger().timestamp() < subscription.next_due_at {
+        return Err(MarketError::SubscriptionNotDue);
+    }
+
+    let period = subscription.periods_run;
+    let best = offerings::cheapest_offering(
+        &env,
+        &subscription.buyer,
+        subscription.asset_id,
+        subscription.token_amount,
+        Some(subscription.budget),
+    );
+
+    match best {
+        Some((offering_id, offering)) => {
+            // The price leaves the deposit before it is paid out of the contract
+            custody::release(&env, offering.price as i128);
+            let trade_id = offerings::settle_offering_from(
+                env.clone(),
+                subscription.buyer.clone(),
+                &env.current_contract_address(),
+                offering_id,
+                offering.token_amount,
+                offering.price,
+            )?;
+            custody::check_invariants(&env, subscription.asset_id)?;
+
+            subscription.escrowed -= offering.price;
+            subscription.bought += offering.token_amount;
+            subscription.spent += offering.price;
+
+            events::emit_subscription_bought_event(
+                &env,
+                subscription_id,
+                period,
+                trade_id,
+                offering.token_amount,
+                offering.price,
+            );
+        }
+        None => {
+            subscription.skipped.push_back(period);
+            events::emit_subscription_skipped_event(&env, subscription_id, period);
+        }
+    }
+
+    subscription.periods_run += 1;
+    subscription.next_due_at += subscription.interval;
+    if subscription.periods_run >= subscription.max_periods {
+        let refunded = subscription.escrowed;
+        close(&env, subscription_id, &mut subscription)?;
+        events::emit_subscription_finished_event(&env, subscription_id, refunded);
+    }
+    storage::set(&env, &DataKey::Subscription(subscription_id), &subscription);
+
+    // The buyer could otherwise farm bounties from their own subscription
+    if keeper == subscription.buyer {
+        return Ok(0);
+    }
+
+    // Trading pays the bounty from its keeper pool, as for expired sale cleanup
+    Ok(utils::trading(&env)?.pay_keeper(&env.current_contract_address(), &keeper))
+}
+
+/// Buyer stops a subscription and gets the unspent deposit back
+pub fn cancel_subscription(
+    env: Env,
+    buyer: Address,
+    subscription_id: u32,
+) -> Result<(), MarketError> {
+    buyer.require_auth();
+
+    let mut subscription = get_subscription(env.clone(), subscription_id)?;
+    if subscription.buyer != buyer {
+        return Err(MarketError::UnauthorizedBuyer);
+    }
+    if !subscription.active {
+        return Err(MarketError::SubscriptionInactive);
+    }
+
+    let refunded = subscription.escrowed;
+    close(&env, subscription_id, &mut subscription)?;
+    storage::set(&env, &DataKey::Subscription(subscription_id), &subscription);
+
+    events::emit_subscription_cancelled_event(&env, subscription_id, refunded);
+
+    Ok(())
+}
+
+pub fn get_subscription(env: Env, subscription_id: u32) -> Result<Subscription, MarketError> {
+    match storage::get(&env, &DataKey::Subscription(subscription_id)) {
+        Some(subscription) => Ok(subscription),
+        None => Err(MarketError::SubscriptionNotFound),
+    }
+}
+
+/// IDs of a buyer's subscriptions that still hold a deposit
+pub fn get_buyer_subscriptions(env: Env, buyer: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::BuyerSubscriptions(buyer)).unwrap_or(Vec::new(&env))
+}
+
+/// Refund what is left of the deposit and drop the subscription from the
+/// buyer's open list
+fn close(
+    env: &Env,
+    subscription_id: u32,
+    subscription: &mut Subscription,
+) -> Result<(), MarketError> {
+    if subscription.escrowed > 0 {
+        let xlm_client = TokenClient::new(env, &utils::get_xlm_contract(env)?);
+        xlm_client.transfer(
+            &env.current_contract_address(),
+            &subscription.buyer,
+            &(subscription.escrowed as i128),
+        );
+        custody::release(env, subscription.escrowed as i128);
+        subscription.escrowed = 0;
+    }
+    subscription.active = false;
+
+    let mut open = get_buyer_subscriptions(env.clone(), subscription.buyer.clone());
+    if let Some(index) = open.first_index_of(subscription_id) {
+        open.remove(index);
+    }
+    storage::set(
+        env,
+        &DataKey::BuyerSubscriptions(subscription.buyer.clone()),
+        &open,
+    );
+
+    Ok(())
+}
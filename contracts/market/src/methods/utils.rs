@@ -0,0 +1,183 @@
+use crate::errors::MarketError;
+use crate::interfaces::{FNFTClient, TradingClient};
+use crate::storage::{self, DataKey, Offering};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+pub fn require_admin(env: &Env, admin: &Address) -> Result<(), MarketError> {
+    admin.require_auth();
+
+    let stored_admin: Option<Address> = storage::get(env, &DataKey::Admin);
+    match stored_admin {
+        Some(stored) if stored == *admin => Ok(()),
+        Some(_) => Err(MarketError::Unauthorized),
+        None => Err(MarketError::NotInitialized),
+    }
+}
+
+pub fn get_trading_contract(env: &Env) -> Result<Address, MarketError> {
+    match storage::get(env, &DataKey::TradingContract) {
+        Some(address) => Ok(address),
+        None => Err(MarketError::NotInitialized),
+    }
+}
+
+pub fn trading(env: &Env) -> Result<TradingClient<'_>, MarketError> {
+    Ok(TradingClient::new(env, &get_trading_contract(env)?))
+}
+
+pub fn get_fnft_contract(env: &Env) -> Result<Address, MarketError> {
+    match storage::get(env, &DataKey::FNFTContract) {
+        Some(address) => Ok(address),
+        None => Err(MarketError::NotInitialized),
+    }
+}
+
+pub fn get_xlm_contract(env: &Env) -> Result<Address, MarketError> {
+    match storage::get(env, &DataKey::XLMContract) {
+        Some(address) => Ok(address),
+        None => Err(MarketError::NotInitialized),
+    }
+}
+
+/// Fail while trading has the asset paused, or fractcore is paused, like a
+/// trade on the trading contract itself would
+pub fn require_not_paused(env: &Env, asset_id: u64) -> Result<(), MarketError> {
+    let trading = trading(env)?;
+    if trading.is_asset_paused(&asset_id) {
+        return Err(MarketError::AssetPaused);
+    }
+    // An unreadable pause state counts as a pause, as it does on trading
+    match trading.try_get_upstream_pause() {
+        Ok(Ok(None)) => Ok(()),
+        _ => Err(MarketError::UpstreamPaused),
+    }
+}
+
+/// Check a listing lifetime against the asset's sale duration limits on trading
+pub fn require_valid_duration(
+    env: &Env,
+    asset_id: u64,
+    duration_seconds: u64,
+) -> Result<(), MarketError> {
+    let limits = trading(env)?.get_asset_sale_duration_limits(&asset_id);
+    if !(limits.min_seconds..=limits.max_seconds).contains(&duration_seconds) {
+        return Err(MarketError::InvalidDuration);
+    }
+    Ok(())
+}
+
+/// Move `token_amount` fractions from the seller, under its allowance to this
+/// contract, to the buyer, and `price` XLM from `payer` to the seller through
+/// trading, which withholds the fee and routes the rest like any sale. The
+/// parties have been checked by the caller. Returns the trade ID trading
+/// recorded.
+pub fn swap(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    payer: &Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+) -> Result<u32, MarketError> {
+    if price > i128::MAX as u128 {
+        return Err(MarketError::PriceOverflow);
+    }
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(env, &get_fnft_contract(env)?);
+    if fnft_client.balance_of(seller, &asset_id) < token_amount {
+        return Err(MarketError::SellerInsufficientBalance);
+    }
+    if fnft_client.allowance(seller, &contract_id, &asset_id) < token_amount {
+        return Err(MarketError::InsufficientAllowance);
+    }
+
+    let xlm_client = TokenClient::new(env, &get_xlm_contract(env)?);
+    if xlm_client.balance(payer) < price as i128 {
+        return Err(MarketError::BuyerInsufficientFunds);
+    }
+
+    let trading = trading(env)?;
+    trading.draw_exit(&contract_id, seller, &asset_id, &token_amount);
+    fnft_client.transfer_from(&contract_id, seller, buyer, &asset_id, &token_amount);
+
+    if *payer == contract_id {
+        authorize_payment(env, &xlm_client.address, &trading.address, price as i128);
+    }
+    trading.pay_seller(&contract_id, payer, seller, &asset_id, &(price as i128));
+
+    Ok(trading.record_trade(
+        &contract_id,
+        seller,
+        buyer,
+        &asset_id,
+        &token_amount,
+        &price,
+    ))
+}
+
+/// Let trading take `amount` of this contract's XLM in the `pay_seller` call
+/// that follows, the one transfer that call makes from its payer
+pub fn authorize_payment(env: &Env, xlm_contract: &Address, trading: &Address, amount: i128) {
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: xlm_contract.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (env.current_contract_address(), trading.clone(), amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+}
+
+pub fn get_offering(env: &Env, offering_id: u32) -> Result<Offering, MarketError> {
+    match storage::get(env, &DataKey::Offering(offering_id)) {
+        Some(offering) => Ok(offering),
+        None => Err(MarketError::OfferingNotFound),
+    }
+}
+
+pub fn add_to_seller_offerings(env: &Env, seller: Address, offering_id: u32) {
+    let mut offerings: Vec<u32> =
+        storage::get(env, &DataKey::SellerOfferings(seller.clone())).unwrap_or(Vec::new(env));
+
+    offerings.push_back(offering_id);
+    storage::set(env, &DataKey::SellerOfferings(seller), &offerings);
+}
+
+pub fn remove_from_seller_offerings(env: &Env, seller: Address, offering_id: u32) {
+    let mut offerings: Vec<u32> =
+        storage::get(env, &DataKey::SellerOfferings(seller.clone())).unwrap_or(Vec::new(env));
+
+    if let Some(index) = offerings.first_index_of(offering_id) {
+        offerings.remove(index);
+    }
+    storage::set(env, &DataKey::SellerOfferings(seller), &offerings);
+}
+
+pub fn add_to_asset_offerings(env: &Env, asset_id: u64, offering_id: u32) {
+    let mut offerings = get_asset_offerings(env, asset_id);
+
+    offerings.push_back(offering_id);
+    storage::set(env, &DataKey::AssetOfferings(asset_id), &offerings);
+}
+
+pub fn remove_from_asset_offerings(env: &Env, asset_id: u64, offering_id: u32) {
+    let mut offerings = get_asset_offerings(env, asset_id);
+
+    if let Some(index) = offerings.first_index_of(offering_id) {
+        offerings.remove(index);
+    }
+    storage::set(env, &DataKey::AssetOfferings(asset_id), &offerings);
+}
+
+pub fn get_asset_offerings(env: &Env, asset_id: u64) -> Vec<u32> {
+    storage::get(env, &DataKey::AssetOfferings(asset_id)).unwrap_or(Vec::new(env))
+}
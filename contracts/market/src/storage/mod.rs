@@ -0,0 +1,276 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// The market keeps nothing in temporary storage: offerings hold allowance and
+// buyer offers and subscriptions hold XLM until they are closed.
+pub use paging::Cursor;
+pub use placement::*;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OfferingPhase {
+    Reserved, // Only the preferred buyer can fill
+    Public,   // Anyone can fill
+    Expired,
+}
+
+// Listing that gives one buyer the right of first refusal before opening to everyone
+#[contracttype]
+#[derive(Clone)]
+pub struct Offering {
+    pub seller: Address,
+    pub preferred_buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub created_at: u64,
+    pub reserved_until: u64, // End of the preferred buyer's exclusive window
+    pub expires_at: u64,
+    pub opened: bool, // Opening event already emitted
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BuyerOfferStatus {
+    Open,      // XLM held by the contract, any holder can accept until expiry
+    Accepted,  // Filled by a seller
+    Cancelled, // Withdrawn by the buyer, XLM returned
+    Refunded,  // Expired and returned by refund_expired_offers
+}
+
+// Buyer bid for fractions of an asset; the price is escrowed when it is made
+#[contracttype]
+#[derive(Clone)]
+pub struct BuyerOffer {
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub status: BuyerOfferStatus,
+}
+
+// One investor's leg of a primary sale: fractions of the new asset for an XLM price
+#[contracttype]
+#[derive(Clone)]
+pub struct PrimaryAllocation {
+    pub investor: Address,
+    pub token_amount: u64,
+    pub price: u128,
+}
+
+// Buyer's standing order to buy fractions of an asset from open offerings every
+// interval, paid from XLM deposited up front
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: Option<u64>, // Lot size to buy; None buys any size within the budget
+    pub budget: u128,              // Most XLM spent in one period
+    pub interval: u64,
+    pub max_periods: u32,
+    pub periods_run: u32,  // Periods bought or skipped so far
+    pub next_due_at: u64,  // Earliest time a keeper can run the next period
+    pub escrowed: u128,    // Deposit not spent or refunded yet
+    pub bought: u64,       // Fractions bought so far
+    pub spent: u128,       // XLM paid for them
+    pub skipped: Vec<u32>, // Periods with no offering within the limits
+    pub active: bool,
+}
+
+// What finish_best_execution does when a cheaper offering is fillable
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExecutionPolicy {
+    Warn,  // Settle the proposal and report the cheaper offering
+    Abort, // Fail instead of overpaying
+    Route, // Fill the cheaper offering when it is for the same token amount
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BestExecutionReport {
+    pub trade_id: u32,
+    pub best_offering: Option<u32>, // Cheapest offering the buyer could fill, if cheaper
+    pub routed: bool,               // The purchase went to best_offering instead
+}
+
+// One order a market order filled, at the order's own terms
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketFill {
+    pub order_id: u32, // Offering for market_buy, buyer offer for market_sell
+    pub trade_id: u32,
+    pub token_amount: u64,
+    pub price: u128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketExecutionReport {
+    pub fills: Vec<MarketFill>, // Best price per fraction first
+    pub token_amount: u64,      // Fractions bought or sold
+    pub total: u128,            // XLM paid or received, before the trading fee
+}
+
+// Basket value at the last traded prices of its components
+#[contracttype]
+#[derive(Clone)]
+pub struct BasketValuation {
+    pub total_value: u128,   // XLM value of everything backing the basket
+    pub supply: u64,         // Basket tokens outstanding
+    pub nav_per_token: u128, // total_value / supply, rounded down
+}
+
+// One offering reserved against a seller's allowance to this contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Commitment {
+    pub counterparty: Address, // The offering's preferred buyer
+    pub id: u32,               // Offering id
+    pub token_amount: u64,
+    pub expires_at: u64,
+}
+
+// Seller's allowance to this contract set against the offerings it backs
+#[contracttype]
+#[derive(Clone)]
+pub struct SellerCommitments {
+    pub commitments: Vec<Commitment>,
+    pub total_committed: u64,
+    pub allowance: u64,
+    pub uncommitted: u64, // Allowance no offering accounts for (orphaned)
+    pub shortfall: u64,   // Commitments the allowance no longer covers
+}
+
+// Fractcore's `BasketComponent`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct BasketComponent {
+    pub asset_id: u64,
+    pub weight: u64,
+}
+
+// Trading's `SaleProposal`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct SaleProposal {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub is_active: bool,
+    pub timestamp: u64,
+    pub expires_at: u64,
+    pub arbiter: Option<Address>,
+}
+
+// Trading's `TradeHistory`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct TradeHistory {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub timestamp: u64,
+}
+
+// Trading's `SaleDurationLimits`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleDurationLimits {
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+// Trading's `UpstreamPause`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpstreamPause {
+    pub since: u64,
+    pub retry_after: u64,
+}
+
+// Trading's `Conduct`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Conduct {
+    Cancellation,
+    Expiry,
+    Dispute,
+}
+
+// Storage keys for market contract
+#[contracttype(export = false)]
+pub enum DataKey {
+    // Core contract data
+    Admin,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
+    TradingContract,
+    FNFTContract, // Read from the trading contract at initialization
+    XLMContract,  // Read from the trading contract at initialization
+
+    // Offerings with a preferred buyer window
+    OfferingCounter,
+    Offering(u32),            // offering_id -> Offering
+    SellerOfferings(Address), // seller -> Vec<u32> (live offering_ids)
+    AssetOfferings(u64),      // asset_id -> Vec<u32> (live offering_ids)
+
+    // Buyer offers with escrowed XLM
+    BuyerOfferCounter,
+    BuyerOffer(u32),          // offer_id -> BuyerOffer
+    OpenBuyerOffers(Address), // buyer -> Vec<u32> (offer_ids whose XLM is still held)
+    OfferRefundCursor,        // First offer id refund_expired_offers checks
+
+    // Recurring purchases
+    SubscriptionCounter,
+    Subscription(u32),           // subscription_id -> Subscription
+    BuyerSubscriptions(Address), // buyer -> Vec<u32> (subscription_ids still holding a deposit)
+
+    // Custody
+    HeldXlm, // XLM held for buyer offers and subscriptions
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("market");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::TradingContract
+            | DataKey::FNFTContract
+            | DataKey::XLMContract
+            | DataKey::OfferingCounter
+            | DataKey::BuyerOfferCounter
+            | DataKey::OfferRefundCursor
+            | DataKey::SubscriptionCounter
+            | DataKey::HeldXlm => Placement::Instance,
+            DataKey::Offering(..)
+            | DataKey::SellerOfferings(..)
+            | DataKey::AssetOfferings(..)
+            | DataKey::BuyerOffer(..)
+            | DataKey::OpenBuyerOffers(..)
+            | DataKey::Subscription(..)
+            | DataKey::BuyerSubscriptions(..) => Placement::Persistent,
+        }
+    }
+}
+
+// Constants
+pub const MAX_BEST_EXECUTION_SCAN: u32 = 25; // Newest offerings compared per settlement
+pub const MAX_MARKET_FILLS: u32 = 10; // Orders one market_buy or market_sell fills
+pub const MAX_MARKET_BID_SCAN: u32 = 50; // Newest buyer offers market_sell compares
+pub const MAX_DISCOVERY_SCAN: u32 = 100; // Offering ids discover_offerings examines per call
+pub const MAX_PAGE_SIZE: u32 = 50; // Offering ids served by one paged call
+pub const MAX_OPEN_OFFERS_PER_BUYER: u32 = 20;
+pub const MAX_OFFER_REFUND_BATCH: u32 = 50; // Offers checked per refund_expired_offers call
+pub const MAX_PRIMARY_ALLOCATIONS: u32 = 25; // Investors in one primary sale
+pub const MAX_SUBSCRIPTIONS_PER_BUYER: u32 = 10;
+pub const MAX_SUBSCRIPTION_PERIODS: u32 = 60; // Five years of monthly purchases
+pub const MIN_SUBSCRIPTION_INTERVAL: u64 = 86400; // 1 day between purchases
@@ -0,0 +1,1075 @@
+#![cfg(test)]
+
+use crate::contract::*;
+use crate::errors::MarketError;
+use crate::storage::{self, DataKey, ExecutionPolicy, OfferingPhase, PrimaryAllocation};
+use pricing::MAX_PRICE_DECIMALS;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token, Address, Env, IntoVal, Val, Vec,
+};
+
+// Import the FNFT and trading contracts for testing
+mod fnft {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+mod trading {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/trading.wasm");
+}
+
+const DAY: u64 = 86400;
+const DEFAULT_SALE_DURATION: u64 = 7 * DAY;
+
+struct MarketSetup {
+    env: Env,
+    admin: Address,
+    market_client: MarketContractClient<'static>,
+    trading_client: trading::Client<'static>,
+    fnft_client: fnft::Client<'static>,
+    xlm_client: token::Client<'static>,
+}
+
+fn setup() -> MarketSetup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
+    let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
+
+    let xlm_sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let xlm_contract_id = xlm_sac.address();
+
+    let trading_contract_id = env.register(
+        trading::WASM,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            xlm_contract_id.clone(),
+        ),
+    );
+    let trading_client = trading::Client::new(&env, &trading_contract_id);
+
+    let market_contract_id =
+        env.register(MarketContract, (admin.clone(), trading_contract_id.clone()));
+    let market_client = MarketContractClient::new(&env, &market_contract_id);
+    trading_client.set_extension(&admin, &market_contract_id, &true);
+
+    let xlm_client = token::Client::new(&env, &xlm_contract_id);
+
+    MarketSetup {
+        env,
+        admin,
+        market_client,
+        trading_client,
+        fnft_client,
+        xlm_client,
+    }
+}
+
+impl MarketSetup {
+    fn account(&self, xlm: i128) -> Address {
+        let address = Address::generate(&self.env);
+        if xlm > 0 {
+            token::StellarAssetClient::new(&self.env, &self.xlm_client.address)
+                .mint(&address, &xlm);
+        }
+        address
+    }
+
+    fn advance_time(&self, seconds: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
+
+    /// List a direct sale on trading. A WASM build of trading authorizes the
+    /// seller for both the listing and the nested approve in one frame, which
+    /// `mock_all_auths` refuses, so the two authorizations are mocked explicitly.
+    fn confirm_sale(
+        &self,
+        seller: &Address,
+        buyer: &Address,
+        asset_id: u64,
+        amount: u64,
+        price: u128,
+    ) {
+        let trading = &self.trading_client.address;
+        let fnft = &self.fnft_client.address;
+        let approve: Vec<Val> =
+            (seller.clone(), trading.clone(), asset_id, amount).into_val(&self.env);
+        self.env.mock_auths(&[
+            MockAuth {
+                address: seller,
+                invoke: &MockAuthInvoke {
+                    contract: trading,
+                    fn_name: "confirm_sale",
+                    args: (
+                        seller.clone(),
+                        buyer.clone(),
+                        asset_id,
+                        amount,
+                        price,
+                        DEFAULT_SALE_DURATION,
+                    )
+                        .into_val(&self.env),
+                    sub_invokes: &[MockAuthInvoke {
+                        contract: fnft,
+                        fn_name: "approve",
+                        args: approve.clone(),
+                        sub_invokes: &[],
+                    }],
+                },
+            },
+            MockAuth {
+                address: seller,
+                invoke: &MockAuthInvoke {
+                    contract: trading,
+                    fn_name: "confirm_sale",
+                    args: (fnft.clone(), symbol_short!("approve"), approve).into_val(&self.env),
+                    sub_invokes: &[],
+                },
+            },
+        ]);
+        self.trading_client.confirm_sale(
+            seller,
+            buyer,
+            &asset_id,
+            &amount,
+            &price,
+            &DEFAULT_SALE_DURATION,
+        );
+        self.env.mock_all_auths();
+    }
+
+    /// Offering reserved for a fresh address for an hour, public for the next
+    fn public_offering(&self, seller: &Address, asset_id: u64, amount: u64, price: u128) -> u32 {
+        self.market_client.create_offering(
+            seller,
+            &Address::generate(&self.env),
+            &asset_id,
+            &amount,
+            &price,
+            &3600,
+            &7200,
+        )
+    }
+}
+
+#[test]
+fn test_initialization_reads_trading_configuration() {
+    let s = setup();
+
+    assert_eq!(
+        s.market_client.get_trading_contract(),
+        s.trading_client.address
+    );
+    assert!(s.market_client.get_init_config_hash().is_some());
+}
+
+#[test]
+fn test_unregistered_market_cannot_settle() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &buyer, &asset_id, &100, &5000, &3600, &7200);
+
+    s.trading_client
+        .set_extension(&s.admin, &s.market_client.address, &false);
+    assert_eq!(
+        s.market_client
+            .try_fill_offering(&buyer, &offering_id, &100, &5000),
+        Err(Ok(MarketError::Unauthorized))
+    );
+    assert_eq!(s.xlm_client.balance(&buyer), 10000);
+}
+
+// === Offering Tests ===
+
+#[test]
+fn test_offering_reserved_for_preferred_buyer() {
+    let s = setup();
+    let seller = s.account(0);
+    let co_owner = s.account(10000);
+    let outsider = s.account(10000);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5000, &3600, &7200);
+    assert_eq!(
+        s.market_client.get_offering_phase(&offering_id),
+        OfferingPhase::Reserved
+    );
+    assert_eq!(
+        s.fnft_client
+            .allowance(&seller, &s.market_client.address, &asset_id),
+        100
+    );
+
+    // Outsiders cannot fill, and the offering cannot be opened early
+    assert_eq!(
+        s.market_client
+            .try_fill_offering(&outsider, &offering_id, &100, &5000),
+        Err(Ok(MarketError::UnauthorizedBuyer))
+    );
+    assert_eq!(
+        s.market_client.try_open_offering(&offering_id),
+        Err(Ok(MarketError::ReservationActive))
+    );
+
+    let trade_id = s
+        .market_client
+        .fill_offering(&co_owner, &offering_id, &100, &5000);
+
+    assert_eq!(
+        s.trading_client.get_trade_history(&trade_id).buyer,
+        co_owner
+    );
+    assert_eq!(s.fnft_client.balance_of(&co_owner, &asset_id), 100);
+    assert_eq!(s.xlm_client.balance(&seller), 5000);
+    assert!(s.market_client.get_seller_offerings(&seller).is_empty());
+    assert!(s.market_client.try_get_offering(&offering_id).is_err());
+}
+
+#[test]
+fn test_offering_opens_to_everyone_after_window() {
+    let s = setup();
+    let seller = s.account(0);
+    let co_owner = s.account(0);
+    let outsider = s.account(10000);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5000, &3600, &7200);
+
+    s.advance_time(3601);
+    assert_eq!(
+        s.market_client.get_offering_phase(&offering_id),
+        OfferingPhase::Public
+    );
+
+    // Opening is recorded once
+    s.market_client.open_offering(&offering_id);
+    assert!(s.market_client.get_offering(&offering_id).opened);
+    s.market_client.open_offering(&offering_id);
+    assert!(s.env.events().all().is_empty());
+
+    s.market_client
+        .fill_offering(&outsider, &offering_id, &100, &5000);
+    assert_eq!(s.fnft_client.balance_of(&outsider, &asset_id), 100);
+}
+
+#[test]
+fn test_offering_expiry_and_withdrawal() {
+    let s = setup();
+    let seller = s.account(0);
+    let co_owner = s.account(10000);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    // The reservation must leave room for a public phase
+    assert_eq!(
+        s.market_client
+            .try_create_offering(&seller, &co_owner, &asset_id, &100, &5000, &7200, &7200),
+        Err(Ok(MarketError::InvalidDuration))
+    );
+
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5000, &3600, &7200);
+
+    s.advance_time(7201);
+    assert_eq!(
+        s.market_client.get_offering_phase(&offering_id),
+        OfferingPhase::Expired
+    );
+    assert_eq!(
+        s.market_client
+            .try_fill_offering(&co_owner, &offering_id, &100, &5000),
+        Err(Ok(MarketError::SaleExpired))
+    );
+
+    assert_eq!(
+        s.market_client
+            .try_withdraw_offering(&co_owner, &offering_id),
+        Err(Ok(MarketError::UnauthorizedSeller))
+    );
+    s.market_client.withdraw_offering(&seller, &offering_id);
+    assert_eq!(
+        s.fnft_client
+            .allowance(&seller, &s.market_client.address, &asset_id),
+        0
+    );
+    assert!(s.market_client.get_seller_offerings(&seller).is_empty());
+    assert_eq!(
+        s.trading_client.get_reputation(&seller).cancelled_proposals,
+        1
+    );
+}
+
+#[test]
+fn test_offerings_follow_trading_eligibility_and_pauses() {
+    let s = setup();
+    let issuer = s.account(0);
+    let seller = s.account(0);
+    let co_owner = s.account(10000);
+
+    let asset_id = s.fnft_client.mint_for(&issuer, &issuer, &1000);
+    s.fnft_client.transfer(&issuer, &seller, &asset_id, &200);
+    s.trading_client.set_buyer_eligibility(
+        &seller,
+        &asset_id,
+        &Some(trading::BuyerEligibility {
+            min_holding: 0,
+            allowlist_only: true,
+        }),
+    );
+
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5000, &3600, &7200);
+    assert_eq!(
+        s.market_client
+            .try_fill_offering(&co_owner, &offering_id, &100, &5000),
+        Err(Ok(MarketError::BuyerNotEligible))
+    );
+
+    // Pausing the asset on trading stops new listings here too
+    s.trading_client
+        .set_asset_allowlisted(&issuer, &asset_id, &co_owner, &true);
+    s.trading_client
+        .set_asset_paused(&s.admin, &asset_id, &true);
+    assert_eq!(
+        s.market_client
+            .try_make_offer(&co_owner, &asset_id, &10, &100, &3600),
+        Err(Ok(MarketError::AssetPaused))
+    );
+    assert_eq!(
+        s.market_client
+            .try_fill_offering(&co_owner, &offering_id, &100, &5000),
+        Err(Ok(MarketError::AssetPaused))
+    );
+
+    s.trading_client
+        .set_asset_paused(&s.admin, &asset_id, &false);
+    s.market_client
+        .fill_offering(&co_owner, &offering_id, &100, &5000);
+    assert_eq!(s.fnft_client.balance_of(&co_owner, &asset_id), 100);
+}
+
+#[test]
+fn test_discover_offerings_filters_by_category() {
+    let s = setup();
+    let seller = s.account(0);
+    let art = symbol_short!("Art");
+    let art_asset = s.fnft_client.mint_with_category(&seller, &1000, &art);
+    let music_asset = s
+        .fnft_client
+        .mint_with_category(&seller, &1000, &symbol_short!("Music"));
+
+    let offer = |asset_id: u64, expires_in: u64| {
+        s.market_client.create_offering(
+            &seller,
+            &Address::generate(&s.env),
+            &asset_id,
+            &10,
+            &1000,
+            &(expires_in / 2),
+            &expires_in,
+        )
+    };
+    let first_art = offer(art_asset, 7200);
+    let music = offer(music_asset, 7200);
+    let second_art = offer(art_asset, 7200);
+    offer(art_asset, 3600);
+    s.advance_time(3601);
+
+    let (all, next) = s.market_client.discover_offerings(&None, &None, &0);
+    assert_eq!(all, Vec::from_array(&s.env, [first_art, music, second_art]));
+    assert_eq!(next, None);
+
+    let (page, next) = s
+        .market_client
+        .discover_offerings(&Some(art.clone()), &None, &1);
+    assert_eq!(page, Vec::from_array(&s.env, [first_art]));
+    let (page, next) = s.market_client.discover_offerings(&Some(art), &next, &1);
+    assert_eq!(page, Vec::from_array(&s.env, [second_art]));
+    assert!(next.is_some());
+}
+
+#[test]
+fn test_cursors_walk_offerings_while_they_change() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(0);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offering_ids = [0; 3].map(|_| {
+        s.market_client
+            .create_offering(&seller, &buyer, &asset_id, &10, &100, &3600, &7200)
+    });
+
+    let (first, cursor) = s
+        .market_client
+        .get_asset_offerings_page(&asset_id, &None, &2);
+    assert_eq!(
+        first,
+        Vec::from_array(&s.env, [offering_ids[0], offering_ids[1]])
+    );
+
+    // Withdrawing a listed offering does not shift the walk, and offerings created
+    // after it started wait for the next walk
+    s.market_client.withdraw_offering(&seller, &offering_ids[0]);
+    let newer = s
+        .market_client
+        .create_offering(&seller, &buyer, &asset_id, &10, &100, &3600, &7200);
+    let (rest, cursor) = s
+        .market_client
+        .get_asset_offerings_page(&asset_id, &cursor, &2);
+    assert_eq!(rest, Vec::from_array(&s.env, [offering_ids[2]]));
+    assert_eq!(cursor, None);
+    let (all, _) = s
+        .market_client
+        .get_asset_offerings_page(&asset_id, &None, &0);
+    assert_eq!(
+        all,
+        Vec::from_array(&s.env, [offering_ids[1], offering_ids[2], newer])
+    );
+}
+
+#[test]
+fn test_seller_commitments_account_for_allowance() {
+    let s = setup();
+    let seller = s.account(0);
+    let co_owner = s.account(0);
+    let market_id = s.market_client.address.clone();
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &50, &2500, &3600, &7200);
+
+    let view = s.market_client.get_seller_commitments(&seller, &asset_id);
+    assert_eq!(view.commitments.len(), 1);
+    let offering = view.commitments.get(0).unwrap();
+    assert_eq!(offering.id, offering_id);
+    assert_eq!(offering.counterparty, co_owner);
+    assert_eq!(view.total_committed, 50);
+    assert_eq!(view.allowance, 50);
+    assert_eq!(view.uncommitted, 0);
+
+    // Allowance granted outside the market flow shows up as orphaned
+    s.fnft_client.approve(&seller, &market_id, &asset_id, &400);
+    let view = s.market_client.get_seller_commitments(&seller, &asset_id);
+    assert_eq!(view.uncommitted, 350);
+
+    // A reset leaves the offering uncovered
+    s.fnft_client.approve(&seller, &market_id, &asset_id, &0);
+    let view = s.market_client.get_seller_commitments(&seller, &asset_id);
+    assert_eq!(view.allowance, 0);
+    assert_eq!(view.shortfall, 50);
+}
+
+// === Baskets ===
+
+#[test]
+fn test_basket_nav_from_last_trades() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(100000);
+    let gold = s.fnft_client.mint(&seller, &1000);
+    let land = s.fnft_client.mint(&seller, &1000);
+
+    let basket_id = s.fnft_client.create_basket(&soroban_sdk::vec![
+        &s.env,
+        fnft::BasketComponent {
+            asset_id: gold,
+            weight: 2,
+        },
+        fnft::BasketComponent {
+            asset_id: land,
+            weight: 1,
+        },
+    ]);
+    s.fnft_client.deposit_to_basket(&seller, &basket_id, &10);
+
+    // Land has never traded, so the basket cannot be valued yet
+    s.confirm_sale(&seller, &buyer, gold, 100, 5000);
+    s.trading_client
+        .finish_transaction(&buyer, &seller, &gold, &100, &5000);
+    assert_eq!(
+        s.market_client.try_basket_nav(&basket_id).err(),
+        Some(Ok(MarketError::NoPriceData))
+    );
+
+    s.confirm_sale(&seller, &buyer, land, 10, 2000);
+    s.trading_client
+        .finish_transaction(&buyer, &seller, &land, &10, &2000);
+
+    // 20 gold at 50 + 10 land at 200 backs 10 basket tokens
+    let valuation = s.market_client.basket_nav(&basket_id);
+    assert_eq!(valuation.total_value, 3000);
+    assert_eq!(valuation.supply, 10);
+    assert_eq!(valuation.nav_per_token, 300);
+
+    assert_eq!(
+        s.market_client.try_basket_nav(&gold).err(),
+        Some(Ok(MarketError::NotABasket))
+    );
+}
+
+#[test]
+fn test_buy_and_stake_deposits_purchase_into_basket() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let gold = s.fnft_client.mint(&seller, &1000);
+    let land = s.fnft_client.mint(&seller, &1000);
+
+    let basket_id = s.fnft_client.create_basket(&soroban_sdk::vec![
+        &s.env,
+        fnft::BasketComponent {
+            asset_id: gold,
+            weight: 2,
+        },
+    ]);
+    let offering_id = s
+        .market_client
+        .create_offering(&seller, &buyer, &gold, &100, &5000, &3600, &7200);
+
+    // The basket must hold the offered asset
+    let land_basket = s.fnft_client.create_basket(&soroban_sdk::vec![
+        &s.env,
+        fnft::BasketComponent {
+            asset_id: land,
+            weight: 1,
+        },
+    ]);
+    assert_eq!(
+        s.market_client
+            .try_buy_and_stake(&buyer, &offering_id, &100, &5000, &land_basket, &50),
+        Err(Ok(MarketError::NotABasket))
+    );
+
+    let trade_id =
+        s.market_client
+            .buy_and_stake(&buyer, &offering_id, &100, &5000, &basket_id, &50);
+
+    assert_eq!(s.trading_client.get_trade_history(&trade_id).buyer, buyer);
+    assert_eq!(s.xlm_client.balance(&seller), 5000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &gold), 0);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &basket_id), 50);
+    assert_eq!(s.fnft_client.basket_holdings(&basket_id).get(0), Some(100));
+}
+
+// === Execution ===
+
+#[test]
+fn test_best_execution_against_public_offerings() {
+    let s = setup();
+    let seller = s.account(0);
+    let rival = s.account(0);
+    let buyer = s.account(20000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    s.fnft_client.transfer(&seller, &rival, &asset_id, &500);
+
+    // 20 per fraction against the proposal's 50, but for a different amount
+    let small = s.public_offering(&rival, asset_id, 50, 1000);
+    let same_size = s.public_offering(&rival, asset_id, 100, 4000);
+    s.advance_time(3601);
+
+    s.confirm_sale(&seller, &buyer, asset_id, 100, 5000);
+    assert_eq!(
+        s.market_client
+            .try_finish_best_execution(
+                &buyer,
+                &seller,
+                &asset_id,
+                &100,
+                &5000,
+                &ExecutionPolicy::Abort
+            )
+            .err(),
+        Some(Ok(MarketError::PriceMismatch))
+    );
+    assert_eq!(
+        s.market_client
+            .try_finish_best_execution(
+                &buyer,
+                &rival,
+                &asset_id,
+                &100,
+                &5000,
+                &ExecutionPolicy::Warn
+            )
+            .err(),
+        Some(Ok(MarketError::SaleNotFound))
+    );
+
+    // Routing fills the cheapest offering of the same size and leaves the proposal open
+    let report = s.market_client.finish_best_execution(
+        &buyer,
+        &seller,
+        &asset_id,
+        &100,
+        &5000,
+        &ExecutionPolicy::Route,
+    );
+    assert!(report.routed);
+    assert_eq!(report.best_offering, Some(same_size));
+    assert_eq!(s.xlm_client.balance(&rival), 4000);
+    assert!(s.trading_client.sale_exists(&seller, &buyer, &asset_id));
+
+    // Nothing left to route to: the proposal settles and the cheaper offering is reported
+    let report = s.market_client.finish_best_execution(
+        &buyer,
+        &seller,
+        &asset_id,
+        &100,
+        &5000,
+        &ExecutionPolicy::Route,
+    );
+    assert!(!report.routed);
+    assert_eq!(report.best_offering, Some(small));
+    assert_eq!(
+        s.trading_client.get_trade_history(&report.trade_id).seller,
+        seller
+    );
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 200);
+}
+
+#[test]
+fn test_market_orders_fill_best_prices_first() {
+    let s = setup();
+    let holder = s.account(0);
+    let rival = s.account(0);
+    let buyer = s.account(20000);
+    let bidder = s.account(9000);
+    let asset_id = s.fnft_client.mint(&holder, &1000);
+    s.fnft_client.transfer(&holder, &rival, &asset_id, &300);
+
+    // 20, 40 and 60 per fraction
+    let cheapest = s.public_offering(&rival, asset_id, 50, 1000);
+    let middle = s.public_offering(&holder, asset_id, 100, 4000);
+    s.public_offering(&rival, asset_id, 100, 6000);
+    s.advance_time(3601);
+
+    // The 60-per-fraction offering no longer fits the 500 left
+    let report = s.market_client.market_buy(&buyer, &asset_id, &5500);
+    assert_eq!(report.fills.len(), 2);
+    assert_eq!(report.fills.get(0).unwrap().order_id, cheapest);
+    assert_eq!(report.fills.get(1).unwrap().order_id, middle);
+    assert_eq!(report.token_amount, 150);
+    assert_eq!(report.total, 5000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 150);
+    assert_eq!(s.xlm_client.balance(&buyer), 15000);
+    assert_eq!(
+        s.market_client
+            .try_market_buy(&buyer, &asset_id, &100)
+            .err(),
+        Some(Ok(MarketError::OfferingNotFound))
+    );
+
+    // 30, 40 and 50 per fraction
+    s.market_client
+        .make_offer(&bidder, &asset_id, &100, &3000, &3600);
+    let smaller = s
+        .market_client
+        .make_offer(&bidder, &asset_id, &50, &2000, &3600);
+    let best = s
+        .market_client
+        .make_offer(&bidder, &asset_id, &80, &4000, &3600);
+
+    // The 100-fraction offer is larger than the 20 left to sell, and the slippage
+    // bound rejects the whole order when the fills pay too little
+    assert_eq!(
+        s.market_client
+            .try_market_sell(&holder, &asset_id, &150, &6001)
+            .err(),
+        Some(Ok(MarketError::PriceMismatch))
+    );
+    assert_eq!(s.market_client.get_open_offers(&bidder).len(), 3);
+
+    let report = s.market_client.market_sell(&holder, &asset_id, &150, &6000);
+    assert_eq!(report.fills.len(), 2);
+    assert_eq!(report.fills.get(0).unwrap().order_id, best);
+    assert_eq!(report.fills.get(1).unwrap().order_id, smaller);
+    assert_eq!(report.token_amount, 130);
+    assert_eq!(report.total, 6000);
+    assert_eq!(s.fnft_client.balance_of(&bidder, &asset_id), 130);
+    assert_eq!(s.fnft_client.balance_of(&holder, &asset_id), 470);
+    assert_eq!(s.xlm_client.balance(&holder), 10000);
+    assert_eq!(
+        s.market_client
+            .try_market_sell(&holder, &asset_id, &20, &0)
+            .err(),
+        Some(Ok(MarketError::SaleNotFound))
+    );
+}
+
+#[test]
+fn test_price_helpers_round_in_the_sellers_favour() {
+    let s = setup();
+    let market = &s.market_client;
+
+    // 3 fractions for 10 stroops: 3.3333333 per fraction at 7 decimals
+    let unit = market.price_per_token_from_total(&10, &3, &7);
+    assert_eq!(unit, 33_333_333);
+    assert_eq!(market.price_per_token_from_total(&10, &3, &0), 3);
+    // Quoting the rounded-down unit price never exceeds the original total
+    assert_eq!(market.quote_total(&unit, &3, &7), 10);
+    assert_eq!(market.quote_total(&33_333_334, &3, &7), 11);
+    assert_eq!(market.quote_total(&5, &100, &0), 500);
+
+    assert_eq!(
+        market.try_price_per_token_from_total(&10, &0, &7),
+        Err(Ok(MarketError::InvalidTokenAmount))
+    );
+    assert_eq!(
+        market.try_quote_total(&1, &1, &(MAX_PRICE_DECIMALS + 1)),
+        Err(Ok(MarketError::InvalidPrice))
+    );
+    // Totals must stay payable as i128 amounts
+    assert_eq!(
+        market.try_quote_total(&(i128::MAX as u128), &2, &0),
+        Err(Ok(MarketError::PriceOverflow))
+    );
+    assert_eq!(
+        market.try_price_per_token_from_total(&u128::MAX, &1, &1),
+        Err(Ok(MarketError::PriceOverflow))
+    );
+    assert_eq!(
+        market.quote_total(&u64::MAX.into(), &u64::MAX, &18),
+        u64::MAX as u128 * u64::MAX as u128 / 10u128.pow(18) + 1
+    );
+}
+
+// === Buyer Offers ===
+
+#[test]
+fn test_buyer_offers_escrow_and_refund_sweep() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let short = s
+        .market_client
+        .make_offer(&buyer, &asset_id, &100, &1000, &3600);
+    let long = s
+        .market_client
+        .make_offer(&buyer, &asset_id, &100, &2000, &DEFAULT_SALE_DURATION);
+    let filled = s
+        .market_client
+        .make_offer(&buyer, &asset_id, &50, &3000, &3600);
+    assert_eq!(s.xlm_client.balance(&buyer), 4000);
+    assert_eq!(s.market_client.get_held_xlm(), 6000);
+
+    assert_eq!(
+        s.market_client
+            .try_accept_offer(&seller, &filled, &50, &2999)
+            .err(),
+        Some(Ok(MarketError::PriceMismatch))
+    );
+    let trade_id = s.market_client.accept_offer(&seller, &filled, &50, &3000);
+    assert_eq!(s.trading_client.get_trade_history(&trade_id).buyer, buyer);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 50);
+    assert_eq!(s.xlm_client.balance(&seller), 3000);
+    assert_eq!(s.market_client.get_open_offers(&buyer).len(), 2);
+
+    // Once the short offer expires it can no longer be accepted, only refunded
+    s.advance_time(7200);
+    assert_eq!(
+        s.market_client
+            .try_accept_offer(&seller, &short, &100, &1000)
+            .err(),
+        Some(Ok(MarketError::SaleExpired))
+    );
+    assert_eq!(s.market_client.get_refundable_amount(&buyer), 1000);
+
+    assert_eq!(s.market_client.refund_expired_offers(&10), 1);
+    assert_eq!(s.xlm_client.balance(&buyer), 5000);
+    assert_eq!(s.market_client.get_refundable_amount(&buyer), 0);
+    // The cursor waits at the long offer, which is still open
+    assert_eq!(s.market_client.get_refund_cursor(), long);
+    assert_eq!(s.market_client.refund_expired_offers(&10), 0);
+
+    s.market_client.cancel_offer(&buyer, &long);
+    assert_eq!(s.xlm_client.balance(&buyer), 7000);
+    assert_eq!(
+        s.market_client.try_cancel_offer(&buyer, &long).err(),
+        Some(Ok(MarketError::SaleNotActive))
+    );
+    s.market_client.refund_expired_offers(&10);
+    assert_eq!(s.market_client.get_refund_cursor(), filled + 1);
+    assert_eq!(s.market_client.get_held_xlm(), 0);
+    assert_eq!(s.xlm_client.balance(&s.market_client.address), 0);
+    assert!(s.market_client.get_open_offers(&buyer).is_empty());
+}
+
+#[test]
+fn test_open_buyer_offers_are_capped() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(100000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    for _ in 0..storage::MAX_OPEN_OFFERS_PER_BUYER {
+        s.market_client
+            .make_offer(&buyer, &asset_id, &1, &10, &3600);
+    }
+    assert_eq!(
+        s.market_client
+            .try_make_offer(&buyer, &asset_id, &1, &10, &3600)
+            .err(),
+        Some(Ok(MarketError::TooManyOffers))
+    );
+
+    // Closing one frees a slot
+    s.market_client.cancel_offer(&buyer, &1);
+    s.market_client
+        .make_offer(&buyer, &asset_id, &1, &10, &3600);
+}
+
+#[test]
+fn test_held_xlm_shortfall_pauses_asset() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let offer_id = s
+        .market_client
+        .make_offer(&buyer, &asset_id, &100, &5000, &3600);
+
+    // Books claim the market holds XLM it does not have
+    s.env.as_contract(&s.market_client.address, || {
+        storage::set(&s.env, &DataKey::HeldXlm, &20000i128);
+    });
+
+    // The trade itself settles, then the check trips and trading pauses the asset
+    s.market_client
+        .accept_offer(&seller, &offer_id, &100, &5000);
+    let alert: Vec<Val> = (symbol_short!("inv_alert"), asset_id).into_val(&s.env);
+    assert!(s
+        .env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == s.market_client.address && topics == alert));
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 100);
+    assert!(s.trading_client.is_asset_paused(&asset_id));
+}
+
+// === Primary Sales ===
+
+#[test]
+fn test_primary_sale_mints_to_investors_and_pays_issuer() {
+    let s = setup();
+    // The fractcore admin signs the nested mint, not the top-level call
+    s.env.mock_all_auths_allowing_non_root_auth();
+    let issuer = s.account(0);
+    let alice = s.account(6000);
+    let bob = s.account(1000);
+
+    let allocation = |investor: &Address, token_amount: u64, price: u128| PrimaryAllocation {
+        investor: investor.clone(),
+        token_amount,
+        price,
+    };
+
+    assert_eq!(
+        s.market_client
+            .try_primary_sale(&issuer, &Vec::new(&s.env), &None)
+            .err(),
+        Some(Ok(MarketError::InvalidAmount))
+    );
+    assert_eq!(
+        s.market_client
+            .try_primary_sale(
+                &issuer,
+                &Vec::from_array(&s.env, [allocation(&issuer, 100, 1000)]),
+                &None
+            )
+            .err(),
+        Some(Ok(MarketError::SelfTrade))
+    );
+    assert_eq!(
+        s.market_client
+            .try_primary_sale(
+                &issuer,
+                &Vec::from_array(
+                    &s.env,
+                    [allocation(&alice, 100, 1000), allocation(&alice, 50, 500)]
+                ),
+                &None
+            )
+            .err(),
+        Some(Ok(MarketError::InvalidAmount))
+    );
+
+    // Bob cannot cover his leg, so Alice's payment is not taken and nothing is minted
+    let short = Vec::from_array(
+        &s.env,
+        [allocation(&alice, 600, 6000), allocation(&bob, 400, 4000)],
+    );
+    assert_eq!(
+        s.market_client
+            .try_primary_sale(&issuer, &short, &None)
+            .err(),
+        Some(Ok(MarketError::BuyerInsufficientFunds))
+    );
+    assert_eq!(s.xlm_client.balance(&alice), 6000);
+    assert!(!s.fnft_client.asset_exists(&1));
+
+    token::StellarAssetClient::new(&s.env, &s.xlm_client.address).mint(&bob, &3000);
+    let asset_id = s.market_client.primary_sale(&issuer, &short, &None);
+
+    assert_eq!(s.fnft_client.balance_of(&alice, &asset_id), 600);
+    assert_eq!(s.fnft_client.balance_of(&bob, &asset_id), 400);
+    assert_eq!(s.fnft_client.asset_supply(&asset_id), 1000);
+    assert_eq!(s.xlm_client.balance(&alice), 0);
+    assert_eq!(s.xlm_client.balance(&bob), 0);
+    assert_eq!(s.xlm_client.balance(&issuer), 10000);
+    assert_eq!(s.xlm_client.balance(&s.trading_client.address), 0);
+
+    // Each leg is a trade from the issuer
+    let trades = s.trading_client.get_asset_trades(&asset_id);
+    assert_eq!(trades.len(), 2);
+    let first = s.trading_client.get_trade_history(&trades.get(0).unwrap());
+    assert_eq!(first.seller, issuer);
+    assert_eq!(first.buyer, alice);
+    assert_eq!(first.token_amount, 600);
+    assert_eq!(first.price, 6000);
+}
+
+// === Subscriptions ===
+
+#[test]
+fn test_subscription_buys_within_budget_and_records_skips() {
+    let s = setup();
+    let seller = s.account(0);
+    let co_owner = s.account(0);
+    let fan = s.account(20000);
+    let keeper = s.account(0);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let subscription_id =
+        s.market_client
+            .create_subscription(&fan, &asset_id, &Some(100), &6000, &DAY, &3);
+    assert_eq!(s.xlm_client.balance(&fan), 2000);
+    assert_eq!(
+        s.market_client.get_buyer_subscriptions(&fan),
+        Vec::from_array(&s.env, [subscription_id])
+    );
+
+    // Only an offering reserved for someone else is open: the period is skipped
+    s.market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5000, &3600, &7200);
+    s.market_client.run_subscription(&keeper, &subscription_id);
+    assert_eq!(
+        s.market_client
+            .try_run_subscription(&keeper, &subscription_id),
+        Err(Ok(MarketError::SubscriptionNotDue))
+    );
+
+    s.advance_time(DAY);
+    let within_budget = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &5500, &3600, &7200);
+    let over_budget = s
+        .market_client
+        .create_offering(&seller, &co_owner, &asset_id, &100, &6500, &3600, &7200);
+    s.advance_time(3601);
+
+    s.market_client.run_subscription(&keeper, &subscription_id);
+    assert_eq!(s.fnft_client.balance_of(&fan, &asset_id), 100);
+    assert_eq!(s.xlm_client.balance(&seller), 5500);
+    assert!(s.market_client.try_get_offering(&within_budget).is_err());
+    assert!(s.market_client.try_get_offering(&over_budget).is_ok());
+
+    let subscription = s.market_client.get_subscription(&subscription_id);
+    assert_eq!(subscription.periods_run, 2);
+    assert_eq!(subscription.skipped, Vec::from_array(&s.env, [0u32]));
+    assert_eq!(subscription.bought, 100);
+    assert_eq!(subscription.spent, 5500);
+    assert_eq!(subscription.escrowed, 12500);
+    assert_eq!(s.market_client.get_held_xlm(), 12500);
+
+    // Cancelling returns the unspent deposit
+    assert_eq!(
+        s.market_client
+            .try_cancel_subscription(&keeper, &subscription_id),
+        Err(Ok(MarketError::UnauthorizedBuyer))
+    );
+    s.market_client.cancel_subscription(&fan, &subscription_id);
+    assert_eq!(s.xlm_client.balance(&fan), 14500);
+    assert!(s.market_client.get_buyer_subscriptions(&fan).is_empty());
+    assert_eq!(
+        s.market_client
+            .try_run_subscription(&keeper, &subscription_id),
+        Err(Ok(MarketError::SubscriptionInactive))
+    );
+    assert_eq!(
+        s.market_client.try_get_subscription(&(subscription_id + 1)),
+        Err(Ok(MarketError::SubscriptionNotFound))
+    );
+}
+
+#[test]
+fn test_subscription_keeper_paid_from_trading_pool() {
+    let s = setup();
+    let fan = s.account(10000);
+    let keeper = s.account(0);
+    let funder = s.account(1000);
+    let asset_id = s.fnft_client.mint(&Address::generate(&s.env), &1000);
+
+    s.trading_client.set_keeper_bounty(&s.admin, &10);
+    s.trading_client.fund_keeper_pool(&funder, &1000);
+
+    let subscription_id = s
+        .market_client
+        .create_subscription(&fan, &asset_id, &None, &1000, &DAY, &2);
+
+    // The buyer running its own subscription earns nothing
+    assert_eq!(s.market_client.run_subscription(&fan, &subscription_id), 0);
+    s.advance_time(DAY);
+    assert_eq!(
+        s.market_client.run_subscription(&keeper, &subscription_id),
+        10
+    );
+    assert_eq!(s.xlm_client.balance(&keeper), 10);
+    assert_eq!(s.trading_client.get_keeper_pool(), 990);
+
+    // The last period returns the whole unspent deposit
+    assert!(!s.market_client.get_subscription(&subscription_id).active);
+    assert_eq!(s.xlm_client.balance(&fan), 10000);
+    assert_eq!(s.market_client.get_held_xlm(), 0);
+}
+
+#[test]
+fn test_subscriptions_per_buyer_are_capped() {
+    let s = setup();
+    let fan = s.account(100000);
+    let asset_id = s.fnft_client.mint(&Address::generate(&s.env), &1000);
+
+    assert_eq!(
+        s.market_client
+            .try_create_subscription(&fan, &asset_id, &None, &100, &(DAY - 1), &2),
+        Err(Ok(MarketError::InvalidDuration))
+    );
+    for _ in 0..storage::MAX_SUBSCRIPTIONS_PER_BUYER {
+        s.market_client
+            .create_subscription(&fan, &asset_id, &None, &100, &DAY, &2);
+    }
+    assert_eq!(
+        s.market_client
+            .try_create_subscription(&fan, &asset_id, &None, &100, &DAY, &2),
+        Err(Ok(MarketError::TooManySubscriptions))
+    );
+}
@@ -0,0 +1,248 @@
+use crate::errors::OtcError;
+use crate::methods::{fiat, initialization, queries, recurring, rfq, utils};
+use crate::storage::{
+    FiatSale, FiatSettlement, RecurringListing, Rfq, RfqQuote, SellerCommitments,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+
+#[contract]
+pub struct OtcContract;
+
+#[contractimpl]
+impl OtcContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        trading_contract: Address,
+    ) -> Result<(), OtcError> {
+        initialization::construct(env, admin, trading_contract)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Buyer asks holders to quote a price for a block of fractions; returns the RFQ ID
+    pub fn request_quotes(
+        env: Env,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        duration_seconds: u64,
+    ) -> Result<u32, OtcError> {
+        rfq::request_quotes(env, buyer, asset_id, token_amount, duration_seconds)
+    }
+
+    /// Seller quotes a price binding for `duration_seconds`, reserving the
+    /// fractions under its allowance
+    pub fn submit_quote(
+        env: Env,
+        seller: Address,
+        rfq_id: u32,
+        price: u128,
+        duration_seconds: u64,
+    ) -> Result<(), OtcError> {
+        rfq::submit_quote(env, seller, rfq_id, price, duration_seconds)
+    }
+
+    /// Buyer accepts a seller's quote and the trade settles; returns the trade ID
+    pub fn accept_quote(
+        env: Env,
+        buyer: Address,
+        rfq_id: u32,
+        seller: Address,
+        expected_price: u128,
+    ) -> Result<u32, OtcError> {
+        rfq::accept_quote(env, buyer, rfq_id, seller, expected_price)
+    }
+
+    pub fn cancel_rfq(env: Env, buyer: Address, rfq_id: u32) -> Result<(), OtcError> {
+        rfq::cancel_rfq(env, buyer, rfq_id)
+    }
+
+    /// Seller withdraws a quote that is no longer binding and releases its allowance
+    pub fn withdraw_quote(env: Env, seller: Address, rfq_id: u32) -> Result<(), OtcError> {
+        rfq::withdraw_quote(env, seller, rfq_id)
+    }
+
+    /// Seller lists the same lot up to `count` times, one listing per interval
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_recurring_listing(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+        interval: u64,
+        count: u32,
+    ) -> Result<u32, OtcError> {
+        recurring::create_recurring_listing(
+            env,
+            seller,
+            buyer,
+            asset_id,
+            token_amount,
+            price,
+            interval,
+            count,
+        )
+    }
+
+    /// Buyer takes a recurring listing's live lot; returns the trade ID
+    pub fn buy_listing(
+        env: Env,
+        buyer: Address,
+        listing_id: u32,
+        token_amount: u64,
+        price: u128,
+    ) -> Result<u32, OtcError> {
+        recurring::buy_listing(env, buyer, listing_id, token_amount, price)
+    }
+
+    /// Re-list a lapsed recurring listing (keepers earn the trading bounty)
+    pub fn roll_listing(env: Env, keeper: Address, listing_id: u32) -> Result<i128, OtcError> {
+        recurring::roll_listing(env, keeper, listing_id)
+    }
+
+    /// Seller stops a recurring listing and releases its unused allowance
+    pub fn cancel_recurring_listing(
+        env: Env,
+        seller: Address,
+        listing_id: u32,
+    ) -> Result<(), OtcError> {
+        recurring::cancel_recurring_listing(env, seller, listing_id)
+    }
+
+    /// Seller lists fractions at a USD price (FIAT_DECIMALS) converted at settlement
+    pub fn confirm_fiat_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        usd_price: u128,
+        duration_seconds: u64,
+    ) -> Result<(), OtcError> {
+        fiat::confirm_fiat_sale(
+            env,
+            seller,
+            buyer,
+            asset_id,
+            token_amount,
+            usd_price,
+            duration_seconds,
+        )
+    }
+
+    /// Buyer settles a fiat-priced sale at the oracle rate, paying at most `max_price` XLM
+    pub fn finish_fiat_transaction(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        asset_id: u64,
+        token_amount: u64,
+        usd_price: u128,
+        max_price: u128,
+    ) -> Result<u128, OtcError> {
+        fiat::finish_fiat_transaction(
+            env,
+            buyer,
+            seller,
+            asset_id,
+            token_amount,
+            usd_price,
+            max_price,
+        )
+    }
+
+    /// Seller withdraws a fiat-priced sale and releases its allowance
+    pub fn withdraw_fiat_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), OtcError> {
+        fiat::withdraw_fiat_sale(env, seller, buyer, asset_id)
+    }
+
+    /// Set or clear the XLM/USD oracle (Reflector interface) for fiat-priced sales (admin only)
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Option<Address>,
+    ) -> Result<(), OtcError> {
+        fiat::set_price_oracle(env, admin, oracle)
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        fiat::get_price_oracle(env)
+    }
+
+    pub fn get_fiat_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<FiatSale, OtcError> {
+        fiat::get_fiat_sale(env, seller, buyer, asset_id)
+    }
+
+    /// Fiat-priced sales of a seller not yet settled or withdrawn, as (buyer, asset_id)
+    pub fn get_seller_fiat_sales(env: Env, seller: Address) -> Vec<(Address, u64)> {
+        fiat::get_seller_fiat_sales(env, seller)
+    }
+
+    /// Oracle rate a fiat-priced trade settled at (None for trades settled elsewhere)
+    pub fn get_fiat_settlement(env: Env, trade_id: u32) -> Option<FiatSettlement> {
+        fiat::get_fiat_settlement(env, trade_id)
+    }
+
+    pub fn get_rfq(env: Env, rfq_id: u32) -> Result<Rfq, OtcError> {
+        rfq::get_rfq(env, rfq_id)
+    }
+
+    /// RFQs of an asset that can still be quoted, oldest first
+    pub fn get_open_rfqs(env: Env, asset_id: u64) -> Result<Vec<u32>, OtcError> {
+        rfq::get_open_rfqs(env, asset_id)
+    }
+
+    pub fn get_rfq_quotes(env: Env, rfq_id: u32) -> Vec<RfqQuote> {
+        rfq::get_rfq_quotes(env, rfq_id)
+    }
+
+    /// Cheapest quote the buyer could accept right now
+    pub fn get_best_quote(env: Env, rfq_id: u32) -> Option<RfqQuote> {
+        rfq::get_best_quote(env, rfq_id)
+    }
+
+    /// RFQs a seller holds a quote on
+    pub fn get_seller_quotes(env: Env, seller: Address) -> Vec<u32> {
+        rfq::get_seller_quotes(env, seller)
+    }
+
+    pub fn get_recurring_listing(env: Env, listing_id: u32) -> Result<RecurringListing, OtcError> {
+        recurring::get_recurring_listing(env, listing_id)
+    }
+
+    pub fn get_seller_listings(env: Env, seller: Address) -> Vec<u32> {
+        recurring::get_seller_listings(env, seller)
+    }
+
+    /// Quotes, fiat-priced sales and recurring listings backed by the seller's
+    /// allowance to this contract, with the total committed and any uncommitted
+    /// allowance or shortfall
+    pub fn get_seller_commitments(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<SellerCommitments, OtcError> {
+        queries::get_seller_commitments(env, seller, asset_id)
+    }
+
+    pub fn get_trading_contract(env: Env) -> Result<Address, OtcError> {
+        utils::get_trading_contract(&env)
+    }
+}
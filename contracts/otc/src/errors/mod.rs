@@ -0,0 +1,48 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by otc entrypoints
+///
+/// Codes shared with the trading contract keep its numbers, so a failure the
+/// trading hooks report reads the same here; codes from 100 are the otc's own.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OtcError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidTokenAmount = 3,
+    InvalidPrice = 4,
+    SelfTrade = 5,
+    InvalidDuration = 6,
+    AssetNotFound = 7,
+    InsufficientBalance = 8,
+    SaleAlreadyExists = 9,
+    SaleNotFound = 10,
+    SaleNotActive = 11,
+    UnauthorizedBuyer = 12,
+    UnauthorizedSeller = 13,
+    SaleExpired = 14,
+    SaleNotExpired = 15,
+    TokenAmountMismatch = 16,
+    PriceMismatch = 17,
+    SellerInsufficientBalance = 18,
+    BuyerInsufficientFunds = 19,
+    InsufficientAllowance = 20,
+    PriceOverflow = 21,
+    Unauthorized = 23,
+    InvalidAmount = 24,
+    ListingNotFound = 40,
+    ListingInactive = 41,
+    ListingNotDue = 42,
+    ListingExhausted = 43,
+    AddressBlocked = 46,
+    ScreeningFailed = 47,
+    AssetPaused = 48,
+    NoPriceData = 50,
+    UpstreamPaused = 51,
+    ExceedsReleasedTranche = 53,
+    BuyerNotEligible = 54,
+    TooManyRequests = 100,
+    TooManyQuotes = 101,
+    TooManySales = 102,
+}
@@ -0,0 +1,122 @@
+use crate::storage::{FiatSale, RecurringListing, Rfq, RfqQuote};
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// contract initialization event
+pub fn emit_init_event(env: &Env, admin: &Address, trading_contract: &Address) {
+    env.events().publish(
+        (symbol_short!("init"),),
+        (admin.clone(), trading_contract.clone()),
+    );
+}
+
+/// rfq posted by a buyer event
+pub fn emit_rfq_created_event(env: &Env, rfq_id: u32, rfq: &Rfq) {
+    env.events().publish(
+        (symbol_short!("rfq_new"), rfq_id),
+        (
+            rfq.buyer.clone(),
+            rfq.asset_id,
+            rfq.token_amount,
+            rfq.expires_at,
+        ),
+    );
+}
+
+/// seller quoted a price for an rfq event
+pub fn emit_rfq_quote_event(env: &Env, rfq_id: u32, quote: &RfqQuote) {
+    env.events().publish(
+        (symbol_short!("rfq_quote"), rfq_id),
+        (quote.seller.clone(), quote.price, quote.expires_at),
+    );
+}
+
+/// buyer accepted a quote and the trade settled event
+pub fn emit_rfq_filled_event(env: &Env, rfq_id: u32, seller: &Address, trade_id: u32) {
+    env.events().publish(
+        (symbol_short!("rfq_fill"), rfq_id),
+        (seller.clone(), trade_id),
+    );
+}
+
+/// rfq withdrawn by the buyer event
+pub fn emit_rfq_cancelled_event(env: &Env, rfq_id: u32) {
+    env.events()
+        .publish((symbol_short!("rfq_cncl"), rfq_id), ());
+}
+
+/// quote withdrawn and its allowance released by the seller event
+pub fn emit_rfq_quote_withdrawn_event(env: &Env, rfq_id: u32, seller: &Address) {
+    env.events()
+        .publish((symbol_short!("rfq_wdrw"), rfq_id), seller.clone());
+}
+
+/// recurring listing template created event
+pub fn emit_listing_created_event(env: &Env, listing_id: u32, listing: &RecurringListing) {
+    env.events().publish(
+        (symbol_short!("rl_new"), listing_id),
+        (
+            listing.seller.clone(),
+            listing.buyer.clone(),
+            listing.asset_id,
+            listing.token_amount,
+            listing.price,
+            listing.interval,
+            listing.remaining,
+        ),
+    );
+}
+
+/// buyer bought a recurring listing's live lot event
+pub fn emit_listing_bought_event(env: &Env, listing_id: u32, trade_id: u32) {
+    env.events()
+        .publish((symbol_short!("rl_buy"), listing_id), trade_id);
+}
+
+/// keeper re-listed a recurring listing event
+pub fn emit_listing_rolled_event(env: &Env, listing_id: u32, keeper: &Address, remaining: u32) {
+    env.events().publish(
+        (symbol_short!("rl_roll"), listing_id),
+        (keeper.clone(), remaining),
+    );
+}
+
+/// recurring listing cancelled by the seller event
+pub fn emit_listing_cancelled_event(env: &Env, listing_id: u32, released: u64) {
+    env.events()
+        .publish((symbol_short!("rl_cncl"), listing_id), released);
+}
+
+/// price oracle set or cleared by the admin event
+pub fn emit_oracle_event(env: &Env, oracle: Option<Address>) {
+    env.events().publish((symbol_short!("oracle"),), oracle);
+}
+
+/// fiat-priced sale listed by a seller event
+pub fn emit_fiat_sale_event(env: &Env, sale: &FiatSale) {
+    env.events().publish(
+        (symbol_short!("fiat_new"), sale.asset_id),
+        (
+            sale.seller.clone(),
+            sale.buyer.clone(),
+            sale.token_amount,
+            sale.usd_price,
+            sale.expires_at,
+        ),
+    );
+}
+
+/// fiat-priced sale settled at an oracle rate event
+pub fn emit_fiat_settled_event(env: &Env, sale: &FiatSale, price: u128, trade_id: u32) {
+    env.events().publish(
+        (symbol_short!("fiat_fill"), sale.asset_id),
+        (sale.seller.clone(), sale.buyer.clone(), price, trade_id),
+    );
+}
+
+/// fiat-priced sale withdrawn by the seller event
+pub fn emit_fiat_withdrawn_event(env: &Env, sale: &FiatSale) {
+    env.events().publish(
+        (symbol_short!("fiat_wdrw"), sale.asset_id),
+        (sale.seller.clone(), sale.buyer.clone()),
+    );
+}
@@ -0,0 +1,57 @@
+use crate::storage::{Conduct, OracleAsset, PriceData, SaleDurationLimits, UpstreamPause};
+use soroban_sdk::{contractclient, Address, Env};
+
+// FNFT contract interface for cross-contract calls
+#[contractclient(name = "FNFTClient")]
+pub trait FNFTInterface {
+    fn asset_exists(env: Env, asset_id: u64) -> bool;
+    fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64;
+    fn transfer_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        asset_id: u64,
+        amount: u64,
+    );
+    fn approve(env: Env, owner: Address, operator: Address, asset_id: u64, amount: u64);
+    fn allowance(env: Env, owner: Address, operator: Address, asset_id: u64) -> u64;
+}
+
+// Trading contract this extension is registered with, and the hooks it calls
+#[contractclient(name = "TradingClient")]
+pub trait TradingInterface {
+    fn get_fnft_contract_address(env: Env) -> Address;
+    fn get_xlm_contract_address_public(env: Env) -> Address;
+    fn get_asset_sale_duration_limits(env: Env, asset_id: u64) -> SaleDurationLimits;
+    fn is_asset_paused(env: Env, asset_id: u64) -> bool;
+    fn get_upstream_pause(env: Env) -> Option<UpstreamPause>;
+    fn check_trade(env: Env, seller: Address, buyer: Address, asset_id: u64);
+    fn draw_exit(env: Env, extension: Address, seller: Address, asset_id: u64, amount: u64);
+    fn pay_seller(
+        env: Env,
+        extension: Address,
+        payer: Address,
+        seller: Address,
+        asset_id: u64,
+        amount: i128,
+    );
+    fn record_trade(
+        env: Env,
+        extension: Address,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+    ) -> u32;
+    fn record_conduct(env: Env, extension: Address, party: Address, conduct: Conduct);
+    fn pay_keeper(env: Env, extension: Address, keeper: Address) -> i128;
+}
+
+// XLM/USD price oracle following Reflector's SEP-40 interface
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData>;
+}
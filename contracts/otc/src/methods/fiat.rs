@@ -0,0 +1,262 @@
+use crate::errors::OtcError;
+use crate::events;
+use crate::interfaces::{FNFTClient, PriceOracleClient};
+use crate::methods::utils;
+use crate::storage::{
+    self, Conduct, DataKey, FiatSale, FiatSettlement, OracleAsset, MAX_FIAT_SALES_PER_SELLER,
+    MAX_ORACLE_PRICE_AGE,
+};
+use soroban_sdk::{symbol_short, Address, Env, Vec};
+
+/// Admin sets (or clears with `None`) the XLM/USD oracle used by fiat-priced sales
+pub fn set_price_oracle(env: Env, admin: Address, oracle: Option<Address>) -> Result<(), OtcError> {
+    utils::require_admin(&env, &admin)?;
+
+    match &oracle {
+        Some(contract) => storage::set(&env, &DataKey::PriceOracle, contract),
+        None => storage::remove(&env, &DataKey::PriceOracle),
+    }
+
+    events::emit_oracle_event(&env, oracle);
+
+    Ok(())
+}
+
+pub fn get_price_oracle(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKey::PriceOracle)
+}
+
+/// Seller lists fractions to a buyer at a USD price; the XLM amount is fixed
+/// only when the buyer settles. The fractions are reserved under the seller's
+/// allowance to this contract, and an oracle rate must be available to list.
+pub fn confirm_fiat_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    usd_price: u128,
+    duration_seconds: u64,
+) -> Result<(), OtcError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(OtcError::InvalidTokenAmount);
+    }
+    if usd_price == 0 {
+        return Err(OtcError::InvalidPrice);
+    }
+    if seller == buyer {
+        return Err(OtcError::SelfTrade);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+    utils::require_not_paused(&env, asset_id)?;
+    // Refuse a price the oracle cannot convert now rather than at settlement
+    usd_to_xlm(usd_price, &current_rate(&env)?)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(OtcError::AssetNotFound);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < token_amount {
+        return Err(OtcError::InsufficientBalance);
+    }
+    if get_fiat_sale(env.clone(), seller.clone(), buyer.clone(), asset_id).is_ok() {
+        return Err(OtcError::SaleAlreadyExists);
+    }
+
+    let mut sales = get_seller_fiat_sales(env.clone(), seller.clone());
+    if sales.len() >= MAX_FIAT_SALES_PER_SELLER {
+        return Err(OtcError::TooManySales);
+    }
+
+    utils::reserve(&env, &seller, asset_id, token_amount)?;
+
+    let now = env.ledger().timestamp();
+    let sale = FiatSale {
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        asset_id,
+        token_amount,
+        usd_price,
+        created_at: now,
+        expires_at: now + duration_seconds,
+    };
+    storage::set(
+        &env,
+        &DataKey::FiatSale(seller.clone(), buyer.clone(), asset_id),
+        &sale,
+    );
+    sales.push_back((buyer, asset_id));
+    storage::set(&env, &DataKey::SellerFiatSales(seller), &sales);
+
+    events::emit_fiat_sale_event(&env, &sale);
+
+    Ok(())
+}
+
+/// Buyer settles a fiat-priced sale at the current oracle rate, paying at most
+/// `max_price` XLM. Returns the XLM amount paid.
+///
+/// Fails with `PriceMismatch` when the USD price differs from the buyer's or the
+/// converted price is above `max_price`.
+pub fn finish_fiat_transaction(
+    env: Env,
+    buyer: Address,
+    seller: Address,
+    asset_id: u64,
+    expected_token_amount: u64,
+    expected_usd_price: u128,
+    max_price: u128,
+) -> Result<u128, OtcError> {
+    buyer.require_auth();
+
+    let sale = get_fiat_sale(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
+    if env.ledger().timestamp() > sale.expires_at {
+        return Err(OtcError::SaleExpired);
+    }
+    if sale.token_amount != expected_token_amount {
+        return Err(OtcError::TokenAmountMismatch);
+    }
+    if sale.usd_price != expected_usd_price {
+        return Err(OtcError::PriceMismatch);
+    }
+
+    let quote = current_rate(&env)?;
+    let price = usd_to_xlm(sale.usd_price, &quote)?;
+    if price > max_price {
+        return Err(OtcError::PriceMismatch);
+    }
+
+    utils::trading(&env)?.check_trade(&seller, &buyer, &asset_id);
+    let trade_id = utils::swap(&env, &seller, &buyer, asset_id, sale.token_amount, price)?;
+    close_sale(&env, &sale);
+
+    storage::set(
+        &env,
+        &DataKey::FiatTrade(trade_id),
+        &FiatSettlement {
+            usd_price: sale.usd_price,
+            rate: quote.rate,
+            rate_decimals: quote.decimals,
+            rate_timestamp: quote.timestamp,
+        },
+    );
+
+    events::emit_fiat_settled_event(&env, &sale, price, trade_id);
+
+    Ok(price)
+}
+
+/// Seller withdraws a fiat-priced sale, expired or not, and releases the
+/// allowance it reserved
+pub fn withdraw_fiat_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), OtcError> {
+    seller.require_auth();
+
+    let sale = get_fiat_sale(env.clone(), seller.clone(), buyer, asset_id)?;
+    utils::release(&env, &seller, asset_id, sale.token_amount)?;
+    close_sale(&env, &sale);
+
+    utils::trading(&env)?.record_conduct(
+        &env.current_contract_address(),
+        &seller,
+        &Conduct::Cancellation,
+    );
+
+    events::emit_fiat_withdrawn_event(&env, &sale);
+
+    Ok(())
+}
+
+pub fn get_fiat_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<FiatSale, OtcError> {
+    match storage::get(&env, &DataKey::FiatSale(seller, buyer, asset_id)) {
+        Some(sale) => Ok(sale),
+        None => Err(OtcError::SaleNotFound),
+    }
+}
+
+/// (buyer, asset_id) of the seller's fiat-priced sales not yet settled or withdrawn
+pub fn get_seller_fiat_sales(env: Env, seller: Address) -> Vec<(Address, u64)> {
+    storage::get(&env, &DataKey::SellerFiatSales(seller)).unwrap_or(Vec::new(&env))
+}
+
+/// Oracle rate a fiat-priced trade settled at
+pub fn get_fiat_settlement(env: Env, trade_id: u32) -> Option<FiatSettlement> {
+    storage::get(&env, &DataKey::FiatTrade(trade_id))
+}
+
+fn close_sale(env: &Env, sale: &FiatSale) {
+    storage::remove(
+        env,
+        &DataKey::FiatSale(sale.seller.clone(), sale.buyer.clone(), sale.asset_id),
+    );
+
+    let mut sales = get_seller_fiat_sales(env.clone(), sale.seller.clone());
+    if let Some(index) = sales.first_index_of((sale.buyer.clone(), sale.asset_id)) {
+        sales.remove(index);
+    }
+    storage::set(env, &DataKey::SellerFiatSales(sale.seller.clone()), &sales);
+}
+
+struct Quote {
+    rate: i128,
+    decimals: u32,
+    timestamp: u64,
+}
+
+/// Latest XLM/USD rate; a missing oracle and missing, non-positive or stale rates
+/// all fail with `NoPriceData`
+fn current_rate(env: &Env) -> Result<Quote, OtcError> {
+    let oracle = match get_price_oracle(env.clone()) {
+        Some(oracle) => oracle,
+        None => return Err(OtcError::NoPriceData),
+    };
+    let client = PriceOracleClient::new(env, &oracle);
+
+    let decimals = match client.try_decimals() {
+        Ok(Ok(decimals)) => decimals,
+        _ => return Err(OtcError::NoPriceData),
+    };
+    let data = match client.try_lastprice(&OracleAsset::Other(symbol_short!("XLM"))) {
+        Ok(Ok(Some(data))) => data,
+        _ => return Err(OtcError::NoPriceData),
+    };
+
+    let age = env.ledger().timestamp().saturating_sub(data.timestamp);
+    if data.price <= 0 || age > MAX_ORACLE_PRICE_AGE {
+        return Err(OtcError::NoPriceData);
+    }
+
+    Ok(Quote {
+        rate: data.price,
+        decimals,
+        timestamp: data.timestamp,
+    })
+}
+
+/// Stroops worth `usd_price` (FIAT_DECIMALS) at the quoted USD-per-XLM rate, rounded down
+fn usd_to_xlm(usd_price: u128, quote: &Quote) -> Result<u128, OtcError> {
+    // USD prices and stroops share FIAT_DECIMALS, so only the oracle's scale is undone
+    let scaled = 10u128
+        .checked_pow(quote.decimals)
+        .and_then(|scale| usd_price.checked_mul(scale));
+    let price = match scaled {
+        Some(scaled) => scaled / quote.rate as u128,
+        None => return Err(OtcError::PriceOverflow),
+    };
+
+    if price == 0 {
+        return Err(OtcError::InvalidPrice);
+    }
+    Ok(price)
+}
@@ -0,0 +1,6 @@
+pub mod fiat;
+pub mod initialization;
+pub mod queries;
+pub mod recurring;
+pub mod rfq;
+pub mod utils;
@@ -0,0 +1,81 @@
+use crate::errors::OtcError;
+use crate::interfaces::FNFTClient;
+use crate::methods::{fiat, recurring, rfq, utils};
+use crate::storage::{Commitment, CommitmentKind, SellerCommitments};
+use soroban_sdk::{Address, Env, Vec};
+
+/// What a seller's allowance to this contract for an asset is reserved for,
+/// and how it compares with the allowance fractcore actually holds.
+///
+/// Quotes and fiat-priced sales stay committed after they expire until they
+/// are withdrawn, so `expires_at` lets wallets spot stale ones. A non-zero
+/// `uncommitted` is orphaned allowance the seller may want to reset. Proposals
+/// on the trading contract are under a separate allowance and not listed here.
+pub fn get_seller_commitments(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+) -> Result<SellerCommitments, OtcError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let allowance = fnft_client.allowance(&seller, &env.current_contract_address(), &asset_id);
+    let mut commitments = Vec::new(&env);
+
+    for rfq_id in rfq::get_seller_quotes(env.clone(), seller.clone()).iter() {
+        let request = rfq::get_rfq(env.clone(), rfq_id)?;
+        if request.asset_id != asset_id {
+            continue;
+        }
+        for quote in rfq::get_rfq_quotes(env.clone(), rfq_id).iter() {
+            if quote.seller == seller {
+                commitments.push_back(Commitment {
+                    kind: CommitmentKind::Quote,
+                    counterparty: request.buyer.clone(),
+                    id: rfq_id,
+                    token_amount: request.token_amount,
+                    expires_at: quote.expires_at,
+                });
+            }
+        }
+    }
+
+    for (buyer, sale_asset) in fiat::get_seller_fiat_sales(env.clone(), seller.clone()).iter() {
+        if sale_asset != asset_id {
+            continue;
+        }
+        let sale = fiat::get_fiat_sale(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
+        commitments.push_back(Commitment {
+            kind: CommitmentKind::FiatSale,
+            counterparty: buyer,
+            id: 0,
+            token_amount: sale.token_amount,
+            expires_at: sale.expires_at,
+        });
+    }
+
+    for listing_id in recurring::get_seller_listings(env.clone(), seller.clone()).iter() {
+        let listing = recurring::get_recurring_listing(env.clone(), listing_id)?;
+        let reserved = recurring::reserved(&listing);
+        if listing.asset_id == asset_id && reserved > 0 {
+            commitments.push_back(Commitment {
+                kind: CommitmentKind::RecurringListing,
+                counterparty: listing.buyer,
+                id: listing_id,
+                token_amount: reserved,
+                expires_at: 0,
+            });
+        }
+    }
+
+    let total_committed = commitments
+        .iter()
+        .map(|commitment| commitment.token_amount)
+        .sum::<u64>();
+
+    Ok(SellerCommitments {
+        commitments,
+        total_committed,
+        allowance,
+        uncommitted: allowance.saturating_sub(total_committed),
+        shortfall: total_committed.saturating_sub(allowance),
+    })
+}
@@ -0,0 +1,234 @@
+use crate::errors::OtcError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{
+    self, DataKey, RecurringListing, MAX_LISTINGS_PER_SELLER, MAX_RECURRING_LISTINGS,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Seller creates a template that lists the same lot to the same buyer up to
+/// `count` times. The first listing goes live immediately; keepers create the
+/// rest with `roll_listing` once each one lapses.
+#[allow(clippy::too_many_arguments)]
+pub fn create_recurring_listing(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    interval: u64,
+    count: u32,
+) -> Result<u32, OtcError> {
+    seller.require_auth();
+
+    if token_amount == 0 {
+        return Err(OtcError::InvalidTokenAmount);
+    }
+    if price == 0 {
+        return Err(OtcError::InvalidPrice);
+    }
+    if price > i128::MAX as u128 {
+        return Err(OtcError::PriceOverflow);
+    }
+    if seller == buyer {
+        return Err(OtcError::SelfTrade);
+    }
+    utils::require_valid_duration(&env, asset_id, interval)?;
+    if count == 0 || count > MAX_RECURRING_LISTINGS {
+        return Err(OtcError::InvalidAmount);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(OtcError::AssetNotFound);
+    }
+    if fnft_client.balance_of(&seller, &asset_id) < token_amount {
+        return Err(OtcError::InsufficientBalance);
+    }
+
+    let mut listings = get_seller_listings(env.clone(), seller.clone());
+    if listings.len() >= MAX_LISTINGS_PER_SELLER {
+        return Err(OtcError::TooManySales);
+    }
+
+    // Approve every listing up front so keepers can roll without the seller
+    let reserved = match token_amount.checked_mul(count as u64) {
+        Some(reserved) => reserved,
+        None => return Err(OtcError::InvalidTokenAmount),
+    };
+    utils::reserve(&env, &seller, asset_id, reserved)?;
+
+    let listing_id: u32 = storage::get(&env, &DataKey::ListingCounter).unwrap_or(0) + 1;
+
+    let listing = RecurringListing {
+        seller: seller.clone(),
+        buyer,
+        asset_id,
+        token_amount,
+        price,
+        interval,
+        remaining: count - 1,
+        last_listed_at: env.ledger().timestamp(),
+        sold: false,
+        lapsed: 0,
+        active: true,
+    };
+
+    storage::set(&env, &DataKey::RecurringListing(listing_id), &listing);
+    storage::set(&env, &DataKey::ListingCounter, &listing_id);
+    listings.push_back(listing_id);
+    storage::set(&env, &DataKey::SellerListings(seller), &listings);
+
+    events::emit_listing_created_event(&env, listing_id, &listing);
+
+    Ok(listing_id)
+}
+
+/// Buyer takes the live listing at the template's terms, while it is open and
+/// unsold. Returns the trade ID.
+pub fn buy_listing(
+    env: Env,
+    buyer: Address,
+    listing_id: u32,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<u32, OtcError> {
+    buyer.require_auth();
+
+    let mut listing = get_recurring_listing(env.clone(), listing_id)?;
+    if listing.buyer != buyer {
+        return Err(OtcError::UnauthorizedBuyer);
+    }
+    if !listing.active || listing.sold {
+        return Err(OtcError::ListingInactive);
+    }
+    if env.ledger().timestamp() > listing.last_listed_at + listing.interval {
+        return Err(OtcError::SaleExpired);
+    }
+    if listing.token_amount != expected_token_amount {
+        return Err(OtcError::TokenAmountMismatch);
+    }
+    if listing.price != expected_price {
+        return Err(OtcError::PriceMismatch);
+    }
+
+    utils::trading(&env)?.check_trade(&listing.seller, &buyer, &listing.asset_id);
+    let trade_id = utils::swap(
+        &env,
+        &listing.seller,
+        &buyer,
+        listing.asset_id,
+        listing.token_amount,
+        listing.price,
+    )?;
+
+    listing.sold = true;
+    storage::set(&env, &DataKey::RecurringListing(listing_id), &listing);
+
+    events::emit_listing_bought_event(&env, listing_id, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Keeper re-lists the lot once the previous listing has lapsed, whether it sold
+/// or not. Returns the bounty trading paid the keeper.
+pub fn roll_listing(env: Env, keeper: Address, listing_id: u32) -> Result<i128, OtcError> {
+    keeper.require_auth();
+
+    let mut listing = get_recurring_listing(env.clone(), listing_id)?;
+
+    if !listing.active {
+        return Err(OtcError::ListingInactive);
+    }
+    if listing.remaining == 0 {
+        return Err(OtcError::ListingExhausted);
+    }
+    if env.ledger().timestamp() <= listing.last_listed_at + listing.interval {
+        return Err(OtcError::ListingNotDue);
+    }
+
+    // An unsold listing keeps its allowance reserved until the template ends
+    if !listing.sold {
+        listing.lapsed += 1;
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if fnft_client.balance_of(&listing.seller, &listing.asset_id) < listing.token_amount {
+        return Err(OtcError::SellerInsufficientBalance);
+    }
+
+    listing.remaining -= 1;
+    listing.last_listed_at = env.ledger().timestamp();
+    listing.sold = false;
+    storage::set(&env, &DataKey::RecurringListing(listing_id), &listing);
+
+    events::emit_listing_rolled_event(&env, listing_id, &keeper, listing.remaining);
+
+    // Parties could otherwise farm bounties from their own template
+    if keeper == listing.seller || keeper == listing.buyer {
+        return Ok(0);
+    }
+
+    Ok(utils::trading(&env)?.pay_keeper(&env.current_contract_address(), &keeper))
+}
+
+/// Seller stops a template: withdraws the live listing, if any, and gives back
+/// the allowance reserved for listings that never sold or were never created
+pub fn cancel_recurring_listing(
+    env: Env,
+    seller: Address,
+    listing_id: u32,
+) -> Result<(), OtcError> {
+    seller.require_auth();
+
+    let mut listing = get_recurring_listing(env.clone(), listing_id)?;
+
+    if listing.seller != seller {
+        return Err(OtcError::UnauthorizedSeller);
+    }
+    if !listing.active {
+        return Err(OtcError::ListingInactive);
+    }
+
+    let released = reserved(&listing);
+    utils::release(&env, &seller, listing.asset_id, released)?;
+
+    listing.active = false;
+    listing.remaining = 0;
+    listing.lapsed = 0;
+    storage::set(&env, &DataKey::RecurringListing(listing_id), &listing);
+
+    let mut listings = get_seller_listings(env.clone(), seller.clone());
+    if let Some(index) = listings.first_index_of(listing_id) {
+        listings.remove(index);
+    }
+    storage::set(&env, &DataKey::SellerListings(seller), &listings);
+
+    events::emit_listing_cancelled_event(&env, listing_id, released);
+
+    Ok(())
+}
+
+pub fn get_recurring_listing(env: Env, listing_id: u32) -> Result<RecurringListing, OtcError> {
+    match storage::get(&env, &DataKey::RecurringListing(listing_id)) {
+        Some(listing) => Ok(listing),
+        None => Err(OtcError::ListingNotFound),
+    }
+}
+
+/// Recurring listing template IDs the seller has not cancelled
+pub fn get_seller_listings(env: Env, seller: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::SellerListings(seller)).unwrap_or(Vec::new(&env))
+}
+
+/// Fractions still reserved by an active template: the listings not created
+/// yet, those that lapsed unsold and the live one unless it sold
+pub fn reserved(listing: &RecurringListing) -> u64 {
+    if !listing.active {
+        return 0;
+    }
+    let live = if listing.sold { 0 } else { 1 };
+    listing.token_amount * (listing.remaining + listing.lapsed + live) as u64
+}
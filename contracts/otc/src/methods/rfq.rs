@@ -0,0 +1,320 @@
+use crate::errors::OtcError;
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{
+    self, DataKey, Rfq, RfqQuote, RfqStatus, MAX_OPEN_RFQS_PER_ASSET, MAX_QUOTES_PER_RFQ,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Buyer asks holders to quote a price for `token_amount` fractions of an asset.
+///
+/// Nothing is escrowed on the buyer's side; the XLM moves when a quote is
+/// accepted. Expired requests drop out of the asset's open list the next time
+/// a request for it is made. Returns the RFQ ID.
+pub fn request_quotes(
+    env: Env,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    duration_seconds: u64,
+) -> Result<u32, OtcError> {
+    buyer.require_auth();
+
+    if token_amount == 0 {
+        return Err(OtcError::InvalidTokenAmount);
+    }
+    utils::require_valid_duration(&env, asset_id, duration_seconds)?;
+    utils::require_not_paused(&env, asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return Err(OtcError::AssetNotFound);
+    }
+
+    let mut open = live_rfqs(&env, asset_id)?;
+    if open.len() >= MAX_OPEN_RFQS_PER_ASSET {
+        return Err(OtcError::TooManyRequests);
+    }
+
+    let rfq_id: u32 = storage::get(&env, &DataKey::RfqCounter).unwrap_or(0) + 1;
+
+    let now = env.ledger().timestamp();
+    let rfq = Rfq {
+        buyer,
+        asset_id,
+        token_amount,
+        created_at: now,
+        expires_at: now + duration_seconds,
+        status: RfqStatus::Open,
+    };
+
+    storage::set(&env, &DataKey::Rfq(rfq_id), &rfq);
+    storage::set(&env, &DataKey::RfqCounter, &rfq_id);
+
+    open.push_back(rfq_id);
+    storage::set(&env, &DataKey::AssetRfqs(asset_id), &open);
+
+    events::emit_rfq_created_event(&env, rfq_id, &rfq);
+
+    Ok(rfq_id)
+}
+
+/// Seller quotes `price` XLM for the whole RFQ, binding for `duration_seconds`.
+///
+/// The fractions are reserved by raising the seller's allowance to this
+/// contract, as offerings do, so the buyer can settle without the seller. The
+/// quote must end by the time the RFQ does; one quote per seller.
+pub fn submit_quote(
+    env: Env,
+    seller: Address,
+    rfq_id: u32,
+    price: u128,
+    duration_seconds: u64,
+) -> Result<(), OtcError> {
+    seller.require_auth();
+
+    let rfq = get_rfq(env.clone(), rfq_id)?;
+    let now = env.ledger().timestamp();
+    if rfq.status != RfqStatus::Open {
+        return Err(OtcError::SaleNotActive);
+    }
+    if now >= rfq.expires_at {
+        return Err(OtcError::SaleExpired);
+    }
+    if seller == rfq.buyer {
+        return Err(OtcError::SelfTrade);
+    }
+    if price == 0 {
+        return Err(OtcError::InvalidPrice);
+    }
+    if price > i128::MAX as u128 {
+        return Err(OtcError::PriceOverflow);
+    }
+    if duration_seconds == 0 || now + duration_seconds > rfq.expires_at {
+        return Err(OtcError::InvalidDuration);
+    }
+    utils::require_not_paused(&env, rfq.asset_id)?;
+
+    let mut quotes = get_rfq_quotes(env.clone(), rfq_id);
+    if quotes.iter().any(|quote| quote.seller == seller) {
+        return Err(OtcError::SaleAlreadyExists);
+    }
+    if quotes.len() >= MAX_QUOTES_PER_RFQ {
+        return Err(OtcError::TooManyQuotes);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if fnft_client.balance_of(&seller, &rfq.asset_id) < rfq.token_amount {
+        return Err(OtcError::InsufficientBalance);
+    }
+    utils::reserve(&env, &seller, rfq.asset_id, rfq.token_amount)?;
+
+    let quote = RfqQuote {
+        seller: seller.clone(),
+        price,
+        quoted_at: now,
+        expires_at: now + duration_seconds,
+    };
+    quotes.push_back(quote.clone());
+    storage::set(&env, &DataKey::RfqQuotes(rfq_id), &quotes);
+
+    let mut seller_quotes = get_seller_quotes(env.clone(), seller.clone());
+    seller_quotes.push_back(rfq_id);
+    storage::set(&env, &DataKey::SellerQuotes(seller), &seller_quotes);
+
+    events::emit_rfq_quote_event(&env, rfq_id, &quote);
+
+    Ok(())
+}
+
+/// Buyer takes a live quote: the fractions move from the seller under the
+/// reserved allowance and the price from the buyer in one call. The expected
+/// price guards against a replaced quote. Returns the trade ID.
+pub fn accept_quote(
+    env: Env,
+    buyer: Address,
+    rfq_id: u32,
+    seller: Address,
+    expected_price: u128,
+) -> Result<u32, OtcError> {
+    buyer.require_auth();
+
+    let mut rfq = get_rfq(env.clone(), rfq_id)?;
+    let now = env.ledger().timestamp();
+    if rfq.buyer != buyer {
+        return Err(OtcError::UnauthorizedBuyer);
+    }
+    if rfq.status != RfqStatus::Open {
+        return Err(OtcError::SaleNotActive);
+    }
+    if now >= rfq.expires_at {
+        return Err(OtcError::SaleExpired);
+    }
+
+    let quote = match find_quote(&env, rfq_id, &seller) {
+        Some((_, quote)) => quote,
+        None => return Err(OtcError::SaleNotFound),
+    };
+    if now >= quote.expires_at {
+        return Err(OtcError::SaleExpired);
+    }
+    if quote.price != expected_price {
+        return Err(OtcError::PriceMismatch);
+    }
+
+    utils::trading(&env)?.check_trade(&seller, &buyer, &rfq.asset_id);
+    let trade_id = utils::swap(
+        &env,
+        &seller,
+        &buyer,
+        rfq.asset_id,
+        rfq.token_amount,
+        quote.price,
+    )?;
+
+    // The accepted quote's reservation was spent by the transfer
+    remove_quote(&env, rfq_id, &seller);
+
+    rfq.status = RfqStatus::Filled;
+    close_rfq(&env, rfq_id, &rfq);
+
+    events::emit_rfq_filled_event(&env, rfq_id, &seller, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Buyer withdraws an open RFQ; sellers then withdraw their quotes to release
+/// the reserved allowance
+pub fn cancel_rfq(env: Env, buyer: Address, rfq_id: u32) -> Result<(), OtcError> {
+    buyer.require_auth();
+
+    let mut rfq = get_rfq(env.clone(), rfq_id)?;
+    if rfq.buyer != buyer {
+        return Err(OtcError::UnauthorizedBuyer);
+    }
+    if rfq.status != RfqStatus::Open {
+        return Err(OtcError::SaleNotActive);
+    }
+
+    rfq.status = RfqStatus::Cancelled;
+    close_rfq(&env, rfq_id, &rfq);
+
+    events::emit_rfq_cancelled_event(&env, rfq_id);
+
+    Ok(())
+}
+
+/// Seller withdraws a quote and lowers the allowance it reserved.
+///
+/// A quote is binding: it can only be withdrawn once it has expired or the RFQ
+/// has expired, been filled or been cancelled.
+pub fn withdraw_quote(env: Env, seller: Address, rfq_id: u32) -> Result<(), OtcError> {
+    seller.require_auth();
+
+    let rfq = get_rfq(env.clone(), rfq_id)?;
+    let quote = match find_quote(&env, rfq_id, &seller) {
+        Some((_, quote)) => quote,
+        None => return Err(OtcError::SaleNotFound),
+    };
+
+    let now = env.ledger().timestamp();
+    if rfq.status == RfqStatus::Open && now < rfq.expires_at && now < quote.expires_at {
+        return Err(OtcError::SaleNotExpired);
+    }
+
+    utils::release(&env, &seller, rfq.asset_id, rfq.token_amount)?;
+
+    remove_quote(&env, rfq_id, &seller);
+
+    events::emit_rfq_quote_withdrawn_event(&env, rfq_id, &seller);
+
+    Ok(())
+}
+
+pub fn get_rfq(env: Env, rfq_id: u32) -> Result<Rfq, OtcError> {
+    match storage::get(&env, &DataKey::Rfq(rfq_id)) {
+        Some(rfq) => Ok(rfq),
+        None => Err(OtcError::SaleNotFound),
+    }
+}
+
+/// IDs of an asset's RFQs that can still be quoted and accepted, oldest first
+pub fn get_open_rfqs(env: Env, asset_id: u64) -> Result<Vec<u32>, OtcError> {
+    live_rfqs(&env, asset_id)
+}
+
+/// Quotes not yet accepted or withdrawn, in the order they were made; expired
+/// quotes stay listed until their sellers withdraw them
+pub fn get_rfq_quotes(env: Env, rfq_id: u32) -> Vec<RfqQuote> {
+    storage::get(&env, &DataKey::RfqQuotes(rfq_id)).unwrap_or(Vec::new(&env))
+}
+
+/// Cheapest quote the buyer could accept right now; the earliest wins a tie
+pub fn get_best_quote(env: Env, rfq_id: u32) -> Option<RfqQuote> {
+    let now = env.ledger().timestamp();
+    let mut best: Option<RfqQuote> = None;
+    for quote in get_rfq_quotes(env.clone(), rfq_id).iter() {
+        if now >= quote.expires_at {
+            continue;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|current| quote.price < current.price)
+        {
+            best = Some(quote);
+        }
+    }
+    best
+}
+
+/// IDs of the RFQs a seller has a quote on that still reserves allowance
+pub fn get_seller_quotes(env: Env, seller: Address) -> Vec<u32> {
+    storage::get(&env, &DataKey::SellerQuotes(seller)).unwrap_or(Vec::new(&env))
+}
+
+/// The asset's open RFQ list with expired entries dropped
+fn live_rfqs(env: &Env, asset_id: u64) -> Result<Vec<u32>, OtcError> {
+    let now = env.ledger().timestamp();
+    let mut live = Vec::new(env);
+    let open: Vec<u32> = storage::get(env, &DataKey::AssetRfqs(asset_id)).unwrap_or(Vec::new(env));
+    for rfq_id in open.iter() {
+        if now < get_rfq(env.clone(), rfq_id)?.expires_at {
+            live.push_back(rfq_id);
+        }
+    }
+    Ok(live)
+}
+
+fn find_quote(env: &Env, rfq_id: u32, seller: &Address) -> Option<(u32, RfqQuote)> {
+    get_rfq_quotes(env.clone(), rfq_id)
+        .iter()
+        .enumerate()
+        .find(|(_, quote)| quote.seller == *seller)
+        .map(|(index, quote)| (index as u32, quote))
+}
+
+fn remove_quote(env: &Env, rfq_id: u32, seller: &Address) {
+    if let Some((index, _)) = find_quote(env, rfq_id, seller) {
+        let mut quotes = get_rfq_quotes(env.clone(), rfq_id);
+        quotes.remove(index);
+        storage::set(env, &DataKey::RfqQuotes(rfq_id), &quotes);
+    }
+
+    let mut seller_quotes = get_seller_quotes(env.clone(), seller.clone());
+    if let Some(index) = seller_quotes.first_index_of(rfq_id) {
+        seller_quotes.remove(index);
+    }
+    storage::set(env, &DataKey::SellerQuotes(seller.clone()), &seller_quotes);
+}
+
+fn close_rfq(env: &Env, rfq_id: u32, rfq: &Rfq) {
+    storage::set(env, &DataKey::Rfq(rfq_id), rfq);
+
+    let key = DataKey::AssetRfqs(rfq.asset_id);
+    let mut open: Vec<u32> = storage::get(env, &key).unwrap_or(Vec::new(env));
+    if let Some(index) = open.first_index_of(rfq_id) {
+        open.remove(index);
+    }
+    storage::set(env, &key, &open);
+}
@@ -0,0 +1,144 @@
+use crate::errors::OtcError;
+use crate::interfaces::{FNFTClient, TradingClient};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+pub fn require_admin(env: &Env, admin: &Address) -> Result<(), OtcError> {
+    admin.require_auth();
+
+    let stored_admin: Option<Address> = storage::get(env, &DataKey::Admin);
+    match stored_admin {
+        Some(stored) if stored == *admin => Ok(()),
+        Some(_) => Err(OtcError::Unauthorized),
+        None => Err(OtcError::NotInitialized),
+    }
+}
+
+pub fn get_trading_contract(env: &Env) -> Result<Address, OtcError> {
+    match storage::get(env, &DataKey::TradingContract) {
+        Some(address) => Ok(address),
+        None => Err(OtcError::NotInitialized),
+    }
+}
+
+pub fn trading(env: &Env) -> Result<TradingClient<'_>, OtcError> {
+    Ok(TradingClient::new(env, &get_trading_contract(env)?))
+}
+
+pub fn get_fnft_contract(env: &Env) -> Result<Address, OtcError> {
+    match storage::get(env, &DataKey::FNFTContract) {
+        Some(address) => Ok(address),
+        None => Err(OtcError::NotInitialized),
+    }
+}
+
+pub fn get_xlm_contract(env: &Env) -> Result<Address, OtcError> {
+    match storage::get(env, &DataKey::XLMContract) {
+        Some(address) => Ok(address),
+        None => Err(OtcError::NotInitialized),
+    }
+}
+
+/// Fail while trading has the asset paused, or fractcore is paused, like a
+/// trade on the trading contract itself would
+pub fn require_not_paused(env: &Env, asset_id: u64) -> Result<(), OtcError> {
+    let trading = trading(env)?;
+    if trading.is_asset_paused(&asset_id) {
+        return Err(OtcError::AssetPaused);
+    }
+    // An unreadable pause state counts as a pause, as it does on trading
+    match trading.try_get_upstream_pause() {
+        Ok(Ok(None)) => Ok(()),
+        _ => Err(OtcError::UpstreamPaused),
+    }
+}
+
+/// Check a listing lifetime against the asset's sale duration limits on trading
+pub fn require_valid_duration(
+    env: &Env,
+    asset_id: u64,
+    duration_seconds: u64,
+) -> Result<(), OtcError> {
+    let limits = trading(env)?.get_asset_sale_duration_limits(&asset_id);
+    if !(limits.min_seconds..=limits.max_seconds).contains(&duration_seconds) {
+        return Err(OtcError::InvalidDuration);
+    }
+    Ok(())
+}
+
+/// Move `token_amount` fractions from the seller, under its allowance to this
+/// contract, to the buyer, and `price` XLM from the buyer to the seller through
+/// trading, which withholds the fee and routes the rest like any sale. The
+/// parties have been checked by the caller. Returns the trade ID trading
+/// recorded.
+pub fn swap(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+) -> Result<u32, OtcError> {
+    if price > i128::MAX as u128 {
+        return Err(OtcError::PriceOverflow);
+    }
+
+    let contract_id = env.current_contract_address();
+    let fnft_client = FNFTClient::new(env, &get_fnft_contract(env)?);
+    if fnft_client.balance_of(seller, &asset_id) < token_amount {
+        return Err(OtcError::SellerInsufficientBalance);
+    }
+    if fnft_client.allowance(seller, &contract_id, &asset_id) < token_amount {
+        return Err(OtcError::InsufficientAllowance);
+    }
+
+    let xlm_client = TokenClient::new(env, &get_xlm_contract(env)?);
+    if xlm_client.balance(buyer) < price as i128 {
+        return Err(OtcError::BuyerInsufficientFunds);
+    }
+
+    let trading = trading(env)?;
+    trading.draw_exit(&contract_id, seller, &asset_id, &token_amount);
+    fnft_client.transfer_from(&contract_id, seller, buyer, &asset_id, &token_amount);
+
+    trading.pay_seller(&contract_id, buyer, seller, &asset_id, &(price as i128));
+
+    Ok(trading.record_trade(
+        &contract_id,
+        seller,
+        buyer,
+        &asset_id,
+        &token_amount,
+        &price,
+    ))
+}
+
+/// Raise the seller's allowance to this contract by `amount`, reserving the
+/// fractions for something the buyer settles without the seller
+pub fn reserve(env: &Env, seller: &Address, asset_id: u64, amount: u64) -> Result<(), OtcError> {
+    let fnft_client = FNFTClient::new(env, &get_fnft_contract(env)?);
+    let contract_id = env.current_contract_address();
+    let current_allowance = fnft_client.allowance(seller, &contract_id, &asset_id);
+    fnft_client.approve(
+        seller,
+        &contract_id,
+        &asset_id,
+        &current_allowance.saturating_add(amount),
+    );
+    Ok(())
+}
+
+/// Lower the seller's allowance to this contract by `amount` once a
+/// reservation is no longer needed
+pub fn release(env: &Env, seller: &Address, asset_id: u64, amount: u64) -> Result<(), OtcError> {
+    let fnft_client = FNFTClient::new(env, &get_fnft_contract(env)?);
+    let contract_id = env.current_contract_address();
+    let current_allowance = fnft_client.allowance(seller, &contract_id, &asset_id);
+    fnft_client.approve(
+        seller,
+        &contract_id,
+        &asset_id,
+        &current_allowance.saturating_sub(amount),
+    );
+    Ok(())
+}
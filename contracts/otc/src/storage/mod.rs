@@ -0,0 +1,214 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// The otc contract holds no XLM: quotes, fiat-priced sales and recurring
+// listings reserve allowance, and buyers pay when they settle.
+pub use placement::*;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RfqStatus {
+    Open,      // Sellers can quote and the buyer can accept until expiry
+    Filled,    // The buyer accepted a quote
+    Cancelled, // Withdrawn by the buyer
+}
+
+// Buyer's request for sellers to quote a price for a block of fractions
+#[contracttype]
+#[derive(Clone)]
+pub struct Rfq {
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub status: RfqStatus,
+}
+
+// Seller's price for an RFQ; the fractions are reserved under the seller's
+// allowance and the quote cannot be withdrawn before expires_at
+#[contracttype]
+#[derive(Clone)]
+pub struct RfqQuote {
+    pub seller: Address,
+    pub price: u128,
+    pub quoted_at: u64,
+    pub expires_at: u64, // Binding until then, never after the RFQ expires
+}
+
+// Template that lists the same lot to the same buyer every interval
+#[contracttype]
+#[derive(Clone)]
+pub struct RecurringListing {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub interval: u64,       // Lifetime of each listing and spacing between rolls
+    pub remaining: u32,      // Listings still to be created by keepers
+    pub last_listed_at: u64, // Timestamp of the listing created most recently
+    pub sold: bool,          // The listing created most recently has been bought
+    pub lapsed: u32,         // Listings replaced unsold, whose allowance is still reserved
+    pub active: bool,
+}
+
+// Sale priced in USD; the XLM amount is fixed at the oracle rate when the
+// buyer settles
+#[contracttype]
+#[derive(Clone)]
+pub struct FiatSale {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub usd_price: u128, // FIAT_DECIMALS
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+// Oracle conversion used to settle a fiat-priced trade
+#[contracttype]
+#[derive(Clone)]
+pub struct FiatSettlement {
+    pub usd_price: u128, // Agreed price, FIAT_DECIMALS
+    pub rate: i128,      // Oracle USD per XLM at settlement
+    pub rate_decimals: u32,
+    pub rate_timestamp: u64,
+}
+
+// What part of a seller's allowance to this contract is reserved for
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentKind {
+    Quote,            // RFQ quote not yet accepted or withdrawn
+    FiatSale,         // Fiat-priced sale not yet settled or withdrawn
+    RecurringListing, // Live, lapsed and not yet created listings of a template
+}
+
+// One reservation against a seller's allowance to this contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Commitment {
+    pub kind: CommitmentKind,
+    pub counterparty: Address, // The RFQ's or sale's buyer
+    pub id: u32,               // RFQ or listing id; 0 for fiat-priced sales
+    pub token_amount: u64,
+    pub expires_at: u64, // 0 for recurring listings
+}
+
+// Seller's allowance to this contract set against what it backs
+#[contracttype]
+#[derive(Clone)]
+pub struct SellerCommitments {
+    pub commitments: Vec<Commitment>,
+    pub total_committed: u64,
+    pub allowance: u64,
+    pub uncommitted: u64, // Allowance nothing accounts for (orphaned)
+    pub shortfall: u64,   // Commitments the allowance no longer covers
+}
+
+// Reflector asset identifier, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+// Reflector price record, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceData {
+    pub price: i128, // USD per XLM, scaled by the oracle's decimals
+    pub timestamp: u64,
+}
+
+// Trading's `SaleDurationLimits`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleDurationLimits {
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+// Trading's `UpstreamPause`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpstreamPause {
+    pub since: u64,
+    pub retry_after: u64,
+}
+
+// Trading's `Conduct`, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Conduct {
+    Cancellation,
+    Expiry,
+    Dispute,
+}
+
+// Storage keys for otc contract
+#[contracttype(export = false)]
+pub enum DataKey {
+    // Core contract data
+    Admin,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
+    TradingContract,
+    FNFTContract, // Read from the trading contract at initialization
+    XLMContract,  // Read from the trading contract at initialization
+
+    // Requests for quotes
+    RfqCounter,
+    Rfq(u32),              // rfq_id -> Rfq
+    RfqQuotes(u32),        // rfq_id -> Vec<RfqQuote> (live quotes, oldest first)
+    AssetRfqs(u64),        // asset_id -> Vec<u32> (open rfq_ids, oldest first)
+    SellerQuotes(Address), // seller -> Vec<u32> (rfq_ids with a live quote)
+
+    // Recurring listing templates
+    ListingCounter,
+    RecurringListing(u32),   // listing_id -> RecurringListing
+    SellerListings(Address), // seller -> Vec<u32> (listing_ids)
+
+    // Fiat-priced sales
+    PriceOracle,                     // XLM/USD oracle (Reflector interface)
+    FiatSale(Address, Address, u64), // (seller, buyer, asset_id) -> FiatSale
+    SellerFiatSales(Address),        // seller -> Vec<(Address, u64)> (buyer, asset_id)
+    FiatTrade(u32),                  // trade_id -> FiatSettlement
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("otc");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::TradingContract
+            | DataKey::FNFTContract
+            | DataKey::XLMContract
+            | DataKey::RfqCounter
+            | DataKey::ListingCounter
+            | DataKey::PriceOracle => Placement::Instance,
+            DataKey::Rfq(..)
+            | DataKey::RfqQuotes(..)
+            | DataKey::AssetRfqs(..)
+            | DataKey::SellerQuotes(..)
+            | DataKey::RecurringListing(..)
+            | DataKey::SellerListings(..)
+            | DataKey::FiatSale(..)
+            | DataKey::SellerFiatSales(..)
+            | DataKey::FiatTrade(..) => Placement::Persistent,
+        }
+    }
+}
+
+// Constants
+pub const MAX_OPEN_RFQS_PER_ASSET: u32 = 25;
+pub const MAX_QUOTES_PER_RFQ: u32 = 20;
+pub const MAX_RECURRING_LISTINGS: u32 = 52; // A year of weekly listings
+pub const MAX_LISTINGS_PER_SELLER: u32 = 20; // Recurring templates a seller keeps at once
+pub const MAX_FIAT_SALES_PER_SELLER: u32 = 50;
+pub const FIAT_DECIMALS: u32 = 7; // USD prices use the same scale as stroops
+pub const MAX_ORACLE_PRICE_AGE: u64 = 900; // Oracle rates older than 15 minutes are refused
@@ -0,0 +1,3 @@
+// Tests seed and inspect storage directly, whatever class a key is placed in
+#![allow(clippy::disallowed_methods)]
+pub mod unit_tests;
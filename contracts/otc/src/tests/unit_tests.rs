@@ -0,0 +1,669 @@
+#![cfg(test)]
+
+use crate::contract::*;
+use crate::errors::OtcError;
+use crate::storage::{CommitmentKind, MAX_QUOTES_PER_RFQ};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+// Import the FNFT and trading contracts for testing
+mod fnft {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+mod trading {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/trading.wasm");
+}
+
+// Reflector-style XLM/USD oracle with 14 decimals and a settable rate
+mod mock_oracle {
+    use crate::storage::{OracleAsset, PriceData};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, price: i128, timestamp: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("price"), &PriceData { price, timestamp });
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            14
+        }
+
+        pub fn lastprice(env: Env, _asset: OracleAsset) -> Option<PriceData> {
+            env.storage().instance().get(&symbol_short!("price"))
+        }
+    }
+}
+
+const DAY: u64 = 86400;
+const DEFAULT_SALE_DURATION: u64 = 7 * DAY;
+
+struct OtcSetup {
+    env: Env,
+    admin: Address,
+    otc_client: OtcContractClient<'static>,
+    trading_client: trading::Client<'static>,
+    fnft_client: fnft::Client<'static>,
+    xlm_client: token::Client<'static>,
+}
+
+fn setup() -> OtcSetup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
+    let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
+
+    let xlm_sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let xlm_contract_id = xlm_sac.address();
+
+    let trading_contract_id = env.register(
+        trading::WASM,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            xlm_contract_id.clone(),
+        ),
+    );
+    let trading_client = trading::Client::new(&env, &trading_contract_id);
+
+    let otc_contract_id = env.register(OtcContract, (admin.clone(), trading_contract_id.clone()));
+    let otc_client = OtcContractClient::new(&env, &otc_contract_id);
+    trading_client.set_extension(&admin, &otc_contract_id, &true);
+
+    let xlm_client = token::Client::new(&env, &xlm_contract_id);
+
+    OtcSetup {
+        env,
+        admin,
+        otc_client,
+        trading_client,
+        fnft_client,
+        xlm_client,
+    }
+}
+
+impl OtcSetup {
+    fn account(&self, xlm: i128) -> Address {
+        let address = Address::generate(&self.env);
+        if xlm > 0 {
+            token::StellarAssetClient::new(&self.env, &self.xlm_client.address)
+                .mint(&address, &xlm);
+        }
+        address
+    }
+
+    fn advance_time(&self, seconds: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
+
+    fn allowance(&self, seller: &Address, asset_id: u64) -> u64 {
+        self.fnft_client
+            .allowance(seller, &self.otc_client.address, &asset_id)
+    }
+}
+
+#[test]
+fn test_initialization_reads_trading_configuration() {
+    let s = setup();
+
+    assert_eq!(
+        s.otc_client.get_trading_contract(),
+        s.trading_client.address
+    );
+    assert!(s.otc_client.get_init_config_hash().is_some());
+}
+
+#[test]
+fn test_unregistered_otc_cannot_settle() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &100, &7200);
+    s.otc_client.submit_quote(&seller, &rfq_id, &5000, &3600);
+
+    s.trading_client
+        .set_extension(&s.admin, &s.otc_client.address, &false);
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &seller, &5000),
+        Err(Ok(OtcError::Unauthorized))
+    );
+    assert_eq!(s.xlm_client.balance(&buyer), 10000);
+    assert_eq!(s.fnft_client.balance_of(&seller, &asset_id), 1000);
+}
+
+// === RFQ Tests ===
+
+#[test]
+fn test_rfq_quotes_reserve_allowance_and_settle_best() {
+    let s = setup();
+    let cheap = s.account(0);
+    let dear = s.account(0);
+    let buyer = s.account(10000);
+
+    let asset_id = s.fnft_client.mint(&cheap, &1000);
+    s.fnft_client.transfer(&cheap, &dear, &asset_id, &400);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &300, &7200);
+    assert_eq!(
+        s.otc_client.get_open_rfqs(&asset_id),
+        Vec::from_array(&s.env, [rfq_id])
+    );
+
+    // Quotes cannot outlive the request
+    assert_eq!(
+        s.otc_client.try_submit_quote(&dear, &rfq_id, &2000, &7201),
+        Err(Ok(OtcError::InvalidDuration))
+    );
+    assert_eq!(
+        s.otc_client.try_submit_quote(&buyer, &rfq_id, &2000, &3600),
+        Err(Ok(OtcError::SelfTrade))
+    );
+    s.otc_client.submit_quote(&dear, &rfq_id, &2000, &3600);
+    s.otc_client.submit_quote(&cheap, &rfq_id, &1500, &1800);
+    assert_eq!(
+        s.otc_client.try_submit_quote(&cheap, &rfq_id, &1400, &1800),
+        Err(Ok(OtcError::SaleAlreadyExists))
+    );
+    assert_eq!(s.otc_client.get_rfq_quotes(&rfq_id).len(), 2);
+    assert_eq!(s.otc_client.get_best_quote(&rfq_id).unwrap().seller, cheap);
+
+    // Each quote reserves the RFQ's fractions under its seller's allowance
+    assert_eq!(s.allowance(&dear, asset_id), 300);
+    let commitments = s.otc_client.get_seller_commitments(&dear, &asset_id);
+    assert_eq!(
+        commitments.commitments.get(0).unwrap().kind,
+        CommitmentKind::Quote
+    );
+    assert_eq!(commitments.uncommitted, 0);
+
+    // Binding until the quote expires
+    assert_eq!(
+        s.otc_client.try_withdraw_quote(&dear, &rfq_id),
+        Err(Ok(OtcError::SaleNotExpired))
+    );
+
+    // Once the cheaper quote lapses the dearer one is the best left
+    s.advance_time(1800);
+    assert_eq!(s.otc_client.get_best_quote(&rfq_id).unwrap().seller, dear);
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &cheap, &1500),
+        Err(Ok(OtcError::SaleExpired))
+    );
+    assert_eq!(
+        s.otc_client.try_accept_quote(&buyer, &rfq_id, &dear, &1999),
+        Err(Ok(OtcError::PriceMismatch))
+    );
+
+    let trade_id = s.otc_client.accept_quote(&buyer, &rfq_id, &dear, &2000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 300);
+    assert_eq!(s.fnft_client.balance_of(&dear, &asset_id), 100);
+    assert_eq!(s.xlm_client.balance(&dear), 2000);
+    assert_eq!(s.trading_client.get_trade_history(&trade_id).price, 2000);
+    assert_eq!(s.allowance(&dear, asset_id), 0);
+    assert!(s.otc_client.get_seller_quotes(&dear).is_empty());
+    assert!(s.otc_client.get_open_rfqs(&asset_id).is_empty());
+
+    // Cancelled and expired requests drop out of the open list
+    let cancelled = s.otc_client.request_quotes(&buyer, &asset_id, &10, &3600);
+    let expiring = s.otc_client.request_quotes(&buyer, &asset_id, &10, &3600);
+    s.otc_client.cancel_rfq(&buyer, &cancelled);
+    assert_eq!(
+        s.otc_client.get_open_rfqs(&asset_id),
+        Vec::from_array(&s.env, [expiring])
+    );
+    s.advance_time(3600);
+    assert!(s.otc_client.get_open_rfqs(&asset_id).is_empty());
+}
+
+#[test]
+fn test_rfq_expiry_refuses_quotes_and_acceptance() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &100, &3600);
+    s.otc_client.submit_quote(&seller, &rfq_id, &5000, &3600);
+
+    s.advance_time(3600);
+    let late = s.account(0);
+    s.fnft_client.transfer(&seller, &late, &asset_id, &200);
+    assert_eq!(
+        s.otc_client.try_submit_quote(&late, &rfq_id, &4000, &60),
+        Err(Ok(OtcError::SaleExpired))
+    );
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &seller, &5000),
+        Err(Ok(OtcError::SaleExpired))
+    );
+    assert!(s.otc_client.get_best_quote(&rfq_id).is_none());
+    assert_eq!(s.xlm_client.balance(&buyer), 10000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 0);
+}
+
+#[test]
+fn test_rfq_second_fill_is_refused() {
+    let s = setup();
+    let first = s.account(0);
+    let second = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&first, &1000);
+    s.fnft_client.transfer(&first, &second, &asset_id, &500);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &100, &7200);
+    s.otc_client.submit_quote(&first, &rfq_id, &3000, &3600);
+    s.otc_client.submit_quote(&second, &rfq_id, &3500, &3600);
+
+    s.otc_client.accept_quote(&buyer, &rfq_id, &first, &3000);
+
+    // Neither the filled quote nor the one left over can settle again
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &first, &3000),
+        Err(Ok(OtcError::SaleNotActive))
+    );
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &second, &3500),
+        Err(Ok(OtcError::SaleNotActive))
+    );
+    assert_eq!(
+        s.otc_client.try_cancel_rfq(&buyer, &rfq_id),
+        Err(Ok(OtcError::SaleNotActive))
+    );
+    assert_eq!(
+        s.otc_client.try_submit_quote(&second, &rfq_id, &1000, &60),
+        Err(Ok(OtcError::SaleNotActive))
+    );
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 100);
+    assert_eq!(s.xlm_client.balance(&buyer), 7000);
+    assert_eq!(s.trading_client.get_trade_count(), 1);
+}
+
+#[test]
+fn test_withdrawn_quote_releases_allowance_and_cannot_be_accepted() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let stranger = s.account(0);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &100, &7200);
+    s.otc_client.submit_quote(&seller, &rfq_id, &5000, &1800);
+    assert_eq!(s.allowance(&seller, asset_id), 100);
+
+    // Only the buyer withdraws the request; a quote without one is not found
+    assert_eq!(
+        s.otc_client.try_cancel_rfq(&stranger, &rfq_id),
+        Err(Ok(OtcError::UnauthorizedBuyer))
+    );
+    assert_eq!(
+        s.otc_client.try_withdraw_quote(&stranger, &rfq_id),
+        Err(Ok(OtcError::SaleNotFound))
+    );
+
+    // The quote lapses before the request and its seller takes the allowance back
+    s.advance_time(1800);
+    s.otc_client.withdraw_quote(&seller, &rfq_id);
+    assert_eq!(s.allowance(&seller, asset_id), 0);
+    assert!(s.otc_client.get_rfq_quotes(&rfq_id).is_empty());
+    assert!(s.otc_client.get_seller_quotes(&seller).is_empty());
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &seller, &5000),
+        Err(Ok(OtcError::SaleNotFound))
+    );
+
+    // A request the buyer cancels frees its quotes straight away
+    s.otc_client.submit_quote(&seller, &rfq_id, &4500, &1800);
+    s.otc_client.cancel_rfq(&buyer, &rfq_id);
+    s.otc_client.withdraw_quote(&seller, &rfq_id);
+    assert_eq!(s.allowance(&seller, asset_id), 0);
+    assert_eq!(s.xlm_client.balance(&buyer), 10000);
+}
+
+#[test]
+fn test_rfq_requests_and_quotes_are_capped() {
+    let s = setup();
+    let holder = s.account(0);
+    let buyer = s.account(0);
+    let asset_id = s.fnft_client.mint(&holder, &1000);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &1, &7200);
+    for _ in 0..MAX_QUOTES_PER_RFQ {
+        let seller = s.account(0);
+        s.fnft_client.transfer(&holder, &seller, &asset_id, &1);
+        s.otc_client.submit_quote(&seller, &rfq_id, &100, &3600);
+    }
+    assert_eq!(
+        s.otc_client.try_submit_quote(&holder, &rfq_id, &100, &3600),
+        Err(Ok(OtcError::TooManyQuotes))
+    );
+
+    for _ in 1..25 {
+        s.otc_client.request_quotes(&buyer, &asset_id, &1, &7200);
+    }
+    assert_eq!(
+        s.otc_client
+            .try_request_quotes(&buyer, &asset_id, &1, &7200),
+        Err(Ok(OtcError::TooManyRequests))
+    );
+}
+
+#[test]
+fn test_paused_asset_blocks_quote_acceptance() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let rfq_id = s.otc_client.request_quotes(&buyer, &asset_id, &100, &7200);
+    s.otc_client.submit_quote(&seller, &rfq_id, &5000, &3600);
+
+    s.trading_client
+        .set_asset_paused(&s.admin, &asset_id, &true);
+    assert_eq!(
+        s.otc_client
+            .try_accept_quote(&buyer, &rfq_id, &seller, &5000),
+        Err(Ok(OtcError::AssetPaused))
+    );
+    assert_eq!(
+        s.otc_client
+            .try_request_quotes(&buyer, &asset_id, &10, &3600),
+        Err(Ok(OtcError::AssetPaused))
+    );
+}
+
+// === Recurring Listing Tests ===
+
+#[test]
+fn test_recurring_listing_rolls_until_exhausted() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(10000);
+    let keeper = s.account(100);
+
+    s.trading_client.set_keeper_bounty(&s.admin, &10);
+    s.trading_client.fund_keeper_pool(&keeper, &100);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let listing_id = s
+        .otc_client
+        .create_recurring_listing(&seller, &buyer, &asset_id, &100, &5000, &3600, &3);
+
+    // Allowance covers all three listings, the first one is live
+    assert_eq!(s.allowance(&seller, asset_id), 300);
+    assert_eq!(s.otc_client.get_seller_listings(&seller).len(), 1);
+
+    s.otc_client.buy_listing(&buyer, &listing_id, &100, &5000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 100);
+    assert_eq!(
+        s.otc_client
+            .try_buy_listing(&buyer, &listing_id, &100, &5000),
+        Err(Ok(OtcError::ListingInactive))
+    );
+
+    // Not due until the first listing's window has passed
+    assert_eq!(
+        s.otc_client.try_roll_listing(&keeper, &listing_id),
+        Err(Ok(OtcError::ListingNotDue))
+    );
+
+    s.advance_time(3601);
+    let keeper_before = s.xlm_client.balance(&keeper);
+    assert_eq!(s.otc_client.roll_listing(&keeper, &listing_id), 10);
+    assert_eq!(s.xlm_client.balance(&keeper), keeper_before + 10);
+
+    // The second listing lapses unsold and is replaced by the last one
+    s.advance_time(3601);
+    assert_eq!(
+        s.otc_client
+            .try_buy_listing(&buyer, &listing_id, &100, &5000),
+        Err(Ok(OtcError::SaleExpired))
+    );
+    s.otc_client.roll_listing(&keeper, &listing_id);
+
+    let listing = s.otc_client.get_recurring_listing(&listing_id);
+    assert_eq!(listing.remaining, 0);
+    assert_eq!(listing.lapsed, 1);
+    assert_eq!(s.allowance(&seller, asset_id), 200);
+    let commitments = s.otc_client.get_seller_commitments(&seller, &asset_id);
+    assert_eq!(
+        commitments.commitments.get(0).unwrap().kind,
+        CommitmentKind::RecurringListing
+    );
+    assert_eq!(commitments.total_committed, 200);
+    assert_eq!(commitments.shortfall, 0);
+
+    s.advance_time(3601);
+    assert_eq!(
+        s.otc_client.try_roll_listing(&keeper, &listing_id),
+        Err(Ok(OtcError::ListingExhausted))
+    );
+}
+
+#[test]
+fn test_recurring_listing_parties_earn_no_bounty() {
+    let s = setup();
+    let seller = s.account(100);
+    let buyer = s.account(0);
+
+    s.trading_client.set_keeper_bounty(&s.admin, &10);
+    s.trading_client.fund_keeper_pool(&seller, &100);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    let listing_id = s
+        .otc_client
+        .create_recurring_listing(&seller, &buyer, &asset_id, &100, &5000, &3600, &3);
+
+    s.advance_time(3601);
+    assert_eq!(s.otc_client.roll_listing(&seller, &listing_id), 0);
+    s.advance_time(3601);
+    assert_eq!(s.otc_client.roll_listing(&buyer, &listing_id), 0);
+    assert_eq!(s.trading_client.get_keeper_pool(), 100);
+}
+
+#[test]
+fn test_cancel_recurring_listing_releases_allowance() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(0);
+    let keeper = s.account(0);
+
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+    // An unrelated quote shares the seller's allowance for the asset
+    let rfq_id = s.otc_client.request_quotes(&keeper, &asset_id, &50, &7200);
+    s.otc_client.submit_quote(&seller, &rfq_id, &1000, &3600);
+    let listing_id = s
+        .otc_client
+        .create_recurring_listing(&seller, &buyer, &asset_id, &100, &5000, &3600, &4);
+    assert_eq!(s.allowance(&seller, asset_id), 450);
+
+    assert_eq!(
+        s.otc_client
+            .try_cancel_recurring_listing(&buyer, &listing_id),
+        Err(Ok(OtcError::UnauthorizedSeller))
+    );
+    s.otc_client.cancel_recurring_listing(&seller, &listing_id);
+
+    assert_eq!(s.allowance(&seller, asset_id), 50);
+    assert!(!s.otc_client.get_recurring_listing(&listing_id).active);
+    assert!(s.otc_client.get_seller_listings(&seller).is_empty());
+    assert_eq!(
+        s.otc_client.try_roll_listing(&keeper, &listing_id),
+        Err(Ok(OtcError::ListingInactive))
+    );
+    assert_eq!(
+        s.otc_client.try_get_recurring_listing(&99).err(),
+        Some(Ok(OtcError::ListingNotFound))
+    );
+}
+
+// === Fiat-Priced Sale Tests ===
+
+#[test]
+fn test_fiat_priced_sale_settles_at_oracle_rate() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(1_000_000_000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let oracle_id = s.env.register(mock_oracle::MockOracle, ());
+    let oracle = mock_oracle::MockOracleClient::new(&s.env, &oracle_id);
+    let usd_price = 50_000_000u128; // $5.00
+
+    // No oracle configured yet
+    assert_eq!(
+        s.otc_client.try_confirm_fiat_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &100,
+            &usd_price,
+            &DEFAULT_SALE_DURATION
+        ),
+        Err(Ok(OtcError::NoPriceData))
+    );
+
+    s.otc_client
+        .set_price_oracle(&s.admin, &Some(oracle_id.clone()));
+    oracle.set_price(&10_000_000_000_000, &s.env.ledger().timestamp()); // $0.10 per XLM
+    s.otc_client.confirm_fiat_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &usd_price,
+        &DEFAULT_SALE_DURATION,
+    );
+    assert_eq!(s.allowance(&seller, asset_id), 100);
+    assert_eq!(
+        s.otc_client.try_confirm_fiat_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &100,
+            &usd_price,
+            &DEFAULT_SALE_DURATION
+        ),
+        Err(Ok(OtcError::SaleAlreadyExists))
+    );
+
+    // XLM drops to $0.08, so $5 now costs 62.5 XLM
+    s.advance_time(600);
+    oracle.set_price(&8_000_000_000_000, &s.env.ledger().timestamp());
+    assert_eq!(
+        s.otc_client.try_finish_fiat_transaction(
+            &buyer,
+            &seller,
+            &asset_id,
+            &100,
+            &usd_price,
+            &600_000_000
+        ),
+        Err(Ok(OtcError::PriceMismatch))
+    );
+
+    // A rate older than the staleness bound is refused
+    s.advance_time(1000);
+    assert_eq!(
+        s.otc_client.try_finish_fiat_transaction(
+            &buyer,
+            &seller,
+            &asset_id,
+            &100,
+            &usd_price,
+            &700_000_000
+        ),
+        Err(Ok(OtcError::NoPriceData))
+    );
+
+    oracle.set_price(&8_000_000_000_000, &s.env.ledger().timestamp());
+    let paid = s.otc_client.finish_fiat_transaction(
+        &buyer,
+        &seller,
+        &asset_id,
+        &100,
+        &usd_price,
+        &700_000_000,
+    );
+    assert_eq!(paid, 625_000_000);
+    assert_eq!(s.xlm_client.balance(&seller), 625_000_000);
+    assert_eq!(s.fnft_client.balance_of(&buyer, &asset_id), 100);
+    assert!(s.otc_client.get_seller_fiat_sales(&seller).is_empty());
+
+    let trade_id = s.trading_client.get_trade_count();
+    assert_eq!(
+        s.trading_client.get_trade_history(&trade_id).price,
+        625_000_000
+    );
+    let settlement = s.otc_client.get_fiat_settlement(&trade_id).unwrap();
+    assert_eq!(settlement.usd_price, usd_price);
+    assert_eq!(settlement.rate, 8_000_000_000_000);
+    assert_eq!(settlement.rate_decimals, 14);
+}
+
+#[test]
+fn test_fiat_priced_sale_expiry_and_withdrawal() {
+    let s = setup();
+    let seller = s.account(0);
+    let buyer = s.account(1_000_000_000);
+    let asset_id = s.fnft_client.mint(&seller, &1000);
+
+    let oracle_id = s.env.register(mock_oracle::MockOracle, ());
+    let oracle = mock_oracle::MockOracleClient::new(&s.env, &oracle_id);
+    s.otc_client.set_price_oracle(&s.admin, &Some(oracle_id));
+    oracle.set_price(&10_000_000_000_000, &s.env.ledger().timestamp());
+
+    s.otc_client
+        .confirm_fiat_sale(&seller, &buyer, &asset_id, &100, &50_000_000, &DAY);
+    let commitments = s.otc_client.get_seller_commitments(&seller, &asset_id);
+    assert_eq!(
+        commitments.commitments.get(0).unwrap().kind,
+        CommitmentKind::FiatSale
+    );
+
+    s.advance_time(DAY + 1);
+    oracle.set_price(&10_000_000_000_000, &s.env.ledger().timestamp());
+    assert_eq!(
+        s.otc_client.try_finish_fiat_transaction(
+            &buyer,
+            &seller,
+            &asset_id,
+            &100,
+            &50_000_000,
+            &1_000_000_000
+        ),
+        Err(Ok(OtcError::SaleExpired))
+    );
+
+    s.otc_client.withdraw_fiat_sale(&seller, &buyer, &asset_id);
+    assert_eq!(s.allowance(&seller, asset_id), 0);
+    assert_eq!(
+        s.trading_client.get_reputation(&seller).cancelled_proposals,
+        1
+    );
+    assert_eq!(
+        s.otc_client
+            .try_get_fiat_sale(&seller, &buyer, &asset_id)
+            .err(),
+        Some(Ok(OtcError::SaleNotFound))
+    );
+    assert_eq!(s.xlm_client.balance(&buyer), 1_000_000_000);
+}
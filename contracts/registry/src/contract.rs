@@ -0,0 +1,145 @@
+use crate::errors::RegistryError;
+use crate::methods::{admin, initialization, names, queries, schedule};
+use crate::storage::ScheduledAction;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+
+#[contract]
+pub struct RegistryContract;
+
+#[contractimpl]
+impl RegistryContract {
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        fee_token: Address,
+        name_fee: i128,
+    ) -> Result<(), RegistryError> {
+        initialization::construct(env, admin, fee_token, name_fee)
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fee_token: Address,
+        name_fee: i128,
+    ) -> Result<(), RegistryError> {
+        initialization::initialize(env, admin, fee_token, name_fee)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
+    }
+
+    /// Claim a display name for the caller (replaces any previous name)
+    pub fn claim_name(env: Env, owner: Address, name: String) -> Result<(), RegistryError> {
+        names::claim_name(env, owner, name)
+    }
+
+    /// Release the caller's display name
+    pub fn release_name(env: Env, owner: Address) -> Result<(), RegistryError> {
+        names::release_name(env, owner)
+    }
+
+    /// Look up the address holding a name
+    pub fn resolve(env: Env, name: String) -> Option<Address> {
+        queries::resolve(env, name)
+    }
+
+    /// Look up the name held by an address
+    pub fn reverse(env: Env, address: Address) -> Option<String> {
+        queries::reverse(env, address)
+    }
+
+    /// Admin removes a name (moderation)
+    pub fn revoke_name(env: Env, admin: Address, name: String) -> Result<(), RegistryError> {
+        admin::revoke_name(env, admin, name)
+    }
+
+    /// Admin updates the per-claim fee
+    pub fn set_name_fee(env: Env, admin: Address, name_fee: i128) -> Result<(), RegistryError> {
+        admin::set_name_fee(env, admin, name_fee)
+    }
+
+    /// Admin withdraws collected claim fees
+    pub fn withdraw_fees(
+        env: Env,
+        admin: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), RegistryError> {
+        admin::withdraw_fees(env, admin, to, amount)
+    }
+
+    /// Admin allows (or stops) a suite contract scheduling keeper actions
+    pub fn set_scheduler(
+        env: Env,
+        admin: Address,
+        contract: Address,
+        approved: bool,
+    ) -> Result<(), RegistryError> {
+        schedule::set_scheduler(env, admin, contract, approved)
+    }
+
+    pub fn is_scheduler(env: Env, contract: Address) -> bool {
+        schedule::is_scheduler(env, contract)
+    }
+
+    /// An approved contract schedules (or reschedules) an action for keepers
+    pub fn schedule(
+        env: Env,
+        contract: Address,
+        action_type: Symbol,
+        key: u64,
+        due_at: u64,
+    ) -> Result<(), RegistryError> {
+        schedule::schedule(env, contract, action_type, key, due_at)
+    }
+
+    /// An approved contract removes an action that ran or no longer applies
+    pub fn unschedule(
+        env: Env,
+        contract: Address,
+        action_type: Symbol,
+        key: u64,
+    ) -> Result<bool, RegistryError> {
+        schedule::unschedule(env, contract, action_type, key)
+    }
+
+    /// Due time of a scheduled action
+    pub fn get_scheduled(
+        env: Env,
+        contract: Address,
+        action_type: Symbol,
+        key: u64,
+    ) -> Option<u64> {
+        schedule::get_scheduled(env, contract, action_type, key)
+    }
+
+    /// Actions across the suite a keeper should execute next, earliest first
+    pub fn due_actions(env: Env, now: u64, limit: u32) -> Vec<ScheduledAction> {
+        schedule::due_actions(env, now, limit)
+    }
+
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), RegistryError> {
+        admin::transfer_admin(env, current_admin, new_admin)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, RegistryError> {
+        admin::get_admin(env)
+    }
+
+    pub fn get_name_fee(env: Env) -> i128 {
+        queries::get_name_fee(env)
+    }
+
+    pub fn get_collected_fees(env: Env) -> i128 {
+        queries::get_collected_fees(env)
+    }
+}
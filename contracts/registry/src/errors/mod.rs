@@ -0,0 +1,19 @@
+use soroban_sdk::contracterror;
+
+/// Error codes returned by registry entrypoints
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidName = 4,
+    NameTaken = 5,
+    NameNotFound = 6,
+    NoNameRegistered = 7,
+    InvalidFee = 8,
+    InvalidAmount = 9,
+    InsufficientFees = 10,
+    ScheduleFull = 11,
+}
@@ -0,0 +1,68 @@
+use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+
+/// Contract initialization
+pub fn emit_init(env: &Env, admin: Address, fee_token: Address, name_fee: i128) {
+    env.events()
+        .publish((symbol_short!("init"),), (admin, fee_token, name_fee));
+}
+
+/// Name claimed by an address
+pub fn emit_name_claimed(env: &Env, owner: Address, name: String, fee: i128) {
+    env.events()
+        .publish((symbol_short!("claimed"), owner), (name, fee));
+}
+
+/// Name released by its owner
+pub fn emit_name_released(env: &Env, owner: Address, name: String) {
+    env.events()
+        .publish((symbol_short!("released"), owner), name);
+}
+
+/// Name removed by the admin
+pub fn emit_name_revoked(env: &Env, admin: Address, owner: Address, name: String) {
+    env.events()
+        .publish((symbol_short!("revoked"), owner), (admin, name));
+}
+
+/// Claim fee changed
+pub fn emit_fee_updated(env: &Env, old_fee: i128, new_fee: i128) {
+    env.events()
+        .publish((symbol_short!("fee_upd"),), (old_fee, new_fee));
+}
+
+/// Collected fees withdrawn by the admin
+pub fn emit_fees_withdrawn(env: &Env, to: Address, amount: i128) {
+    env.events().publish((symbol_short!("fee_wdr"), to), amount);
+}
+
+/// Admin role transfer
+pub fn emit_admin_transfer(env: &Env, old_admin: Address, new_admin: Address) {
+    env.events()
+        .publish((symbol_short!("admin"), old_admin, new_admin), ());
+}
+
+/// Contract allowed or no longer allowed to schedule keeper actions
+pub fn emit_scheduler_updated(env: &Env, contract: Address, approved: bool) {
+    env.events()
+        .publish((symbol_short!("sched_upd"), contract), approved);
+}
+
+/// Keeper action scheduled or moved to a new due time
+pub fn emit_action_scheduled(
+    env: &Env,
+    contract: Address,
+    action_type: Symbol,
+    key: u64,
+    due_at: u64,
+) {
+    env.events().publish(
+        (symbol_short!("scheduled"), contract),
+        (action_type, key, due_at),
+    );
+}
+
+/// Keeper action removed by its contract
+pub fn emit_action_unscheduled(env: &Env, contract: Address, action_type: Symbol, key: u64) {
+    env.events()
+        .publish((symbol_short!("unsched"), contract), (action_type, key));
+}
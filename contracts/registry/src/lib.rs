@@ -0,0 +1,13 @@
+#![no_std]
+
+pub mod contract;
+pub mod errors;
+pub mod events;
+pub mod methods;
+pub mod storage;
+
+#[cfg(test)]
+pub mod tests;
+
+pub use contract::RegistryContract;
+pub use errors::RegistryError;
@@ -0,0 +1,92 @@
+use crate::errors::RegistryError;
+use crate::events;
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Address, Env, String};
+
+pub fn get_admin(env: Env) -> Result<Address, RegistryError> {
+    storage::get(&env, &DataKey::Admin).ok_or(RegistryError::NotInitialized)
+}
+
+pub fn require_admin_auth(env: Env, caller: Address) -> Result<(), RegistryError> {
+    caller.require_auth();
+
+    let admin = get_admin(env)?;
+    if caller != admin {
+        return Err(RegistryError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Transfer admin role (only current admin)
+pub fn transfer_admin(
+    env: Env,
+    current_admin: Address,
+    new_admin: Address,
+) -> Result<(), RegistryError> {
+    require_admin_auth(env.clone(), current_admin.clone())?;
+
+    storage::set(&env, &DataKey::Admin, &new_admin);
+
+    events::emit_admin_transfer(&env, current_admin, new_admin);
+
+    Ok(())
+}
+
+/// Change the fee charged per claim (0 makes claims free)
+pub fn set_name_fee(env: Env, admin: Address, name_fee: i128) -> Result<(), RegistryError> {
+    require_admin_auth(env.clone(), admin)?;
+
+    if name_fee < 0 {
+        return Err(RegistryError::InvalidFee);
+    }
+
+    let old_fee = utils::get_name_fee(&env);
+    storage::set(&env, &DataKey::NameFee, &name_fee);
+
+    events::emit_fee_updated(&env, old_fee, name_fee);
+
+    Ok(())
+}
+
+/// Moderation: remove an offensive or impersonating name from its owner
+pub fn revoke_name(env: Env, admin: Address, name: String) -> Result<(), RegistryError> {
+    require_admin_auth(env.clone(), admin.clone())?;
+
+    let owner: Address =
+        storage::get(&env, &DataKey::NameOwner(name.clone())).ok_or(RegistryError::NameNotFound)?;
+
+    utils::remove_record(&env, &owner, &name);
+
+    events::emit_name_revoked(&env, admin, owner, name);
+
+    Ok(())
+}
+
+/// Withdraw collected claim fees
+pub fn withdraw_fees(
+    env: Env,
+    admin: Address,
+    to: Address,
+    amount: i128,
+) -> Result<(), RegistryError> {
+    require_admin_auth(env.clone(), admin)?;
+
+    if amount <= 0 {
+        return Err(RegistryError::InvalidAmount);
+    }
+
+    let collected = utils::get_collected_fees(&env);
+    if amount > collected {
+        return Err(RegistryError::InsufficientFees);
+    }
+
+    storage::set(&env, &DataKey::CollectedFees, &(collected - amount));
+
+    let token = utils::get_fee_token(&env)?;
+    TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+    events::emit_fees_withdrawn(&env, to, amount);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::errors::RegistryError;
+use crate::events;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
+
+/// Initialize the registry with the token used to pay claim fees
+pub fn initialize(
+    env: Env,
+    admin: Address,
+    fee_token: Address,
+    name_fee: i128,
+) -> Result<(), RegistryError> {
+    admin.require_auth();
+    configure(env, admin, fee_token, name_fee)
+}
+
+/// Deploy-time initialization. The constructor runs atomically with contract
+/// creation, so no one can initialize first and the admin's auth is not needed.
+pub fn construct(
+    env: Env,
+    admin: Address,
+    fee_token: Address,
+    name_fee: i128,
+) -> Result<(), RegistryError> {
+    configure(env, admin, fee_token, name_fee)
+}
+
+fn configure(
+    env: Env,
+    admin: Address,
+    fee_token: Address,
+    name_fee: i128,
+) -> Result<(), RegistryError> {
+    if storage::has_or_bare(&env, &DataKey::Admin) {
+        return Err(RegistryError::AlreadyInitialized);
+    }
+
+    if name_fee < 0 {
+        return Err(RegistryError::InvalidFee);
+    }
+
+    storage::mark_namespaced(&env);
+
+    storage::set(&env, &DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::FeeToken, &fee_token);
+    storage::set(&env, &DataKey::NameFee, &name_fee);
+
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(admin.clone(), fee_token.clone(), name_fee).to_xdr(&env))
+        .into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
+
+    events::emit_init(&env, admin, fee_token, name_fee);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
+}
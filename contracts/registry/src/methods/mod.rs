@@ -0,0 +1,6 @@
+pub mod admin;
+pub mod initialization;
+pub mod names;
+pub mod queries;
+pub mod schedule;
+pub mod utils;
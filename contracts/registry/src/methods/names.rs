@@ -0,0 +1,57 @@
+use crate::errors::RegistryError;
+use crate::events;
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Address, Env, String};
+
+/// Claim a unique display name. An address holds at most one name, so
+/// claiming a new one releases the previous name.
+pub fn claim_name(env: Env, owner: Address, name: String) -> Result<(), RegistryError> {
+    owner.require_auth();
+
+    utils::validate_name(&name)?;
+
+    let name_key = DataKey::NameOwner(name.clone());
+    if let Some(current) = storage::get::<_, Address>(&env, &name_key) {
+        if current == owner {
+            return Ok(());
+        }
+        return Err(RegistryError::NameTaken);
+    }
+
+    let fee = utils::get_name_fee(&env);
+    if fee > 0 {
+        let token = utils::get_fee_token(&env)?;
+        TokenClient::new(&env, &token).transfer(&owner, &env.current_contract_address(), &fee);
+
+        let collected = utils::get_collected_fees(&env);
+        storage::set(&env, &DataKey::CollectedFees, &(collected + fee));
+    }
+
+    let reverse_key = DataKey::AddressName(owner.clone());
+    if let Some(previous) = storage::get::<_, String>(&env, &reverse_key) {
+        storage::remove(&env, &DataKey::NameOwner(previous.clone()));
+        events::emit_name_released(&env, owner.clone(), previous);
+    }
+
+    storage::set(&env, &name_key, &owner);
+    storage::set(&env, &reverse_key, &name);
+
+    events::emit_name_claimed(&env, owner, name, fee);
+
+    Ok(())
+}
+
+/// Give up the caller's name so someone else can claim it
+pub fn release_name(env: Env, owner: Address) -> Result<(), RegistryError> {
+    owner.require_auth();
+
+    let name: String = storage::get(&env, &DataKey::AddressName(owner.clone()))
+        .ok_or(RegistryError::NoNameRegistered)?;
+
+    utils::remove_record(&env, &owner, &name);
+
+    events::emit_name_released(&env, owner, name);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::methods::utils;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{Address, Env, String};
+
+/// Address currently holding a name
+pub fn resolve(env: Env, name: String) -> Option<Address> {
+    storage::get(&env, &DataKey::NameOwner(name))
+}
+
+/// Name currently held by an address
+pub fn reverse(env: Env, address: Address) -> Option<String> {
+    storage::get(&env, &DataKey::AddressName(address))
+}
+
+pub fn get_name_fee(env: Env) -> i128 {
+    utils::get_name_fee(&env)
+}
+
+pub fn get_collected_fees(env: Env) -> i128 {
+    utils::get_collected_fees(&env)
+}
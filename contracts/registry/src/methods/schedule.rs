@@ -0,0 +1,192 @@
+use crate::errors::RegistryError;
+use crate::events;
+use crate::methods::admin;
+use crate::storage::{
+    self, DataKey, ScheduledAction, MAX_ACTIONS_PER_BUCKET, MAX_DUE_ACTIONS, MAX_SCHEDULE_BUCKETS,
+    SCHEDULE_BUCKET_SECONDS,
+};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+/// Allow or disallow a contract of the suite to schedule keeper actions
+pub fn set_scheduler(
+    env: Env,
+    admin: Address,
+    contract: Address,
+    approved: bool,
+) -> Result<(), RegistryError> {
+    admin::require_admin_auth(env.clone(), admin)?;
+
+    let key = DataKey::Scheduler(contract.clone());
+    if approved {
+        storage::set(&env, &key, &true);
+    } else {
+        storage::remove(&env, &key);
+    }
+
+    events::emit_scheduler_updated(&env, contract, approved);
+
+    Ok(())
+}
+
+pub fn is_scheduler(env: Env, contract: Address) -> bool {
+    storage::has(&env, &DataKey::Scheduler(contract))
+}
+
+/// Record that `(action_type, key)` of the calling contract is due at `due_at`.
+///
+/// Entries go into the day bucket of their due time. Scheduling the same
+/// action again moves it to the new time.
+pub fn schedule(
+    env: Env,
+    contract: Address,
+    action_type: Symbol,
+    key: u64,
+    due_at: u64,
+) -> Result<(), RegistryError> {
+    require_scheduler(&env, &contract)?;
+
+    remove_entry(&env, &contract, &action_type, key);
+
+    let bucket = due_at / SCHEDULE_BUCKET_SECONDS;
+    let mut entries = bucket_entries(&env, bucket);
+    if entries.len() >= MAX_ACTIONS_PER_BUCKET {
+        return Err(RegistryError::ScheduleFull);
+    }
+    if entries.is_empty() {
+        open_bucket(&env, bucket)?;
+    }
+
+    entries.push_back(ScheduledAction {
+        contract: contract.clone(),
+        action_type: action_type.clone(),
+        key,
+        due_at,
+    });
+    storage::set(&env, &DataKey::ScheduleBucket(bucket), &entries);
+    storage::set(
+        &env,
+        &DataKey::ScheduledAt(contract.clone(), action_type.clone(), key),
+        &due_at,
+    );
+
+    events::emit_action_scheduled(&env, contract, action_type, key, due_at);
+
+    Ok(())
+}
+
+/// Drop an action of the calling contract once it ran or no longer applies.
+/// Returns whether it was scheduled.
+pub fn unschedule(
+    env: Env,
+    contract: Address,
+    action_type: Symbol,
+    key: u64,
+) -> Result<bool, RegistryError> {
+    require_scheduler(&env, &contract)?;
+
+    let removed = remove_entry(&env, &contract, &action_type, key);
+    if removed {
+        events::emit_action_unscheduled(&env, contract, action_type, key);
+    }
+
+    Ok(removed)
+}
+
+/// Due time of a scheduled action, if it is scheduled
+pub fn get_scheduled(env: Env, contract: Address, action_type: Symbol, key: u64) -> Option<u64> {
+    storage::get(&env, &DataKey::ScheduledAt(contract, action_type, key))
+}
+
+/// Up to `limit` actions due at or before `now` (capped at MAX_DUE_ACTIONS),
+/// earliest bucket first and in scheduling order within a bucket. Actions stay
+/// listed until their contract unschedules them, so a keeper reads the next
+/// batch once the previous one ran. `limit` 0 means the maximum.
+pub fn due_actions(env: Env, now: u64, limit: u32) -> Vec<ScheduledAction> {
+    let limit = if limit == 0 || limit > MAX_DUE_ACTIONS {
+        MAX_DUE_ACTIONS
+    } else {
+        limit
+    };
+    let last_bucket = now / SCHEDULE_BUCKET_SECONDS;
+
+    let mut due = Vec::new(&env);
+    for bucket in schedule_buckets(&env).iter() {
+        if bucket > last_bucket {
+            break;
+        }
+        for action in bucket_entries(&env, bucket).iter() {
+            if due.len() >= limit {
+                return due;
+            }
+            if action.due_at <= now {
+                due.push_back(action);
+            }
+        }
+    }
+    due
+}
+
+/// The scheduling contract itself must call, and the admin must have approved it
+fn require_scheduler(env: &Env, contract: &Address) -> Result<(), RegistryError> {
+    contract.require_auth();
+
+    if !storage::has(env, &DataKey::Scheduler(contract.clone())) {
+        return Err(RegistryError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn schedule_buckets(env: &Env) -> Vec<u64> {
+    storage::get(env, &DataKey::ScheduleBuckets).unwrap_or(Vec::new(env))
+}
+
+fn bucket_entries(env: &Env, bucket: u64) -> Vec<ScheduledAction> {
+    storage::get(env, &DataKey::ScheduleBucket(bucket)).unwrap_or(Vec::new(env))
+}
+
+/// Insert a bucket id into the sorted list of buckets holding entries
+fn open_bucket(env: &Env, bucket: u64) -> Result<(), RegistryError> {
+    let mut buckets = schedule_buckets(env);
+    if buckets.len() >= MAX_SCHEDULE_BUCKETS {
+        return Err(RegistryError::ScheduleFull);
+    }
+
+    let position = buckets.iter().position(|open| open > bucket);
+    match position {
+        Some(index) => buckets.insert(index as u32, bucket),
+        None => buckets.push_back(bucket),
+    }
+    storage::set(env, &DataKey::ScheduleBuckets, &buckets);
+    Ok(())
+}
+
+/// Remove an entry and, once empty, its bucket; false if it was not scheduled
+fn remove_entry(env: &Env, contract: &Address, action_type: &Symbol, key: u64) -> bool {
+    let at_key = DataKey::ScheduledAt(contract.clone(), action_type.clone(), key);
+    let due_at: u64 = match storage::get(env, &at_key) {
+        Some(due_at) => due_at,
+        None => return false,
+    };
+    storage::remove(env, &at_key);
+
+    let bucket = due_at / SCHEDULE_BUCKET_SECONDS;
+    let mut entries = bucket_entries(env, bucket);
+    if let Some(index) = entries.iter().position(|action| {
+        action.contract == *contract && action.action_type == *action_type && action.key == key
+    }) {
+        entries.remove(index as u32);
+    }
+
+    if !entries.is_empty() {
+        storage::set(env, &DataKey::ScheduleBucket(bucket), &entries);
+        return true;
+    }
+
+    storage::remove(env, &DataKey::ScheduleBucket(bucket));
+    let mut buckets = schedule_buckets(env);
+    if let Some(index) = buckets.iter().position(|open| open == bucket) {
+        buckets.remove(index as u32);
+    }
+    storage::set(env, &DataKey::ScheduleBuckets, &buckets);
+    true
+}
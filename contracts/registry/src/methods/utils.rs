@@ -0,0 +1,46 @@
+use crate::errors::RegistryError;
+use crate::storage::{self, DataKey};
+use soroban_sdk::{Address, Env, String};
+
+pub const MIN_NAME_LENGTH: u32 = 3;
+pub const MAX_NAME_LENGTH: u32 = 32;
+
+pub fn get_fee_token(env: &Env) -> Result<Address, RegistryError> {
+    storage::get(env, &DataKey::FeeToken).ok_or(RegistryError::NotInitialized)
+}
+
+pub fn get_name_fee(env: &Env) -> i128 {
+    storage::get(env, &DataKey::NameFee).unwrap_or(0)
+}
+
+pub fn get_collected_fees(env: &Env) -> i128 {
+    storage::get(env, &DataKey::CollectedFees).unwrap_or(0)
+}
+
+/// Names are 3-32 characters of lowercase ASCII letters, digits, '-' or '_'.
+/// Restricting to lowercase keeps lookups case-insensitive without normalizing.
+pub fn validate_name(name: &String) -> Result<(), RegistryError> {
+    let len = name.len();
+    if !(MIN_NAME_LENGTH..=MAX_NAME_LENGTH).contains(&len) {
+        return Err(RegistryError::InvalidName);
+    }
+
+    let mut buf = [0u8; MAX_NAME_LENGTH as usize];
+    let bytes = &mut buf[..len as usize];
+    name.copy_into_slice(bytes);
+
+    let valid = bytes
+        .iter()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-' || *b == b'_');
+    if !valid {
+        return Err(RegistryError::InvalidName);
+    }
+
+    Ok(())
+}
+
+/// Drop both directions of a name record
+pub fn remove_record(env: &Env, owner: &Address, name: &String) {
+    storage::remove(env, &DataKey::NameOwner(name.clone()));
+    storage::remove(env, &DataKey::AddressName(owner.clone()));
+}
@@ -0,0 +1,71 @@
+use soroban_sdk::{contracttype, symbol_short, Address, String, Symbol};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// The keeper schedule keeps at most MAX_SCHEDULE_BUCKETS bucket ids and
+// MAX_ACTIONS_PER_BUCKET entries per bucket. The registry keeps nothing in
+// temporary storage: names stay registered until they are released, and
+// scheduled actions until their contract unschedules them.
+pub use placement::*;
+
+/// Storage keys for registry contract data
+#[contracttype(export = false)]
+pub enum DataKey {
+    // Core contract data
+    Admin,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
+    FeeToken,
+    NameFee,
+    CollectedFees,
+
+    // Name records
+    NameOwner(String),    // name → owner
+    AddressName(Address), // owner → name (reverse lookup)
+
+    // Keeper schedule
+    Scheduler(Address),                // Contract allowed to schedule actions
+    ScheduleBuckets,                   // Sorted ids of buckets holding entries
+    ScheduleBucket(u64),               // Entries due in one bucket
+    ScheduledAt(Address, Symbol, u64), // (contract, action type, key) → due_at
+}
+
+/// Action a contract wants a keeper to trigger once `due_at` has passed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledAction {
+    pub contract: Address,
+    pub action_type: Symbol, // Chosen by the contract, e.g. "poll_end"
+    pub key: u64,            // Record the action applies to, e.g. a poll id
+    pub due_at: u64,
+}
+
+/// Seconds covered by one schedule bucket
+pub const SCHEDULE_BUCKET_SECONDS: u64 = 24 * 60 * 60;
+
+/// Entries one bucket holds
+pub const MAX_ACTIONS_PER_BUCKET: u32 = 50;
+
+/// Buckets holding entries at once, about a year of daily buckets
+pub const MAX_SCHEDULE_BUCKETS: u32 = 366;
+
+/// Most entries `due_actions` returns per call
+pub const MAX_DUE_ACTIONS: u32 = 50;
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("reg");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::FeeToken
+            | DataKey::NameFee
+            | DataKey::CollectedFees => Placement::Instance,
+            DataKey::NameOwner(..)
+            | DataKey::AddressName(..)
+            | DataKey::Scheduler(..)
+            | DataKey::ScheduleBuckets
+            | DataKey::ScheduleBucket(..)
+            | DataKey::ScheduledAt(..) => Placement::Persistent,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+#![cfg(test)]
+
+use crate::contract::*;
+use crate::errors::RegistryError;
+use crate::storage::{
+    DataKey, PlacedKey, ScheduledAction, MAX_ACTIONS_PER_BUCKET, SCHEDULE_BUCKET_SECONDS,
+};
+use placement::testutils::strip_namespaces;
+use soroban_sdk::{symbol_short, testutils::Address as _, token, Address, Env, String};
+
+fn setup(
+    name_fee: i128,
+) -> (
+    Env,
+    Address,
+    Address,
+    RegistryContractClient<'static>,
+    token::Client<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let fee_sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let fee_token = fee_sac.address();
+
+    let admin = Address::generate(&env);
+    let registry_id = env.register(
+        RegistryContract,
+        (admin.clone(), fee_token.clone(), name_fee),
+    );
+    let client = RegistryContractClient::new(&env, &registry_id);
+
+    let token_client = token::Client::new(&env, &fee_token);
+
+    (env, admin, fee_token, client, token_client)
+}
+
+fn fund(env: &Env, token: &Address, user: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(user, &amount);
+}
+
+#[test]
+fn test_claim_resolve_and_reverse() {
+    let (env, _admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+    let name = String::from_str(&env, "alice_01");
+
+    client.claim_name(&alice, &name);
+
+    assert_eq!(client.resolve(&name), Some(alice.clone()));
+    assert_eq!(client.reverse(&alice), Some(name));
+    assert_eq!(client.resolve(&String::from_str(&env, "bob")), None);
+}
+
+#[test]
+fn test_claim_taken_name_fails() {
+    let (env, _admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let name = String::from_str(&env, "koltena");
+
+    client.claim_name(&alice, &name);
+
+    assert_eq!(
+        client.try_claim_name(&bob, &name),
+        Err(Ok(RegistryError::NameTaken))
+    );
+}
+
+#[test]
+fn test_invalid_names_rejected() {
+    let (env, _admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+
+    for bad in [
+        "ab",
+        "Alice",
+        "with space",
+        "emoji✓",
+        "a23456789012345678901234567890123",
+    ] {
+        assert_eq!(
+            client.try_claim_name(&alice, &String::from_str(&env, bad)),
+            Err(Ok(RegistryError::InvalidName))
+        );
+    }
+}
+
+#[test]
+fn test_rename_frees_previous_name() {
+    let (env, _admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let old_name = String::from_str(&env, "first");
+    let new_name = String::from_str(&env, "second");
+
+    client.claim_name(&alice, &old_name);
+    client.claim_name(&alice, &new_name);
+
+    assert_eq!(client.resolve(&old_name), None);
+    assert_eq!(client.reverse(&alice), Some(new_name));
+
+    client.claim_name(&bob, &old_name);
+    assert_eq!(client.resolve(&old_name), Some(bob));
+}
+
+#[test]
+fn test_release_name() {
+    let (env, _admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+    let name = String::from_str(&env, "alice");
+
+    client.claim_name(&alice, &name);
+    client.release_name(&alice);
+
+    assert_eq!(client.resolve(&name), None);
+    assert_eq!(client.reverse(&alice), None);
+    assert_eq!(
+        client.try_release_name(&alice),
+        Err(Ok(RegistryError::NoNameRegistered))
+    );
+}
+
+#[test]
+fn test_claim_fee_collected_and_withdrawn() {
+    let (env, admin, token, client, token_client) = setup(100);
+    let alice = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    fund(&env, &token, &alice, 250);
+
+    client.claim_name(&alice, &String::from_str(&env, "alice"));
+    // Re-claiming the name already held is a free no-op
+    client.claim_name(&alice, &String::from_str(&env, "alice"));
+
+    assert_eq!(token_client.balance(&alice), 150);
+    assert_eq!(client.get_collected_fees(), 100);
+
+    assert_eq!(
+        client.try_withdraw_fees(&admin, &treasury, &101),
+        Err(Ok(RegistryError::InsufficientFees))
+    );
+    client.withdraw_fees(&admin, &treasury, &100);
+    assert_eq!(token_client.balance(&treasury), 100);
+    assert_eq!(client.get_collected_fees(), 0);
+}
+
+#[test]
+fn test_admin_revokes_name() {
+    let (env, admin, _token, client, _token_client) = setup(0);
+    let alice = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    let name = String::from_str(&env, "official-koltena");
+
+    client.claim_name(&mallory, &name);
+
+    assert_eq!(
+        client.try_revoke_name(&alice, &name),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+
+    client.revoke_name(&admin, &name);
+    assert_eq!(client.resolve(&name), None);
+    assert_eq!(client.reverse(&mallory), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_double_initialize_fails() {
+    let (_env, admin, token, client, _token_client) = setup(0);
+    client.initialize(&admin, &token, &0);
+}
+
+#[test]
+fn test_upgraded_deployment_cannot_be_initialized_again() {
+    let (env, _admin, token, client, _token_client) = setup(0);
+
+    // A deployment upgraded from before namespacing keeps its admin under the
+    // bare key until it migrates
+    strip_namespaces(&env, &client.address, &[DataKey::NAMESPACE]);
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env), &token, &0),
+        Err(Ok(RegistryError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_due_actions_across_contracts_in_due_order() {
+    let (env, admin, _token, client, _token_client) = setup(0);
+    let governance = Address::generate(&env);
+    let trading = Address::generate(&env);
+    client.set_scheduler(&admin, &governance, &true);
+    client.set_scheduler(&admin, &trading, &true);
+
+    let day = SCHEDULE_BUCKET_SECONDS;
+    client.schedule(&trading, &symbol_short!("sale_exp"), &7, &(3 * day + 10));
+    client.schedule(&governance, &symbol_short!("poll_end"), &1, &(day + 500));
+    client.schedule(&governance, &symbol_short!("poll_end"), &2, &(day + 100));
+
+    let entry = |contract: &Address, action_type, key, due_at| ScheduledAction {
+        contract: contract.clone(),
+        action_type,
+        key,
+        due_at,
+    };
+
+    // Entries past `now` stay hidden, even in the current bucket
+    assert_eq!(client.due_actions(&(day + 200), &0).len(), 1);
+    let due = client.due_actions(&(4 * day), &0);
+    assert_eq!(
+        due,
+        soroban_sdk::vec![
+            &env,
+            entry(&governance, symbol_short!("poll_end"), 1, day + 500),
+            entry(&governance, symbol_short!("poll_end"), 2, day + 100),
+            entry(&trading, symbol_short!("sale_exp"), 7, 3 * day + 10),
+        ]
+    );
+    assert_eq!(client.due_actions(&(4 * day), &2).len(), 2);
+
+    // Rescheduling moves the entry; unscheduling drops it
+    client.schedule(&governance, &symbol_short!("poll_end"), &1, &(5 * day));
+    assert_eq!(
+        client.get_scheduled(&governance, &symbol_short!("poll_end"), &1),
+        Some(5 * day)
+    );
+    assert!(client.unschedule(&governance, &symbol_short!("poll_end"), &2));
+    assert!(!client.unschedule(&governance, &symbol_short!("poll_end"), &2));
+    assert_eq!(
+        client.due_actions(&(4 * day), &0),
+        soroban_sdk::vec![
+            &env,
+            entry(&trading, symbol_short!("sale_exp"), 7, 3 * day + 10)
+        ]
+    );
+}
+
+#[test]
+fn test_schedule_requires_approved_contract_and_room() {
+    let (env, admin, _token, client, _token_client) = setup(0);
+    let contract = Address::generate(&env);
+
+    assert_eq!(
+        client.try_schedule(&contract, &symbol_short!("poll_end"), &1, &100),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_scheduler(&contract, &contract, &true),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+
+    client.set_scheduler(&admin, &contract, &true);
+    assert!(client.is_scheduler(&contract));
+    for key in 0..MAX_ACTIONS_PER_BUCKET as u64 {
+        client.schedule(&contract, &symbol_short!("poll_end"), &key, &100);
+    }
+    assert_eq!(
+        client.try_schedule(&contract, &symbol_short!("poll_end"), &99, &200),
+        Err(Ok(RegistryError::ScheduleFull))
+    );
+    // The next day has its own bucket
+    client.schedule(
+        &contract,
+        &symbol_short!("poll_end"),
+        &99,
+        &(SCHEDULE_BUCKET_SECONDS + 200),
+    );
+
+    client.set_scheduler(&admin, &contract, &false);
+    assert_eq!(
+        client.try_unschedule(&contract, &symbol_short!("poll_end"), &99),
+        Err(Ok(RegistryError::Unauthorized))
+    );
+}
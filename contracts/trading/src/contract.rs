@@ -1,14 +1,43 @@
-use crate::methods::{initialization, queries, sales};
-use crate::storage::{SaleProposal, TradeHistory};
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use crate::errors::TradingError;
+use crate::methods::{
+    admin_log, durations, eligibility, exit_queue, extensions, history, initialization, invariants,
+    keeper, platform, queries, reputation, revenue, sales, screening, token_leg, utils,
+};
+use crate::storage::{
+    AdminLogEntry, AdminLogHead, AssetMarketStats, BuyerEligibility, Conduct, Cursor,
+    ExitQueueConfig, ExitQueueEntry, ExitQueuePosition, Reputation, SaleDurationLimits,
+    SaleProposal, TradeHistory, TradeRecordV2, TradingConfig, TradingFee, UpstreamPause,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Val, Vec};
 
 #[contract]
 pub struct TradingContract;
 
 #[contractimpl]
 impl TradingContract {
-    pub fn initialize(env: Env, admin: Address, fnft_contract: Address, xlm_contract: Address) {
-        initialization::initialize(env, admin, fnft_contract, xlm_contract);
+    /// Initialize fresh deployments at deploy time
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        fnft_contract: Address,
+        xlm_contract: Address,
+    ) -> Result<(), TradingError> {
+        initialization::construct(env, admin, fnft_contract, xlm_contract)
+    }
+
+    /// Initialize a contract deployed from a build without the constructor
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fnft_contract: Address,
+        xlm_contract: Address,
+    ) -> Result<(), TradingError> {
+        initialization::initialize(env, admin, fnft_contract, xlm_contract)
+    }
+
+    /// Hash of the initialization arguments, for pipelines checking a deployment
+    pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+        initialization::get_init_config_hash(env)
     }
 
     /// Seller confirms a sale
@@ -20,7 +49,7 @@ impl TradingContract {
         token_amount: u64,
         price: u128,
         duration_seconds: u64,
-    ) {
+    ) -> Result<(), TradingError> {
         sales::confirm_sale(
             env,
             seller,
@@ -29,7 +58,7 @@ impl TradingContract {
             token_amount,
             price,
             duration_seconds,
-        );
+        )
     }
 
     /// Buyer finishes the transaction
@@ -40,24 +69,414 @@ impl TradingContract {
         asset_id: u64,
         token_amount: u64,
         price: u128,
-    ) {
-        sales::finish_transaction(env, buyer, seller, asset_id, token_amount, price);
+    ) -> Result<(), TradingError> {
+        sales::finish_transaction(env, buyer, seller, asset_id, token_amount, price)
+    }
+
+    /// Remove an expired proposal; returns the keeper bounty paid
+    pub fn cleanup_expired_sale(
+        env: Env,
+        keeper: Address,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<i128, TradingError> {
+        sales::cleanup_expired_sale(env, keeper, seller, buyer, asset_id)
+    }
+
+    /// Set the XLM bounty paid to keepers (admin only)
+    pub fn set_keeper_bounty(env: Env, admin: Address, bounty: i128) -> Result<(), TradingError> {
+        keeper::set_keeper_bounty(env, admin, bounty)
+    }
+
+    /// Deposit XLM into the keeper bounty pool
+    pub fn fund_keeper_pool(env: Env, funder: Address, amount: i128) -> Result<(), TradingError> {
+        keeper::fund_keeper_pool(env, funder, amount)
+    }
+
+    pub fn get_keeper_bounty(env: Env) -> i128 {
+        keeper::get_keeper_bounty(env)
+    }
+
+    pub fn get_keeper_pool(env: Env) -> i128 {
+        keeper::get_keeper_pool(env)
+    }
+
+    /// Set or clear the screening contract whose `is_blocked` gates every trade (admin only)
+    pub fn set_screening_contract(
+        env: Env,
+        admin: Address,
+        screening_contract: Option<Address>,
+    ) -> Result<(), TradingError> {
+        screening::set_screening_contract(env, admin, screening_contract)
+    }
+
+    pub fn get_screening_contract(env: Env) -> Option<Address> {
+        screening::get_screening_contract(env)
+    }
+
+    /// Set or clear the funding contract that routed sale proceeds are deposited into (admin only)
+    pub fn set_funding_contract(
+        env: Env,
+        admin: Address,
+        funding_contract: Option<Address>,
+    ) -> Result<(), TradingError> {
+        revenue::set_funding_contract(env, admin, funding_contract)
+    }
+
+    pub fn get_funding_contract(env: Env) -> Option<Address> {
+        revenue::get_funding_contract(env)
+    }
+
+    /// Route a seller's sale proceeds to the funding contract as revenue of the
+    /// asset sold (admin and seller)
+    pub fn set_revenue_routing(
+        env: Env,
+        admin: Address,
+        seller: Address,
+        enabled: bool,
+    ) -> Result<(), TradingError> {
+        revenue::set_revenue_routing(env, admin, seller, enabled)
+    }
+
+    pub fn is_revenue_routed(env: Env, seller: Address) -> bool {
+        revenue::is_revenue_routed(env, seller)
+    }
+
+    /// Hand the platform parameters to a governance contract (admin for the
+    /// first hand-over, then the current governor; `None` is refused)
+    pub fn set_governor(
+        env: Env,
+        caller: Address,
+        governor: Option<Address>,
+    ) -> Result<(), TradingError> {
+        platform::set_governor(env, caller, governor)
+    }
+
+    pub fn get_governor(env: Env) -> Option<Address> {
+        platform::get_governor(env)
+    }
+
+    /// Replace the trading fee and deployment sale duration limits (governor only)
+    pub fn apply_trading_config(
+        env: Env,
+        governor: Address,
+        config: TradingConfig,
+    ) -> Result<(), TradingError> {
+        platform::apply_trading_config(env, governor, config)
+    }
+
+    /// Replace the contract code with an uploaded WASM (governor only)
+    pub fn upgrade(
+        env: Env,
+        governor: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), TradingError> {
+        platform::upgrade(env, governor, new_wasm_hash)
+    }
+
+    /// Move entries written before namespacing under their namespace, in
+    /// batches; `last` closes the migration (admin only)
+    pub fn migrate_storage(
+        env: Env,
+        admin: Address,
+        keys: Vec<Val>,
+        ext_keys: Vec<Val>,
+        last: bool,
+    ) -> Result<u32, TradingError> {
+        platform::migrate_storage(env, admin, keys, ext_keys, last)
+    }
+
+    /// Set or clear the exit queue that paces large sellers of an asset (governor only)
+    pub fn set_exit_queue(
+        env: Env,
+        governor: Address,
+        asset_id: u64,
+        config: Option<ExitQueueConfig>,
+    ) -> Result<(), TradingError> {
+        exit_queue::set_exit_queue(env, governor, asset_id, config)
+    }
+
+    pub fn get_exit_queue_config(env: Env, asset_id: u64) -> Option<ExitQueueConfig> {
+        exit_queue::get_exit_queue_config(env, asset_id)
+    }
+
+    /// Settle new sales of an asset through a SEP-41 token's approve/transfer_from
+    /// (admin only; `None` returns them to the fractcore allowance)
+    pub fn set_asset_token(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        token: Option<Address>,
+    ) -> Result<(), TradingError> {
+        token_leg::set_asset_token(env, admin, asset_id, token)
+    }
+
+    pub fn get_asset_token(env: Env, asset_id: u64) -> Option<Address> {
+        token_leg::get_asset_token(env, asset_id)
+    }
+
+    /// Join the asset's exit queue to sell `amount` fractions; returns the position
+    pub fn enroll_exit(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+        amount: u64,
+    ) -> Result<u32, TradingError> {
+        exit_queue::enroll_exit(env, seller, asset_id, amount)
+    }
+
+    pub fn leave_exit_queue(env: Env, seller: Address, asset_id: u64) -> Result<(), TradingError> {
+        exit_queue::leave_exit_queue(env, seller, asset_id)
+    }
+
+    pub fn get_exit_queue(env: Env, asset_id: u64) -> Vec<ExitQueueEntry> {
+        exit_queue::get_exit_queue(env, asset_id)
+    }
+
+    pub fn get_exit_queue_position(
+        env: Env,
+        asset_id: u64,
+        seller: Address,
+    ) -> Option<ExitQueuePosition> {
+        exit_queue::get_exit_queue_position(env, asset_id, seller)
+    }
+
+    pub fn get_trading_fee(env: Env) -> Option<TradingFee> {
+        platform::get_trading_fee(env)
+    }
+
+    /// Set the shortest and longest sale duration for the deployment (admin
+    /// only, until a governor is set)
+    pub fn set_sale_duration_limits(
+        env: Env,
+        admin: Address,
+        limits: SaleDurationLimits,
+    ) -> Result<(), TradingError> {
+        durations::set_sale_duration_limits(env, admin, limits)
+    }
+
+    /// Override the sale duration limits for one asset (admin only; `None`
+    /// restores the deployment limits)
+    pub fn set_asset_sale_duration_limits(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        limits: Option<SaleDurationLimits>,
+    ) -> Result<(), TradingError> {
+        durations::set_asset_sale_duration_limits(env, admin, asset_id, limits)
+    }
+
+    pub fn get_sale_duration_limits(env: Env) -> SaleDurationLimits {
+        durations::get_sale_duration_limits(env)
+    }
+
+    /// Limits applied to sales of an asset: its override, else the deployment limits
+    pub fn get_asset_sale_duration_limits(env: Env, asset_id: u64) -> SaleDurationLimits {
+        durations::get_asset_sale_duration_limits(env, asset_id)
+    }
+
+    /// Restrict buyers of the seller's fractions of an asset, checked at settlement
+    /// (`None` lets anyone buy)
+    pub fn set_buyer_eligibility(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+        eligibility: Option<BuyerEligibility>,
+    ) -> Result<(), TradingError> {
+        eligibility::set_buyer_eligibility(env, seller, asset_id, eligibility)
+    }
+
+    pub fn get_buyer_eligibility(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Option<BuyerEligibility> {
+        eligibility::get_buyer_eligibility(env, seller, asset_id)
+    }
+
+    /// Add or remove a buyer on an asset's allowlist (fractcore asset manager only)
+    pub fn set_asset_allowlisted(
+        env: Env,
+        manager: Address,
+        asset_id: u64,
+        buyer: Address,
+        allowed: bool,
+    ) -> Result<(), TradingError> {
+        eligibility::set_asset_allowlisted(env, manager, asset_id, buyer, allowed)
+    }
+
+    pub fn is_asset_allowlisted(env: Env, asset_id: u64, buyer: Address) -> bool {
+        eligibility::is_asset_allowlisted(env, asset_id, buyer)
+    }
+
+    /// Whether a buyer currently meets a seller's criteria for an asset
+    pub fn is_eligible_buyer(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<bool, TradingError> {
+        eligibility::is_eligible_buyer(env, seller, buyer, asset_id)
+    }
+
+    /// Pause or unpause trading of an asset (admin only). Assets are also paused
+    /// automatically when the contract's holdings no longer cover what it owes.
+    pub fn set_asset_paused(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        paused: bool,
+    ) -> Result<(), TradingError> {
+        invariants::set_asset_paused(env, admin, asset_id, paused)
+    }
+
+    pub fn is_asset_paused(env: Env, asset_id: u64) -> bool {
+        invariants::is_asset_paused(env, asset_id)
+    }
+
+    /// Fractcore's emergency pause, which fails every trade with `UpstreamPaused`
+    /// until `retry_after`; `None` when fractcore is running
+    pub fn get_upstream_pause(env: Env) -> Result<Option<UpstreamPause>, TradingError> {
+        invariants::get_upstream_pause(env)
+    }
+
+    /// One page of the admin action log, oldest first; pages older than the
+    /// retention window come back empty and are covered by the head's hash
+    pub fn get_admin_log(env: Env, page: u32) -> Vec<AdminLogEntry> {
+        admin_log::get_admin_log(env, page)
+    }
+
+    pub fn get_admin_log_head(env: Env) -> AdminLogHead {
+        admin_log::get_admin_log_head(env)
+    }
+
+    /// Fractions of an asset held by this contract, which only ever holds any by mistake
+    pub fn get_rescuable_tokens(env: Env, asset_id: u64) -> Result<u64, TradingError> {
+        invariants::get_rescuable_tokens(env, asset_id)
+    }
+
+    /// Return fractions sent to this contract by mistake (admin only)
+    pub fn rescue_fractions(
+        env: Env,
+        admin: Address,
+        asset_id: u64,
+        to: Address,
+        amount: u64,
+    ) -> Result<(), TradingError> {
+        invariants::rescue_fractions(env, admin, asset_id, to, amount)
+    }
+
+    /// Register or retire an extension contract allowed to call the hooks below (admin only)
+    pub fn set_extension(
+        env: Env,
+        admin: Address,
+        extension: Address,
+        enabled: bool,
+    ) -> Result<(), TradingError> {
+        extensions::set_extension(env, admin, extension, enabled)
+    }
+
+    pub fn is_extension(env: Env, extension: Address) -> bool {
+        extensions::is_extension(env, extension)
+    }
+
+    /// Fail unless neither party is screened out and the asset is not paused
+    pub fn check_parties(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), TradingError> {
+        extensions::check_parties(env, seller, buyer, asset_id)
+    }
+
+    /// `check_parties` plus the seller's buyer eligibility criteria
+    pub fn check_trade(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), TradingError> {
+        extensions::check_trade(env, seller, buyer, asset_id)
+    }
+
+    /// Extension hook: count fractions leaving the seller against its exit queue tranche
+    pub fn draw_exit(
+        env: Env,
+        extension: Address,
+        seller: Address,
+        asset_id: u64,
+        amount: u64,
+    ) -> Result<(), TradingError> {
+        extensions::draw_exit(env, extension, seller, asset_id, amount)
+    }
+
+    /// Extension hook: take XLM from `payer` and pay it to a seller, less the
+    /// trading fee and through revenue routing
+    pub fn pay_seller(
+        env: Env,
+        extension: Address,
+        payer: Address,
+        seller: Address,
+        asset_id: u64,
+        amount: i128,
+    ) -> Result<(), TradingError> {
+        extensions::pay_seller(env, extension, payer, seller, asset_id, amount)
+    }
+
+    /// Extension hook: record a trade the extension settled; returns its trade ID
+    pub fn record_trade(
+        env: Env,
+        extension: Address,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+        token_amount: u64,
+        price: u128,
+    ) -> Result<u32, TradingError> {
+        extensions::record_trade(env, extension, seller, buyer, asset_id, token_amount, price)
+    }
+
+    /// Extension hook: count a cancellation, lapse or dispute in a reputation
+    pub fn record_conduct(
+        env: Env,
+        extension: Address,
+        party: Address,
+        conduct: Conduct,
+    ) -> Result<(), TradingError> {
+        extensions::record_conduct(env, extension, party, conduct)
     }
 
-    pub fn cleanup_expired_sale(env: Env, seller: Address, buyer: Address, asset_id: u64) {
-        sales::cleanup_expired_sale(env, seller, buyer, asset_id);
+    /// Extension hook: pay the keeper bounty from the keeper pool
+    pub fn pay_keeper(env: Env, extension: Address, keeper: Address) -> Result<i128, TradingError> {
+        extensions::pay_keeper(env, extension, keeper)
     }
 
-    pub fn withdraw_sale(env: Env, seller: Address, buyer: Address, asset_id: u64) {
-        sales::withdraw_sale(env, seller, buyer, asset_id);
+    /// Extension hook: halt an asset whose holdings fell short of what the extension owes
+    pub fn pause_asset(env: Env, extension: Address, asset_id: u64) -> Result<(), TradingError> {
+        extensions::pause_asset(env, extension, asset_id)
     }
 
-    pub fn emergency_reset_allowance(env: Env, seller: Address, asset_id: u64) {
-        sales::emergency_reset_allowance(env, seller, asset_id);
+    pub fn withdraw_sale(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<(), TradingError> {
+        sales::withdraw_sale(env, seller, buyer, asset_id)
+    }
+
+    pub fn emergency_reset_allowance(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<(), TradingError> {
+        sales::emergency_reset_allowance(env, seller, asset_id)
     }
 
     /// Get XLM contract address
-    pub fn get_xlm_contract_address_public(env: Env) -> Address {
+    pub fn get_xlm_contract_address_public(env: Env) -> Result<Address, TradingError> {
         queries::get_xlm_contract_address_public(env)
     }
 
@@ -66,7 +485,7 @@ impl TradingContract {
         seller: Address,
         buyer: Address,
         asset_id: u64,
-    ) -> SaleProposal {
+    ) -> Result<SaleProposal, TradingError> {
         queries::get_sale_proposal(env, seller, buyer, asset_id)
     }
 
@@ -82,7 +501,7 @@ impl TradingContract {
         queries::get_buyer_offers(env, buyer)
     }
 
-    pub fn get_trade_history(env: Env, trade_id: u32) -> TradeHistory {
+    pub fn get_trade_history(env: Env, trade_id: u32) -> Result<TradeHistory, TradingError> {
         queries::get_trade_history(env, trade_id)
     }
 
@@ -90,19 +509,87 @@ impl TradingContract {
         queries::get_trade_count(env)
     }
 
+    /// Latest unit price plus volume and trade count since `since` (newest 100 trades)
+    pub fn get_asset_market_stats(
+        env: Env,
+        asset_id: u64,
+        since: u64,
+    ) -> Result<AssetMarketStats, TradingError> {
+        queries::get_asset_market_stats(env, asset_id, since)
+    }
+
+    /// Completed trades, volume, cancellations, unsold expiries and disputes of
+    /// an address, as seller or buyer
+    pub fn get_reputation(env: Env, address: Address) -> Reputation {
+        reputation::get_reputation(env, address)
+    }
+
+    /// Trade with its settlement breakdown (gross, fees, royalties, net, payment token)
+    pub fn get_trade_record(env: Env, trade_id: u32) -> Result<TradeRecordV2, TradingError> {
+        history::get_trade_record(env, trade_id)
+    }
+
+    /// 1 while legacy trade records remain, 2 once migrated
+    pub fn get_trade_record_version(env: Env) -> u32 {
+        history::get_trade_record_version(env)
+    }
+
+    /// Upgrade a batch of legacy trade records (admin only); true once all are migrated
+    pub fn migrate_trade_records(
+        env: Env,
+        admin: Address,
+        limit: u32,
+    ) -> Result<bool, TradingError> {
+        history::migrate_trade_records(env, admin, limit)
+    }
+
+    /// Last sequence number on an account's `acct` event topic (0 if none yet)
+    pub fn get_account_event_seq(env: Env, account: Address) -> u64 {
+        utils::get_account_event_seq(&env, &account)
+    }
+
     pub fn get_asset_trades(env: Env, asset_id: u64) -> Vec<u32> {
         queries::get_asset_trades(env, asset_id)
     }
 
-    pub fn get_fnft_contract_address(env: Env) -> Address {
+    /// Trades an address sold or bought in, with their ids, paged like `get_asset_trades_page`
+    pub fn get_user_trades_page(
+        env: Env,
+        user: Address,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<(u32, TradeRecordV2)>, Option<Cursor>) {
+        queries::get_user_trades_page(env, user, cursor, limit)
+    }
+
+    /// Up to `limit` trade ids (capped at MAX_BULK_READ) from `cursor`, with the cursor of the next page
+    pub fn get_asset_trades_page(
+        env: Env,
+        asset_id: u64,
+        cursor: Option<Cursor>,
+        limit: u32,
+    ) -> (Vec<u32>, Option<Cursor>) {
+        queries::get_asset_trades_page(env, asset_id, cursor, limit)
+    }
+
+    pub fn get_fnft_contract_address(env: Env) -> Result<Address, TradingError> {
         queries::get_fnft_contract_address(env)
     }
 
-    pub fn time_until_expiry(env: Env, seller: Address, buyer: Address, asset_id: u64) -> u64 {
+    pub fn time_until_expiry(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        asset_id: u64,
+    ) -> Result<u64, TradingError> {
         queries::time_until_expiry(env, seller, buyer, asset_id)
     }
 
-    pub fn get_current_allowance(env: Env, seller: Address, asset_id: u64) -> u64 {
+    pub fn get_current_allowance(
+        env: Env,
+        seller: Address,
+        asset_id: u64,
+    ) -> Result<u64, TradingError> {
         queries::get_current_allowance(env, seller, asset_id)
     }
 }
@@ -0,0 +1,137 @@
+use core::fmt;
+use pricing::PriceError;
+use soroban_sdk::contracterror;
+
+/// Error codes returned by trading entrypoints
+///
+/// Codes 25-45, 49 and 50 belonged to milestone escrows, arbitrated and
+/// forward sales, offerings, recurring listings, subscriptions, fiat-priced
+/// sales and basket purchases and valuation, which moved to the escrow, market
+/// and otc extension contracts; those keep the numbers, and they are not reused here.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TradingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidTokenAmount = 3,
+    InvalidPrice = 4,
+    SelfTrade = 5,
+    InvalidDuration = 6,
+    AssetNotFound = 7,
+    InsufficientBalance = 8,
+    SaleAlreadyExists = 9,
+    SaleNotFound = 10,
+    SaleNotActive = 11,
+    UnauthorizedBuyer = 12,
+    UnauthorizedSeller = 13,
+    SaleExpired = 14,
+    SaleNotExpired = 15,
+    TokenAmountMismatch = 16,
+    PriceMismatch = 17,
+    SellerInsufficientBalance = 18,
+    BuyerInsufficientFunds = 19,
+    InsufficientAllowance = 20,
+    PriceOverflow = 21,
+    TradeNotFound = 22,
+    Unauthorized = 23,
+    InvalidAmount = 24,
+    AddressBlocked = 46,
+    ScreeningFailed = 47,
+    AssetPaused = 48,
+    UpstreamPaused = 51,
+    ExitQueueNotConfigured = 52,
+    ExceedsReleasedTranche = 53,
+    BuyerNotEligible = 54,
+    BatchTooLarge = 55,
+    TooManyProposals = 56,
+    NotGovernanceContract = 57,
+}
+
+impl TradingError {
+    /// Message used by the string panics that predate typed errors
+    pub const fn message(&self) -> &'static str {
+        match self {
+            TradingError::AlreadyInitialized => "Contract already initialized",
+            TradingError::NotInitialized => "Contract not initialized",
+            TradingError::InvalidTokenAmount => "Token amount must be > 0",
+            TradingError::InvalidPrice => "Price must be > 0",
+            TradingError::SelfTrade => "Cannot trade with yourself",
+            TradingError::InvalidDuration => "Duration must be between 1 hour and 1 week",
+            TradingError::AssetNotFound => "Asset does not exist",
+            TradingError::InsufficientBalance => "Insufficient balance",
+            TradingError::SaleAlreadyExists => "Sale proposal already exists - withdraw first",
+            TradingError::SaleNotFound => "Sale proposal not found",
+            TradingError::SaleNotActive => "Sale proposal is not active",
+            TradingError::UnauthorizedBuyer => "Not authorized buyer for this sale",
+            TradingError::UnauthorizedSeller => "Only the seller can withdraw this proposal",
+            TradingError::SaleExpired => "Sale proposal has expired",
+            TradingError::SaleNotExpired => "Sale has not expired yet",
+            TradingError::TokenAmountMismatch => "Token amount mismatch",
+            TradingError::PriceMismatch => "Price mismatch",
+            TradingError::SellerInsufficientBalance => "Seller has insufficient token balance",
+            TradingError::BuyerInsufficientFunds => "Buyer has insufficient XLM funds",
+            TradingError::InsufficientAllowance => "Insufficient allowance for token transfer",
+            TradingError::PriceOverflow => {
+                "Proposal price exceeds maximum allowable value for i128"
+            }
+            TradingError::TradeNotFound => "Trade not found",
+            TradingError::Unauthorized => "Only admin can perform this action",
+            TradingError::InvalidAmount => "Amount must be > 0",
+            TradingError::AddressBlocked => "Address is blocked by the screening contract",
+            TradingError::ScreeningFailed => "Screening contract call failed",
+            TradingError::AssetPaused => "Asset is paused pending investigation",
+            TradingError::UpstreamPaused => "Fractcore is paused or its pause state is unreadable",
+            TradingError::ExitQueueNotConfigured => "Asset has no exit queue",
+            TradingError::ExceedsReleasedTranche => {
+                "Sale exceeds what the exit queue has released to the seller"
+            }
+            TradingError::BuyerNotEligible => {
+                "Buyer does not meet the seller's eligibility criteria"
+            }
+            TradingError::BatchTooLarge => "Batch exceeds maximum size",
+            TradingError::TooManyProposals => "Too many open sale proposals for this account",
+            TradingError::NotGovernanceContract => {
+                "Governor must be a governance contract for this fractcore"
+            }
+        }
+    }
+}
+
+impl From<PriceError> for TradingError {
+    fn from(err: PriceError) -> Self {
+        match err {
+            PriceError::InvalidTokenAmount => TradingError::InvalidTokenAmount,
+            PriceError::InvalidPrice => TradingError::InvalidPrice,
+            PriceError::PriceOverflow => TradingError::PriceOverflow,
+        }
+    }
+}
+
+/// Surface an error to the caller.
+///
+/// With the `legacy-panics` feature the contract traps with the old string
+/// message instead, for integrations that still match on panic text.
+pub fn fail<T>(err: TradingError) -> Result<T, TradingError> {
+    #[cfg(feature = "legacy-panics")]
+    panic!("{}", err.message());
+
+    #[cfg(not(feature = "legacy-panics"))]
+    Err(err)
+}
+
+/// `fail` for the checks whose legacy panic carried its own text, such as the
+/// expected and found values of a mismatch
+pub fn fail_with<T>(err: TradingError, legacy: fmt::Arguments) -> Result<T, TradingError> {
+    #[cfg(feature = "legacy-panics")]
+    {
+        let _ = err;
+        panic!("{}", legacy);
+    }
+
+    #[cfg(not(feature = "legacy-panics"))]
+    {
+        let _ = legacy;
+        Err(err)
+    }
+}
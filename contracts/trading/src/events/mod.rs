@@ -1,5 +1,8 @@
-use crate::storage::SaleProposal;
-use soroban_sdk::{symbol_short, Address, Env};
+use crate::storage::{
+    AdminLogEntry, BuyerEligibility, ExitQueueConfig, SaleDurationLimits, SaleProposal,
+    TradingConfig,
+};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol};
 
 /// contract initialization event
 pub fn emit_init_event(
@@ -56,3 +59,204 @@ pub fn emit_emergency_reset_event(env: &Env, seller: &Address, asset_id: u64) {
     env.events()
         .publish((symbol_short!("reset"),), (seller.clone(), asset_id));
 }
+
+/// keeper bounty configuration event
+pub fn emit_keeper_bounty_event(env: &Env, bounty: i128) {
+    env.events().publish((symbol_short!("kp_bnty"),), bounty);
+}
+
+/// batch of legacy trade records upgraded (cursor = last trade_id checked)
+pub fn emit_records_migrated_event(env: &Env, cursor: u32, migrated: u32, done: bool) {
+    env.events()
+        .publish((symbol_short!("rec_migr"),), (cursor, migrated, done));
+}
+
+/// screening contract changed (None = screening disabled)
+pub fn emit_screening_event(env: &Env, screening_contract: Option<Address>) {
+    env.events()
+        .publish((symbol_short!("screening"),), screening_contract);
+}
+
+/// funding contract for routed proceeds changed (None = routing disabled)
+pub fn emit_funding_contract_event(env: &Env, funding_contract: Option<Address>) {
+    env.events()
+        .publish((symbol_short!("funding"),), funding_contract);
+}
+
+/// platform governor changed (None = the admin holds the platform parameters) event
+pub fn emit_governor_event(env: &Env, governor: Option<Address>) {
+    env.events().publish((symbol_short!("governor"),), governor);
+}
+
+/// code replaced by the governor (old hash None = code deployed at creation) event
+pub fn emit_upgraded_event(
+    env: &Env,
+    old_wasm_hash: Option<BytesN<32>>,
+    new_wasm_hash: BytesN<32>,
+) {
+    env.events()
+        .publish((symbol_short!("upgraded"),), (old_wasm_hash, new_wasm_hash));
+}
+
+/// governor applied a new trading fee and sale duration limits event
+pub fn emit_trading_config_event(env: &Env, config: TradingConfig) {
+    env.events().publish((symbol_short!("trd_cfg"),), config);
+}
+
+/// admin set (or cleared) the SEP-41 token an asset's sales settle in event
+pub fn emit_asset_token_event(env: &Env, asset_id: u64, token: Option<Address>) {
+    env.events()
+        .publish((symbol_short!("asset_tok"), asset_id), token);
+}
+
+/// governor set (or cleared) an asset's exit queue event
+pub fn emit_exit_queue_config_event(env: &Env, asset_id: u64, config: Option<ExitQueueConfig>) {
+    env.events()
+        .publish((symbol_short!("exit_cfg"), asset_id), config);
+}
+
+/// seller joined an asset's exit queue event
+pub fn emit_exit_enrolled_event(env: &Env, seller: &Address, asset_id: u64, amount: u64) {
+    env.events().publish(
+        (symbol_short!("exit_enrl"), seller.clone(), asset_id),
+        amount,
+    );
+}
+
+/// seller left an asset's exit queue event
+pub fn emit_exit_left_event(env: &Env, seller: &Address, asset_id: u64) {
+    env.events()
+        .publish((symbol_short!("exit_left"), seller.clone(), asset_id), ());
+}
+
+/// settlement drew on a seller's released tranche event
+pub fn emit_exit_sold_event(
+    env: &Env,
+    seller: &Address,
+    asset_id: u64,
+    amount: u64,
+    remaining: u64,
+) {
+    env.events().publish(
+        (symbol_short!("exit_sold"), seller.clone(), asset_id),
+        (amount, remaining),
+    );
+}
+
+/// admin returned fractions sent to the contract by mistake event
+pub fn emit_rescued_event(env: &Env, asset_id: u64, to: &Address, amount: u64) {
+    env.events()
+        .publish((symbol_short!("rescued"), asset_id), (to.clone(), amount));
+}
+
+/// trading fee withheld from a seller's proceeds event
+pub fn emit_fee_event(env: &Env, recipient: &Address, asset_id: u64, amount: i128) {
+    env.events()
+        .publish((symbol_short!("fee"), recipient.clone(), asset_id), amount);
+}
+
+/// seller designated for (or released from) revenue routing event
+pub fn emit_revenue_route_event(env: &Env, seller: &Address, enabled: bool) {
+    env.events()
+        .publish((symbol_short!("rev_route"), seller.clone()), enabled);
+}
+
+/// sale proceeds deposited into the funding contract as asset revenue event
+pub fn emit_revenue_event(env: &Env, seller: &Address, asset_id: u64, amount: i128) {
+    env.events()
+        .publish((symbol_short!("revenue"), seller.clone(), asset_id), amount);
+}
+
+/// seller buyer criteria changed (None = anyone may buy) event
+pub fn emit_eligibility_event(
+    env: &Env,
+    seller: &Address,
+    asset_id: u64,
+    eligibility: Option<BuyerEligibility>,
+) {
+    env.events().publish(
+        (symbol_short!("elig_set"), seller.clone(), asset_id),
+        eligibility,
+    );
+}
+
+/// buyer added to or removed from an asset's allowlist event
+pub fn emit_allowlist_event(env: &Env, asset_id: u64, buyer: &Address, allowed: bool) {
+    env.events().publish(
+        (symbol_short!("allowlist"), asset_id, buyer.clone()),
+        allowed,
+    );
+}
+
+/// deployment-wide sale duration limits changed event
+pub fn emit_duration_limits_event(env: &Env, limits: SaleDurationLimits) {
+    env.events().publish((symbol_short!("dur_lim"),), limits);
+}
+
+/// per-asset sale duration limits changed (None = deployment limits apply) event
+pub fn emit_asset_duration_limits_event(
+    env: &Env,
+    asset_id: u64,
+    limits: Option<SaleDurationLimits>,
+) {
+    env.events()
+        .publish((symbol_short!("dur_lim"), asset_id), limits);
+}
+
+/// keeper pool top-up event
+pub fn emit_keeper_funded_event(env: &Env, funder: &Address, amount: i128) {
+    env.events()
+        .publish((symbol_short!("kp_fund"),), (funder.clone(), amount));
+}
+
+/// keeper bounty payout event
+pub fn emit_keeper_paid_event(env: &Env, keeper: &Address, amount: i128) {
+    env.events()
+        .publish((symbol_short!("kp_paid"),), (keeper.clone(), amount));
+}
+
+/// extension contract registered or retired by the admin event
+pub fn emit_extension_event(env: &Env, extension: &Address, enabled: bool) {
+    env.events()
+        .publish((symbol_short!("extension"), extension.clone()), enabled);
+}
+
+/// asset paused or unpaused by the admin or an extension event
+pub fn emit_asset_paused_event(env: &Env, asset_id: u64, paused: bool) {
+    env.events()
+        .publish((symbol_short!("paused"), asset_id), paused);
+}
+
+/// XLM holdings fell short of the keeper pool and the asset was paused event.
+/// Carries (owed, held).
+pub fn emit_invariant_violation_event(env: &Env, asset_id: u64, xlm: (i128, i128)) {
+    env.events()
+        .publish((symbol_short!("inv_alert"), asset_id), xlm);
+}
+
+/// proposal lifecycle event on a party's account topic, for per-account subscriptions
+pub fn emit_account_event(
+    env: &Env,
+    account: &Address,
+    kind: Symbol,
+    seq: u64,
+    proposal: &SaleProposal,
+) {
+    env.events().publish(
+        (symbol_short!("acct"), account.clone(), kind),
+        (
+            seq,
+            proposal.seller.clone(),
+            proposal.buyer.clone(),
+            proposal.asset_id,
+            proposal.token_amount,
+            proposal.price,
+        ),
+    );
+}
+
+/// Entry `index` of the admin action log, so archivers can keep entries after they are folded
+pub fn emit_admin_log_event(env: &Env, index: u32, entry: &AdminLogEntry) {
+    env.events()
+        .publish((symbol_short!("admin_log"), index), entry.clone());
+}
@@ -1,3 +1,4 @@
+use crate::storage::UpstreamPause;
 use soroban_sdk::{contractclient, Address, Env};
 
 // FNFT contract interface for cross-contract calls
@@ -5,6 +6,7 @@ use soroban_sdk::{contractclient, Address, Env};
 pub trait FNFTInterface {
     fn asset_exists(env: Env, asset_id: u64) -> bool;
     fn balance_of(env: Env, owner: Address, asset_id: u64) -> u64;
+    fn transfer(env: Env, from: Address, to: Address, asset_id: u64, amount: u64);
     fn transfer_from(
         env: Env,
         operator: Address,
@@ -15,4 +17,25 @@ pub trait FNFTInterface {
     );
     fn approve(env: Env, owner: Address, operator: Address, asset_id: u64, amount: u64);
     fn allowance(env: Env, owner: Address, operator: Address, asset_id: u64) -> u64;
+    fn asset_manager(env: Env, asset_id: u64) -> Address;
+    fn pause_state(env: Env) -> Option<UpstreamPause>;
+}
+
+// Funding contract that routed sale proceeds are deposited into
+#[contractclient(name = "FundingClient")]
+pub trait FundingInterface {
+    fn get_asset_sac(env: Env, asset_id: u64) -> Option<Address>;
+    fn deposit_funds(env: Env, depositor: Address, asset_id: u64, amount: i128);
+}
+
+// Governance contract proposed as governor, asked which fractcore it governs
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn get_fractcore_contract(env: Env) -> Address;
+}
+
+// Screening (sanctions / blacklist) contract consulted before trades settle
+#[contractclient(name = "ScreeningClient")]
+pub trait ScreeningInterface {
+    fn is_blocked(env: Env, address: Address) -> bool;
 }
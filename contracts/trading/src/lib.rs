@@ -1,6 +1,7 @@
 #![no_std]
 
 pub mod contract;
+pub mod errors;
 pub mod events;
 pub mod interfaces;
 pub mod methods;
@@ -8,4 +9,5 @@ pub mod storage;
 pub mod tests;
 
 pub use contract::*;
-pub use storage::{DataKey, SaleProposal, TradeHistory};
+pub use errors::TradingError;
+pub use storage::{DataKey, SaleProposal, SettlementBreakdown, TradeHistory, TradeRecordV2};
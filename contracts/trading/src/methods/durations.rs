@@ -0,0 +1,102 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::methods::{admin_log, platform, utils};
+use crate::storage::{
+    self, DataKey, SaleDurationLimits, DURATION_LIMIT_CEILING, DURATION_LIMIT_FLOOR,
+    MAX_SALE_DURATION, MIN_SALE_DURATION,
+};
+use soroban_sdk::{Address, Env};
+
+/// Admin sets the sale duration limits for the whole deployment, until a
+/// governor takes them over
+pub fn set_sale_duration_limits(
+    env: Env,
+    admin: Address,
+    limits: SaleDurationLimits,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "set_sale_duration_limits", (limits.clone(),));
+    platform::require_no_governor(&env)?;
+
+    store_sale_duration_limits(&env, &limits)
+}
+
+/// Validate and store the deployment limits; also the governor's path
+pub fn store_sale_duration_limits(
+    env: &Env,
+    limits: &SaleDurationLimits,
+) -> Result<(), TradingError> {
+    validate(limits)?;
+
+    storage::set(env, &DataKey::SaleDurationLimits, limits);
+
+    events::emit_duration_limits_event(env, limits.clone());
+
+    Ok(())
+}
+
+/// Admin overrides the limits for one asset; `None` falls back to the deployment limits
+pub fn set_asset_sale_duration_limits(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    limits: Option<SaleDurationLimits>,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(
+        &env,
+        &admin,
+        "set_asset_sale_duration_limits",
+        (asset_id, limits.clone()),
+    );
+
+    let key = DataKey::AssetSaleDurationLimits(asset_id);
+    match &limits {
+        Some(limits) => {
+            validate(limits)?;
+            storage::set(&env, &key, limits);
+        }
+        None => storage::remove(&env, &key),
+    }
+
+    events::emit_asset_duration_limits_event(&env, asset_id, limits);
+
+    Ok(())
+}
+
+/// Deployment-wide limits (1 hour to 1 week until the admin changes them)
+pub fn get_sale_duration_limits(env: Env) -> SaleDurationLimits {
+    storage::get(&env, &DataKey::SaleDurationLimits).unwrap_or(SaleDurationLimits {
+        min_seconds: MIN_SALE_DURATION,
+        max_seconds: MAX_SALE_DURATION,
+    })
+}
+
+/// Limits that apply to sales of `asset_id`: its override, else the deployment limits
+pub fn get_asset_sale_duration_limits(env: Env, asset_id: u64) -> SaleDurationLimits {
+    storage::get(&env, &DataKey::AssetSaleDurationLimits(asset_id))
+        .unwrap_or_else(|| get_sale_duration_limits(env.clone()))
+}
+
+/// Check a proposal, offering, offer or listing interval against the asset's limits
+pub fn require_valid_duration(
+    env: &Env,
+    asset_id: u64,
+    duration_seconds: u64,
+) -> Result<(), TradingError> {
+    let limits = get_asset_sale_duration_limits(env.clone(), asset_id);
+    if !(limits.min_seconds..=limits.max_seconds).contains(&duration_seconds) {
+        return fail(TradingError::InvalidDuration);
+    }
+    Ok(())
+}
+
+fn validate(limits: &SaleDurationLimits) -> Result<(), TradingError> {
+    if limits.min_seconds < DURATION_LIMIT_FLOOR
+        || limits.max_seconds > DURATION_LIMIT_CEILING
+        || limits.min_seconds > limits.max_seconds
+    {
+        return fail(TradingError::InvalidDuration);
+    }
+    Ok(())
+}
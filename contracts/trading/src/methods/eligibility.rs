@@ -0,0 +1,107 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::utils;
+use crate::storage::{self, BuyerEligibility, DataKey};
+use soroban_sdk::{Address, Env};
+
+/// Seller restricts who may buy their fractions of an asset, e.g. to existing
+/// co-owners only. Applies to every proposal and offering of theirs for the
+/// asset and is checked when the trade settles; `None` lifts the restriction.
+pub fn set_buyer_eligibility(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+    eligibility: Option<BuyerEligibility>,
+) -> Result<(), TradingError> {
+    seller.require_auth();
+
+    let key = DataKey::BuyerEligibility(seller.clone(), asset_id);
+    match &eligibility {
+        Some(criteria) if criteria.min_holding > 0 || criteria.allowlist_only => {
+            storage::set(&env, &key, criteria)
+        }
+        Some(_) => return fail(TradingError::InvalidAmount),
+        None => storage::remove(&env, &key),
+    }
+
+    events::emit_eligibility_event(&env, &seller, asset_id, eligibility);
+
+    Ok(())
+}
+
+pub fn get_buyer_eligibility(env: Env, seller: Address, asset_id: u64) -> Option<BuyerEligibility> {
+    storage::get(&env, &DataKey::BuyerEligibility(seller, asset_id))
+}
+
+/// Asset manager (per fractcore) adds or removes a buyer on the asset's allowlist
+pub fn set_asset_allowlisted(
+    env: Env,
+    manager: Address,
+    asset_id: u64,
+    buyer: Address,
+    allowed: bool,
+) -> Result<(), TradingError> {
+    manager.require_auth();
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return fail(TradingError::AssetNotFound);
+    }
+    if fnft_client.asset_manager(&asset_id) != manager {
+        return fail(TradingError::Unauthorized);
+    }
+
+    let key = DataKey::AssetAllowlist(asset_id, buyer.clone());
+    if allowed {
+        storage::set(&env, &key, &true);
+    } else {
+        storage::remove(&env, &key);
+    }
+
+    events::emit_allowlist_event(&env, asset_id, &buyer, allowed);
+
+    Ok(())
+}
+
+pub fn is_asset_allowlisted(env: Env, asset_id: u64, buyer: Address) -> bool {
+    storage::has(&env, &DataKey::AssetAllowlist(asset_id, buyer))
+}
+
+/// Whether `buyer` currently meets the seller's criteria for the asset
+pub fn is_eligible_buyer(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<bool, TradingError> {
+    let criteria = match get_buyer_eligibility(env.clone(), seller, asset_id) {
+        Some(criteria) => criteria,
+        None => return Ok(true),
+    };
+
+    if criteria.allowlist_only && !is_asset_allowlisted(env.clone(), asset_id, buyer.clone()) {
+        return Ok(false);
+    }
+    if criteria.min_holding > 0 {
+        let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+        if fnft_client.balance_of(&buyer, &asset_id) < criteria.min_holding {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Refuse to settle with a buyer outside the seller's criteria
+pub fn require_eligible(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+    asset_id: u64,
+) -> Result<(), TradingError> {
+    if !is_eligible_buyer(env.clone(), seller.clone(), buyer.clone(), asset_id)? {
+        return fail(TradingError::BuyerNotEligible);
+    }
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{admin_log, platform, utils};
+use crate::storage::{
+    self, DataKeyExt, ExitQueue, ExitQueueConfig, ExitQueueEntry, ExitQueuePosition,
+    MAX_EXIT_QUEUE_ENTRIES,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Governor sets or clears the exit queue of an asset.
+///
+/// Sellers holding more than `size_threshold` fractions then sell only what the
+/// queue has released to them: each window `tranche` fractions are handed out
+/// to enrolled sellers in enrollment order. Clearing the config drops the queue.
+pub fn set_exit_queue(
+    env: Env,
+    governor: Address,
+    asset_id: u64,
+    config: Option<ExitQueueConfig>,
+) -> Result<(), TradingError> {
+    governor.require_auth();
+    if platform::get_governor(env.clone()) != Some(governor.clone()) {
+        return fail(TradingError::Unauthorized);
+    }
+    admin_log::record(
+        &env,
+        &governor,
+        "set_exit_queue",
+        (asset_id, config.clone()),
+    );
+
+    let Some(new_config) = config.clone() else {
+        storage::remove(&env, &DataKeyExt::ExitQueueConfig(asset_id));
+        storage::remove(&env, &DataKeyExt::ExitQueue(asset_id));
+        events::emit_exit_queue_config_event(&env, asset_id, config);
+        return Ok(());
+    };
+
+    if new_config.tranche == 0 {
+        return fail(TradingError::InvalidAmount);
+    }
+    if new_config.window_seconds == 0 {
+        return fail(TradingError::InvalidDuration);
+    }
+
+    // Hand out what the old windows released before the schedule changes
+    if let Some(old_config) = get_exit_queue_config(env.clone(), asset_id) {
+        let mut queue = current_queue(&env, asset_id, &old_config);
+        queue.next_window = window_of(&env, &new_config) + 1;
+        storage::set(&env, &DataKeyExt::ExitQueue(asset_id), &queue);
+    }
+    storage::set(&env, &DataKeyExt::ExitQueueConfig(asset_id), &new_config);
+
+    events::emit_exit_queue_config_event(&env, asset_id, config);
+
+    Ok(())
+}
+
+pub fn get_exit_queue_config(env: Env, asset_id: u64) -> Option<ExitQueueConfig> {
+    storage::get(&env, &DataKeyExt::ExitQueueConfig(asset_id))
+}
+
+/// Seller joins the back of the asset's exit queue to sell `amount` fractions.
+/// The first seller in an empty queue receives the current window's tranche at
+/// once. Returns the seller's position (0 = first in line).
+pub fn enroll_exit(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+    amount: u64,
+) -> Result<u32, TradingError> {
+    seller.require_auth();
+
+    let Some(config) = get_exit_queue_config(env.clone(), asset_id) else {
+        return fail(TradingError::ExitQueueNotConfigured);
+    };
+    if amount == 0 {
+        return fail(TradingError::InvalidTokenAmount);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if fnft_client.balance_of(&seller, &asset_id) < amount {
+        return fail(TradingError::InsufficientBalance);
+    }
+
+    let mut queue = current_queue(&env, asset_id, &config);
+    if find(&queue, &seller).is_some() {
+        return fail(TradingError::SaleAlreadyExists);
+    }
+    if queue.entries.len() >= MAX_EXIT_QUEUE_ENTRIES {
+        return fail(TradingError::InvalidAmount);
+    }
+
+    if queue.entries.is_empty() {
+        queue.next_window = window_of(&env, &config);
+    }
+    queue.entries.push_back(ExitQueueEntry {
+        seller: seller.clone(),
+        enrolled: amount,
+        released: 0,
+        sold: 0,
+        enrolled_at: env.ledger().timestamp(),
+    });
+    release_tranches(&env, &config, &mut queue);
+    storage::set(&env, &DataKeyExt::ExitQueue(asset_id), &queue);
+
+    events::emit_exit_enrolled_event(&env, &seller, asset_id, amount);
+
+    Ok(queue.entries.len() - 1)
+}
+
+/// Seller leaves the queue; fractions released but not sold are given up
+pub fn leave_exit_queue(env: Env, seller: Address, asset_id: u64) -> Result<(), TradingError> {
+    seller.require_auth();
+
+    let Some(config) = get_exit_queue_config(env.clone(), asset_id) else {
+        return fail(TradingError::SaleNotFound);
+    };
+    let mut queue = current_queue(&env, asset_id, &config);
+    let Some(index) = find(&queue, &seller) else {
+        return fail(TradingError::SaleNotFound);
+    };
+
+    queue.entries.remove(index);
+    storage::set(&env, &DataKeyExt::ExitQueue(asset_id), &queue);
+
+    events::emit_exit_left_event(&env, &seller, asset_id);
+
+    Ok(())
+}
+
+/// Enrolled sellers as of now, first in line first
+pub fn get_exit_queue(env: Env, asset_id: u64) -> Vec<ExitQueueEntry> {
+    match get_exit_queue_config(env.clone(), asset_id) {
+        Some(config) => current_queue(&env, asset_id, &config).entries,
+        None => Vec::new(&env),
+    }
+}
+
+pub fn get_exit_queue_position(
+    env: Env,
+    asset_id: u64,
+    seller: Address,
+) -> Option<ExitQueuePosition> {
+    let config = get_exit_queue_config(env.clone(), asset_id)?;
+    let queue = current_queue(&env, asset_id, &config);
+    let index = find(&queue, &seller)?;
+
+    let mut ahead = 0u64;
+    for entry in queue.entries.iter().take(index as usize) {
+        ahead += entry.enrolled - entry.released;
+    }
+    let entry = queue.entries.get(index).unwrap();
+
+    Some(ExitQueuePosition {
+        position: index,
+        sellable: entry.released - entry.sold,
+        entry,
+        ahead,
+        next_release_at: queue.next_window.saturating_mul(config.window_seconds),
+    })
+}
+
+/// Settlement paths call this before `amount` fractions leave a seller who
+/// holds `holding`. Sellers above the asset's size threshold must be enrolled
+/// and may only sell what the queue has released to them.
+pub fn draw(
+    env: &Env,
+    seller: &Address,
+    asset_id: u64,
+    amount: u64,
+    holding: u64,
+) -> Result<(), TradingError> {
+    let Some(config) = get_exit_queue_config(env.clone(), asset_id) else {
+        return Ok(());
+    };
+    if holding <= config.size_threshold {
+        return Ok(());
+    }
+
+    let mut queue = current_queue(env, asset_id, &config);
+    let Some(index) = find(&queue, seller) else {
+        return fail(TradingError::ExceedsReleasedTranche);
+    };
+    let mut entry = queue.entries.get(index).unwrap();
+    if amount > entry.released - entry.sold {
+        return fail(TradingError::ExceedsReleasedTranche);
+    }
+
+    entry.sold += amount;
+    if entry.sold >= entry.enrolled {
+        queue.entries.remove(index);
+    } else {
+        queue.entries.set(index, entry.clone());
+    }
+    storage::set(env, &DataKeyExt::ExitQueue(asset_id), &queue);
+
+    events::emit_exit_sold_event(env, seller, asset_id, amount, entry.enrolled - entry.sold);
+
+    Ok(())
+}
+
+/// Stored queue with every window that has started since it was written released
+fn current_queue(env: &Env, asset_id: u64, config: &ExitQueueConfig) -> ExitQueue {
+    let mut queue = storage::get(env, &DataKeyExt::ExitQueue(asset_id)).unwrap_or(ExitQueue {
+        entries: Vec::new(env),
+        next_window: window_of(env, config),
+    });
+    release_tranches(env, config, &mut queue);
+    queue
+}
+
+/// Hand each started window's tranche to the sellers in line order
+fn release_tranches(env: &Env, config: &ExitQueueConfig, queue: &mut ExitQueue) {
+    let window = window_of(env, config);
+    if window < queue.next_window {
+        return;
+    }
+
+    let mut budget = config
+        .tranche
+        .saturating_mul(window - queue.next_window + 1);
+    for index in 0..queue.entries.len() {
+        if budget == 0 {
+            break;
+        }
+        let mut entry = queue.entries.get(index).unwrap();
+        let released = budget.min(entry.enrolled - entry.released);
+        entry.released += released;
+        budget -= released;
+        queue.entries.set(index, entry);
+    }
+    queue.next_window = window + 1;
+}
+
+fn window_of(env: &Env, config: &ExitQueueConfig) -> u64 {
+    env.ledger().timestamp() / config.window_seconds
+}
+
+fn find(queue: &ExitQueue, seller: &Address) -> Option<u32> {
+    queue
+        .entries
+        .iter()
+        .position(|entry| entry.seller == *seller)
+        .map(|index| index as u32)
+}
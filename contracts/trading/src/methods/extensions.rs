@@ -0,0 +1,190 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{
+    admin_log, eligibility, exit_queue, history, invariants, keeper, reputation, revenue,
+    screening, utils,
+};
+use crate::storage::{self, Conduct, DataKey, DataKeyExt, SaleProposal};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+// Extensions are contracts the admin registers to settle trades this contract
+// does not list itself: escrows, forward and arbitrated sales, offerings,
+// buyer offers and the like. They keep their own records and custody, and go
+// through the hooks below for what every trade shares: screening, pauses,
+// eligibility, exit queues, the trading fee and revenue routing, the trade
+// history, reputation and the keeper pool.
+
+/// Admin registers or retires an extension contract
+pub fn set_extension(
+    env: Env,
+    admin: Address,
+    extension: Address,
+    enabled: bool,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "set_extension", (extension.clone(), enabled));
+
+    let key = DataKeyExt::Extension(extension.clone());
+    if enabled {
+        storage::set(&env, &key, &true);
+    } else {
+        storage::remove(&env, &key);
+    }
+
+    events::emit_extension_event(&env, &extension, enabled);
+
+    Ok(())
+}
+
+pub fn is_extension(env: Env, extension: Address) -> bool {
+    storage::has(&env, &DataKeyExt::Extension(extension))
+}
+
+/// Screening and pauses, which every trade passes before any fraction moves
+pub fn check_parties(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), TradingError> {
+    screening::require_not_blocked(&env, &seller, &buyer)?;
+    invariants::require_not_paused(&env, asset_id)
+}
+
+/// `check_parties` plus the seller's buyer eligibility criteria, for trades
+/// the seller listed
+pub fn check_trade(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), TradingError> {
+    check_parties(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
+    eligibility::require_eligible(&env, &seller, &buyer, asset_id)
+}
+
+/// Count fractions leaving the seller against its exit queue tranche
+pub fn draw_exit(
+    env: Env,
+    extension: Address,
+    seller: Address,
+    asset_id: u64,
+    amount: u64,
+) -> Result<(), TradingError> {
+    require_extension(&env, &extension)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    let seller_balance = fnft_client.balance_of(&seller, &asset_id);
+    exit_queue::draw(&env, &seller, asset_id, amount, seller_balance)
+}
+
+/// Take `amount` of XLM from `payer` and pay it to a seller, less the trading
+/// fee and through revenue routing like any sale. The proceeds come in with
+/// the call, so an extension only ever pays out what it brings; a contract
+/// paying from its own holdings authorizes the one transfer to this contract.
+pub fn pay_seller(
+    env: Env,
+    extension: Address,
+    payer: Address,
+    seller: Address,
+    asset_id: u64,
+    amount: i128,
+) -> Result<(), TradingError> {
+    require_extension(&env, &extension)?;
+
+    if amount < 0 {
+        return fail(TradingError::InvalidAmount);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+    let contract_id = env.current_contract_address();
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract_address(env.clone())?);
+    if xlm_client.balance(&payer) < amount {
+        return fail(TradingError::BuyerInsufficientFunds);
+    }
+    xlm_client.transfer(&payer, &contract_id, &amount);
+
+    revenue::pay_seller(&env, &contract_id, &seller, asset_id, amount)?;
+    invariants::check_invariants(&env, asset_id)?;
+
+    Ok(())
+}
+
+/// Record a trade the extension settled; returns its trade ID
+pub fn record_trade(
+    env: Env,
+    extension: Address,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+) -> Result<u32, TradingError> {
+    require_extension(&env, &extension)?;
+
+    let now = env.ledger().timestamp();
+    let proposal = SaleProposal {
+        seller,
+        buyer,
+        asset_id,
+        token_amount,
+        price,
+        is_active: false,
+        timestamp: now,
+        expires_at: now,
+        arbiter: None,
+    };
+    let trade_id = history::record_trade(&env, &proposal)?;
+    utils::add_to_asset_trades(&env, asset_id, trade_id);
+
+    events::emit_trade_event(&env, &proposal, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Count a cancellation, lapsed listing or dispute in an address's reputation
+pub fn record_conduct(
+    env: Env,
+    extension: Address,
+    party: Address,
+    conduct: Conduct,
+) -> Result<(), TradingError> {
+    require_extension(&env, &extension)?;
+
+    match conduct {
+        Conduct::Cancellation => reputation::record_cancellation(&env, &party),
+        Conduct::Expiry => reputation::record_expiry(&env, &party),
+        Conduct::Dispute => reputation::record_dispute(&env, &party),
+    }
+
+    Ok(())
+}
+
+/// Pay the keeper bounty for maintenance the extension carried out
+pub fn pay_keeper(env: Env, extension: Address, keeper: Address) -> Result<i128, TradingError> {
+    require_extension(&env, &extension)?;
+
+    keeper::pay_keeper(&env, &keeper)
+}
+
+/// Halt trading of an asset whose holdings no longer cover what the extension
+/// owes; the admin unpauses it with `set_asset_paused`
+pub fn pause_asset(env: Env, extension: Address, asset_id: u64) -> Result<(), TradingError> {
+    require_extension(&env, &extension)?;
+
+    storage::set(&env, &DataKey::AssetPaused(asset_id), &true);
+    events::emit_asset_paused_event(&env, asset_id, true);
+
+    Ok(())
+}
+
+fn require_extension(env: &Env, extension: &Address) -> Result<(), TradingError> {
+    extension.require_auth();
+
+    if !is_extension(env.clone(), extension.clone()) {
+        return fail(TradingError::Unauthorized);
+    }
+    Ok(())
+}
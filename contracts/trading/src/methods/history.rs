@@ -0,0 +1,132 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::methods::{admin_log, platform, queries, reputation, utils};
+use crate::storage::{
+    self, DataKey, DataKeyExt, SaleProposal, SettlementBreakdown, TradeHistory, TradeRecordV2,
+    MAX_TRADE_MIGRATION_BATCH, TRADE_RECORD_VERSION,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Record a settled trade and return its new trade ID.
+///
+/// The breakdown shows the trading fee in force when the trade is recorded; no
+/// royalties are withheld yet.
+pub fn record_trade(env: &Env, proposal: &SaleProposal) -> Result<u32, TradingError> {
+    let trade_id = queries::get_trade_count(env.clone()) + 1;
+
+    let record = TradeRecordV2 {
+        seller: proposal.seller.clone(),
+        buyer: proposal.buyer.clone(),
+        asset_id: proposal.asset_id,
+        token_amount: proposal.token_amount,
+        price: proposal.price,
+        timestamp: env.ledger().timestamp(),
+        referrer: None,
+        settlement: xlm_breakdown(env, proposal.price, platform::fee_on(env, proposal.price))?,
+    };
+
+    storage::set(env, &DataKey::TradeRecord(trade_id), &record);
+
+    storage::set(env, &DataKey::TradeCounter, &trade_id);
+
+    reputation::record_trade(env, &proposal.seller, &proposal.buyer, proposal.price);
+    for party in [&proposal.seller, &proposal.buyer] {
+        index_user_trade(env, party, trade_id);
+    }
+
+    Ok(trade_id)
+}
+
+fn index_user_trade(env: &Env, user: &Address, trade_id: u32) {
+    let key = DataKeyExt::UserTrades(user.clone());
+    let mut trades: Vec<u32> = storage::get(env, &key).unwrap_or(Vec::new(env));
+    trades.push_back(trade_id);
+    storage::set(env, &key, &trades);
+}
+
+/// Full record of a trade; trades stored before version 2 are upgraded on read
+pub fn get_trade_record(env: Env, trade_id: u32) -> Result<TradeRecordV2, TradingError> {
+    if let Some(record) = storage::get(&env, &DataKey::TradeRecord(trade_id)) {
+        return Ok(record);
+    }
+
+    match storage::get::<_, TradeHistory>(&env, &DataKey::TradeHistory(trade_id)) {
+        Some(history) => upgrade(&env, history),
+        None => fail(TradingError::TradeNotFound),
+    }
+}
+
+/// Version of the stored trade records: 1 until every legacy record is migrated
+pub fn get_trade_record_version(env: Env) -> u32 {
+    storage::get(&env, &DataKey::TradeRecordVersion).unwrap_or(1)
+}
+
+/// Rewrite up to `limit` legacy trade records as `TradeRecordV2` (admin only).
+///
+/// Progress is kept between calls, so the admin repeats the call until it returns
+/// true; the record version is then set to 2. `limit` is capped at
+/// `MAX_TRADE_MIGRATION_BATCH`.
+pub fn migrate_trade_records(env: Env, admin: Address, limit: u32) -> Result<bool, TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "migrate_trade_records", (limit,));
+
+    if get_trade_record_version(env.clone()) >= TRADE_RECORD_VERSION {
+        return Ok(true);
+    }
+
+    if limit == 0 {
+        return fail(TradingError::InvalidAmount);
+    }
+
+    let cursor: u32 = storage::get(&env, &DataKey::TradeMigrationCursor).unwrap_or(0);
+    let trade_count = queries::get_trade_count(env.clone());
+    let end = trade_count.min(cursor + limit.min(MAX_TRADE_MIGRATION_BATCH));
+
+    let mut migrated = 0u32;
+    for trade_id in (cursor + 1)..=end {
+        let legacy_key = DataKey::TradeHistory(trade_id);
+        if let Some(history) = storage::get::<_, TradeHistory>(&env, &legacy_key) {
+            let record = upgrade(&env, history)?;
+            storage::set(&env, &DataKey::TradeRecord(trade_id), &record);
+            storage::remove(&env, &legacy_key);
+            migrated += 1;
+        }
+    }
+
+    let done = end >= trade_count;
+    if done {
+        storage::remove(&env, &DataKey::TradeMigrationCursor);
+        storage::set(&env, &DataKey::TradeRecordVersion, &TRADE_RECORD_VERSION);
+    } else {
+        storage::set(&env, &DataKey::TradeMigrationCursor, &end);
+    }
+
+    events::emit_records_migrated_event(&env, end, migrated, done);
+
+    Ok(done)
+}
+
+/// Legacy trades were all paid in XLM with nothing withheld
+fn upgrade(env: &Env, history: TradeHistory) -> Result<TradeRecordV2, TradingError> {
+    Ok(TradeRecordV2 {
+        // Trades before version 2 predate the trading fee
+        settlement: xlm_breakdown(env, history.price, 0)?,
+        seller: history.seller,
+        buyer: history.buyer,
+        asset_id: history.asset_id,
+        token_amount: history.token_amount,
+        price: history.price,
+        timestamp: history.timestamp,
+        referrer: None,
+    })
+}
+
+fn xlm_breakdown(env: &Env, price: u128, fees: u128) -> Result<SettlementBreakdown, TradingError> {
+    Ok(SettlementBreakdown {
+        gross: price,
+        fees,
+        royalties: 0,
+        net_to_seller: price - fees,
+        payment_token: utils::get_xlm_contract_address(env.clone())?,
+    })
+}
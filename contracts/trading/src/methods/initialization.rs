@@ -1,22 +1,62 @@
+use crate::errors::{fail, TradingError};
 use crate::events;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::storage::{self, DataKey, TRADE_RECORD_VERSION};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
 
-pub fn initialize(env: Env, admin: Address, fnft_contract: Address, xlm_contract: Address) {
-    if env.storage().instance().has(&DataKey::Admin) {
-        panic!("Contract already initialized");
+pub fn initialize(
+    env: Env,
+    admin: Address,
+    fnft_contract: Address,
+    xlm_contract: Address,
+) -> Result<(), TradingError> {
+    admin.require_auth();
+    configure(env, admin, fnft_contract, xlm_contract)
+}
+
+/// Deploy-time initialization. The constructor runs atomically with contract
+/// creation, so no one can initialize first and the admin's auth is not needed.
+pub fn construct(
+    env: Env,
+    admin: Address,
+    fnft_contract: Address,
+    xlm_contract: Address,
+) -> Result<(), TradingError> {
+    configure(env, admin, fnft_contract, xlm_contract)
+}
+
+fn configure(
+    env: Env,
+    admin: Address,
+    fnft_contract: Address,
+    xlm_contract: Address,
+) -> Result<(), TradingError> {
+    if storage::has_or_bare(&env, &DataKey::Admin) {
+        return fail(TradingError::AlreadyInitialized);
     }
 
-    admin.require_auth();
+    // New deployments only ever write namespaced entries
+    storage::mark_namespaced(&env);
+
+    storage::set(&env, &DataKey::Admin, &admin);
+    storage::set(&env, &DataKey::FNFTContract, &fnft_contract);
+    storage::set(&env, &DataKey::XLMContract, &xlm_contract);
+    storage::set(&env, &DataKey::TradeCounter, &0u32);
+    // New deployments only ever write current-version trade records
+    storage::set(&env, &DataKey::TradeRecordVersion, &TRADE_RECORD_VERSION);
 
-    env.storage().instance().set(&DataKey::Admin, &admin);
-    env.storage()
-        .instance()
-        .set(&DataKey::FNFTContract, &fnft_contract);
-    env.storage()
-        .instance()
-        .set(&DataKey::XLMContract, &xlm_contract);
-    env.storage().instance().set(&DataKey::TradeCounter, &0u32);
+    let config_hash: BytesN<32> = env
+        .crypto()
+        .sha256(&(admin.clone(), fnft_contract.clone(), xlm_contract.clone()).to_xdr(&env))
+        .into();
+    storage::set(&env, &DataKey::InitConfigHash, &config_hash);
 
     events::emit_init_event(&env, &admin, &fnft_contract, &xlm_contract);
+
+    Ok(())
+}
+
+/// SHA-256 of the XDR-encoded initialization arguments (None for contracts
+/// initialized before the hash was recorded)
+pub fn get_init_config_hash(env: Env) -> Option<BytesN<32>> {
+    storage::get(&env, &DataKey::InitConfigHash)
 }
@@ -0,0 +1,115 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{admin_log, keeper, utils};
+use crate::storage::{self, DataKey, UpstreamPause};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Admin pauses or unpauses trading of an asset.
+///
+/// Assets are paused automatically when a post-settlement invariant check
+/// fails; unpausing is how an investigated asset is put back into service.
+pub fn set_asset_paused(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    paused: bool,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "set_asset_paused", (asset_id, paused));
+
+    if paused {
+        storage::set(&env, &DataKey::AssetPaused(asset_id), &true);
+    } else {
+        storage::remove(&env, &DataKey::AssetPaused(asset_id));
+    }
+
+    events::emit_asset_paused_event(&env, asset_id, paused);
+
+    Ok(())
+}
+
+/// Fractions of an asset this contract holds. Sales move fractions straight
+/// from seller to buyer and extensions keep their own custody, so any balance
+/// here was sent by mistake.
+pub fn get_rescuable_tokens(env: Env, asset_id: u64) -> Result<u64, TradingError> {
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    Ok(fnft_client.balance_of(&env.current_contract_address(), &asset_id))
+}
+
+/// Admin returns fractions sent to this contract by mistake
+pub fn rescue_fractions(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    to: Address,
+    amount: u64,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(
+        &env,
+        &admin,
+        "rescue_fractions",
+        (asset_id, to.clone(), amount),
+    );
+
+    if amount == 0 {
+        return fail(TradingError::InvalidTokenAmount);
+    }
+    if amount > get_rescuable_tokens(env.clone(), asset_id)? {
+        return fail(TradingError::InsufficientBalance);
+    }
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    fnft_client.transfer(&env.current_contract_address(), &to, &asset_id, &amount);
+
+    events::emit_rescued_event(&env, asset_id, &to, amount);
+
+    Ok(())
+}
+
+pub fn is_asset_paused(env: Env, asset_id: u64) -> bool {
+    storage::get(&env, &DataKey::AssetPaused(asset_id)).unwrap_or(false)
+}
+
+/// Fail trades of a paused asset with `AssetPaused`, and of every asset while
+/// fractcore is paused with `UpstreamPaused`, before any fraction moves.
+pub fn require_not_paused(env: &Env, asset_id: u64) -> Result<(), TradingError> {
+    if is_asset_paused(env.clone(), asset_id) {
+        return fail(TradingError::AssetPaused);
+    }
+    if get_upstream_pause(env.clone())?.is_some() {
+        return fail(TradingError::UpstreamPaused);
+    }
+    Ok(())
+}
+
+/// Fractcore's emergency pause, if any. Fails closed: a fractcore whose pause
+/// state cannot be read counts as paused and fails with `UpstreamPaused`.
+pub fn get_upstream_pause(env: Env) -> Result<Option<UpstreamPause>, TradingError> {
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    match FNFTClient::new(&env, &fnft_contract).try_pause_state() {
+        Ok(Ok(pause)) => Ok(pause),
+        _ => fail(TradingError::UpstreamPaused),
+    }
+}
+
+/// Check the contract still holds what it owes after a settlement.
+///
+/// Its XLM balance must cover the keeper pool. A violation pauses the asset and
+/// emits an alert instead of reverting, so the pause survives; it stays paused
+/// until the admin unpauses it. Returns whether the invariant held.
+pub fn check_invariants(env: &Env, asset_id: u64) -> Result<bool, TradingError> {
+    let xlm_client = TokenClient::new(env, &utils::get_xlm_contract_address(env.clone())?);
+    let xlm_balance = xlm_client.balance(&env.current_contract_address());
+    let xlm_owed = keeper::get_keeper_pool(env.clone());
+
+    if xlm_balance >= xlm_owed {
+        return Ok(true);
+    }
+
+    storage::set(env, &DataKey::AssetPaused(asset_id), &true);
+    events::emit_invariant_violation_event(env, asset_id, (xlm_owed, xlm_balance));
+
+    Ok(false)
+}
@@ -0,0 +1,67 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::methods::{admin_log, utils};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Admin sets the XLM bounty paid per successful maintenance call (0 disables)
+pub fn set_keeper_bounty(env: Env, admin: Address, bounty: i128) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "set_keeper_bounty", (bounty,));
+
+    if bounty < 0 {
+        return fail(TradingError::InvalidAmount);
+    }
+
+    storage::set(&env, &DataKey::KeeperBounty, &bounty);
+
+    events::emit_keeper_bounty_event(&env, bounty);
+
+    Ok(())
+}
+
+/// Anyone can top up the keeper pool with XLM
+pub fn fund_keeper_pool(env: Env, funder: Address, amount: i128) -> Result<(), TradingError> {
+    funder.require_auth();
+
+    if amount <= 0 {
+        return fail(TradingError::InvalidAmount);
+    }
+
+    let xlm_client = TokenClient::new(&env, &utils::get_xlm_contract_address(env.clone())?);
+    xlm_client.transfer(&funder, &env.current_contract_address(), &amount);
+
+    let pool = get_keeper_pool(env.clone());
+    storage::set(&env, &DataKey::KeeperPool, &(pool + amount));
+
+    events::emit_keeper_funded_event(&env, &funder, amount);
+
+    Ok(())
+}
+
+pub fn get_keeper_bounty(env: Env) -> i128 {
+    storage::get(&env, &DataKey::KeeperBounty).unwrap_or(0)
+}
+
+pub fn get_keeper_pool(env: Env) -> i128 {
+    storage::get(&env, &DataKey::KeeperPool).unwrap_or(0)
+}
+
+/// Pay the configured bounty to a keeper, capped by what is left in the pool.
+/// Callers must only invoke this after the maintenance work actually happened.
+pub fn pay_keeper(env: &Env, keeper: &Address) -> Result<i128, TradingError> {
+    let pool = get_keeper_pool(env.clone());
+    let payout = get_keeper_bounty(env.clone()).min(pool);
+    if payout <= 0 {
+        return Ok(0);
+    }
+
+    storage::set(env, &DataKey::KeeperPool, &(pool - payout));
+
+    let xlm_client = TokenClient::new(env, &utils::get_xlm_contract_address(env.clone())?);
+    xlm_client.transfer(&env.current_contract_address(), keeper, &payout);
+
+    events::emit_keeper_paid_event(env, keeper, payout);
+
+    Ok(payout)
+}
@@ -1,4 +1,17 @@
+pub mod admin_log;
+pub mod durations;
+pub mod eligibility;
+pub mod exit_queue;
+pub mod extensions;
+pub mod history;
 pub mod initialization;
+pub mod invariants;
+pub mod keeper;
+pub mod platform;
 pub mod queries;
+pub mod reputation;
+pub mod revenue;
 pub mod sales;
+pub mod screening;
+pub mod token_leg;
 pub mod utils;
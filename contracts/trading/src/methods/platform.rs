@@ -0,0 +1,170 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::GovernanceClient;
+use crate::methods::{admin_log, durations, utils};
+use crate::storage::{
+    self, DataKey, DataKeyExt, PlacedKey, TradingConfig, TradingFee, MAX_TRADING_FEE_BPS,
+};
+use soroban_sdk::{Address, BytesN, Env, Val, Vec};
+
+/// Hand the platform parameters to a governor, the governance contract voting
+/// with the platform asset. The admin makes the first hand-over; after that
+/// only the current governor can name its successor. The governor must answer
+/// `get_fractcore_contract` with the fractcore linked here, and cannot be
+/// cleared, so the admin never takes the parameters back.
+pub fn set_governor(
+    env: Env,
+    caller: Address,
+    governor: Option<Address>,
+) -> Result<(), TradingError> {
+    match get_governor(env.clone()) {
+        Some(current) => {
+            caller.require_auth();
+            if caller != current {
+                return fail(TradingError::Unauthorized);
+            }
+        }
+        None => utils::require_admin(&env, &caller)?,
+    }
+    let Some(target) = &governor else {
+        return fail(TradingError::NotGovernanceContract);
+    };
+    let fnft_contract = utils::get_fnft_contract(&env)?;
+    let governed = GovernanceClient::new(&env, target).try_get_fractcore_contract();
+    if !matches!(governed, Ok(Ok(fractcore)) if fractcore == fnft_contract) {
+        return fail(TradingError::NotGovernanceContract);
+    }
+    admin_log::record(&env, &caller, "set_governor", (governor.clone(),));
+
+    storage::set(&env, &DataKey::Governor, target);
+
+    events::emit_governor_event(&env, governor);
+
+    Ok(())
+}
+
+pub fn get_governor(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKey::Governor)
+}
+
+/// Governor replaces the trading fee and the deployment's sale duration limits
+pub fn apply_trading_config(
+    env: Env,
+    governor: Address,
+    config: TradingConfig,
+) -> Result<(), TradingError> {
+    governor.require_auth();
+    if get_governor(env.clone()) != Some(governor.clone()) {
+        return fail(TradingError::Unauthorized);
+    }
+    admin_log::record(&env, &governor, "apply_trading_config", (config.clone(),));
+
+    if config.fee.bps > MAX_TRADING_FEE_BPS {
+        return fail(TradingError::InvalidAmount);
+    }
+    durations::store_sale_duration_limits(&env, &config.duration_limits)?;
+
+    if config.fee.bps == 0 {
+        storage::remove(&env, &DataKey::TradingFee);
+    } else {
+        storage::set(&env, &DataKey::TradingFee, &config.fee);
+    }
+
+    events::emit_trading_config_event(&env, config);
+
+    Ok(())
+}
+
+/// Governor replaces this contract's code with an uploaded WASM. There is no
+/// admin path: without a governor the code cannot change.
+pub fn upgrade(env: Env, governor: Address, new_wasm_hash: BytesN<32>) -> Result<(), TradingError> {
+    governor.require_auth();
+    if get_governor(env.clone()) != Some(governor.clone()) {
+        return fail(TradingError::Unauthorized);
+    }
+    admin_log::record(&env, &governor, "upgrade", (new_wasm_hash.clone(),));
+
+    let old_wasm_hash: Option<BytesN<32>> = storage::get(&env, &DataKeyExt::WasmHash);
+    storage::set(&env, &DataKeyExt::WasmHash, &new_wasm_hash);
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    events::emit_upgraded_event(&env, old_wasm_hash, new_wasm_hash);
+
+    Ok(())
+}
+
+/// Move entries an upgraded deployment wrote before namespacing from their
+/// bare keys under their namespace (admin only). The admin lists the keys,
+/// `DataKey` ones in `keys` and `DataKeyExt` ones in `ext_keys`, over as many
+/// calls as the budget needs, and sets `last` on the final call
+/// to mark the contract namespaced, after which this entrypoint is closed.
+/// Until its own entry has moved, the admin is read from its bare key.
+/// A call takes at most MAX_MIGRATE_BATCH keys. Returns the number of entries moved.
+pub fn migrate_storage(
+    env: Env,
+    admin: Address,
+    keys: Vec<Val>,
+    ext_keys: Vec<Val>,
+    last: bool,
+) -> Result<u32, TradingError> {
+    admin.require_auth();
+    let stored_admin: Option<Address> =
+        storage::get(&env, &DataKey::Admin).or_else(|| storage::get_bare(&env, &DataKey::Admin));
+    match stored_admin {
+        Some(stored) if stored == admin => {}
+        Some(_) => return fail(TradingError::Unauthorized),
+        None => return fail(TradingError::NotInitialized),
+    }
+    if storage::is_namespaced(&env) {
+        return fail(TradingError::AlreadyInitialized);
+    }
+    if keys.len() + ext_keys.len() > storage::MAX_MIGRATE_BATCH {
+        return fail(TradingError::BatchTooLarge);
+    }
+
+    let mut moved = 0;
+    for key in keys.iter() {
+        moved += storage::migrate(&env, DataKey::NAMESPACE, key) as u32;
+    }
+    for key in ext_keys.iter() {
+        moved += storage::migrate(&env, DataKeyExt::NAMESPACE, key) as u32;
+    }
+    if last {
+        // Logged by the final batch alone: until then the log's own entries may
+        // still sit under their bare keys, and moving them would drop the entry
+        admin_log::record(
+            &env,
+            &admin,
+            "migrate_storage",
+            (keys.len(), ext_keys.len()),
+        );
+        storage::mark_namespaced(&env);
+    }
+    Ok(moved)
+}
+
+/// Fee withheld from seller proceeds, None while trading is fee-free
+pub fn get_trading_fee(env: Env) -> Option<TradingFee> {
+    storage::get(&env, &DataKey::TradingFee)
+}
+
+/// Admin changes to platform parameters stop once a governor holds them
+pub fn require_no_governor(env: &Env) -> Result<(), TradingError> {
+    if get_governor(env.clone()).is_some() {
+        return fail(TradingError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Share of `amount` the current fee withholds
+pub fn fee_on(env: &Env, amount: u128) -> u128 {
+    get_trading_fee(env.clone()).map_or(0, |fee| withheld(&fee, amount))
+}
+
+/// Share of `amount` a fee withholds, rounded down
+pub fn withheld(fee: &TradingFee, amount: u128) -> u128 {
+    // Split so large prices cannot overflow the multiplication
+    let bps = fee.bps as u128;
+    amount / 10_000 * bps + amount % 10_000 * bps / 10_000
+}
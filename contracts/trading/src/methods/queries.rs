@@ -1,80 +1,185 @@
+use crate::errors::{fail, TradingError};
 use crate::interfaces::FNFTClient;
-use crate::methods::utils;
-use crate::storage::{DataKey, SaleProposal, TradeHistory};
+use crate::methods::{history, utils};
+use crate::storage::{
+    self, AssetMarketStats, Cursor, DataKey, DataKeyExt, SaleProposal, TradeHistory, TradeRecordV2,
+    MAX_BULK_READ, MAX_MARKET_STATS_SCAN,
+};
 use soroban_sdk::{Address, Env, Vec};
 
 /// Get the XLM contract address
-pub fn get_xlm_contract_address_public(env: Env) -> Address {
+pub fn get_xlm_contract_address_public(env: Env) -> Result<Address, TradingError> {
     utils::get_xlm_contract_address(env)
 }
 
-pub fn get_sale_proposal(env: Env, seller: Address, buyer: Address, asset_id: u64) -> SaleProposal {
-    env.storage()
-        .persistent()
-        .get(&DataKey::SaleProposal(seller, buyer, asset_id))
-        .unwrap_or_else(|| panic!("Sale proposal not found"))
+pub fn get_sale_proposal(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<SaleProposal, TradingError> {
+    utils::get_sale_proposal(env, seller, buyer, asset_id)
 }
 
 pub fn sale_exists(env: Env, seller: Address, buyer: Address, asset_id: u64) -> bool {
-    env.storage()
-        .persistent()
-        .has(&DataKey::SaleProposal(seller, buyer, asset_id))
+    storage::has(&env, &DataKey::SaleProposal(seller, buyer, asset_id))
 }
 
 pub fn get_seller_sales(env: Env, seller: Address) -> Vec<(Address, u64)> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::SellerSales(seller))
-        .unwrap_or(Vec::new(&env))
+    storage::get(&env, &DataKey::SellerSales(seller)).unwrap_or(Vec::new(&env))
 }
 
 pub fn get_buyer_offers(env: Env, buyer: Address) -> Vec<(Address, u64)> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::BuyerOffers(buyer))
-        .unwrap_or(Vec::new(&env))
+    storage::get(&env, &DataKey::BuyerOffers(buyer)).unwrap_or(Vec::new(&env))
 }
 
-pub fn get_trade_history(env: Env, trade_id: u32) -> TradeHistory {
-    env.storage()
-        .persistent()
-        .get(&DataKey::TradeHistory(trade_id))
-        .unwrap_or_else(|| panic!("Trade not found"))
+/// Version 1 view of a trade, kept for existing integrations; see `get_trade_record`
+pub fn get_trade_history(env: Env, trade_id: u32) -> Result<TradeHistory, TradingError> {
+    let record = history::get_trade_record(env, trade_id)?;
+    Ok(TradeHistory {
+        seller: record.seller,
+        buyer: record.buyer,
+        asset_id: record.asset_id,
+        token_amount: record.token_amount,
+        price: record.price,
+        timestamp: record.timestamp,
+    })
 }
 
 pub fn get_trade_count(env: Env) -> u32 {
-    env.storage()
-        .instance()
-        .get(&DataKey::TradeCounter)
-        .unwrap_or(0)
+    storage::get(&env, &DataKey::TradeCounter).unwrap_or(0)
 }
 
 pub fn get_asset_trades(env: Env, asset_id: u64) -> Vec<u32> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::AssetTrades(asset_id))
-        .unwrap_or(Vec::new(&env))
+    storage::get(&env, &DataKey::AssetTrades(asset_id)).unwrap_or(Vec::new(&env))
 }
 
-pub fn get_fnft_contract_address(env: Env) -> Address {
-    utils::get_fnft_contract(&env)
+/// Up to `limit` trade ids of an asset from `cursor`, oldest first.
+///
+/// The asset's trade list only grows, so the cursor records its length when the
+/// walk started and trades settled meanwhile wait for the next walk.
+pub fn get_asset_trades_page(
+    env: Env,
+    asset_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<u32>, Option<Cursor>) {
+    let trades = get_asset_trades(env.clone(), asset_id);
+    let (start, end, next) = window(trades.len(), cursor, limit);
+    (trades.slice(start..end), next)
 }
 
-pub fn time_until_expiry(env: Env, seller: Address, buyer: Address, asset_id: u64) -> u64 {
-    let proposal = get_sale_proposal(env.clone(), seller, buyer, asset_id);
-    let current_time = env.ledger().timestamp();
+/// Up to `limit` trades the address took part in, as seller or buyer, from
+/// `cursor`, oldest first. Paged like `get_asset_trades_page`; trades settled
+/// before the per-address index existed are not listed.
+pub fn get_user_trades_page(
+    env: Env,
+    user: Address,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> (Vec<(u32, TradeRecordV2)>, Option<Cursor>) {
+    let trade_ids: Vec<u32> =
+        storage::get(&env, &DataKeyExt::UserTrades(user)).unwrap_or(Vec::new(&env));
+    let (start, end, next) = window(trade_ids.len(), cursor, limit);
+
+    let mut trades = Vec::new(&env);
+    for trade_id in trade_ids.slice(start..end).iter() {
+        if let Ok(record) = history::get_trade_record(env.clone(), trade_id) {
+            trades.push_back((trade_id, record));
+        }
+    }
+    (trades, next)
+}
 
-    if current_time >= proposal.expires_at {
-        0
+/// Bounds of the next page of an append-only list of `len` items, and the
+/// cursor after it. The walk stops at the length it started on.
+fn window(len: u32, cursor: Option<Cursor>, limit: u32) -> (u32, u32, Option<Cursor>) {
+    let snapshot = cursor
+        .as_ref()
+        .map_or(len as u64, |cursor| cursor.snapshot)
+        .min(len as u64) as u32;
+    let start = cursor.map_or(0, |cursor| cursor.position).min(snapshot);
+    let end = snapshot.min(start + page_limit(limit));
+
+    let next = (end < snapshot).then_some(Cursor {
+        page: 0,
+        position: end,
+        snapshot: snapshot as u64,
+    });
+    (start, end, next)
+}
+
+/// Items served per paginated call: `limit`, where 0 or anything above
+/// MAX_BULK_READ means MAX_BULK_READ
+fn page_limit(limit: u32) -> u32 {
+    if limit == 0 || limit > MAX_BULK_READ {
+        MAX_BULK_READ
     } else {
-        proposal.expires_at - current_time
+        limit
+    }
+}
+
+/// Latest price and the volume traded since `since`, for dashboards.
+///
+/// Only the `MAX_MARKET_STATS_SCAN` newest trades are summed, so the volume of a
+/// very active asset over a long window is a lower bound.
+pub fn get_asset_market_stats(
+    env: Env,
+    asset_id: u64,
+    since: u64,
+) -> Result<AssetMarketStats, TradingError> {
+    let trades = get_asset_trades(env.clone(), asset_id);
+    let mut stats = AssetMarketStats {
+        last_unit_price: None,
+        last_trade_at: None,
+        volume: 0,
+        trade_count: 0,
+    };
+
+    let oldest = trades.len().saturating_sub(MAX_MARKET_STATS_SCAN);
+    for index in (oldest..trades.len()).rev() {
+        let record = history::get_trade_record(env.clone(), trades.get(index).unwrap())?;
+        if stats.last_trade_at.is_none() {
+            stats.last_unit_price = Some(
+                pricing::price_per_token_from_total(record.price, record.token_amount, 0)
+                    .or_else(|err| fail(err.into()))?,
+            );
+            stats.last_trade_at = Some(record.timestamp);
+        }
+        if record.timestamp < since {
+            break;
+        }
+        stats.volume += record.price;
+        stats.trade_count += 1;
     }
+
+    Ok(stats)
+}
+
+pub fn get_fnft_contract_address(env: Env) -> Result<Address, TradingError> {
+    utils::get_fnft_contract(&env)
+}
+
+pub fn time_until_expiry(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<u64, TradingError> {
+    let proposal = utils::get_sale_proposal(env.clone(), seller, buyer, asset_id)?;
+    let current_time = env.ledger().timestamp();
+
+    Ok(proposal.expires_at.saturating_sub(current_time))
 }
 
-pub fn get_current_allowance(env: Env, seller: Address, asset_id: u64) -> u64 {
-    let fnft_contract = utils::get_fnft_contract(&env);
+pub fn get_current_allowance(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+) -> Result<u64, TradingError> {
+    let fnft_contract = utils::get_fnft_contract(&env)?;
     let fnft_client = FNFTClient::new(&env, &fnft_contract);
     let trading_contract_id = env.current_contract_address();
 
-    fnft_client.allowance(&seller, &trading_contract_id, &asset_id)
+    Ok(fnft_client.allowance(&seller, &trading_contract_id, &asset_id))
 }
@@ -0,0 +1,44 @@
+use crate::storage::{self, DataKey, Reputation};
+use soroban_sdk::{Address, Env};
+
+/// Both parties of a settled trade gain a completed trade and its XLM volume
+pub fn record_trade(env: &Env, seller: &Address, buyer: &Address, price: u128) {
+    for party in [seller, buyer] {
+        update(env, party, |reputation| {
+            reputation.completed_trades += 1;
+            reputation.volume = reputation.volume.saturating_add(price);
+        });
+    }
+}
+
+/// The address called off a listing or sale it had offered
+pub fn record_cancellation(env: &Env, party: &Address) {
+    update(env, party, |reputation| reputation.cancelled_proposals += 1);
+}
+
+/// The seller's proposal lapsed without being filled
+pub fn record_expiry(env: &Env, seller: &Address) {
+    update(env, seller, |reputation| reputation.expired_unsold += 1);
+}
+
+/// The address was party to a disputed settlement or milestone, whoever raised it
+pub fn record_dispute(env: &Env, party: &Address) {
+    update(env, party, |reputation| reputation.disputes += 1);
+}
+
+/// Counters for an address; all zero for one that never traded
+pub fn get_reputation(env: Env, address: Address) -> Reputation {
+    storage::get(&env, &DataKey::Reputation(address)).unwrap_or(Reputation {
+        completed_trades: 0,
+        volume: 0,
+        cancelled_proposals: 0,
+        expired_unsold: 0,
+        disputes: 0,
+    })
+}
+
+fn update(env: &Env, party: &Address, apply: impl FnOnce(&mut Reputation)) {
+    let mut reputation = get_reputation(env.clone(), party.clone());
+    apply(&mut reputation);
+    storage::set(env, &DataKey::Reputation(party.clone()), &reputation);
+}
@@ -0,0 +1,149 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FundingClient;
+use crate::methods::{admin_log, platform, utils};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Symbol,
+};
+
+/// Admin sets (or clears with `None`) the funding contract that routed sale
+/// proceeds are deposited into. Clearing it pays every seller directly again.
+pub fn set_funding_contract(
+    env: Env,
+    admin: Address,
+    funding_contract: Option<Address>,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(
+        &env,
+        &admin,
+        "set_funding_contract",
+        (funding_contract.clone(),),
+    );
+
+    match &funding_contract {
+        Some(contract) => storage::set(&env, &DataKey::FundingContract, contract),
+        None => storage::remove(&env, &DataKey::FundingContract),
+    }
+
+    events::emit_funding_contract_event(&env, funding_contract);
+
+    Ok(())
+}
+
+pub fn get_funding_contract(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKey::FundingContract)
+}
+
+/// Designate (or release) a seller, typically an organization's treasury,
+/// whose sale proceeds are deposited as revenue of the asset sold instead of
+/// paid to its wallet. The admin designates and the seller must agree, so the
+/// admin cannot redirect anyone else's proceeds.
+pub fn set_revenue_routing(
+    env: Env,
+    admin: Address,
+    seller: Address,
+    enabled: bool,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(
+        &env,
+        &admin,
+        "set_revenue_routing",
+        (seller.clone(), enabled),
+    );
+    seller.require_auth();
+
+    let key = DataKey::RevenueRoute(seller.clone());
+    if enabled {
+        if get_funding_contract(env.clone()).is_none() {
+            return fail(TradingError::NotInitialized);
+        }
+        storage::set(&env, &key, &true);
+    } else {
+        storage::remove(&env, &key);
+    }
+
+    events::emit_revenue_route_event(&env, &seller, enabled);
+
+    Ok(())
+}
+
+pub fn is_revenue_routed(env: Env, seller: Address) -> bool {
+    storage::has(&env, &DataKey::RevenueRoute(seller))
+}
+
+/// Pay a seller the XLM proceeds of a sale of `asset_id`, held by `from`
+/// (the buyer, or this contract for escrowed XLM), less the trading fee, which
+/// goes straight to the fee recipient.
+///
+/// A routed seller's proceeds go through this contract into the funding
+/// contract's `deposit_funds` for the asset, so they reach the asset's SAC as
+/// revenue. Fails with `AssetNotFound` when the asset has no SAC in funding or
+/// its SAC is not the XLM token trades settle in.
+pub fn pay_seller(
+    env: &Env,
+    from: &Address,
+    seller: &Address,
+    asset_id: u64,
+    amount: i128,
+) -> Result<(), TradingError> {
+    // Funding refuses empty deposits
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let xlm_contract = utils::get_xlm_contract_address(env.clone())?;
+    let xlm_client = TokenClient::new(env, &xlm_contract);
+
+    let amount = match platform::get_trading_fee(env.clone()) {
+        Some(trading_fee) => {
+            let fee = platform::withheld(&trading_fee, amount as u128) as i128;
+            if fee > 0 {
+                xlm_client.transfer(from, &trading_fee.recipient, &fee);
+                events::emit_fee_event(env, &trading_fee.recipient, asset_id, fee);
+            }
+            amount - fee
+        }
+        None => amount,
+    };
+
+    let funding_contract = match get_funding_contract(env.clone()) {
+        Some(contract) if is_revenue_routed(env.clone(), seller.clone()) => contract,
+        _ => {
+            xlm_client.transfer(from, seller, &amount);
+            return Ok(());
+        }
+    };
+
+    let funding_client = FundingClient::new(env, &funding_contract);
+    if funding_client.get_asset_sac(&asset_id) != Some(xlm_contract.clone()) {
+        return fail(TradingError::AssetNotFound);
+    }
+
+    let contract_id = env.current_contract_address();
+    if *from != contract_id {
+        xlm_client.transfer(from, &contract_id, &amount);
+    }
+
+    // deposit_funds moves the XLM from this contract into the SAC
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: xlm_contract.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (contract_id.clone(), xlm_contract, amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+    funding_client.deposit_funds(&contract_id, &asset_id, &amount);
+
+    events::emit_revenue_event(env, seller, asset_id, amount);
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
+use crate::errors::{fail, fail_with, TradingError};
 use crate::events;
 use crate::interfaces::FNFTClient;
-use crate::methods::utils;
-use crate::storage::{DataKey, SaleProposal, MAX_SALE_DURATION, MIN_SALE_DURATION};
-#[allow(unused_imports)]
-use soroban_sdk::IntoVal;
+use crate::methods::token_leg::FractionLeg;
+use crate::methods::{
+    durations, eligibility, exit_queue, history, invariants, keeper, queries, reputation, revenue,
+    screening, token_leg, utils,
+};
+use crate::storage::{self, DataKey, DataKeyExt, SaleProposal, MAX_OPEN_SALES_PER_ACCOUNT};
 use soroban_sdk::{symbol_short, token::TokenClient, Address, Env};
 
 /// Seller confirms sale: grants allowance to the trading contract and creates the proposal
@@ -15,69 +18,75 @@ pub fn confirm_sale(
     token_amount: u64,
     price: u128,
     duration_seconds: u64,
-) {
+) -> Result<(), TradingError> {
+    let leg = token_leg::for_asset(&env, asset_id)?;
+    create_proposal(
+        env,
+        seller,
+        buyer,
+        asset_id,
+        token_amount,
+        price,
+        duration_seconds,
+        leg,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_proposal(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+    token_amount: u64,
+    price: u128,
+    duration_seconds: u64,
+    leg: FractionLeg,
+) -> Result<(), TradingError> {
     seller.require_auth();
 
     if token_amount == 0 {
-        panic!("Token amount must be > 0");
+        return fail(TradingError::InvalidTokenAmount);
     }
     if price == 0 {
-        panic!("Price must be > 0");
+        return fail(TradingError::InvalidPrice);
     }
     if seller == buyer {
-        panic!("Cannot trade with yourself");
-    }
-    if duration_seconds < MIN_SALE_DURATION || duration_seconds > MAX_SALE_DURATION {
-        panic!("Duration must be between 1 hour and 1 week");
+        return fail(TradingError::SelfTrade);
     }
+    durations::require_valid_duration(&env, asset_id, duration_seconds)?;
 
-    let fnft_contract = utils::get_fnft_contract(&env);
-    let fnft_client = FNFTClient::new(&env, &fnft_contract);
+    screening::require_not_blocked(&env, &seller, &buyer)?;
+    invariants::require_not_paused(&env, asset_id)?;
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
 
     if !fnft_client.asset_exists(&asset_id) {
-        panic!("Asset does not exist");
+        return fail(TradingError::AssetNotFound);
     }
 
-    let seller_balance = fnft_client.balance_of(&seller, &asset_id);
+    let seller_balance = leg.balance(&env, &seller, asset_id);
     if seller_balance < token_amount {
-        panic!("Insufficient balance");
+        return fail(TradingError::InsufficientBalance);
     }
 
     // Check if sale proposal already exists
-    if env.storage().persistent().has(&DataKey::SaleProposal(
-        seller.clone(),
-        buyer.clone(),
-        asset_id,
-    )) {
-        panic!("Sale proposal already exists - withdraw first");
+    if storage::has(
+        &env,
+        &DataKey::SaleProposal(seller.clone(), buyer.clone(), asset_id),
+    ) {
+        return fail(TradingError::SaleAlreadyExists);
     }
 
     // Grant allowance to trading contract for secure trade
-    let trading_contract_id = env.current_contract_address();
-    let current_allowance = fnft_client.allowance(&seller, &trading_contract_id, &asset_id);
-    let new_total_allowance = current_allowance + token_amount;
-
-    // Require authorization for allowance modification in production
-    #[cfg(not(test))]
-    seller.require_auth_for_args(
-        (
-            fnft_contract.clone(),
-            symbol_short!("approve"),
-            (
-                &seller,
-                &trading_contract_id,
-                &asset_id,
-                &new_total_allowance,
-            ),
-        )
-            .into_val(&env),
-    );
-
-    fnft_client.approve(
+    let expires_at = env.ledger().timestamp() + duration_seconds;
+    let current_allowance = leg.allowance(&env, &seller, asset_id);
+    leg.approve(
+        &env,
         &seller,
-        &trading_contract_id,
-        &asset_id,
-        &new_total_allowance,
+        asset_id,
+        current_allowance + token_amount,
+        Some(expires_at),
     );
 
     let proposal = SaleProposal {
@@ -88,19 +97,68 @@ pub fn confirm_sale(
         price,
         timestamp: env.ledger().timestamp(),
         is_active: true,
-        expires_at: env.ledger().timestamp() + duration_seconds,
+        expires_at,
+        arbiter: None,
     };
 
-    env.storage().persistent().set(
-        &DataKey::SaleProposal(seller.clone(), buyer.clone(), asset_id),
-        &proposal,
+    list_proposal(&env, &proposal)?;
+    token_leg::record_for_sale(&env, &proposal, &leg);
+
+    Ok(())
+}
+
+/// Store a proposal and index it for both parties.
+fn list_proposal(env: &Env, proposal: &SaleProposal) -> Result<(), TradingError> {
+    if queries::get_seller_sales(env.clone(), proposal.seller.clone()).len()
+        >= MAX_OPEN_SALES_PER_ACCOUNT
+        || queries::get_buyer_offers(env.clone(), proposal.buyer.clone()).len()
+            >= MAX_OPEN_SALES_PER_ACCOUNT
+    {
+        return fail(TradingError::TooManyProposals);
+    }
+
+    storage::set(
+        env,
+        &DataKey::SaleProposal(
+            proposal.seller.clone(),
+            proposal.buyer.clone(),
+            proposal.asset_id,
+        ),
+        proposal,
+    );
+
+    utils::add_to_seller_sales(
+        env,
+        proposal.seller.clone(),
+        proposal.buyer.clone(),
+        proposal.asset_id,
+    );
+
+    utils::add_to_buyer_offers(
+        env,
+        proposal.buyer.clone(),
+        proposal.seller.clone(),
+        proposal.asset_id,
     );
 
-    utils::add_to_seller_sales(&env, seller.clone(), buyer.clone(), asset_id);
+    events::emit_sale_event(env, proposal);
+    utils::notify_parties(env, symbol_short!("created"), proposal);
 
-    utils::add_to_buyer_offers(&env, buyer.clone(), seller.clone(), asset_id);
+    Ok(())
+}
 
-    events::emit_sale_event(&env, &proposal);
+/// Remove a proposal and its indexes without touching the allowance
+fn delist_proposal(env: &Env, seller: &Address, buyer: &Address, asset_id: u64) {
+    storage::remove(
+        env,
+        &DataKey::SaleProposal(seller.clone(), buyer.clone(), asset_id),
+    );
+    utils::remove_from_seller_sales(env, seller.clone(), buyer.clone(), asset_id);
+    utils::remove_from_buyer_offers(env, buyer.clone(), seller.clone(), asset_id);
+    storage::remove(
+        env,
+        &DataKeyExt::SaleToken(seller.clone(), buyer.clone(), asset_id),
+    );
 }
 
 /// Buyer finishes transaction: completes the trade
@@ -111,175 +169,232 @@ pub fn finish_transaction(
     asset_id: u64,
     expected_token_amount: u64,
     expected_price: u128,
-) {
+) -> Result<(), TradingError> {
     buyer.require_auth();
 
-    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id);
+    let proposal = open_proposal(
+        &env,
+        &buyer,
+        &seller,
+        asset_id,
+        expected_token_amount,
+        expected_price,
+    )?;
+
+    settle_proposal(&env, proposal)?;
+
+    Ok(())
+}
+
+/// Load a proposal the buyer is about to fill and check it is still fillable
+/// on the terms they expect
+fn open_proposal(
+    env: &Env,
+    buyer: &Address,
+    seller: &Address,
+    asset_id: u64,
+    expected_token_amount: u64,
+    expected_price: u128,
+) -> Result<SaleProposal, TradingError> {
+    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
     if !proposal.is_active {
-        panic!("Sale proposal is not active");
+        return fail(TradingError::SaleNotActive);
     }
-    if proposal.buyer != buyer {
-        panic!("Not authorized buyer for this sale");
+    if proposal.buyer != *buyer {
+        return fail(TradingError::UnauthorizedBuyer);
     }
     if env.ledger().timestamp() > proposal.expires_at {
-        panic!("Sale proposal has expired");
+        return fail(TradingError::SaleExpired);
     }
 
     // Validate buyer's expected terms to prevent bait-and-switch attacks
     if proposal.token_amount != expected_token_amount {
-        panic!(
-            "Token amount mismatch - expected {}, found {}",
-            expected_token_amount, proposal.token_amount
+        return fail_with(
+            TradingError::TokenAmountMismatch,
+            format_args!(
+                "Token amount mismatch - expected {}, found {}",
+                expected_token_amount, proposal.token_amount
+            ),
         );
     }
     if proposal.price != expected_price {
-        panic!(
-            "Price mismatch - expected {}, found {}",
-            expected_price, proposal.price
+        return fail_with(
+            TradingError::PriceMismatch,
+            format_args!(
+                "Price mismatch - expected {}, found {}",
+                expected_price, proposal.price
+            ),
         );
     }
 
-    let fnft_contract = utils::get_fnft_contract(&env);
-    let fnft_client = FNFTClient::new(&env, &fnft_contract);
-    let trading_contract_id = env.current_contract_address();
+    Ok(proposal)
+}
+
+/// Swap both legs of an accepted proposal and delist it; returns the trade id
+fn settle_proposal(env: &Env, proposal: SaleProposal) -> Result<u32, TradingError> {
+    let leg = token_leg::for_sale(env, &proposal)?;
+    swap(env, &proposal, &leg)?;
 
-    let seller_balance = fnft_client.balance_of(&proposal.seller, &proposal.asset_id);
+    // Reentrancy protection - Immediately clean up state
+    delist_proposal(env, &proposal.seller, &proposal.buyer, proposal.asset_id);
+    utils::notify_parties(env, symbol_short!("filled"), &proposal);
+
+    let trade_id = history::record_trade(env, &proposal)?;
+    utils::add_to_asset_trades(env, proposal.asset_id, trade_id);
+
+    events::emit_trade_event(env, &proposal, trade_id);
+
+    Ok(trade_id)
+}
+
+/// Check both parties can settle, then move the fractions through `leg` from
+/// the seller to the buyer and the price from the buyer to the seller in one
+/// atomic step
+fn swap(env: &Env, proposal: &SaleProposal, leg: &FractionLeg) -> Result<(), TradingError> {
+    screening::require_not_blocked(env, &proposal.seller, &proposal.buyer)?;
+    invariants::require_not_paused(env, proposal.asset_id)?;
+    eligibility::require_eligible(env, &proposal.seller, &proposal.buyer, proposal.asset_id)?;
+
+    let seller_balance = leg.balance(env, &proposal.seller, proposal.asset_id);
     if seller_balance < proposal.token_amount {
-        panic!("Seller has insufficient token balance");
+        return fail(TradingError::SellerInsufficientBalance);
     }
-
-    let xlm_contract_address = utils::get_xlm_contract_address(env.clone());
-    let xlm_client = TokenClient::new(&env, &xlm_contract_address);
-    let buyer_xlm_balance = xlm_client.balance(&buyer);
+    exit_queue::draw(
+        env,
+        &proposal.seller,
+        proposal.asset_id,
+        proposal.token_amount,
+        seller_balance,
+    )?;
+
+    let xlm_contract_address = utils::get_xlm_contract_address(env.clone())?;
+    let xlm_client = TokenClient::new(env, &xlm_contract_address);
+    let buyer_xlm_balance = xlm_client.balance(&proposal.buyer);
     if buyer_xlm_balance < proposal.price as i128 {
-        panic!("Buyer has insufficient XLM funds");
+        return fail(TradingError::BuyerInsufficientFunds);
     }
 
-    let allowance =
-        fnft_client.allowance(&proposal.seller, &trading_contract_id, &proposal.asset_id);
+    let allowance = leg.allowance(env, &proposal.seller, proposal.asset_id);
     if allowance < proposal.token_amount {
-        panic!("Insufficient allowance for token transfer");
+        return fail(TradingError::InsufficientAllowance);
+    }
+
+    if proposal.price > i128::MAX as u128 {
+        return fail(TradingError::PriceOverflow);
     }
 
     // Atomic transaction: All or nothing
-    fnft_client.transfer_from(
-        &trading_contract_id,
+    leg.transfer_from(
+        env,
         &proposal.seller,
         &proposal.buyer,
-        &proposal.asset_id,
-        &proposal.token_amount,
+        proposal.asset_id,
+        proposal.token_amount,
     );
+    revenue::pay_seller(
+        env,
+        &proposal.buyer,
+        &proposal.seller,
+        proposal.asset_id,
+        proposal.price as i128,
+    )?;
 
-    if proposal.price > i128::MAX as u128 {
-        panic!("Proposal price exceeds maximum allowable value for i128");
-    }
-    xlm_client.transfer(&buyer, &seller, &(proposal.price as i128));
-
-    // Reentrancy protection - Immediately clean up state
-    env.storage().persistent().remove(&DataKey::SaleProposal(
-        seller.clone(),
-        buyer.clone(),
-        asset_id,
-    ));
-    utils::remove_from_seller_sales(&env, seller.clone(), buyer.clone(), asset_id);
-    utils::remove_from_buyer_offers(&env, buyer.clone(), seller.clone(), asset_id);
-
-    let trade_id = utils::record_trade_history(&env, &proposal);
-    utils::add_to_asset_trades(&env, asset_id, trade_id);
+    invariants::check_invariants(env, proposal.asset_id)?;
 
-    events::emit_trade_event(&env, &proposal, trade_id);
+    Ok(())
 }
 
-pub fn cleanup_expired_sale(env: Env, seller: Address, buyer: Address, asset_id: u64) {
-    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id);
+/// Anyone can remove an expired proposal; the keeper earns the configured
+/// bounty unless they are a party to the proposal
+pub fn cleanup_expired_sale(
+    env: Env,
+    keeper: Address,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<i128, TradingError> {
+    keeper.require_auth();
+
+    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
 
     if env.ledger().timestamp() <= proposal.expires_at {
-        panic!("Sale has not expired yet");
+        return fail(TradingError::SaleNotExpired);
     }
 
-    env.storage().persistent().remove(&DataKey::SaleProposal(
-        seller.clone(),
-        buyer.clone(),
-        asset_id,
-    ));
-    utils::remove_from_seller_sales(&env, seller.clone(), buyer.clone(), asset_id);
-    utils::remove_from_buyer_offers(&env, buyer.clone(), seller.clone(), asset_id);
+    delist_proposal(&env, &seller, &buyer, asset_id);
+    utils::notify_parties(&env, symbol_short!("expired"), &proposal);
+    reputation::record_expiry(&env, &seller);
 
     env.events().publish(
         (symbol_short!("expired"),),
-        (seller, buyer, asset_id, proposal.token_amount),
+        (
+            seller.clone(),
+            buyer.clone(),
+            asset_id,
+            proposal.token_amount,
+        ),
     );
+
+    // Parties could otherwise farm bounties by letting their own proposals lapse
+    if keeper == seller || keeper == buyer {
+        return Ok(0);
+    }
+
+    keeper::pay_keeper(&env, &keeper)
 }
 
 /// Seller withdraws sale proposal: cancels the trade
-pub fn withdraw_sale(env: Env, seller: Address, buyer: Address, asset_id: u64) {
+pub fn withdraw_sale(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<(), TradingError> {
     seller.require_auth();
 
-    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id);
+    let proposal = utils::get_sale_proposal(env.clone(), seller.clone(), buyer.clone(), asset_id)?;
 
     if proposal.seller != seller {
-        panic!("Only the seller can withdraw this proposal");
+        return fail(TradingError::UnauthorizedSeller);
     }
 
     if !proposal.is_active {
-        panic!("Sale proposal is not active");
+        return fail(TradingError::SaleNotActive);
     }
 
     // Critical security: Reduce allowance by this proposal's amount
-    let fnft_contract = utils::get_fnft_contract(&env);
-    let fnft_client = FNFTClient::new(&env, &fnft_contract);
-    let trading_contract_id = env.current_contract_address();
-
-    let current_allowance = fnft_client.allowance(&seller, &trading_contract_id, &asset_id);
-    let new_allowance = if current_allowance >= proposal.token_amount {
-        current_allowance - proposal.token_amount
-    } else {
-        0 // Safety fallback
-    };
-
-    // Require authorization for allowance modification in production
-    #[cfg(not(test))]
-    seller.require_auth_for_args(
-        (
-            fnft_contract.clone(),
-            symbol_short!("approve"),
-            (&seller, &trading_contract_id, &asset_id, &new_allowance),
-        )
-            .into_val(&env),
-    );
-    fnft_client.approve(&seller, &trading_contract_id, &asset_id, &new_allowance);
+    let leg = token_leg::for_sale(&env, &proposal)?;
+    let current_allowance = leg.allowance(&env, &seller, asset_id);
+    // Saturate to 0 as a safety fallback
+    let new_allowance = current_allowance.saturating_sub(proposal.token_amount);
+    leg.approve(&env, &seller, asset_id, new_allowance, None);
 
-    env.storage().persistent().remove(&DataKey::SaleProposal(
-        seller.clone(),
-        buyer.clone(),
-        asset_id,
-    ));
-    utils::remove_from_seller_sales(&env, seller.clone(), buyer.clone(), asset_id);
-    utils::remove_from_buyer_offers(&env, buyer.clone(), seller.clone(), asset_id);
+    delist_proposal(&env, &seller, &buyer, asset_id);
+    utils::notify_parties(&env, symbol_short!("withdrawn"), &proposal);
+    reputation::record_cancellation(&env, &seller);
 
     events::emit_withdraw_event(&env, &seller, &buyer, asset_id);
+
+    Ok(())
 }
 
 /// Emergency function: Seller can reset all allowances to 0 for security
-pub fn emergency_reset_allowance(env: Env, seller: Address, asset_id: u64) {
+pub fn emergency_reset_allowance(
+    env: Env,
+    seller: Address,
+    asset_id: u64,
+) -> Result<(), TradingError> {
     seller.require_auth();
 
-    let fnft_contract = utils::get_fnft_contract(&env);
-    let fnft_client = FNFTClient::new(&env, &fnft_contract);
-    let trading_contract_id = env.current_contract_address();
-
-    // Reset allowance to 0
-    #[cfg(not(test))]
-    seller.require_auth_for_args(
-        (
-            fnft_contract.clone(),
-            symbol_short!("approve"),
-            (&seller, &trading_contract_id, &asset_id, &0u64),
-        )
-            .into_val(&env),
-    );
+    let fnft_contract = utils::get_fnft_contract(&env)?;
 
-    fnft_client.approve(&seller, &trading_contract_id, &asset_id, &0u64);
+    // Reset allowance to 0, on the asset's token too
+    utils::set_trading_allowance(&env, &fnft_contract, &seller, asset_id, 0);
+    if let Some(token) = token_leg::get_asset_token(env.clone(), asset_id) {
+        FractionLeg::Token(token).approve(&env, &seller, asset_id, 0, None);
+    }
 
     events::emit_emergency_reset_event(&env, &seller, asset_id);
+
+    Ok(())
 }
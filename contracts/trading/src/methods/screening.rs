@@ -0,0 +1,60 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::ScreeningClient;
+use crate::methods::{admin_log, utils};
+use crate::storage::{self, DataKey};
+use soroban_sdk::{Address, Env};
+
+/// Admin sets (or clears with `None`) the contract consulted before any trade settles
+pub fn set_screening_contract(
+    env: Env,
+    admin: Address,
+    screening_contract: Option<Address>,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(
+        &env,
+        &admin,
+        "set_screening_contract",
+        (screening_contract.clone(),),
+    );
+
+    match &screening_contract {
+        Some(contract) => storage::set(&env, &DataKey::ScreeningContract, contract),
+        None => storage::remove(&env, &DataKey::ScreeningContract),
+    }
+
+    events::emit_screening_event(&env, screening_contract);
+
+    Ok(())
+}
+
+pub fn get_screening_contract(env: Env) -> Option<Address> {
+    storage::get(&env, &DataKey::ScreeningContract)
+}
+
+/// Refuse to trade when the screening contract blocks either party.
+///
+/// Fails closed: a screening contract that errors is treated as blocking.
+/// The call reverts, so no event survives a refused trade.
+pub fn require_not_blocked(
+    env: &Env,
+    seller: &Address,
+    buyer: &Address,
+) -> Result<(), TradingError> {
+    let screening_contract = match get_screening_contract(env.clone()) {
+        Some(contract) => contract,
+        None => return Ok(()),
+    };
+    let client = ScreeningClient::new(env, &screening_contract);
+
+    for party in [seller, buyer] {
+        match client.try_is_blocked(party) {
+            Ok(Ok(false)) => {}
+            Ok(Ok(true)) => return fail(TradingError::AddressBlocked),
+            _ => return fail(TradingError::ScreeningFailed),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::errors::{fail, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::methods::{admin_log, utils};
+use crate::storage::{self, DataKeyExt, SaleProposal, LEDGER_CLOSE_SECONDS};
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+/// Contract a sale's fractions move through: fractcore's own allowance API, or
+/// a SEP-41 token (a wrapper token or SAC) with standard approve/transfer_from
+/// and allowances that lapse at an expiration ledger
+pub enum FractionLeg {
+    Fractcore(Address),
+    Token(Address),
+}
+
+/// Settle new direct sales of an asset in a SEP-41 token, or back in fractcore
+/// with `None` (admin only). Listed sales keep the leg they were created with.
+pub fn set_asset_token(
+    env: Env,
+    admin: Address,
+    asset_id: u64,
+    token: Option<Address>,
+) -> Result<(), TradingError> {
+    utils::require_admin(&env, &admin)?;
+    admin_log::record(&env, &admin, "set_asset_token", (asset_id, token.clone()));
+
+    let fnft_client = FNFTClient::new(&env, &utils::get_fnft_contract(&env)?);
+    if !fnft_client.asset_exists(&asset_id) {
+        return fail(TradingError::AssetNotFound);
+    }
+
+    let key = DataKeyExt::AssetToken(asset_id);
+    match &token {
+        Some(token) => storage::set(&env, &key, token),
+        None => storage::remove(&env, &key),
+    }
+
+    events::emit_asset_token_event(&env, asset_id, token);
+
+    Ok(())
+}
+
+pub fn get_asset_token(env: Env, asset_id: u64) -> Option<Address> {
+    storage::get(&env, &DataKeyExt::AssetToken(asset_id))
+}
+
+/// Leg a new sale of the asset settles in
+pub fn for_asset(env: &Env, asset_id: u64) -> Result<FractionLeg, TradingError> {
+    match get_asset_token(env.clone(), asset_id) {
+        Some(token) => Ok(FractionLeg::Token(token)),
+        None => fractcore(env),
+    }
+}
+
+/// Fractcore's allowance API, which sales that hold or escrow the fractions use
+pub fn fractcore(env: &Env) -> Result<FractionLeg, TradingError> {
+    Ok(FractionLeg::Fractcore(utils::get_fnft_contract(env)?))
+}
+
+/// Leg a listed sale settles in, as recorded when it was confirmed
+pub fn for_sale(env: &Env, proposal: &SaleProposal) -> Result<FractionLeg, TradingError> {
+    let key = DataKeyExt::SaleToken(
+        proposal.seller.clone(),
+        proposal.buyer.clone(),
+        proposal.asset_id,
+    );
+    match storage::get(env, &key) {
+        Some(token) => Ok(FractionLeg::Token(token)),
+        None => fractcore(env),
+    }
+}
+
+/// Remember a token leg for the listed sale; fractcore is the default
+pub fn record_for_sale(env: &Env, proposal: &SaleProposal, leg: &FractionLeg) {
+    if let FractionLeg::Token(token) = leg {
+        storage::set(
+            env,
+            &DataKeyExt::SaleToken(
+                proposal.seller.clone(),
+                proposal.buyer.clone(),
+                proposal.asset_id,
+            ),
+            token,
+        );
+    }
+}
+
+impl FractionLeg {
+    pub fn balance(&self, env: &Env, owner: &Address, asset_id: u64) -> u64 {
+        match self {
+            FractionLeg::Fractcore(fnft) => FNFTClient::new(env, fnft).balance_of(owner, &asset_id),
+            FractionLeg::Token(token) => to_amount(TokenClient::new(env, token).balance(owner)),
+        }
+    }
+
+    /// What this contract may move from `owner`; a token allowance reads 0
+    /// once its expiration ledger has passed
+    pub fn allowance(&self, env: &Env, owner: &Address, asset_id: u64) -> u64 {
+        let trading = env.current_contract_address();
+        match self {
+            FractionLeg::Fractcore(fnft) => {
+                FNFTClient::new(env, fnft).allowance(owner, &trading, &asset_id)
+            }
+            FractionLeg::Token(token) => {
+                to_amount(TokenClient::new(env, token).allowance(owner, &trading))
+            }
+        }
+    }
+
+    /// Set this contract's allowance over `owner`'s fractions to `amount`.
+    ///
+    /// A token allowance is kept live until the `live_until` timestamp and is
+    /// never shortened, as other open sales may rely on it; `None` keeps the
+    /// expiration granted before. The owner must authorize the nested approve.
+    pub fn approve(
+        &self,
+        env: &Env,
+        owner: &Address,
+        asset_id: u64,
+        amount: u64,
+        live_until: Option<u64>,
+    ) {
+        match self {
+            FractionLeg::Fractcore(fnft) => {
+                utils::set_trading_allowance(env, fnft, owner, asset_id, amount)
+            }
+            FractionLeg::Token(token) => {
+                let key = DataKeyExt::TokenAllowanceExpiry(owner.clone(), token.clone());
+                let granted: u32 = storage::get(env, &key).unwrap_or(0);
+                let expiration = match live_until {
+                    Some(live_until) => granted.max(expiration_ledger(env, live_until)),
+                    None => granted.max(env.ledger().sequence()),
+                };
+                storage::set(env, &key, &expiration);
+
+                TokenClient::new(env, token).approve(
+                    owner,
+                    &env.current_contract_address(),
+                    &(amount as i128),
+                    &expiration,
+                );
+            }
+        }
+    }
+
+    /// Move `amount` fractions from `from` under this contract's allowance
+    pub fn transfer_from(
+        &self,
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        asset_id: u64,
+        amount: u64,
+    ) {
+        let trading = env.current_contract_address();
+        match self {
+            FractionLeg::Fractcore(fnft) => {
+                FNFTClient::new(env, fnft).transfer_from(&trading, from, to, &asset_id, &amount)
+            }
+            FractionLeg::Token(token) => {
+                TokenClient::new(env, token).transfer_from(&trading, from, to, &(amount as i128))
+            }
+        }
+    }
+}
+
+/// First ledger expected to close after `live_until`, at LEDGER_CLOSE_SECONDS a ledger
+fn expiration_ledger(env: &Env, live_until: u64) -> u32 {
+    let remaining = live_until.saturating_sub(env.ledger().timestamp());
+    let ledgers = remaining.div_ceil(LEDGER_CLOSE_SECONDS) + 1;
+    env.ledger()
+        .sequence()
+        .saturating_add(u32::try_from(ledgers).unwrap_or(u32::MAX))
+}
+
+/// Token amounts are i128; fractions are counted in u64
+fn to_amount(amount: i128) -> u64 {
+    u64::try_from(amount.max(0)).unwrap_or(u64::MAX)
+}
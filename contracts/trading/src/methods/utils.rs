@@ -1,76 +1,87 @@
-use crate::storage::{DataKey, SaleProposal, TradeHistory};
-use soroban_sdk::{Address, Env, Vec};
-
-pub fn get_fnft_contract(env: &Env) -> Address {
-    env.storage()
-        .instance()
-        .get(&DataKey::FNFTContract)
-        .unwrap()
+use crate::errors::{fail, fail_with, TradingError};
+use crate::events;
+use crate::interfaces::FNFTClient;
+use crate::storage::{self, DataKey, SaleProposal};
+#[allow(unused_imports)]
+use soroban_sdk::{symbol_short, IntoVal};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+pub fn require_admin(env: &Env, admin: &Address) -> Result<(), TradingError> {
+    admin.require_auth();
+
+    let stored_admin: Option<Address> = storage::get(env, &DataKey::Admin);
+    match stored_admin {
+        Some(stored) if stored == *admin => Ok(()),
+        Some(_) => fail(TradingError::Unauthorized),
+        None => fail(TradingError::NotInitialized),
+    }
+}
+
+/// Set the seller's FNFT allowance for the trading contract.
+/// The seller must have authorized the nested approve call.
+pub fn set_trading_allowance(
+    env: &Env,
+    fnft_contract: &Address,
+    seller: &Address,
+    asset_id: u64,
+    amount: u64,
+) {
+    let trading_contract_id = env.current_contract_address();
+
+    // Require authorization for allowance modification in production
+    #[cfg(not(test))]
+    seller.require_auth_for_args(
+        (
+            fnft_contract.clone(),
+            symbol_short!("approve"),
+            (seller, &trading_contract_id, &asset_id, &amount),
+        )
+            .into_val(env),
+    );
+
+    FNFTClient::new(env, fnft_contract).approve(seller, &trading_contract_id, &asset_id, &amount);
 }
 
-pub fn get_xlm_contract_address(env: Env) -> Address {
-    env.storage()
-        .instance()
-        .get(&DataKey::XLMContract)
-        .unwrap_or_else(|| panic!("XLM contract address not configured"))
+pub fn get_fnft_contract(env: &Env) -> Result<Address, TradingError> {
+    match storage::get(env, &DataKey::FNFTContract) {
+        Some(address) => Ok(address),
+        None => fail(TradingError::NotInitialized),
+    }
 }
 
-pub fn get_sale_proposal(env: Env, seller: Address, buyer: Address, asset_id: u64) -> SaleProposal {
-    env.storage()
-        .persistent()
-        .get(&DataKey::SaleProposal(seller, buyer, asset_id))
-        .unwrap_or_else(|| panic!("Sale proposal not found"))
+pub fn get_xlm_contract_address(env: Env) -> Result<Address, TradingError> {
+    match storage::get(&env, &DataKey::XLMContract) {
+        Some(address) => Ok(address),
+        None => fail_with(
+            TradingError::NotInitialized,
+            format_args!("XLM contract address not configured"),
+        ),
+    }
 }
 
-/// Record trade history and return new trade ID
-pub fn record_trade_history(env: &Env, proposal: &SaleProposal) -> u32 {
-    let trade_id: u32 = env
-        .storage()
-        .instance()
-        .get(&DataKey::TradeCounter)
-        .unwrap_or(0);
-
-    let new_trade_id = trade_id + 1;
-
-    let history = TradeHistory {
-        seller: proposal.seller.clone(),
-        buyer: proposal.buyer.clone(),
-        asset_id: proposal.asset_id,
-        token_amount: proposal.token_amount,
-        price: proposal.price,
-        timestamp: env.ledger().timestamp(),
-    };
-
-    env.storage()
-        .persistent()
-        .set(&DataKey::TradeHistory(new_trade_id), &history);
-
-    env.storage()
-        .instance()
-        .set(&DataKey::TradeCounter, &new_trade_id);
-
-    new_trade_id
+pub fn get_sale_proposal(
+    env: Env,
+    seller: Address,
+    buyer: Address,
+    asset_id: u64,
+) -> Result<SaleProposal, TradingError> {
+    match storage::get(&env, &DataKey::SaleProposal(seller, buyer, asset_id)) {
+        Some(proposal) => Ok(proposal),
+        None => fail(TradingError::SaleNotFound),
+    }
 }
 
 pub fn add_to_seller_sales(env: &Env, seller: Address, buyer: Address, asset_id: u64) {
-    let mut sales: Vec<(Address, u64)> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::SellerSales(seller.clone()))
-        .unwrap_or(Vec::new(env));
+    let mut sales: Vec<(Address, u64)> =
+        storage::get(env, &DataKey::SellerSales(seller.clone())).unwrap_or(Vec::new(env));
 
     sales.push_back((buyer, asset_id));
-    env.storage()
-        .persistent()
-        .set(&DataKey::SellerSales(seller), &sales);
+    storage::set(env, &DataKey::SellerSales(seller), &sales);
 }
 
 pub fn remove_from_seller_sales(env: &Env, seller: Address, buyer: Address, asset_id: u64) {
-    let sales: Vec<(Address, u64)> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::SellerSales(seller.clone()))
-        .unwrap_or(Vec::new(env));
+    let sales: Vec<(Address, u64)> =
+        storage::get(env, &DataKey::SellerSales(seller.clone())).unwrap_or(Vec::new(env));
 
     let mut new_sales = Vec::new(env);
     for i in 0..sales.len() {
@@ -80,30 +91,20 @@ pub fn remove_from_seller_sales(env: &Env, seller: Address, buyer: Address, asse
         }
     }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::SellerSales(seller), &new_sales);
+    storage::set(env, &DataKey::SellerSales(seller), &new_sales);
 }
 
 pub fn add_to_buyer_offers(env: &Env, buyer: Address, seller: Address, asset_id: u64) {
-    let mut offers: Vec<(Address, u64)> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::BuyerOffers(buyer.clone()))
-        .unwrap_or(Vec::new(env));
+    let mut offers: Vec<(Address, u64)> =
+        storage::get(env, &DataKey::BuyerOffers(buyer.clone())).unwrap_or(Vec::new(env));
 
     offers.push_back((seller, asset_id));
-    env.storage()
-        .persistent()
-        .set(&DataKey::BuyerOffers(buyer), &offers);
+    storage::set(env, &DataKey::BuyerOffers(buyer), &offers);
 }
 
 pub fn remove_from_buyer_offers(env: &Env, buyer: Address, seller: Address, asset_id: u64) {
-    let offers: Vec<(Address, u64)> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::BuyerOffers(buyer.clone()))
-        .unwrap_or(Vec::new(env));
+    let offers: Vec<(Address, u64)> =
+        storage::get(env, &DataKey::BuyerOffers(buyer.clone())).unwrap_or(Vec::new(env));
 
     let mut new_offers = Vec::new(env);
     for i in 0..offers.len() {
@@ -113,20 +114,30 @@ pub fn remove_from_buyer_offers(env: &Env, buyer: Address, seller: Address, asse
         }
     }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::BuyerOffers(buyer), &new_offers);
+    storage::set(env, &DataKey::BuyerOffers(buyer), &new_offers);
 }
 
 pub fn add_to_asset_trades(env: &Env, asset_id: u64, trade_id: u32) {
-    let mut trades: Vec<u32> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AssetTrades(asset_id))
-        .unwrap_or(Vec::new(env));
+    let mut trades: Vec<u32> =
+        storage::get(env, &DataKey::AssetTrades(asset_id)).unwrap_or(Vec::new(env));
 
     trades.push_back(trade_id);
-    env.storage()
-        .persistent()
-        .set(&DataKey::AssetTrades(asset_id), &trades);
+    storage::set(env, &DataKey::AssetTrades(asset_id), &trades);
+}
+
+/// Emit a proposal lifecycle event on the seller's and the buyer's account topic.
+///
+/// Each account has its own sequence number, so a subscriber filtering on one
+/// address can spot missed events.
+pub fn notify_parties(env: &Env, kind: Symbol, proposal: &SaleProposal) {
+    for account in [&proposal.seller, &proposal.buyer] {
+        let seq = get_account_event_seq(env, account) + 1;
+        storage::set(env, &DataKey::AccountEventSeq(account.clone()), &seq);
+        events::emit_account_event(env, account, kind.clone(), seq, proposal);
+    }
+}
+
+/// Sequence number of the last account event emitted for an address
+pub fn get_account_event_seq(env: &Env, account: &Address) -> u64 {
+    storage::get(env, &DataKey::AccountEventSeq(account.clone())).unwrap_or(0)
 }
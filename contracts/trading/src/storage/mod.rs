@@ -1,4 +1,11 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+// Classes come from each key's `placement` (see the shared `placement` crate).
+// Trading keeps nothing in temporary storage: proposals still hold allowance
+// that is released after they expire.
+pub use admin_log::{AdminLogEntry, AdminLogHead, ADMIN_LOG_PAGE_SIZE, MAX_ADMIN_LOG_PAGES};
+pub use paging::Cursor;
+pub use placement::*;
 
 // Data structures for trading
 #[contracttype]
@@ -12,6 +19,7 @@ pub struct SaleProposal {
     pub is_active: bool,
     pub timestamp: u64,
     pub expires_at: u64,
+    pub arbiter: Option<Address>, // Always None: arbitrated sales are settled by the escrow extension
 }
 
 #[contracttype]
@@ -25,11 +33,145 @@ pub struct TradeHistory {
     pub timestamp: u64,
 }
 
-// Storage keys for trading contract
+/// How the buyer's payment for a trade was split
+#[contracttype]
+#[derive(Clone)]
+pub struct SettlementBreakdown {
+    pub gross: u128,         // What the buyer paid
+    pub fees: u128,          // Protocol fees withheld
+    pub royalties: u128,     // Creator royalties withheld
+    pub net_to_seller: u128, // gross - fees - royalties
+    pub payment_token: Address,
+}
+
+/// Trade record written since record version 2; older trades are upgraded on read
+#[contracttype]
+#[derive(Clone)]
+pub struct TradeRecordV2 {
+    pub seller: Address,
+    pub buyer: Address,
+    pub asset_id: u64,
+    pub token_amount: u64,
+    pub price: u128,
+    pub timestamp: u64,
+    pub referrer: Option<Address>,
+    pub settlement: SettlementBreakdown,
+}
+
+// Allowed lifetime of proposals, and of the listings extensions take
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleDurationLimits {
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+// Share of each sale's XLM proceeds withheld for the platform
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradingFee {
+    pub bps: u32, // Basis points of the seller's proceeds, at most MAX_TRADING_FEE_BPS
+    pub recipient: Address, // Receives the withheld fee
+}
+
+// Platform parameters a governor applies in one step
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradingConfig {
+    pub fee: TradingFee, // 0 bps withholds nothing
+    pub duration_limits: SaleDurationLimits,
+}
+
+// Pace at which large holders of an asset may sell
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitQueueConfig {
+    pub size_threshold: u64, // Sellers holding more fractions than this sell through the queue
+    pub tranche: u64,        // Fractions released to the queue each window
+    pub window_seconds: u64,
+}
+
+// Seller waiting in an asset's exit queue
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitQueueEntry {
+    pub seller: Address,
+    pub enrolled: u64, // Fractions the seller asked to exit
+    pub released: u64, // Of which released by the queue so far
+    pub sold: u64,     // Of which already settled
+    pub enrolled_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitQueue {
+    pub entries: Vec<ExitQueueEntry>, // Oldest enrollment first
+    pub next_window: u64,             // First window whose tranche is not handed out yet
+}
+
+// A seller's place in an exit queue
 #[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitQueuePosition {
+    pub position: u32, // 0 = first in line
+    pub entry: ExitQueueEntry,
+    pub sellable: u64, // Released and not yet sold
+    pub ahead: u64,    // Fractions still to be released to sellers ahead in line
+    pub next_release_at: u64,
+}
+
+// Seller's criteria a buyer must meet when a trade of the asset settles
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuyerEligibility {
+    pub min_holding: u64, // Fractions the buyer must already own (0 = no minimum)
+    pub allowlist_only: bool, // Buyer must be on the asset manager's allowlist
+}
+
+// Recent trading activity of an asset
+#[contracttype]
+#[derive(Clone)]
+pub struct AssetMarketStats {
+    pub last_unit_price: Option<u128>, // Price per fraction of the latest trade, rounded down
+    pub last_trade_at: Option<u64>,
+    pub volume: u128,     // XLM traded since the requested timestamp
+    pub trade_count: u32, // Trades since the requested timestamp
+}
+
+// Per-address counters kept as trades settle, lapse or are called off
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reputation {
+    pub completed_trades: u32,    // Trades settled as seller or buyer
+    pub volume: u128,             // XLM traded as seller or buyer
+    pub cancelled_proposals: u32, // Sales and offerings withdrawn, forward sales cancelled
+    pub expired_unsold: u32,      // Own proposals cleaned up after lapsing unfilled
+    pub disputes: u32,            // Settlements and milestones disputed, as either party
+}
+
+// Conduct an extension reports for an address's reputation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Conduct {
+    Cancellation, // Called off a listing or sale it had offered
+    Expiry,       // Own listing lapsed unfilled
+    Dispute,      // Party to a disputed settlement or milestone
+}
+
+// Fractcore's emergency pause, mirrored for cross-contract calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpstreamPause {
+    pub since: u64,
+    pub retry_after: u64, // When fractcore's admin expects to unpause (0 = unknown)
+}
+
+// Storage keys for trading contract
+#[contracttype(export = false)]
 pub enum DataKey {
     // Core contract data
     Admin,
+    InitConfigHash, // SHA-256 of the XDR-encoded initialization arguments
     FNFTContract,
     XLMContract, // Address of the XLM contract for payments
 
@@ -38,7 +180,10 @@ pub enum DataKey {
 
     // Trade history counter and records
     TradeCounter,
-    TradeHistory(u32), // trade_id -> TradeHistory
+    TradeHistory(u32),    // trade_id -> TradeHistory (legacy, version 1)
+    TradeRecord(u32),     // trade_id -> TradeRecordV2
+    TradeRecordVersion,   // 2 once every legacy record has been migrated
+    TradeMigrationCursor, // last trade_id checked by migrate_trade_records
 
     // User's active sales (for querying)
     SellerSales(Address), // seller -> Vec<(Address, u64)> (buyer, asset_id pairs)
@@ -46,8 +191,140 @@ pub enum DataKey {
 
     // Asset trade activity
     AssetTrades(u64), // asset_id -> Vec<u32> (trade_ids)
+
+    // Keeper incentives
+    KeeperBounty, // XLM paid per successful maintenance call
+    KeeperPool,   // XLM held by the contract for keeper bounties
+
+    // Optional sanctions/blacklist contract consulted before trades
+    ScreeningContract,
+
+    // Buyer eligibility
+    BuyerEligibility(Address, u64), // (seller, asset_id) -> BuyerEligibility
+    AssetAllowlist(u64, Address),   // (asset_id, buyer) -> true while allowlisted
+
+    // Circuit breaker
+    AssetPaused(u64), // asset_id -> true while trading is halted
+
+    // Per-account event subscriptions
+    AccountEventSeq(Address), // account -> sequence number of its last account event
+
+    // Sale duration limits
+    SaleDurationLimits,           // Deployment-wide SaleDurationLimits
+    AssetSaleDurationLimits(u64), // asset_id -> SaleDurationLimits override
+
+    // Counterparty track record
+    Reputation(Address), // address -> Reputation
+
+    // Sale proceeds deposited as asset revenue
+    FundingContract,       // Funding contract receiving routed proceeds
+    RevenueRoute(Address), // seller -> true while its proceeds are routed
+
+    // Platform parameters under token-holder governance
+    Governor, // Contract that alone may change the TradingConfig or upgrade the code once set
+    TradingFee, // TradingFee withheld from seller proceeds (none if unset)
+}
+
+impl PlacedKey for DataKey {
+    const NAMESPACE: Symbol = symbol_short!("trade");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKey::Admin
+            | DataKey::InitConfigHash
+            | DataKey::FNFTContract
+            | DataKey::XLMContract
+            | DataKey::TradeCounter
+            | DataKey::TradeRecordVersion
+            | DataKey::TradeMigrationCursor
+            | DataKey::KeeperBounty
+            | DataKey::KeeperPool
+            | DataKey::ScreeningContract
+            | DataKey::SaleDurationLimits
+            | DataKey::FundingContract
+            | DataKey::Governor
+            | DataKey::TradingFee => Placement::Instance,
+            DataKey::SaleProposal(..)
+            | DataKey::TradeHistory(..)
+            | DataKey::TradeRecord(..)
+            | DataKey::SellerSales(..)
+            | DataKey::BuyerOffers(..)
+            | DataKey::AssetTrades(..)
+            | DataKey::BuyerEligibility(..)
+            | DataKey::AssetAllowlist(..)
+            | DataKey::AssetPaused(..)
+            | DataKey::AccountEventSeq(..)
+            | DataKey::AssetSaleDurationLimits(..)
+            | DataKey::Reputation(..)
+            | DataKey::RevenueRoute(..) => Placement::Persistent,
+        }
+    }
+}
+
+// Storage keys added once DataKey reached the 50 variants a contracttype enum can hold
+#[contracttype(export = false)]
+pub enum DataKeyExt {
+    // Per-address activity
+    UserTrades(Address), // seller or buyer -> Vec<u32> (trade_ids)
+
+    // Upgrades
+    WasmHash, // Code installed by the last upgrade (absent = code deployed at creation)
+
+    // Exit queues for large sellers
+    ExitQueueConfig(u64), // asset_id -> ExitQueueConfig set by the governor
+    ExitQueue(u64),       // asset_id -> ExitQueue (enrolled sellers, first come first served)
+
+    // Admin action log
+    AdminLogHead,      // AdminLogHead: entry count and the hash of folded entries
+    AdminLogPage(u32), // page -> Vec<AdminLogEntry>, ADMIN_LOG_PAGE_SIZE per page
+
+    // SEP-41 fraction tokens
+    AssetToken(u64), // asset_id -> token new sales of the asset settle in
+    SaleToken(Address, Address, u64), // (seller, buyer, asset_id) -> token the open sale settles in
+    TokenAllowanceExpiry(Address, Address), // (seller, token) -> expiration ledger last granted
+
+    // Contracts settling trades through the extension hooks
+    Extension(Address), // extension -> true while registered by the admin
+}
+
+impl PlacedKey for DataKeyExt {
+    const NAMESPACE: Symbol = symbol_short!("trade_ext");
+
+    fn placement(&self) -> Placement {
+        match self {
+            DataKeyExt::WasmHash | DataKeyExt::AdminLogHead => Placement::Instance,
+            DataKeyExt::UserTrades(..)
+            | DataKeyExt::ExitQueueConfig(..)
+            | DataKeyExt::ExitQueue(..)
+            | DataKeyExt::AdminLogPage(..)
+            | DataKeyExt::AssetToken(..)
+            | DataKeyExt::SaleToken(..)
+            | DataKeyExt::TokenAllowanceExpiry(..)
+            | DataKeyExt::Extension(..) => Placement::Persistent,
+        }
+    }
+}
+
+impl admin_log::AdminLogKey for DataKeyExt {
+    fn head() -> Self {
+        DataKeyExt::AdminLogHead
+    }
+
+    fn page(page: u32) -> Self {
+        DataKeyExt::AdminLogPage(page)
+    }
 }
 
 // Constants
-pub const MIN_SALE_DURATION: u64 = 3600; // 1 hour
-pub const MAX_SALE_DURATION: u64 = 604800; // 1 week
+pub const MIN_SALE_DURATION: u64 = 3600; // 1 hour, default until the admin sets limits
+pub const MAX_SALE_DURATION: u64 = 604800; // 1 week, default until the admin sets limits
+pub const DURATION_LIMIT_FLOOR: u64 = 300; // Shortest minimum the admin can set (5 minutes)
+pub const DURATION_LIMIT_CEILING: u64 = 7776000; // Longest maximum the admin can set (90 days)
+pub const LEDGER_CLOSE_SECONDS: u64 = 5; // Turns a sale's expiry into a token allowance's expiration ledger
+pub const TRADE_RECORD_VERSION: u32 = 2;
+pub const MAX_TRADING_FEE_BPS: u32 = 1000; // 10% of a sale's proceeds
+pub const MAX_TRADE_MIGRATION_BATCH: u32 = 50; // Trades checked per migrate_trade_records call
+pub const MAX_MARKET_STATS_SCAN: u32 = 100; // Newest trades summed by get_asset_market_stats
+pub const MAX_BULK_READ: u32 = 50; // Keys accepted by one bulk getter call
+pub const MAX_OPEN_SALES_PER_ACCOUNT: u32 = 50; // Sale proposals a seller or buyer can be party to
+pub const MAX_EXIT_QUEUE_ENTRIES: u32 = 25; // Sellers waiting in one asset's exit queue
@@ -0,0 +1,51 @@
+#![cfg(test)]
+//! Canonical trading scenarios whose events must serialize to exactly the XDR
+//! in `golden/`. A failure means a topic or payload layout changed: if that is
+//! intended, rerun with `UPDATE_GOLDEN=1` and commit the new golden file so
+//! indexers see the change in review.
+
+extern crate std;
+
+use crate::contract::*;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use std::string::String;
+use test_support::{assert_golden, record_events};
+
+mod fnft {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
+}
+
+#[test]
+fn test_golden_trade_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let admin = Address::generate(&env);
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
+    let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
+    let xlm_contract_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let contract_id = env.register(
+        TradingContract,
+        (admin, fnft_contract_id, xlm_contract_id.clone()),
+    );
+    let client = TradingContractClient::new(&env, &contract_id);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    token::StellarAssetClient::new(&env, &xlm_contract_id).mint(&buyer, &10000);
+    let mut golden = String::new();
+
+    client.confirm_sale(&seller, &buyer, &asset_id, &100, &5000, &3600);
+    record_events(&env, &contract_id, "confirm_sale", &mut golden);
+
+    client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    record_events(&env, &contract_id, "finish_transaction", &mut golden);
+
+    client.confirm_sale(&seller, &buyer, &asset_id, &50, &2000, &3600);
+    client.withdraw_sale(&seller, &buyer, &asset_id);
+    record_events(&env, &contract_id, "withdraw_sale", &mut golden);
+
+    assert_golden(env!("CARGO_MANIFEST_DIR"), "trade", &golden);
+}
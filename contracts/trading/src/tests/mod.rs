@@ -1,6 +1,32 @@
+// Tests seed and inspect storage directly, whatever class a key is placed in
+#![allow(clippy::disallowed_methods)]
 // Test modules for trading contract
-#[cfg(test)]
+// With `sdk-compat` only the fractcore WASM suite is built, see scripts/sdk-compat.sh
+#[cfg(all(test, not(feature = "sdk-compat")))]
 pub mod unit_tests;
 
 #[cfg(test)]
 pub mod integration_tests;
+
+#[cfg(all(test, not(feature = "sdk-compat")))]
+pub mod golden_event_tests;
+
+#[cfg(test)]
+use crate::errors::TradingError;
+#[cfg(test)]
+use soroban_sdk::InvokeError;
+
+/// What a `try_` call returns when the contract fails with `err`. With
+/// `legacy-panics` the contract traps with the old message instead, which the
+/// host reports as an abort; the `should_panic` tests check those messages.
+#[cfg(test)]
+pub fn failed<T>(err: TradingError) -> Result<T, Result<TradingError, InvokeError>> {
+    #[cfg(feature = "legacy-panics")]
+    {
+        let _ = err;
+        Err(Err(InvokeError::Abort))
+    }
+
+    #[cfg(not(feature = "legacy-panics"))]
+    Err(Ok(err))
+}
@@ -1,17 +1,70 @@
 #![cfg(test)]
 
 use crate::contract::*;
+use crate::errors::TradingError;
+use crate::storage::{
+    self, BuyerEligibility, DataKey, DataKeyExt, ExitQueueConfig, PlacedKey, SaleDurationLimits,
+    TradeHistory, TradingConfig, TradingFee, UpstreamPause, MAX_OPEN_SALES_PER_ACCOUNT,
+};
+use crate::tests::failed;
+use placement::testutils::strip_namespaces;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, Env,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger, LedgerInfo},
+    token,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
 };
 
 // Import the FNFT contract for testing
 mod fnft {
     soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/fractcore.wasm");
 }
+// Funding contract that routed sale proceeds are deposited into
+mod funding {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/funding.wasm");
+}
+// Governance contract a governor must be, built for the fractcore trading uses
+#[allow(clippy::too_many_arguments)]
+mod governance {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/governance.wasm");
+}
 const DEFAULT_SALE_DURATION: u64 = 604800; // 1 week default
 
+/// A governance contract over `fnft_contract_id`, to hand the platform parameters to
+fn register_governance(env: &Env, admin: &Address, fnft_contract_id: &Address) -> Address {
+    env.register(
+        governance::WASM,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            Address::generate(env),
+            60u32,
+            30u32,
+            7u32,
+        ),
+    )
+}
+
+// Minimal screening contract with an admin-free blocklist
+mod mock_screening {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockScreening;
+
+    #[contractimpl]
+    impl MockScreening {
+        pub fn block(env: Env, address: Address) {
+            env.storage().persistent().set(&address, &true);
+        }
+
+        pub fn is_blocked(env: Env, address: Address) -> bool {
+            env.storage().persistent().has(&address)
+        }
+    }
+}
+
 fn setup() -> (
     Env,
     Address,
@@ -23,24 +76,29 @@ fn setup() -> (
 ) {
     let env = Env::default();
     env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
 
-    // Deploy FNFT contract
-    let fnft_contract_id = env.register(fnft::WASM, ());
+    // Deploy and initialize FNFT contract
+    let fnft_contract_id = env.register(fnft::WASM, (admin.clone(),));
     let fnft_client = fnft::Client::new(&env, &fnft_contract_id);
 
     // Create XLM token using Stellar Asset Contract
     let xlm_sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
     let xlm_contract_id = xlm_sac.address();
 
-    // Deploy Trading contract
-    let trading_contract_id = env.register(TradingContract, ());
+    // Deploy and initialize Trading contract
+    let trading_contract_id = env.register(
+        TradingContract,
+        (
+            admin.clone(),
+            fnft_contract_id.clone(),
+            xlm_contract_id.clone(),
+        ),
+    );
     let trading_client = TradingContractClient::new(&env, &trading_contract_id);
 
-    // Initialize contracts
-    let admin = Address::generate(&env);
-    fnft_client.initialize(&admin);
-    trading_client.initialize(&admin, &fnft_contract_id, &xlm_contract_id);
-
     // Use token::Client for XLM operations
     let xlm_client = token::Client::new(&env, &xlm_contract_id);
 
@@ -85,7 +143,38 @@ fn test_initialize_trading_contract() {
 }
 
 #[test]
-#[should_panic(expected = "Contract already initialized")]
+fn test_rescue_returns_fractions_sent_by_mistake() {
+    let (env, admin, _, _, trading_client, fnft_client, _) = setup();
+    let holder = Address::generate(&env);
+    let asset_id = fnft_client.mint(&holder, &1000);
+    let contract_id = trading_client.address.clone();
+
+    fnft_client.transfer(&holder, &contract_id, &asset_id, &50);
+    assert_eq!(trading_client.get_rescuable_tokens(&asset_id), 50);
+
+    assert_eq!(
+        trading_client.try_rescue_fractions(&holder, &asset_id, &holder, &50),
+        failed(TradingError::Unauthorized)
+    );
+    assert_eq!(
+        trading_client.try_rescue_fractions(&admin, &asset_id, &holder, &51),
+        failed(TradingError::InsufficientBalance)
+    );
+
+    trading_client.rescue_fractions(&admin, &asset_id, &holder, &50);
+    assert_eq!(fnft_client.balance_of(&holder, &asset_id), 1000);
+    assert_eq!(trading_client.get_rescuable_tokens(&asset_id), 0);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #1)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Contract already initialized")
+)]
 fn test_double_initialization() {
     let (env, _admin, fnft_contract_id, xlm_contract_id, trading_client, _fnft_client, _xlm_client) =
         setup();
@@ -143,7 +232,14 @@ fn test_withdraw_sale() {
 // === Error Condition Tests ===
 
 #[test]
-#[should_panic(expected = "Token amount must be > 0")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #3)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Token amount must be > 0")
+)]
 fn test_confirm_sale_zero_tokens() {
     let (
         env,
@@ -169,7 +265,14 @@ fn test_confirm_sale_zero_tokens() {
 }
 
 #[test]
-#[should_panic(expected = "Price must be > 0")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #4)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Price must be > 0")
+)]
 fn test_confirm_sale_zero_price() {
     let (
         env,
@@ -188,7 +291,14 @@ fn test_confirm_sale_zero_price() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot trade with yourself")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #5)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Cannot trade with yourself")
+)]
 fn test_confirm_sale_self_trade() {
     let (
         env,
@@ -213,7 +323,14 @@ fn test_confirm_sale_self_trade() {
 }
 
 #[test]
-#[should_panic(expected = "Asset does not exist")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #7)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Asset does not exist")
+)]
 fn test_confirm_sale_nonexistent_asset() {
     let (
         env,
@@ -231,7 +348,14 @@ fn test_confirm_sale_nonexistent_asset() {
 }
 
 #[test]
-#[should_panic(expected = "Insufficient balance")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #8)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Insufficient balance")
+)]
 fn test_confirm_sale_insufficient_balance() {
     let (
         env,
@@ -257,7 +381,14 @@ fn test_confirm_sale_insufficient_balance() {
 }
 
 #[test]
-#[should_panic(expected = "Sale proposal already exists - withdraw first")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #9)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Sale proposal already exists - withdraw first")
+)]
 fn test_confirm_sale_duplicate_proposal() {
     let (
         env,
@@ -295,7 +426,14 @@ fn test_confirm_sale_duplicate_proposal() {
 }
 
 #[test]
-#[should_panic(expected = "Sale proposal not found")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #10)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Sale proposal not found")
+)]
 fn test_finish_transaction_no_proposal() {
     let (
         env,
@@ -313,7 +451,14 @@ fn test_finish_transaction_no_proposal() {
 }
 
 #[test]
-#[should_panic(expected = "Buyer has insufficient XLM funds")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #19)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Buyer has insufficient XLM funds")
+)]
 fn test_finish_transaction_insufficient_xlm() {
     let (
         env,
@@ -342,7 +487,14 @@ fn test_finish_transaction_insufficient_xlm() {
 }
 
 #[test]
-#[should_panic(expected = "Seller has insufficient token balance")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #18)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Seller has insufficient token balance")
+)]
 fn test_finish_transaction_seller_insufficient_tokens() {
     let (
         env,
@@ -378,7 +530,62 @@ fn test_finish_transaction_seller_insufficient_tokens() {
 }
 
 #[test]
-#[should_panic(expected = "Sale proposal not found")]
+fn test_open_sales_per_account_are_capped() {
+    let (
+        env,
+        _admin,
+        _fnft_contract_id,
+        _xlm_contract_id,
+        trading_client,
+        fnft_client,
+        _xlm_client,
+    ) = setup();
+    let seller = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+
+    for _ in 0..MAX_OPEN_SALES_PER_ACCOUNT {
+        trading_client.confirm_sale(
+            &seller,
+            &Address::generate(&env),
+            &asset_id,
+            &1,
+            &100,
+            &DEFAULT_SALE_DURATION,
+        );
+    }
+    assert_eq!(
+        trading_client.get_seller_sales(&seller).len(),
+        MAX_OPEN_SALES_PER_ACCOUNT
+    );
+
+    let buyer = Address::generate(&env);
+    assert_eq!(
+        trading_client.try_confirm_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &1,
+            &100,
+            &DEFAULT_SALE_DURATION,
+        ),
+        failed(TradingError::TooManyProposals)
+    );
+    // Nothing was reserved for the refused proposal
+    assert_eq!(
+        fnft_client.allowance(&seller, &trading_client.address, &asset_id),
+        MAX_OPEN_SALES_PER_ACCOUNT as u64
+    );
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #10)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Sale proposal not found")
+)]
 fn test_withdraw_sale_unauthorized() {
     let (
         env,
@@ -467,7 +674,11 @@ fn test_view_functions() {
 }
 
 #[test]
-#[should_panic(expected = "Trade not found")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #22)")
+)]
+#[cfg_attr(feature = "legacy-panics", should_panic(expected = "Trade not found"))]
 fn test_get_nonexistent_trade_history() {
     let (
         _env,
@@ -485,7 +696,14 @@ fn test_get_nonexistent_trade_history() {
 // === Expiration Tests ===
 
 #[test]
-#[should_panic(expected = "Duration must be between 1 hour and 1 week")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #6)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Duration must be between 1 hour and 1 week")
+)]
 fn test_confirm_sale_invalid_duration_too_short() {
     let (
         env,
@@ -505,7 +723,14 @@ fn test_confirm_sale_invalid_duration_too_short() {
 }
 
 #[test]
-#[should_panic(expected = "Duration must be between 1 hour and 1 week")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #6)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Duration must be between 1 hour and 1 week")
+)]
 fn test_confirm_sale_invalid_duration_too_long() {
     let (
         env,
@@ -525,7 +750,14 @@ fn test_confirm_sale_invalid_duration_too_long() {
 }
 
 #[test]
-#[should_panic(expected = "Sale proposal has expired")]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #14)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Sale proposal has expired")
+)]
 fn test_finish_transaction_expired() {
     let (
         env,
@@ -598,14 +830,90 @@ fn test_cleanup_expired_sale() {
     });
 
     // Anyone can clean up expired sale
-    trading_client.cleanup_expired_sale(&seller, &buyer, &asset_id);
+    let keeper = Address::generate(&env);
+    trading_client.cleanup_expired_sale(&keeper, &seller, &buyer, &asset_id);
 
     // Verify sale was cleaned up
     assert!(!trading_client.sale_exists(&seller, &buyer, &asset_id));
 }
 
 #[test]
-#[should_panic(expected = "Sale has not expired yet")]
+fn test_cleanup_pays_keeper_bounty() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+
+    trading_client.set_keeper_bounty(&admin, &50);
+    mint_xlm_for_user(&env, &xlm_contract_id, &sponsor, 80);
+    trading_client.fund_keeper_pool(&sponsor, &80);
+    assert_eq!(trading_client.get_keeper_pool(), 80);
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    trading_client.confirm_sale(&seller, &buyer, &asset_id, &100, &5000, &3600);
+    trading_client.confirm_sale(&seller, &keeper, &asset_id, &100, &5000, &3600);
+
+    let current_ledger = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: env.ledger().timestamp() + 3601,
+        ..current_ledger
+    });
+
+    // A party to the proposal gets no bounty for cleaning up their own sale
+    assert_eq!(
+        trading_client.cleanup_expired_sale(&keeper, &seller, &keeper, &asset_id),
+        0
+    );
+
+    // An independent keeper is paid the bounty
+    assert_eq!(
+        trading_client.cleanup_expired_sale(&keeper, &seller, &buyer, &asset_id),
+        50
+    );
+    assert_eq!(xlm_client.balance(&keeper), 50);
+    assert_eq!(trading_client.get_keeper_pool(), 30);
+
+    // Nothing left to clean: no work, no payout
+    assert!(trading_client
+        .try_cleanup_expired_sale(&keeper, &seller, &buyer, &asset_id)
+        .is_err());
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #23)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Only admin can perform this action")
+)]
+fn test_set_keeper_bounty_unauthorized() {
+    let (
+        env,
+        _admin,
+        _fnft_contract_id,
+        _xlm_contract_id,
+        trading_client,
+        _fnft_client,
+        _xlm_client,
+    ) = setup();
+    let intruder = Address::generate(&env);
+
+    trading_client.set_keeper_bounty(&intruder, &50);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "legacy-panics"),
+    should_panic(expected = "Error(Contract, #15)")
+)]
+#[cfg_attr(
+    feature = "legacy-panics",
+    should_panic(expected = "Sale has not expired yet")
+)]
 fn test_cleanup_non_expired_sale() {
     let (
         env,
@@ -623,7 +931,8 @@ fn test_cleanup_non_expired_sale() {
     trading_client.confirm_sale(&seller, &buyer, &asset_id, &100, &5000, &3600);
 
     // Try to cleanup before expiration (should fail)
-    trading_client.cleanup_expired_sale(&seller, &buyer, &asset_id);
+    let keeper = Address::generate(&env);
+    trading_client.cleanup_expired_sale(&keeper, &seller, &buyer, &asset_id);
 }
 
 #[test]
@@ -773,8 +1082,9 @@ fn test_allowance_security_scenario() {
     });
 
     // Anyone can clean up expired proposals
-    trading_client.cleanup_expired_sale(&seller, &buyer1, &asset_id);
-    trading_client.cleanup_expired_sale(&seller, &buyer2, &asset_id);
+    let keeper = Address::generate(&env);
+    trading_client.cleanup_expired_sale(&keeper, &seller, &buyer1, &asset_id);
+    trading_client.cleanup_expired_sale(&keeper, &seller, &buyer2, &asset_id);
 
     // But allowance remains! (This is the security issue we're documenting)
     assert_eq!(
@@ -788,3 +1098,1093 @@ fn test_allowance_security_scenario() {
     // Now it's secure
     assert_eq!(trading_client.get_current_allowance(&seller, &asset_id), 0);
 }
+
+#[test]
+fn test_buyer_eligibility_checked_at_settlement() {
+    let (env, _admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let issuer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let co_owner = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &co_owner, 10000);
+    mint_xlm_for_user(&env, &xlm_contract_id, &outsider, 10000);
+
+    let asset_id = fnft_client.mint_for(&issuer, &issuer, &1000);
+    fnft_client.transfer(&issuer, &seller, &asset_id, &200);
+    fnft_client.transfer(&issuer, &co_owner, &asset_id, &50);
+
+    assert_eq!(
+        trading_client.try_set_buyer_eligibility(
+            &seller,
+            &asset_id,
+            &Some(BuyerEligibility {
+                min_holding: 0,
+                allowlist_only: false,
+            }),
+        ),
+        failed(TradingError::InvalidAmount)
+    );
+    let criteria = BuyerEligibility {
+        min_holding: 50,
+        allowlist_only: true,
+    };
+    trading_client.set_buyer_eligibility(&seller, &asset_id, &Some(criteria.clone()));
+    assert_eq!(
+        trading_client.get_buyer_eligibility(&seller, &asset_id),
+        Some(criteria)
+    );
+
+    trading_client.confirm_sale(
+        &seller,
+        &outsider,
+        &asset_id,
+        &50,
+        &2500,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.confirm_sale(
+        &seller,
+        &co_owner,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+
+    // Outsiders hold nothing; the co-owner holds enough but is not allowlisted
+    assert_eq!(
+        trading_client.try_finish_transaction(&outsider, &seller, &asset_id, &50, &2500),
+        failed(TradingError::BuyerNotEligible)
+    );
+    assert_eq!(
+        trading_client.try_finish_transaction(&co_owner, &seller, &asset_id, &100, &5000),
+        failed(TradingError::BuyerNotEligible)
+    );
+
+    // Only the asset manager maintains the allowlist
+    assert_eq!(
+        trading_client.try_set_asset_allowlisted(&seller, &asset_id, &co_owner, &true),
+        failed(TradingError::Unauthorized)
+    );
+    trading_client.set_asset_allowlisted(&issuer, &asset_id, &co_owner, &true);
+    trading_client.set_asset_allowlisted(&issuer, &asset_id, &outsider, &true);
+    assert!(trading_client.is_eligible_buyer(&seller, &co_owner, &asset_id));
+    assert!(!trading_client.is_eligible_buyer(&seller, &outsider, &asset_id));
+
+    trading_client.finish_transaction(&co_owner, &seller, &asset_id, &100, &5000);
+    assert_eq!(fnft_client.balance_of(&co_owner, &asset_id), 150);
+
+    // Other sellers are unaffected
+    assert!(trading_client.is_eligible_buyer(&issuer, &outsider, &asset_id));
+    trading_client.set_buyer_eligibility(&seller, &asset_id, &None);
+    assert!(trading_client.is_eligible_buyer(&seller, &outsider, &asset_id));
+}
+
+#[test]
+fn test_screening_blocks_sanctioned_parties() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let sanctioned = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    let screening_id = env.register(mock_screening::MockScreening, ());
+    let screening = mock_screening::MockScreeningClient::new(&env, &screening_id);
+    trading_client.set_screening_contract(&admin, &Some(screening_id.clone()));
+    assert_eq!(trading_client.get_screening_contract(), Some(screening_id));
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    screening.block(&sanctioned);
+
+    // Listings to a blocked buyer are refused
+    assert_eq!(
+        trading_client.try_confirm_sale(
+            &seller,
+            &sanctioned,
+            &asset_id,
+            &100,
+            &5000,
+            &DEFAULT_SALE_DURATION
+        ),
+        failed(TradingError::AddressBlocked)
+    );
+
+    // A buyer blocked after listing cannot settle either
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    screening.block(&buyer);
+    assert_eq!(
+        trading_client.try_finish_transaction(&buyer, &seller, &asset_id, &100, &5000),
+        failed(TradingError::AddressBlocked)
+    );
+
+    // Clearing the screening contract lifts the check
+    trading_client.set_screening_contract(&admin, &None);
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    assert_eq!(fnft_client.balance_of(&buyer, &asset_id), 100);
+}
+
+#[test]
+fn test_fractcore_pause_fails_trades_before_settlement() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+    let asset_id = fnft_client.mint(&seller, &1000);
+
+    let retry_after = env.ledger().timestamp() + 3600;
+    fnft_client.pause(&admin, &retry_after);
+    assert_eq!(
+        trading_client.try_confirm_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &100,
+            &5000,
+            &DEFAULT_SALE_DURATION
+        ),
+        failed(TradingError::UpstreamPaused)
+    );
+    assert!(!trading_client.is_asset_paused(&asset_id));
+    assert_eq!(
+        trading_client.get_upstream_pause(),
+        Some(UpstreamPause {
+            since: env.ledger().timestamp(),
+            retry_after,
+        })
+    );
+
+    fnft_client.unpause(&admin);
+    assert_eq!(trading_client.get_upstream_pause(), None);
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    assert_eq!(fnft_client.balance_of(&buyer, &asset_id), 100);
+}
+
+#[test]
+fn test_admin_log_records_admin_and_governor_actions() {
+    let (env, admin, fnft_contract_id, _xlm_contract_id, trading_client, _fnft_client, _xlm_client) =
+        setup();
+    let governor = register_governance(&env, &admin, &fnft_contract_id);
+
+    assert_eq!(
+        trading_client.try_set_keeper_bounty(&governor, &50),
+        failed(TradingError::Unauthorized)
+    );
+    trading_client.set_keeper_bounty(&admin, &50);
+    trading_client.set_governor(&admin, &Some(governor.clone()));
+    let config = TradingConfig {
+        fee: TradingFee {
+            bps: 100,
+            recipient: Address::generate(&env),
+        },
+        duration_limits: trading_client.get_sale_duration_limits(),
+    };
+    trading_client.apply_trading_config(&governor, &config);
+
+    let log = trading_client.get_admin_log(&0);
+    assert_eq!(log.len(), 3);
+    assert_eq!(trading_client.get_admin_log_head().entries, 3);
+
+    let bounty = log.get(0).unwrap();
+    assert_eq!(bounty.actor, admin);
+    assert_eq!(bounty.function, Symbol::new(&env, "set_keeper_bounty"));
+    let args_hash: BytesN<32> = env.crypto().sha256(&(50i128,).to_xdr(&env)).into();
+    assert_eq!(bounty.args_hash, args_hash);
+
+    let applied = log.get(2).unwrap();
+    assert_eq!(applied.actor, governor);
+    assert_eq!(applied.function, Symbol::new(&env, "apply_trading_config"));
+}
+
+#[test]
+fn test_invariant_violation_pauses_asset() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+    let asset_id = fnft_client.mint(&seller, &1000);
+
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+
+    // Books claim a keeper pool the contract does not hold
+    env.as_contract(&trading_client.address, || {
+        storage::set(&env, &DataKey::KeeperPool, &50i128);
+    });
+
+    // The trade itself settles, then the check trips and pauses the asset
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    let alert: Vec<Val> = (symbol_short!("inv_alert"), asset_id).into_val(&env);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == trading_client.address && topics == alert));
+    assert_eq!(fnft_client.balance_of(&buyer, &asset_id), 100);
+    assert!(trading_client.is_asset_paused(&asset_id));
+
+    assert_eq!(
+        trading_client.try_confirm_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &100,
+            &5000,
+            &DEFAULT_SALE_DURATION
+        ),
+        failed(TradingError::AssetPaused)
+    );
+
+    // Admin corrects the books and resumes trading
+    env.as_contract(&trading_client.address, || {
+        storage::remove(&env, &DataKey::KeeperPool);
+    });
+    trading_client.set_asset_paused(&admin, &asset_id, &false);
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    assert!(!trading_client.is_asset_paused(&asset_id));
+}
+
+fn account_event_seq(env: &Env, trading: &Address, account: &Address, kind: Symbol) -> Option<u64> {
+    let topics: Vec<Val> = (symbol_short!("acct"), account.clone(), kind).into_val(env);
+    env.events()
+        .all()
+        .iter()
+        .find(|(contract, t, _)| contract == trading && *t == topics)
+        .map(|(_, _, data)| {
+            let (seq, _, _, _, _, _): (u64, Address, Address, u64, u64, u128) = data.into_val(env);
+            seq
+        })
+}
+
+#[test]
+fn test_account_events_carry_per_account_sequence() {
+    let (env, _admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let other_buyer = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    let trading = trading_client.address.clone();
+
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    assert_eq!(
+        account_event_seq(&env, &trading, &seller, symbol_short!("created")),
+        Some(1)
+    );
+    assert_eq!(
+        account_event_seq(&env, &trading, &buyer, symbol_short!("created")),
+        Some(1)
+    );
+
+    trading_client.withdraw_sale(&seller, &buyer, &asset_id);
+    assert_eq!(
+        account_event_seq(&env, &trading, &seller, symbol_short!("withdrawn")),
+        Some(2)
+    );
+
+    // A sale to someone else advances only the seller's sequence
+    trading_client.confirm_sale(
+        &seller,
+        &other_buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    assert_eq!(
+        account_event_seq(&env, &trading, &other_buyer, symbol_short!("created")),
+        Some(1)
+    );
+
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    assert_eq!(
+        account_event_seq(&env, &trading, &seller, symbol_short!("filled")),
+        Some(5)
+    );
+    assert_eq!(
+        account_event_seq(&env, &trading, &buyer, symbol_short!("filled")),
+        Some(4)
+    );
+    assert_eq!(trading_client.get_account_event_seq(&seller), 5);
+    assert_eq!(trading_client.get_account_event_seq(&buyer), 4);
+}
+
+#[test]
+fn test_trade_records_carry_settlement_and_migrate_legacy_history() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+    let trade_id = trading_client.get_trade_count();
+    assert_eq!(trading_client.get_trade_record_version(), 2);
+
+    let record = trading_client.get_trade_record(&trade_id);
+    assert_eq!(record.settlement.gross, 5000);
+    assert_eq!(record.settlement.fees + record.settlement.royalties, 0);
+    assert_eq!(record.settlement.net_to_seller, 5000);
+    assert_eq!(record.settlement.payment_token, xlm_contract_id);
+    assert_eq!(record.referrer, None);
+    assert_eq!(trading_client.get_trade_history(&trade_id).price, 5000);
+
+    // A contract upgraded from version 1 still holds legacy history entries
+    let legacy_id = trade_id + 1;
+    env.as_contract(&trading_client.address, || {
+        storage::remove(&env, &DataKey::TradeRecordVersion);
+        storage::set(&env, &DataKey::TradeCounter, &legacy_id);
+        storage::set(
+            &env,
+            &DataKey::TradeHistory(legacy_id),
+            &TradeHistory {
+                seller: seller.clone(),
+                buyer: buyer.clone(),
+                asset_id,
+                token_amount: 10,
+                price: 700,
+                timestamp: 42,
+            },
+        );
+    });
+    assert_eq!(trading_client.get_trade_record_version(), 1);
+    let upgraded = trading_client.get_trade_record(&legacy_id);
+    assert_eq!(upgraded.timestamp, 42);
+    assert_eq!(upgraded.settlement.net_to_seller, 700);
+
+    assert_eq!(
+        trading_client.try_migrate_trade_records(&seller, &10),
+        failed(TradingError::Unauthorized)
+    );
+    assert!(!trading_client.migrate_trade_records(&admin, &1));
+    assert!(trading_client.migrate_trade_records(&admin, &1));
+    assert_eq!(trading_client.get_trade_record_version(), 2);
+    env.as_contract(&trading_client.address, || {
+        assert!(!storage::has(&env, &DataKey::TradeHistory(legacy_id)));
+        assert!(storage::has(&env, &DataKey::TradeRecord(legacy_id)));
+    });
+    assert_eq!(trading_client.get_trade_history(&legacy_id).price, 700);
+}
+
+#[test]
+fn test_asset_market_stats_over_window() {
+    let (env, _admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    let stats = trading_client.get_asset_market_stats(&asset_id, &0);
+    assert_eq!(stats.last_unit_price, None);
+    assert_eq!(stats.trade_count, 0);
+
+    let first_at = env.ledger().timestamp();
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 10 * 24 * 60 * 60);
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &50,
+        &3000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &50, &3000);
+
+    let stats = trading_client.get_asset_market_stats(&asset_id, &first_at);
+    assert_eq!(stats.last_unit_price, Some(60));
+    assert_eq!(stats.last_trade_at, Some(env.ledger().timestamp()));
+    assert_eq!(stats.volume, 8000);
+    assert_eq!(stats.trade_count, 2);
+
+    // Older trades fall outside the window but the last price is kept
+    let stats = trading_client.get_asset_market_stats(&asset_id, &(first_at + 1));
+    assert_eq!(stats.volume, 3000);
+    assert_eq!(stats.trade_count, 1);
+    let stats = trading_client.get_asset_market_stats(&asset_id, &u64::MAX);
+    assert_eq!(stats.last_unit_price, Some(60));
+    assert_eq!(stats.trade_count, 0);
+}
+
+#[test]
+fn test_sale_duration_limits() {
+    let (env, admin, _fnft_contract_id, _xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    let other_asset_id = fnft_client.mint(&seller, &1000);
+
+    // Defaults match the old constants
+    let defaults = SaleDurationLimits {
+        min_seconds: 3600,
+        max_seconds: DEFAULT_SALE_DURATION,
+    };
+    assert_eq!(trading_client.get_sale_duration_limits(), defaults);
+    assert_eq!(
+        trading_client.get_asset_sale_duration_limits(&asset_id),
+        defaults
+    );
+
+    // Only the admin may change them, and only within the hard bounds
+    let limits = SaleDurationLimits {
+        min_seconds: 600,
+        max_seconds: 2592000,
+    };
+    assert_eq!(
+        trading_client.try_set_sale_duration_limits(&seller, &limits),
+        failed(TradingError::Unauthorized)
+    );
+    for invalid in [
+        SaleDurationLimits {
+            min_seconds: 60,
+            max_seconds: 3600,
+        },
+        SaleDurationLimits {
+            min_seconds: 3600,
+            max_seconds: 7776001,
+        },
+        SaleDurationLimits {
+            min_seconds: 7200,
+            max_seconds: 3600,
+        },
+    ] {
+        assert_eq!(
+            trading_client.try_set_sale_duration_limits(&admin, &invalid),
+            failed(TradingError::InvalidDuration)
+        );
+    }
+
+    trading_client.set_sale_duration_limits(&admin, &limits);
+    assert_eq!(trading_client.get_sale_duration_limits(), limits);
+
+    // A two-week sale is now allowed, a five-minute one is not
+    trading_client.confirm_sale(&seller, &buyer, &asset_id, &100, &5000, &1209600);
+    assert_eq!(
+        trading_client.try_confirm_sale(&seller, &buyer, &other_asset_id, &100, &5000, &300),
+        failed(TradingError::InvalidDuration)
+    );
+
+    // A per-asset override applies to that asset only
+    let short_only = SaleDurationLimits {
+        min_seconds: 300,
+        max_seconds: 900,
+    };
+    trading_client.set_asset_sale_duration_limits(
+        &admin,
+        &other_asset_id,
+        &Some(short_only.clone()),
+    );
+    assert_eq!(
+        trading_client.get_asset_sale_duration_limits(&other_asset_id),
+        short_only
+    );
+    assert_eq!(
+        trading_client.try_confirm_sale(&seller, &buyer, &other_asset_id, &100, &5000, &3600),
+        failed(TradingError::InvalidDuration)
+    );
+    trading_client.confirm_sale(&seller, &buyer, &other_asset_id, &100, &5000, &300);
+
+    trading_client.set_asset_sale_duration_limits(&admin, &other_asset_id, &None);
+    assert_eq!(
+        trading_client.get_asset_sale_duration_limits(&other_asset_id),
+        limits
+    );
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    let current_ledger = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: current_ledger.timestamp + seconds,
+        ..current_ledger
+    });
+}
+
+#[test]
+fn test_reputation_tracks_trades_cancellations_and_expiries() {
+    let (env, _admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    let fresh = trading_client.get_reputation(&seller);
+    assert_eq!(fresh.completed_trades, 0);
+    assert_eq!(fresh.volume, 0);
+
+    // Settled trade counts for both sides
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &5000);
+
+    // Withdrawn proposals count against the seller only
+    for _ in 0..2 {
+        trading_client.confirm_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &100,
+            &3000,
+            &DEFAULT_SALE_DURATION,
+        );
+        trading_client.withdraw_sale(&seller, &buyer, &asset_id);
+    }
+
+    // An unfilled proposal cleaned up after expiry counts against the seller
+    trading_client.confirm_sale(&seller, &buyer, &asset_id, &100, &3000, &3600);
+    advance_time(&env, 3601);
+    trading_client.cleanup_expired_sale(&keeper, &seller, &buyer, &asset_id);
+
+    let seller_reputation = trading_client.get_reputation(&seller);
+    assert_eq!(seller_reputation.completed_trades, 1);
+    assert_eq!(seller_reputation.volume, 5000);
+    assert_eq!(seller_reputation.cancelled_proposals, 2);
+    assert_eq!(seller_reputation.expired_unsold, 1);
+    assert_eq!(seller_reputation.disputes, 0);
+
+    let buyer_reputation = trading_client.get_reputation(&buyer);
+    assert_eq!(buyer_reputation.completed_trades, 1);
+    assert_eq!(buyer_reputation.volume, 5000);
+    assert_eq!(buyer_reputation.cancelled_proposals, 0);
+    assert_eq!(buyer_reputation.expired_unsold, 0);
+
+    assert_eq!(trading_client.get_reputation(&keeper).completed_trades, 0);
+}
+
+#[test]
+fn test_treasury_proceeds_routed_to_funding() {
+    let (env, admin, fnft_contract_id, xlm_contract_id, trading_client, fnft_client, xlm_client) =
+        setup();
+    let treasury = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let funding_id = env.register(funding::WASM, (admin.clone(), fnft_contract_id.clone()));
+    let funding_client = funding::Client::new(&env, &funding_id);
+
+    let asset_id = fnft_client.mint(&treasury, &1000);
+    fnft_client.transfer(&treasury, &holder, &asset_id, &100);
+    funding_client.register_asset_sac(&treasury, &asset_id, &xlm_contract_id);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    trading_client.set_funding_contract(&admin, &Some(funding_id.clone()));
+    trading_client.set_revenue_routing(&admin, &treasury, &true);
+    assert!(trading_client.is_revenue_routed(&treasury));
+    assert!(!trading_client.is_revenue_routed(&holder));
+
+    trading_client.confirm_sale(
+        &treasury,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &treasury, &asset_id, &100, &5000);
+    let revenue: Vec<Val> = (symbol_short!("revenue"), treasury.clone(), asset_id).into_val(&env);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == trading_client.address && topics == revenue));
+
+    // Proceeds land in the asset's SAC as revenue, not in the treasury wallet
+    assert_eq!(xlm_client.balance(&treasury), 0);
+    assert_eq!(xlm_client.balance(&xlm_contract_id), 5000);
+    assert_eq!(funding_client.asset_funds(&asset_id), 5000);
+    assert_eq!(xlm_client.balance(&trading_client.address), 0);
+
+    // Other sellers are still paid directly
+    trading_client.confirm_sale(
+        &holder,
+        &buyer,
+        &asset_id,
+        &50,
+        &2000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &holder, &asset_id, &50, &2000);
+    assert_eq!(xlm_client.balance(&holder), 2000);
+
+    // Released treasuries are paid directly again
+    trading_client.set_revenue_routing(&admin, &treasury, &false);
+    trading_client.confirm_sale(
+        &treasury,
+        &buyer,
+        &asset_id,
+        &100,
+        &3000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &treasury, &asset_id, &100, &3000);
+    assert_eq!(xlm_client.balance(&treasury), 3000);
+    assert_eq!(funding_client.asset_funds(&asset_id), 5000);
+}
+
+#[test]
+fn test_routed_sale_requires_asset_revenue_pool() {
+    let (env, admin, fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let treasury = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    // Routing needs a funding contract to deposit into
+    assert_eq!(
+        trading_client.try_set_revenue_routing(&admin, &treasury, &true),
+        failed(TradingError::NotInitialized)
+    );
+
+    let funding_id = env.register(funding::WASM, (admin.clone(), fnft_contract_id.clone()));
+    trading_client.set_funding_contract(&admin, &Some(funding_id));
+    trading_client.set_revenue_routing(&admin, &treasury, &true);
+
+    // No SAC registered for the asset in funding: the sale cannot settle
+    let asset_id = fnft_client.mint(&treasury, &1000);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+    trading_client.confirm_sale(
+        &treasury,
+        &buyer,
+        &asset_id,
+        &100,
+        &5000,
+        &DEFAULT_SALE_DURATION,
+    );
+    assert_eq!(
+        trading_client.try_finish_transaction(&buyer, &treasury, &asset_id, &100, &5000),
+        failed(TradingError::AssetNotFound)
+    );
+}
+
+#[test]
+fn test_cursors_walk_trades_while_they_change() {
+    let (env, _admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let asset_id = fnft_client.mint(&seller, &1000);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 100000);
+    for _ in 0..3 {
+        trading_client.confirm_sale(
+            &seller,
+            &buyer,
+            &asset_id,
+            &10,
+            &100,
+            &DEFAULT_SALE_DURATION,
+        );
+        trading_client.finish_transaction(&buyer, &seller, &asset_id, &10, &100);
+    }
+    let trades = trading_client.get_asset_trades(&asset_id);
+    let (first, cursor) = trading_client.get_asset_trades_page(&asset_id, &None, &2);
+    assert_eq!(first, trades.slice(0..2));
+
+    // A trade settled mid-walk is left for the next walk
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &10,
+        &100,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &10, &100);
+    let (rest, cursor) = trading_client.get_asset_trades_page(&asset_id, &cursor, &2);
+    assert_eq!(rest, trades.slice(2..3));
+    assert_eq!(cursor, None);
+
+    // Both parties see every trade they settled, newest walk included
+    let (seller_trades, cursor) = trading_client.get_user_trades_page(&seller, &None, &3);
+    assert_eq!(seller_trades.len(), 3);
+    assert_eq!(seller_trades.get(0).unwrap().0, trades.get(0).unwrap());
+    let (rest, cursor) = trading_client.get_user_trades_page(&seller, &cursor, &3);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(cursor, None);
+    let (buyer_trades, _) = trading_client.get_user_trades_page(&buyer, &None, &0);
+    assert_eq!(buyer_trades.len(), 4);
+    let (_, last) = buyer_trades.get(3).unwrap();
+    assert_eq!((last.seller, last.buyer, last.price), (seller, buyer, 100));
+}
+
+#[test]
+fn test_governor_sets_fee_withheld_from_proceeds() {
+    let (env, admin, fnft_contract_id, xlm_contract_id, trading_client, fnft_client, xlm_client) =
+        setup();
+    let governor = register_governance(&env, &admin, &fnft_contract_id);
+    let treasury = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 10000);
+
+    let limits = SaleDurationLimits {
+        min_seconds: 1800,
+        max_seconds: 1209600,
+    };
+    let config = TradingConfig {
+        fee: TradingFee {
+            bps: 250,
+            recipient: treasury.clone(),
+        },
+        duration_limits: limits.clone(),
+    };
+
+    // Only the governor applies a config, and only once it is set
+    assert_eq!(
+        trading_client.try_apply_trading_config(&governor, &config),
+        failed(TradingError::Unauthorized)
+    );
+
+    // Accounts, governance contracts of another fractcore and no governor at
+    // all are refused
+    let other_fractcore = env.register(fnft::WASM, (admin.clone(),));
+    for target in [
+        Some(Address::generate(&env)),
+        Some(register_governance(&env, &admin, &other_fractcore)),
+        None,
+    ] {
+        assert_eq!(
+            trading_client.try_set_governor(&admin, &target),
+            failed(TradingError::NotGovernanceContract)
+        );
+    }
+    assert_eq!(trading_client.get_governor(), None);
+
+    trading_client.set_governor(&admin, &Some(governor.clone()));
+    assert_eq!(trading_client.get_governor(), Some(governor.clone()));
+    assert_eq!(
+        trading_client.try_apply_trading_config(&admin, &config),
+        failed(TradingError::Unauthorized)
+    );
+
+    let mut steep = config.clone();
+    steep.fee.bps = 1001;
+    assert_eq!(
+        trading_client.try_apply_trading_config(&governor, &steep),
+        failed(TradingError::InvalidAmount)
+    );
+
+    trading_client.apply_trading_config(&governor, &config);
+    assert_eq!(trading_client.get_trading_fee(), Some(config.fee.clone()));
+    assert_eq!(trading_client.get_sale_duration_limits(), limits);
+
+    // The admin no longer holds the platform parameters
+    assert_eq!(
+        trading_client.try_set_sale_duration_limits(&admin, &limits),
+        failed(TradingError::Unauthorized)
+    );
+    assert_eq!(
+        trading_client.try_set_governor(&admin, &None),
+        failed(TradingError::Unauthorized)
+    );
+
+    trading_client.confirm_sale(
+        &seller,
+        &buyer,
+        &asset_id,
+        &100,
+        &4000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &seller, &asset_id, &100, &4000);
+
+    assert_eq!(xlm_client.balance(&treasury), 100);
+    assert_eq!(xlm_client.balance(&seller), 3900);
+    let settlement = trading_client.get_trade_record(&1).settlement;
+    assert_eq!(settlement.fees, 100);
+    assert_eq!(settlement.net_to_seller, 3900);
+
+    // A zero fee withholds nothing and clears the stored fee
+    let mut free = config.clone();
+    free.fee.bps = 0;
+    trading_client.apply_trading_config(&governor, &free);
+    assert_eq!(trading_client.get_trading_fee(), None);
+
+    // The governor cannot hand the parameters back to the admin
+    assert_eq!(
+        trading_client.try_set_governor(&governor, &None),
+        failed(TradingError::NotGovernanceContract)
+    );
+    assert_eq!(trading_client.get_governor(), Some(governor));
+}
+
+#[test]
+fn test_exit_queue_paces_large_sellers() {
+    let (env, admin, fnft_contract_id, xlm_contract_id, trading_client, fnft_client, _xlm_client) =
+        setup();
+    let governor = register_governance(&env, &admin, &fnft_contract_id);
+    let whale = Address::generate(&env);
+    let later = Address::generate(&env);
+    let minnow = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let asset_id = fnft_client.mint(&whale, &1000);
+    fnft_client.transfer(&whale, &later, &asset_id, &300);
+    fnft_client.transfer(&whale, &minnow, &asset_id, &50);
+    mint_xlm_for_user(&env, &xlm_contract_id, &buyer, 100_000);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 86400);
+
+    let config = ExitQueueConfig {
+        size_threshold: 100,
+        tranche: 100,
+        window_seconds: 86400,
+    };
+    assert_eq!(
+        trading_client.try_set_exit_queue(&governor, &asset_id, &Some(config.clone())),
+        failed(TradingError::Unauthorized)
+    );
+    assert_eq!(
+        trading_client.try_enroll_exit(&whale, &asset_id, &150),
+        failed(TradingError::ExitQueueNotConfigured)
+    );
+    trading_client.set_governor(&admin, &Some(governor.clone()));
+    trading_client.set_exit_queue(&governor, &asset_id, &Some(config));
+
+    // Holders under the threshold sell freely; larger ones must queue
+    trading_client.confirm_sale(
+        &minnow,
+        &buyer,
+        &asset_id,
+        &50,
+        &500,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &minnow, &asset_id, &50, &500);
+    trading_client.confirm_sale(&whale, &buyer, &asset_id, &80, &800, &DEFAULT_SALE_DURATION);
+    assert_eq!(
+        trading_client.try_finish_transaction(&buyer, &whale, &asset_id, &80, &800),
+        failed(TradingError::ExceedsReleasedTranche)
+    );
+
+    // The first seller in line receives the current window's tranche at once
+    assert_eq!(trading_client.enroll_exit(&whale, &asset_id, &150), 0);
+    assert_eq!(trading_client.enroll_exit(&later, &asset_id, &200), 1);
+    let position = trading_client
+        .get_exit_queue_position(&asset_id, &later)
+        .unwrap();
+    assert_eq!((position.sellable, position.ahead), (0, 50));
+    assert_eq!(position.next_release_at, 11 * 86400);
+
+    trading_client.finish_transaction(&buyer, &whale, &asset_id, &80, &800);
+    trading_client.confirm_sale(&whale, &buyer, &asset_id, &30, &300, &DEFAULT_SALE_DURATION);
+    assert_eq!(
+        trading_client.try_finish_transaction(&buyer, &whale, &asset_id, &30, &300),
+        failed(TradingError::ExceedsReleasedTranche)
+    );
+
+    // The next window finishes the first enrollment and starts on the second
+    env.ledger().with_mut(|li| li.timestamp = 11 * 86400);
+    let whale_position = trading_client
+        .get_exit_queue_position(&asset_id, &whale)
+        .unwrap();
+    assert_eq!(whale_position.sellable, 70);
+    assert_eq!(
+        trading_client
+            .get_exit_queue_position(&asset_id, &later)
+            .unwrap()
+            .sellable,
+        50
+    );
+    trading_client.finish_transaction(&buyer, &whale, &asset_id, &30, &300);
+    assert_eq!(trading_client.get_exit_queue(&asset_id).len(), 2);
+
+    trading_client.leave_exit_queue(&later, &asset_id);
+    assert_eq!(
+        trading_client.get_exit_queue_position(&asset_id, &later),
+        None
+    );
+
+    // Clearing the config lifts the queue
+    trading_client.set_exit_queue(&governor, &asset_id, &None);
+    assert!(trading_client.get_exit_queue(&asset_id).is_empty());
+    trading_client.confirm_sale(
+        &later,
+        &buyer,
+        &asset_id,
+        &300,
+        &3000,
+        &DEFAULT_SALE_DURATION,
+    );
+    trading_client.finish_transaction(&buyer, &later, &asset_id, &300, &3000);
+}
+
+#[test]
+fn test_migrate_storage_moves_bare_entries_once() {
+    let (env, admin, fnft_contract_id, _xlm_contract_id, trading_client, _fnft_client, _xlm_client) =
+        setup();
+    let governor = register_governance(&env, &admin, &fnft_contract_id);
+    assert_eq!(
+        trading_client.try_migrate_storage(&admin, &Vec::new(&env), &Vec::new(&env), &true),
+        failed(TradingError::AlreadyInitialized)
+    );
+
+    // A deployment upgraded from before namespacing holds every entry under its
+    // bare key, the admin's included, and reads miss until the admin moves them
+    trading_client.set_governor(&admin, &Some(governor.clone()));
+    let logged = trading_client.get_admin_log_head().entries;
+    let bare = strip_namespaces(
+        &env,
+        &trading_client.address,
+        &[DataKey::NAMESPACE, DataKeyExt::NAMESPACE],
+    );
+    let keys = bare.get(DataKey::NAMESPACE).unwrap();
+    let ext_keys = bare.get(DataKeyExt::NAMESPACE).unwrap();
+    assert!(keys.contains(IntoVal::<Env, Val>::into_val(&DataKey::Admin, &env)));
+    assert_eq!(trading_client.get_governor(), None);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        trading_client.try_initialize(&stranger, &stranger, &stranger),
+        failed(TradingError::AlreadyInitialized)
+    );
+
+    assert_eq!(
+        trading_client.try_migrate_storage(&governor, &keys, &ext_keys, &true),
+        failed(TradingError::Unauthorized)
+    );
+    assert_eq!(
+        trading_client.migrate_storage(&admin, &keys, &ext_keys, &true),
+        keys.len() + ext_keys.len()
+    );
+    assert_eq!(trading_client.get_governor(), Some(governor));
+    assert_eq!(trading_client.get_admin_log_head().entries, logged + 1);
+    assert_eq!(
+        trading_client.try_migrate_storage(&admin, &keys, &ext_keys, &true),
+        failed(TradingError::AlreadyInitialized)
+    );
+}
+
+#[test]
+fn test_extension_pays_sellers_only_from_what_it_brings() {
+    let (env, admin, _fnft_contract_id, xlm_contract_id, trading_client, fnft_client, xlm_client) =
+        setup();
+    // The payer signs the extension's own call, above this one
+    env.mock_all_auths_allowing_non_root_auth();
+    let extension = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let asset_id = fnft_client.mint(&seller, &1000);
+    trading_client.set_extension(&admin, &extension, &true);
+
+    // XLM sitting in trading is not an extension's to pay out
+    mint_xlm_for_user(&env, &xlm_contract_id, &trading_client.address, 5000);
+    assert_eq!(
+        trading_client.try_pay_seller(&extension, &payer, &seller, &asset_id, &1000),
+        failed(TradingError::BuyerInsufficientFunds)
+    );
+    assert_eq!(xlm_client.balance(&seller), 0);
+
+    // What the payer brings is paid on, less the fee
+    mint_xlm_for_user(&env, &xlm_contract_id, &payer, 1000);
+    trading_client.pay_seller(&extension, &payer, &seller, &asset_id, &1000);
+    assert_eq!(xlm_client.balance(&payer), 0);
+    let paid = xlm_client.balance(&seller);
+    assert!(paid > 0 && paid <= 1000);
+    assert!(xlm_client.balance(&trading_client.address) >= 5000);
+}
+
+#[test]
+fn test_unreadable_upstream_pause_fails_closed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    // A "fractcore" without `pause_state`
+    let fnft_contract_id = env.register(mock_screening::MockScreening, ());
+    let xlm_contract_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let trading_contract_id =
+        env.register(TradingContract, (admin, fnft_contract_id, xlm_contract_id));
+    let trading_client = TradingContractClient::new(&env, &trading_contract_id);
+
+    assert_eq!(
+        trading_client.try_get_upstream_pause(),
+        failed(TradingError::UpstreamPaused)
+    );
+    assert_eq!(
+        trading_client.try_confirm_sale(
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &1,
+            &100,
+            &5000,
+            &DEFAULT_SALE_DURATION
+        ),
+        failed(TradingError::UpstreamPaused)
+    );
+}
@@ -0,0 +1,113 @@
+//! Admin action log shared by the contracts that keep one.
+//!
+//! Role-checked entrypoints append one entry per privileged call. Entries are
+//! stored in pages of `ADMIN_LOG_PAGE_SIZE`; once more than
+//! `MAX_ADMIN_LOG_PAGES` pages exist the oldest is folded into the head's hash
+//! and dropped, so storage stays bounded while the full history remains
+//! verifiable. Each contract keeps the log under its own key type and emits
+//! its own event for a new entry.
+#![no_std]
+
+use placement::{get, remove, set, PlacedKey};
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+/// Entries per page of the admin action log
+pub const ADMIN_LOG_PAGE_SIZE: u32 = 20;
+
+/// Pages of the admin action log kept in storage; older ones are folded into
+/// the head's hash
+pub const MAX_ADMIN_LOG_PAGES: u32 = 10;
+
+/// One privileged call, as recorded in the admin action log
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AdminLogEntry {
+    pub actor: Address,
+    pub function: Symbol,
+    pub args_hash: BytesN<32>, // sha256 of the XDR of the call's arguments
+    pub timestamp: u64,
+}
+
+/// Where the admin action log stands. Entries below `folded` are no longer
+/// stored; `folded_hash` chains them as sha256(folded_hash || entry XDR),
+/// starting from 32 zero bytes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AdminLogHead {
+    pub entries: u32, // Entries ever recorded
+    pub folded: u32,
+    pub folded_hash: BytesN<32>,
+}
+
+/// Key type a contract stores its admin action log under
+pub trait AdminLogKey: PlacedKey + Sized {
+    fn head() -> Self;
+    fn page(page: u32) -> Self;
+}
+
+/// Append a privileged call to the log; `args` is hashed, not stored. Returns
+/// the entry and its index for the contract's `admin_log` event.
+pub fn record<K: AdminLogKey>(
+    env: &Env,
+    actor: &Address,
+    function: &str,
+    args: impl ToXdr,
+) -> (u32, AdminLogEntry) {
+    append::<K>(env, actor, Symbol::new(env, function), &args.to_xdr(env))
+}
+
+// Shared by every call site, whatever the type of its arguments
+fn append<K: AdminLogKey>(
+    env: &Env,
+    actor: &Address,
+    function: Symbol,
+    args: &Bytes,
+) -> (u32, AdminLogEntry) {
+    let entry = AdminLogEntry {
+        actor: actor.clone(),
+        function,
+        args_hash: env.crypto().sha256(args).into(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    let mut head = get_admin_log_head::<K>(env);
+    let page = head.entries / ADMIN_LOG_PAGE_SIZE;
+    if head.entries.is_multiple_of(ADMIN_LOG_PAGE_SIZE) && page >= MAX_ADMIN_LOG_PAGES {
+        fold_page::<K>(env, &mut head, page - MAX_ADMIN_LOG_PAGES);
+    }
+
+    let mut entries = get_admin_log::<K>(env, page);
+    entries.push_back(entry.clone());
+    set(env, &K::page(page), &entries);
+
+    let index = head.entries;
+    head.entries += 1;
+    set(env, &K::head(), &head);
+
+    (index, entry)
+}
+
+/// One page of the log, oldest entry first, ADMIN_LOG_PAGE_SIZE per page;
+/// empty once the page has been folded
+pub fn get_admin_log<K: AdminLogKey>(env: &Env, page: u32) -> Vec<AdminLogEntry> {
+    get(env, &K::page(page)).unwrap_or(Vec::new(env))
+}
+
+pub fn get_admin_log_head<K: AdminLogKey>(env: &Env) -> AdminLogHead {
+    get(env, &K::head()).unwrap_or(AdminLogHead {
+        entries: 0,
+        folded: 0,
+        folded_hash: BytesN::from_array(env, &[0; 32]),
+    })
+}
+
+/// Chain the page's entries into the head's hash and drop the page
+fn fold_page<K: AdminLogKey>(env: &Env, head: &mut AdminLogHead, page: u32) {
+    for entry in get_admin_log::<K>(env, page).iter() {
+        let mut material = Bytes::from(head.folded_hash.clone());
+        material.append(&entry.to_xdr(env));
+        head.folded_hash = env.crypto().sha256(&material).into();
+        head.folded += 1;
+    }
+    remove(env, &K::page(page));
+}
@@ -0,0 +1,15 @@
+//! Cursor shared by the paginated views of every contract of the workspace,
+//! so clients walk any of them with one type.
+#![no_std]
+
+use soroban_sdk::contracttype;
+
+/// Position in a paginated view. Start with `None` and pass back each page's
+/// next cursor unchanged; `None` is returned once the walk is complete.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub page: u32,     // Storage page of the next item (0 for unpaged lists)
+    pub position: u32, // Index (or id) of the next item within that page
+    pub snapshot: u64, // Version of the list the walk started on
+}
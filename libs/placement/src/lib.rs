@@ -0,0 +1,273 @@
+//! Storage class routing shared by every contract of the workspace.
+//!
+//! Every key type declares the storage class of each of its variants in
+//! `PlacedKey::placement`, an exhaustive match, so a new key does not compile
+//! until it is classified. Contract code reads and writes only through the
+//! helpers below, which pick the class from the key; direct use of
+//! `Storage::{instance, persistent, temporary}` is denied by clippy.toml.
+//! The helpers convert keys and values at the edges and share one
+//! non-generic path per storage operation, so each contract carries a single
+//! copy of the host calls rather than one per key and value type.
+//!
+//! Every key type also declares a `NAMESPACE`, and entries are stored under
+//! `(namespace, key)`. Key types of one contract have distinct namespaces, so
+//! a variant added to one enum can never reuse an entry of another enum that
+//! happens to have a variant of the same name and fields. New deployments set
+//! the namespaced marker before their first write. A contract upgraded from a
+//! build without namespacing lacks the marker and still holds entries under
+//! bare keys; its admin moves them once with `migrate`, which keeps each entry
+//! in the class it was written to, then sets the marker. Lookups never probe
+//! bare keys, except `get_bare`, which lets the migration check its admin
+//! before the admin's own entry has moved.
+//!
+//! - Instance: bounded, contract-wide configuration and counters. Loaded with
+//!   every invocation, so nothing here may grow with usage.
+//! - Persistent: data keyed per asset, account or record. A Vec stored under a
+//!   single key has a documented cap or is split into pages.
+//! - Temporary: data whose loss is harmless once it has expired, kept alive
+//!   with `bump` for as long as it matters.
+#![no_std]
+#![allow(clippy::disallowed_methods)]
+
+use soroban_sdk::{symbol_short, unwrap::UnwrapOptimized, Env, IntoVal, Symbol, TryFromVal, Val};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Placement {
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+/// A storage key that knows which storage class it belongs in
+pub trait PlacedKey: IntoVal<Env, Val> {
+    /// Prefix of every entry of this key type, unique among the contract's key types
+    const NAMESPACE: Symbol;
+
+    fn placement(&self) -> Placement;
+}
+
+/// Key an entry is stored under: the key prefixed with its type's namespace
+pub fn stored_key<K: PlacedKey>(env: &Env, key: &K) -> Val {
+    namespaced(env, K::NAMESPACE, key.into_val(env))
+}
+
+fn namespaced(env: &Env, namespace: Symbol, key: Val) -> Val {
+    (namespace, key).into_val(env)
+}
+
+/// Instance entry of contracts whose entries all sit under their namespace
+pub const NAMESPACED: Symbol = symbol_short!("ns");
+
+/// Mark every entry as namespaced: called by new deployments before their
+/// first write, and by upgraded ones once `migrate` has moved their entries
+pub fn mark_namespaced(env: &Env) {
+    write(env, NAMESPACED.to_val(), true.into(), Placement::Instance);
+}
+
+/// Whether the contract was deployed namespaced or has finished migrating
+pub fn is_namespaced(env: &Env) -> bool {
+    contains(env, NAMESPACED.to_val(), Placement::Instance)
+}
+
+/// Most keys one `migrate_storage` call accepts; larger migrations take
+/// several calls
+pub const MAX_MIGRATE_BATCH: u32 = 50;
+
+/// Move an entry written before namespacing from its bare key under
+/// `namespace`, in the storage class it was written to; returns whether there
+/// was one to move
+pub fn migrate(env: &Env, namespace: Symbol, key: Val) -> bool {
+    match find_bare(env, key) {
+        Some((value, placement)) => {
+            write(env, namespaced(env, namespace, key), value, placement);
+            delete(env, key, placement);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Read an entry still under its bare key, written before namespacing and not
+/// yet moved by `migrate`
+pub fn get_bare<K: PlacedKey, V: TryFromVal<Env, Val>>(env: &Env, key: &K) -> Option<V> {
+    find_bare(env, key.into_val(env))
+        .map(|(value, _)| V::try_from_val(env, &value).unwrap_optimized())
+}
+
+// A bare entry and the class it was written to, whichever that was
+fn find_bare(env: &Env, key: Val) -> Option<(Val, Placement)> {
+    [
+        Placement::Persistent,
+        Placement::Instance,
+        Placement::Temporary,
+    ]
+    .into_iter()
+    .find_map(|placement| read(env, key, placement).map(|value| (value, placement)))
+}
+
+pub fn get<K: PlacedKey, V: TryFromVal<Env, Val>>(env: &Env, key: &K) -> Option<V> {
+    get_val(env, key).map(|value| V::try_from_val(env, &value).unwrap_optimized())
+}
+
+pub fn set<K: PlacedKey, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+    set_val(env, key, value.into_val(env));
+}
+
+// The key half of `get` and `set`, instantiated once per key type rather than
+// once per key and value type
+fn get_val<K: PlacedKey>(env: &Env, key: &K) -> Option<Val> {
+    read(env, stored_key(env, key), key.placement())
+}
+
+fn set_val<K: PlacedKey>(env: &Env, key: &K, value: Val) {
+    write(env, stored_key(env, key), value, key.placement());
+}
+
+pub fn has<K: PlacedKey>(env: &Env, key: &K) -> bool {
+    contains(env, stored_key(env, key), key.placement())
+}
+
+/// Whether `key` holds an entry, namespaced or still under its bare key.
+/// Initialization guards check the admin with it, so a deployment upgraded
+/// from before namespacing counts as initialized until and after it migrates.
+pub fn has_or_bare<K: PlacedKey>(env: &Env, key: &K) -> bool {
+    has(env, key) || find_bare(env, key.into_val(env)).is_some()
+}
+
+pub fn remove<K: PlacedKey>(env: &Env, key: &K) {
+    delete(env, stored_key(env, key), key.placement());
+}
+
+/// Extend the TTL of the contract instance and every instance key with it
+pub fn extend_instance(env: &Env, threshold: u32, extend_to: u32) {
+    env.storage().instance().extend_ttl(threshold, extend_to);
+}
+
+/// Extend an entry's TTL; instance keys extend the whole instance
+pub fn bump<K: PlacedKey>(env: &Env, key: &K, threshold: u32, extend_to: u32) {
+    extend(
+        env,
+        stored_key(env, key),
+        key.placement(),
+        threshold,
+        extend_to,
+    );
+}
+
+/// Read an entry from the class it was stored in before its key was moved;
+/// callers remove it once the value has been rewritten under `placement`
+pub fn get_moved<K: PlacedKey, V: TryFromVal<Env, Val>>(
+    env: &Env,
+    key: &K,
+    from: Placement,
+) -> Option<V> {
+    read(env, stored_key(env, key), from)
+        .map(|value| V::try_from_val(env, &value).unwrap_optimized())
+}
+
+/// Remove an entry left in the class its key was stored in before it moved
+pub fn remove_moved<K: PlacedKey>(env: &Env, key: &K, from: Placement) {
+    delete(env, stored_key(env, key), from);
+}
+
+// Class-routed access to a raw storage key
+
+fn read(env: &Env, key: Val, placement: Placement) -> Option<Val> {
+    match placement {
+        Placement::Instance => env.storage().instance().get(&key),
+        Placement::Persistent => env.storage().persistent().get(&key),
+        Placement::Temporary => env.storage().temporary().get(&key),
+    }
+}
+
+fn write(env: &Env, key: Val, value: Val, placement: Placement) {
+    match placement {
+        Placement::Instance => env.storage().instance().set(&key, &value),
+        Placement::Persistent => env.storage().persistent().set(&key, &value),
+        Placement::Temporary => env.storage().temporary().set(&key, &value),
+    }
+}
+
+fn contains(env: &Env, key: Val, placement: Placement) -> bool {
+    match placement {
+        Placement::Instance => env.storage().instance().has(&key),
+        Placement::Persistent => env.storage().persistent().has(&key),
+        Placement::Temporary => env.storage().temporary().has(&key),
+    }
+}
+
+fn delete(env: &Env, key: Val, placement: Placement) {
+    match placement {
+        Placement::Instance => env.storage().instance().remove(&key),
+        Placement::Persistent => env.storage().persistent().remove(&key),
+        Placement::Temporary => env.storage().temporary().remove(&key),
+    }
+}
+
+fn extend(env: &Env, key: Val, placement: Placement, threshold: u32, extend_to: u32) {
+    match placement {
+        Placement::Instance => env.storage().instance().extend_ttl(threshold, extend_to),
+        Placement::Persistent => env
+            .storage()
+            .persistent()
+            .extend_ttl(&key, threshold, extend_to),
+        Placement::Temporary => env
+            .storage()
+            .temporary()
+            .extend_ttl(&key, threshold, extend_to),
+    }
+}
+
+#[cfg(feature = "testutils")]
+pub mod testutils {
+    extern crate std;
+
+    use super::NAMESPACED;
+    use soroban_sdk::testutils::storage::{Instance, Persistent, Temporary};
+    use soroban_sdk::{Address, Env, Map, Symbol, TryFromVal, Val, Vec};
+
+    /// Turn a contract into a deployment from before namespacing: drop the
+    /// marker and move every entry under one of `namespaces` to its bare key,
+    /// in the class it sits in. Returns the bare keys of each namespace, as
+    /// `migrate` takes them.
+    pub fn strip_namespaces(
+        env: &Env,
+        contract: &Address,
+        namespaces: &[Symbol],
+    ) -> Map<Symbol, Vec<Val>> {
+        let mut bare: Map<Symbol, Vec<Val>> = Map::new(env);
+        for namespace in namespaces {
+            bare.set(namespace.clone(), Vec::new(env));
+        }
+        let mut strip = |key: &Val| {
+            let (namespace, key) = <(Symbol, Val)>::try_from_val(env, key).ok()?;
+            let mut keys = bare.get(namespace.clone())?;
+            keys.push_back(key);
+            bare.set(namespace, keys);
+            Some(key)
+        };
+        env.as_contract(contract, || {
+            let storage = env.storage();
+            storage.instance().remove(&NAMESPACED);
+            for (key, value) in storage.instance().all().iter() {
+                if let Some(bare_key) = strip(&key) {
+                    storage.instance().remove(&key);
+                    storage.instance().set(&bare_key, &value);
+                }
+            }
+            for (key, value) in storage.persistent().all().iter() {
+                if let Some(bare_key) = strip(&key) {
+                    storage.persistent().remove(&key);
+                    storage.persistent().set(&bare_key, &value);
+                }
+            }
+            for (key, value) in storage.temporary().all().iter() {
+                if let Some(bare_key) = strip(&key) {
+                    storage.temporary().remove(&key);
+                    storage.temporary().set(&bare_key, &value);
+                }
+            }
+        });
+        bare
+    }
+}
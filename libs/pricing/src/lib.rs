@@ -0,0 +1,71 @@
+//! Per-fraction price math shared by trading and its market extension.
+//!
+//! Quotes served to wallets and the totals the contracts compute go through
+//! the same functions, so the two always round the same way. Prices are
+//! fixed-point stroops with `decimals` decimal places (0 for whole stroops per
+//! fraction). Each contract maps `PriceError` onto its own error type, whose
+//! codes for these failures are shared.
+#![no_std]
+
+/// Decimal places accepted by the unit price helpers
+pub const MAX_PRICE_DECIMALS: u32 = 18;
+
+/// Why a price conversion failed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PriceError {
+    InvalidTokenAmount, // Zero fractions
+    InvalidPrice,       // More than MAX_PRICE_DECIMALS decimal places
+    PriceOverflow,
+}
+
+/// Total price of `amount` fractions at `price_per_token`, a fixed-point price
+/// with `decimals` decimal places.
+///
+/// Rounded up, so a seller never receives less than the quoted unit price.
+/// Fails with `InvalidPrice` when `decimals` exceeds `MAX_PRICE_DECIMALS` and
+/// with `PriceOverflow` when the total cannot be paid as an i128 amount.
+pub fn quote_total(price_per_token: u128, amount: u64, decimals: u32) -> Result<u128, PriceError> {
+    let scale = scale_of(decimals)?;
+
+    // Split the price so the multiplication cannot overflow before the division
+    let whole = (price_per_token / scale).checked_mul(amount as u128);
+    let fraction = (price_per_token % scale * amount as u128).div_ceil(scale);
+    match whole.and_then(|whole| whole.checked_add(fraction)) {
+        Some(total) if total <= i128::MAX as u128 => Ok(total),
+        _ => Err(PriceError::PriceOverflow),
+    }
+}
+
+/// Price per fraction, with `decimals` decimal places, of `amount` fractions
+/// sold for `total`.
+///
+/// Rounded down, so quoting the result for the same amount never exceeds
+/// `total`. Fails with `InvalidTokenAmount` for a zero amount, `InvalidPrice`
+/// when `decimals` exceeds `MAX_PRICE_DECIMALS` and `PriceOverflow` when the
+/// scaled price does not fit in a u128.
+pub fn price_per_token_from_total(
+    total: u128,
+    amount: u64,
+    decimals: u32,
+) -> Result<u128, PriceError> {
+    if amount == 0 {
+        return Err(PriceError::InvalidTokenAmount);
+    }
+    let scale = scale_of(decimals)?;
+
+    let amount = amount as u128;
+    let whole = (total / amount).checked_mul(scale);
+    let fraction = total % amount * scale / amount;
+    match whole.and_then(|whole| whole.checked_add(fraction)) {
+        Some(price) => Ok(price),
+        None => Err(PriceError::PriceOverflow),
+    }
+}
+
+// The remainders above stay below 2^64 * 10^18, so their products fit in a u128
+fn scale_of(decimals: u32) -> Result<u128, PriceError> {
+    if decimals > MAX_PRICE_DECIMALS {
+        return Err(PriceError::InvalidPrice);
+    }
+    Ok(10u128.pow(decimals))
+}
@@ -0,0 +1,53 @@
+//! Test helpers shared by the contracts' test suites, pulled in as a
+//! dev-dependency only. Golden event files pin the XDR of the events a
+//! canonical scenario emits, so a changed topic or payload layout shows up in
+//! review before indexers see it.
+
+use soroban_sdk::testutils::Events;
+use soroban_sdk::xdr::{Limits, ScVal, WriteXdr};
+use soroban_sdk::{Address, Env, TryFromVal};
+use std::fs;
+
+/// Append the events `contract` emitted in the last invocation to a golden
+/// scenario, one line each: first topic, topics XDR, data XDR (base64)
+pub fn record_events(env: &Env, contract: &Address, step: &str, out: &mut String) {
+    out.push_str(&format!("# {step}\n"));
+    for (source, topics, data) in env.events().all().iter() {
+        if source != *contract {
+            continue;
+        }
+        let topics = ScVal::try_from_val(env, &topics.to_val()).unwrap();
+        let data = ScVal::try_from_val(env, &data).unwrap();
+        let name = match &topics {
+            ScVal::Vec(Some(items)) => match items.first() {
+                Some(ScVal::Symbol(symbol)) => symbol.to_utf8_string_lossy(),
+                Some(ScVal::String(string)) => string.to_utf8_string_lossy(),
+                _ => String::from("-"),
+            },
+            _ => String::from("-"),
+        };
+        out.push_str(&format!(
+            "{name} {} {}\n",
+            topics.to_xdr_base64(Limits::none()).unwrap(),
+            data.to_xdr_base64(Limits::none()).unwrap()
+        ));
+    }
+}
+
+/// Compare a scenario's events with `<manifest_dir>/golden/<scenario>.events`,
+/// the calling crate's pinned copy, or rewrite that file when `UPDATE_GOLDEN`
+/// is set
+pub fn assert_golden(manifest_dir: &str, scenario: &str, actual: &str) {
+    let path = format!("{manifest_dir}/golden/{scenario}.events");
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(format!("{manifest_dir}/golden")).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("{path} is missing; run with UPDATE_GOLDEN=1 to create it"));
+    assert!(
+        actual == expected,
+        "events of {scenario} no longer match {path}; if the change is intended, rerun with UPDATE_GOLDEN=1\n--- expected\n{expected}--- actual\n{actual}"
+    );
+}
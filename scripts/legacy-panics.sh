@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# Run the trading suites with the `legacy-panics` feature.
+#
+# The feature makes trading trap with the string messages that predate
+# TradingError. The WASMs the suites load are built without it, as deployed;
+# in the suites, `failed` expects the resulting abort wherever a typed error is
+# asserted, and the `should_panic` tests match the legacy messages.
+#
+# Usage: scripts/legacy-panics.sh [CARGO TEST ARGS...]
+#   Set OFFLINE=1 to resolve from the local registry cache only.
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+CARGO_FLAGS=()
+if [[ "${OFFLINE:-0}" == "1" ]]; then
+    CARGO_FLAGS+=(--offline)
+fi
+
+cd "$ROOT"
+cargo build "${CARGO_FLAGS[@]}" --target wasm32v1-none --release \
+    -p fractcore -p funding -p governance
+cargo test "${CARGO_FLAGS[@]}" -p trading --features legacy-panics "$@"
@@ -0,0 +1,61 @@
+#!/usr/bin/env bash
+# Run the cross-contract WASM suites against several soroban-sdk versions.
+#
+# For each version the workspace is copied to a scratch directory, every
+# soroban-sdk requirement is pinned to that version, the fractcore, funding and
+# trading WASMs are rebuilt and the suites built with the `sdk-compat` feature
+# run: trading <-> fractcore (trading integration_tests) and
+# governance <-> funding/fractcore (governance cross_contract_integration_tests).
+# The checked-in manifests and Cargo.lock are never touched.
+#
+# Usage: scripts/sdk-compat.sh [VERSION...]
+#   Versions default to $SDK_VERSIONS, or the locked 22.x release and 25.3.2.
+#   Set OFFLINE=1 to resolve from the local registry cache only.
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+read -r -a VERSIONS <<< "${*:-${SDK_VERSIONS:-22.0.8 25.3.2}}"
+CARGO_FLAGS=()
+if [[ "${OFFLINE:-0}" == "1" ]]; then
+    CARGO_FLAGS+=(--offline)
+fi
+
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+declare -A RESULT
+failed=0
+
+for version in "${VERSIONS[@]}"; do
+    tree="$WORK/$version"
+    mkdir -p "$tree"
+    tar -C "$ROOT" --exclude=./target --exclude=./.git -cf - . | tar -C "$tree" -xf -
+
+    # Pin both `soroban-sdk = "x"` and `soroban-sdk = { version = "x", ... }`
+    find "$tree" -name Cargo.toml -not -path '*/target/*' -exec sed -i -E \
+        -e "s/^(soroban-sdk = )\"[^\"]*\"/\1\"=$version\"/" \
+        -e "s/^(soroban-sdk = \{ *version = )\"[^\"]*\"/\1\"=$version\"/" {} +
+
+    echo "==> soroban-sdk $version"
+    if (
+        cd "$tree"
+        cargo update "${CARGO_FLAGS[@]}" -p soroban-sdk
+        cargo build "${CARGO_FLAGS[@]}" --target wasm32v1-none --release \
+            -p fractcore -p funding -p trading
+        cargo test "${CARGO_FLAGS[@]}" -p trading -p governance --features sdk-compat
+    ); then
+        RESULT[$version]="ok"
+    else
+        RESULT[$version]="FAILED"
+        failed=1
+    fi
+done
+
+echo
+echo "soroban-sdk compatibility:"
+for version in "${VERSIONS[@]}"; do
+    printf '  %-10s %s\n' "$version" "${RESULT[$version]}"
+done
+
+exit "$failed"